@@ -29,12 +29,48 @@ impl InMemoryCacheBuilder {
 
     /// Sets the number of messages to cache per channel.
     ///
+    /// Once a channel has this many messages cached, caching another one
+    /// evicts the oldest, along with its reactions. Setting 0 disables
+    /// caching messages.
+    ///
     /// Defaults to 100.
     pub fn message_cache_size(mut self, message_cache_size: usize) -> Self {
         self.0.message_cache_size = message_cache_size;
 
         self
     }
+
+    /// Sets the number of messages to cache across all channels.
+    ///
+    /// Once this many messages are cached, caching another one evicts the
+    /// least recently cached message of any channel, along with its
+    /// reactions. This bounds the memory used by messages regardless of the
+    /// number of channels, in addition to the [`message_cache_size`] of each
+    /// channel. Setting 0 disables caching messages.
+    ///
+    /// Defaults to no limit.
+    ///
+    /// [`message_cache_size`]: Self::message_cache_size
+    pub fn total_message_cache_size(mut self, total_message_cache_size: usize) -> Self {
+        self.0
+            .total_message_cache_size
+            .replace(total_message_cache_size);
+
+        self
+    }
+
+    /// Sets whether to remove the presence of a user when they go offline,
+    /// instead of caching the offline presence.
+    ///
+    /// Guild creates only contain the presences of online users, so removing
+    /// offline presences keeps the cache consistent with them.
+    ///
+    /// Defaults to false.
+    pub fn remove_offline_presences(mut self, remove_offline_presences: bool) -> Self {
+        self.0.remove_offline_presences = remove_offline_presences;
+
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,9 @@
 use bitflags::bitflags;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::gateway::Intents;
 
 bitflags! {
     /// A set of bitflags which can be used to specify what resource to process
@@ -7,10 +12,23 @@ bitflags! {
     /// For example, specifying [`CHANNEL`] but not [`MESSAGE`] will cache
     /// created channels, channel updates, and channel deletes, but not their
     /// messages.
+    ///
+    /// Resources that aren't enabled are never stored, so their accessors
+    /// return `None`, but events removing them still clean up whatever was
+    /// stored, such as a guild deletion removing the guild's channels even
+    /// when [`GUILD`] isn't enabled.
+    ///
+    /// [`CHANNEL`]: Self::CHANNEL
+    /// [`GUILD`]: Self::GUILD
+    /// [`MESSAGE`]: Self::MESSAGE
     pub struct ResourceType: u64 {
         const CHANNEL = 1;
         const EMOJI = 1 << 1;
         const GUILD = 1 << 2;
+        /// Members of guilds.
+        ///
+        /// The member of the current user is cached even if this isn't
+        /// enabled, as long as the current user is cached.
         const MEMBER = 1 << 3;
         const MESSAGE = 1 << 4;
         const PRESENCE = 1 << 5;
@@ -19,9 +37,98 @@ bitflags! {
         const USER_CURRENT = 1 << 8;
         const USER = 1 << 9;
         const VOICE_STATE = 1 << 10;
+        /// Stickers of guilds and of cached messages.
+        const STICKER = 1 << 11;
     }
 }
 
+/// Intents delivering the events that resource types are cached from.
+///
+/// A resource type is cached if any one of its intents is enabled, such as
+/// messages being cached from either guild or direct messages. The current
+/// user is cached from the `READY` event, which is always delivered.
+const RESOURCE_INTENTS: [(ResourceType, Intents); 11] = [
+    (
+        ResourceType::CHANNEL,
+        Intents::from_bits_truncate(Intents::GUILDS.bits() | Intents::DIRECT_MESSAGES.bits()),
+    ),
+    (
+        ResourceType::EMOJI,
+        Intents::from_bits_truncate(Intents::GUILDS.bits() | Intents::GUILD_EMOJIS.bits()),
+    ),
+    (ResourceType::GUILD, Intents::GUILDS),
+    (ResourceType::MEMBER, Intents::GUILD_MEMBERS),
+    (
+        ResourceType::MESSAGE,
+        Intents::from_bits_truncate(
+            Intents::GUILD_MESSAGES.bits() | Intents::DIRECT_MESSAGES.bits(),
+        ),
+    ),
+    (ResourceType::PRESENCE, Intents::GUILD_PRESENCES),
+    (
+        ResourceType::REACTION,
+        Intents::from_bits_truncate(
+            Intents::GUILD_MESSAGE_REACTIONS.bits() | Intents::DIRECT_MESSAGE_REACTIONS.bits(),
+        ),
+    ),
+    (ResourceType::ROLE, Intents::GUILDS),
+    (
+        ResourceType::STICKER,
+        Intents::from_bits_truncate(Intents::GUILDS.bits() | Intents::GUILD_EMOJIS.bits()),
+    ),
+    (
+        ResourceType::USER,
+        Intents::from_bits_truncate(
+            Intents::GUILDS.bits()
+                | Intents::GUILD_MEMBERS.bits()
+                | Intents::GUILD_MESSAGES.bits()
+                | Intents::DIRECT_MESSAGES.bits(),
+        ),
+    ),
+    (ResourceType::VOICE_STATE, Intents::GUILD_VOICE_STATES),
+];
+
+/// Determine the resource types that can't be cached under the intents.
+pub(super) fn undeliverable(resource_types: ResourceType, intents: Intents) -> ResourceType {
+    RESOURCE_INTENTS
+        .iter()
+        .filter(|(_, resource_intents)| !intents.intersects(*resource_intents))
+        .fold(
+            ResourceType::empty(),
+            |undeliverable, (resource_type, _)| undeliverable | *resource_type,
+        )
+        & resource_types
+}
+
+/// Resource types configured to be cached, but whose events aren't
+/// delivered under the intents of the shards updating the cache.
+///
+/// Returned by [`InMemoryCache::validate_intents`].
+///
+/// [`InMemoryCache::validate_intents`]: crate::InMemoryCache::validate_intents
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentsMismatch {
+    pub(super) resource_types: ResourceType,
+}
+
+impl IntentsMismatch {
+    /// Resource types that can't be cached under the intents.
+    pub fn resource_types(&self) -> ResourceType {
+        self.resource_types
+    }
+}
+
+impl Display for IntentsMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_fmt(format_args!(
+            "resource types {:?} can't be cached under the intents",
+            self.resource_types
+        ))
+    }
+}
+
+impl Error for IntentsMismatch {}
+
 /// Configuration for an [`InMemoryCache`].
 ///
 /// [`InMemoryCache`]: crate::InMemoryCache
@@ -29,6 +136,8 @@ bitflags! {
 pub struct Config {
     pub(super) resource_types: ResourceType,
     pub(super) message_cache_size: usize,
+    pub(super) remove_offline_presences: bool,
+    pub(super) total_message_cache_size: Option<usize>,
 }
 
 impl Config {
@@ -41,6 +150,30 @@ impl Config {
     pub fn message_cache_size_mut(&mut self) -> &mut usize {
         &mut self.message_cache_size
     }
+
+    /// Returns whether presences are removed when their users go offline.
+    pub fn remove_offline_presences(&self) -> bool {
+        self.remove_offline_presences
+    }
+
+    /// Returns a mutable reference to whether presences are removed when
+    /// their users go offline.
+    pub fn remove_offline_presences_mut(&mut self) -> &mut bool {
+        &mut self.remove_offline_presences
+    }
+
+    /// Returns the number of messages to cache across all channels, if
+    /// limited.
+    pub fn total_message_cache_size(&self) -> Option<usize> {
+        self.total_message_cache_size
+    }
+
+    /// Returns a mutable reference to the number of messages to cache across
+    /// all channels.
+    pub fn total_message_cache_size_mut(&mut self) -> &mut Option<usize> {
+        &mut self.total_message_cache_size
+    }
+
     /// Returns an immutable reference to the resource types enabled.
     pub fn resource_types(&self) -> ResourceType {
         self.resource_types
@@ -57,13 +190,20 @@ impl Default for Config {
         Self {
             resource_types: ResourceType::all(),
             message_cache_size: 100,
+            remove_offline_presences: false,
+            total_message_cache_size: None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, ResourceType};
+    use super::{undeliverable, Config, IntentsMismatch, ResourceType, RESOURCE_INTENTS};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::gateway::Intents;
+
+    assert_impl_all!(IntentsMismatch: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
 
     #[test]
     #[allow(clippy::cognitive_complexity)]
@@ -79,6 +219,7 @@ mod tests {
         assert_eq!(1 << 8, ResourceType::USER_CURRENT.bits());
         assert_eq!(1 << 9, ResourceType::USER.bits());
         assert_eq!(1 << 10, ResourceType::VOICE_STATE.bits());
+        assert_eq!(1 << 11, ResourceType::STICKER.bits());
     }
 
     #[test]
@@ -86,14 +227,80 @@ mod tests {
         let conf = Config {
             resource_types: ResourceType::all(),
             message_cache_size: 100,
+            remove_offline_presences: false,
+            total_message_cache_size: None,
         };
         let default = Config::default();
         assert_eq!(conf.resource_types, default.resource_types);
         assert_eq!(conf.message_cache_size, default.message_cache_size);
+        assert_eq!(
+            conf.remove_offline_presences,
+            default.remove_offline_presences
+        );
+        assert_eq!(
+            conf.total_message_cache_size,
+            default.total_message_cache_size
+        );
     }
 
     #[test]
     fn test_config_fields() {
-        static_assertions::assert_fields!(Config: resource_types, message_cache_size);
+        static_assertions::assert_fields!(
+            Config: resource_types,
+            message_cache_size,
+            remove_offline_presences,
+            total_message_cache_size
+        );
+    }
+
+    #[test]
+    fn test_resource_intents_cover_resource_types() {
+        let covered = RESOURCE_INTENTS
+            .iter()
+            .fold(ResourceType::empty(), |covered, (resource_type, _)| {
+                covered | *resource_type
+            });
+
+        assert_eq!(ResourceType::all() - ResourceType::USER_CURRENT, covered);
+    }
+
+    #[test]
+    fn test_undeliverable() {
+        // Every resource type can be cached under every intent.
+        assert!(undeliverable(ResourceType::all(), Intents::all()).is_empty());
+        // The current user is always delivered.
+        assert_eq!(
+            ResourceType::all() - ResourceType::USER_CURRENT,
+            undeliverable(ResourceType::all(), Intents::empty())
+        );
+        // Resource types that aren't configured are never listed.
+        assert!(undeliverable(ResourceType::empty(), Intents::empty()).is_empty());
+
+        for (resource_type, resource_intents) in RESOURCE_INTENTS.iter() {
+            for bits in 0..15 {
+                let intent = Intents::from_bits_truncate(1 << bits);
+                let expected = if resource_intents.contains(intent) {
+                    ResourceType::empty()
+                } else {
+                    *resource_type
+                };
+
+                assert_eq!(
+                    expected,
+                    undeliverable(*resource_type, intent),
+                    "{:?} under {:?}",
+                    resource_type,
+                    intent
+                );
+            }
+        }
+
+        assert_eq!(
+            ResourceType::MEMBER | ResourceType::PRESENCE,
+            undeliverable(
+                ResourceType::GUILD | ResourceType::MEMBER | ResourceType::PRESENCE,
+                Intents::GUILDS | Intents::GUILD_MESSAGES,
+            )
+        );
     }
 }
@@ -0,0 +1,327 @@
+//! Iterators over the resources of an [`InMemoryCache`].
+//!
+//! Iterators hold references into the cache's concurrent maps instead of
+//! cloning every entry, and are weakly consistent: resources inserted,
+//! updated, or removed while iterating may or may not be yielded.
+//!
+//! # Deadlocks
+//!
+//! A [`Reference`] yielded by an iterator holds a read lock on part of the
+//! cache. Updating the cache on the same thread while holding a reference
+//! may deadlock, so drop references before calling [`InMemoryCache::update`].
+//!
+//! [`InMemoryCache`]: crate::InMemoryCache
+//! [`InMemoryCache::update`]: crate::InMemoryCache::update
+//! [`Reference`]: crate::Reference
+
+use super::{
+    model::{CachedEmoji, CachedGuild, CachedMember, CachedPresence, CachedSticker},
+    GuildItem, InMemoryCache, InMemoryCacheRef, Reference,
+};
+use dashmap::{iter::Iter, DashMap};
+use std::{collections::BTreeSet, hash::Hash, sync::Arc, vec::IntoIter as VecIntoIter};
+use twilight_model::{
+    channel::{message::sticker::StickerId, Group, GuildChannel, PrivateChannel},
+    guild::Role,
+    id::{ChannelId, EmojiId, GuildId, RoleId, UserId},
+    user::User,
+    voice::VoiceState,
+};
+
+/// Iterator over all cached resources of a type.
+///
+/// Created via the methods of [`InMemoryCacheIter`].
+pub struct ResourceIter<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for ResourceIter<'a, K, V> {
+    type Item = Reference<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Reference::multiple)
+    }
+}
+
+/// Iterator over the cached resources of a type in a guild.
+///
+/// The IDs of the guild's resources are copied when the iterator is created,
+/// so resources added to the guild afterwards aren't yielded.
+///
+/// Created via [`InMemoryCacheIter::guild_channels`] and
+/// [`InMemoryCacheIter::guild_members`].
+pub struct GuildResourceIter<'a, K, V> {
+    keys: VecIntoIter<K>,
+    map: &'a DashMap<K, V>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for GuildResourceIter<'a, K, V> {
+    type Item = Reference<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Resources removed since the iterator was created are skipped.
+        for key in &mut self.keys {
+            if let Some(inner) = self.map.get(&key) {
+                return Some(Reference::one(inner));
+            }
+        }
+
+        None
+    }
+}
+
+/// Interface to iterate over the resources of an [`InMemoryCache`].
+///
+/// Created via [`InMemoryCache::iter`].
+///
+/// # Examples
+///
+/// Count the cached members of every guild:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use twilight_cache_inmemory::InMemoryCache;
+///
+/// let cache = InMemoryCache::new();
+///
+/// let mut counts = HashMap::new();
+///
+/// for member in cache.iter().members() {
+///     let (guild_id, _) = member.key();
+///     *counts.entry(*guild_id).or_insert(0) += 1;
+/// }
+/// ```
+///
+/// [`InMemoryCache`]: crate::InMemoryCache
+/// [`InMemoryCache::iter`]: crate::InMemoryCache::iter
+#[derive(Clone, Copy, Debug)]
+pub struct InMemoryCacheIter<'a>(&'a InMemoryCacheRef);
+
+impl<'a> InMemoryCacheIter<'a> {
+    pub(super) fn new(cache: &'a InMemoryCache) -> Self {
+        Self(&cache.0)
+    }
+
+    /// Iterate over the cached guild channels.
+    pub fn channels(&self) -> ResourceIter<'a, ChannelId, GuildItem<GuildChannel>> {
+        ResourceIter {
+            iter: self.0.channels_guild.iter(),
+        }
+    }
+
+    /// Iterate over the cached emojis.
+    pub fn emojis(&self) -> ResourceIter<'a, EmojiId, GuildItem<CachedEmoji>> {
+        ResourceIter {
+            iter: self.0.emojis.iter(),
+        }
+    }
+
+    /// Iterate over the cached groups.
+    pub fn groups(&self) -> ResourceIter<'a, ChannelId, Arc<Group>> {
+        ResourceIter {
+            iter: self.0.groups.iter(),
+        }
+    }
+
+    /// Iterate over the cached channels of a guild.
+    ///
+    /// Returns `None` if no channels of the guild are cached.
+    pub fn guild_channels(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<GuildResourceIter<'a, ChannelId, GuildItem<GuildChannel>>> {
+        let keys = self
+            .0
+            .guild_channels
+            .get(&guild_id)?
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+
+        Some(GuildResourceIter {
+            keys: keys.into_iter(),
+            map: &self.0.channels_guild,
+        })
+    }
+
+    /// Iterate over the cached members of a guild.
+    ///
+    /// Returns `None` if no members of the guild are cached.
+    pub fn guild_members(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<GuildResourceIter<'a, (GuildId, UserId), Arc<CachedMember>>> {
+        let keys = self
+            .0
+            .guild_members
+            .get(&guild_id)?
+            .iter()
+            .map(|user_id| (guild_id, *user_id))
+            .collect::<Vec<_>>();
+
+        Some(GuildResourceIter {
+            keys: keys.into_iter(),
+            map: &self.0.members,
+        })
+    }
+
+    /// Iterate over the cached guilds.
+    pub fn guilds(&self) -> ResourceIter<'a, GuildId, Arc<CachedGuild>> {
+        ResourceIter {
+            iter: self.0.guilds.iter(),
+        }
+    }
+
+    /// Iterate over the cached members of all guilds.
+    pub fn members(&self) -> ResourceIter<'a, (GuildId, UserId), Arc<CachedMember>> {
+        ResourceIter {
+            iter: self.0.members.iter(),
+        }
+    }
+
+    /// Iterate over the cached presences of all guilds.
+    pub fn presences(&self) -> ResourceIter<'a, (GuildId, UserId), Arc<CachedPresence>> {
+        ResourceIter {
+            iter: self.0.presences.iter(),
+        }
+    }
+
+    /// Iterate over the cached private channels.
+    pub fn private_channels(&self) -> ResourceIter<'a, ChannelId, Arc<PrivateChannel>> {
+        ResourceIter {
+            iter: self.0.channels_private.iter(),
+        }
+    }
+
+    /// Iterate over the cached roles.
+    pub fn roles(&self) -> ResourceIter<'a, RoleId, GuildItem<Role>> {
+        ResourceIter {
+            iter: self.0.roles.iter(),
+        }
+    }
+
+    /// Iterate over the cached guild stickers.
+    pub fn stickers(&self) -> ResourceIter<'a, StickerId, GuildItem<CachedSticker>> {
+        ResourceIter {
+            iter: self.0.stickers.iter(),
+        }
+    }
+
+    /// Iterate over the cached users, along with the IDs of the guilds they're
+    /// cached in.
+    pub fn users(&self) -> ResourceIter<'a, UserId, (Arc<User>, BTreeSet<GuildId>)> {
+        ResourceIter {
+            iter: self.0.users.iter(),
+        }
+    }
+
+    /// Iterate over the cached voice states of all guilds.
+    pub fn voice_states(&self) -> ResourceIter<'a, (GuildId, UserId), Arc<VoiceState>> {
+        ResourceIter {
+            iter: self.0.voice_states.iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GuildResourceIter, InMemoryCacheIter, ResourceIter};
+    use crate::{
+        test::{channel, guild_create, member},
+        GuildItem, InMemoryCache, Reference,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{collections::HashSet, fmt::Debug, sync::Arc};
+    use twilight_model::{
+        channel::GuildChannel,
+        gateway::payload::MemberRemove,
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    assert_impl_all!(InMemoryCacheIter<'_>: Clone, Copy, Debug, Send, Sync);
+    assert_impl_all!(GuildResourceIter<'_, ChannelId, GuildItem<GuildChannel>>: Iterator, Send, Sync);
+    assert_impl_all!(Reference<'_, ChannelId, GuildItem<GuildChannel>>: Send, Sync);
+    assert_impl_all!(ResourceIter<'_, ChannelId, GuildItem<GuildChannel>>: Iterator, Send, Sync);
+
+    fn cache() -> InMemoryCache {
+        let cache = InMemoryCache::new();
+        cache.update(&guild_create(
+            GuildId(1),
+            vec![channel(ChannelId(10)), channel(ChannelId(11))],
+            vec![member(GuildId(1), UserId(2)), member(GuildId(1), UserId(3))],
+        ));
+        cache.update(&guild_create(
+            GuildId(4),
+            vec![channel(ChannelId(12))],
+            vec![member(GuildId(4), UserId(2))],
+        ));
+
+        cache
+    }
+
+    #[test]
+    fn test_iter() {
+        let cache = cache();
+        let iter = cache.iter();
+
+        let guilds = iter.guilds().map(|r| *r.key()).collect::<HashSet<_>>();
+        assert_eq!(
+            vec![GuildId(1), GuildId(4)]
+                .into_iter()
+                .collect::<HashSet<_>>(),
+            guilds
+        );
+
+        let channels = iter
+            .channels()
+            .map(|r| (*r.key(), r.value().guild_id()))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            vec![
+                (ChannelId(10), GuildId(1)),
+                (ChannelId(11), GuildId(1)),
+                (ChannelId(12), GuildId(4)),
+            ]
+            .into_iter()
+            .collect::<HashSet<_>>(),
+            channels
+        );
+
+        assert_eq!(3, iter.members().count());
+        assert_eq!(2, iter.users().count());
+
+        let user = iter.users().find(|r| *r.key() == UserId(2)).unwrap();
+        assert_eq!(2, user.value().1.len());
+        assert_eq!("test", user.value().0.name);
+    }
+
+    #[test]
+    fn test_iter_guild() {
+        let cache = cache();
+        let iter = cache.iter();
+
+        let channels = iter
+            .guild_channels(GuildId(1))
+            .unwrap()
+            .map(|r| *r.key())
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            vec![ChannelId(10), ChannelId(11)]
+                .into_iter()
+                .collect::<HashSet<_>>(),
+            channels
+        );
+
+        let members = iter.guild_members(GuildId(1)).unwrap();
+        cache.update(&MemberRemove {
+            guild_id: GuildId(1),
+            user: member(GuildId(1), UserId(3)).user,
+        });
+        let members = members.map(|r| Arc::clone(r.value())).collect::<Vec<_>>();
+        assert_eq!(1, members.len());
+        assert_eq!(UserId(2), members[0].user.id);
+
+        assert!(iter.guild_channels(GuildId(5)).is_none());
+        assert!(iter.guild_members(GuildId(5)).is_none());
+    }
+}
@@ -33,6 +33,34 @@
 //! # Ok(()) }
 //! ```
 //!
+//! ## Features
+//!
+//! ### `snapshot`
+//!
+//! The `snapshot` feature enables writing the state of the cache to a snapshot
+//! and restoring it, such as to skip processing the guilds of a bot again after
+//! a restart. It is not enabled by default.
+//!
+//! ### `metrics`
+//!
+//! The `metrics` feature records metrics about the cache via the `metrics`
+//! crate when it's updated with events:
+//!
+//! - `CacheEvents`: counter of events applied, labeled by their type as
+//!   `EventType`
+//! - `CacheUpdateLatency`: histogram of how long applying events took, labeled
+//!   by their type as `EventType`
+//! - `CacheResources`: gauge of the number of resources cached, labeled by
+//!   their type as `ResourceType`, such as `Guild` or `Member`. Counting some
+//!   resources locks all of the shards of their maps, so this is recorded at
+//!   most once per second rather than after every event
+//! - `CacheEvictions`: counter of resources evicted by the configured cache
+//!   sizes, labeled by their type as `ResourceType`
+//!
+//! Metrics aren't labeled by guild, and are shared by all caches in the
+//! process. When the feature is disabled no metrics code is compiled. It is
+//! not enabled by default.
+//!
 //! ## License
 //!
 //! All first-party crates are licensed under [ISC][LICENSE.md]
@@ -49,41 +77,254 @@
 
 #![deny(rust_2018_idioms, broken_intra_doc_links, unused, warnings)]
 
+pub mod iter;
 pub mod model;
+pub mod permission;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod stats;
 
 mod builder;
 mod config;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(test)]
+mod test;
 mod updates;
 
 pub use self::{
     builder::InMemoryCacheBuilder,
-    config::{Config, ResourceType},
-    updates::UpdateCache,
+    config::{Config, IntentsMismatch, ResourceType},
+    updates::{InGuild, UpdateCache, UpdateResult},
 };
 
-use self::model::*;
-use dashmap::{mapref::entry::Entry, DashMap, DashSet};
+use self::{
+    iter::InMemoryCacheIter, model::*, permission::InMemoryCachePermissions,
+    stats::InMemoryCacheStats,
+};
+use dashmap::{
+    lock::RwLockReadGuard,
+    mapref::{entry::Entry, multiple::RefMulti, one::Ref},
+    DashMap, DashSet, SharedValue,
+};
+#[cfg(feature = "snapshot")]
+use snapshot::SnapshotError;
+#[cfg(feature = "snapshot")]
+use std::io::{Read, Write};
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, BTreeSet, HashSet},
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::{Debug, Formatter, Result as FmtResult},
     hash::Hash,
-    sync::{Arc, Mutex},
+    mem,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
 };
 use twilight_model::{
-    channel::{Group, GuildChannel, PrivateChannel},
-    gateway::presence::{Presence, UserOrId},
+    channel::{
+        message::{sticker::StickerId, Sticker},
+        thread::ThreadMember,
+        Group, GuildChannel, PrivateChannel,
+    },
+    gateway::{
+        presence::{Presence, Status, UserOrId},
+        Intents,
+    },
     guild::{Emoji, Guild, Member, PartialMember, Role},
     id::{ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId},
     user::{CurrentUser, User},
     voice::VoiceState,
 };
 
+/// Resource of a guild, along with the ID of the guild it's in.
 #[derive(Debug)]
-struct GuildItem<T> {
+pub struct GuildItem<T> {
     data: Arc<T>,
     guild_id: GuildId,
 }
 
+impl<T> Clone for GuildItem<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+            guild_id: self.guild_id,
+        }
+    }
+}
+
+impl<T> GuildItem<T> {
+    /// Resource itself.
+    pub fn data(&self) -> &Arc<T> {
+        &self.data
+    }
+
+    /// ID of the guild the resource is in.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+}
+
+/// Reference to a resource in the cache.
+///
+/// Holds a read lock on the part of the cache the resource is in until it's
+/// dropped, which lets accessors return resources without copying them out
+/// of the cache. Clone the [`value`] to keep a resource after the reference
+/// is dropped, which for most resources only clones an [`Arc`].
+///
+/// # Deadlocks
+///
+/// Updating the cache while holding a reference on the same thread may
+/// deadlock, since the update may need to write to the part of the cache
+/// that the reference holds a read lock on. Drop references before calling
+/// [`InMemoryCache::update`].
+///
+/// [`value`]: Self::value
+pub struct Reference<'a, K, V> {
+    inner: ReferenceInner<'a, K, V>,
+}
+
+enum ReferenceInner<'a, K, V> {
+    Multiple(RefMulti<'a, K, V>),
+    One(Ref<'a, K, V>),
+    /// Locked shard of a map that's known to contain the key, created when
+    /// trying to retrieve a resource without blocking.
+    Shard {
+        guard: RwLockReadGuard<'a, HashMap<K, SharedValue<V>>>,
+        key: K,
+    },
+}
+
+impl<'a, K: Eq + Hash, V> Reference<'a, K, V> {
+    fn multiple(inner: RefMulti<'a, K, V>) -> Self {
+        Self {
+            inner: ReferenceInner::Multiple(inner),
+        }
+    }
+
+    fn one(inner: Ref<'a, K, V>) -> Self {
+        Self {
+            inner: ReferenceInner::One(inner),
+        }
+    }
+
+    /// Key of the resource, such as its ID.
+    pub fn key(&self) -> &K {
+        match &self.inner {
+            ReferenceInner::Multiple(inner) => inner.key(),
+            ReferenceInner::One(inner) => inner.key(),
+            ReferenceInner::Shard { key, .. } => key,
+        }
+    }
+
+    /// Resource.
+    pub fn value(&self) -> &V {
+        match &self.inner {
+            ReferenceInner::Multiple(inner) => inner.value(),
+            ReferenceInner::One(inner) => inner.value(),
+            ReferenceInner::Shard { guard, key } => {
+                guard.get(key).expect("locked shard contains key").get()
+            }
+        }
+    }
+}
+
+impl<K: Debug + Eq + Hash, V: Debug> Debug for Reference<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Reference")
+            .field("key", self.key())
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+impl<K: Eq + Hash, V> Deref for Reference<'_, K, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        self.value()
+    }
+}
+
+/// Result of trying to retrieve a resource from the cache without blocking.
+///
+/// Returned by the `try_` variants of accessors, such as
+/// [`InMemoryCache::try_guild`].
+#[derive(Debug)]
+pub enum TryResult<R> {
+    /// The resource isn't cached.
+    Absent,
+    /// The part of the cache the resource is in is being updated.
+    Locked,
+    /// The resource is cached.
+    Present(R),
+}
+
+impl<R> TryResult<R> {
+    /// Whether the resource is cached.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Self::Present(_))
+    }
+
+    /// Whether the part of the cache the resource is in is being updated.
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Self::Locked)
+    }
+
+    /// Resource, if it's cached and wasn't locked.
+    pub fn present(self) -> Option<R> {
+        match self {
+            Self::Present(resource) => Some(resource),
+            Self::Absent | Self::Locked => None,
+        }
+    }
+}
+
+/// Retrieve a resource from a map, unless the shard it's in is locked for
+/// writing.
+fn try_reference<'a, K: Clone + Eq + Hash, V>(
+    map: &'a DashMap<K, V>,
+    key: &K,
+) -> TryResult<Reference<'a, K, V>> {
+    let guard = match map.shards()[map.determine_map(key)].try_read() {
+        Some(guard) => guard,
+        None => return TryResult::Locked,
+    };
+
+    if !guard.contains_key(key) {
+        return TryResult::Absent;
+    }
+
+    TryResult::Present(Reference {
+        inner: ReferenceInner::Shard {
+            guard,
+            key: key.clone(),
+        },
+    })
+}
+
+/// Remove the IDs of a guild's resources from a guild map, along with the
+/// resources themselves.
+fn remove_ids<T: Eq + Hash, U>(
+    guild_map: &DashMap<GuildId, HashSet<T>>,
+    container: &DashMap<T, U>,
+    guild_id: GuildId,
+) {
+    if let Some((_, ids)) = guild_map.remove(&guild_id) {
+        for id in ids {
+            container.remove(&id);
+        }
+    }
+}
+
+/// Key ordering roles from the lowest to the highest in the role hierarchy.
+fn role_hierarchy(role: &Role) -> (i64, Reverse<RoleId>) {
+    (role.position, Reverse(role.id))
+}
+
 fn upsert_guild_item<K: Eq + Hash, V: PartialEq>(
     map: &DashMap<K, GuildItem<V>>,
     guild_id: GuildId,
@@ -129,11 +370,63 @@ fn upsert_item<K: Eq + Hash, V: PartialEq>(map: &DashMap<K, Arc<V>>, k: K, v: V)
     }
 }
 
+/// Order in which messages were cached across all channels, used to evict the
+/// least recently cached messages once the total message cache size is hit.
+#[derive(Debug, Default)]
+struct MessageOrder {
+    /// Mapping of insertion sequences to the messages inserted at them.
+    messages: BTreeMap<u64, (ChannelId, MessageId)>,
+    /// Sequence of the next message to be inserted.
+    next: u64,
+    /// Mapping of messages to their insertion sequences.
+    sequences: HashMap<(ChannelId, MessageId), u64>,
+}
+
+impl MessageOrder {
+    fn clear(&mut self) {
+        self.messages.clear();
+        self.sequences.clear();
+    }
+
+    /// Insert a message as the most recently cached one.
+    fn insert(&mut self, channel_id: ChannelId, message_id: MessageId) {
+        let sequence = self.next;
+        self.next += 1;
+
+        if let Some(previous) = self.sequences.insert((channel_id, message_id), sequence) {
+            self.messages.remove(&previous);
+        }
+
+        self.messages.insert(sequence, (channel_id, message_id));
+    }
+
+    fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Remove the least recently cached message.
+    fn pop_oldest(&mut self) -> Option<(ChannelId, MessageId)> {
+        let sequence = self.messages.keys().next().copied()?;
+        let message = self.messages.remove(&sequence)?;
+        self.sequences.remove(&message);
+
+        Some(message)
+    }
+
+    fn remove(&mut self, channel_id: ChannelId, message_id: MessageId) {
+        if let Some(sequence) = self.sequences.remove(&(channel_id, message_id)) {
+            self.messages.remove(&sequence);
+        }
+    }
+}
+
 // When adding a field here, be sure to add it to `InMemoryCache::clear` if
 // necessary.
 #[derive(Debug, Default)]
 struct InMemoryCacheRef {
     config: Arc<Config>,
+    /// Mapping of channels and the IDs of the threads created in them.
+    channel_threads: DashMap<ChannelId, HashSet<ChannelId>>,
     channels_guild: DashMap<ChannelId, GuildItem<GuildChannel>>,
     channels_private: DashMap<ChannelId, Arc<PrivateChannel>>,
     // So long as the lock isn't held across await or panic points this is fine.
@@ -146,10 +439,29 @@ struct InMemoryCacheRef {
     guild_members: DashMap<GuildId, HashSet<UserId>>,
     guild_presences: DashMap<GuildId, HashSet<UserId>>,
     guild_roles: DashMap<GuildId, HashSet<RoleId>>,
+    guild_stickers: DashMap<GuildId, HashSet<StickerId>>,
     members: DashMap<(GuildId, UserId), Arc<CachedMember>>,
+    /// Number of messages cached across all channels.
+    message_count: AtomicUsize,
+    /// Number of messages evicted from the cache by its message cache sizes.
+    message_evictions: AtomicUsize,
+    /// When the gauges of the number of cached resources were last recorded.
+    #[cfg(feature = "metrics")]
+    resources_recorded: Mutex<Option<std::time::Instant>>,
+    /// Order in which messages were cached, only maintained if a total
+    /// message cache size is configured.
+    ///
+    /// Locked before the channels of messages when inserting or removing
+    /// them, so that it stays consistent with them.
+    message_order: Mutex<MessageOrder>,
     messages: DashMap<ChannelId, BTreeMap<MessageId, Arc<CachedMessage>>>,
     presences: DashMap<(GuildId, UserId), Arc<CachedPresence>>,
+    /// Mapping of recipients to the IDs of their private channels.
+    private_channel_users: DashMap<UserId, ChannelId>,
     roles: DashMap<RoleId, GuildItem<Role>>,
+    stickers: DashMap<StickerId, GuildItem<CachedSticker>>,
+    /// Mapping of threads and the thread members of the current user in them.
+    thread_members: DashMap<ChannelId, ThreadMember>,
     unavailable_guilds: DashSet<GuildId>,
     users: DashMap<UserId, (Arc<User>, BTreeSet<GuildId>)>,
     /// Mapping of channels and the users currently connected.
@@ -176,28 +488,38 @@ struct InMemoryCacheRef {
 ///
 /// # Design and Performance
 ///
-/// The defining characteristic of this cache is that returned types (such as a
-/// guild or user) do not use locking for access. The internals of the cache use
-/// a concurrent map for mutability and the returned types themselves are Arcs.
-/// If a user is retrieved from the cache, an `Arc<User>` is returned. If a
-/// reference to that user is held but the cache updates the user, the reference
-/// held by you will be outdated, but still exist.
+/// The internals of the cache use concurrent maps for mutability, and most
+/// resources are stored in Arcs. Accessors return a [`Reference`] to a
+/// resource, which holds a read lock on the part of the cache the resource is
+/// in instead of cloning the resource out of the cache, so retrieval
+/// operations are extremely cheap.
 ///
-/// The intended use is that data is held outside the cache for only as long
-/// as necessary, where the state of the value at that point time doesn't need
-/// to be up-to-date. If you need to ensure you always have the most up-to-date
-/// "version" of a cached resource, then you can re-retrieve it whenever you use
-/// it: retrieval operations are extremely cheap.
+/// The intended use is that references are held for only as long as
+/// necessary. Updating the cache on the same thread while holding a
+/// reference may deadlock, so drop references before calling
+/// [`update`]. If you need a resource for longer, such as across an await
+/// point, clone the Arc it's stored in out of the reference: if the cache
+/// then updates the resource, the clone held by you will be outdated, but
+/// still exist. If you need to ensure you always have the most up-to-date
+/// "version" of a cached resource, then you can re-retrieve it whenever you
+/// use it.
 ///
 /// For example, say you're deleting some of the guilds of a channel. You'll
-/// probably need the guild to do that, so you retrieve it from the cache. You
-/// can then use the guild to update all of the channels, because for most use
-/// cases you don't need the guild to be up-to-date in real time, you only need
-/// its state at that *point in time* or maybe across the lifetime of an
-/// operation. If you need the guild to always be up-to-date between operations,
-/// then the intent is that you keep getting it from the cache.
+/// probably need the guild to do that, so you retrieve it from the cache and
+/// clone it out of the reference. You can then use the guild to update all of
+/// the channels, because for most use cases you don't need the guild to be
+/// up-to-date in real time, you only need its state at that *point in time*
+/// or maybe across the lifetime of an operation. If you need the guild to
+/// always be up-to-date between operations, then the intent is that you keep
+/// getting it from the cache.
+///
+/// Accessors of single resources have `try_` variants, such as
+/// [`try_guild`], which return [`TryResult::Locked`] instead of blocking if
+/// the resource is being updated.
 ///
 /// [`Intents`]: ::twilight_model::gateway::Intents
+/// [`try_guild`]: Self::try_guild
+/// [`update`]: Self::update
 #[derive(Clone, Debug, Default)]
 pub struct InMemoryCache(Arc<InMemoryCacheRef>);
 
@@ -236,21 +558,207 @@ impl InMemoryCache {
         (*self.0.config).clone()
     }
 
+    /// Validate that the resource types can be cached under the intents of
+    /// the shards updating the cache.
+    ///
+    /// Resource types are cached from events that are only delivered under
+    /// certain intents, so a cache configured with resource types that the
+    /// shards' intents don't deliver silently stays empty for them.
+    ///
+    /// # Examples
+    ///
+    /// Members aren't delivered without the `GUILD_MEMBERS` intent:
+    ///
+    /// ```
+    /// use twilight_cache_inmemory::{InMemoryCache, ResourceType};
+    /// use twilight_model::gateway::Intents;
+    ///
+    /// let resource_types = ResourceType::GUILD | ResourceType::MEMBER;
+    ///
+    /// let error = InMemoryCache::validate_intents(resource_types, Intents::GUILDS).unwrap_err();
+    /// assert_eq!(ResourceType::MEMBER, error.resource_types());
+    ///
+    /// let intents = Intents::GUILDS | Intents::GUILD_MEMBERS;
+    /// assert!(InMemoryCache::validate_intents(resource_types, intents).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IntentsMismatch`] listing the resource types whose events
+    /// aren't delivered under the intents.
+    pub fn validate_intents(
+        resource_types: ResourceType,
+        intents: Intents,
+    ) -> Result<(), IntentsMismatch> {
+        let resource_types = config::undeliverable(resource_types, intents);
+
+        if resource_types.is_empty() {
+            Ok(())
+        } else {
+            Err(IntentsMismatch { resource_types })
+        }
+    }
+
+    /// Create an interface for iterating over the resources in the cache.
+    ///
+    /// Iteration is weakly consistent with concurrent updates. Refer to the
+    /// [`iter`] module for more information.
+    ///
+    /// [`iter`]: crate::iter
+    pub fn iter(&self) -> InMemoryCacheIter<'_> {
+        InMemoryCacheIter::new(self)
+    }
+
+    /// Create an interface for calculating the permissions of members from
+    /// the contents of the cache.
+    ///
+    /// Refer to the [`permission`] module for more information.
+    ///
+    /// [`permission`]: crate::permission
+    pub fn permissions(&self) -> InMemoryCachePermissions<'_> {
+        InMemoryCachePermissions::new(self)
+    }
+
+    /// Create an interface for retrieving statistics about the cache, such as
+    /// the number of guilds cached.
+    pub fn stats(&self) -> InMemoryCacheStats<'_> {
+        InMemoryCacheStats::new(self)
+    }
+
+    /// Write a snapshot of the state of the cache to a writer.
+    ///
+    /// The snapshot is written one resource at a time as the cache is
+    /// iterated, so it's weakly consistent: resources updated while the
+    /// snapshot is written may or may not be in it. Wrap the writer in a
+    /// [`BufWriter`] if writes to it are expensive, such as for a file.
+    ///
+    /// Requires the `snapshot` feature. Refer to the [`snapshot`] module for
+    /// more information.
+    ///
+    /// # Examples
+    ///
+    /// Write a snapshot of the cache to a file before shutting down:
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::{fs::File, io::BufWriter};
+    /// use twilight_cache_inmemory::InMemoryCache;
+    ///
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let file = File::create("cache.json")?;
+    /// cache.snapshot(BufWriter::new(file))?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError::Serializing`] if writing the snapshot failed.
+    ///
+    /// [`BufWriter`]: std::io::BufWriter
+    /// [`SnapshotError::Serializing`]: snapshot::SnapshotError::Serializing
+    /// [`snapshot`]: crate::snapshot
+    #[cfg(feature = "snapshot")]
+    pub fn snapshot(&self, writer: impl Write) -> Result<(), SnapshotError> {
+        snapshot::write(self, writer)
+    }
+
+    /// Replace the contents of the cache with a snapshot read from a reader.
+    ///
+    /// The maps indexing the resources of the snapshot and the counters of
+    /// the cache are rebuilt from those resources. Channels are restored with
+    /// at most as many messages as the cache is configured to cache.
+    ///
+    /// Requires the `snapshot` feature. Refer to the [`snapshot`] module for
+    /// more information.
+    ///
+    /// # Examples
+    ///
+    /// Restore a snapshot of the cache written before a restart:
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::{fs::File, io::BufReader};
+    /// use twilight_cache_inmemory::InMemoryCache;
+    ///
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let file = File::open("cache.json")?;
+    /// cache.restore(BufReader::new(file))?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError::UnsupportedVersion`] if the snapshot was
+    /// created with another version of the snapshot format.
+    ///
+    /// Returns [`SnapshotError::Deserializing`] if reading the snapshot
+    /// failed, such as because it's malformed.
+    ///
+    /// The cache is left empty if restoring the snapshot failed.
+    ///
+    /// [`SnapshotError::Deserializing`]: snapshot::SnapshotError::Deserializing
+    /// [`SnapshotError::UnsupportedVersion`]: snapshot::SnapshotError::UnsupportedVersion
+    /// [`snapshot`]: crate::snapshot
+    #[cfg(feature = "snapshot")]
+    pub fn restore(&self, reader: impl Read) -> Result<(), SnapshotError> {
+        snapshot::restore(self, reader)
+    }
+
     /// Update the cache with an event from the gateway.
     pub fn update(&self, value: &impl UpdateCache) {
         value.update(self);
     }
 
+    /// Update the cache with an event from the gateway, returning the
+    /// resources it removed from the cache.
+    ///
+    /// Channel deletes, member removes, message deletes, and thread deletes
+    /// return the cached resource they removed, if it was cached. Message
+    /// updates return the cached message as it was before the edit, such as
+    /// for logging edits. Other updates return [`UpdateResult::None`].
+    ///
+    /// # Examples
+    ///
+    /// Retrieve the content of a deleted message:
+    ///
+    /// ```
+    /// use twilight_cache_inmemory::{InMemoryCache, UpdateResult};
+    /// use twilight_model::gateway::payload::MessageDelete;
+    ///
+    /// fn deleted_content(cache: &InMemoryCache, event: &MessageDelete) -> Option<String> {
+    ///     match cache.update_with_result(event) {
+    ///         UpdateResult::MessageDelete(message) => Some(message.content.clone()),
+    ///         _ => None,
+    ///     }
+    /// }
+    /// ```
+    pub fn update_with_result(&self, value: &impl UpdateCache) -> UpdateResult {
+        value.update_with_result(self)
+    }
+
     /// Gets a channel by ID.
     ///
     /// This is an O(1) operation. This requires the [`GUILDS`] intent.
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
-    pub fn guild_channel(&self, channel_id: ChannelId) -> Option<Arc<GuildChannel>> {
-        self.0
-            .channels_guild
-            .get(&channel_id)
-            .map(|x| Arc::clone(&x.data))
+    pub fn guild_channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> Option<Reference<'_, ChannelId, GuildItem<GuildChannel>>> {
+        self.0.channels_guild.get(&channel_id).map(Reference::one)
+    }
+
+    /// Tries to get a channel by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the channel is in is being updated.
+    pub fn try_guild_channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> TryResult<Reference<'_, ChannelId, GuildItem<GuildChannel>>> {
+        try_reference(&self.0.channels_guild, &channel_id)
     }
 
     /// Gets the current user.
@@ -269,18 +777,40 @@ impl InMemoryCache {
     /// This is an O(1) operation. This requires the [`GUILD_EMOJIS`] intent.
     ///
     /// [`GUILD_EMOJIS`]: ::twilight_model::gateway::Intents::GUILD_EMOJIS
-    pub fn emoji(&self, emoji_id: EmojiId) -> Option<Arc<CachedEmoji>> {
-        self.0.emojis.get(&emoji_id).map(|x| Arc::clone(&x.data))
+    pub fn emoji(
+        &self,
+        emoji_id: EmojiId,
+    ) -> Option<Reference<'_, EmojiId, GuildItem<CachedEmoji>>> {
+        self.0.emojis.get(&emoji_id).map(Reference::one)
+    }
+
+    /// Tries to get an emoji by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the emoji is in is being updated.
+    pub fn try_emoji(
+        &self,
+        emoji_id: EmojiId,
+    ) -> TryResult<Reference<'_, EmojiId, GuildItem<CachedEmoji>>> {
+        try_reference(&self.0.emojis, &emoji_id)
     }
 
     /// Gets a group by ID.
     ///
     /// This is an O(1) operation.
-    pub fn group(&self, channel_id: ChannelId) -> Option<Arc<Group>> {
-        self.0
-            .groups
-            .get(&channel_id)
-            .map(|r| Arc::clone(r.value()))
+    pub fn group(&self, channel_id: ChannelId) -> Option<Reference<'_, ChannelId, Arc<Group>>> {
+        self.0.groups.get(&channel_id).map(Reference::one)
+    }
+
+    /// Tries to get a group by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the group is in is being updated.
+    pub fn try_group(
+        &self,
+        channel_id: ChannelId,
+    ) -> TryResult<Reference<'_, ChannelId, Arc<Group>>> {
+        try_reference(&self.0.groups, &channel_id)
     }
 
     /// Gets a guild by ID.
@@ -288,8 +818,35 @@ impl InMemoryCache {
     /// This is an O(1) operation. This requires the [`GUILDS`] intent.
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
-    pub fn guild(&self, guild_id: GuildId) -> Option<Arc<CachedGuild>> {
-        self.0.guilds.get(&guild_id).map(|r| Arc::clone(r.value()))
+    pub fn guild(&self, guild_id: GuildId) -> Option<Reference<'_, GuildId, Arc<CachedGuild>>> {
+        self.0.guilds.get(&guild_id).map(Reference::one)
+    }
+
+    /// Tries to get a guild by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the guild is in is being updated, such as to avoid blocking an
+    /// asynchronous task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_cache_inmemory::{InMemoryCache, TryResult};
+    /// use twilight_model::id::GuildId;
+    ///
+    /// fn print_name(cache: &InMemoryCache, guild_id: GuildId) {
+    ///     match cache.try_guild(guild_id) {
+    ///         TryResult::Present(guild) => println!("guild name: {}", guild.name),
+    ///         TryResult::Absent => println!("guild isn't cached"),
+    ///         TryResult::Locked => println!("guild is being updated"),
+    ///     }
+    /// }
+    /// ```
+    pub fn try_guild(
+        &self,
+        guild_id: GuildId,
+    ) -> TryResult<Reference<'_, GuildId, Arc<CachedGuild>>> {
+        try_reference(&self.0.guilds, &guild_id)
     }
 
     /// Gets the set of channels in a guild.
@@ -298,11 +855,11 @@ impl InMemoryCache {
     /// guild. This requires the [`GUILDS`] intent.
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
-    pub fn guild_channels(&self, guild_id: GuildId) -> Option<HashSet<ChannelId>> {
-        self.0
-            .guild_channels
-            .get(&guild_id)
-            .map(|r| r.value().clone())
+    pub fn guild_channels(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<ChannelId>>> {
+        self.0.guild_channels.get(&guild_id).map(Reference::one)
     }
 
     /// Gets the set of emojis in a guild.
@@ -312,11 +869,31 @@ impl InMemoryCache {
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
     /// [`GUILD_EMOJIS`]: ::twilight_model::gateway::Intents::GUILD_EMOJIS
-    pub fn guild_emojis(&self, guild_id: GuildId) -> Option<HashSet<EmojiId>> {
-        self.0
-            .guild_emojis
-            .get(&guild_id)
-            .map(|r| r.value().clone())
+    pub fn guild_emojis(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<EmojiId>>> {
+        self.0.guild_emojis.get(&guild_id).map(Reference::one)
+    }
+
+    /// Gets the number of members in a guild.
+    ///
+    /// The count is set by guild creates and then maintained by member adds
+    /// and removes, so unlike the set of [cached members] it includes members
+    /// that aren't cached. Member removes always decrement the count, even
+    /// for members that aren't cached, but never below zero.
+    ///
+    /// Returns `None` if the guild isn't cached or its count is unknown, such
+    /// as for guilds only fetched over HTTP.
+    ///
+    /// This is an O(1) operation. This requires both the [`GUILDS`] and
+    /// [`GUILD_MEMBERS`] intents.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    /// [`GUILD_MEMBERS`]: ::twilight_model::gateway::Intents::GUILD_MEMBERS
+    /// [cached members]: Self::guild_members
+    pub fn guild_member_count(&self, guild_id: GuildId) -> Option<u64> {
+        self.0.guilds.get(&guild_id)?.member_count
     }
 
     /// Gets the set of members in a guild.
@@ -327,11 +904,11 @@ impl InMemoryCache {
     /// This requires the [`GUILD_MEMBERS`] intent.
     ///
     /// [`GUILD_MEMBERS`]: ::twilight_model::gateway::Intents::GUILD_MEMBERS
-    pub fn guild_members(&self, guild_id: GuildId) -> Option<HashSet<UserId>> {
-        self.0
-            .guild_members
-            .get(&guild_id)
-            .map(|r| r.value().clone())
+    pub fn guild_members(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<UserId>>> {
+        self.0.guild_members.get(&guild_id).map(Reference::one)
     }
 
     /// Gets the set of presences in a guild.
@@ -342,11 +919,11 @@ impl InMemoryCache {
     /// This requires the [`GUILD_PRESENCES`] intent.
     ///
     /// [`GUILD_PRESENCES`]: ::twilight_model::gateway::Intents::GUILD_PRESENCES
-    pub fn guild_presences(&self, guild_id: GuildId) -> Option<HashSet<UserId>> {
-        self.0
-            .guild_presences
-            .get(&guild_id)
-            .map(|r| r.value().clone())
+    pub fn guild_presences(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<UserId>>> {
+        self.0.guild_presences.get(&guild_id).map(Reference::one)
     }
 
     /// Gets the set of roles in a guild.
@@ -355,8 +932,48 @@ impl InMemoryCache {
     /// This requires the [`GUILDS`] intent.
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
-    pub fn guild_roles(&self, guild_id: GuildId) -> Option<HashSet<RoleId>> {
-        self.0.guild_roles.get(&guild_id).map(|r| r.value().clone())
+    pub fn guild_roles(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<RoleId>>> {
+        self.0.guild_roles.get(&guild_id).map(Reference::one)
+    }
+
+    /// Gets the roles of a guild, ordered from the lowest to the highest in
+    /// the role hierarchy.
+    ///
+    /// Roles are ordered by their positions. Roles sharing a position are
+    /// ordered by their IDs, with the older role, which has the lower ID,
+    /// being higher.
+    ///
+    /// This is a O(m log m) operation, where m is the amount of roles in the
+    /// guild. This requires the [`GUILDS`] intent.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    pub fn guild_roles_sorted(&self, guild_id: GuildId) -> Option<Vec<Arc<Role>>> {
+        let role_ids = self.guild_roles(guild_id)?;
+
+        let mut roles = role_ids
+            .iter()
+            .filter_map(|role_id| self.role(*role_id).map(|role| Arc::clone(role.data())))
+            .collect::<Vec<_>>();
+        roles.sort_by_key(|role| role_hierarchy(role));
+
+        Some(roles)
+    }
+
+    /// Gets the set of stickers in a guild.
+    ///
+    /// This is a O(m) operation, where m is the amount of stickers in the
+    /// guild. This requires both the [`GUILDS`] and [`GUILD_EMOJIS`] intents.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    /// [`GUILD_EMOJIS`]: ::twilight_model::gateway::Intents::GUILD_EMOJIS
+    pub fn guild_stickers(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, GuildId, HashSet<StickerId>>> {
+        self.0.guild_stickers.get(&guild_id).map(Reference::one)
     }
 
     /// Gets a member by guild ID and user ID.
@@ -364,11 +981,144 @@ impl InMemoryCache {
     /// This is an O(1) operation. This requires the [`GUILD_MEMBERS`] intent.
     ///
     /// [`GUILD_MEMBERS`]: ::twilight_model::gateway::Intents::GUILD_MEMBERS
-    pub fn member(&self, guild_id: GuildId, user_id: UserId) -> Option<Arc<CachedMember>> {
-        self.0
-            .members
-            .get(&(guild_id, user_id))
-            .map(|r| Arc::clone(r.value()))
+    pub fn member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Option<Reference<'_, (GuildId, UserId), Arc<CachedMember>>> {
+        self.0.members.get(&(guild_id, user_id)).map(Reference::one)
+    }
+
+    /// Tries to get a member by guild ID and user ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the member is in is being updated.
+    pub fn try_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> TryResult<Reference<'_, (GuildId, UserId), Arc<CachedMember>>> {
+        try_reference(&self.0.members, &(guild_id, user_id))
+    }
+
+    /// Gets the member of the current user in a guild.
+    ///
+    /// The member of the current user is cached even if the [`MEMBER`]
+    /// resource type isn't enabled, so that the current user's permissions
+    /// can be calculated, but only as long as the current user is cached.
+    /// This is an O(1) operation. This requires the [`GUILDS`] intent.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    /// [`MEMBER`]: ResourceType::MEMBER
+    pub fn current_member(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, (GuildId, UserId), Arc<CachedMember>>> {
+        let user_id = self.current_user()?.id;
+
+        self.member(guild_id, user_id)
+    }
+
+    /// Gets the ID of the highest role of a member in the role hierarchy, as
+    /// ordered by [`guild_roles_sorted`].
+    ///
+    /// Roles of the member that aren't cached are skipped. If none of the
+    /// member's roles are cached, the ID of the guild's `@everyone` role,
+    /// which shares the guild's ID, is returned.
+    ///
+    /// Returns `None` if the member isn't cached.
+    ///
+    /// This is a O(m) operation, where m is the amount of roles of the
+    /// member. This requires both the [`GUILDS`] and [`GUILD_MEMBERS`]
+    /// intents.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    /// [`GUILD_MEMBERS`]: ::twilight_model::gateway::Intents::GUILD_MEMBERS
+    /// [`guild_roles_sorted`]: Self::guild_roles_sorted
+    pub fn member_highest_role(&self, guild_id: GuildId, user_id: UserId) -> Option<RoleId> {
+        let member = self.member(guild_id, user_id)?;
+
+        let highest = member
+            .roles
+            .iter()
+            .filter_map(|role_id| self.role(*role_id))
+            .max_by_key(|role| role_hierarchy(role.data()))
+            .map_or(RoleId(guild_id.0), |role| *role.key());
+
+        Some(highest)
+    }
+
+    /// Gets the IDs of the messages cached for a channel, newest first.
+    ///
+    /// Messages are ordered by their IDs, which are ordered by when the
+    /// messages were sent, so the order is kept when messages are deleted
+    /// and isn't affected by the order the gateway delivered them in.
+    ///
+    /// At most the configured [`message_cache_size`] of messages are cached
+    /// per channel. This is an O(n) operation. This requires one or both of
+    /// the [`GUILD_MESSAGES`] or [`DIRECT_MESSAGES`] intents.
+    ///
+    /// [`message_cache_size`]: InMemoryCacheBuilder::message_cache_size
+    /// [`GUILD_MESSAGES`]: ::twilight_model::gateway::Intents::GUILD_MESSAGES
+    /// [`DIRECT_MESSAGES`]: ::twilight_model::gateway::Intents::DIRECT_MESSAGES
+    pub fn channel_messages(&self, channel_id: ChannelId) -> Option<Vec<MessageId>> {
+        let channel = self.0.messages.get(&channel_id)?;
+
+        Some(channel.keys().rev().copied().collect())
+    }
+
+    /// Gets the IDs of up to `limit` messages cached for a channel that were
+    /// sent before a message, newest first.
+    ///
+    /// The message doesn't need to be cached itself, such as to get the
+    /// messages before one that was just deleted. This is an O(log n + limit)
+    /// operation. Refer to [`channel_messages`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Get the IDs of the 10 messages sent before a message, such as to purge
+    /// them:
+    ///
+    /// ```
+    /// use twilight_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{ChannelId, MessageId};
+    ///
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let ids = cache.channel_messages_before(ChannelId(1), MessageId(2), 10);
+    /// # assert!(ids.is_none());
+    /// ```
+    ///
+    /// [`channel_messages`]: Self::channel_messages
+    pub fn channel_messages_before(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        limit: usize,
+    ) -> Option<Vec<MessageId>> {
+        let channel = self.0.messages.get(&channel_id)?;
+
+        Some(
+            channel
+                .range(..message_id)
+                .rev()
+                .take(limit)
+                .map(|(id, _)| *id)
+                .collect(),
+        )
+    }
+
+    /// Gets the set of threads created in a channel.
+    ///
+    /// Both active and archived threads are included, as long as they're
+    /// cached. This is an O(1) operation. This requires the [`GUILDS`] intent.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    pub fn channel_threads(
+        &self,
+        channel_id: ChannelId,
+    ) -> Option<Reference<'_, ChannelId, HashSet<ChannelId>>> {
+        self.0.channel_threads.get(&channel_id).map(Reference::one)
     }
 
     /// Gets a message by channel ID and message ID.
@@ -388,16 +1138,38 @@ impl InMemoryCache {
         channel.get(&message_id).cloned()
     }
 
-    /// Gets a presence by, optionally, guild ID, and user ID.
+    /// Gets the presence of a user in a guild, containing their status,
+    /// client status, and activities.
+    ///
+    /// Users going offline keep an offline presence unless the cache is
+    /// configured to [remove offline presences].
     ///
     /// This is an O(1) operation. This requires the [`GUILD_PRESENCES`] intent.
     ///
+    /// [remove offline presences]: InMemoryCacheBuilder::remove_offline_presences
+    ///
     /// [`GUILD_PRESENCES`]: ::twilight_model::gateway::Intents::GUILD_PRESENCES
-    pub fn presence(&self, guild_id: GuildId, user_id: UserId) -> Option<Arc<CachedPresence>> {
+    pub fn presence(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Option<Reference<'_, (GuildId, UserId), Arc<CachedPresence>>> {
         self.0
             .presences
             .get(&(guild_id, user_id))
-            .map(|r| Arc::clone(r.value()))
+            .map(Reference::one)
+    }
+
+    /// Tries to get the presence of a user in a guild without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the presence is in is being updated.
+    pub fn try_presence(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> TryResult<Reference<'_, (GuildId, UserId), Arc<CachedPresence>>> {
+        try_reference(&self.0.presences, &(guild_id, user_id))
     }
 
     /// Gets a private channel by ID.
@@ -405,23 +1177,114 @@ impl InMemoryCache {
     /// This is an O(1) operation. This requires the [`DIRECT_MESSAGES`] intent.
     ///
     /// [`DIRECT_MESSAGES`]: ::twilight_model::gateway::Intents::DIRECT_MESSAGES
-    pub fn private_channel(&self, channel_id: ChannelId) -> Option<Arc<PrivateChannel>> {
+    pub fn private_channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> Option<Reference<'_, ChannelId, Arc<PrivateChannel>>> {
+        self.0.channels_private.get(&channel_id).map(Reference::one)
+    }
+
+    /// Tries to get a private channel by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the channel is in is being updated.
+    pub fn try_private_channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> TryResult<Reference<'_, ChannelId, Arc<PrivateChannel>>> {
+        try_reference(&self.0.channels_private, &channel_id)
+    }
+
+    /// Gets the private channel with a user by the user's ID.
+    ///
+    /// This is an O(1) operation. This requires the [`DIRECT_MESSAGES`] intent.
+    ///
+    /// [`DIRECT_MESSAGES`]: ::twilight_model::gateway::Intents::DIRECT_MESSAGES
+    pub fn private_channel_by_user(
+        &self,
+        user_id: UserId,
+    ) -> Option<Reference<'_, ChannelId, Arc<PrivateChannel>>> {
+        let channel_id = *self.0.private_channel_users.get(&user_id)?;
+
+        self.private_channel(channel_id)
+    }
+
+    /// Gets a role by ID.
+    ///
+    /// This is an O(1) operation. This requires the [`GUILDS`] intent.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    pub fn role(&self, role_id: RoleId) -> Option<Reference<'_, RoleId, GuildItem<Role>>> {
+        self.0.roles.get(&role_id).map(Reference::one)
+    }
+
+    /// Tries to get a role by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the role is in is being updated.
+    pub fn try_role(&self, role_id: RoleId) -> TryResult<Reference<'_, RoleId, GuildItem<Role>>> {
+        try_reference(&self.0.roles, &role_id)
+    }
+
+    /// Gets a sticker of a guild by ID.
+    ///
+    /// Stickers of messages are only cached along with their messages.
+    ///
+    /// This is an O(1) operation. This requires the [`GUILD_EMOJIS`] intent.
+    ///
+    /// [`GUILD_EMOJIS`]: ::twilight_model::gateway::Intents::GUILD_EMOJIS
+    pub fn sticker(
+        &self,
+        sticker_id: StickerId,
+    ) -> Option<Reference<'_, StickerId, GuildItem<CachedSticker>>> {
+        self.0.stickers.get(&sticker_id).map(Reference::one)
+    }
+
+    /// Tries to get a sticker of a guild by ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the sticker is in is being updated.
+    pub fn try_sticker(
+        &self,
+        sticker_id: StickerId,
+    ) -> TryResult<Reference<'_, StickerId, GuildItem<CachedSticker>>> {
+        try_reference(&self.0.stickers, &sticker_id)
+    }
+
+    /// Gets a thread by ID.
+    ///
+    /// Returns `None` if the channel isn't cached or isn't a thread. Whether
+    /// the thread is archived is tracked in its [`thread_metadata`], while
+    /// its [`member`] is always `None` and is instead retrieved via
+    /// [`thread_member`]. This is an O(1) operation. This requires the
+    /// [`GUILDS`] intent.
+    ///
+    /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
+    /// [`member`]: twilight_model::channel::ThreadChannel::member
+    /// [`thread_member`]: Self::thread_member
+    /// [`thread_metadata`]: twilight_model::channel::ThreadChannel::thread_metadata
+    pub fn thread(
+        &self,
+        thread_id: ChannelId,
+    ) -> Option<Reference<'_, ChannelId, GuildItem<GuildChannel>>> {
         self.0
-            .channels_private
-            .get(&channel_id)
-            .map(|r| Arc::clone(r.value()))
+            .channels_guild
+            .get(&thread_id)
+            .filter(|item| matches!(item.data.as_ref(), GuildChannel::Thread(_)))
+            .map(Reference::one)
     }
 
-    /// Gets a role by ID.
+    /// Gets the thread member of the current user in a thread.
     ///
-    /// This is an O(1) operation. This requires the [`GUILDS`] intent.
+    /// Returns `None` if the current user hasn't joined the thread. This is an
+    /// O(1) operation. This requires the [`GUILDS`] intent.
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
-    pub fn role(&self, role_id: RoleId) -> Option<Arc<Role>> {
-        self.0
-            .roles
-            .get(&role_id)
-            .map(|role| Arc::clone(&role.data))
+    pub fn thread_member(
+        &self,
+        thread_id: ChannelId,
+    ) -> Option<Reference<'_, ChannelId, ThreadMember>> {
+        self.0.thread_members.get(&thread_id).map(Reference::one)
     }
 
     /// Gets a user by ID.
@@ -433,6 +1296,21 @@ impl InMemoryCache {
         self.0.users.get(&user_id).map(|r| Arc::clone(&r.0))
     }
 
+    /// Gets the IDs of the guilds a user is cached in, in ascending order.
+    ///
+    /// Users are cached in the guilds they're cached as a member of, and in
+    /// the guilds of the messages and emojis they authored that are cached.
+    /// Guilds are removed when the user's member is removed from them or
+    /// when they're deleted. This is an O(n) operation. This requires the
+    /// [`GUILD_MEMBERS`] intent.
+    ///
+    /// [`GUILD_MEMBERS`]: ::twilight_model::gateway::Intents::GUILD_MEMBERS
+    pub fn user_guilds(&self, user_id: UserId) -> Option<Vec<GuildId>> {
+        let user = self.0.users.get(&user_id)?;
+
+        Some(user.1.iter().copied().collect())
+    }
+
     /// Gets the voice states within a voice channel.
     ///
     /// This requires both the [`GUILDS`] and [`GUILD_VOICE_STATES`] intents.
@@ -457,17 +1335,39 @@ impl InMemoryCache {
     ///
     /// [`GUILDS`]: ::twilight_model::gateway::Intents::GUILDS
     /// [`GUILD_VOICE_STATES`]: ::twilight_model::gateway::Intents::GUILD_VOICE_STATES
-    pub fn voice_state(&self, user_id: UserId, guild_id: GuildId) -> Option<Arc<VoiceState>> {
+    pub fn voice_state(
+        &self,
+        user_id: UserId,
+        guild_id: GuildId,
+    ) -> Option<Reference<'_, (GuildId, UserId), Arc<VoiceState>>> {
         self.0
             .voice_states
             .get(&(guild_id, user_id))
-            .map(|r| Arc::clone(r.value()))
+            .map(Reference::one)
+    }
+
+    /// Tries to get a voice state by user ID and guild ID without blocking.
+    ///
+    /// Returns [`TryResult::Locked`] instead of waiting if the part of the
+    /// cache the voice state is in is being updated.
+    pub fn try_voice_state(
+        &self,
+        user_id: UserId,
+        guild_id: GuildId,
+    ) -> TryResult<Reference<'_, (GuildId, UserId), Arc<VoiceState>>> {
+        try_reference(&self.0.voice_states, &(guild_id, user_id))
     }
 
     /// Clear the state of the Cache.
     ///
-    /// This is equal to creating a new empty cache.
+    /// This is equal to creating a new empty cache, but keeps the cache shared
+    /// with every clone of it. Clear the cache when a shard identifies a new
+    /// session, since the resources of the previous session may be outdated.
+    ///
+    /// Resources that have already been retrieved from the cache are kept
+    /// alive by their readers, but are no longer in the cache.
     pub fn clear(&self) {
+        self.0.channel_threads.clear();
         self.0.channels_guild.clear();
         self.0.channels_private.clear();
         self.0
@@ -483,10 +1383,18 @@ impl InMemoryCache {
         self.0.guild_members.clear();
         self.0.guild_presences.clear();
         self.0.guild_roles.clear();
+        self.0.guild_stickers.clear();
         self.0.members.clear();
+        let mut message_order = self.0.message_order.lock().expect("message order poisoned");
+        message_order.clear();
         self.0.messages.clear();
+        self.0.message_count.store(0, Ordering::Relaxed);
+        drop(message_order);
         self.0.presences.clear();
+        self.0.private_channel_users.clear();
         self.0.roles.clear();
+        self.0.stickers.clear();
+        self.0.thread_members.clear();
         self.0.unavailable_guilds.clear();
         self.0.users.clear();
         self.0.voice_state_channels.clear();
@@ -494,6 +1402,55 @@ impl InMemoryCache {
         self.0.voice_states.clear();
     }
 
+    /// Remove all of the resources of a guild from the cache, while keeping
+    /// the guild itself.
+    ///
+    /// This removes the guild's channels and threads along with their
+    /// messages, emojis, members, presences, roles, stickers, and voice states.
+    /// Users are removed once they're not cached in any other guild.
+    pub fn wipe_guild(&self, guild_id: GuildId) {
+        if let Some((_, ids)) = self.0.guild_channels.remove(&guild_id) {
+            for id in ids {
+                self.0.channels_guild.remove(&id);
+                self.0.channel_threads.remove(&id);
+                self.0.thread_members.remove(&id);
+
+                self.remove_channel_messages(id);
+            }
+        }
+
+        remove_ids(&self.0.guild_emojis, &self.0.emojis, guild_id);
+        remove_ids(&self.0.guild_roles, &self.0.roles, guild_id);
+        remove_ids(&self.0.guild_stickers, &self.0.stickers, guild_id);
+
+        if let Some((_, ids)) = self.0.voice_state_guilds.remove(&guild_id) {
+            for user_id in ids {
+                self.delete_voice_state(guild_id, user_id);
+            }
+        }
+
+        if let Some((_, ids)) = self.0.guild_members.remove(&guild_id) {
+            for user_id in ids {
+                self.0.members.remove(&(guild_id, user_id));
+            }
+        }
+
+        // Users are also cached in guilds without being members of them, such
+        // as the authors of messages sent by webhooks and the creators of
+        // emojis, so every user has to be unlinked from the guild.
+        self.0.users.retain(|_, user_tuple| {
+            user_tuple.1.remove(&guild_id);
+
+            !user_tuple.1.is_empty()
+        });
+
+        if let Some((_, ids)) = self.0.guild_presences.remove(&guild_id) {
+            for user_id in ids {
+                self.0.presences.remove(&(guild_id, user_id));
+            }
+        }
+    }
+
     fn cache_current_user(&self, mut current_user: CurrentUser) {
         let mut user = self.0.current_user.lock().expect("current user poisoned");
 
@@ -530,12 +1487,32 @@ impl InMemoryCache {
             GuildChannel::Text(ref mut c) => {
                 c.guild_id.replace(guild_id);
             }
+            GuildChannel::Thread(ref mut c) => {
+                c.guild_id.replace(guild_id);
+            }
             GuildChannel::Voice(ref mut c) => {
                 c.guild_id.replace(guild_id);
             }
         }
 
         let id = channel.id();
+
+        if let GuildChannel::Thread(ref mut thread) = channel {
+            if let Some(parent_id) = thread.parent_id {
+                self.0
+                    .channel_threads
+                    .entry(parent_id)
+                    .or_default()
+                    .insert(id);
+            }
+
+            // The thread member is only included in some events, so it's
+            // cached on its own, and an update without one keeps the current
+            // one.
+            if let Some(member) = thread.member.take() {
+                self.cache_thread_member(id, member);
+            }
+        }
         self.0
             .guild_channels
             .entry(guild_id)
@@ -584,6 +1561,13 @@ impl InMemoryCache {
         cached
     }
 
+    /// Replace all of the cached emojis of a guild.
+    fn replace_emojis(&self, guild_id: GuildId, emojis: Vec<Emoji>) {
+        remove_ids(&self.0.guild_emojis, &self.0.emojis, guild_id);
+        self.0.guild_emojis.insert(guild_id, HashSet::new());
+        self.cache_emojis(guild_id, emojis);
+    }
+
     fn cache_emojis(&self, guild_id: GuildId, emojis: Vec<Emoji>) {
         if let Some(mut guild_emojis) = self.0.guild_emojis.get_mut(&guild_id) {
             let incoming: Vec<EmojiId> = emojis.iter().map(|e| e.id).collect();
@@ -612,39 +1596,76 @@ impl InMemoryCache {
         upsert_item(&self.0.groups, group.id, group)
     }
 
-    fn cache_guild(&self, guild: Guild) {
+    fn cache_guild(&self, mut guild: Guild) {
         // The map and set creation needs to occur first, so caching states and
         // objects always has a place to put them.
+        //
+        // A guild create contains all of the guild's channels, emojis, and
+        // roles, so those cached during a previous session of the guild are
+        // replaced.
         if self.wants(ResourceType::CHANNEL) {
+            remove_ids(&self.0.guild_channels, &self.0.channels_guild, guild.id);
             self.0.guild_channels.insert(guild.id, HashSet::new());
-            self.cache_guild_channels(guild.id, guild.channels);
+            self.cache_guild_channels(guild.id, mem::take(&mut guild.channels));
         }
 
         if self.wants(ResourceType::EMOJI) {
-            self.0.guild_emojis.insert(guild.id, HashSet::new());
-            self.cache_emojis(guild.id, guild.emojis);
+            self.replace_emojis(guild.id, mem::take(&mut guild.emojis));
         }
 
+        // Guild creates of large guilds only contain some of their members
+        // and presences, so those already cached are kept.
         if self.wants(ResourceType::MEMBER) {
-            self.0.guild_members.insert(guild.id, HashSet::new());
-            self.cache_members(guild.id, guild.members);
+            self.0.guild_members.entry(guild.id).or_default();
         }
 
+        let members = mem::take(&mut guild.members)
+            .into_iter()
+            .filter(|member| self.wants_member(member.user.id));
+        self.cache_members(guild.id, members);
+
         if self.wants(ResourceType::PRESENCE) {
-            self.0.guild_presences.insert(guild.id, HashSet::new());
-            self.cache_presences(guild.id, guild.presences);
+            self.0.guild_presences.entry(guild.id).or_default();
+            self.cache_presences(guild.id, mem::take(&mut guild.presences));
         }
 
         if self.wants(ResourceType::ROLE) {
-            self.0.guild_roles.insert(guild.id, HashSet::new());
-            self.cache_roles(guild.id, guild.roles);
+            self.replace_roles(guild.id, mem::take(&mut guild.roles));
+        }
+
+        if self.wants(ResourceType::STICKER) {
+            self.replace_stickers(guild.id, mem::take(&mut guild.stickers));
         }
 
         if self.wants(ResourceType::VOICE_STATE) {
-            self.0.voice_state_guilds.insert(guild.id, HashSet::new());
-            self.cache_voice_states(guild.voice_states);
+            let guild_id = guild.id;
+
+            // Voice states cached during a previous session of the guild may
+            // be outdated.
+            if let Some((_, user_ids)) = self.0.voice_state_guilds.remove(&guild_id) {
+                for user_id in user_ids {
+                    self.delete_voice_state(guild_id, user_id);
+                }
+            }
+
+            self.0.voice_state_guilds.insert(guild_id, HashSet::new());
+
+            // Voice states of a guild create don't have guild IDs.
+            self.cache_voice_states(mem::take(&mut guild.voice_states).into_iter().map(
+                |mut voice_state| {
+                    voice_state.guild_id.get_or_insert(guild_id);
+
+                    voice_state
+                },
+            ));
         }
 
+        self.insert_guild(guild);
+    }
+
+    /// Insert a guild, without its channels, emojis, members, presences,
+    /// roles, stickers, and voice states.
+    fn insert_guild(&self, guild: Guild) {
         let guild = CachedGuild {
             id: guild.id,
             afk_channel_id: guild.afk_channel_id,
@@ -716,6 +1737,11 @@ impl InMemoryCache {
         cached
     }
 
+    /// Cache a partial member, such as the author of a message.
+    ///
+    /// Partial members carry fewer fields than full members, so they only
+    /// fill in the fields missing from an already cached member and never
+    /// clear them. Full members replace cached members instead.
     fn cache_borrowed_partial_member(
         &self,
         guild_id: GuildId,
@@ -723,33 +1749,75 @@ impl InMemoryCache {
         user: Arc<User>,
     ) -> Arc<CachedMember> {
         let id = (guild_id, user.id);
-        match self.0.members.get(&id) {
-            Some(m) if **m == member => return Arc::clone(&m),
-            Some(_) | None => {}
-        }
+
+        // Clone the cached member so that the lock to the map isn't held
+        // while inserting into it.
+        let existing = self.0.members.get(&id).map(|m| Arc::clone(&m));
+
+        let cached = match existing {
+            Some(existing) => {
+                let merged = CachedMember {
+                    deaf: existing.deaf,
+                    guild_id,
+                    joined_at: existing
+                        .joined_at
+                        .clone()
+                        .or_else(|| member.joined_at.clone()),
+                    mute: existing.mute,
+                    nick: existing.nick.clone().or_else(|| member.nick.clone()),
+                    pending: existing.pending,
+                    premium_since: existing
+                        .premium_since
+                        .clone()
+                        .or_else(|| member.premium_since.clone()),
+                    roles: if existing.roles.is_empty() {
+                        member.roles.clone()
+                    } else {
+                        existing.roles.clone()
+                    },
+                    user,
+                };
+
+                if merged == *existing {
+                    return existing;
+                }
+
+                merged
+            }
+            None => CachedMember {
+                deaf: member.deaf,
+                guild_id,
+                joined_at: member.joined_at.clone(),
+                mute: member.mute,
+                nick: member.nick.clone(),
+                pending: false,
+                premium_since: member.premium_since.clone(),
+                roles: member.roles.clone(),
+                user,
+            },
+        };
 
         self.0
             .guild_members
             .entry(guild_id)
             .or_default()
-            .insert(user.id);
+            .insert(id.1);
 
-        let cached = Arc::new(CachedMember {
-            deaf: member.deaf,
-            guild_id,
-            joined_at: member.joined_at.to_owned(),
-            mute: member.mute,
-            nick: member.nick.to_owned(),
-            pending: false,
-            premium_since: None,
-            roles: member.roles.to_owned(),
-            user,
-        });
+        let cached = Arc::new(cached);
         self.0.members.insert(id, Arc::clone(&cached));
 
         cached
     }
 
+    /// Update the member count of a cached guild, if it's known.
+    fn update_member_count(&self, guild_id: GuildId, update: impl FnOnce(u64) -> u64) {
+        if let Some(mut guild) = self.0.guilds.get_mut(&guild_id) {
+            if let Some(count) = Arc::make_mut(&mut guild).member_count.as_mut() {
+                *count = update(*count);
+            }
+        }
+    }
+
     fn cache_members(&self, guild_id: GuildId, members: impl IntoIterator<Item = Member>) {
         for member in members {
             self.cache_member(guild_id, member);
@@ -762,32 +1830,57 @@ impl InMemoryCache {
         }
     }
 
-    fn cache_presence(&self, guild_id: GuildId, presence: Presence) -> Arc<CachedPresence> {
+    fn cache_presence(&self, guild_id: GuildId, presence: Presence) -> Option<Arc<CachedPresence>> {
         let k = (guild_id, presence_user_id(&presence));
 
+        if presence.status == Status::Offline && self.0.config.remove_offline_presences() {
+            self.delete_presence(guild_id, k.1);
+
+            return None;
+        }
+
         match self.0.presences.get(&k) {
-            Some(p) if **p == presence => return Arc::clone(&p),
+            Some(p) if **p == presence => return Some(Arc::clone(&p)),
             Some(_) | None => {}
         }
         let cached = Arc::new(CachedPresence::from(&presence));
 
         self.0.presences.insert(k, Arc::clone(&cached));
+        self.0
+            .guild_presences
+            .entry(guild_id)
+            .or_default()
+            .insert(k.1);
 
-        cached
+        Some(cached)
     }
 
     fn cache_private_channel(&self, private_channel: PrivateChannel) -> Arc<PrivateChannel> {
         let id = private_channel.id;
 
         match self.0.channels_private.get(&id) {
-            Some(c) if **c == private_channel => Arc::clone(&c),
-            Some(_) | None => {
-                let v = Arc::new(private_channel);
-                self.0.channels_private.insert(id, Arc::clone(&v));
+            Some(c) if **c == private_channel => return Arc::clone(&c),
+            Some(_) | None => {}
+        }
+
+        let current_user_id = self
+            .0
+            .current_user
+            .lock()
+            .expect("current user poisoned")
+            .as_ref()
+            .map(|user| user.id);
 
-                v
+        for recipient in &private_channel.recipients {
+            if Some(recipient.id) != current_user_id {
+                self.0.private_channel_users.insert(recipient.id, id);
             }
         }
+
+        let v = Arc::new(private_channel);
+        self.0.channels_private.insert(id, Arc::clone(&v));
+
+        v
     }
 
     fn cache_roles(&self, guild_id: GuildId, roles: impl IntoIterator<Item = Role>) {
@@ -796,6 +1889,13 @@ impl InMemoryCache {
         }
     }
 
+    /// Replace all of the cached roles of a guild.
+    fn replace_roles(&self, guild_id: GuildId, roles: Vec<Role>) {
+        remove_ids(&self.0.guild_roles, &self.0.roles, guild_id);
+        self.0.guild_roles.insert(guild_id, HashSet::new());
+        self.cache_roles(guild_id, roles);
+    }
+
     fn cache_role(&self, guild_id: GuildId, role: Role) -> Arc<Role> {
         // Insert the role into the guild_roles map
         self.0
@@ -808,6 +1908,23 @@ impl InMemoryCache {
         upsert_guild_item(&self.0.roles, guild_id, role.id, role)
     }
 
+    /// Replace all of the cached stickers of a guild.
+    fn replace_stickers(&self, guild_id: GuildId, stickers: Vec<Sticker>) {
+        remove_ids(&self.0.guild_stickers, &self.0.stickers, guild_id);
+
+        let ids = stickers.iter().map(|sticker| sticker.id.clone()).collect();
+        self.0.guild_stickers.insert(guild_id, ids);
+
+        for sticker in stickers {
+            upsert_guild_item(
+                &self.0.stickers,
+                guild_id,
+                sticker.id.clone(),
+                CachedSticker::from(sticker),
+            );
+        }
+    }
+
     fn cache_user(&self, user: Cow<'_, User>, guild_id: Option<GuildId>) -> Arc<User> {
         match self.0.users.get_mut(&user.id) {
             Some(mut u) if *u.0 == *user => {
@@ -821,119 +1938,382 @@ impl InMemoryCache {
         }
         let user = Arc::new(user.into_owned());
         if let Some(guild_id) = guild_id {
-            let mut guild_id_set = BTreeSet::new();
-            guild_id_set.insert(guild_id);
-            self.0
+            // Keep the guilds the user is already cached in, so that it isn't
+            // removed while it's still in one of them.
+            let mut user_tuple = self
+                .0
                 .users
-                .insert(user.id, (Arc::clone(&user), guild_id_set));
+                .entry(user.id)
+                .or_insert_with(|| (Arc::clone(&user), BTreeSet::new()));
+            user_tuple.0 = Arc::clone(&user);
+            user_tuple.1.insert(guild_id);
         }
 
         user
     }
 
-    fn cache_voice_states(&self, voice_states: impl IntoIterator<Item = VoiceState>) {
-        for voice_state in voice_states {
-            self.cache_voice_state(voice_state);
+    fn cache_voice_states(&self, voice_states: impl IntoIterator<Item = VoiceState>) {
+        for voice_state in voice_states {
+            self.cache_voice_state(voice_state);
+        }
+    }
+
+    fn cache_voice_state(&self, vs: VoiceState) -> Option<Arc<VoiceState>> {
+        // This should always exist, but just incase use a match
+        let guild_id = match vs.guild_id {
+            Some(id) => id,
+            None => return None,
+        };
+
+        let user_id = vs.user_id;
+
+        // Check if the user is switching channels in the same guild (ie. they already have a voice state entry)
+        if let Some(voice_state) = self.0.voice_states.get(&(guild_id, user_id)) {
+            if let Some(channel_id) = voice_state.channel_id {
+                let remove_channel_mapping = self
+                    .0
+                    .voice_state_channels
+                    .get_mut(&channel_id)
+                    .map(|mut channel_voice_states| {
+                        channel_voice_states.remove(&(guild_id, user_id));
+
+                        channel_voice_states.is_empty()
+                    })
+                    .unwrap_or_default();
+
+                if remove_channel_mapping {
+                    self.0.voice_state_channels.remove(&channel_id);
+                }
+            }
+        }
+
+        // Check if the voice channel_id does not exist, signifying that the user has left
+        if vs.channel_id.is_none() {
+            {
+                let remove_guild = self
+                    .0
+                    .voice_state_guilds
+                    .get_mut(&guild_id)
+                    .map(|mut guild_users| {
+                        guild_users.remove(&user_id);
+
+                        guild_users.is_empty()
+                    })
+                    .unwrap_or_default();
+
+                if remove_guild {
+                    self.0.voice_state_guilds.remove(&guild_id);
+                }
+            }
+
+            let (_, state) = self.0.voice_states.remove(&(guild_id, user_id))?;
+
+            return Some(state);
+        }
+
+        let state = Arc::new(vs);
+
+        self.0
+            .voice_states
+            .insert((guild_id, user_id), Arc::clone(&state));
+
+        self.0
+            .voice_state_guilds
+            .entry(guild_id)
+            .or_default()
+            .insert(user_id);
+
+        if let Some(channel_id) = state.channel_id {
+            self.0
+                .voice_state_channels
+                .entry(channel_id)
+                .or_default()
+                .insert((guild_id, user_id));
+        }
+
+        Some(state)
+    }
+
+    /// Delete the presence of a user in a guild from the cache, along with
+    /// its entry in the guild's list of presences.
+    fn delete_presence(&self, guild_id: GuildId, user_id: UserId) {
+        self.0.presences.remove(&(guild_id, user_id));
+
+        if let Some(mut presences) = self.0.guild_presences.get_mut(&guild_id) {
+            presences.remove(&user_id);
+        }
+    }
+
+    /// Delete the voice state of a user in a guild from the cache, along with
+    /// its entries in the mappings of channels and guilds.
+    fn delete_voice_state(&self, guild_id: GuildId, user_id: UserId) -> Option<Arc<VoiceState>> {
+        let (_, state) = self.0.voice_states.remove(&(guild_id, user_id))?;
+
+        if let Some(channel_id) = state.channel_id {
+            if let Some(mut channel_voice_states) = self.0.voice_state_channels.get_mut(&channel_id)
+            {
+                channel_voice_states.remove(&(guild_id, user_id));
+            }
+
+            self.0
+                .voice_state_channels
+                .remove_if(&channel_id, |_, channel_voice_states| {
+                    channel_voice_states.is_empty()
+                });
+        }
+
+        if let Some(mut guild_users) = self.0.voice_state_guilds.get_mut(&guild_id) {
+            guild_users.remove(&user_id);
+        }
+
+        self.0
+            .voice_state_guilds
+            .remove_if(&guild_id, |_, guild_users| guild_users.is_empty());
+
+        Some(state)
+    }
+
+    /// Remove a guild from the guilds a user is cached in, deleting the user
+    /// once it's no longer cached in any guild.
+    fn unlink_user(&self, guild_id: GuildId, user_id: UserId) {
+        // Avoid a deadlock by mutating the user, dropping the lock to the map,
+        // and then maybe conditionally removing the user later.
+        let maybe_remove_user = self
+            .0
+            .users
+            .get_mut(&user_id)
+            .map(|mut user_tuple| user_tuple.1.remove(&guild_id))
+            .unwrap_or_default();
+
+        if maybe_remove_user {
+            self.0
+                .users
+                .remove_if(&user_id, |_, user_tuple| user_tuple.1.is_empty());
+        }
+    }
+
+    fn delete_group(&self, channel_id: ChannelId) -> Option<Arc<Group>> {
+        self.0.groups.remove(&channel_id).map(|(_, v)| v)
+    }
+
+    /// Delete a private channel from the cache, along with the entries of its
+    /// recipients.
+    fn delete_private_channel(&self, channel_id: ChannelId) -> Option<Arc<PrivateChannel>> {
+        let (_, channel) = self.0.channels_private.remove(&channel_id)?;
+
+        for recipient in &channel.recipients {
+            self.0
+                .private_channel_users
+                .remove_if(&recipient.id, |_, id| *id == channel_id);
+        }
+
+        Some(channel)
+    }
+
+    fn unavailable_guild(&self, guild_id: GuildId) {
+        self.0.unavailable_guilds.insert(guild_id);
+        self.0.guilds.remove(&guild_id);
+    }
+
+    /// Delete a guild channel from the cache.
+    ///
+    /// The guild channel data itself and the channel entry in its guild's list
+    /// of channels will be deleted.
+    ///
+    /// Deleting a thread removes it from its parent channel's threads, and
+    /// deleting any other channel deletes its threads too. The messages of
+    /// deleted channels are removed as well.
+    fn delete_guild_channel(&self, channel_id: ChannelId) -> Option<Arc<GuildChannel>> {
+        let GuildItem { data, guild_id } = self.0.channels_guild.remove(&channel_id)?.1;
+        self.remove_channel_messages(channel_id);
+
+        if let Some(mut guild_channels) = self.0.guild_channels.get_mut(&guild_id) {
+            guild_channels.remove(&channel_id);
+        }
+
+        if let GuildChannel::Thread(ref thread) = *data {
+            self.0.thread_members.remove(&channel_id);
+
+            if let Some(parent_id) = thread.parent_id {
+                if let Some(mut threads) = self.0.channel_threads.get_mut(&parent_id) {
+                    threads.remove(&channel_id);
+                }
+            }
+        } else if let Some((_, thread_ids)) = self.0.channel_threads.remove(&channel_id) {
+            for thread_id in thread_ids {
+                self.delete_guild_channel(thread_id);
+            }
+        }
+
+        Some(data)
+    }
+
+    /// Cache a message, evicting the oldest messages of its channel once the
+    /// message cache size is hit, and the least recently cached messages of
+    /// all channels once the total message cache size is hit.
+    fn cache_message(&self, message: CachedMessage) {
+        let message_cache_size = self.0.config.message_cache_size();
+        let total_message_cache_size = self.0.config.total_message_cache_size();
+
+        if message_cache_size == 0 || total_message_cache_size == Some(0) {
+            return;
         }
-    }
 
-    fn cache_voice_state(&self, vs: VoiceState) -> Option<Arc<VoiceState>> {
-        // This should always exist, but just incase use a match
-        let guild_id = match vs.guild_id {
-            Some(id) => id,
-            None => return None,
-        };
+        let mut message_order = self.lock_message_order();
+        let (channel_id, message_id) = (message.channel_id, message.id);
 
-        let user_id = vs.user_id;
+        {
+            let mut channel = self.0.messages.entry(channel_id).or_default();
 
-        // Check if the user is switching channels in the same guild (ie. they already have a voice state entry)
-        if let Some(voice_state) = self.0.voice_states.get(&(guild_id, user_id)) {
-            if let Some(channel_id) = voice_state.channel_id {
-                let remove_channel_mapping = self
-                    .0
-                    .voice_state_channels
-                    .get_mut(&channel_id)
-                    .map(|mut channel_voice_states| {
-                        channel_voice_states.remove(&(guild_id, user_id));
+            if channel.insert(message_id, Arc::new(message)).is_none() {
+                self.0.message_count.fetch_add(1, Ordering::Relaxed);
+            }
 
-                        channel_voice_states.is_empty()
-                    })
-                    .unwrap_or_default();
+            if let Some(message_order) = message_order.as_mut() {
+                message_order.insert(channel_id, message_id);
+            }
 
-                if remove_channel_mapping {
-                    self.0.voice_state_channels.remove(&channel_id);
+            // Evict the oldest messages, which are the ones with the lowest
+            // IDs, along with their reactions.
+            while channel.len() > message_cache_size {
+                let oldest = match channel.keys().next().copied() {
+                    Some(oldest) => oldest,
+                    None => break,
+                };
+
+                channel.remove(&oldest);
+                self.0.message_count.fetch_sub(1, Ordering::Relaxed);
+                self.0.message_evictions.fetch_add(1, Ordering::Relaxed);
+
+                #[cfg(feature = "metrics")]
+                self::metrics::record_evictions("Message", 1);
+
+                if let Some(message_order) = message_order.as_mut() {
+                    message_order.remove(channel_id, oldest);
                 }
             }
         }
 
-        // Check if the voice channel_id does not exist, signifying that the user has left
-        if vs.channel_id.is_none() {
-            {
-                let remove_guild = self
+        if let (Some(message_order), Some(total_message_cache_size)) =
+            (message_order.as_mut(), total_message_cache_size)
+        {
+            while message_order.len() > total_message_cache_size {
+                let (channel_id, message_id) = match message_order.pop_oldest() {
+                    Some(oldest) => oldest,
+                    None => break,
+                };
+
+                let removed = self
                     .0
-                    .voice_state_guilds
-                    .get_mut(&guild_id)
-                    .map(|mut guild_users| {
-                        guild_users.remove(&user_id);
+                    .messages
+                    .get_mut(&channel_id)
+                    .and_then(|mut channel| channel.remove(&message_id));
 
-                        guild_users.is_empty()
-                    })
-                    .unwrap_or_default();
+                if removed.is_some() {
+                    self.0.message_count.fetch_sub(1, Ordering::Relaxed);
+                    self.0.message_evictions.fetch_add(1, Ordering::Relaxed);
 
-                if remove_guild {
-                    self.0.voice_state_guilds.remove(&guild_id);
+                    #[cfg(feature = "metrics")]
+                    self::metrics::record_evictions("Message", 1);
                 }
             }
+        }
+    }
 
-            let (_, state) = self.0.voice_states.remove(&(guild_id, user_id))?;
+    /// Lock the order in which messages were cached, if a total message cache
+    /// size is configured.
+    fn lock_message_order(&self) -> Option<MutexGuard<'_, MessageOrder>> {
+        self.0
+            .config
+            .total_message_cache_size()
+            .map(|_| self.0.message_order.lock().expect("message order poisoned"))
+    }
 
-            return Some(state);
+    /// Remove messages of a channel from the cache.
+    fn remove_messages(
+        &self,
+        channel_id: ChannelId,
+        message_ids: &[MessageId],
+    ) -> Vec<Arc<CachedMessage>> {
+        let mut message_order = self.lock_message_order();
+        let mut messages = Vec::new();
+
+        if let Some(mut channel) = self.0.messages.get_mut(&channel_id) {
+            messages.extend(message_ids.iter().filter_map(|id| channel.remove(id)));
         }
 
-        let state = Arc::new(vs);
+        if let Some(message_order) = message_order.as_mut() {
+            for message in &messages {
+                message_order.remove(channel_id, message.id);
+            }
+        }
 
         self.0
-            .voice_states
-            .insert((guild_id, user_id), Arc::clone(&state));
+            .message_count
+            .fetch_sub(messages.len(), Ordering::Relaxed);
 
-        self.0
-            .voice_state_guilds
-            .entry(guild_id)
-            .or_default()
-            .insert(user_id);
+        messages
+    }
 
-        if let Some(channel_id) = state.channel_id {
-            self.0
-                .voice_state_channels
-                .entry(channel_id)
-                .or_default()
-                .insert((guild_id, user_id));
-        }
+    /// Remove all of the messages of a channel from the cache.
+    fn remove_channel_messages(&self, channel_id: ChannelId) {
+        let mut message_order = self.lock_message_order();
 
-        Some(state)
-    }
+        if let Some((_, messages)) = self.0.messages.remove(&channel_id) {
+            if let Some(message_order) = message_order.as_mut() {
+                for message_id in messages.keys() {
+                    message_order.remove(channel_id, *message_id);
+                }
+            }
 
-    fn delete_group(&self, channel_id: ChannelId) -> Option<Arc<Group>> {
-        self.0.groups.remove(&channel_id).map(|(_, v)| v)
+            self.0
+                .message_count
+                .fetch_sub(messages.len(), Ordering::Relaxed);
+        }
     }
 
-    fn unavailable_guild(&self, guild_id: GuildId) {
-        self.0.unavailable_guilds.insert(guild_id);
-        self.0.guilds.remove(&guild_id);
+    /// Cache the thread member of the current user in a thread.
+    fn cache_thread_member(&self, thread_id: ChannelId, mut member: ThreadMember) {
+        member.id.replace(thread_id);
+        self.0.thread_members.insert(thread_id, member);
     }
 
-    /// Delete a guild channel from the cache.
+    /// Replace the cached threads of a guild's channels with those of a sync.
     ///
-    /// The guild channel data itself and the channel entry in its guild's list
-    /// of channels will be deleted.
-    fn delete_guild_channel(&self, channel_id: ChannelId) -> Option<Arc<GuildChannel>> {
-        let GuildItem { data, guild_id } = self.0.channels_guild.remove(&channel_id)?.1;
+    /// If no parent channel IDs are provided, the threads of every channel of
+    /// the guild are replaced.
+    fn replace_threads(
+        &self,
+        guild_id: GuildId,
+        parent_ids: Option<&[ChannelId]>,
+        threads: impl IntoIterator<Item = GuildChannel>,
+        members: impl IntoIterator<Item = ThreadMember>,
+    ) {
+        let parent_ids = match parent_ids {
+            Some(parent_ids) => parent_ids.to_vec(),
+            None => self
+                .0
+                .guild_channels
+                .get(&guild_id)
+                .map(|ids| ids.iter().copied().collect())
+                .unwrap_or_default(),
+        };
 
-        if let Some(mut guild_channels) = self.0.guild_channels.get_mut(&guild_id) {
-            guild_channels.remove(&channel_id);
+        for parent_id in parent_ids {
+            if let Some((_, thread_ids)) = self.0.channel_threads.remove(&parent_id) {
+                for thread_id in thread_ids {
+                    self.delete_guild_channel(thread_id);
+                }
+            }
         }
 
-        Some(data)
+        self.cache_guild_channels(guild_id, threads);
+
+        for member in members {
+            if let Some(thread_id) = member.id {
+                self.cache_thread_member(thread_id, member);
+            }
+        }
     }
 
     fn delete_role(&self, role_id: RoleId) -> Option<Arc<Role>> {
@@ -951,6 +2331,22 @@ impl InMemoryCache {
     fn wants(&self, resource_type: ResourceType) -> bool {
         self.0.config.resource_types().contains(resource_type)
     }
+
+    /// Determine whether the configured cache wants the member of a user to
+    /// be processed.
+    ///
+    /// The member of the current user is always processed, so that its
+    /// permissions can be calculated even if members aren't otherwise cached.
+    fn wants_member(&self, user_id: UserId) -> bool {
+        self.wants(ResourceType::MEMBER)
+            || self
+                .0
+                .current_user
+                .lock()
+                .expect("current user poisoned")
+                .as_ref()
+                .map_or(false, |user| user.id == user_id)
+    }
 }
 
 fn presence_user_id(presence: &Presence) -> UserId {
@@ -962,16 +2358,19 @@ fn presence_user_id(presence: &Presence) -> UserId {
 
 #[cfg(test)]
 mod tests {
-    use crate::InMemoryCache;
-    use std::borrow::Cow;
+    use crate::{stats::GuildStats, test, InMemoryCache};
+    use std::{borrow::Cow, sync::Arc};
     use twilight_model::{
         channel::{ChannelType, GuildChannel, TextChannel},
-        gateway::payload::{GuildEmojisUpdate, MemberRemove, RoleDelete},
+        gateway::payload::{
+            GuildDelete, GuildEmojisUpdate, MemberAdd, MemberRemove, MessageCreate, MessageDelete,
+            RoleCreate, RoleDelete, RoleUpdate, VoiceStateUpdate,
+        },
         guild::{
             DefaultMessageNotificationLevel, Emoji, ExplicitContentFilter, Guild, Member, MfaLevel,
             Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
         },
-        id::{ChannelId, EmojiId, GuildId, RoleId, UserId},
+        id::{ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId},
         user::{CurrentUser, User},
         voice::VoiceState,
     };
@@ -1136,6 +2535,7 @@ mod tests {
             region: "us-east".to_owned(),
             roles: Vec::new(),
             splash: None,
+            stickers: Vec::new(),
             system_channel_id: None,
             system_channel_flags: SystemChannelFlags::SUPPRESS_JOIN_NOTIFICATIONS,
             rules_channel_id: None,
@@ -1159,7 +2559,7 @@ mod tests {
         // it's part of a guild create, the cache can automatically attach the
         // guild ID to it. So now, the channel's guild ID is present with the
         // correct value.
-        match *channel {
+        match **channel.data() {
             GuildChannel::Text(ref c) => {
                 assert_eq!(Some(GuildId(123)), c.guild_id);
             }
@@ -1220,6 +2620,72 @@ mod tests {
         assert!(!cache.0.users.contains_key(&user_id));
     }
 
+    #[test]
+    fn test_user_guilds() {
+        let cache = InMemoryCache::new();
+        let guilds = |ids: &[u64]| Some(ids.iter().copied().map(GuildId).collect::<Vec<_>>());
+
+        for id in 1..=3 {
+            cache.update(&test::guild_create(
+                GuildId(id),
+                Vec::new(),
+                vec![test::member(GuildId(id), UserId(4))],
+            ));
+        }
+        cache.update(&MemberAdd(test::member(GuildId(5), UserId(4))));
+        cache.update(&MemberAdd(test::member(GuildId(5), UserId(6))));
+        assert_eq!(guilds(&[1, 2, 3, 5]), cache.user_guilds(UserId(4)));
+        assert_eq!(guilds(&[5]), cache.user_guilds(UserId(6)));
+
+        cache.update(&MemberRemove {
+            guild_id: GuildId(2),
+            user: user(UserId(4)),
+        });
+        assert_eq!(guilds(&[1, 3, 5]), cache.user_guilds(UserId(4)));
+
+        cache.update(&GuildDelete {
+            id: GuildId(5),
+            unavailable: false,
+        });
+        assert_eq!(guilds(&[1, 3]), cache.user_guilds(UserId(4)));
+        assert!(cache.user_guilds(UserId(6)).is_none());
+
+        // Guilds becoming unavailable are wiped as well.
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: true,
+        });
+        assert_eq!(guilds(&[3]), cache.user_guilds(UserId(4)));
+    }
+
+    #[test]
+    fn test_user_guilds_without_members() {
+        let cache = InMemoryCache::new();
+
+        for id in 1..=2 {
+            cache.update(&test::guild_create(GuildId(id), Vec::new(), Vec::new()));
+
+            // The author of the message isn't a member, such as a webhook.
+            let mut message = test::message(ChannelId(id * 10), MessageId(id));
+            message.author = test::user(UserId(3));
+            message.guild_id = Some(GuildId(id));
+            cache.update(&MessageCreate(message));
+        }
+        assert_eq!(
+            Some(vec![GuildId(1), GuildId(2)]),
+            cache.user_guilds(UserId(3))
+        );
+
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        });
+        assert_eq!(Some(vec![GuildId(2)]), cache.user_guilds(UserId(3)));
+
+        cache.wipe_guild(GuildId(2));
+        assert!(cache.user_guilds(UserId(3)).is_none());
+    }
+
     #[test]
     fn test_voice_state_inserts_and_removes() {
         let cache = InMemoryCache::new();
@@ -1394,9 +2860,11 @@ mod tests {
             assert!(guild_1_role_ids.iter().all(|id| cached_roles.contains(id)));
 
             // Check for the cached role
-            assert!(guild_1_roles
-                .into_iter()
-                .all(|role| *cache.role(role.id).expect("Role missing from cache") == role))
+            assert!(guild_1_roles.into_iter().all(|role| **cache
+                .role(role.id)
+                .expect("Role missing from cache")
+                .data()
+                == role))
         }
 
         // Bulk inserts
@@ -1418,10 +2886,121 @@ mod tests {
             assert!(guild_2_role_ids.iter().all(|id| cached_roles.contains(id)));
 
             // Check for the cached role
-            assert!(guild_2_roles
-                .into_iter()
-                .all(|role| *cache.role(role.id).expect("Role missing from cache") == role))
+            assert!(guild_2_roles.into_iter().all(|role| **cache
+                .role(role.id)
+                .expect("Role missing from cache")
+                .data()
+                == role))
+        }
+    }
+
+    #[test]
+    fn test_private_channels() {
+        use twilight_model::{
+            channel::{Channel, PrivateChannel},
+            gateway::payload::{ChannelCreate, ChannelDelete},
+        };
+
+        let cache = InMemoryCache::new();
+        cache.cache_current_user(current_user(1));
+
+        let channel = PrivateChannel {
+            id: ChannelId(2),
+            last_message_id: None,
+            last_pin_timestamp: None,
+            kind: ChannelType::Private,
+            recipients: vec![user(UserId(1)), user(UserId(3))],
+        };
+        cache.update(&ChannelCreate(Channel::Private(channel.clone())));
+
+        assert_eq!(
+            ChannelId(2),
+            cache.private_channel_by_user(UserId(3)).unwrap().id
+        );
+        // The current user is a recipient of every private channel.
+        assert!(cache.private_channel_by_user(UserId(1)).is_none());
+
+        cache.update(&ChannelDelete(Channel::Private(channel)));
+        assert!(cache.private_channel(ChannelId(2)).is_none());
+        assert!(cache.private_channel_by_user(UserId(3)).is_none());
+
+        // Private channels are also cached from messages sent to the current
+        // user, but not from those sent by the current user.
+        let mut message = test::message(ChannelId(4), MessageId(5));
+        message.author = user(UserId(1));
+        cache.update(&MessageCreate(message.clone()));
+        assert!(cache.private_channel(ChannelId(4)).is_none());
+
+        message.author = user(UserId(6));
+        cache.update(&MessageCreate(message));
+        let channel = cache.private_channel_by_user(UserId(6)).unwrap();
+        assert_eq!(ChannelId(4), channel.id);
+        assert_eq!(Some(MessageId(5)), channel.last_message_id);
+    }
+
+    #[test]
+    fn test_role_hierarchy() {
+        fn role_at(id: u64, position: i64) -> Role {
+            let mut role = role(RoleId(id));
+            role.position = position;
+
+            role
+        }
+
+        fn sorted(cache: &InMemoryCache) -> Vec<RoleId> {
+            cache
+                .guild_roles_sorted(GuildId(1))
+                .unwrap()
+                .iter()
+                .map(|role| role.id)
+                .collect()
+        }
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        // Roles 3 and 4 share a position, so the older role 3 is higher.
+        for role in [role_at(1, 0), role_at(4, 1), role_at(3, 1), role_at(2, 2)].iter() {
+            cache.update(&RoleCreate {
+                guild_id,
+                role: role.clone(),
+            });
         }
+        assert_eq!(
+            vec![RoleId(1), RoleId(4), RoleId(3), RoleId(2)],
+            sorted(&cache)
+        );
+
+        let mut moderator = member(UserId(5), guild_id);
+        moderator.roles = vec![RoleId(4), RoleId(3)];
+        cache.cache_member(guild_id, moderator);
+        assert_eq!(
+            Some(RoleId(3)),
+            cache.member_highest_role(guild_id, UserId(5))
+        );
+
+        // Moving role 4 above role 2 changes the hierarchy.
+        cache.update(&RoleUpdate {
+            guild_id,
+            role: role_at(4, 3),
+        });
+        assert_eq!(
+            vec![RoleId(1), RoleId(3), RoleId(2), RoleId(4)],
+            sorted(&cache)
+        );
+        assert_eq!(
+            Some(RoleId(4)),
+            cache.member_highest_role(guild_id, UserId(5))
+        );
+
+        // Members without cached roles only have the @everyone role.
+        cache.cache_member(guild_id, member(UserId(6), guild_id));
+        assert_eq!(
+            Some(RoleId(1)),
+            cache.member_highest_role(guild_id, UserId(6))
+        );
+        assert!(cache.member_highest_role(guild_id, UserId(7)).is_none());
+        assert!(cache.guild_roles_sorted(GuildId(8)).is_none());
     }
 
     #[test]
@@ -1481,6 +3060,115 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_current_member_without_members() {
+        use crate::ResourceType;
+        use twilight_model::gateway::payload::{MemberChunk, MemberUpdate, UserUpdate};
+
+        let guild_id = GuildId(1);
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::all() - ResourceType::MEMBER)
+            .build();
+        cache.update(&UserUpdate(current_user(2)));
+        cache.update(&test::guild_create(
+            guild_id,
+            Vec::new(),
+            vec![
+                test::member(guild_id, UserId(2)),
+                test::member(guild_id, UserId(3)),
+            ],
+        ));
+
+        // Only the member of the current user is cached.
+        assert_eq!(UserId(2), cache.current_member(guild_id).unwrap().user.id);
+        assert!(cache.member(guild_id, UserId(3)).is_none());
+        assert_eq!(1, cache.stats().members());
+        assert!(cache.current_member(GuildId(4)).is_none());
+
+        cache.update(&MemberUpdate {
+            guild_id,
+            joined_at: String::new(),
+            nick: Some("bot".to_owned()),
+            pending: false,
+            premium_since: None,
+            roles: vec![RoleId(5)],
+            user: test::user(UserId(2)),
+        });
+        let member = Arc::clone(&cache.current_member(guild_id).unwrap());
+        assert_eq!(vec![RoleId(5)], member.roles);
+        assert_eq!(Some("bot"), member.nick.as_deref());
+
+        cache.update(&MemberChunk {
+            chunk_count: 1,
+            chunk_index: 0,
+            guild_id: GuildId(4),
+            members: vec![
+                test::member(GuildId(4), UserId(2)),
+                test::member(GuildId(4), UserId(6)),
+            ],
+            nonce: None,
+            not_found: Vec::new(),
+            presences: Vec::new(),
+        });
+        assert!(cache.current_member(GuildId(4)).is_some());
+        assert!(cache.member(GuildId(4), UserId(6)).is_none());
+
+        cache.update(&MemberRemove {
+            guild_id,
+            user: test::user(UserId(2)),
+        });
+        assert!(cache.current_member(guild_id).is_none());
+
+        // Without a cached current user, no members are cached.
+        cache.clear();
+        cache.update(&test::guild_create(
+            guild_id,
+            Vec::new(),
+            vec![test::member(guild_id, UserId(2))],
+        ));
+        assert_eq!(0, cache.stats().members());
+    }
+
+    #[test]
+    fn test_current_member_from_messages_and_voice_states() {
+        use crate::ResourceType;
+        use twilight_model::{gateway::payload::UserUpdate, guild::PartialMember};
+
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::all() - ResourceType::MEMBER)
+            .build();
+        cache.update(&UserUpdate(current_user(2)));
+
+        for user_id in &[UserId(2), UserId(3)] {
+            let mut message = test::message(ChannelId(10), MessageId(user_id.0));
+            message.author = test::user(*user_id);
+            message.guild_id = Some(GuildId(1));
+            message.member = Some(PartialMember {
+                deaf: false,
+                joined_at: None,
+                mute: false,
+                nick: None,
+                premium_since: None,
+                roles: vec![RoleId(5)],
+            });
+            cache.update(&MessageCreate(message));
+
+            let mut voice_state = voice_state(GuildId(4), Some(ChannelId(11)), *user_id);
+            voice_state.member = Some(test::member(GuildId(4), *user_id));
+            cache.update(&VoiceStateUpdate(voice_state));
+        }
+
+        assert_eq!(
+            vec![RoleId(5)],
+            cache.current_member(GuildId(1)).unwrap().roles
+        );
+        assert!(cache.current_member(GuildId(4)).is_some());
+        assert!(cache.member(GuildId(1), UserId(3)).is_none());
+        assert!(cache.member(GuildId(4), UserId(3)).is_none());
+        assert!(cache.user(UserId(3)).is_some());
+        assert_eq!(2, cache.stats().members());
+    }
+
     #[test]
     fn test_cache_emoji() {
         let cache = InMemoryCache::new();
@@ -1547,6 +3235,36 @@ mod tests {
         }
     }
 
+    /// Cache two guilds sharing user 2, each with a channel, a member, a
+    /// message, and a voice state, and the first with a role.
+    fn populated() -> InMemoryCache {
+        let cache = InMemoryCache::new();
+
+        for (guild_id, channel_id, user_id) in &[
+            (GuildId(1), ChannelId(10), UserId(3)),
+            (GuildId(4), ChannelId(11), UserId(5)),
+        ] {
+            cache.update(&test::guild_create(
+                *guild_id,
+                vec![test::channel(*channel_id)],
+                vec![
+                    test::member(*guild_id, UserId(2)),
+                    test::member(*guild_id, *user_id),
+                ],
+            ));
+            cache.update(&MessageCreate(test::message(*channel_id, MessageId(1))));
+            cache.update(&VoiceStateUpdate(voice_state(
+                *guild_id,
+                Some(*channel_id),
+                *user_id,
+            )));
+        }
+
+        cache.cache_role(GuildId(1), role(RoleId(6)));
+
+        cache
+    }
+
     #[test]
     fn test_clear() {
         let cache = InMemoryCache::new();
@@ -1555,6 +3273,69 @@ mod tests {
         cache.clear();
         assert!(cache.0.emojis.is_empty());
         assert!(cache.0.members.is_empty());
+
+        let cache = populated();
+        let clone = cache.clone();
+        let guild = Arc::clone(&cache.guild(GuildId(1)).unwrap());
+        cache.clear();
+
+        // Clones share the cleared cache, while resources cloned out of it are
+        // kept alive by their readers.
+        assert!(clone.guild(GuildId(1)).is_none());
+        assert_eq!(GuildId(1), guild.id);
+
+        let stats = clone.stats();
+        assert_eq!(0, stats.channels());
+        assert_eq!(0, stats.guilds());
+        assert_eq!(0, stats.members());
+        assert_eq!(0, stats.messages());
+        assert_eq!(0, stats.roles());
+        assert_eq!(0, stats.users());
+        assert_eq!(0, stats.voice_states());
+        assert!(cache.0.voice_state_channels.is_empty());
+        assert!(cache.0.voice_state_guilds.is_empty());
+
+        // The cache is usable again after being cleared.
+        cache.update(&MessageCreate(test::message(ChannelId(10), MessageId(2))));
+        assert_eq!(1, stats.messages());
+    }
+
+    #[test]
+    fn test_wipe_guild() {
+        let cache = populated();
+        cache.wipe_guild(GuildId(1));
+
+        // The guild itself is kept.
+        assert!(cache.guild(GuildId(1)).is_some());
+        assert!(cache.guild_channel(ChannelId(10)).is_none());
+        assert!(cache.message(ChannelId(10), MessageId(1)).is_none());
+        assert!(cache.role(RoleId(6)).is_none());
+        assert!(cache.member(GuildId(1), UserId(2)).is_none());
+        assert!(cache.voice_state(UserId(3), GuildId(1)).is_none());
+        assert!(cache.voice_channel_states(ChannelId(10)).is_none());
+        assert!(cache.guild_channels(GuildId(1)).is_none());
+        assert!(cache.guild_members(GuildId(1)).is_none());
+        assert!(cache.guild_roles(GuildId(1)).is_none());
+        assert_eq!(GuildStats::default(), cache.stats().guild(GuildId(1)));
+
+        // User 3 was only cached in the wiped guild, while user 2 is still
+        // cached in the other one.
+        assert!(cache.user(UserId(3)).is_none());
+        assert_eq!(
+            Some(vec![GuildId(4)].into_iter().collect()),
+            cache.0.users.get(&UserId(2)).map(|user| user.1.clone())
+        );
+
+        let stats = cache.stats();
+        assert_eq!(2, stats.guilds());
+        assert_eq!(1, stats.channels());
+        assert_eq!(2, stats.members());
+        assert_eq!(1, stats.messages());
+        assert_eq!(0, stats.roles());
+        assert_eq!(2, stats.users());
+        assert_eq!(1, stats.voice_states());
+        assert!(cache.message(ChannelId(11), MessageId(1)).is_some());
+        assert!(cache.voice_state(UserId(5), GuildId(4)).is_some());
     }
 
     #[test]
@@ -1612,4 +3393,132 @@ mod tests {
         assert!(cache.0.emojis.is_empty());
         assert!(cache.0.guild_emojis.get(&guild_id).unwrap().is_empty());
     }
+
+    #[test]
+    fn test_channel_messages_order() {
+        let cache = InMemoryCache::new();
+        let create = |id| MessageCreate(test::message(ChannelId(1), MessageId(id)));
+        let delete = |id| MessageDelete {
+            channel_id: ChannelId(1),
+            guild_id: None,
+            id: MessageId(id),
+        };
+        let ids = |ids: &[u64]| Some(ids.iter().copied().map(MessageId).collect::<Vec<_>>());
+
+        // Messages delivered out of order are still ordered by their IDs.
+        for id in &[1, 2, 4, 3, 5] {
+            cache.update(&create(*id));
+        }
+        cache.update(&delete(3));
+        cache.update(&create(6));
+        cache.update(&delete(5));
+
+        assert_eq!(ids(&[6, 4, 2, 1]), cache.channel_messages(ChannelId(1)));
+        assert_eq!(
+            ids(&[4, 2]),
+            cache.channel_messages_before(ChannelId(1), MessageId(6), 2)
+        );
+        // Deleted messages can still be walked back from.
+        assert_eq!(
+            ids(&[4, 2, 1]),
+            cache.channel_messages_before(ChannelId(1), MessageId(5), 10)
+        );
+        assert_eq!(
+            ids(&[]),
+            cache.channel_messages_before(ChannelId(1), MessageId(1), 10)
+        );
+        assert_eq!(
+            ids(&[]),
+            cache.channel_messages_before(ChannelId(1), MessageId(6), 0)
+        );
+
+        cache.update(&create(3));
+        assert_eq!(
+            ids(&[4, 3, 2]),
+            cache.channel_messages_before(ChannelId(1), MessageId(6), 3)
+        );
+        assert!(cache
+            .channel_messages_before(ChannelId(2), MessageId(6), 3)
+            .is_none());
+    }
+
+    #[test]
+    fn test_reference_no_clone() {
+        use super::Reference;
+        use dashmap::DashMap;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug)]
+        struct Counted(u64);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::Relaxed);
+
+                Self(self.0)
+            }
+        }
+
+        let map = DashMap::new();
+        map.insert(1, Counted(2));
+
+        let reference = Reference::one(map.get(&1).unwrap());
+        assert_eq!(1, *reference.key());
+        assert_eq!(2, reference.0);
+        assert_eq!(2, reference.value().0);
+        assert_eq!(0, CLONES.load(Ordering::Relaxed));
+
+        // Ownership is opted into by cloning the value.
+        let owned = reference.value().clone();
+        assert_eq!(2, owned.0);
+        assert_eq!(1, CLONES.load(Ordering::Relaxed));
+
+        // Accessors don't even clone the Arcs models are stored in.
+        let cache = InMemoryCache::new();
+        cache.cache_member(GuildId(1), member(UserId(2), GuildId(1)));
+        let member = cache.member(GuildId(1), UserId(2)).unwrap();
+        assert_eq!(1, Arc::strong_count(member.value()));
+        assert_eq!(UserId(2), member.user.id);
+    }
+
+    #[test]
+    fn test_try_accessors() {
+        use super::TryResult;
+
+        let cache = InMemoryCache::new();
+        cache.cache_member(GuildId(1), member(UserId(2), GuildId(1)));
+        cache.cache_role(GuildId(1), role(RoleId(3)));
+
+        match cache.try_member(GuildId(1), UserId(2)) {
+            TryResult::Present(member) => {
+                assert_eq!((GuildId(1), UserId(2)), *member.key());
+                assert_eq!(UserId(2), member.user.id);
+            }
+            other => panic!("expected a cached member: {:?}", other),
+        }
+        assert!(matches!(
+            cache.try_member(GuildId(1), UserId(4)),
+            TryResult::Absent
+        ));
+        assert_eq!(
+            Some(RoleId(3)),
+            cache
+                .try_role(RoleId(3))
+                .present()
+                .map(|role| role.data().id)
+        );
+
+        // Readers don't block each other.
+        let role = cache.role(RoleId(3)).unwrap();
+        assert!(cache.try_role(RoleId(3)).is_present());
+        drop(role);
+
+        // An entry being written to is locked rather than waited for.
+        let writing = cache.0.members.get_mut(&(GuildId(1), UserId(2))).unwrap();
+        assert!(cache.try_member(GuildId(1), UserId(2)).is_locked());
+        drop(writing);
+        assert!(cache.try_member(GuildId(1), UserId(2)).is_present());
+    }
 }
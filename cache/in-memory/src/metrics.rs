@@ -0,0 +1,287 @@
+//! Metrics about an [`InMemoryCache`], recorded via the `metrics` crate.
+//!
+//! Resources are labeled with their type, and never with the IDs of guilds
+//! or channels, to keep the number of series recorded bounded.
+//!
+//! [`InMemoryCache`]: crate::InMemoryCache
+
+use super::InMemoryCache;
+use std::{
+    convert::TryInto,
+    time::{Duration, Instant},
+};
+use twilight_model::gateway::event::EventType;
+
+/// Minimum interval between recording the number of resources in a cache.
+///
+/// Counting most resources locks every shard of their maps, which is too
+/// expensive to do after every event.
+const RESOURCES_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Record that an event was applied to the cache, along with the number of
+/// resources in the cache afterwards if they haven't been recorded recently.
+pub(super) fn record_update(cache: &InMemoryCache, kind: EventType, latency: Duration) {
+    if ::metrics::try_recorder().is_none() {
+        return;
+    }
+
+    let event_type = kind
+        .name()
+        .map_or_else(|| format!("{:?}", kind), ToOwned::to_owned);
+
+    ::metrics::counter!("CacheEvents", 1, "EventType" => event_type.clone());
+    ::metrics::timing!("CacheUpdateLatency", latency, "EventType" => event_type);
+
+    if resources_due(cache) {
+        record_resources(cache);
+    }
+}
+
+/// Whether the number of resources in the cache is due to be recorded,
+/// marking them as recorded if so.
+fn resources_due(cache: &InMemoryCache) -> bool {
+    // Another event recording the resources at the same time is just as good.
+    let mut recorded = match cache.0.resources_recorded.try_lock() {
+        Ok(recorded) => recorded,
+        Err(_) => return false,
+    };
+    let now = Instant::now();
+
+    if recorded.map_or(false, |at| now.duration_since(at) < RESOURCES_INTERVAL) {
+        return false;
+    }
+
+    recorded.replace(now);
+
+    true
+}
+
+/// Record the number of resources in the cache.
+fn record_resources(cache: &InMemoryCache) {
+    let stats = cache.stats();
+
+    let resources = [
+        ("Channel", stats.channels()),
+        ("Emoji", stats.emojis()),
+        ("Guild", stats.guilds()),
+        ("Member", stats.members()),
+        ("Message", stats.messages()),
+        ("Presence", stats.presences()),
+        ("PrivateChannel", stats.private_channels()),
+        ("Role", stats.roles()),
+        ("Sticker", stats.stickers()),
+        ("UnavailableGuild", stats.unavailable_guilds()),
+        ("User", stats.users()),
+        ("VoiceState", stats.voice_states()),
+    ];
+
+    for (resource_type, count) in resources.iter() {
+        ::metrics::gauge!(
+            "CacheResources",
+            (*count).try_into().unwrap_or(-1),
+            "ResourceType" => *resource_type,
+        );
+    }
+}
+
+/// Record that resources were evicted from the cache.
+pub(super) fn record_evictions(resource_type: &'static str, count: u64) {
+    ::metrics::counter!("CacheEvictions", count, "ResourceType" => resource_type);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test::{channel, guild_create, member, message},
+        InMemoryCache,
+    };
+    use metrics::{Key, Recorder};
+    use std::{cell::RefCell, sync::Once};
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::{MemberRemove, MessageCreate},
+        },
+        id::{ChannelId, GuildId, MessageId, UserId},
+    };
+
+    thread_local! {
+        /// Metrics recorded on the current thread, so that tests running in
+        /// parallel don't observe each other's metrics.
+        static RECORDED: RefCell<Vec<Recorded>> = RefCell::new(Vec::new());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Recorded {
+        Counter(String, u64),
+        Gauge(String, i64),
+        Histogram(String),
+    }
+
+    /// Recorder keeping the metrics recorded on each thread.
+    struct DebuggingRecorder;
+
+    impl DebuggingRecorder {
+        /// Install the recorder, and take the metrics recorded on the current
+        /// thread since the last call.
+        fn take() -> Vec<Recorded> {
+            static INSTALL: Once = Once::new();
+            static RECORDER: DebuggingRecorder = DebuggingRecorder;
+
+            INSTALL.call_once(|| {
+                metrics::set_recorder(&RECORDER).expect("recorder already set");
+            });
+
+            RECORDED.with(|recorded| recorded.borrow_mut().drain(..).collect())
+        }
+
+        fn record(recorded: Recorded) {
+            RECORDED.with(|r| r.borrow_mut().push(recorded));
+        }
+    }
+
+    /// Format a key as its name followed by the values of its labels.
+    fn key(key: &Key) -> String {
+        key.labels().fold(key.name().into_owned(), |name, label| {
+            format!("{}:{}", name, label.value())
+        })
+    }
+
+    impl Recorder for DebuggingRecorder {
+        fn increment_counter(&self, key: Key, value: u64) {
+            Self::record(Recorded::Counter(self::key(&key), value));
+        }
+
+        fn update_gauge(&self, key: Key, value: i64) {
+            Self::record(Recorded::Gauge(self::key(&key), value));
+        }
+
+        fn record_histogram(&self, key: Key, _: u64) {
+            Self::record(Recorded::Histogram(self::key(&key)));
+        }
+    }
+
+    /// Allow the number of resources in the cache to be recorded again by the
+    /// next event.
+    fn reset_resources(cache: &InMemoryCache) {
+        cache
+            .0
+            .resources_recorded
+            .lock()
+            .expect("resources recorded poisoned")
+            .take();
+    }
+
+    /// Value of a gauge the last time it was recorded.
+    fn gauge(recorded: &[Recorded], name: &str) -> Option<i64> {
+        recorded.iter().rev().find_map(|recorded| match recorded {
+            Recorded::Gauge(key, value) if key == name => Some(*value),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_gauges() {
+        let cache = InMemoryCache::builder().message_cache_size(1).build();
+        DebuggingRecorder::take();
+
+        cache.update(&Event::GuildCreate(Box::new(guild_create(
+            GuildId(1),
+            vec![channel(ChannelId(10))],
+            vec![member(GuildId(1), UserId(2)), member(GuildId(1), UserId(3))],
+        ))));
+        let recorded = DebuggingRecorder::take();
+        assert!(recorded.contains(&Recorded::Counter("CacheEvents:GUILD_CREATE".to_owned(), 1)));
+        assert!(recorded.contains(&Recorded::Histogram(
+            "CacheUpdateLatency:GUILD_CREATE".to_owned()
+        )));
+        assert_eq!(Some(1), gauge(&recorded, "CacheResources:Guild"));
+        assert_eq!(Some(1), gauge(&recorded, "CacheResources:Channel"));
+        assert_eq!(Some(2), gauge(&recorded, "CacheResources:Member"));
+        assert_eq!(Some(0), gauge(&recorded, "CacheResources:Message"));
+
+        reset_resources(&cache);
+        cache.update(&Event::MemberRemove(MemberRemove {
+            guild_id: GuildId(1),
+            user: member(GuildId(1), UserId(3)).user,
+        }));
+        let recorded = DebuggingRecorder::take();
+        assert_eq!(Some(1), gauge(&recorded, "CacheResources:Member"));
+
+        // Caching the second message of the channel evicts the first one.
+        for id in 1..=2 {
+            reset_resources(&cache);
+            cache.update(&Event::MessageCreate(Box::new(MessageCreate(message(
+                ChannelId(10),
+                MessageId(id),
+            )))));
+        }
+        let recorded = DebuggingRecorder::take();
+        assert_eq!(Some(1), gauge(&recorded, "CacheResources:Message"));
+        assert!(recorded.contains(&Recorded::Counter("CacheEvictions:Message".to_owned(), 1)));
+    }
+
+    #[test]
+    fn test_gauges_sampled() {
+        let cache = InMemoryCache::new();
+        DebuggingRecorder::take();
+
+        for id in 1..=2 {
+            cache.update(&Event::MessageCreate(Box::new(MessageCreate(message(
+                ChannelId(10),
+                MessageId(id),
+            )))));
+        }
+
+        // Events within the interval are counted, but only the first records
+        // the number of resources.
+        let recorded = DebuggingRecorder::take();
+        let gauges = recorded
+            .iter()
+            .filter(|recorded| match recorded {
+                Recorded::Gauge(key, _) => key == "CacheResources:Message",
+                _ => false,
+            })
+            .count();
+        assert_eq!(1, gauges);
+        assert_eq!(Some(1), gauge(&recorded, "CacheResources:Message"));
+        assert!(recorded.contains(&Recorded::Counter(
+            "CacheEvents:MESSAGE_CREATE".to_owned(),
+            1
+        )));
+
+        reset_resources(&cache);
+        cache.update(&Event::MessageCreate(Box::new(MessageCreate(message(
+            ChannelId(10),
+            MessageId(3),
+        )))));
+        assert_eq!(
+            Some(3),
+            gauge(&DebuggingRecorder::take(), "CacheResources:Message")
+        );
+    }
+
+    #[test]
+    fn test_payloads_not_recorded_twice() {
+        let cache = InMemoryCache::new();
+        DebuggingRecorder::take();
+
+        // Payloads applied directly aren't events, so they're not recorded.
+        cache.update(&guild_create(GuildId(1), Vec::new(), Vec::new()));
+        assert!(DebuggingRecorder::take().is_empty());
+
+        cache.update(&Event::GuildCreate(Box::new(guild_create(
+            GuildId(1),
+            Vec::new(),
+            Vec::new(),
+        ))));
+        let events = DebuggingRecorder::take()
+            .into_iter()
+            .filter(|recorded| match recorded {
+                Recorded::Counter(key, _) => key.starts_with("CacheEvents"),
+                _ => false,
+            })
+            .count();
+        assert_eq!(1, events);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use twilight_model::{
     guild::Emoji,
@@ -6,7 +6,7 @@ use twilight_model::{
     user::User,
 };
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CachedEmoji {
     pub id: EmojiId,
     pub animated: bool,
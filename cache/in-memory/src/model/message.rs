@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use twilight_model::{
     channel::{
         embed::Embed,
@@ -12,7 +12,7 @@ use twilight_model::{
     id::{ChannelId, GuildId, MessageId, RoleId, UserId, WebhookId},
 };
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CachedMessage {
     pub id: MessageId,
     pub activity: Option<MessageActivity>,
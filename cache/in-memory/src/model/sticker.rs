@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+    channel::message::sticker::{Sticker, StickerFormatType, StickerId, StickerPackId},
+    id::UserId,
+};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CachedSticker {
+    pub id: StickerId,
+    pub asset: String,
+    pub available: Option<bool>,
+    pub description: String,
+    pub format_type: StickerFormatType,
+    pub name: String,
+    pub pack_id: Option<StickerPackId>,
+    pub preview_asset: Option<String>,
+    pub tags: Option<String>,
+    pub user_id: Option<UserId>,
+}
+
+impl PartialEq<Sticker> for CachedSticker {
+    fn eq(&self, other: &Sticker) -> bool {
+        (
+            &self.id,
+            &self.asset,
+            self.available,
+            &self.description,
+            self.format_type,
+            &self.name,
+            &self.pack_id,
+            &self.preview_asset,
+            &self.tags,
+            self.user_id,
+        ) == (
+            &other.id,
+            &other.asset,
+            other.available,
+            &other.description,
+            other.format_type,
+            &other.name,
+            &other.pack_id,
+            &other.preview_asset,
+            &other.tags,
+            other.user.as_ref().map(|user| user.id),
+        )
+    }
+}
+
+impl From<Sticker> for CachedSticker {
+    fn from(sticker: Sticker) -> Self {
+        Self {
+            id: sticker.id,
+            asset: sticker.asset,
+            available: sticker.available,
+            description: sticker.description,
+            format_type: sticker.format_type,
+            name: sticker.name,
+            pack_id: sticker.pack_id,
+            preview_asset: sticker.preview_asset,
+            tags: sticker.tags,
+            user_id: sticker.user.map(|user| user.id),
+        }
+    }
+}
@@ -0,0 +1,800 @@
+//! Calculating the permissions of members from the contents of an
+//! [`InMemoryCache`].
+//!
+//! Permissions are calculated following [Discord's documented algorithm]:
+//!
+//! 1. The owner of a guild has all permissions.
+//! 2. The permissions of the `@everyone` role are the base permissions.
+//! 3. The permissions of the member's roles are added.
+//! 4. Members with the [`ADMINISTRATOR`] permission have all permissions.
+//! 5. In a channel, the channel's permission overwrites are applied: first
+//!    the `@everyone` role's, then those of the member's roles together, and
+//!    then the member's own.
+//! 6. In a channel, permissions that depend on another permission that
+//!    isn't granted are removed, such as [`ATTACH_FILES`] when
+//!    [`SEND_MESSAGES`] isn't granted.
+//!
+//! This requires the [`GUILD`], [`CHANNEL`], [`MEMBER`], and [`ROLE`]
+//! resource types to be cached. The member of the current user is cached
+//! even without [`MEMBER`], so the current user's own permissions only
+//! require the current user to be cached.
+//!
+//! [Discord's documented algorithm]: https://discord.com/developers/docs/topics/permissions#permission-overwrites
+//! [`ADMINISTRATOR`]: twilight_model::guild::Permissions::ADMINISTRATOR
+//! [`ATTACH_FILES`]: twilight_model::guild::Permissions::ATTACH_FILES
+//! [`CHANNEL`]: crate::ResourceType::CHANNEL
+//! [`GUILD`]: crate::ResourceType::GUILD
+//! [`InMemoryCache`]: crate::InMemoryCache
+//! [`MEMBER`]: crate::ResourceType::MEMBER
+//! [`ROLE`]: crate::ResourceType::ROLE
+//! [`SEND_MESSAGES`]: twilight_model::guild::Permissions::SEND_MESSAGES
+
+use super::{InMemoryCache, InMemoryCacheRef};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        GuildChannel,
+    },
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Permissions that are removed in a text channel when [`SEND_MESSAGES`]
+/// isn't granted.
+///
+/// [`SEND_MESSAGES`]: Permissions::SEND_MESSAGES
+const MESSAGE_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::ATTACH_FILES.bits()
+        | Permissions::EMBED_LINKS.bits()
+        | Permissions::MENTION_EVERYONE.bits()
+        | Permissions::SEND_TTS_MESSAGES.bits(),
+);
+
+/// Permissions that are removed in a voice channel when [`CONNECT`] isn't
+/// granted.
+///
+/// [`CONNECT`]: Permissions::CONNECT
+const VOICE_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::DEAFEN_MEMBERS.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::MUTE_MEMBERS.bits()
+        | Permissions::PRIORITY_SPEAKER.bits()
+        | Permissions::SPEAK.bits()
+        | Permissions::STREAM.bits()
+        | Permissions::USE_VAD.bits(),
+);
+
+/// Calculating the permissions of a member in a channel failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ChannelError {
+    /// The channel isn't cached, or isn't a guild channel.
+    ChannelNotCached {
+        /// ID of the channel.
+        channel_id: ChannelId,
+    },
+    /// The guild of the channel isn't cached.
+    GuildNotCached {
+        /// ID of the guild.
+        guild_id: GuildId,
+    },
+    /// The member isn't cached in the guild of the channel.
+    MemberNotCached {
+        /// ID of the guild.
+        guild_id: GuildId,
+        /// ID of the user.
+        user_id: UserId,
+    },
+    /// The `@everyone` role of the guild isn't cached.
+    RoleNotCached {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+}
+
+impl Display for ChannelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ChannelNotCached { channel_id } => {
+                f.write_fmt(format_args!("guild channel {} isn't cached", channel_id))
+            }
+            Self::GuildNotCached { guild_id } => {
+                f.write_fmt(format_args!("guild {} isn't cached", guild_id))
+            }
+            Self::MemberNotCached { guild_id, user_id } => f.write_fmt(format_args!(
+                "member {} isn't cached in guild {}",
+                user_id, guild_id
+            )),
+            Self::RoleNotCached { role_id } => {
+                f.write_fmt(format_args!("role {} isn't cached", role_id))
+            }
+        }
+    }
+}
+
+impl Error for ChannelError {}
+
+impl From<RootError> for ChannelError {
+    fn from(error: RootError) -> Self {
+        match error {
+            RootError::GuildNotCached { guild_id } => Self::GuildNotCached { guild_id },
+            RootError::MemberNotCached { guild_id, user_id } => {
+                Self::MemberNotCached { guild_id, user_id }
+            }
+            RootError::RoleNotCached { role_id } => Self::RoleNotCached { role_id },
+        }
+    }
+}
+
+/// Calculating the permissions of a member in a guild failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RootError {
+    /// The guild isn't cached.
+    GuildNotCached {
+        /// ID of the guild.
+        guild_id: GuildId,
+    },
+    /// The member isn't cached in the guild.
+    MemberNotCached {
+        /// ID of the guild.
+        guild_id: GuildId,
+        /// ID of the user.
+        user_id: UserId,
+    },
+    /// The `@everyone` role of the guild isn't cached.
+    RoleNotCached {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+}
+
+impl Display for RootError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::GuildNotCached { guild_id } => {
+                f.write_fmt(format_args!("guild {} isn't cached", guild_id))
+            }
+            Self::MemberNotCached { guild_id, user_id } => f.write_fmt(format_args!(
+                "member {} isn't cached in guild {}",
+                user_id, guild_id
+            )),
+            Self::RoleNotCached { role_id } => {
+                f.write_fmt(format_args!("role {} isn't cached", role_id))
+            }
+        }
+    }
+}
+
+impl Error for RootError {}
+
+/// Interface to calculate the permissions of members from the contents of an
+/// [`InMemoryCache`].
+///
+/// Created via [`InMemoryCache::permissions`]. Refer to the [module]
+/// documentation for the algorithm used.
+///
+/// # Examples
+///
+/// Check whether a member can send messages in a channel:
+///
+/// ```
+/// use twilight_cache_inmemory::InMemoryCache;
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{ChannelId, UserId},
+/// };
+///
+/// let cache = InMemoryCache::new();
+///
+/// match cache.permissions().in_channel(UserId(1), ChannelId(2)) {
+///     Ok(permissions) => {
+///         println!(
+///             "can send messages: {}",
+///             permissions.contains(Permissions::SEND_MESSAGES)
+///         );
+///     }
+///     Err(source) => println!("couldn't calculate permissions: {}", source),
+/// }
+/// ```
+///
+/// [`InMemoryCache`]: crate::InMemoryCache
+/// [`InMemoryCache::permissions`]: crate::InMemoryCache::permissions
+/// [module]: crate::permission
+#[derive(Clone, Copy, Debug)]
+pub struct InMemoryCachePermissions<'a>(&'a InMemoryCacheRef);
+
+impl<'a> InMemoryCachePermissions<'a> {
+    pub(super) fn new(cache: &'a InMemoryCache) -> Self {
+        Self(&cache.0)
+    }
+
+    /// Calculate the permissions of a member in a guild channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChannelError::ChannelNotCached`] if the channel isn't cached
+    /// as a guild channel, or if it's a thread whose parent channel isn't
+    /// cached.
+    ///
+    /// Returns [`ChannelError::GuildNotCached`] if the guild of the channel
+    /// isn't cached.
+    ///
+    /// Returns [`ChannelError::MemberNotCached`] if the member isn't cached
+    /// in the guild of the channel.
+    ///
+    /// Returns [`ChannelError::RoleNotCached`] if the `@everyone` role of the
+    /// guild isn't cached.
+    pub fn in_channel(
+        &self,
+        user_id: UserId,
+        channel_id: ChannelId,
+    ) -> Result<Permissions, ChannelError> {
+        let (guild_id, channel) = {
+            let item = self
+                .0
+                .channels_guild
+                .get(&channel_id)
+                .ok_or(ChannelError::ChannelNotCached { channel_id })?;
+
+            (item.guild_id, item.data.clone())
+        };
+
+        // Threads don't have overwrites of their own, and inherit those of
+        // the channel they were created in.
+        let channel = match channel.as_ref() {
+            GuildChannel::Thread(thread) => {
+                let parent_id = thread
+                    .parent_id
+                    .ok_or(ChannelError::ChannelNotCached { channel_id })?;
+
+                self.0
+                    .channels_guild
+                    .get(&parent_id)
+                    .ok_or(ChannelError::ChannelNotCached {
+                        channel_id: parent_id,
+                    })?
+                    .data
+                    .clone()
+            }
+            _ => channel,
+        };
+
+        let (root, roles) = self.root_with_roles(user_id, guild_id)?;
+
+        if root.contains(Permissions::ADMINISTRATOR) {
+            return Ok(Permissions::all());
+        }
+
+        let overwrites: &[PermissionOverwrite] = match channel.as_ref() {
+            GuildChannel::Category(category) => &category.permission_overwrites,
+            GuildChannel::Text(text) => &text.permission_overwrites,
+            GuildChannel::Thread(_) => &[],
+            GuildChannel::Voice(voice) => &voice.permission_overwrites,
+        };
+
+        let mut permissions = apply_overwrites(root, overwrites, guild_id, user_id, &roles);
+
+        // Members who can't view a channel can't do anything else in it.
+        if !permissions.contains(Permissions::VIEW_CHANNEL) {
+            return Ok(Permissions::empty());
+        }
+
+        match channel.as_ref() {
+            GuildChannel::Text(_) | GuildChannel::Thread(_)
+                if !permissions.contains(Permissions::SEND_MESSAGES) =>
+            {
+                permissions.remove(MESSAGE_DEPENDENT);
+            }
+            GuildChannel::Voice(_) if !permissions.contains(Permissions::CONNECT) => {
+                permissions.remove(VOICE_DEPENDENT);
+            }
+            _ => {}
+        }
+
+        Ok(permissions)
+    }
+
+    /// Calculate the permissions of a member in a guild, without the
+    /// overwrites of any channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RootError::GuildNotCached`] if the guild isn't cached.
+    ///
+    /// Returns [`RootError::MemberNotCached`] if the member isn't cached in
+    /// the guild.
+    ///
+    /// Returns [`RootError::RoleNotCached`] if the `@everyone` role of the
+    /// guild isn't cached.
+    pub fn root(&self, user_id: UserId, guild_id: GuildId) -> Result<Permissions, RootError> {
+        self.root_with_roles(user_id, guild_id)
+            .map(|(permissions, _)| permissions)
+    }
+
+    /// Calculate the permissions of a member in a guild, returning them along
+    /// with the member's roles.
+    ///
+    /// The owner and administrators are given all permissions.
+    fn root_with_roles(
+        &self,
+        user_id: UserId,
+        guild_id: GuildId,
+    ) -> Result<(Permissions, Vec<RoleId>), RootError> {
+        let owner_id = self
+            .0
+            .guilds
+            .get(&guild_id)
+            .ok_or(RootError::GuildNotCached { guild_id })?
+            .owner_id;
+
+        let roles = self
+            .0
+            .members
+            .get(&(guild_id, user_id))
+            .ok_or(RootError::MemberNotCached { guild_id, user_id })?
+            .roles
+            .clone();
+
+        if owner_id == user_id {
+            return Ok((Permissions::all(), roles));
+        }
+
+        // The @everyone role has the same ID as the guild.
+        let everyone_id = RoleId(guild_id.0);
+        let mut permissions =
+            self.role_permissions(everyone_id)
+                .ok_or(RootError::RoleNotCached {
+                    role_id: everyone_id,
+                })?;
+
+        // Deleting a role doesn't update the members that had it, so roles
+        // that are no longer cached are skipped.
+        for role_id in &roles {
+            permissions |= self
+                .role_permissions(*role_id)
+                .unwrap_or_else(Permissions::empty);
+        }
+
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Ok((Permissions::all(), roles));
+        }
+
+        Ok((permissions, roles))
+    }
+
+    fn role_permissions(&self, role_id: RoleId) -> Option<Permissions> {
+        self.0.roles.get(&role_id).map(|role| role.data.permissions)
+    }
+}
+
+/// Apply the permission overwrites of a channel to the root permissions of a
+/// member, in the order of the `@everyone` role, the member's roles, and the
+/// member.
+fn apply_overwrites(
+    mut permissions: Permissions,
+    overwrites: &[PermissionOverwrite],
+    guild_id: GuildId,
+    user_id: UserId,
+    roles: &[RoleId],
+) -> Permissions {
+    let everyone_id = RoleId(guild_id.0);
+
+    let mut everyone = None;
+    let mut member = None;
+    let mut role_allow = Permissions::empty();
+    let mut role_deny = Permissions::empty();
+
+    for overwrite in overwrites {
+        match overwrite.kind {
+            PermissionOverwriteType::Role(role_id) if role_id == everyone_id => {
+                everyone = Some(overwrite);
+            }
+            PermissionOverwriteType::Role(role_id) if roles.contains(&role_id) => {
+                role_allow |= overwrite.allow;
+                role_deny |= overwrite.deny;
+            }
+            PermissionOverwriteType::Member(id) if id == user_id => {
+                member = Some(overwrite);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(overwrite) = everyone {
+        permissions.remove(overwrite.deny);
+        permissions.insert(overwrite.allow);
+    }
+
+    // A role allowing a permission wins over another role denying it.
+    permissions.remove(role_deny);
+    permissions.insert(role_allow);
+
+    if let Some(overwrite) = member {
+        permissions.remove(overwrite.deny);
+        permissions.insert(overwrite.allow);
+    }
+
+    permissions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelError, InMemoryCachePermissions, RootError};
+    use crate::{
+        test::{self, guild_create, member, role},
+        InMemoryCache,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            Channel, ChannelType, GuildChannel, TextChannel, VoiceChannel,
+        },
+        gateway::payload::{ChannelDelete, GuildCreate, MemberAdd, RoleDelete, ThreadCreate},
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(ChannelError: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(InMemoryCachePermissions<'_>: Clone, Copy, Debug, Send, Sync);
+    assert_impl_all!(RootError: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
+
+    const GUILD_ID: GuildId = GuildId(1);
+    const EVERYONE_ID: RoleId = RoleId(1);
+    const OWNER_ID: UserId = UserId(2);
+    const MEMBER_ID: UserId = UserId(3);
+    const MODERATOR_ID: RoleId = RoleId(4);
+    const MUTED_ID: RoleId = RoleId(5);
+    const ADMIN_ID: RoleId = RoleId(6);
+    const TEXT_ID: ChannelId = ChannelId(7);
+    const VOICE_ID: ChannelId = ChannelId(8);
+
+    fn overwrite(
+        kind: PermissionOverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    fn text(overwrites: Vec<PermissionOverwrite>) -> GuildChannel {
+        GuildChannel::Text(TextChannel {
+            guild_id: None,
+            id: TEXT_ID,
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "text".to_owned(),
+            nsfw: false,
+            parent_id: None,
+            permission_overwrites: overwrites,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        })
+    }
+
+    fn voice(overwrites: Vec<PermissionOverwrite>) -> GuildChannel {
+        GuildChannel::Voice(VoiceChannel {
+            bitrate: 64_000,
+            guild_id: None,
+            id: VOICE_ID,
+            kind: ChannelType::GuildVoice,
+            name: "voice".to_owned(),
+            parent_id: None,
+            permission_overwrites: overwrites,
+            position: 1,
+            user_limit: None,
+        })
+    }
+
+    fn base() -> Permissions {
+        Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::ATTACH_FILES
+            | Permissions::EMBED_LINKS
+            | Permissions::CONNECT
+            | Permissions::SPEAK
+    }
+
+    /// Create a cache with a guild whose member has the given roles, and
+    /// whose channels have the given overwrites.
+    fn cache(roles: Vec<RoleId>, channels: Vec<GuildChannel>) -> InMemoryCache {
+        let cache = InMemoryCache::new();
+
+        let mut guild_member = member(GUILD_ID, MEMBER_ID);
+        guild_member.roles = roles;
+
+        let GuildCreate(mut guild) = guild_create(
+            GUILD_ID,
+            channels,
+            vec![member(GUILD_ID, OWNER_ID), guild_member],
+        );
+        guild.owner_id = OWNER_ID;
+        guild.roles = vec![
+            role(EVERYONE_ID, base()),
+            role(
+                MODERATOR_ID,
+                Permissions::KICK_MEMBERS | Permissions::MANAGE_MESSAGES,
+            ),
+            role(MUTED_ID, Permissions::empty()),
+            role(ADMIN_ID, Permissions::ADMINISTRATOR),
+        ];
+        cache.update(&GuildCreate(guild));
+
+        cache
+    }
+
+    #[test]
+    fn test_owner() {
+        let everyone_denied = overwrite(
+            PermissionOverwriteType::Role(EVERYONE_ID),
+            Permissions::empty(),
+            Permissions::all(),
+        );
+        let cache = cache(Vec::new(), vec![text(vec![everyone_denied])]);
+        let permissions = cache.permissions();
+
+        assert_eq!(Ok(Permissions::all()), permissions.root(OWNER_ID, GUILD_ID));
+        assert_eq!(
+            Ok(Permissions::all()),
+            permissions.in_channel(OWNER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_administrator() {
+        let member_denied = overwrite(
+            PermissionOverwriteType::Member(MEMBER_ID),
+            Permissions::empty(),
+            Permissions::all(),
+        );
+        let cache = cache(vec![ADMIN_ID], vec![text(vec![member_denied])]);
+        let permissions = cache.permissions();
+
+        assert_eq!(
+            Ok(Permissions::all()),
+            permissions.root(MEMBER_ID, GUILD_ID)
+        );
+        assert_eq!(
+            Ok(Permissions::all()),
+            permissions.in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_root_aggregates_roles() {
+        let cache = cache(vec![MODERATOR_ID, MUTED_ID], Vec::new());
+
+        assert_eq!(
+            Ok(base() | Permissions::KICK_MEMBERS | Permissions::MANAGE_MESSAGES),
+            cache.permissions().root(MEMBER_ID, GUILD_ID)
+        );
+    }
+
+    #[test]
+    fn test_channel_without_overwrites() {
+        let cache = cache(Vec::new(), vec![text(Vec::new())]);
+
+        assert_eq!(
+            Ok(base()),
+            cache.permissions().in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_overwrite_order() {
+        let cache = cache(
+            vec![MODERATOR_ID],
+            vec![text(vec![
+                // The member's overwrite is applied last, so it wins over
+                // the role denying embeds.
+                overwrite(
+                    PermissionOverwriteType::Member(MEMBER_ID),
+                    Permissions::EMBED_LINKS,
+                    Permissions::ATTACH_FILES,
+                ),
+                overwrite(
+                    PermissionOverwriteType::Role(MODERATOR_ID),
+                    Permissions::ADD_REACTIONS,
+                    Permissions::EMBED_LINKS,
+                ),
+                // The role's overwrite is applied after @everyone's, so it
+                // wins over it.
+                overwrite(
+                    PermissionOverwriteType::Role(EVERYONE_ID),
+                    Permissions::MENTION_EVERYONE,
+                    Permissions::ADD_REACTIONS | Permissions::KICK_MEMBERS,
+                ),
+            ])],
+        );
+
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::EMBED_LINKS
+                | Permissions::CONNECT
+                | Permissions::SPEAK
+                | Permissions::MANAGE_MESSAGES
+                | Permissions::ADD_REACTIONS
+                | Permissions::MENTION_EVERYONE),
+            cache.permissions().in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_conflicting_role_overwrites() {
+        let cache = cache(
+            vec![MODERATOR_ID, MUTED_ID],
+            vec![text(vec![
+                overwrite(
+                    PermissionOverwriteType::Role(MUTED_ID),
+                    Permissions::empty(),
+                    Permissions::ATTACH_FILES | Permissions::EMBED_LINKS,
+                ),
+                overwrite(
+                    PermissionOverwriteType::Role(MODERATOR_ID),
+                    Permissions::ATTACH_FILES,
+                    Permissions::empty(),
+                ),
+                // The member doesn't have this role, so it's ignored.
+                overwrite(
+                    PermissionOverwriteType::Role(ADMIN_ID),
+                    Permissions::empty(),
+                    Permissions::all(),
+                ),
+            ])],
+        );
+
+        let permissions = cache.permissions().in_channel(MEMBER_ID, TEXT_ID).unwrap();
+        assert!(permissions.contains(Permissions::ATTACH_FILES));
+        assert!(!permissions.contains(Permissions::EMBED_LINKS));
+    }
+
+    #[test]
+    fn test_thread_inherits_parent_overwrites() {
+        let cache = cache(
+            Vec::new(),
+            vec![text(vec![overwrite(
+                PermissionOverwriteType::Role(EVERYONE_ID),
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES,
+            )])],
+        );
+        let thread_id = ChannelId(9);
+        cache.update(&ThreadCreate(test::thread(GUILD_ID, thread_id, TEXT_ID)));
+
+        assert_eq!(
+            cache.permissions().in_channel(MEMBER_ID, TEXT_ID),
+            cache.permissions().in_channel(MEMBER_ID, thread_id)
+        );
+        assert!(!cache
+            .permissions()
+            .in_channel(MEMBER_ID, thread_id)
+            .unwrap()
+            .contains(Permissions::EMBED_LINKS));
+
+        cache.update(&ChannelDelete(Channel::Guild(text(Vec::new()))));
+        assert_eq!(
+            Err(ChannelError::ChannelNotCached {
+                channel_id: thread_id
+            }),
+            cache.permissions().in_channel(MEMBER_ID, thread_id)
+        );
+    }
+
+    #[test]
+    fn test_implicit_send_messages_deny() {
+        let cache = cache(
+            vec![MODERATOR_ID],
+            vec![text(vec![overwrite(
+                PermissionOverwriteType::Role(MODERATOR_ID),
+                Permissions::MENTION_EVERYONE,
+                Permissions::SEND_MESSAGES,
+            )])],
+        );
+
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL
+                | Permissions::CONNECT
+                | Permissions::SPEAK
+                | Permissions::KICK_MEMBERS
+                | Permissions::MANAGE_MESSAGES),
+            cache.permissions().in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_implicit_connect_deny() {
+        let cache = cache(
+            Vec::new(),
+            vec![voice(vec![overwrite(
+                PermissionOverwriteType::Member(MEMBER_ID),
+                Permissions::empty(),
+                Permissions::CONNECT,
+            )])],
+        );
+
+        let permissions = cache.permissions().in_channel(MEMBER_ID, VOICE_ID).unwrap();
+        assert!(!permissions.contains(Permissions::SPEAK));
+        assert!(permissions.contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_implicit_view_channel_deny() {
+        let cache = cache(
+            Vec::new(),
+            vec![text(vec![overwrite(
+                PermissionOverwriteType::Role(EVERYONE_ID),
+                Permissions::MANAGE_MESSAGES,
+                Permissions::VIEW_CHANNEL,
+            )])],
+        );
+
+        assert_eq!(
+            Ok(Permissions::empty()),
+            cache.permissions().in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let cache = cache(vec![MODERATOR_ID], vec![text(Vec::new())]);
+        let permissions = cache.permissions();
+
+        assert_eq!(
+            Err(RootError::GuildNotCached {
+                guild_id: GuildId(9),
+            }),
+            permissions.root(MEMBER_ID, GuildId(9))
+        );
+        assert_eq!(
+            Err(RootError::MemberNotCached {
+                guild_id: GUILD_ID,
+                user_id: UserId(9),
+            }),
+            permissions.root(UserId(9), GUILD_ID)
+        );
+        assert_eq!(
+            Err(ChannelError::ChannelNotCached {
+                channel_id: ChannelId(9),
+            }),
+            permissions.in_channel(MEMBER_ID, ChannelId(9))
+        );
+        assert_eq!(
+            Err(ChannelError::MemberNotCached {
+                guild_id: GUILD_ID,
+                user_id: UserId(9),
+            }),
+            permissions.in_channel(UserId(9), TEXT_ID)
+        );
+
+        // Members added later are taken into account.
+        cache.update(&MemberAdd(member(GUILD_ID, UserId(9))));
+        assert_eq!(Ok(base()), permissions.in_channel(UserId(9), TEXT_ID));
+
+        // Deleted roles of members grant nothing.
+        cache.update(&RoleDelete {
+            guild_id: GUILD_ID,
+            role_id: MODERATOR_ID,
+        });
+        assert_eq!(Ok(base()), permissions.in_channel(MEMBER_ID, TEXT_ID));
+
+        cache.update(&RoleDelete {
+            guild_id: GUILD_ID,
+            role_id: EVERYONE_ID,
+        });
+        assert_eq!(
+            Err(ChannelError::RoleNotCached {
+                role_id: EVERYONE_ID,
+            }),
+            permissions.in_channel(MEMBER_ID, TEXT_ID)
+        );
+    }
+}
@@ -0,0 +1,695 @@
+//! Snapshots of the state of an [`InMemoryCache`].
+//!
+//! A snapshot contains the cached resources, but not the maps indexing them
+//! or the counters kept alongside them, which are rebuilt while restoring. It
+//! is written and read as JSON, one resource at a time, so neither creating
+//! nor restoring a snapshot buffers the whole cache in memory.
+//!
+//! Snapshots are versioned with [`VERSION`], and restoring a snapshot of
+//! another version fails with [`SnapshotError::UnsupportedVersion`].
+//!
+//! Requires the `snapshot` feature.
+//!
+//! [`InMemoryCache`]: crate::InMemoryCache
+
+use super::{
+    model::{CachedEmoji, CachedGuild, CachedMember, CachedMessage, CachedPresence, CachedSticker},
+    upsert_guild_item, InMemoryCache,
+};
+use serde::{
+    de::{DeserializeSeed, Error as DeError, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Error as JsonError;
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    collections::BTreeSet,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{Read, Write},
+    marker::PhantomData,
+    sync::Arc,
+};
+use twilight_model::{
+    channel::{thread::ThreadMember, Group, GuildChannel, PrivateChannel},
+    guild::Role,
+    id::GuildId,
+    user::{CurrentUser, User},
+    voice::VoiceState,
+};
+
+/// Version of the snapshot format.
+///
+/// Incremented whenever the format changes incompatibly.
+pub const VERSION: u32 = 1;
+
+/// Creating or restoring a snapshot failed.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Reading or deserializing the snapshot failed, such as because it's
+    /// malformed.
+    Deserializing {
+        /// Reason for the error.
+        source: JsonError,
+    },
+    /// Serializing or writing the snapshot failed.
+    Serializing {
+        /// Reason for the error.
+        source: JsonError,
+    },
+    /// The snapshot was created with a different version of the format.
+    UnsupportedVersion {
+        /// Version of the snapshot.
+        version: u32,
+    },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Deserializing { .. } => f.write_str("deserializing the snapshot failed"),
+            Self::Serializing { .. } => f.write_str("serializing the snapshot failed"),
+            Self::UnsupportedVersion { version } => f.write_fmt(format_args!(
+                "snapshot version {} isn't supported, expected version {}",
+                version, VERSION
+            )),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Deserializing { source } | Self::Serializing { source } => Some(source),
+            Self::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+pub(super) fn write(cache: &InMemoryCache, writer: impl Write) -> Result<(), SnapshotError> {
+    serde_json::to_writer(writer, &Snapshot(cache))
+        .map_err(|source| SnapshotError::Serializing { source })
+}
+
+pub(super) fn restore(cache: &InMemoryCache, reader: impl Read) -> Result<(), SnapshotError> {
+    cache.clear();
+
+    let version = Cell::new(None);
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+
+    let result = Restore {
+        cache,
+        version: &version,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|_| deserializer.end());
+
+    if let Err(source) = result {
+        // Don't leave a partially restored snapshot behind.
+        cache.clear();
+
+        return Err(match version.get() {
+            Some(version) => SnapshotError::UnsupportedVersion { version },
+            None => SnapshotError::Deserializing { source },
+        });
+    }
+
+    Ok(())
+}
+
+/// Fields of a snapshot, in the order they're written in.
+///
+/// Users precede the members and emojis referencing them, and the current
+/// user precedes the private channels, so that they're available when those
+/// are restored.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Version,
+    CurrentUser,
+    Guilds,
+    UnavailableGuilds,
+    Users,
+    Channels,
+    ThreadMembers,
+    PrivateChannels,
+    Groups,
+    Emojis,
+    Members,
+    Presences,
+    Roles,
+    Stickers,
+    Messages,
+    VoiceStates,
+}
+
+const FIELDS: &[&str] = &[
+    "version",
+    "current_user",
+    "guilds",
+    "unavailable_guilds",
+    "users",
+    "channels",
+    "thread_members",
+    "private_channels",
+    "groups",
+    "emojis",
+    "members",
+    "presences",
+    "roles",
+    "stickers",
+    "messages",
+    "voice_states",
+];
+
+struct Snapshot<'a>(&'a InMemoryCache);
+
+impl Serialize for Snapshot<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let cache = &(self.0).0;
+        // Release the lock before writing the current user out.
+        let current_user = cache
+            .current_user
+            .lock()
+            .expect("current user poisoned")
+            .clone();
+
+        let mut state = serializer.serialize_struct("Snapshot", FIELDS.len())?;
+        state.serialize_field("version", &VERSION)?;
+        state.serialize_field("current_user", &current_user)?;
+        state.serialize_field(
+            "guilds",
+            &Items(|| cache.guilds.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+        state.serialize_field(
+            "unavailable_guilds",
+            &Items(|| cache.unavailable_guilds.iter().map(|r| *r.key())),
+        )?;
+        state.serialize_field(
+            "users",
+            &Items(|| {
+                cache
+                    .users
+                    .iter()
+                    .map(|r| (Arc::clone(&r.value().0), r.value().1.clone()))
+            }),
+        )?;
+        state.serialize_field(
+            "channels",
+            &Items(|| {
+                cache
+                    .channels_guild
+                    .iter()
+                    .map(|r| (r.guild_id, Arc::clone(&r.data)))
+            }),
+        )?;
+        state.serialize_field(
+            "thread_members",
+            &Items(|| cache.thread_members.iter().map(|r| r.value().clone())),
+        )?;
+        state.serialize_field(
+            "private_channels",
+            &Items(|| cache.channels_private.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+        state.serialize_field(
+            "groups",
+            &Items(|| cache.groups.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+        state.serialize_field(
+            "emojis",
+            &Items(|| {
+                cache
+                    .emojis
+                    .iter()
+                    .map(|r| (r.guild_id, Arc::clone(&r.data)))
+            }),
+        )?;
+        state.serialize_field(
+            "members",
+            &Items(|| cache.members.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+        state.serialize_field(
+            "presences",
+            &Items(|| cache.presences.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+        state.serialize_field(
+            "roles",
+            &Items(|| {
+                cache
+                    .roles
+                    .iter()
+                    .map(|r| (r.guild_id, Arc::clone(&r.data)))
+            }),
+        )?;
+        state.serialize_field(
+            "stickers",
+            &Items(|| {
+                cache
+                    .stickers
+                    .iter()
+                    .map(|r| (r.guild_id, Arc::clone(&r.data)))
+            }),
+        )?;
+        // Messages are copied out a channel at a time, so that the lock on a
+        // channel isn't held while its messages are written.
+        state.serialize_field(
+            "messages",
+            &Items(|| {
+                cache
+                    .messages
+                    .iter()
+                    .flat_map(|r| r.values().cloned().collect::<Vec<_>>())
+            }),
+        )?;
+        state.serialize_field(
+            "voice_states",
+            &Items(|| cache.voice_states.iter().map(|r| Arc::clone(r.value()))),
+        )?;
+
+        state.end()
+    }
+}
+
+/// Sequence of resources serialized from an iterator as they're yielded.
+struct Items<F>(F);
+
+impl<F, I> Serialize for Items<F>
+where
+    F: Fn() -> I,
+    I: Iterator,
+    I::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((self.0)())
+    }
+}
+
+struct Restore<'a> {
+    cache: &'a InMemoryCache,
+    /// Version of the snapshot, if it isn't supported.
+    version: &'a Cell<Option<u32>>,
+}
+
+impl<'de> DeserializeSeed<'de> for Restore<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_struct("Snapshot", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for Restore<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("a cache snapshot")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        // The version is checked before anything else is read, since the
+        // rest of the snapshot may be in an incompatible format.
+        match map.next_key()? {
+            Some(Field::Version) => {}
+            _ => return Err(DeError::missing_field("version")),
+        }
+
+        let version = map.next_value::<u32>()?;
+
+        if version != VERSION {
+            self.version.set(Some(version));
+
+            return Err(DeError::custom("unsupported snapshot version"));
+        }
+
+        let cache = self.cache;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Version => return Err(DeError::duplicate_field("version")),
+                Field::CurrentUser => {
+                    if let Some(current_user) = map.next_value::<Option<CurrentUser>>()? {
+                        cache.cache_current_user(current_user);
+                    }
+                }
+                Field::Guilds => map.next_value_seed(Insert::new(|guild: CachedGuild| {
+                    cache.0.guilds.insert(guild.id, Arc::new(guild));
+                }))?,
+                Field::UnavailableGuilds => {
+                    map.next_value_seed(Insert::new(|guild_id: GuildId| {
+                        cache.0.unavailable_guilds.insert(guild_id);
+                    }))?
+                }
+                // Users are linked to the guilds they were cached in, which
+                // includes the guilds of messages they authored.
+                Field::Users => map.next_value_seed(Insert::new(
+                    |(user, guilds): (User, BTreeSet<GuildId>)| {
+                        cache.0.users.insert(user.id, (Arc::new(user), guilds));
+                    },
+                ))?,
+                Field::Channels => map.next_value_seed(Insert::new(
+                    |(guild_id, channel): (GuildId, GuildChannel)| {
+                        cache.cache_guild_channel(guild_id, channel);
+                    },
+                ))?,
+                Field::ThreadMembers => {
+                    map.next_value_seed(Insert::new(|member: ThreadMember| {
+                        if let Some(thread_id) = member.id {
+                            cache.cache_thread_member(thread_id, member);
+                        }
+                    }))?
+                }
+                Field::PrivateChannels => {
+                    map.next_value_seed(Insert::new(|channel: PrivateChannel| {
+                        cache.cache_private_channel(channel);
+                    }))?
+                }
+                Field::Groups => map.next_value_seed(Insert::new(|group: Group| {
+                    cache.cache_group(group);
+                }))?,
+                Field::Emojis => map.next_value_seed(Insert::new(
+                    |(guild_id, mut emoji): (GuildId, CachedEmoji)| {
+                        emoji.user = emoji
+                            .user
+                            .map(|user| cache.cache_user(Cow::Borrowed(&user), Some(guild_id)));
+
+                        cache
+                            .0
+                            .guild_emojis
+                            .entry(guild_id)
+                            .or_default()
+                            .insert(emoji.id);
+                        upsert_guild_item(&cache.0.emojis, guild_id, emoji.id, emoji);
+                    },
+                ))?,
+                Field::Members => {
+                    map.next_value_seed(Insert::new(|mut member: CachedMember| {
+                        let guild_id = member.guild_id;
+                        let user_id = member.user.id;
+
+                        // Share the user with the users map.
+                        member.user = cache.cache_user(Cow::Borrowed(&member.user), Some(guild_id));
+
+                        cache
+                            .0
+                            .guild_members
+                            .entry(guild_id)
+                            .or_default()
+                            .insert(user_id);
+                        cache
+                            .0
+                            .members
+                            .insert((guild_id, user_id), Arc::new(member));
+                    }))?
+                }
+                Field::Presences => {
+                    map.next_value_seed(Insert::new(|presence: CachedPresence| {
+                        let key = (presence.guild_id, presence.user_id);
+
+                        cache
+                            .0
+                            .guild_presences
+                            .entry(presence.guild_id)
+                            .or_default()
+                            .insert(presence.user_id);
+                        cache.0.presences.insert(key, Arc::new(presence));
+                    }))?
+                }
+                Field::Roles => {
+                    map.next_value_seed(Insert::new(|(guild_id, role): (GuildId, Role)| {
+                        cache.cache_role(guild_id, role);
+                    }))?
+                }
+                Field::Stickers => map.next_value_seed(Insert::new(
+                    |(guild_id, sticker): (GuildId, CachedSticker)| {
+                        cache
+                            .0
+                            .guild_stickers
+                            .entry(guild_id)
+                            .or_default()
+                            .insert(sticker.id.clone());
+                        upsert_guild_item(&cache.0.stickers, guild_id, sticker.id.clone(), sticker);
+                    },
+                ))?,
+                Field::Messages => map.next_value_seed(Insert::new(|message: CachedMessage| {
+                    cache.cache_message(message);
+                }))?,
+                Field::VoiceStates => {
+                    map.next_value_seed(Insert::new(|voice_state: VoiceState| {
+                        cache.cache_voice_state(voice_state);
+                    }))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sequence of resources deserialized and inserted into the cache one at a
+/// time.
+struct Insert<T, F> {
+    insert: F,
+    kind: PhantomData<fn(T)>,
+}
+
+impl<T, F: FnMut(T)> Insert<T, F> {
+    fn new(insert: F) -> Self {
+        Self {
+            insert,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, F: FnMut(T)> DeserializeSeed<'de> for Insert<T, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, F: FnMut(T)> Visitor<'de> for Insert<T, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("a sequence of resources")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(item) = seq.next_element()? {
+            (self.insert)(item);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SnapshotError, VERSION};
+    use crate::{
+        test::{channel, guild_create, member, message, presence, role, user},
+        InMemoryCache,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug, sync::Arc};
+    use twilight_model::{
+        gateway::{
+            payload::{MemberRemove, MessageCreate, RoleCreate, VoiceStateUpdate},
+            presence::Status,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+        user::CurrentUser,
+        voice::VoiceState,
+    };
+
+    assert_impl_all!(SnapshotError: Debug, Error, Send, Sync);
+
+    fn cache() -> InMemoryCache {
+        let cache = InMemoryCache::builder().message_cache_size(5).build();
+        cache.cache_current_user(CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(9),
+            locale: None,
+            mfa_enabled: false,
+            name: "bot".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            verified: None,
+        });
+
+        cache.update(&guild_create(
+            GuildId(1),
+            vec![channel(ChannelId(10)), channel(ChannelId(11))],
+            vec![member(GuildId(1), UserId(2)), member(GuildId(1), UserId(3))],
+        ));
+        cache.update(&guild_create(
+            GuildId(4),
+            vec![channel(ChannelId(12))],
+            vec![member(GuildId(4), UserId(2))],
+        ));
+        cache.update(&RoleCreate {
+            guild_id: GuildId(1),
+            role: role(RoleId(5), Permissions::SEND_MESSAGES),
+        });
+        cache.cache_presence(GuildId(1), presence(GuildId(1), UserId(2), Status::Idle));
+        cache.update(&VoiceStateUpdate(VoiceState {
+            channel_id: Some(ChannelId(11)),
+            deaf: false,
+            guild_id: Some(GuildId(1)),
+            member: None,
+            mute: false,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: false,
+            session_id: "a".to_owned(),
+            suppress: false,
+            token: None,
+            user_id: UserId(3),
+        }));
+
+        // The author of the messages isn't a member of the guild.
+        for id in 1..=3 {
+            let mut message = message(ChannelId(10), MessageId(id));
+            message.content = format!("message {}", id);
+            message.guild_id = Some(GuildId(1));
+            cache.update(&MessageCreate(message));
+        }
+
+        cache
+    }
+
+    fn snapshot(cache: &InMemoryCache) -> Vec<u8> {
+        let mut buf = Vec::new();
+        cache.snapshot(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = cache();
+        let restored = InMemoryCache::builder().message_cache_size(5).build();
+        restored.restore(snapshot(&cache).as_slice()).unwrap();
+
+        let (stats, restored_stats) = (cache.stats(), restored.stats());
+        assert_eq!(stats.guilds(), restored_stats.guilds());
+        assert_eq!(3, restored_stats.channels());
+        assert_eq!(3, restored_stats.members());
+        assert_eq!(3, restored_stats.messages());
+        assert_eq!(1, restored_stats.presences());
+        assert_eq!(1, restored_stats.roles());
+        assert_eq!(stats.users(), restored_stats.users());
+        assert_eq!(1, restored_stats.voice_states());
+        assert_eq!(stats.guild(GuildId(1)), restored_stats.guild(GuildId(1)));
+        assert_eq!(stats.guild(GuildId(4)), restored_stats.guild(GuildId(4)));
+
+        assert_eq!(cache.current_user(), restored.current_user());
+        assert_eq!(
+            cache.guild(GuildId(1)).as_deref(),
+            restored.guild(GuildId(1)).as_deref()
+        );
+        assert_eq!(
+            cache.guild_channels(GuildId(1)).as_deref(),
+            restored.guild_channels(GuildId(1)).as_deref()
+        );
+        assert_eq!(
+            cache.role(RoleId(5)).unwrap().data(),
+            restored.role(RoleId(5)).unwrap().data()
+        );
+        assert_eq!(
+            cache.presence(GuildId(1), UserId(2)).as_deref(),
+            restored.presence(GuildId(1), UserId(2)).as_deref()
+        );
+        assert_eq!(
+            Some(vec![MessageId(3), MessageId(2), MessageId(1)]),
+            restored.channel_messages(ChannelId(10))
+        );
+        assert_eq!(
+            "message 2",
+            restored
+                .message(ChannelId(10), MessageId(2))
+                .unwrap()
+                .content
+        );
+        assert_eq!(
+            cache.voice_channel_states(ChannelId(11)),
+            restored.voice_channel_states(ChannelId(11))
+        );
+
+        // Members share their user with the users map.
+        let member = Arc::clone(&restored.member(GuildId(1), UserId(2)).unwrap());
+        assert_eq!(
+            cache.member(GuildId(1), UserId(2)).as_deref(),
+            Some(&member)
+        );
+        assert!(Arc::ptr_eq(
+            &member.user,
+            &restored.user(UserId(2)).unwrap()
+        ));
+
+        // The links of users to guilds are restored, so users are removed
+        // once they're no longer in any guild.
+        restored.update(&MemberRemove {
+            guild_id: GuildId(1),
+            user: user(UserId(3)),
+        });
+        assert!(restored.user(UserId(3)).is_none());
+        assert!(restored.voice_channel_states(ChannelId(11)).is_none());
+        restored.update(&MemberRemove {
+            guild_id: GuildId(1),
+            user: user(UserId(2)),
+        });
+        assert!(restored.user(UserId(2)).is_some());
+        assert!(restored.user(UserId(1)).is_some());
+    }
+
+    #[test]
+    fn test_restore_message_cache_size() {
+        let restored = InMemoryCache::builder().message_cache_size(2).build();
+        restored.restore(snapshot(&cache()).as_slice()).unwrap();
+
+        assert_eq!(2, restored.stats().messages());
+        assert_eq!(
+            Some(vec![MessageId(3), MessageId(2)]),
+            restored.channel_messages(ChannelId(10))
+        );
+    }
+
+    #[test]
+    fn test_restore_invalid() {
+        let snapshot = String::from_utf8(snapshot(&cache())).unwrap();
+        let restored = cache();
+
+        let version = format!("{{\"version\":{}", VERSION);
+        assert!(snapshot.starts_with(&version));
+        let unsupported = snapshot.replacen(&version, "{\"version\":0", 1);
+        assert!(matches!(
+            restored.restore(unsupported.as_bytes()),
+            Err(SnapshotError::UnsupportedVersion { version: 0 })
+        ));
+        assert_eq!(0, restored.stats().guilds());
+
+        let truncated = &snapshot.as_bytes()[..snapshot.len() / 2];
+        assert!(matches!(
+            restored.restore(truncated),
+            Err(SnapshotError::Deserializing { .. })
+        ));
+        assert_eq!(0, restored.stats().members());
+        assert!(restored.current_user().is_none());
+        assert_eq!(0, restored.stats().messages());
+
+        assert!(matches!(
+            restored.restore(&b"{\"guilds\":[]}"[..]),
+            Err(SnapshotError::Deserializing { .. })
+        ));
+    }
+}
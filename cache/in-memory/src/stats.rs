@@ -0,0 +1,335 @@
+//! Statistics about the resources in an [`InMemoryCache`].
+//!
+//! Statistics are read from the lengths the cache's maps maintain and from
+//! counters updated alongside them, so retrieving them doesn't walk the
+//! cache and is cheap enough to be done frequently, such as for exporting
+//! metrics.
+//!
+//! [`InMemoryCache`]: crate::InMemoryCache
+
+use super::{InMemoryCache, InMemoryCacheRef};
+use dashmap::DashMap;
+use std::{collections::HashSet, sync::atomic::Ordering};
+use twilight_model::id::GuildId;
+
+/// Interface to retrieve statistics about the resources of an
+/// [`InMemoryCache`].
+///
+/// Created via [`InMemoryCache::stats`].
+///
+/// # Examples
+///
+/// Retrieve the number of guilds and members cached:
+///
+/// ```
+/// use twilight_cache_inmemory::InMemoryCache;
+///
+/// let cache = InMemoryCache::new();
+/// let stats = cache.stats();
+///
+/// println!("guilds: {}", stats.guilds());
+/// println!("members: {}", stats.members());
+/// ```
+///
+/// [`InMemoryCache`]: crate::InMemoryCache
+/// [`InMemoryCache::stats`]: crate::InMemoryCache::stats
+#[derive(Clone, Copy, Debug)]
+pub struct InMemoryCacheStats<'a>(&'a InMemoryCacheRef);
+
+impl<'a> InMemoryCacheStats<'a> {
+    pub(super) fn new(cache: &'a InMemoryCache) -> Self {
+        Self(&cache.0)
+    }
+
+    /// Number of guild channels in the cache.
+    pub fn channels(&self) -> usize {
+        self.0.channels_guild.len()
+    }
+
+    /// Number of emojis in the cache.
+    pub fn emojis(&self) -> usize {
+        self.0.emojis.len()
+    }
+
+    /// Statistics about the resources of a guild in the cache.
+    pub fn guild(&self, guild_id: GuildId) -> GuildStats {
+        fn len<T>(map: &DashMap<GuildId, HashSet<T>>, guild_id: GuildId) -> usize {
+            map.get(&guild_id).map_or(0, |ids| ids.len())
+        }
+
+        GuildStats {
+            channels: len(&self.0.guild_channels, guild_id),
+            members: len(&self.0.guild_members, guild_id),
+            presences: len(&self.0.guild_presences, guild_id),
+            roles: len(&self.0.guild_roles, guild_id),
+            voice_states: len(&self.0.voice_state_guilds, guild_id),
+        }
+    }
+
+    /// Number of guilds in the cache.
+    pub fn guilds(&self) -> usize {
+        self.0.guilds.len()
+    }
+
+    /// Number of members in the cache, across all guilds.
+    pub fn members(&self) -> usize {
+        self.0.members.len()
+    }
+
+    /// Number of messages in the cache, across all channels.
+    pub fn messages(&self) -> usize {
+        self.0.message_count.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages evicted from the cache by the configured message
+    /// cache sizes, per channel or across all channels.
+    ///
+    /// This only ever increases, including when the cache is cleared, so that
+    /// it can be exported as a counter.
+    pub fn message_evictions(&self) -> usize {
+        self.0.message_evictions.load(Ordering::Relaxed)
+    }
+
+    /// Number of presences in the cache, across all guilds.
+    pub fn presences(&self) -> usize {
+        self.0.presences.len()
+    }
+
+    /// Number of private channels in the cache.
+    pub fn private_channels(&self) -> usize {
+        self.0.channels_private.len()
+    }
+
+    /// Number of roles in the cache.
+    pub fn roles(&self) -> usize {
+        self.0.roles.len()
+    }
+
+    /// Number of guild stickers in the cache.
+    pub fn stickers(&self) -> usize {
+        self.0.stickers.len()
+    }
+
+    /// Number of guilds that are unavailable.
+    pub fn unavailable_guilds(&self) -> usize {
+        self.0.unavailable_guilds.len()
+    }
+
+    /// Number of users in the cache.
+    pub fn users(&self) -> usize {
+        self.0.users.len()
+    }
+
+    /// Number of voice states in the cache, across all guilds.
+    pub fn voice_states(&self) -> usize {
+        self.0.voice_states.len()
+    }
+}
+
+/// Statistics about the resources of a guild in the cache.
+///
+/// Created via [`InMemoryCacheStats::guild`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GuildStats {
+    channels: usize,
+    members: usize,
+    presences: usize,
+    roles: usize,
+    voice_states: usize,
+}
+
+impl GuildStats {
+    /// Number of channels of the guild in the cache.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of members of the guild in the cache.
+    pub fn members(&self) -> usize {
+        self.members
+    }
+
+    /// Number of presences of the guild in the cache.
+    pub fn presences(&self) -> usize {
+        self.presences
+    }
+
+    /// Number of roles of the guild in the cache.
+    pub fn roles(&self) -> usize {
+        self.roles
+    }
+
+    /// Number of voice states of the guild in the cache.
+    pub fn voice_states(&self) -> usize {
+        self.voice_states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GuildStats, InMemoryCacheStats};
+    use crate::{
+        test::{channel, guild_create, member, message},
+        InMemoryCache,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::Channel,
+        gateway::payload::{
+            ChannelDelete, GuildDelete, MemberChunk, MessageCreate, MessageDelete,
+            MessageDeleteBulk,
+        },
+        id::{ChannelId, GuildId, MessageId, UserId},
+    };
+
+    assert_impl_all!(InMemoryCacheStats<'_>: Clone, Copy, Debug, Send, Sync);
+    assert_impl_all!(
+        GuildStats: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    fn cache() -> InMemoryCache {
+        let cache = InMemoryCache::new();
+        cache.update(&guild_create(
+            GuildId(1),
+            vec![channel(ChannelId(10)), channel(ChannelId(11))],
+            vec![member(GuildId(1), UserId(2)), member(GuildId(1), UserId(3))],
+        ));
+        cache.update(&guild_create(
+            GuildId(4),
+            vec![channel(ChannelId(12))],
+            vec![member(GuildId(4), UserId(2))],
+        ));
+
+        cache
+    }
+
+    #[test]
+    fn test_guild_create() {
+        let cache = cache();
+        let stats = cache.stats();
+
+        assert_eq!(2, stats.guilds());
+        assert_eq!(3, stats.channels());
+        assert_eq!(3, stats.members());
+        assert_eq!(2, stats.users());
+        assert_eq!(2, stats.guild(GuildId(1)).channels());
+        assert_eq!(2, stats.guild(GuildId(1)).members());
+        assert_eq!(GuildStats::default(), stats.guild(GuildId(5)));
+    }
+
+    #[test]
+    fn test_guild_delete() {
+        let cache = cache();
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        });
+
+        let stats = cache.stats();
+        assert_eq!(1, stats.guilds());
+        assert_eq!(1, stats.channels());
+        assert_eq!(1, stats.members());
+        // User 3 was only cached in the deleted guild.
+        assert_eq!(1, stats.users());
+        assert_eq!(GuildStats::default(), stats.guild(GuildId(1)));
+        assert_eq!(1, stats.guild(GuildId(4)).members());
+    }
+
+    #[test]
+    fn test_member_chunk() {
+        let cache = cache();
+        cache.update(&MemberChunk {
+            chunk_count: 1,
+            chunk_index: 0,
+            guild_id: GuildId(4),
+            // Member 2 is already cached.
+            members: vec![
+                member(GuildId(4), UserId(2)),
+                member(GuildId(4), UserId(5)),
+                member(GuildId(4), UserId(6)),
+            ],
+            nonce: None,
+            not_found: Vec::new(),
+            presences: Vec::new(),
+        });
+
+        let stats = cache.stats();
+        assert_eq!(5, stats.members());
+        assert_eq!(4, stats.users());
+        assert_eq!(3, stats.guild(GuildId(4)).members());
+    }
+
+    #[test]
+    fn test_messages() {
+        let cache = InMemoryCache::builder().message_cache_size(3).build();
+
+        for id in 1..=4 {
+            cache.update(&MessageCreate(message(ChannelId(10), MessageId(id))));
+        }
+        cache.update(&MessageCreate(message(ChannelId(11), MessageId(5))));
+        // The oldest message of the first channel was evicted.
+        assert_eq!(4, cache.stats().messages());
+
+        cache.update(&MessageDeleteBulk {
+            channel_id: ChannelId(10),
+            guild_id: None,
+            // Message 1 was already evicted.
+            ids: vec![MessageId(1), MessageId(2), MessageId(3)],
+        });
+        assert_eq!(2, cache.stats().messages());
+
+        cache.update(&MessageDelete {
+            channel_id: ChannelId(11),
+            guild_id: None,
+            id: MessageId(5),
+        });
+        assert_eq!(1, cache.stats().messages());
+
+        cache.clear();
+        assert_eq!(0, cache.stats().messages());
+    }
+
+    #[test]
+    fn test_channel_delete() {
+        let cache = InMemoryCache::builder()
+            .message_cache_size(5)
+            .total_message_cache_size(3)
+            .build();
+        cache.update(&guild_create(
+            GuildId(1),
+            vec![channel(ChannelId(10)), channel(ChannelId(11))],
+            Vec::new(),
+        ));
+
+        for id in 1..=2 {
+            cache.update(&MessageCreate(message(ChannelId(10), MessageId(id))));
+        }
+        cache.update(&MessageCreate(message(ChannelId(11), MessageId(3))));
+        assert_eq!(3, cache.stats().messages());
+
+        cache.update(&ChannelDelete(Channel::Guild(channel(ChannelId(10)))));
+        assert_eq!(1, cache.stats().messages());
+        assert!(cache.channel_messages(ChannelId(10)).is_none());
+
+        // The deleted messages no longer take up room in the total message
+        // cache size.
+        for id in 4..=5 {
+            cache.update(&MessageCreate(message(ChannelId(11), MessageId(id))));
+        }
+        assert_eq!(3, cache.stats().messages());
+        assert_eq!(0, cache.stats().message_evictions());
+        assert_eq!(
+            Some(vec![MessageId(5), MessageId(4), MessageId(3)]),
+            cache.channel_messages(ChannelId(11))
+        );
+    }
+}
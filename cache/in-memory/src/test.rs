@@ -0,0 +1,199 @@
+//! Fixtures shared by the tests of the cache.
+
+use twilight_model::{
+    channel::{
+        message::{MessageFlags, MessageType},
+        thread::ThreadMetadata,
+        ChannelType, GuildChannel, Message, TextChannel, ThreadChannel,
+    },
+    gateway::{
+        payload::GuildCreate,
+        presence::{ClientStatus, Presence, Status, UserOrId},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, Member, MfaLevel,
+        Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    user::User,
+};
+
+pub fn channel(id: ChannelId) -> GuildChannel {
+    GuildChannel::Text(TextChannel {
+        guild_id: None,
+        id,
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "test".to_owned(),
+        nsfw: false,
+        parent_id: None,
+        permission_overwrites: Vec::new(),
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    })
+}
+
+pub fn thread(guild_id: GuildId, id: ChannelId, parent_id: ChannelId) -> ThreadChannel {
+    ThreadChannel {
+        guild_id: Some(guild_id),
+        id,
+        kind: ChannelType::GuildPublicThread,
+        last_message_id: None,
+        member: None,
+        member_count: 0,
+        message_count: 0,
+        name: "test".to_owned(),
+        owner_id: None,
+        parent_id: Some(parent_id),
+        rate_limit_per_user: None,
+        thread_metadata: ThreadMetadata {
+            archive_timestamp: "2021-07-01T00:00:00.000000+00:00".to_owned(),
+            archived: false,
+            auto_archive_duration: 60,
+            locked: false,
+        },
+    }
+}
+
+pub fn member(guild_id: GuildId, user_id: UserId) -> Member {
+    Member {
+        deaf: false,
+        guild_id,
+        hoisted_role: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        pending: false,
+        premium_since: None,
+        roles: Vec::new(),
+        user: user(user_id),
+    }
+}
+
+pub fn guild_create(id: GuildId, channels: Vec<GuildChannel>, members: Vec<Member>) -> GuildCreate {
+    GuildCreate(Guild {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        channels,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        emojis: Vec::new(),
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id,
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: None,
+        members,
+        mfa_level: MfaLevel::None,
+        name: "test".to_owned(),
+        owner: None,
+        owner_id: UserId(1),
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        presences: Vec::new(),
+        region: "us-east".to_owned(),
+        roles: Vec::new(),
+        rules_channel_id: None,
+        splash: None,
+        stickers: Vec::new(),
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        voice_states: Vec::new(),
+        widget_channel_id: None,
+        widget_enabled: None,
+    })
+}
+
+pub fn message(channel_id: ChannelId, id: MessageId) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: Vec::new(),
+        author: user(UserId(1)),
+        channel_id,
+        content: "ping".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: Some(MessageFlags::empty()),
+        guild_id: None,
+        id,
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: Vec::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        referenced_message: None,
+        timestamp: String::new(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+pub fn presence(guild_id: GuildId, user_id: UserId, status: Status) -> Presence {
+    Presence {
+        activities: Vec::new(),
+        client_status: ClientStatus {
+            desktop: Some(status),
+            mobile: None,
+            web: None,
+        },
+        guild_id,
+        status,
+        user: UserOrId::UserId { id: user_id },
+    }
+}
+
+pub fn role(id: RoleId, permissions: Permissions) -> Role {
+    Role {
+        color: 0,
+        hoist: false,
+        id,
+        managed: false,
+        mentionable: false,
+        name: "test".to_owned(),
+        permissions,
+        position: 0,
+        tags: None,
+    }
+}
+
+pub fn user(id: UserId) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id,
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
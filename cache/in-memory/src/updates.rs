@@ -1,17 +1,105 @@
-use super::{config::ResourceType, InMemoryCache};
-use dashmap::DashMap;
-use std::{borrow::Cow, collections::HashSet, hash::Hash, ops::Deref, sync::Arc};
+use super::{
+    config::ResourceType,
+    model::{CachedMember, CachedMessage},
+    InMemoryCache,
+};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+use std::{borrow::Cow, mem, ops::Deref, sync::Arc};
 use twilight_model::{
-    channel::{message::MessageReaction, Channel, GuildChannel, ReactionType},
+    channel::{
+        message::MessageReaction, Channel, ChannelType, GuildChannel, Message, PrivateChannel,
+        ReactionType, ThreadChannel,
+    },
     gateway::{event::Event, payload::*, presence::Presence},
-    guild::GuildStatus,
-    id::GuildId,
+    guild::{Guild, GuildStatus, Member, Role},
+    id::{ChannelId, GuildId, MessageId},
 };
 
+/// Resource fetched over HTTP that doesn't contain the ID of its guild,
+/// along with that ID so that it can be cached.
+///
+/// # Examples
+///
+/// Cache the roles of a guild fetched over HTTP:
+///
+/// ```
+/// use twilight_cache_inmemory::{InGuild, InMemoryCache};
+/// use twilight_model::{guild::Role, id::GuildId};
+///
+/// fn cache_roles(cache: &InMemoryCache, guild_id: GuildId, roles: Vec<Role>) {
+///     cache.update(&InGuild::new(guild_id, roles));
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InGuild<T> {
+    guild_id: GuildId,
+    resource: T,
+}
+
+impl<T> InGuild<T> {
+    /// Create a new resource along with the ID of its guild.
+    pub fn new(guild_id: GuildId, resource: T) -> Self {
+        Self { guild_id, resource }
+    }
+
+    /// ID of the guild.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+
+    /// Resource.
+    pub fn resource(&self) -> &T {
+        &self.resource
+    }
+}
+
+/// Resources removed or replaced in the cache by an update.
+///
+/// Returned by [`InMemoryCache::update_with_result`], so that the state of a
+/// resource before its deletion or edit, such as the content of a deleted
+/// message, can be inspected.
+///
+/// [`InMemoryCache::update_with_result`]: crate::InMemoryCache::update_with_result
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UpdateResult {
+    /// Channel removed by a channel delete.
+    ChannelDelete(Channel),
+    /// Member removed by a member remove.
+    MemberRemove(Arc<CachedMember>),
+    /// Message removed by a message delete.
+    MessageDelete(Arc<CachedMessage>),
+    /// Messages removed by a bulk message delete.
+    ///
+    /// Messages that weren't cached are skipped.
+    MessageDeleteBulk(Vec<Arc<CachedMessage>>),
+    /// Message as it was cached before a message update was applied to it.
+    MessageUpdate(Arc<CachedMessage>),
+    /// Thread removed by a thread delete.
+    ///
+    /// The thread member of the current user in it is removed as well.
+    ThreadDelete(ThreadChannel),
+    /// No resource was removed, either because the update isn't a deletion or
+    /// because the removed resource wasn't cached.
+    None,
+}
+
 pub trait UpdateCache {
     // Allow this for presentation purposes in documentation.
     #[allow(unused_variables)]
     fn update(&self, cache: &InMemoryCache) {}
+
+    /// Update the cache, returning the resources removed from it.
+    ///
+    /// Defaults to calling [`update`] and returning [`UpdateResult::None`].
+    ///
+    /// [`update`]: Self::update
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
+        self.update(cache);
+
+        UpdateResult::None
+    }
 }
 
 impl UpdateCache for Event {
@@ -19,6 +107,9 @@ impl UpdateCache for Event {
     fn update(&self, c: &InMemoryCache) {
         use Event::*;
 
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+
         match self {
             BanAdd(_) => {}
             BanRemove(_) => {}
@@ -36,7 +127,9 @@ impl UpdateCache for Event {
             GuildDelete(v) => c.update(v.deref()),
             GuildEmojisUpdate(v) => c.update(v),
             GuildIntegrationsUpdate(v) => c.update(v),
+            GuildStickersUpdate(v) => c.update(v),
             GuildUpdate(v) => c.update(v.deref()),
+            InteractionCreate(_) => {}
             InviteCreate(_) => {}
             InviteDelete(_) => {}
             MemberAdd(v) => c.update(v.deref()),
@@ -65,6 +158,13 @@ impl UpdateCache for Event {
             ShardReconnecting(_) => {}
             ShardPayload(_) => {}
             ShardResuming(_) => {}
+            ShardStopped(_) => {}
+            ThreadCreate(v) => c.update(v),
+            ThreadDelete(v) => c.update(v),
+            ThreadListSync(v) => c.update(v),
+            ThreadMemberUpdate(v) => c.update(v),
+            ThreadMembersUpdate(v) => c.update(v),
+            ThreadUpdate(v) => c.update(v),
             TypingStart(v) => c.update(v.deref()),
             UnavailableGuild(v) => c.update(v),
             UserUpdate(v) => c.update(v),
@@ -72,6 +172,33 @@ impl UpdateCache for Event {
             VoiceStateUpdate(v) => c.update(v.deref()),
             WebhooksUpdate(v) => c.update(v),
         }
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_update(c, self.kind(), started.elapsed());
+    }
+
+    fn update_with_result(&self, c: &InMemoryCache) -> UpdateResult {
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+
+        let result = match self {
+            Event::ChannelDelete(v) => c.update_with_result(v),
+            Event::MemberRemove(v) => c.update_with_result(v),
+            Event::MessageDelete(v) => c.update_with_result(v),
+            Event::MessageDeleteBulk(v) => c.update_with_result(v),
+            Event::MessageUpdate(v) => c.update_with_result(v.deref()),
+            Event::ThreadDelete(v) => c.update_with_result(v),
+            _ => {
+                self.update(c);
+
+                return UpdateResult::None;
+            }
+        };
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_update(c, self.kind(), started.elapsed());
+
+        result
     }
 }
 
@@ -81,43 +208,33 @@ impl UpdateCache for BanRemove {}
 
 impl UpdateCache for ChannelCreate {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::CHANNEL) {
-            return;
-        }
-
-        match &self.0 {
-            Channel::Group(c) => {
-                super::upsert_item(&cache.0.groups, c.id, c.clone());
-            }
-            Channel::Guild(c) => {
-                if let Some(gid) = c.guild_id() {
-                    cache.cache_guild_channel(gid, c.clone());
-                }
-            }
-            Channel::Private(c) => {
-                cache.cache_private_channel(c.clone());
-            }
-        }
+        self.0.update(cache);
     }
 }
 
 impl UpdateCache for ChannelDelete {
     fn update(&self, cache: &InMemoryCache) {
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
         if !cache.wants(ResourceType::CHANNEL) {
-            return;
+            return UpdateResult::None;
         }
 
-        match self.0 {
-            Channel::Group(ref c) => {
-                cache.delete_group(c.id);
-            }
-            Channel::Guild(ref c) => {
-                cache.delete_guild_channel(c.id());
-            }
-            Channel::Private(ref c) => {
-                cache.0.channels_private.remove(&c.id);
-            }
-        }
+        let channel = match self.0 {
+            Channel::Group(ref c) => cache
+                .delete_group(c.id)
+                .map(|group| Channel::Group((*group).clone())),
+            Channel::Guild(ref c) => cache
+                .delete_guild_channel(c.id())
+                .map(|channel| Channel::Guild((*channel).clone())),
+            Channel::Private(ref c) => cache
+                .delete_private_channel(c.id)
+                .map(|channel| Channel::Private((*channel).clone())),
+        };
+
+        channel.map_or(UpdateResult::None, UpdateResult::ChannelDelete)
     }
 }
 
@@ -151,23 +268,7 @@ impl UpdateCache for ChannelPinsUpdate {
 
 impl UpdateCache for ChannelUpdate {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::CHANNEL) {
-            return;
-        }
-
-        match self.0.clone() {
-            Channel::Group(c) => {
-                cache.cache_group(c);
-            }
-            Channel::Guild(c) => {
-                if let Some(gid) = c.guild_id() {
-                    cache.cache_guild_channel(gid, c);
-                }
-            }
-            Channel::Private(c) => {
-                cache.cache_private_channel(c);
-            }
-        }
+        self.0.update(cache);
     }
 }
 
@@ -183,58 +284,10 @@ impl UpdateCache for GuildCreate {
 
 impl UpdateCache for GuildDelete {
     fn update(&self, cache: &InMemoryCache) {
-        fn remove_ids<T: Eq + Hash, U>(
-            guild_map: &DashMap<GuildId, HashSet<T>>,
-            container: &DashMap<T, U>,
-            guild_id: GuildId,
-        ) {
-            if let Some((_, ids)) = guild_map.remove(&guild_id) {
-                for id in ids {
-                    container.remove(&id);
-                }
-            }
-        }
-
-        if !cache.wants(ResourceType::GUILD) {
-            return;
-        }
-
-        let id = self.id;
-
-        cache.0.guilds.remove(&id);
-
-        if cache.wants(ResourceType::CHANNEL) {
-            remove_ids(&cache.0.guild_channels, &cache.0.channels_guild, id);
-        }
-
-        if cache.wants(ResourceType::EMOJI) {
-            remove_ids(&cache.0.guild_emojis, &cache.0.emojis, id);
-        }
-
-        if cache.wants(ResourceType::ROLE) {
-            remove_ids(&cache.0.guild_roles, &cache.0.roles, id);
-        }
-
-        if cache.wants(ResourceType::VOICE_STATE) {
-            // Clear out a guilds voice states when a guild leaves
-            cache.0.voice_state_guilds.remove(&id);
-        }
-
-        if cache.wants(ResourceType::MEMBER) {
-            if let Some((_, ids)) = cache.0.guild_members.remove(&id) {
-                for user_id in ids {
-                    cache.0.members.remove(&(id, user_id));
-                }
-            }
-        }
-
-        if cache.wants(ResourceType::PRESENCE) {
-            if let Some((_, ids)) = cache.0.guild_presences.remove(&id) {
-                for user_id in ids {
-                    cache.0.presences.remove(&(id, user_id));
-                }
-            }
-        }
+        // Resources are removed regardless of the configured resource types,
+        // since they may have been cached through other events.
+        cache.0.guilds.remove(&self.id);
+        cache.wipe_guild(self.id);
     }
 }
 
@@ -250,6 +303,16 @@ impl UpdateCache for GuildEmojisUpdate {
 
 impl UpdateCache for GuildIntegrationsUpdate {}
 
+impl UpdateCache for GuildStickersUpdate {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::STICKER) {
+            return;
+        }
+
+        cache.replace_stickers(self.guild_id, self.stickers.clone());
+    }
+}
+
 impl UpdateCache for GuildUpdate {
     fn update(&self, cache: &InMemoryCache) {
         if !cache.wants(ResourceType::GUILD) {
@@ -290,7 +353,11 @@ impl UpdateCache for GuildUpdate {
 
 impl UpdateCache for MemberAdd {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::MEMBER) {
+        if cache.wants(ResourceType::GUILD) {
+            cache.update_member_count(self.guild_id, |count| count.saturating_add(1));
+        }
+
+        if !cache.wants_member(self.0.user.id) {
             return;
         }
 
@@ -307,54 +374,54 @@ impl UpdateCache for MemberAdd {
 
 impl UpdateCache for MemberChunk {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::MEMBER) {
-            return;
-        }
-
-        if self.members.is_empty() {
-            return;
-        }
+        let members = self
+            .members
+            .iter()
+            .filter(|member| cache.wants_member(member.user.id))
+            .cloned();
 
-        cache.cache_members(self.guild_id, self.members.clone());
-        let mut guild = cache.0.guild_members.entry(self.guild_id).or_default();
-        guild.extend(self.members.iter().map(|member| member.user.id));
+        // Members are indexed by their guild as they're cached.
+        cache.cache_members(self.guild_id, members);
     }
 }
 
 impl UpdateCache for MemberRemove {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::MEMBER) {
-            return;
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
+        // The count includes members that aren't cached, so it's decremented
+        // even if the member isn't cached.
+        if cache.wants(ResourceType::GUILD) {
+            cache.update_member_count(self.guild_id, |count| count.saturating_sub(1));
+        }
+
+        if !cache.wants_member(self.user.id) {
+            return UpdateResult::None;
         }
 
-        cache.0.members.remove(&(self.guild_id, self.user.id));
+        let member = cache.0.members.remove(&(self.guild_id, self.user.id));
 
         if let Some(mut members) = cache.0.guild_members.get_mut(&self.guild_id) {
             members.remove(&self.user.id);
         }
 
-        // Avoid a deadlock by mutating the user, dropping the lock to the map,
-        // and then maybe conditionally removing the user later.
-        let mut maybe_remove_user = false;
+        // Resources of the user that are scoped to the guild are removed
+        // along with the member.
+        cache.delete_presence(self.guild_id, self.user.id);
+        cache.delete_voice_state(self.guild_id, self.user.id);
+        cache.unlink_user(self.guild_id, self.user.id);
 
-        if let Some(mut user_tuple) = cache.0.users.get_mut(&self.user.id) {
-            user_tuple.1.remove(&self.guild_id);
-
-            maybe_remove_user = true;
-        }
-
-        if maybe_remove_user {
-            cache
-                .0
-                .users
-                .remove_if(&self.user.id, |_, guild_set| guild_set.1.is_empty());
-        }
+        member.map_or(UpdateResult::None, |(_, member)| {
+            UpdateResult::MemberRemove(member)
+        })
     }
 }
 
 impl UpdateCache for MemberUpdate {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::MEMBER) {
+        if !cache.wants_member(self.user.id) {
             return;
         }
 
@@ -373,63 +440,67 @@ impl UpdateCache for MemberUpdate {
 
 impl UpdateCache for MessageCreate {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.wants(ResourceType::MESSAGE) {
-            return;
-        }
-
-        let mut channel = cache.0.messages.entry(self.0.channel_id).or_default();
-
-        if channel.len() > cache.0.config.message_cache_size() {
-            if let Some(k) = channel.iter().next_back().map(|x| *x.0) {
-                channel.remove(&k);
-            }
-        }
-
-        channel.insert(self.0.id, Arc::new(From::from(self.0.clone())));
-
-        let user = cache.cache_user(Cow::Borrowed(&self.author), self.guild_id);
-
-        if let (Some(member), Some(guild_id)) = (&self.member, self.guild_id) {
-            cache.cache_borrowed_partial_member(guild_id, member, user);
-        }
+        self.0.update(cache);
     }
 }
 
 impl UpdateCache for MessageDelete {
     fn update(&self, cache: &InMemoryCache) {
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
         if !cache.wants(ResourceType::MESSAGE) {
-            return;
+            return UpdateResult::None;
         }
 
-        let mut channel = cache.0.messages.entry(self.channel_id).or_default();
-        channel.remove(&self.id);
+        match cache.remove_messages(self.channel_id, &[self.id]).pop() {
+            Some(message) => UpdateResult::MessageDelete(message),
+            None => UpdateResult::None,
+        }
     }
 }
 
 impl UpdateCache for MessageDeleteBulk {
     fn update(&self, cache: &InMemoryCache) {
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
         if !cache.wants(ResourceType::MESSAGE) {
-            return;
+            return UpdateResult::None;
         }
 
-        let mut channel = cache.0.messages.entry(self.channel_id).or_default();
+        let messages = cache.remove_messages(self.channel_id, &self.ids);
 
-        for id in &self.ids {
-            channel.remove(id);
+        if messages.is_empty() {
+            return UpdateResult::None;
         }
+
+        UpdateResult::MessageDeleteBulk(messages)
     }
 }
 
 impl UpdateCache for MessageUpdate {
     fn update(&self, cache: &InMemoryCache) {
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
         if !cache.wants(ResourceType::MESSAGE) {
-            return;
+            return UpdateResult::None;
         }
 
-        let mut channel = cache.0.messages.entry(self.channel_id).or_default();
+        let mut channel = match cache.0.messages.get_mut(&self.channel_id) {
+            Some(channel) => channel,
+            None => return UpdateResult::None,
+        };
 
-        if let Some(mut message) = channel.get_mut(&self.id) {
-            let mut msg = Arc::make_mut(&mut message);
+        if let Some(message) = channel.get_mut(&self.id) {
+            // Keep a reference to the previous message, so that it's cloned
+            // instead of being edited in place.
+            let previous = Arc::clone(message);
+            let msg = Arc::make_mut(message);
 
             if let Some(attachments) = &self.attachments {
                 msg.attachments = attachments.clone();
@@ -470,7 +541,11 @@ impl UpdateCache for MessageUpdate {
             if let Some(tts) = self.tts {
                 msg.tts = tts;
             }
+
+            return UpdateResult::MessageUpdate(previous);
         }
+
+        UpdateResult::None
     }
 }
 
@@ -638,12 +713,7 @@ impl UpdateCache for RoleCreate {
             return;
         }
 
-        super::upsert_guild_item(
-            &cache.0.roles,
-            self.guild_id,
-            self.role.id,
-            self.role.clone(),
-        );
+        cache.cache_role(self.guild_id, self.role.clone());
     }
 }
 
@@ -667,6 +737,106 @@ impl UpdateCache for RoleUpdate {
     }
 }
 
+impl UpdateCache for ThreadCreate {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        if let Some(guild_id) = self.0.guild_id {
+            cache.cache_guild_channel(guild_id, GuildChannel::Thread(self.0.clone()));
+        }
+    }
+}
+
+impl UpdateCache for ThreadDelete {
+    fn update(&self, cache: &InMemoryCache) {
+        self.update_with_result(cache);
+    }
+
+    fn update_with_result(&self, cache: &InMemoryCache) -> UpdateResult {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return UpdateResult::None;
+        }
+
+        match cache.delete_guild_channel(self.id).as_deref() {
+            Some(GuildChannel::Thread(thread)) => UpdateResult::ThreadDelete(thread.clone()),
+            _ => UpdateResult::None,
+        }
+    }
+}
+
+impl UpdateCache for ThreadListSync {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        cache.replace_threads(
+            self.guild_id,
+            self.channel_ids.as_deref(),
+            self.threads.iter().cloned().map(GuildChannel::Thread),
+            self.members.clone(),
+        );
+    }
+}
+
+impl UpdateCache for ThreadMemberUpdate {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        if let Some(thread_id) = self.0.id {
+            cache.cache_thread_member(thread_id, self.0.clone());
+        }
+    }
+}
+
+impl UpdateCache for ThreadMembersUpdate {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        if let Some(mut item) = cache.0.channels_guild.get_mut(&self.id) {
+            if let GuildChannel::Thread(thread) = Arc::make_mut(&mut item.data) {
+                thread.member_count = self.member_count;
+            }
+        }
+
+        // Only the thread member of the current user is cached.
+        let current_user_id = match cache.current_user() {
+            Some(user) => user.id,
+            None => return,
+        };
+
+        if self.removed_member_ids.contains(&current_user_id) {
+            cache.0.thread_members.remove(&self.id);
+        }
+
+        if let Some(member) = self
+            .added_members
+            .iter()
+            .find(|member| member.user_id == Some(current_user_id))
+        {
+            cache.cache_thread_member(self.id, member.clone());
+        }
+    }
+}
+
+impl UpdateCache for ThreadUpdate {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        if let Some(guild_id) = self.0.guild_id {
+            cache.cache_guild_channel(guild_id, GuildChannel::Thread(self.0.clone()));
+        }
+    }
+}
+
 impl UpdateCache for TypingStart {}
 
 impl UpdateCache for UnavailableGuild {
@@ -703,54 +873,310 @@ impl UpdateCache for VoiceStateUpdate {
         cache.cache_voice_state(self.0.clone());
 
         if let (Some(guild_id), Some(member)) = (self.0.guild_id, &self.0.member) {
-            cache.cache_member(guild_id, member.clone());
+            if cache.wants_member(member.user.id) {
+                cache.cache_member(guild_id, member.clone());
+            }
         }
     }
 }
 
 impl UpdateCache for WebhooksUpdate {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::ResourceType;
-    use twilight_model::{
-        channel::{
-            message::{MessageFlags, MessageType},
-            ChannelType, GuildChannel, Message, Reaction, TextChannel,
-        },
-        gateway::payload::{reaction_remove_emoji::PartialEmoji, ChannelDelete},
-        guild::{
-            DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, Member, MfaLevel,
-            PartialGuild, PartialMember, PremiumTier, SystemChannelFlags, VerificationLevel,
-        },
-        id::{ChannelId, GuildId, MessageId, UserId},
-        user::User,
-        voice::VoiceState,
-    };
-
-    fn guild_channel_text() -> (GuildId, ChannelId, GuildChannel) {
-        let guild_id = GuildId(1);
-        let channel_id = ChannelId(2);
-        let channel = GuildChannel::Text(TextChannel {
-            guild_id: Some(guild_id),
-            id: channel_id,
-            kind: ChannelType::GuildText,
-            last_message_id: None,
-            last_pin_timestamp: None,
-            name: "test".to_owned(),
-            nsfw: false,
-            parent_id: None,
-            permission_overwrites: Vec::new(),
-            position: 3,
-            rate_limit_per_user: None,
-            topic: None,
-        });
+// Models fetched over HTTP.
 
-        (guild_id, channel_id, channel)
-    }
+impl UpdateCache for Channel {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
 
-    fn cache_with_message_and_reactions() -> InMemoryCache {
+        match self.clone() {
+            Channel::Group(c) => {
+                cache.cache_group(c);
+            }
+            Channel::Guild(c) => {
+                if let Some(gid) = c.guild_id() {
+                    cache.cache_guild_channel(gid, c);
+                }
+            }
+            Channel::Private(c) => {
+                cache.cache_private_channel(c);
+            }
+        }
+    }
+}
+
+impl UpdateCache for Guild {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::GUILD) {
+            return;
+        }
+
+        let mut guild = self.clone();
+
+        // Guilds fetched over HTTP don't contain their channels, members,
+        // presences, and voice states, nor the fields only sent over the
+        // gateway, so those already cached are kept.
+        if let Some(cached) = cache.0.guilds.get(&guild.id) {
+            guild.joined_at = guild.joined_at.or_else(|| cached.joined_at.clone());
+            guild.large |= cached.large;
+            guild.lazy = guild.lazy.or(cached.lazy);
+            guild.member_count = guild.member_count.or(cached.member_count);
+            guild.unavailable |= cached.unavailable;
+        }
+
+        if cache.wants(ResourceType::EMOJI) {
+            cache.replace_emojis(guild.id, mem::take(&mut guild.emojis));
+        }
+
+        if cache.wants(ResourceType::ROLE) {
+            cache.replace_roles(guild.id, mem::take(&mut guild.roles));
+        }
+
+        if cache.wants(ResourceType::STICKER) {
+            cache.replace_stickers(guild.id, mem::take(&mut guild.stickers));
+        }
+
+        cache.insert_guild(guild);
+    }
+}
+
+impl UpdateCache for InGuild<GuildChannel> {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::CHANNEL) {
+            return;
+        }
+
+        cache.cache_guild_channel(self.guild_id, self.resource.clone());
+    }
+}
+
+impl UpdateCache for InGuild<Role> {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::ROLE) {
+            return;
+        }
+
+        cache.cache_role(self.guild_id, self.resource.clone());
+    }
+}
+
+/// Replaces all of the cached roles of the guild, since the list of a
+/// guild's roles is always complete.
+impl UpdateCache for InGuild<Vec<Role>> {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants(ResourceType::ROLE) {
+            return;
+        }
+
+        cache.replace_roles(self.guild_id, self.resource.clone());
+    }
+}
+
+impl UpdateCache for Member {
+    fn update(&self, cache: &InMemoryCache) {
+        if !cache.wants_member(self.user.id) {
+            return;
+        }
+
+        cache.cache_member(self.guild_id, self.clone());
+    }
+}
+
+impl UpdateCache for Message {
+    fn update(&self, cache: &InMemoryCache) {
+        // Channel creates aren't always sent for private channels, so they're
+        // also cached from messages sent to the current user outside of
+        // guilds.
+        if self.guild_id.is_none()
+            && cache.wants(ResourceType::CHANNEL)
+            && !cache.0.channels_private.contains_key(&self.channel_id)
+            && !cache.0.groups.contains_key(&self.channel_id)
+            && cache.current_user().map(|user| user.id) != Some(self.author.id)
+        {
+            cache.cache_private_channel(PrivateChannel {
+                id: self.channel_id,
+                last_message_id: Some(self.id),
+                last_pin_timestamp: None,
+                kind: ChannelType::Private,
+                recipients: vec![self.author.clone()],
+            });
+        }
+
+        if cache.wants(ResourceType::CHANNEL) {
+            update_last_message_id(cache, self.channel_id, self.id);
+        }
+
+        if !cache.wants(ResourceType::MESSAGE) {
+            return;
+        }
+
+        let mut message = CachedMessage::from(self.clone());
+
+        if !cache.wants(ResourceType::STICKER) {
+            message.stickers.clear();
+        }
+
+        cache.cache_message(message);
+
+        match (&self.member, self.guild_id) {
+            // The author of a message is a partial member, which only fills
+            // in the fields missing from an already cached member. Members
+            // are cached along with their users.
+            (Some(member), Some(guild_id)) if cache.wants_member(self.author.id) => {
+                let user = cache.cache_user(Cow::Borrowed(&self.author), Some(guild_id));
+                cache.cache_borrowed_partial_member(guild_id, member, user);
+            }
+            _ if cache.wants(ResourceType::USER) => {
+                cache.cache_user(Cow::Borrowed(&self.author), self.guild_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Advance the ID of the last message of a cached channel to a message sent
+/// in it.
+///
+/// Deleting the last message of a channel doesn't change its last message ID,
+/// which is also how Discord reports it, so the ID is only ever advanced.
+fn update_last_message_id(cache: &InMemoryCache, channel_id: ChannelId, message_id: MessageId) {
+    let is_newer =
+        |last_message_id: Option<MessageId>| last_message_id.map_or(true, |id| id < message_id);
+
+    if let Some(mut item) = cache.0.channels_guild.get_mut(&channel_id) {
+        let last_message_id = match *item.data {
+            GuildChannel::Text(ref text) => text.last_message_id,
+            GuildChannel::Thread(ref thread) => thread.last_message_id,
+            GuildChannel::Category(_) | GuildChannel::Voice(_) => return,
+        };
+
+        if !is_newer(last_message_id) {
+            return;
+        }
+
+        match Arc::make_mut(&mut item.data) {
+            GuildChannel::Text(text) => text.last_message_id = Some(message_id),
+            GuildChannel::Thread(thread) => thread.last_message_id = Some(message_id),
+            GuildChannel::Category(_) | GuildChannel::Voice(_) => {}
+        }
+
+        return;
+    }
+
+    if let Some(mut channel) = cache.0.channels_private.get_mut(&channel_id) {
+        if is_newer(channel.last_message_id) {
+            Arc::make_mut(&mut channel).last_message_id = Some(message_id);
+        }
+
+        return;
+    }
+
+    if let Some(mut group) = cache.0.groups.get_mut(&channel_id) {
+        if is_newer(group.last_message_id) {
+            Arc::make_mut(&mut group).last_message_id = Some(message_id);
+        }
+    }
+}
+
+impl UpdateCache for Vec<Member> {
+    fn update(&self, cache: &InMemoryCache) {
+        for member in self {
+            if cache.wants_member(member.user.id) {
+                cache.cache_member(member.guild_id, member.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ResourceType;
+    use std::{collections::HashSet, hash::Hash};
+    use twilight_model::{
+        channel::{
+            embed::Embed,
+            message::{MessageFlags, MessageType},
+            thread::ThreadMember,
+            Attachment, ChannelType, GuildChannel, Message, Reaction, TextChannel,
+        },
+        gateway::payload::{reaction_remove_emoji::PartialEmoji, ChannelDelete},
+        guild::{
+            DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, Member, MfaLevel,
+            PartialGuild, PartialMember, PremiumTier, SystemChannelFlags, VerificationLevel,
+        },
+        id::{AttachmentId, ChannelId, GuildId, MessageId, UserId},
+        user::{CurrentUser, User},
+        voice::VoiceState,
+    };
+
+    fn guild_channel_text() -> (GuildId, ChannelId, GuildChannel) {
+        let guild_id = GuildId(1);
+        let channel_id = ChannelId(2);
+        let channel = GuildChannel::Text(TextChannel {
+            guild_id: Some(guild_id),
+            id: channel_id,
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "test".to_owned(),
+            nsfw: false,
+            parent_id: None,
+            permission_overwrites: Vec::new(),
+            position: 3,
+            rate_limit_per_user: None,
+            topic: None,
+        });
+
+        (guild_id, channel_id, channel)
+    }
+
+    fn message(id: MessageId) -> Message {
+        Message {
+            activity: None,
+            application: None,
+            attachments: Vec::new(),
+            author: User {
+                avatar: None,
+                bot: false,
+                discriminator: "0001".to_owned(),
+                email: None,
+                flags: None,
+                id: UserId(3),
+                locale: None,
+                mfa_enabled: None,
+                name: "test".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            },
+            channel_id: ChannelId(2),
+            content: "ping".to_owned(),
+            edited_timestamp: None,
+            embeds: Vec::new(),
+            flags: Some(MessageFlags::empty()),
+            guild_id: None,
+            id,
+            kind: MessageType::Regular,
+            member: None,
+            mention_channels: Vec::new(),
+            mention_everyone: false,
+            mention_roles: Vec::new(),
+            mentions: Vec::new(),
+            pinned: false,
+            reactions: Vec::new(),
+            reference: None,
+            stickers: Vec::new(),
+            referenced_message: None,
+            timestamp: String::new(),
+            tts: false,
+            webhook_id: None,
+        }
+    }
+
+    fn cache_with_message_and_reactions() -> InMemoryCache {
         let cache = InMemoryCache::new();
 
         let msg = Message {
@@ -921,6 +1347,7 @@ mod tests {
             roles: Vec::new(),
             rules_channel_id: None,
             splash: None,
+            stickers: Vec::new(),
             system_channel_flags: SystemChannelFlags::empty(),
             system_channel_id: None,
             unavailable: false,
@@ -1032,6 +1459,43 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_voice_states_without_members() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::VOICE_STATE)
+            .build();
+
+        cache.update(&VoiceStateUpdate(VoiceState {
+            channel_id: Some(ChannelId(4)),
+            deaf: false,
+            guild_id: Some(GuildId(2)),
+            member: Some(Member {
+                deaf: false,
+                guild_id: GuildId(2),
+                hoisted_role: None,
+                joined_at: None,
+                mute: false,
+                nick: None,
+                pending: false,
+                premium_since: None,
+                roles: Vec::new(),
+                user: message(MessageId(1)).author,
+            }),
+            mute: false,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: false,
+            session_id: "".to_owned(),
+            suppress: false,
+            token: None,
+            user_id: UserId(3),
+        }));
+
+        assert!(cache.voice_state(UserId(3), GuildId(2)).is_some());
+        assert!(cache.member(GuildId(2), UserId(3)).is_none());
+        assert!(cache.user(UserId(3)).is_none());
+    }
+
     #[test]
     fn test_voice_states_members() {
         use twilight_model::{guild::member::Member, user::User};
@@ -1094,7 +1558,7 @@ mod tests {
     #[test]
     fn test_message_create() {
         let cache = InMemoryCache::builder()
-            .resource_types(ResourceType::MESSAGE)
+            .resource_types(ResourceType::MEMBER | ResourceType::MESSAGE | ResourceType::USER)
             .message_cache_size(1)
             .build();
         let msg = Message {
@@ -1162,6 +1626,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_create_only_messages() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::MESSAGE)
+            .build();
+        let mut message = message(MessageId(4));
+        message.guild_id = Some(GuildId(1));
+        message.member = Some(PartialMember {
+            deaf: false,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            roles: Vec::new(),
+        });
+
+        cache.update(&MessageCreate(message.clone()));
+        assert!(cache.message(ChannelId(2), MessageId(4)).is_some());
+        assert!(cache.user(UserId(3)).is_none());
+        assert!(cache.member(GuildId(1), UserId(3)).is_none());
+
+        // Users are cached without members if only users are enabled.
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::MESSAGE | ResourceType::USER)
+            .build();
+        cache.update(&MessageCreate(message));
+        assert!(cache.user(UserId(3)).is_some());
+        assert!(cache.member(GuildId(1), UserId(3)).is_none());
+    }
+
     #[test]
     fn test_reaction_add() {
         let cache = cache_with_message_and_reactions();
@@ -1261,4 +1755,1316 @@ mod tests {
 
         assert_eq!(msg.reactions.len(), 0);
     }
+
+    #[test]
+    fn test_presence_update_not_wanted() {
+        use twilight_model::gateway::presence::{ClientStatus, Status, UserOrId};
+
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::all() - ResourceType::PRESENCE)
+            .build();
+
+        cache.update(&PresenceUpdate {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: Some(Status::Online),
+                mobile: None,
+                web: None,
+            },
+            game: None,
+            guild_id: GuildId(1),
+            status: Status::Online,
+            user: UserOrId::UserId { id: UserId(2) },
+        });
+
+        assert!(cache.0.presences.is_empty());
+        assert!(cache.guild_presences(GuildId(1)).is_none());
+        assert!(cache.presence(GuildId(1), UserId(2)).is_none());
+    }
+
+    #[test]
+    fn test_guild_delete_without_guilds() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::CHANNEL)
+            .build();
+        let (guild_id, channel_id, channel) = guild_channel_text();
+
+        cache.update(&ChannelCreate(Channel::Guild(channel)));
+        assert!(cache.guild(guild_id).is_none());
+        assert!(cache.guild_channel(channel_id).is_some());
+
+        cache.update(&GuildDelete {
+            id: guild_id,
+            unavailable: false,
+        });
+        assert!(cache.0.channels_guild.is_empty());
+        assert!(cache.guild_channels(guild_id).is_none());
+    }
+
+    #[test]
+    fn test_message_cache_size_evicts_oldest() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::MESSAGE)
+            .message_cache_size(3)
+            .build();
+
+        for id in 1..=5 {
+            cache.update(&MessageCreate(message(MessageId(id))));
+        }
+
+        assert_eq!(
+            Some(vec![MessageId(5), MessageId(4), MessageId(3)]),
+            cache.channel_messages(ChannelId(2))
+        );
+        assert!(cache.message(ChannelId(2), MessageId(2)).is_none());
+
+        cache.update(&MessageDelete {
+            channel_id: ChannelId(2),
+            guild_id: None,
+            id: MessageId(4),
+        });
+        assert_eq!(
+            Some(vec![MessageId(5), MessageId(3)]),
+            cache.channel_messages(ChannelId(2))
+        );
+
+        cache.update(&MessageCreate(message(MessageId(6))));
+        cache.update(&MessageCreate(message(MessageId(7))));
+        assert_eq!(
+            Some(vec![MessageId(7), MessageId(6), MessageId(5)]),
+            cache.channel_messages(ChannelId(2))
+        );
+
+        cache.update(&MessageDeleteBulk {
+            channel_id: ChannelId(2),
+            guild_id: None,
+            ids: vec![MessageId(5), MessageId(7)],
+        });
+        assert_eq!(
+            Some(vec![MessageId(6)]),
+            cache.channel_messages(ChannelId(2))
+        );
+    }
+
+    #[test]
+    fn test_total_message_cache_size_evicts_across_channels() {
+        fn message_in(channel_id: u64, id: u64) -> MessageCreate {
+            let mut message = message(MessageId(id));
+            message.channel_id = ChannelId(channel_id);
+
+            MessageCreate(message)
+        }
+
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::MESSAGE)
+            .message_cache_size(2)
+            .total_message_cache_size(3)
+            .build();
+
+        cache.update(&message_in(10, 1));
+        cache.update(&message_in(20, 2));
+        cache.update(&message_in(10, 3));
+        cache.update(&message_in(20, 4));
+        // The least recently cached message is evicted, regardless of its
+        // channel.
+        assert_eq!(
+            Some(vec![MessageId(3)]),
+            cache.channel_messages(ChannelId(10))
+        );
+        assert_eq!(
+            Some(vec![MessageId(4), MessageId(2)]),
+            cache.channel_messages(ChannelId(20))
+        );
+
+        cache.update(&message_in(10, 5));
+        assert_eq!(
+            Some(vec![MessageId(4)]),
+            cache.channel_messages(ChannelId(20))
+        );
+        assert_eq!(2, cache.stats().message_evictions());
+
+        // Deleted messages no longer count towards the total.
+        cache.update(&MessageDelete {
+            channel_id: ChannelId(10),
+            guild_id: None,
+            id: MessageId(3),
+        });
+        cache.update(&message_in(20, 6));
+        assert_eq!(3, cache.stats().messages());
+        assert_eq!(2, cache.stats().message_evictions());
+
+        // Messages evicted by the size of their channel are also removed from
+        // the order across channels.
+        cache.update(&message_in(10, 7));
+        cache.update(&message_in(10, 8));
+        assert_eq!(
+            Some(vec![MessageId(8), MessageId(7)]),
+            cache.channel_messages(ChannelId(10))
+        );
+        assert_eq!(
+            Some(vec![MessageId(6)]),
+            cache.channel_messages(ChannelId(20))
+        );
+        assert_eq!(4, cache.stats().message_evictions());
+
+        cache.update(&message_in(20, 9));
+        assert_eq!(
+            Some(vec![MessageId(8), MessageId(7)]),
+            cache.channel_messages(ChannelId(10))
+        );
+        assert_eq!(
+            Some(vec![MessageId(9)]),
+            cache.channel_messages(ChannelId(20))
+        );
+        assert_eq!(3, cache.stats().messages());
+        assert_eq!(5, cache.stats().message_evictions());
+    }
+
+    #[test]
+    fn test_message_cache_size_zero() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::MESSAGE)
+            .message_cache_size(0)
+            .build();
+
+        cache.update(&MessageCreate(message(MessageId(1))));
+
+        assert!(cache.channel_messages(ChannelId(2)).is_none());
+        assert!(cache.message(ChannelId(2), MessageId(1)).is_none());
+    }
+
+    #[test]
+    fn test_member_remove_drops_unreferenced_user() {
+        use crate::test;
+        use twilight_model::gateway::presence::{ClientStatus, Status, UserOrId};
+
+        let cache = InMemoryCache::new();
+        let user_id = UserId(5);
+
+        for guild_id in &[GuildId(1), GuildId(2)] {
+            cache.update(&MemberAdd(test::member(*guild_id, user_id)));
+            cache.update(&PresenceUpdate {
+                activities: Vec::new(),
+                client_status: ClientStatus {
+                    desktop: Some(Status::Online),
+                    mobile: None,
+                    web: None,
+                },
+                game: None,
+                guild_id: *guild_id,
+                status: Status::Online,
+                user: UserOrId::UserId { id: user_id },
+            });
+        }
+        cache.update(&VoiceStateUpdate(VoiceState {
+            channel_id: Some(ChannelId(3)),
+            deaf: false,
+            guild_id: Some(GuildId(1)),
+            member: None,
+            mute: false,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: false,
+            session_id: "a".to_owned(),
+            suppress: false,
+            token: None,
+            user_id,
+        }));
+
+        cache.update(&MemberRemove {
+            guild_id: GuildId(1),
+            user: test::user(user_id),
+        });
+        assert!(cache.user(user_id).is_some());
+        assert!(cache.member(GuildId(1), user_id).is_none());
+        assert!(cache.presence(GuildId(1), user_id).is_none());
+        assert!(cache.presence(GuildId(2), user_id).is_some());
+        assert!(cache.voice_state(user_id, GuildId(1)).is_none());
+        assert!(cache.voice_channel_states(ChannelId(3)).is_none());
+        assert!(cache.0.voice_state_guilds.is_empty());
+
+        cache.update(&MemberRemove {
+            guild_id: GuildId(2),
+            user: test::user(user_id),
+        });
+        assert!(cache.user(user_id).is_none());
+        assert!(cache.0.presences.is_empty());
+    }
+
+    #[test]
+    fn test_guild_delete_drops_unreferenced_users() {
+        use crate::test;
+        use twilight_model::{guild::Emoji, id::EmojiId};
+
+        let cache = InMemoryCache::new();
+        cache.update(&test::guild_create(GuildId(1), Vec::new(), Vec::new()));
+
+        // Authors of messages without a member, such as webhooks, and creators
+        // of emojis are cached in the guild without being members of it.
+        let mut message = test::message(ChannelId(2), MessageId(3));
+        message.author = test::user(UserId(4));
+        message.guild_id = Some(GuildId(1));
+        cache.update(&MessageCreate(message));
+        cache.update(&GuildEmojisUpdate {
+            emojis: vec![Emoji {
+                animated: false,
+                available: true,
+                id: EmojiId(5),
+                managed: false,
+                name: "test".to_owned(),
+                require_colons: true,
+                roles: Vec::new(),
+                user: Some(test::user(UserId(6))),
+            }],
+            guild_id: GuildId(1),
+        });
+
+        let author = Arc::downgrade(&cache.user(UserId(4)).unwrap());
+        let creator = Arc::downgrade(&cache.user(UserId(6)).unwrap());
+
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        });
+        assert!(cache.user(UserId(4)).is_none());
+        assert!(cache.user(UserId(6)).is_none());
+        assert!(cache.0.users.is_empty());
+        assert!(author.upgrade().is_none());
+        assert!(creator.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_user_update_keeps_guilds() {
+        use crate::test;
+
+        let cache = InMemoryCache::new();
+        let user_id = UserId(5);
+
+        cache.update(&MemberAdd(test::member(GuildId(1), user_id)));
+
+        // The user changed their name before being cached in another guild.
+        let mut member = test::member(GuildId(2), user_id);
+        member.user.name = "renamed".to_owned();
+        cache.update(&MemberAdd(member));
+
+        cache.update(&MemberRemove {
+            guild_id: GuildId(2),
+            user: test::user(user_id),
+        });
+        assert_eq!("renamed", cache.user(user_id).unwrap().name);
+
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        });
+        assert!(cache.user(user_id).is_none());
+    }
+
+    #[test]
+    fn test_voice_channel_occupancy() {
+        use crate::test;
+
+        fn voice_state(user_id: UserId, channel_id: Option<ChannelId>) -> VoiceState {
+            VoiceState {
+                channel_id,
+                deaf: false,
+                guild_id: None,
+                member: None,
+                mute: false,
+                self_deaf: false,
+                self_mute: false,
+                self_stream: false,
+                session_id: "a".to_owned(),
+                suppress: false,
+                token: None,
+                user_id,
+            }
+        }
+
+        fn update(cache: &InMemoryCache, user_id: UserId, channel_id: Option<ChannelId>) {
+            let mut state = voice_state(user_id, channel_id);
+            state.guild_id = Some(GuildId(1));
+            cache.update(&VoiceStateUpdate(state));
+        }
+
+        fn occupants(cache: &InMemoryCache, channel_id: ChannelId) -> Option<HashSet<UserId>> {
+            cache
+                .voice_channel_states(channel_id)
+                .map(|states| states.iter().map(|state| state.user_id).collect())
+        }
+
+        let cache = InMemoryCache::new();
+
+        // The guild create seeds the voice states without their guild IDs.
+        let mut guild = test::guild_create(GuildId(1), Vec::new(), Vec::new());
+        guild.0.voice_states = vec![
+            voice_state(UserId(2), Some(ChannelId(10))),
+            voice_state(UserId(3), Some(ChannelId(10))),
+        ];
+        cache.update(&guild);
+        assert_eq!(
+            Some(vec![UserId(2), UserId(3)].into_iter().collect()),
+            occupants(&cache, ChannelId(10))
+        );
+        assert_eq!(
+            Some(GuildId(1)),
+            cache.voice_state(UserId(2), GuildId(1)).unwrap().guild_id
+        );
+
+        // Join.
+        update(&cache, UserId(4), Some(ChannelId(11)));
+        assert_eq!(
+            Some(vec![UserId(4)].into_iter().collect()),
+            occupants(&cache, ChannelId(11))
+        );
+
+        // Move between channels.
+        update(&cache, UserId(2), Some(ChannelId(11)));
+        update(&cache, UserId(3), Some(ChannelId(11)));
+        assert!(occupants(&cache, ChannelId(10)).is_none());
+        assert_eq!(
+            Some(vec![UserId(2), UserId(3), UserId(4)].into_iter().collect()),
+            occupants(&cache, ChannelId(11))
+        );
+        assert_eq!(
+            Some(ChannelId(11)),
+            cache.voice_state(UserId(2), GuildId(1)).unwrap().channel_id
+        );
+
+        // Disconnect.
+        update(&cache, UserId(4), None);
+        assert!(cache.voice_state(UserId(4), GuildId(1)).is_none());
+        assert_eq!(
+            Some(vec![UserId(2), UserId(3)].into_iter().collect()),
+            occupants(&cache, ChannelId(11))
+        );
+
+        // Creating the guild again replaces its voice states.
+        cache.update(&test::guild_create(GuildId(1), Vec::new(), Vec::new()));
+        assert!(occupants(&cache, ChannelId(11)).is_none());
+        assert!(cache.0.voice_states.is_empty());
+    }
+
+    #[test]
+    fn test_guild_id_sets() {
+        use crate::test;
+        use twilight_model::{
+            gateway::presence::{ClientStatus, Status, UserOrId},
+            guild::{Emoji, Permissions},
+            id::{EmojiId, RoleId},
+        };
+
+        fn emoji(id: EmojiId) -> Emoji {
+            Emoji {
+                animated: false,
+                available: true,
+                id,
+                managed: false,
+                name: "test".to_owned(),
+                require_colons: true,
+                roles: Vec::new(),
+                user: None,
+            }
+        }
+
+        fn set<T: Eq + Hash>(ids: Vec<T>) -> Option<HashSet<T>> {
+            Some(ids.into_iter().collect())
+        }
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        let mut guild = test::guild_create(
+            guild_id,
+            vec![test::channel(ChannelId(2)), test::channel(ChannelId(3))],
+            vec![
+                test::member(guild_id, UserId(4)),
+                test::member(guild_id, UserId(5)),
+            ],
+        );
+        guild.0.emojis = vec![emoji(EmojiId(6)), emoji(EmojiId(7))];
+        guild.0.roles = vec![
+            test::role(RoleId(8), Permissions::empty()),
+            test::role(RoleId(9), Permissions::empty()),
+        ];
+        guild.0.presences = vec![Presence {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: Some(Status::Online),
+                mobile: None,
+                web: None,
+            },
+            guild_id,
+            status: Status::Online,
+            user: UserOrId::UserId { id: UserId(4) },
+        }];
+        cache.update(&guild);
+
+        assert_eq!(
+            set(vec![ChannelId(2), ChannelId(3)]).as_ref(),
+            cache.guild_channels(guild_id).as_deref()
+        );
+        assert_eq!(
+            set(vec![EmojiId(6), EmojiId(7)]).as_ref(),
+            cache.guild_emojis(guild_id).as_deref()
+        );
+        assert_eq!(
+            set(vec![UserId(4), UserId(5)]).as_ref(),
+            cache.guild_members(guild_id).as_deref()
+        );
+        assert_eq!(
+            set(vec![UserId(4)]).as_ref(),
+            cache.guild_presences(guild_id).as_deref()
+        );
+        assert_eq!(
+            set(vec![RoleId(8), RoleId(9)]).as_ref(),
+            cache.guild_roles(guild_id).as_deref()
+        );
+
+        cache.update(&ChannelDelete(Channel::Guild(test::channel(ChannelId(2)))));
+        assert_eq!(
+            set(vec![ChannelId(3)]).as_ref(),
+            cache.guild_channels(guild_id).as_deref()
+        );
+
+        cache.update(&RoleDelete {
+            guild_id,
+            role_id: RoleId(8),
+        });
+        assert_eq!(
+            set(vec![RoleId(9)]).as_ref(),
+            cache.guild_roles(guild_id).as_deref()
+        );
+
+        // An emoji update replaces all of the guild's emojis.
+        cache.update(&GuildEmojisUpdate {
+            emojis: vec![emoji(EmojiId(7)), emoji(EmojiId(10))],
+            guild_id,
+        });
+        assert_eq!(
+            set(vec![EmojiId(7), EmojiId(10)]).as_ref(),
+            cache.guild_emojis(guild_id).as_deref()
+        );
+        assert!(cache.emoji(EmojiId(6)).is_none());
+
+        cache.update(&MemberRemove {
+            guild_id,
+            user: test::user(UserId(4)),
+        });
+        assert_eq!(
+            set(vec![UserId(5)]).as_ref(),
+            cache.guild_members(guild_id).as_deref()
+        );
+        assert_eq!(
+            set(Vec::new()).as_ref(),
+            cache.guild_presences(guild_id).as_deref()
+        );
+
+        // Creating the guild again replaces its channels, emojis, and roles,
+        // but keeps the members that are already cached.
+        let mut guild =
+            test::guild_create(guild_id, vec![test::channel(ChannelId(11))], Vec::new());
+        guild.0.roles = vec![test::role(RoleId(12), Permissions::empty())];
+        cache.update(&guild);
+
+        assert_eq!(
+            set(vec![ChannelId(11)]).as_ref(),
+            cache.guild_channels(guild_id).as_deref()
+        );
+        assert!(cache.guild_channel(ChannelId(3)).is_none());
+        assert_eq!(
+            set(Vec::new()).as_ref(),
+            cache.guild_emojis(guild_id).as_deref()
+        );
+        assert!(cache.emoji(EmojiId(7)).is_none());
+        assert_eq!(
+            set(vec![RoleId(12)]).as_ref(),
+            cache.guild_roles(guild_id).as_deref()
+        );
+        assert!(cache.role(RoleId(9)).is_none());
+        assert_eq!(
+            set(vec![UserId(5)]).as_ref(),
+            cache.guild_members(guild_id).as_deref()
+        );
+    }
+
+    #[test]
+    fn test_http_models() {
+        use crate::test;
+        use twilight_model::{guild::Permissions, id::RoleId};
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        let mut guild_create =
+            test::guild_create(guild_id, vec![test::channel(ChannelId(2))], Vec::new());
+        guild_create.0.large = true;
+        guild_create.0.member_count = Some(2);
+        cache.update(&guild_create);
+
+        // A member fetched over HTTP is cached like one from the gateway.
+        cache.update(&test::member(guild_id, UserId(3)));
+        assert!(cache.member(guild_id, UserId(3)).is_some());
+        assert!(cache.user(UserId(3)).is_some());
+
+        cache.update(&vec![
+            test::member(guild_id, UserId(4)),
+            test::member(guild_id, UserId(5)),
+        ]);
+        assert_eq!(3, cache.guild_members(guild_id).unwrap().len());
+
+        cache.update(&InGuild::new(
+            guild_id,
+            test::role(RoleId(6), Permissions::empty()),
+        ));
+        assert!(cache.role(RoleId(6)).is_some());
+        assert!(cache.guild_roles(guild_id).unwrap().contains(&RoleId(6)));
+
+        // A list of roles replaces the guild's roles.
+        cache.update(&InGuild::new(
+            guild_id,
+            vec![test::role(RoleId(7), Permissions::empty())],
+        ));
+        assert!(cache.role(RoleId(6)).is_none());
+        assert_eq!(
+            Some(&vec![RoleId(7)].into_iter().collect()),
+            cache.guild_roles(guild_id).as_deref()
+        );
+
+        cache.update(&InGuild::new(guild_id, test::channel(ChannelId(8))));
+        assert!(cache.guild_channel(ChannelId(8)).is_some());
+        assert_eq!(2, cache.guild_channels(guild_id).unwrap().len());
+
+        // Guilds fetched over HTTP don't have channels, members, or fields
+        // only sent over the gateway, so those already cached are kept.
+        let mut guild = test::guild_create(guild_id, Vec::new(), Vec::new()).0;
+        guild.name = "renamed".to_owned();
+        cache.update(&guild);
+
+        let cached = cache.guild(guild_id).unwrap();
+        assert_eq!("renamed", cached.name);
+        assert!(cached.large);
+        assert_eq!(Some(2), cached.member_count);
+        assert_eq!(2, cache.guild_channels(guild_id).unwrap().len());
+        assert_eq!(3, cache.guild_members(guild_id).unwrap().len());
+        assert_eq!(
+            Some(&HashSet::new()),
+            cache.guild_roles(guild_id).as_deref()
+        );
+
+        cache.update(&test::message(ChannelId(2), MessageId(9)));
+        assert!(cache.message(ChannelId(2), MessageId(9)).is_some());
+    }
+
+    #[test]
+    fn test_presences() {
+        use crate::test;
+        use twilight_model::gateway::presence::{Activity, ActivityType, ClientStatus, Status};
+
+        fn update(presence: Presence) -> PresenceUpdate {
+            PresenceUpdate {
+                activities: presence.activities,
+                client_status: presence.client_status,
+                game: None,
+                guild_id: presence.guild_id,
+                status: presence.status,
+                user: presence.user,
+            }
+        }
+
+        for remove_offline_presences in &[false, true] {
+            let cache = InMemoryCache::builder()
+                .remove_offline_presences(*remove_offline_presences)
+                .build();
+            let guild_id = GuildId(1);
+
+            // Presences are seeded from guild creates.
+            let mut guild = test::guild_create(guild_id, Vec::new(), Vec::new());
+            guild.0.presences = vec![
+                test::presence(guild_id, UserId(2), Status::Online),
+                test::presence(guild_id, UserId(3), Status::Idle),
+            ];
+            cache.update(&guild);
+
+            let presence = Arc::clone(&cache.presence(guild_id, UserId(3)).unwrap());
+            assert_eq!(Status::Idle, presence.status);
+            assert_eq!(Some(Status::Idle), presence.client_status.desktop);
+            assert_eq!(2, cache.guild_presences(guild_id).unwrap().len());
+
+            let mut presence = test::presence(guild_id, UserId(2), Status::Online);
+            presence.activities = vec![Activity {
+                application_id: None,
+                assets: None,
+                created_at: None,
+                details: None,
+                emoji: None,
+                flags: None,
+                id: None,
+                instance: None,
+                kind: ActivityType::Playing,
+                name: "test".to_owned(),
+                party: None,
+                secrets: None,
+                state: None,
+                timestamps: None,
+                url: None,
+            }];
+            presence.client_status = ClientStatus {
+                desktop: None,
+                mobile: Some(Status::Online),
+                web: None,
+            };
+            cache.update(&update(presence));
+
+            let presence = Arc::clone(&cache.presence(guild_id, UserId(2)).unwrap());
+            assert_eq!(1, presence.activities.len());
+            assert_eq!("test", presence.activities[0].name);
+            assert_eq!(Some(Status::Online), presence.client_status.mobile);
+
+            cache.update(&update(test::presence(
+                guild_id,
+                UserId(3),
+                Status::Offline,
+            )));
+
+            if *remove_offline_presences {
+                assert!(cache.presence(guild_id, UserId(3)).is_none());
+                assert_eq!(1, cache.guild_presences(guild_id).unwrap().len());
+            } else {
+                let presence = Arc::clone(&cache.presence(guild_id, UserId(3)).unwrap());
+                assert_eq!(Status::Offline, presence.status);
+                assert_eq!(2, cache.guild_presences(guild_id).unwrap().len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_guild_stickers() {
+        use crate::test;
+        use twilight_model::channel::message::{
+            sticker::{StickerFormatType, StickerId},
+            Sticker,
+        };
+
+        fn sticker(id: u64, guild_id: GuildId) -> Sticker {
+            Sticker {
+                asset: String::new(),
+                available: Some(true),
+                description: "test".to_owned(),
+                format_type: StickerFormatType::Png,
+                guild_id: Some(guild_id),
+                id: StickerId(id),
+                name: "test".to_owned(),
+                pack_id: None,
+                preview_asset: None,
+                tags: Some("test".to_owned()),
+                user: Some(test::user(UserId(5))),
+            }
+        }
+
+        fn set(ids: Vec<u64>) -> Option<HashSet<StickerId>> {
+            Some(ids.into_iter().map(StickerId).collect())
+        }
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        let mut guild = test::guild_create(guild_id, Vec::new(), Vec::new());
+        guild.0.stickers = vec![sticker(2, guild_id), sticker(3, guild_id)];
+        cache.update(&guild);
+
+        let cached = Arc::clone(cache.sticker(StickerId(2)).unwrap().data());
+        assert_eq!(StickerId(2), cached.id);
+        assert_eq!(Some(UserId(5)), cached.user_id);
+        assert_eq!(
+            set(vec![2, 3]).as_ref(),
+            cache.guild_stickers(guild_id).as_deref()
+        );
+
+        // A stickers update replaces all of the guild's stickers.
+        cache.update(&GuildStickersUpdate {
+            guild_id,
+            stickers: vec![sticker(3, guild_id), sticker(4, guild_id)],
+        });
+        assert!(cache.sticker(StickerId(2)).is_none());
+        assert!(cache.sticker(StickerId(4)).is_some());
+        assert_eq!(
+            set(vec![3, 4]).as_ref(),
+            cache.guild_stickers(guild_id).as_deref()
+        );
+        assert_eq!(2, cache.stats().stickers());
+
+        cache.update(&GuildDelete {
+            id: guild_id,
+            unavailable: false,
+        });
+        assert!(cache.sticker(StickerId(3)).is_none());
+        assert!(cache.guild_stickers(guild_id).is_none());
+
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::all() - ResourceType::STICKER)
+            .build();
+        cache.update(&guild);
+        assert!(cache.sticker(StickerId(2)).is_none());
+        assert!(cache.guild_stickers(guild_id).is_none());
+    }
+
+    #[test]
+    fn test_partial_member_merge() {
+        use crate::test;
+        use twilight_model::id::RoleId;
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        let mut member = test::member(guild_id, UserId(2));
+        member.joined_at = Some("2020-01-01T00:00:00.000000+00:00".to_owned());
+        member.roles = vec![RoleId(3)];
+        cache.update(&MemberAdd(member));
+
+        let mut message = test::message(ChannelId(4), MessageId(5));
+        message.author = test::user(UserId(2));
+        message.guild_id = Some(guild_id);
+        message.member = Some(PartialMember {
+            deaf: false,
+            joined_at: None,
+            mute: false,
+            nick: Some("nick".to_owned()),
+            premium_since: None,
+            roles: Vec::new(),
+        });
+        cache.update(&MessageCreate(message.clone()));
+
+        // The partial member fills in the nickname, but doesn't clear the
+        // join date and roles.
+        let cached = Arc::clone(&cache.member(guild_id, UserId(2)).unwrap());
+        assert_eq!(vec![RoleId(3)], cached.roles);
+        assert_eq!(
+            Some("2020-01-01T00:00:00.000000+00:00"),
+            cached.joined_at.as_deref()
+        );
+        assert_eq!(Some("nick"), cached.nick.as_deref());
+
+        // Full members replace the cached member.
+        cache.update(&MemberAdd(test::member(guild_id, UserId(2))));
+        let cached = Arc::clone(&cache.member(guild_id, UserId(2)).unwrap());
+        assert!(cached.roles.is_empty());
+        assert!(cached.nick.is_none());
+
+        // Partial members are cached as-is if no member is cached.
+        message.author = test::user(UserId(6));
+        message.member.as_mut().unwrap().roles = vec![RoleId(3)];
+        cache.update(&MessageCreate(message));
+        let cached = Arc::clone(&cache.member(guild_id, UserId(6)).unwrap());
+        assert_eq!(vec![RoleId(3)], cached.roles);
+        assert!(cache.guild_members(guild_id).unwrap().contains(&UserId(6)));
+    }
+
+    #[test]
+    fn test_guild_member_count() {
+        use crate::test;
+
+        fn member_remove(user_id: UserId) -> MemberRemove {
+            MemberRemove {
+                guild_id: GuildId(1),
+                user: test::user(user_id),
+            }
+        }
+
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+
+        let mut guild = test::guild_create(
+            guild_id,
+            Vec::new(),
+            vec![test::member(guild_id, UserId(2))],
+        );
+        guild.0.member_count = Some(2);
+        cache.update(&guild);
+        assert_eq!(Some(2), cache.guild_member_count(guild_id));
+
+        cache.update(&MemberAdd(test::member(guild_id, UserId(3))));
+        assert_eq!(Some(3), cache.guild_member_count(guild_id));
+        assert_eq!(Some(3), cache.guild(guild_id).unwrap().member_count);
+
+        // Members that aren't cached are still counted.
+        cache.update(&member_remove(UserId(4)));
+        assert_eq!(Some(2), cache.guild_member_count(guild_id));
+        cache.update(&member_remove(UserId(2)));
+        cache.update(&member_remove(UserId(3)));
+        assert_eq!(Some(0), cache.guild_member_count(guild_id));
+
+        // The count never goes below zero.
+        cache.update(&member_remove(UserId(2)));
+        assert_eq!(Some(0), cache.guild_member_count(guild_id));
+
+        // A guild create resets the count.
+        guild.0.member_count = Some(5);
+        cache.update(&guild);
+        assert_eq!(Some(5), cache.guild_member_count(guild_id));
+
+        assert!(cache.guild_member_count(GuildId(6)).is_none());
+    }
+
+    #[test]
+    fn test_update_with_result() {
+        use crate::test;
+
+        let cache = InMemoryCache::new();
+
+        let mut edited = message(MessageId(1));
+        edited.content = "pong".to_owned();
+        cache.update(&MessageCreate(edited));
+        cache.update(&MessageCreate(message(MessageId(2))));
+        cache.update(&MessageCreate(message(MessageId(3))));
+
+        let delete = |id| MessageDelete {
+            channel_id: ChannelId(2),
+            guild_id: None,
+            id,
+        };
+
+        match cache.update_with_result(&delete(MessageId(1))) {
+            UpdateResult::MessageDelete(message) => assert_eq!("pong", message.content),
+            other => panic!("expected a deleted message: {:?}", other),
+        }
+        assert!(cache.message(ChannelId(2), MessageId(1)).is_none());
+        assert_eq!(2, cache.stats().messages());
+
+        // The message is no longer cached.
+        assert_eq!(
+            UpdateResult::None,
+            cache.update_with_result(&delete(MessageId(1)))
+        );
+
+        let bulk = Event::MessageDeleteBulk(MessageDeleteBulk {
+            channel_id: ChannelId(2),
+            guild_id: None,
+            ids: vec![MessageId(1), MessageId(2), MessageId(3)],
+        });
+        match cache.update_with_result(&bulk) {
+            UpdateResult::MessageDeleteBulk(messages) => {
+                let ids = messages.iter().map(|m| m.id).collect::<Vec<_>>();
+                assert_eq!(vec![MessageId(2), MessageId(3)], ids);
+            }
+            other => panic!("expected deleted messages: {:?}", other),
+        }
+        assert_eq!(0, cache.stats().messages());
+        assert_eq!(UpdateResult::None, cache.update_with_result(&bulk));
+
+        let guild_id = GuildId(1);
+        cache.update(&MemberAdd(test::member(guild_id, UserId(4))));
+        let remove = MemberRemove {
+            guild_id,
+            user: test::user(UserId(4)),
+        };
+        match cache.update_with_result(&remove) {
+            UpdateResult::MemberRemove(member) => assert_eq!(UserId(4), member.user.id),
+            other => panic!("expected a removed member: {:?}", other),
+        }
+        assert_eq!(UpdateResult::None, cache.update_with_result(&remove));
+
+        let (guild_id, channel_id, channel) = guild_channel_text();
+        cache.cache_guild_channel(guild_id, channel.clone());
+        let delete = ChannelDelete(Channel::Guild(channel.clone()));
+        assert_eq!(
+            UpdateResult::ChannelDelete(Channel::Guild(channel)),
+            cache.update_with_result(&delete)
+        );
+        assert!(cache.guild_channel(channel_id).is_none());
+        assert_eq!(UpdateResult::None, cache.update_with_result(&delete));
+
+        // Updates that don't delete anything still update the cache.
+        assert_eq!(
+            UpdateResult::None,
+            cache.update_with_result(&MessageCreate(message(MessageId(5))))
+        );
+        assert!(cache.message(ChannelId(2), MessageId(5)).is_some());
+    }
+
+    fn message_update(id: MessageId) -> MessageUpdate {
+        MessageUpdate {
+            attachments: None,
+            author: None,
+            channel_id: ChannelId(2),
+            content: None,
+            edited_timestamp: None,
+            embeds: None,
+            guild_id: None,
+            id,
+            kind: None,
+            mention_everyone: None,
+            mention_roles: None,
+            mentions: None,
+            pinned: None,
+            timestamp: None,
+            tts: None,
+        }
+    }
+
+    #[test]
+    fn test_message_update_partial() {
+        let cache = InMemoryCache::new();
+        let mut original = message(MessageId(1));
+        original.attachments = vec![Attachment {
+            filename: "a.png".to_owned(),
+            height: None,
+            id: AttachmentId(4),
+            proxy_url: String::new(),
+            size: 1,
+            url: String::new(),
+            width: None,
+        }];
+        cache.update(&MessageCreate(original));
+
+        // Link unfurls only update the embeds of a message.
+        let embed = Embed {
+            author: None,
+            color: None,
+            description: None,
+            fields: Vec::new(),
+            footer: None,
+            image: None,
+            kind: "link".to_owned(),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: Some("a link".to_owned()),
+            url: None,
+            video: None,
+        };
+        let mut unfurl = message_update(MessageId(1));
+        unfurl.embeds = Some(vec![embed.clone()]);
+
+        match cache.update_with_result(&Event::MessageUpdate(Box::new(unfurl))) {
+            UpdateResult::MessageUpdate(previous) => assert!(previous.embeds.is_empty()),
+            other => panic!("expected the previous message: {:?}", other),
+        }
+        let cached = Arc::clone(&cache.message(ChannelId(2), MessageId(1)).unwrap());
+        assert_eq!(vec![embed], cached.embeds);
+        assert_eq!("ping", cached.content);
+        assert_eq!(UserId(3), cached.author);
+        assert_eq!(1, cached.attachments.len());
+        assert!(cached.edited_timestamp.is_none());
+
+        // Edits replace the content and the embeds as a whole.
+        let mut edit = message_update(MessageId(1));
+        edit.content = Some("pong".to_owned());
+        edit.edited_timestamp = Some("2021-07-01T00:00:00+00:00".to_owned());
+        edit.embeds = Some(Vec::new());
+
+        match cache.update_with_result(&edit) {
+            UpdateResult::MessageUpdate(previous) => {
+                assert_eq!("ping", previous.content);
+                assert_eq!(1, previous.embeds.len());
+            }
+            other => panic!("expected the previous message: {:?}", other),
+        }
+        let cached = Arc::clone(&cache.message(ChannelId(2), MessageId(1)).unwrap());
+        assert_eq!("pong", cached.content);
+        assert!(cached.embeds.is_empty());
+        assert_eq!(
+            Some("2021-07-01T00:00:00+00:00"),
+            cached.edited_timestamp.as_deref()
+        );
+        assert_eq!(1, cached.attachments.len());
+
+        // Updates of messages that aren't cached are ignored.
+        assert_eq!(
+            UpdateResult::None,
+            cache.update_with_result(&message_update(MessageId(5)))
+        );
+        let mut other_channel = message_update(MessageId(1));
+        other_channel.channel_id = ChannelId(6);
+        assert_eq!(UpdateResult::None, cache.update_with_result(&other_channel));
+        assert!(cache.channel_messages(ChannelId(6)).is_none());
+    }
+
+    fn thread_member(thread_id: ChannelId, user_id: UserId) -> ThreadMember {
+        ThreadMember {
+            flags: 0,
+            id: Some(thread_id),
+            join_timestamp: "2021-07-01T00:00:00.000000+00:00".to_owned(),
+            user_id: Some(user_id),
+        }
+    }
+
+    fn thread_ids(cache: &InMemoryCache, parent_id: u64) -> Option<HashSet<ChannelId>> {
+        cache
+            .channel_threads(ChannelId(parent_id))
+            .map(|ids| ids.value().clone())
+    }
+
+    fn set(ids: &[u64]) -> Option<HashSet<ChannelId>> {
+        Some(ids.iter().copied().map(ChannelId).collect())
+    }
+
+    #[test]
+    fn test_thread_list_sync() {
+        use crate::test;
+
+        let guild_id = GuildId(1);
+        let user_id = UserId(2);
+        let cache = InMemoryCache::new();
+        cache.update(&test::guild_create(
+            guild_id,
+            vec![
+                test::channel(ChannelId(10)),
+                test::channel(ChannelId(11)),
+                test::channel(ChannelId(12)),
+            ],
+            Vec::new(),
+        ));
+
+        for (id, parent_id) in &[(100, 10), (101, 10), (110, 11), (120, 12)] {
+            let mut thread = test::thread(guild_id, ChannelId(*id), ChannelId(*parent_id));
+            thread.member = Some(thread_member(ChannelId(*id), user_id));
+            cache.update(&ThreadCreate(thread));
+        }
+        assert_eq!(set(&[100, 101]), thread_ids(&cache, 10));
+        // The nested thread member is cached on its own.
+        assert!(cache.thread_member(ChannelId(101)).is_some());
+        match cache.thread(ChannelId(101)).unwrap().data().as_ref() {
+            GuildChannel::Thread(thread) => assert!(thread.member.is_none()),
+            other => panic!("expected a thread: {:?}", other),
+        }
+        // Text channels aren't threads.
+        assert!(cache.thread(ChannelId(10)).is_none());
+
+        // Only the threads of the synced channels are replaced.
+        cache.update(&ThreadListSync {
+            channel_ids: Some(vec![ChannelId(10), ChannelId(11)]),
+            guild_id,
+            members: vec![thread_member(ChannelId(102), user_id)],
+            threads: vec![test::thread(guild_id, ChannelId(102), ChannelId(10))],
+        });
+        assert_eq!(set(&[102]), thread_ids(&cache, 10));
+        assert!(thread_ids(&cache, 11).is_none());
+        assert_eq!(set(&[120]), thread_ids(&cache, 12));
+        for id in &[100, 101, 110] {
+            assert!(cache.thread(ChannelId(*id)).is_none());
+            assert!(cache.thread_member(ChannelId(*id)).is_none());
+        }
+        assert!(cache.thread_member(ChannelId(102)).is_some());
+        assert!(cache.thread_member(ChannelId(120)).is_some());
+        assert_eq!(
+            set(&[10, 11, 12, 102, 120]),
+            cache
+                .guild_channels(guild_id)
+                .map(|ids| ids.value().clone())
+        );
+
+        // Without channel IDs, the threads of the whole guild are replaced.
+        cache.update(&ThreadListSync {
+            channel_ids: None,
+            guild_id,
+            members: Vec::new(),
+            threads: vec![test::thread(guild_id, ChannelId(121), ChannelId(12))],
+        });
+        assert!(thread_ids(&cache, 10).is_none());
+        assert_eq!(set(&[121]), thread_ids(&cache, 12));
+        assert!(cache.thread(ChannelId(102)).is_none());
+        assert!(cache.thread_member(ChannelId(120)).is_none());
+        assert!(cache.thread(ChannelId(121)).is_some());
+        // Channels that aren't threads are kept.
+        assert!(cache.guild_channel(ChannelId(10)).is_some());
+    }
+
+    #[test]
+    fn test_thread_delete() {
+        use crate::test;
+
+        let guild_id = GuildId(1);
+        let cache = InMemoryCache::new();
+        let parent = test::channel(ChannelId(10));
+        cache.cache_guild_channel(guild_id, parent.clone());
+        cache.update(&ThreadCreate(test::thread(
+            guild_id,
+            ChannelId(100),
+            ChannelId(10),
+        )));
+        cache.update(&ThreadCreate(test::thread(
+            guild_id,
+            ChannelId(101),
+            ChannelId(10),
+        )));
+        cache.update(&ThreadMemberUpdate(thread_member(
+            ChannelId(100),
+            UserId(2),
+        )));
+
+        let delete = ThreadDelete {
+            guild_id,
+            id: ChannelId(100),
+            kind: ChannelType::GuildPublicThread,
+            parent_id: ChannelId(10),
+        };
+        assert_eq!(
+            UpdateResult::ThreadDelete(test::thread(guild_id, ChannelId(100), ChannelId(10))),
+            cache.update_with_result(&delete)
+        );
+        assert_eq!(set(&[101]), thread_ids(&cache, 10));
+        assert!(cache.thread_member(ChannelId(100)).is_none());
+        assert_eq!(UpdateResult::None, cache.update_with_result(&delete));
+
+        // Deleting the parent channel deletes its threads.
+        cache.update(&ChannelDelete(Channel::Guild(parent)));
+        assert!(thread_ids(&cache, 10).is_none());
+        assert!(cache.thread(ChannelId(101)).is_none());
+        assert!(cache.guild_channels(guild_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_thread_members_update() {
+        use crate::test;
+
+        let guild_id = GuildId(1);
+        let thread_id = ChannelId(100);
+        let cache = InMemoryCache::new();
+        cache.update(&UserUpdate(CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0002".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(2),
+            locale: None,
+            mfa_enabled: false,
+            name: "test".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            verified: None,
+        }));
+        cache.update(&ThreadCreate(test::thread(
+            guild_id,
+            thread_id,
+            ChannelId(10),
+        )));
+
+        let update = |added_members, removed_member_ids, member_count| ThreadMembersUpdate {
+            added_members,
+            guild_id,
+            id: thread_id,
+            member_count,
+            removed_member_ids,
+        };
+
+        // Only the thread member of the current user is cached.
+        cache.update(&update(
+            vec![thread_member(thread_id, UserId(3))],
+            Vec::new(),
+            1,
+        ));
+        assert!(cache.thread_member(thread_id).is_none());
+
+        cache.update(&update(
+            vec![thread_member(thread_id, UserId(2))],
+            Vec::new(),
+            2,
+        ));
+        assert_eq!(
+            Some(UserId(2)),
+            cache.thread_member(thread_id).unwrap().user_id
+        );
+        match cache.thread(thread_id).unwrap().data().as_ref() {
+            GuildChannel::Thread(thread) => assert_eq!(2, thread.member_count),
+            other => panic!("expected a thread: {:?}", other),
+        }
+
+        cache.update(&update(Vec::new(), vec![UserId(3)], 1));
+        assert!(cache.thread_member(thread_id).is_some());
+        cache.update(&update(Vec::new(), vec![UserId(2)], 0));
+        assert!(cache.thread_member(thread_id).is_none());
+    }
+
+    fn text_channel(cache: &InMemoryCache, channel_id: ChannelId) -> TextChannel {
+        match cache.guild_channel(channel_id).unwrap().data().as_ref() {
+            GuildChannel::Text(text) => text.clone(),
+            other => panic!("expected a text channel: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_message_create_last_message_id() {
+        let cache = InMemoryCache::new();
+        let (guild_id, channel_id, channel) = guild_channel_text();
+        cache.cache_guild_channel(guild_id, channel);
+
+        cache.update(&MessageCreate(message(MessageId(5))));
+        assert_eq!(
+            Some(MessageId(5)),
+            text_channel(&cache, channel_id).last_message_id
+        );
+
+        // Messages delivered out of order don't move it back.
+        cache.update(&MessageCreate(message(MessageId(3))));
+        assert_eq!(
+            Some(MessageId(5)),
+            text_channel(&cache, channel_id).last_message_id
+        );
+
+        // Deleting the last message leaves it as-is, like Discord does.
+        cache.update(&MessageDelete {
+            channel_id,
+            guild_id: Some(guild_id),
+            id: MessageId(5),
+        });
+        assert_eq!(
+            Some(MessageId(5)),
+            text_channel(&cache, channel_id).last_message_id
+        );
+
+        cache.update(&MessageCreate(message(MessageId(6))));
+        assert_eq!(
+            Some(MessageId(6)),
+            text_channel(&cache, channel_id).last_message_id
+        );
+    }
+
+    #[test]
+    fn test_message_create_last_message_id_private() {
+        let cache = InMemoryCache::new();
+
+        // The private channel is cached from the first message, and advanced
+        // by the next ones.
+        cache.update(&MessageCreate(message(MessageId(5))));
+        cache.update(&MessageCreate(message(MessageId(6))));
+        assert_eq!(
+            Some(MessageId(6)),
+            cache
+                .private_channel(ChannelId(2))
+                .unwrap()
+                .value()
+                .last_message_id
+        );
+    }
+
+    #[test]
+    fn test_channel_pins_update() {
+        let cache = InMemoryCache::new();
+        let (guild_id, channel_id, channel) = guild_channel_text();
+        cache.cache_guild_channel(guild_id, channel);
+
+        cache.update(&ChannelPinsUpdate {
+            channel_id,
+            guild_id: Some(guild_id),
+            last_pin_timestamp: Some("2021-01-01T00:00:00+00:00".to_owned()),
+        });
+        assert_eq!(
+            Some("2021-01-01T00:00:00+00:00"),
+            text_channel(&cache, channel_id)
+                .last_pin_timestamp
+                .as_deref()
+        );
+
+        // Unpinning the last pinned message clears it.
+        cache.update(&ChannelPinsUpdate {
+            channel_id,
+            guild_id: Some(guild_id),
+            last_pin_timestamp: None,
+        });
+        assert!(text_channel(&cache, channel_id)
+            .last_pin_timestamp
+            .is_none());
+    }
 }
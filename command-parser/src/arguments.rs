@@ -1,14 +1,88 @@
+use crate::parse::{self, ParseDurationError, ParseIdError};
 use std::{
-    fmt::{Debug, Formatter, Result as FmtResult},
-    str::CharIndices,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    ops::Range,
+    str::FromStr,
+    time::Duration,
 };
+use twilight_model::id::{ChannelId, RoleId, UserId};
+
+/// Characters that group the words of an argument together.
+const QUOTES: [char; 2] = ['"', '\''];
+
+/// Parsing an argument into a type failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ArgumentError<E> {
+    /// The argument that failed to parse.
+    pub argument: String,
+    /// The position of the argument among the arguments, starting at 0.
+    pub position: usize,
+    /// The error of parsing the argument.
+    pub source: E,
+    /// The byte range of the argument within the content.
+    ///
+    /// Refer to [`Arguments::next_with_span`] for more information.
+    pub span: Range<usize>,
+}
+
+impl<E: Display> Display for ArgumentError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "argument {} (`{}`) is invalid: {}",
+            self.position + 1,
+            self.argument,
+            self.source
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for ArgumentError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
 
 /// An iterator over command arguments.
+///
+/// Arguments are separated by whitespace. Words can be grouped into a single
+/// argument by surrounding them with double or single quotes, such as
+/// `"spamming invite links"`. The other kind of quote can be used within the
+/// quotes, and a quote of the same kind can be escaped with a backslash, as can
+/// a backslash. Escaping backslashes are kept in the argument, as arguments
+/// are views of the buffer.
+///
+/// Quotes only group words when they're at the start of an argument, and are
+/// only closed at the end of a word, so that apostrophes within words don't
+/// group them. A quote that isn't closed is part of the argument it starts,
+/// which is split on whitespace like any other.
+///
+/// The byte ranges of arguments within the content can be retrieved with
+/// [`next_with_span`], and the untouched rest of the content with
+/// [`remainder`].
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_command_parser::Arguments;
+///
+/// let mut args = Arguments::new(r#"@user "spamming invite links" 7d"#);
+/// assert_eq!(Some("@user"), args.next());
+/// assert_eq!(Some("spamming invite links"), args.next());
+/// assert_eq!(Some("7d"), args.next());
+/// assert_eq!(None, args.next());
+/// ```
+///
+/// [`next_with_span`]: Self::next_with_span
+/// [`remainder`]: Self::remainder
 #[derive(Clone)]
 pub struct Arguments<'a> {
     buf: &'a str,
-    indices: CharIndices<'a>,
     idx: usize,
+    offset: usize,
+    position: usize,
 }
 
 impl<'a> Arguments<'a> {
@@ -59,18 +133,212 @@ impl<'a> Arguments<'a> {
     /// assert_eq!(Some("3 4 5"), args.into_remainder());
     /// ```
     pub fn into_remainder(self) -> Option<&'a str> {
-        self.buf.get(self.idx..)
+        self.remainder()
+    }
+
+    /// Returns the remainder of the buffer after the last parsed argument,
+    /// without consuming the iterator.
+    ///
+    /// Only the whitespace separating the remainder from the last parsed
+    /// argument is skipped: the remainder is otherwise exactly as it is in
+    /// the content, including quotes and repeated whitespace. Returns `None`
+    /// if all arguments were parsed.
+    ///
+    /// # Examples
+    ///
+    /// Parse a mention and keep the reason of a ban as it was typed:
+    ///
+    /// ```rust
+    /// use twilight_command_parser::Arguments;
+    ///
+    /// let mut args = Arguments::new("<@123>  posting \"free nitro\"\n\nlinks");
+    /// assert_eq!(Some("<@123>"), args.next());
+    /// assert_eq!(Some("posting \"free nitro\"\n\nlinks"), args.remainder());
+    /// assert_eq!(Some("posting"), args.next());
+    /// ```
+    pub fn remainder(&self) -> Option<&'a str> {
+        self.buf.get(self.idx..).map(str::trim_start)
+    }
+
+    /// Returns the next argument along with its byte range within the
+    /// content.
+    ///
+    /// The range is of the argument as returned, so it excludes the quotes
+    /// of quoted arguments. When the arguments are of a [`Command`], the
+    /// range is within the content that was parsed, prefix included; when
+    /// they're created from a buffer, it's within that buffer.
+    ///
+    /// # Examples
+    ///
+    /// Point at an invalid argument:
+    ///
+    /// ```rust
+    /// use twilight_command_parser::Arguments;
+    ///
+    /// let content = r#"  @user "7 days" reason"#;
+    /// let mut args = Arguments::new(content);
+    /// assert_eq!(Some(("@user", 2..7)), args.next_with_span());
+    ///
+    /// let (arg, span) = args.next_with_span().unwrap();
+    /// assert_eq!("7 days", arg);
+    /// assert_eq!("7 days", &content[span]);
+    /// ```
+    ///
+    /// [`Command`]: crate::Command
+    pub fn next_with_span(&mut self) -> Option<(&'a str, Range<usize>)> {
+        let range = self.next_range()?;
+
+        Some((
+            &self.buf[range.clone()],
+            self.offset + range.start..self.offset + range.end,
+        ))
+    }
+
+    /// Parses the next argument into any type implementing [`FromStr`].
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_command_parser::Arguments;
+    ///
+    /// let mut args = Arguments::new("5 five");
+    /// assert_eq!(Some(Ok(5)), args.next_parsed::<u64>().map(|res| res.map_err(drop)));
+    ///
+    /// let error = args.next_parsed::<u64>().unwrap().unwrap_err();
+    /// assert_eq!("five", error.argument);
+    /// assert_eq!(1, error.position);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with the error of the type's parser if the
+    /// argument failed to parse.
+    pub fn next_parsed<T: FromStr>(&mut self) -> Option<Result<T, ArgumentError<T::Err>>> {
+        self.next_with(str::parse)
+    }
+
+    /// Parses the next argument with a parser.
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with the error of the parser if the
+    /// argument failed to parse.
+    pub fn next_with<T, E>(
+        &mut self,
+        parse: impl FnOnce(&'a str) -> Result<T, E>,
+    ) -> Option<Result<T, ArgumentError<E>>> {
+        let position = self.position;
+        let (argument, span) = self.next_with_span()?;
+
+        Some(parse(argument).map_err(|source| ArgumentError {
+            argument: argument.to_owned(),
+            position,
+            source,
+            span,
+        }))
+    }
+
+    /// Parses the next argument as a channel mention or ID.
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with [`ParseIdError::Channel`] if the
+    /// argument is neither.
+    pub fn next_channel_id(&mut self) -> Option<Result<ChannelId, ArgumentError<ParseIdError>>> {
+        self.next_with(parse::channel_id)
+    }
+
+    /// Parses the next argument as a humane duration, such as `7d` or `1h30m`.
+    ///
+    /// Refer to [`parse::duration`] for the format of durations.
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Examples
+    ///
+    /// Parse the arguments of a temporary ban:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use twilight_command_parser::Arguments;
+    /// use twilight_model::id::UserId;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut args = Arguments::new(r#"<@!123> 1d12h "spamming invite links""#);
+    /// assert_eq!(Some(UserId(123)), args.next_user_id().transpose()?);
+    /// assert_eq!(Some(Duration::from_secs(129_600)), args.next_duration().transpose()?);
+    /// assert_eq!(Some("spamming invite links"), args.next());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with a [`ParseDurationError`] if the
+    /// argument isn't a duration.
+    pub fn next_duration(&mut self) -> Option<Result<Duration, ArgumentError<ParseDurationError>>> {
+        self.next_with(parse::duration)
+    }
+
+    /// Parses the next argument as a role mention or ID.
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with [`ParseIdError::Role`] if the
+    /// argument is neither.
+    pub fn next_role_id(&mut self) -> Option<Result<RoleId, ArgumentError<ParseIdError>>> {
+        self.next_with(parse::role_id)
+    }
+
+    /// Parses the next argument as a user mention or ID.
+    ///
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArgumentError`] with [`ParseIdError::User`] if the
+    /// argument is neither.
+    pub fn next_user_id(&mut self) -> Option<Result<UserId, ArgumentError<ParseIdError>>> {
+        self.next_with(parse::user_id)
     }
 }
 
-impl<'a> From<&'a str> for Arguments<'a> {
-    fn from(buf: &'a str) -> Self {
+impl<'a> Arguments<'a> {
+    /// Create an iterator of arguments from a buffer that starts at a byte
+    /// offset in the content.
+    pub(crate) fn with_offset(buf: &'a str, offset: usize) -> Self {
+        let trimmed = buf.trim_start();
+
         Self {
-            buf: buf.trim(),
-            indices: buf.trim().char_indices(),
+            buf: trimmed.trim_end(),
             idx: 0,
+            offset: offset + buf.len() - trimmed.len(),
+            position: 0,
         }
     }
+
+    /// Move past an argument ending at an index, marking the buffer as fully
+    /// parsed if there's nothing left.
+    fn advance(&mut self, idx: usize) {
+        self.idx = if idx < self.buf.len() {
+            idx
+        } else {
+            usize::max_value()
+        };
+    }
+}
+
+impl<'a> From<&'a str> for Arguments<'a> {
+    fn from(buf: &'a str) -> Self {
+        Self::with_offset(buf, 0)
+    }
 }
 
 impl<'a> Debug for Arguments<'a> {
@@ -78,6 +346,8 @@ impl<'a> Debug for Arguments<'a> {
         f.debug_struct("Arguments")
             .field("buf", &self.buf)
             .field("idx", &self.idx)
+            .field("offset", &self.offset)
+            .field("position", &self.position)
             .finish()
     }
 }
@@ -85,60 +355,91 @@ impl<'a> Debug for Arguments<'a> {
 impl<'a> Iterator for Arguments<'a> {
     type Item = &'a str;
 
-    // todo: clean this up
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx > self.buf.len() {
+        let range = self.next_range()?;
+
+        Some(&self.buf[range])
+    }
+}
+
+impl Arguments<'_> {
+    /// Parse the next argument, returning its byte range within the buffer.
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        let rest = self.buf.get(self.idx..)?;
+        let arg = rest.trim_start();
+        let start = self.idx + rest.len() - arg.len();
+
+        if arg.is_empty() {
+            self.idx = usize::max_value();
+
             return None;
         }
 
-        let mut start_idx = self.idx;
-        let mut quoted = false;
-        let mut started = false;
+        self.position += 1;
 
-        while let Some((i, ch)) = self.indices.next() {
-            if quoted {
-                if ch == '"' {
-                    let v = self.buf.get(start_idx..i);
-                    self.idx = i + 1;
+        if let Some(quote) = arg.chars().next().filter(|ch| QUOTES.contains(ch)) {
+            // Quotes are a single byte long.
+            if let Some(len) = quoted_len(&arg[1..], quote) {
+                self.advance(start + len + 2);
 
-                    return v.map(str::trim);
-                }
-            } else if ch == ' ' {
-                if started {
-                    let v = self.buf.get(start_idx..i);
-                    self.idx = i + 1;
+                let quoted = &arg[1..=len];
+                let end = start + 1 + quoted.trim_end().len();
+                let leading = quoted.len() - quoted.trim_start().len();
 
-                    return v.map(str::trim);
-                }
-                self.idx = i;
-                start_idx = i;
-                started = true;
-                continue;
-            } else if ch == '"' {
-                start_idx = i + 1;
-                quoted = true;
+                return Some((start + 1 + leading).min(end)..end);
             }
-
-            self.idx = i;
-            started = true;
         }
 
-        self.idx = usize::max_value();
+        let len = arg.find(char::is_whitespace).unwrap_or(arg.len());
+        self.advance(start + len);
+
+        Some(start..start + len)
+    }
+}
+
+/// Returns the length of a quoted argument up to its closing quote, or `None`
+/// if the quote isn't closed.
+///
+/// Closing quotes are followed by whitespace, another quote, or nothing.
+fn quoted_len(buf: &str, quote: char) -> Option<usize> {
+    let mut chars = buf.char_indices();
 
-        match self.buf.get(start_idx..) {
-            Some("") | None => None,
-            Some(v) => Some(v.trim()),
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some((_, escaped)) = chars.clone().next() {
+                    if escaped == quote || escaped == '\\' {
+                        chars.next();
+                    }
+                }
+            }
+            ch if ch == quote => {
+                let closes = chars.clone().next().map_or(true, |(_, next)| {
+                    next.is_whitespace() || QUOTES.contains(&next)
+                });
+
+                if closes {
+                    return Some(idx);
+                }
+            }
+            _ => {}
         }
     }
+
+    None
 }
 
 #[allow(clippy::non_ascii_literal)]
 #[cfg(test)]
 mod tests {
-    use super::Arguments;
-    use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use super::{ArgumentError, Arguments};
+    use crate::parse::{self, ParseDurationError, ParseIdError, ResolveError, Resolved};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, num::ParseIntError};
+    use twilight_model::id::{ChannelId, RoleId, UserId};
 
+    assert_fields!(ArgumentError<ParseIdError>: argument, position, source, span);
+    assert_impl_all!(ArgumentError<ParseIntError>: Clone, Debug, Eq, Error, PartialEq, Send, Sync);
     assert_impl_all!(Arguments<'_>: Clone, Debug, From<&'static str>, Iterator, Send, Sync);
 
     #[test]
@@ -167,12 +468,145 @@ mod tests {
 
     #[test]
     fn test_quoted_close_args() {
-        let mut args = Arguments::new(r#""kind of weird""but okay"#);
+        let mut args = Arguments::new(r#""kind of weird""but okay""#);
         assert_eq!(Some("kind of weird"), args.next());
         assert_eq!(Some("but okay"), args.next());
         assert_eq!(None, args.next());
     }
 
+    #[test]
+    fn test_single_quoted_args() {
+        let mut args = Arguments::new("@user 'spamming invite links' 7d");
+        assert_eq!(Some("@user"), args.next());
+        assert_eq!(Some("spamming invite links"), args.next());
+        assert_eq!(Some("7d"), args.next());
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn test_nested_quotes() {
+        let mut args = Arguments::new(r#""they said 'hi there'" 'a "b" c'"#);
+        assert_eq!(Some("they said 'hi there'"), args.next());
+        assert_eq!(Some(r#"a "b" c"#), args.next());
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn test_escaped_quotes() {
+        let mut args = Arguments::new(r#""say \"hi\" now" 'it\'s' "back\\" slash"#);
+        assert_eq!(Some(r#"say \"hi\" now"#), args.next());
+        assert_eq!(Some(r"it\'s"), args.next());
+        assert_eq!(Some(r"back\\"), args.next());
+        assert_eq!(Some("slash"), args.next());
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn test_unterminated_quotes() {
+        let mut args = Arguments::new(r#""kind of weird""but okay"#);
+        assert_eq!(Some("kind of weird"), args.next());
+        assert_eq!(Some(r#""but"#), args.next());
+        assert_eq!(Some("okay"), args.next());
+        assert_eq!(None, args.next());
+
+        let mut args = Arguments::new(r#"'twas "fine \""#);
+        assert_eq!(Some("'twas"), args.next());
+        assert_eq!(Some(r#""fine"#), args.next());
+        assert_eq!(Some(r#"\""#), args.next());
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn test_apostrophes() {
+        let mut args = Arguments::new("don't stop 'til it's done");
+        assert_eq!(Some("don't"), args.next());
+        assert_eq!(Some("stop"), args.next());
+        assert_eq!(Some("'til"), args.next());
+        assert_eq!(Some("it's"), args.next());
+        assert_eq!(Some("done"), args.next());
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn test_remainder() {
+        let mut args = Arguments::new(r#"@user "one reason"   the rest  of it"#);
+        assert_eq!(Some("@user"), args.next());
+        assert_eq!(Some("one reason"), args.next());
+        assert_eq!(Some("the rest  of it"), args.into_remainder());
+
+        // There's no remainder once the last argument was parsed.
+        let mut args = Arguments::new(r#"done "for real""#);
+        assert_eq!(Some("done"), args.next());
+        assert_eq!(Some(r#""for real""#), args.clone().into_remainder());
+        assert_eq!(Some("for real"), args.next());
+        assert_eq!(None, args.into_remainder());
+    }
+
+    #[test]
+    fn test_remainder_borrowed() {
+        let content = "<@1>\t 'first offence'  posting\n\n  \"links\"   again  \n";
+        let mut args = Arguments::new(content);
+        assert_eq!(Some(content.trim()), args.remainder());
+        assert_eq!(Some("<@1>"), args.next());
+        assert_eq!(Some("first offence"), args.next());
+
+        // The remainder is left as typed, including quotes and whitespace.
+        let remainder = args.remainder();
+        assert_eq!(Some("posting\n\n  \"links\"   again"), remainder);
+        assert_eq!(remainder, args.clone().into_remainder());
+        assert_eq!(Some("posting"), args.next());
+        assert_eq!(Some("links"), args.next());
+        assert_eq!(Some("again"), args.remainder());
+        assert_eq!(Some("again"), args.next());
+        assert_eq!(None, args.remainder());
+    }
+
+    #[test]
+    fn test_spans() {
+        let content = " \n foo   \"bar  baz\"\t'  qux '  \"unclosed  end ";
+        let mut args = Arguments::new(content);
+        let spans = [
+            ("foo", 3..6),
+            ("bar  baz", 10..18),
+            ("qux", 23..26),
+            ("\"unclosed", 30..39),
+            ("end", 41..44),
+        ];
+
+        for (arg, span) in &spans {
+            assert_eq!(Some((*arg, span.clone())), args.next_with_span());
+            assert_eq!(*arg, &content[span.clone()]);
+        }
+
+        assert_eq!(None, args.next_with_span());
+
+        // Quotes around nothing but whitespace give an empty range after the
+        // opening quote.
+        let mut args = Arguments::new("a '   ' b");
+        assert_eq!(Some(("a", 0..1)), args.next_with_span());
+        assert_eq!(Some(("", 3..3)), args.next_with_span());
+        assert_eq!(Some(("b", 8..9)), args.next_with_span());
+    }
+
+    #[test]
+    fn test_spans_unicode() {
+        let content = "  𝓒𝓢𝓐 \u{3000}\"héllo wörld\" 👍🏽";
+        let mut args = Arguments::new(content);
+
+        let (arg, span) = args.next_with_span().unwrap();
+        assert_eq!("𝓒𝓢𝓐", arg);
+        assert_eq!(2..14, span);
+
+        let (arg, span) = args.next_with_span().unwrap();
+        assert_eq!("héllo wörld", arg);
+        assert_eq!(&content[span.clone()], arg);
+        assert_eq!(19..32, span);
+
+        let (arg, span) = args.next_with_span().unwrap();
+        assert_eq!("👍🏽", arg);
+        assert_eq!(content.len() - 8..content.len(), span);
+    }
+
     #[test]
     fn test_unicode_chars_1() {
         let mut args = Arguments::new("𝓒𝓢𝓐 nice try");
@@ -219,4 +653,114 @@ mod tests {
         assert_eq!(Some("kewl"), args.next());
         assert_eq!(None, args.next());
     }
+
+    #[test]
+    fn test_next_parsed() {
+        let mut args = Arguments::new("5 -3 five");
+        assert_eq!(Some(5), args.next_parsed::<u64>().and_then(Result::ok));
+        assert_eq!(Some(-3), args.next_parsed::<i8>().and_then(Result::ok));
+
+        let error = args.next_parsed::<u64>().unwrap().unwrap_err();
+        assert_eq!("five", error.argument);
+        assert_eq!(2, error.position);
+        assert_eq!(
+            "argument 3 (`five`) is invalid: invalid digit found in string",
+            error.to_string()
+        );
+        assert!(error.source().is_some());
+
+        assert!(args.next_parsed::<u64>().is_none());
+    }
+
+    #[test]
+    fn test_next_ids() {
+        let mut args = Arguments::new("<@!1> 2 <#3> 4 <@&5> 6");
+        assert_eq!(Some(Ok(UserId(1))), args.next_user_id());
+        assert_eq!(Some(Ok(UserId(2))), args.next_user_id());
+        assert_eq!(Some(Ok(ChannelId(3))), args.next_channel_id());
+        assert_eq!(Some(Ok(ChannelId(4))), args.next_channel_id());
+        assert_eq!(Some(Ok(RoleId(5))), args.next_role_id());
+        assert_eq!(Some(Ok(RoleId(6))), args.next_role_id());
+        assert_eq!(None, args.next_user_id());
+    }
+
+    #[test]
+    fn test_next_ids_invalid() {
+        let mut args = Arguments::new("<@&1> <@2> everyone");
+        assert_eq!(
+            Some(Err(ArgumentError {
+                argument: "<@&1>".to_owned(),
+                position: 0,
+                source: ParseIdError::User,
+                span: 0..5,
+            })),
+            args.next_user_id()
+        );
+        assert_eq!(
+            Some(Err(ArgumentError {
+                argument: "<@2>".to_owned(),
+                position: 1,
+                source: ParseIdError::Channel,
+                span: 6..10,
+            })),
+            args.next_channel_id()
+        );
+
+        let error = args.next_role_id().unwrap().unwrap_err();
+        assert_eq!(
+            "argument 3 (`everyone`) is invalid: not a role mention or ID",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_next_duration() {
+        let mut args = Arguments::new(r#"<@123> 1h30m "the reason" 7 days"#);
+        assert_eq!(Some(Ok(UserId(123))), args.next_user_id());
+        assert_eq!(
+            Some(Ok(5400)),
+            args.next_duration()
+                .map(|res| res.map(|duration| duration.as_secs()))
+        );
+        assert_eq!(Some("the reason"), args.next());
+        assert_eq!(
+            Some(Err(ArgumentError {
+                argument: "7".to_owned(),
+                position: 3,
+                source: ParseDurationError::MissingUnit,
+                span: 26..27,
+            })),
+            args.next_duration()
+        );
+        assert_eq!(
+            Some(Err(ArgumentError {
+                argument: "days".to_owned(),
+                position: 4,
+                source: ParseDurationError::MissingNumber,
+                span: 28..32,
+            })),
+            args.next_duration()
+        );
+        assert_eq!(None, args.next_duration());
+    }
+
+    #[test]
+    fn test_next_resolved() {
+        let mut args = Arguments::new("<@!1> twilight#0001 #logs");
+        assert_eq!(
+            Some(Ok(Resolved::Mention(UserId(1)))),
+            args.next_with(parse::resolve_user)
+        );
+        assert_eq!(
+            Some(Ok(Resolved::Name(UserId(2)))),
+            args.next_with(|arg| parse::resolve_user_with(arg, |_, _| Some(UserId(2))))
+        );
+
+        let error = args
+            .next_with(|arg| parse::resolve_channel_with(arg, |_| None))
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(ResolveError::NotFound, error.source);
+        assert_eq!(2, error.position);
+    }
 }
@@ -3,11 +3,13 @@
 //! Provided are methods for [adding commands][`add_command`] and
 //! [removing them][`remove_command`], as well as
 //! [adding prefixes][`add_prefix`] and [removing prefixes][`remove_prefix`].
+//! Commands can have [subcommands][`add_subcommand`].
 //! You can also [iterate over commands][`commands`] and [prefixes][`prefixes`].
 //!
 //! [`Parser`]: super::Parser
 //! [`add_command`]: CommandParserConfig::add_command
 //! [`add_prefix`]: CommandParserConfig::add_prefix
+//! [`add_subcommand`]: CommandParserConfig::add_subcommand
 //! [`commands`]: CommandParserConfig::commands
 //! [`prefixes`]: CommandParserConfig::prefixes
 //! [`remove_command`]: CommandParserConfig::remove_command
@@ -15,6 +17,7 @@
 
 use std::borrow::Cow;
 use std::slice::{Iter, IterMut};
+use twilight_model::id::UserId;
 
 use crate::CaseSensitivity;
 
@@ -24,7 +27,18 @@ use crate::CaseSensitivity;
 #[derive(Clone, Debug, Default)]
 pub struct CommandParserConfig<'a> {
     pub(crate) commands: Vec<CaseSensitivity>,
+    pub(crate) mention: Option<UserId>,
     pub(crate) prefixes: Vec<Cow<'a, str>>,
+    pub(crate) prefixes_case_insensitive: bool,
+    pub(crate) subcommands: Vec<Subcommand>,
+}
+
+/// A subcommand and the path of configured names of the command and
+/// subcommands that it belongs to.
+#[derive(Clone, Debug)]
+pub(crate) struct Subcommand {
+    pub(crate) name: CaseSensitivity,
+    pub(crate) parent: Vec<String>,
 }
 
 impl<'a> CommandParserConfig<'a> {
@@ -73,8 +87,89 @@ impl<'a> CommandParserConfig<'a> {
         }
     }
 
+    /// Returns the ID of the user whose mention is a prefix, if any.
+    ///
+    /// Refer to [`set_mention_prefix`] for more information.
+    ///
+    /// [`set_mention_prefix`]: Self::set_mention_prefix
+    pub fn mention_prefix(&self) -> Option<UserId> {
+        self.mention
+    }
+
+    /// Sets the user whose mention is a prefix, usually the bot's user, or
+    /// `None` to not use a mention as a prefix.
+    ///
+    /// Both the `<@id>` and `<@!id>` forms of mentions are matched, and can
+    /// be followed by whitespace before the command. The mention is tried
+    /// before the other prefixes, so it takes precedence over prefixes that
+    /// begin with `<`.
+    ///
+    /// # Examples
+    ///
+    /// Match both `"!ping"` and `"@Bot ping"`:
+    ///
+    /// ```rust
+    /// use twilight_command_parser::{CommandParserConfig, Parser};
+    /// use twilight_model::id::UserId;
+    ///
+    /// let mut config = CommandParserConfig::new();
+    /// config.add_prefix("!");
+    /// config.add_command("ping", false);
+    /// config.set_mention_prefix(UserId(123));
+    ///
+    /// let parser = Parser::new(config);
+    /// assert!(parser.parse("!ping").is_some());
+    ///
+    /// let command = parser.parse("<@!123> ping").unwrap();
+    /// assert!(command.mention);
+    /// assert_eq!("<@!123>", command.prefix);
+    /// ```
+    pub fn set_mention_prefix(&mut self, user_id: impl Into<Option<UserId>>) {
+        self.mention = user_id.into();
+    }
+
+    /// Returns whether prefixes are matched regardless of case.
+    ///
+    /// Refer to [`set_prefixes_case_insensitive`] for more information.
+    ///
+    /// [`set_prefixes_case_insensitive`]: Self::set_prefixes_case_insensitive
+    pub fn prefixes_case_insensitive(&self) -> bool {
+        self.prefixes_case_insensitive
+    }
+
+    /// Sets whether prefixes are matched regardless of case.
+    ///
+    /// Prefixes are case-sensitive by default. This is independent from the
+    /// case sensitivity of commands, which is set when [adding them].
+    ///
+    /// # Examples
+    ///
+    /// Match both `"bot!ping"` and `"BOT!ping"`:
+    ///
+    /// ```rust
+    /// use twilight_command_parser::{CommandParserConfig, Parser};
+    ///
+    /// let mut config = CommandParserConfig::new();
+    /// config.add_prefix("bot!");
+    /// config.add_command("ping", true);
+    /// config.set_prefixes_case_insensitive(true);
+    ///
+    /// let parser = Parser::new(config);
+    /// assert!(parser.parse("bot!ping").is_some());
+    /// assert!(parser.parse("BOT!ping").is_some());
+    /// ```
+    ///
+    /// [adding them]: Self::add_command
+    pub fn set_prefixes_case_insensitive(&mut self, case_insensitive: bool) {
+        self.prefixes_case_insensitive = case_insensitive;
+    }
+
     /// Add a command to the list of commands.
     ///
+    /// Case-insensitive commands match regardless of the case of the command
+    /// name in messages, such as both `"!BAN"` and `"!Ban"` for the command
+    /// `"ban"`.
+    ///
     /// # Examples
     ///
     /// Add a case-sensitive "ping" command:
@@ -91,11 +186,7 @@ impl<'a> CommandParserConfig<'a> {
     }
 
     fn _add_command(&mut self, name: String, case_sensitive: bool) -> bool {
-        let command = if case_sensitive {
-            CaseSensitivity::Sensitive(name)
-        } else {
-            CaseSensitivity::Insensitive(name.into())
-        };
+        let command = casing(name, case_sensitive);
         if self.commands.contains(&command) {
             false
         } else {
@@ -123,7 +214,148 @@ impl<'a> CommandParserConfig<'a> {
     /// assert_eq!(config.commands().len(), 0);
     /// ```
     pub fn remove_command(&mut self, command: impl AsRef<str>) {
-        self.commands.retain(|c| c != command.as_ref());
+        let command = command.as_ref();
+        self.commands.retain(|c| c != command);
+
+        // Subcommands of removed commands can no longer be reached.
+        let commands = &self.commands;
+        self.subcommands
+            .retain(|subcommand| commands.iter().any(|c| c.as_ref() == subcommand.parent[0]));
+    }
+
+    /// Add a subcommand to a command or to another subcommand.
+    ///
+    /// The parent is the path of names from the command to the subcommand
+    /// that the new subcommand belongs to, such as `&["config", "prefix"]`
+    /// for the `set` subcommand of `!config prefix set`. When parsing, the
+    /// deepest matching subcommand is resolved into the [`path`] of the
+    /// command.
+    ///
+    /// Case sensitivity is set per subcommand, like for commands. The names
+    /// of the parent are matched against the configured names regardless of
+    /// case if those are case-insensitive.
+    ///
+    /// Returns whether the subcommand was added: `false` if the parent isn't
+    /// configured or if it already has the subcommand.
+    ///
+    /// # Examples
+    ///
+    /// Add the subcommands of `!config prefix set` and `!config prefix reset`:
+    ///
+    /// ```rust
+    /// use twilight_command_parser::{CommandParserConfig, Parser};
+    ///
+    /// let mut config = CommandParserConfig::new();
+    /// config.add_prefix("!");
+    /// config.add_command("config", false);
+    /// assert!(config.add_subcommand(&["config"], "prefix", false));
+    /// assert!(config.add_subcommand(&["config", "prefix"], "set", false));
+    /// assert!(config.add_subcommand(&["config", "prefix"], "reset", false));
+    ///
+    /// // The parent must already be configured.
+    /// assert!(!config.add_subcommand(&["config", "role"], "set", false));
+    ///
+    /// let parser = Parser::new(config);
+    /// let command = parser.parse("!config prefix set ?").unwrap();
+    /// assert_eq!(vec!["config", "prefix", "set"], command.path);
+    /// assert_eq!("?", command.arguments.as_str());
+    /// ```
+    ///
+    /// [`path`]: crate::Command::path
+    pub fn add_subcommand(
+        &mut self,
+        parent: &[&str],
+        name: impl Into<String>,
+        case_sensitive: bool,
+    ) -> bool {
+        self._add_subcommand(parent, name.into(), case_sensitive)
+    }
+
+    fn _add_subcommand(&mut self, parent: &[&str], name: String, case_sensitive: bool) -> bool {
+        let parent = match self.resolve_path(parent) {
+            Some(parent) => parent,
+            None => return false,
+        };
+        let name = casing(name, case_sensitive);
+
+        if self
+            .subcommands
+            .iter()
+            .any(|subcommand| subcommand.parent == parent && subcommand.name == name)
+        {
+            return false;
+        }
+
+        self.subcommands.push(Subcommand { name, parent });
+
+        true
+    }
+
+    /// Removes a subcommand of a command or of another subcommand, along with
+    /// its own subcommands.
+    ///
+    /// Any subcommands of the parent that would match the name provided are
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_command_parser::{CommandParserConfig, Parser};
+    ///
+    /// let mut config = CommandParserConfig::new();
+    /// config.add_prefix("!");
+    /// config.add_command("config", false);
+    /// config.add_subcommand(&["config"], "prefix", false);
+    /// config.add_subcommand(&["config", "prefix"], "set", false);
+    ///
+    /// config.remove_subcommand(&["config"], "prefix");
+    /// assert!(!config.add_subcommand(&["config", "prefix"], "reset", false));
+    ///
+    /// let parser = Parser::new(config);
+    /// let command = parser.parse("!config prefix set ?").unwrap();
+    /// assert_eq!(vec!["config"], command.path);
+    /// ```
+    pub fn remove_subcommand(&mut self, parent: &[&str], name: impl AsRef<str>) {
+        let mut path = match self.resolve_path(parent) {
+            Some(path) => path,
+            None => return,
+        };
+        let name = name.as_ref();
+
+        let removed = self
+            .subcommands
+            .iter()
+            .filter(|subcommand| subcommand.parent == path && subcommand.name == *name)
+            .map(|subcommand| subcommand.name.as_ref().to_owned())
+            .collect::<Vec<_>>();
+
+        for removed in removed {
+            path.push(removed);
+            self.subcommands
+                .retain(|subcommand| !subcommand.parent.starts_with(&path));
+            path.pop();
+        }
+
+        self.subcommands
+            .retain(|subcommand| !(subcommand.parent == path && subcommand.name == *name));
+    }
+
+    /// Resolve a path of names of a command and its subcommands into their
+    /// configured names, if they're all configured.
+    fn resolve_path(&self, path: &[&str]) -> Option<Vec<String>> {
+        let (command, subcommands) = path.split_first()?;
+        let command = self.commands.iter().find(|c| *c == *command)?;
+        let mut resolved = vec![command.as_ref().to_owned()];
+
+        for name in subcommands {
+            let subcommand = self
+                .subcommands
+                .iter()
+                .find(|subcommand| subcommand.parent == resolved && subcommand.name == **name)?;
+            resolved.push(subcommand.name.as_ref().to_owned());
+        }
+
+        Some(resolved)
     }
 
     /// Adds a prefix to the list of prefixes.
@@ -172,6 +404,15 @@ impl<'a> CommandParserConfig<'a> {
     }
 }
 
+/// Create a command or subcommand name with a case sensitivity.
+fn casing(name: String, case_sensitive: bool) -> CaseSensitivity {
+    if case_sensitive {
+        CaseSensitivity::Sensitive(name)
+    } else {
+        CaseSensitivity::Insensitive(name.into())
+    }
+}
+
 /// Iterator over the parser configuration's immutably borrowed commands.
 pub struct Commands<'a> {
     iter: Iter<'a, CaseSensitivity>,
@@ -268,4 +509,39 @@ mod tests {
         assert!(config.prefixes().len() == 0);
         assert!(config.prefixes_mut().len() == 0);
     }
+
+    #[test]
+    fn test_subcommands() {
+        let mut config = CommandParserConfig::new();
+        config.add_command("Config", false);
+        assert!(config.add_subcommand(&["config"], "Prefix", false));
+        assert!(config.add_subcommand(&["CONFIG", "prefix"], "set", true));
+        assert!(config.add_subcommand(&["config", "prefix"], "Set", true));
+
+        // Parents are resolved into their configured names.
+        assert_eq!(
+            vec!["Config".to_owned(), "Prefix".to_owned()],
+            config.subcommands[1].parent
+        );
+
+        // Duplicate subcommands and subcommands of unknown parents aren't
+        // added.
+        assert!(!config.add_subcommand(&["config"], "PREFIX", false));
+        assert!(!config.add_subcommand(&["config", "prefix"], "set", true));
+        assert!(!config.add_subcommand(&["config", "role"], "set", true));
+        assert!(!config.add_subcommand(&["ping"], "set", true));
+        assert!(!config.add_subcommand(&[], "set", true));
+        assert_eq!(3, config.subcommands.len());
+
+        config.remove_subcommand(&["config", "prefix"], "set");
+        assert_eq!(2, config.subcommands.len());
+
+        config.remove_subcommand(&["config"], "prefix");
+        assert!(config.subcommands.is_empty());
+
+        config.add_subcommand(&["config"], "prefix", false);
+        config.add_subcommand(&["config", "prefix"], "set", false);
+        config.remove_command("config");
+        assert!(config.subcommands.is_empty());
+    }
 }
@@ -66,13 +66,14 @@
 #![allow(clippy::module_name_repetitions, clippy::must_use_candidate)]
 
 pub mod config;
+pub mod parse;
 
 mod arguments;
 mod casing;
 mod parser;
 
 pub use self::{
-    arguments::Arguments,
+    arguments::{ArgumentError, Arguments},
     casing::CaseSensitivity,
     config::CommandParserConfig,
     parser::{Command, Parser},
@@ -0,0 +1,763 @@
+//! Parsers for arguments of Discord types.
+//!
+//! These are used by the typed extraction methods of [`Arguments`], such as
+//! [`next_user_id`] and [`next_duration`], but can also be used on their own.
+//!
+//! The resolvers, such as [`resolve_user`], additionally tell how the target
+//! of a moderation command was referred to, and can look up targets by name.
+//! They can be used on arguments with [`Arguments::next_with`].
+//!
+//! [`Arguments`]: crate::Arguments
+//! [`Arguments::next_with`]: crate::Arguments::next_with
+//! [`next_duration`]: crate::Arguments::next_duration
+//! [`next_user_id`]: crate::Arguments::next_user_id
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+use twilight_model::id::{ChannelId, RoleId, UserId};
+
+/// Parsing a mention or bare ID failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseIdError {
+    /// The argument is neither a channel mention nor an ID.
+    Channel,
+    /// The argument is neither a role mention nor an ID.
+    Role,
+    /// The argument is neither a user mention nor an ID.
+    User,
+}
+
+impl Display for ParseIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Channel => f.write_str("not a channel mention or ID"),
+            Self::Role => f.write_str("not a role mention or ID"),
+            Self::User => f.write_str("not a user mention or ID"),
+        }
+    }
+}
+
+impl Error for ParseIdError {}
+
+/// Parsing a duration failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseDurationError {
+    /// The duration has no components.
+    Empty,
+    /// A unit isn't preceded by a number, such as in `1hm`.
+    MissingNumber,
+    /// A number isn't followed by a unit, such as in `7`.
+    MissingUnit,
+    /// The duration is too long to be represented.
+    Overflow,
+    /// A unit isn't one of `s`, `m`, `h`, `d`, or `w`.
+    UnknownUnit {
+        /// The unknown unit.
+        unit: char,
+    },
+}
+
+impl Display for ParseDurationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Empty => f.write_str("the duration is empty"),
+            Self::MissingNumber => f.write_str("a unit of the duration has no number"),
+            Self::MissingUnit => f.write_str("a number of the duration has no unit"),
+            Self::Overflow => f.write_str("the duration is too long"),
+            Self::UnknownUnit { unit } => write!(
+                f,
+                "`{}` is not a unit of duration, which are s, m, h, d, and w",
+                unit
+            ),
+        }
+    }
+}
+
+impl Error for ParseDurationError {}
+
+/// How the target of an argument was referred to.
+///
+/// Mentioned targets existed when the message was sent, and are usually in
+/// the mentions of the message. Targets referred to by a bare ID may not
+/// exist, so they may still need to be fetched to be checked.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Resolved<T> {
+    /// The target was referred to by a bare ID.
+    Id(T),
+    /// The target was mentioned.
+    Mention(T),
+    /// The target was looked up by name.
+    Name(T),
+}
+
+impl<T: Copy> Resolved<T> {
+    /// Return the ID of the target, however it was referred to.
+    pub fn id(&self) -> T {
+        match self {
+            Self::Id(id) | Self::Mention(id) | Self::Name(id) => *id,
+        }
+    }
+}
+
+/// Resolving the target of an argument failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResolveError<T> {
+    /// The name matches multiple targets.
+    Ambiguous {
+        /// The targets that the name matches, in the order of the lookup.
+        matches: Vec<T>,
+    },
+    /// The argument is neither a mention nor an ID, nor a name if names are
+    /// looked up.
+    Invalid,
+    /// The name doesn't match any target.
+    NotFound,
+}
+
+impl<T> Display for ResolveError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Ambiguous { matches } => write!(f, "the name matches {} targets", matches.len()),
+            Self::Invalid => f.write_str("not a mention or ID"),
+            Self::NotFound => f.write_str("nothing matches the name"),
+        }
+    }
+}
+
+impl<T: Debug> Error for ResolveError<T> {}
+
+/// Parse a channel mention, such as `<#123>`, or a bare channel ID.
+///
+/// # Errors
+///
+/// Returns [`ParseIdError::Channel`] if the argument is neither.
+pub fn channel_id(arg: &str) -> Result<ChannelId, ParseIdError> {
+    id(arg, &["<#"]).map(ChannelId).ok_or(ParseIdError::Channel)
+}
+
+/// Parse a role mention, such as `<@&123>`, or a bare role ID.
+///
+/// # Errors
+///
+/// Returns [`ParseIdError::Role`] if the argument is neither.
+pub fn role_id(arg: &str) -> Result<RoleId, ParseIdError> {
+    id(arg, &["<@&"]).map(RoleId).ok_or(ParseIdError::Role)
+}
+
+/// Parse a user mention, such as `<@123>` or `<@!123>`, or a bare user ID.
+///
+/// # Errors
+///
+/// Returns [`ParseIdError::User`] if the argument is neither.
+pub fn user_id(arg: &str) -> Result<UserId, ParseIdError> {
+    id(arg, &["<@!", "<@"])
+        .map(UserId)
+        .ok_or(ParseIdError::User)
+}
+
+/// Resolve a user mention, such as `<@123>` or `<@!123>`, or a bare user ID.
+///
+/// Use [`resolve_user_with`] to also look up users by name.
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Invalid`] if the argument is neither.
+pub fn resolve_user(arg: &str) -> Result<Resolved<UserId>, ResolveError<UserId>> {
+    resolve(arg, &["<@!", "<@"], UserId).ok_or(ResolveError::Invalid)
+}
+
+/// Resolve a user mention, a bare user ID, or a tag of a user, such as
+/// `name#1234` or `@name#1234`.
+///
+/// The lookup is given the name and the discriminator of tags, and returns
+/// the users that match them; it isn't called for mentions and IDs.
+///
+/// # Examples
+///
+/// Look up users of the cache by tag:
+///
+/// ```rust
+/// use twilight_command_parser::parse::{self, Resolved};
+/// use twilight_model::id::UserId;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let users = vec![(UserId(1), "twilight", "0001"), (UserId(2), "twilight", "0002")];
+/// let lookup = |name: &str, discriminator: &str| {
+///     users
+///         .iter()
+///         .filter(|user| user.1 == name && user.2 == discriminator)
+///         .map(|user| user.0)
+///         .collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!(Resolved::Name(UserId(2)), parse::resolve_user_with("twilight#0002", lookup)?);
+/// assert_eq!(Resolved::Mention(UserId(3)), parse::resolve_user_with("<@3>", lookup)?);
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Ambiguous`] if the lookup returned multiple users.
+///
+/// Returns [`ResolveError::Invalid`] if the argument is neither a mention, an
+/// ID, nor a tag.
+///
+/// Returns [`ResolveError::NotFound`] if the lookup returned no users.
+pub fn resolve_user_with<I: IntoIterator<Item = UserId>>(
+    arg: &str,
+    lookup: impl FnOnce(&str, &str) -> I,
+) -> Result<Resolved<UserId>, ResolveError<UserId>> {
+    if let Some(resolved) = resolve(arg, &["<@!", "<@"], UserId) {
+        return Ok(resolved);
+    }
+
+    let tag = arg.strip_prefix('@').unwrap_or(arg);
+    let split = tag.rfind('#').ok_or(ResolveError::Invalid)?;
+    let (name, discriminator) = (&tag[..split], &tag[split + 1..]);
+
+    if name.is_empty()
+        || discriminator.len() != 4
+        || !discriminator.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return Err(ResolveError::Invalid);
+    }
+
+    single(lookup(name, discriminator)).map(Resolved::Name)
+}
+
+/// Resolve a channel mention, such as `<#123>`, or a bare channel ID.
+///
+/// Use [`resolve_channel_with`] to also look up channels by name.
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Invalid`] if the argument is neither.
+pub fn resolve_channel(arg: &str) -> Result<Resolved<ChannelId>, ResolveError<ChannelId>> {
+    resolve(arg, &["<#"], ChannelId).ok_or(ResolveError::Invalid)
+}
+
+/// Resolve a channel mention, a bare channel ID, or a channel name preceded
+/// by `#`, such as `#general`.
+///
+/// The lookup is given the name without the `#`, and returns the channels
+/// that match it; it isn't called for mentions and IDs.
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Ambiguous`] if the lookup returned multiple
+/// channels, as names of channels aren't unique.
+///
+/// Returns [`ResolveError::Invalid`] if the argument is neither a mention, an
+/// ID, nor a name.
+///
+/// Returns [`ResolveError::NotFound`] if the lookup returned no channels.
+pub fn resolve_channel_with<I: IntoIterator<Item = ChannelId>>(
+    arg: &str,
+    lookup: impl FnOnce(&str) -> I,
+) -> Result<Resolved<ChannelId>, ResolveError<ChannelId>> {
+    if let Some(resolved) = resolve(arg, &["<#"], ChannelId) {
+        return Ok(resolved);
+    }
+
+    match arg.strip_prefix('#') {
+        Some(name) if !name.is_empty() => single(lookup(name)).map(Resolved::Name),
+        _ => Err(ResolveError::Invalid),
+    }
+}
+
+/// Resolve a role mention, such as `<@&123>`, or a bare role ID.
+///
+/// Use [`resolve_role_with`] to also look up roles by name.
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Invalid`] if the argument is neither.
+pub fn resolve_role(arg: &str) -> Result<Resolved<RoleId>, ResolveError<RoleId>> {
+    resolve(arg, &["<@&"], RoleId).ok_or(ResolveError::Invalid)
+}
+
+/// Resolve a role mention, a bare role ID, or a role name, optionally
+/// preceded by `@`, such as `@Moderators` or `Moderators`.
+///
+/// The lookup is given the name without the `@`, and returns the roles that
+/// match it; it isn't called for mentions and IDs. Names with spaces need to
+/// be quoted to be a single argument.
+///
+/// # Errors
+///
+/// Returns [`ResolveError::Ambiguous`] if the lookup returned multiple roles,
+/// as names of roles aren't unique.
+///
+/// Returns [`ResolveError::Invalid`] if the argument is empty.
+///
+/// Returns [`ResolveError::NotFound`] if the lookup returned no roles.
+pub fn resolve_role_with<I: IntoIterator<Item = RoleId>>(
+    arg: &str,
+    lookup: impl FnOnce(&str) -> I,
+) -> Result<Resolved<RoleId>, ResolveError<RoleId>> {
+    if let Some(resolved) = resolve(arg, &["<@&"], RoleId) {
+        return Ok(resolved);
+    }
+
+    let name = arg.strip_prefix('@').unwrap_or(arg);
+
+    if name.is_empty() {
+        return Err(ResolveError::Invalid);
+    }
+
+    single(lookup(name)).map(Resolved::Name)
+}
+
+/// Resolve a mention starting with one of the prefixes, or a bare ID.
+fn resolve<T>(arg: &str, prefixes: &[&str], wrap: fn(u64) -> T) -> Option<Resolved<T>> {
+    if let Some(mention) = prefixes
+        .iter()
+        .find_map(|prefix| arg.strip_prefix(prefix)?.strip_suffix('>'))
+    {
+        return digits(mention).map(wrap).map(Resolved::Mention);
+    }
+
+    digits(arg).map(wrap).map(Resolved::Id)
+}
+
+/// Return the only target of a lookup.
+fn single<T>(matches: impl IntoIterator<Item = T>) -> Result<T, ResolveError<T>> {
+    let mut matches = matches.into_iter();
+    let first = matches.next().ok_or(ResolveError::NotFound)?;
+
+    match matches.next() {
+        Some(second) => {
+            let mut all = vec![first, second];
+            all.extend(matches);
+
+            Err(ResolveError::Ambiguous { matches: all })
+        }
+        None => Ok(first),
+    }
+}
+
+/// Parse an ID out of a mention starting with one of the prefixes, or a bare
+/// ID.
+fn id(arg: &str, prefixes: &[&str]) -> Option<u64> {
+    resolve(arg, prefixes, |id| id).as_ref().map(Resolved::id)
+}
+
+/// Parse a string made only of digits into an ID.
+fn digits(digits: &str) -> Option<u64> {
+    // Leading signs are accepted by `u64`'s parser, but not in IDs.
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+/// Parse a humane duration made of numbers followed by units, such as `7d`
+/// or `1h30m`.
+///
+/// The units are `s` for seconds, `m` for minutes, `h` for hours, `d` for days
+/// and `w` for weeks, and may be in any case. Components with the same unit
+/// are added together.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use twilight_command_parser::parse;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// assert_eq!(Duration::from_secs(7 * 86400), parse::duration("7d")?);
+/// assert_eq!(Duration::from_secs(5400), parse::duration("1h30m")?);
+/// assert!(parse::duration("soon").is_err());
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ParseDurationError::Empty`] if the duration has no components.
+///
+/// Returns [`ParseDurationError::MissingNumber`] if a unit isn't preceded by
+/// a number.
+///
+/// Returns [`ParseDurationError::MissingUnit`] if a number isn't followed by
+/// a unit.
+///
+/// Returns [`ParseDurationError::Overflow`] if the duration is too long.
+///
+/// Returns [`ParseDurationError::UnknownUnit`] if a unit isn't known.
+pub fn duration(arg: &str) -> Result<Duration, ParseDurationError> {
+    if arg.is_empty() {
+        return Err(ParseDurationError::Empty);
+    }
+
+    let mut secs = 0u64;
+    let mut number: Option<u64> = None;
+
+    for ch in arg.chars() {
+        if let Some(digit) = ch.to_digit(10) {
+            number = number
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|number| number.checked_add(u64::from(digit)));
+
+            if number.is_none() {
+                return Err(ParseDurationError::Overflow);
+            }
+
+            continue;
+        }
+
+        let unit = match ch.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return Err(ParseDurationError::UnknownUnit { unit: ch }),
+        };
+
+        let component = number
+            .take()
+            .ok_or(ParseDurationError::MissingNumber)?
+            .checked_mul(unit)
+            .ok_or(ParseDurationError::Overflow)?;
+
+        secs = secs
+            .checked_add(component)
+            .ok_or(ParseDurationError::Overflow)?;
+    }
+
+    if number.is_some() {
+        return Err(ParseDurationError::MissingUnit);
+    }
+
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseDurationError, ParseIdError, ResolveError, Resolved};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug, hash::Hash};
+    use twilight_model::id::{ChannelId, RoleId, UserId};
+
+    assert_impl_all!(ParseDurationError: Clone, Debug, Eq, Error, PartialEq, Send, Sync);
+    assert_impl_all!(ParseIdError: Clone, Debug, Eq, Error, PartialEq, Send, Sync);
+    assert_impl_all!(ResolveError<UserId>: Clone, Debug, Eq, Error, PartialEq, Send, Sync);
+    assert_impl_all!(Resolved<UserId>: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    fn users(name: &str, discriminator: &str) -> Vec<UserId> {
+        let users = [
+            (UserId(1), "twilight", "0001"),
+            (UserId(2), "twilight", "0002"),
+            (UserId(3), "a#b", "1234"),
+            // The same user may be returned by careless lookups.
+            (UserId(4), "dupe", "0004"),
+            (UserId(5), "dupe", "0004"),
+        ];
+
+        users
+            .iter()
+            .filter(|user| user.1 == name && user.2 == discriminator)
+            .map(|user| user.0)
+            .collect()
+    }
+
+    fn no_lookup<T>(_: &str) -> Vec<T> {
+        panic!("names aren't looked up for mentions and IDs");
+    }
+
+    #[test]
+    fn test_user_id() {
+        assert_eq!(Ok(UserId(123)), super::user_id("<@123>"));
+        assert_eq!(Ok(UserId(123)), super::user_id("<@!123>"));
+        assert_eq!(Ok(UserId(123)), super::user_id("123"));
+    }
+
+    #[test]
+    fn test_channel_id() {
+        assert_eq!(Ok(ChannelId(123)), super::channel_id("<#123>"));
+        assert_eq!(Ok(ChannelId(123)), super::channel_id("123"));
+    }
+
+    #[test]
+    fn test_role_id() {
+        assert_eq!(Ok(RoleId(123)), super::role_id("<@&123>"));
+        assert_eq!(Ok(RoleId(123)), super::role_id("123"));
+    }
+
+    #[test]
+    fn test_id_other_mentions() {
+        assert_eq!(Err(ParseIdError::User), super::user_id("<@&123>"));
+        assert_eq!(Err(ParseIdError::User), super::user_id("<#123>"));
+        assert_eq!(Err(ParseIdError::Channel), super::channel_id("<@123>"));
+        assert_eq!(Err(ParseIdError::Role), super::role_id("<@123>"));
+        assert_eq!(Err(ParseIdError::Role), super::role_id("<@!123>"));
+    }
+
+    #[test]
+    fn test_id_invalid() {
+        for arg in &[
+            "",
+            "<@>",
+            "<@123",
+            "@123>",
+            "<@!!123>",
+            "+123",
+            "-123",
+            "12a3",
+            "<@ 123>",
+            "18446744073709551616",
+        ] {
+            assert_eq!(Err(ParseIdError::User), super::user_id(arg), "{}", arg);
+        }
+
+        assert_eq!(Ok(UserId(u64::MAX)), super::user_id("18446744073709551615"));
+    }
+
+    #[test]
+    fn test_duration() {
+        let secs = |arg| super::duration(arg).map(|duration| duration.as_secs());
+
+        assert_eq!(Ok(30), secs("30s"));
+        assert_eq!(Ok(7 * 86400), secs("7d"));
+        assert_eq!(Ok(5400), secs("1h30m"));
+        assert_eq!(Ok(5400), secs("1H30M"));
+        assert_eq!(Ok(2 * 604_800 + 86400 + 1), secs("2w1d1s"));
+        assert_eq!(Ok(120), secs("1m1m"));
+        assert_eq!(Ok(0), secs("0s"));
+    }
+
+    #[test]
+    fn test_duration_invalid() {
+        assert_eq!(Err(ParseDurationError::Empty), super::duration(""));
+        assert_eq!(Err(ParseDurationError::MissingUnit), super::duration("7"));
+        assert_eq!(
+            Err(ParseDurationError::MissingUnit),
+            super::duration("1h30")
+        );
+        assert_eq!(Err(ParseDurationError::MissingNumber), super::duration("d"));
+        assert_eq!(
+            Err(ParseDurationError::MissingNumber),
+            super::duration("1hm")
+        );
+        assert_eq!(
+            Err(ParseDurationError::UnknownUnit { unit: 'y' }),
+            super::duration("1y")
+        );
+        assert_eq!(
+            Err(ParseDurationError::UnknownUnit { unit: ' ' }),
+            super::duration("1h 30m")
+        );
+        assert_eq!(
+            Err(ParseDurationError::Overflow),
+            super::duration("99999999999999999999s")
+        );
+        assert_eq!(
+            Err(ParseDurationError::Overflow),
+            super::duration("9999999999999999w")
+        );
+    }
+
+    #[test]
+    fn test_resolve_user() {
+        assert_eq!(
+            Ok(Resolved::Mention(UserId(1))),
+            super::resolve_user("<@1>")
+        );
+        assert_eq!(
+            Ok(Resolved::Mention(UserId(1))),
+            super::resolve_user("<@!1>")
+        );
+        assert_eq!(Ok(Resolved::Id(UserId(1))), super::resolve_user("1"));
+        assert_eq!(Ok(UserId(1)), super::resolve_user("<@!1>").map(|r| r.id()));
+
+        // Tags aren't resolved without a lookup.
+        for arg in &["twilight#0001", "<@&1>", "<#1>", "<@x>", "@1", ""] {
+            assert_eq!(
+                Err(ResolveError::Invalid),
+                super::resolve_user(arg),
+                "{}",
+                arg
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_user_with() {
+        let lookup = |name: &str, discriminator: &str| {
+            assert!(!name.starts_with("<@"), "mentions aren't looked up");
+
+            users(name, discriminator)
+        };
+
+        assert_eq!(
+            Ok(Resolved::Mention(UserId(9))),
+            super::resolve_user_with("<@!9>", lookup)
+        );
+        assert_eq!(
+            Ok(Resolved::Id(UserId(9))),
+            super::resolve_user_with("9", lookup)
+        );
+        assert_eq!(
+            Ok(Resolved::Name(UserId(1))),
+            super::resolve_user_with("twilight#0001", lookup)
+        );
+        assert_eq!(
+            Ok(Resolved::Name(UserId(2))),
+            super::resolve_user_with("@twilight#0002", lookup)
+        );
+
+        // Names may contain `#`, but discriminators may not.
+        assert_eq!(
+            Ok(Resolved::Name(UserId(3))),
+            super::resolve_user_with("a#b#1234", lookup)
+        );
+
+        assert_eq!(
+            Err(ResolveError::NotFound),
+            super::resolve_user_with("twilight#0003", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::Ambiguous {
+                matches: vec![UserId(4), UserId(5)],
+            }),
+            super::resolve_user_with("dupe#0004", lookup)
+        );
+
+        for arg in &[
+            "twilight",
+            "#0001",
+            "twilight#1",
+            "twilight#00001",
+            "x#12a4",
+            "<@&1>",
+        ] {
+            assert_eq!(
+                Err(ResolveError::Invalid),
+                super::resolve_user_with(arg, lookup),
+                "{}",
+                arg
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_channel() {
+        assert_eq!(
+            Ok(Resolved::Mention(ChannelId(1))),
+            super::resolve_channel("<#1>")
+        );
+        assert_eq!(Ok(Resolved::Id(ChannelId(1))), super::resolve_channel("1"));
+        assert_eq!(
+            Err(ResolveError::Invalid),
+            super::resolve_channel("#general")
+        );
+        assert_eq!(Err(ResolveError::Invalid), super::resolve_channel("<@1>"));
+
+        assert_eq!(
+            Ok(Resolved::Mention(ChannelId(1))),
+            super::resolve_channel_with("<#1>", no_lookup)
+        );
+        assert_eq!(
+            Ok(Resolved::Id(ChannelId(1))),
+            super::resolve_channel_with("1", no_lookup)
+        );
+
+        let lookup = |name: &str| match name {
+            "general" => vec![ChannelId(2)],
+            "logs" => vec![ChannelId(3), ChannelId(4)],
+            _ => Vec::new(),
+        };
+
+        assert_eq!(
+            Ok(Resolved::Name(ChannelId(2))),
+            super::resolve_channel_with("#general", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::Ambiguous {
+                matches: vec![ChannelId(3), ChannelId(4)],
+            }),
+            super::resolve_channel_with("#logs", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::NotFound),
+            super::resolve_channel_with("#random", lookup)
+        );
+
+        for arg in &["general", "#", "<#x>", ""] {
+            assert_eq!(
+                Err(ResolveError::Invalid),
+                super::resolve_channel_with(arg, lookup),
+                "{}",
+                arg
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_role() {
+        assert_eq!(
+            Ok(Resolved::Mention(RoleId(1))),
+            super::resolve_role("<@&1>")
+        );
+        assert_eq!(Ok(Resolved::Id(RoleId(1))), super::resolve_role("1"));
+        assert_eq!(
+            Err(ResolveError::Invalid),
+            super::resolve_role("Moderators")
+        );
+        assert_eq!(Err(ResolveError::Invalid), super::resolve_role("<@1>"));
+
+        assert_eq!(
+            Ok(Resolved::Mention(RoleId(1))),
+            super::resolve_role_with("<@&1>", no_lookup)
+        );
+
+        let lookup = |name: &str| match name {
+            "Moderators" => vec![RoleId(2)],
+            "Muted" => vec![RoleId(3), RoleId(4)],
+            _ => Vec::new(),
+        };
+
+        assert_eq!(
+            Ok(Resolved::Name(RoleId(2))),
+            super::resolve_role_with("@Moderators", lookup)
+        );
+        assert_eq!(
+            Ok(Resolved::Name(RoleId(2))),
+            super::resolve_role_with("Moderators", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::Ambiguous {
+                matches: vec![RoleId(3), RoleId(4)],
+            }),
+            super::resolve_role_with("Muted", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::NotFound),
+            super::resolve_role_with("<@1>", lookup)
+        );
+        assert_eq!(
+            Err(ResolveError::Invalid),
+            super::resolve_role_with("@", lookup)
+        );
+    }
+
+    #[test]
+    fn test_resolve_error_display() {
+        let error = ResolveError::Ambiguous {
+            matches: vec![UserId(1), UserId(2)],
+        };
+        assert_eq!("the name matches 2 targets", error.to_string());
+        assert_eq!(
+            "not a mention or ID",
+            ResolveError::<UserId>::Invalid.to_string()
+        );
+    }
+}
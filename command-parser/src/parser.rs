@@ -6,9 +6,29 @@ use crate::{Arguments, CommandParserConfig};
 pub struct Command<'a> {
     /// A lazy iterator of command arguments. Refer to its documentation on
     /// how to use it.
+    ///
+    /// The [spans] of the arguments are within the parsed content.
+    ///
+    /// [spans]: Arguments::next_with_span
     pub arguments: Arguments<'a>,
+    /// Whether the command was called by mentioning the configured user,
+    /// in which case the [`prefix`] is the mention.
+    ///
+    /// [`prefix`]: Self::prefix
+    pub mention: bool,
     /// The name of the command that was called.
     pub name: &'a str,
+    /// The path of names of the command and of the deepest subcommand that
+    /// was called, such as `["config", "prefix", "set"]`.
+    ///
+    /// The path only has the name of the command if no subcommand was
+    /// matched. The names are the configured ones, and the [`arguments`] are
+    /// positioned after the last one.
+    ///
+    /// Refer to [`CommandParserConfig::add_subcommand`] for more information.
+    ///
+    /// [`arguments`]: Self::arguments
+    pub path: Vec<&'a str>,
     /// The prefix used to call the command.
     pub prefix: &'a str,
 }
@@ -83,10 +103,22 @@ impl<'a> Parser<'a> {
     ///
     /// If a matching prefix or command weren't found, then `None` is returned.
     ///
+    /// If a [mention prefix] is configured, then it's tried before the other
+    /// prefixes.
+    ///
     /// Refer to the struct-level documentation on how to use this.
+    ///
+    /// [mention prefix]: CommandParserConfig::set_mention_prefix
     pub fn parse(&'a self, buf: &'a str) -> Option<Command<'a>> {
-        let prefix = self.find_prefix(buf)?;
-        self.parse_with_prefix(prefix, buf)
+        if let Some(len) = self.mention_len(buf) {
+            let mut command = self.parse_after_prefix(&buf[..len], buf, len)?;
+            command.mention = true;
+
+            return Some(command);
+        }
+
+        let (prefix, len) = self.find_prefix(buf)?;
+        self.parse_after_prefix(prefix, buf, len)
     }
 
     /// Parse a command out of a buffer with a specific prefix.
@@ -95,6 +127,9 @@ impl<'a> Parser<'a> {
     /// to parse the message, this can be used to have a kind of dynamic
     /// prefixes.
     ///
+    /// The prefix is matched regardless of case if the configuration's
+    /// [prefixes are case-insensitive].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -113,47 +148,180 @@ impl<'a> Parser<'a> {
     /// # Some(())
     /// # }
     /// ```
+    ///
+    /// [prefixes are case-insensitive]: CommandParserConfig::set_prefixes_case_insensitive
     pub fn parse_with_prefix(&'a self, prefix: &'a str, buf: &'a str) -> Option<Command<'a>> {
-        if !buf.starts_with(prefix) {
-            return None;
-        }
+        let len = self.prefix_len(prefix, buf)?;
+
+        self.parse_after_prefix(prefix, buf, len)
+    }
 
-        let mut idx = prefix.len();
+    /// Parse a command out of a buffer with one of several specific prefixes.
+    ///
+    /// Like [`parse_with_prefix`], the configured prefixes are bypassed, which
+    /// can be used to look up the prefixes of a guild before parsing. The
+    /// first prefix that the buffer starts with is used, and is the
+    /// [`prefix`] of the returned command.
+    ///
+    /// # Examples
+    ///
+    /// Parse messages of guilds that configured different prefixes:
+    ///
+    /// ```rust
+    /// # use twilight_command_parser::{CommandParserConfig, Parser};
+    /// # fn example() -> Option<()> {
+    /// let mut config = CommandParserConfig::new();
+    /// config.add_command("echo", false);
+    ///
+    /// let parser = Parser::new(config);
+    ///
+    /// let command = parser.parse_with_prefixes(vec!["!", "?"], "?echo foo")?;
+    /// assert_eq!("?", command.prefix);
+    ///
+    /// let command = parser.parse_with_prefixes(vec!["$"], "$ echo foo")?;
+    /// assert_eq!("$", command.prefix);
+    /// assert_eq!("echo", command.name);
+    /// # Some(())
+    /// # }
+    /// ```
+    ///
+    /// [`parse_with_prefix`]: Self::parse_with_prefix
+    /// [`prefix`]: Command::prefix
+    pub fn parse_with_prefixes(
+        &'a self,
+        prefixes: impl IntoIterator<Item = &'a str>,
+        buf: &'a str,
+    ) -> Option<Command<'a>> {
+        let (prefix, len) = prefixes
+            .into_iter()
+            .find_map(|prefix| self.prefix_len(prefix, buf).map(|len| (prefix, len)))?;
+
+        self.parse_after_prefix(prefix, buf, len)
+    }
+
+    /// Parse a command out of a buffer starting with a prefix that is a
+    /// number of bytes long in the buffer.
+    fn parse_after_prefix(
+        &'a self,
+        prefix: &'a str,
+        buf: &'a str,
+        mut idx: usize,
+    ) -> Option<Command<'a>> {
         let command_buf = buf.get(idx..)?;
-        let command = self.find_command(command_buf)?;
+        let (command, len) = self.find_command(command_buf)?;
 
-        idx += command.len();
+        // The command name in the buffer may have another length than the
+        // configured name if it's in another case.
+        idx += len;
 
         // Advance from the amount of whitespace that was between the prefix and
         // the command name.
         idx += command_buf.len() - command_buf.trim_start().len();
 
+        let mut path = vec![command];
+
+        // Words that aren't subcommands of the deepest subcommand so far are
+        // left in the arguments.
+        while let Some((subcommand, len)) = self.find_subcommand(&path, buf.get(idx..)?) {
+            path.push(subcommand);
+            idx += len;
+        }
+
         Some(Command {
-            arguments: Arguments::new(buf.get(idx..)?),
+            arguments: Arguments::with_offset(buf.get(idx..)?, idx),
+            mention: false,
             name: command,
+            path,
             prefix,
         })
     }
 
-    fn find_command(&'a self, buf: &'a str) -> Option<&'a str> {
+    /// Find the subcommand of a path that a buffer starts with, returning it
+    /// and the length of its name in the buffer, including the whitespace
+    /// before it.
+    fn find_subcommand(&'a self, path: &[&str], buf: &str) -> Option<(&'a str, usize)> {
+        let trimmed = buf.trim_start();
+        let word = trimmed.split_whitespace().next()?;
+
+        self.config.subcommands.iter().find_map(|subcommand| {
+            if subcommand.name == *word && subcommand.parent.iter().eq(path) {
+                Some((
+                    subcommand.name.as_ref(),
+                    buf.len() - trimmed.len() + word.len(),
+                ))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Find the command that a buffer starts with, returning it and the length
+    /// of its name in the buffer.
+    fn find_command(&'a self, buf: &'a str) -> Option<(&'a str, usize)> {
         let buf = buf.split_whitespace().next()?;
         self.config.commands.iter().find_map(|command| {
             if command == buf {
-                Some(command.as_ref())
+                Some((command.as_ref(), buf.len()))
             } else {
                 None
             }
         })
     }
 
-    fn find_prefix(&self, buf: &str) -> Option<&str> {
+    /// Returns the length of the mention of the configured user that a buffer
+    /// starts with, if any.
+    fn mention_len(&self, buf: &str) -> Option<usize> {
+        let user_id = self.config.mention?;
+        let rest = buf.strip_prefix("<@")?;
+        let rest = rest.strip_prefix('!').unwrap_or(rest);
+        let end = rest.find('>')?;
+        let id = &rest[..end];
+
+        if id.is_empty() || !id.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        if id.parse::<u64>().ok()? != user_id.0 {
+            return None;
+        }
+
+        Some(buf.len() - rest.len() + end + 1)
+    }
+
+    /// Find the prefix that a buffer starts with, returning it and its length
+    /// in the buffer.
+    fn find_prefix(&self, buf: &str) -> Option<(&str, usize)> {
         self.config.prefixes.iter().find_map(|prefix| {
-            if buf.starts_with(prefix.as_ref()) {
-                Some(prefix.as_ref())
+            self.prefix_len(prefix, buf)
+                .map(|len| (prefix.as_ref(), len))
+        })
+    }
+
+    /// Returns the length of a prefix in a buffer if the buffer starts with
+    /// it.
+    ///
+    /// Prefixes are compared character by character if they're
+    /// case-insensitive, so that messages don't need to be lowercased.
+    fn prefix_len(&self, prefix: &str, buf: &str) -> Option<usize> {
+        if !self.config.prefixes_case_insensitive {
+            return if buf.starts_with(prefix) {
+                Some(prefix.len())
             } else {
                 None
+            };
+        }
+
+        let mut chars = buf.char_indices();
+
+        for expected in prefix.chars() {
+            let (_, ch) = chars.next()?;
+
+            if ch != expected && !ch.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
             }
-        })
+        }
+
+        Some(chars.next().map_or(buf.len(), |(idx, _)| idx))
     }
 }
 
@@ -168,8 +336,9 @@ mod tests {
     use crate::{Command, CommandParserConfig, Parser};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::fmt::Debug;
+    use twilight_model::id::UserId;
 
-    assert_fields!(Command<'_>: arguments, name, prefix);
+    assert_fields!(Command<'_>: arguments, mention, name, path, prefix);
     assert_impl_all!(Command<'_>: Clone, Debug, Send, Sync);
     assert_impl_all!(Parser<'_>: Clone, Debug, Send, Sync);
 
@@ -244,6 +413,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mixed_case_commands() {
+        let mut config = CommandParserConfig::new();
+        config.add_prefix("!");
+        config.add_command("ban", false);
+        config.add_command("kick", true);
+        let parser = Parser::new(config);
+
+        for message in &["!ban @user", "!Ban @user", "!BAN @user"] {
+            let mut command = parser.parse(message).expect("command not matched");
+            assert_eq!("ban", command.name);
+            assert_eq!(Some("@user"), command.arguments.next());
+        }
+
+        assert!(parser.parse("!kick @user").is_some());
+        assert!(parser.parse("!Kick @user").is_none());
+        assert!(parser.parse("!KICK @user").is_none());
+    }
+
+    #[test]
+    fn test_prefixes_case_sensitive_by_default() {
+        let mut config = CommandParserConfig::new();
+        config.add_prefix("bot!");
+        config.add_command("ping", false);
+        let parser = Parser::new(config);
+
+        assert!(!parser.config().prefixes_case_insensitive());
+        assert!(parser.parse("bot!ping").is_some());
+        assert!(parser.parse("Bot!ping").is_none());
+        assert!(parser.parse("BOT!PING").is_none());
+        assert!(parser.parse_with_prefix("=", "=PING").is_some());
+    }
+
+    #[test]
+    fn test_prefixes_case_insensitive() {
+        let mut config = CommandParserConfig::new();
+        config.add_prefix("bot!");
+        config.add_prefix("\u{3b4}>");
+        config.add_command("ping", true);
+        config.set_prefixes_case_insensitive(true);
+        let parser = Parser::new(config);
+
+        for message in &["bot!ping", "Bot!ping", "BOT!ping"] {
+            let command = parser.parse(message).expect("prefix not matched");
+            assert_eq!("bot!", command.prefix);
+            assert_eq!("ping", command.name);
+        }
+
+        // Commands keep their own case sensitivity.
+        assert!(parser.parse("BOT!PING").is_none());
+
+        let command = parser.parse("\u{394}>ping").expect("prefix not matched");
+        assert_eq!("\u{3b4}>", command.prefix);
+
+        let command = parser.parse_with_prefix("x!", "X!ping foo").unwrap();
+        assert_eq!("x!", command.prefix);
+        assert_eq!("foo", command.arguments.as_str());
+    }
+
     #[test]
     fn test_simple_config_no_prefix() {
         let mut parser = simple_config();
@@ -289,6 +517,116 @@ mod tests {
         assert_eq!("echo", command.name);
     }
 
+    #[test]
+    fn test_dynamic_prefixes() {
+        let parser = simple_config();
+        let first = ["?", "pls "];
+        let second = ["$"];
+
+        let command = parser
+            .parse_with_prefixes(first.iter().copied(), "pls echo foo")
+            .unwrap();
+        assert_eq!("pls ", command.prefix);
+        assert_eq!("echo", command.name);
+        assert_eq!("foo", command.arguments.as_str());
+
+        let command = parser
+            .parse_with_prefixes(second.iter().copied(), "$  echo bar")
+            .unwrap();
+        assert_eq!("$", command.prefix);
+        assert_eq!("echo", command.name);
+        assert_eq!("bar", command.arguments.as_str());
+
+        // The configured prefixes are bypassed.
+        assert!(parser
+            .parse_with_prefixes(first.iter().copied(), "$echo foo")
+            .is_none());
+        assert!(parser
+            .parse_with_prefixes(second.iter().copied(), "!echo foo")
+            .is_none());
+        assert!(parser
+            .parse_with_prefixes(Vec::new(), "!echo foo")
+            .is_none());
+    }
+
+    fn mention_config() -> Parser<'static> {
+        let mut config = CommandParserConfig::new();
+        config.add_prefix("!");
+        config.add_prefix("<<");
+        config.add_command("ping", false);
+        config.set_mention_prefix(UserId(123));
+
+        Parser::new(config)
+    }
+
+    #[test]
+    fn test_mention_prefix() {
+        let parser = mention_config();
+
+        for (message, mention) in &[
+            ("<@123> ping foo", "<@123>"),
+            ("<@!123> ping foo", "<@!123>"),
+        ] {
+            let command = parser.parse(message).expect("mention not matched");
+            assert!(command.mention);
+            assert_eq!(*mention, command.prefix);
+            assert_eq!("ping", command.name);
+            assert_eq!("foo", command.arguments.as_str());
+        }
+
+        let command = parser.parse("!ping").unwrap();
+        assert!(!command.mention);
+        assert_eq!("!", command.prefix);
+    }
+
+    #[test]
+    fn test_mention_prefix_whitespace() {
+        let parser = mention_config();
+
+        let command = parser.parse("<@!123>ping").unwrap();
+        assert_eq!("ping", command.name);
+
+        let command = parser.parse("<@123>  \n ping   foo bar").unwrap();
+        assert_eq!("ping", command.name);
+        assert_eq!("foo bar", command.arguments.as_str());
+    }
+
+    #[test]
+    fn test_mention_prefix_no_match() {
+        let parser = mention_config();
+
+        // Mentions within the message aren't prefixes.
+        assert!(parser.parse("hey <@123> ping").is_none());
+        assert!(parser.parse("ping <@!123>").is_none());
+
+        // Neither are mentions of other users or malformed mentions.
+        assert!(parser.parse("<@456> ping").is_none());
+        assert!(parser.parse("<@+123> ping").is_none());
+        assert!(parser.parse("<@123 ping").is_none());
+        assert!(parser.parse("<@&123> ping").is_none());
+
+        // Prefixes without a configured mention aren't affected.
+        assert!(simple_config().parse("<@123> echo").is_none());
+    }
+
+    #[test]
+    fn test_mention_prefix_angle_bracket_prefixes() {
+        let mut parser = mention_config();
+
+        // Prefixes beginning with `<` still match if the mention doesn't.
+        let command = parser.parse("<<ping").unwrap();
+        assert!(!command.mention);
+        assert_eq!("<<", command.prefix);
+
+        parser.config_mut().add_prefix("<@");
+        let command = parser.parse("<@123> ping").unwrap();
+        assert!(command.mention);
+
+        let command = parser.parse("<@ping").unwrap();
+        assert!(!command.mention);
+        assert_eq!("<@", command.prefix);
+    }
+
     #[test]
     fn test_prefix_mention() {
         let mut config = CommandParserConfig::new();
@@ -300,10 +638,99 @@ mod tests {
             mut arguments,
             name,
             prefix,
+            ..
         } = parser.parse("foo dump test").unwrap();
         assert_eq!("foo", prefix);
         assert_eq!("dump", name);
         assert_eq!(Some("test"), arguments.next());
         assert!(arguments.next().is_none());
     }
+
+    fn subcommand_config() -> Parser<'static> {
+        let mut config = CommandParserConfig::new();
+        config.add_prefix("!");
+        config.add_command("config", false);
+        config.add_command("ping", false);
+        config.add_subcommand(&["config"], "prefix", false);
+        config.add_subcommand(&["config"], "role", true);
+        config.add_subcommand(&["config", "prefix"], "set", false);
+        config.add_subcommand(&["config", "prefix"], "reset", false);
+
+        Parser::new(config)
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let parser = subcommand_config();
+
+        let command = parser.parse("!config prefix set !").unwrap();
+        assert_eq!("config", command.name);
+        assert_eq!(vec!["config", "prefix", "set"], command.path);
+        assert_eq!("!", command.arguments.as_str());
+
+        let command = parser.parse("!CONFIG  Prefix\nRESET").unwrap();
+        assert_eq!(vec!["config", "prefix", "reset"], command.path);
+        assert!(command.arguments.as_str().is_empty());
+
+        let command = parser.parse("!config role @Moderators").unwrap();
+        assert_eq!(vec!["config", "role"], command.path);
+        assert_eq!("@Moderators", command.arguments.as_str());
+
+        let command = parser.parse("!ping prefix").unwrap();
+        assert_eq!(vec!["ping"], command.path);
+        assert_eq!("prefix", command.arguments.as_str());
+    }
+
+    #[test]
+    fn test_subcommands_fallback() {
+        let parser = subcommand_config();
+
+        // Unknown subcommands are left in the arguments of the deepest
+        // matching parent.
+        let mut command = parser.parse("!config prefix toggle on").unwrap();
+        assert_eq!(vec!["config", "prefix"], command.path);
+        assert_eq!(Some("toggle"), command.arguments.next());
+        assert_eq!(Some("on"), command.arguments.next());
+
+        let command = parser.parse("!config colour set").unwrap();
+        assert_eq!(vec!["config"], command.path);
+        assert_eq!("colour set", command.arguments.as_str());
+
+        // Subcommands are only matched as whole words of their parent.
+        let command = parser.parse("!config prefixset").unwrap();
+        assert_eq!(vec!["config"], command.path);
+
+        let command = parser.parse("!config set").unwrap();
+        assert_eq!(vec!["config"], command.path);
+
+        let command = parser.parse("!config Role").unwrap();
+        assert_eq!(vec!["config"], command.path);
+        assert_eq!("Role", command.arguments.as_str());
+
+        let command = parser.parse("!config").unwrap();
+        assert_eq!(vec!["config"], command.path);
+    }
+
+    #[test]
+    fn test_argument_spans() {
+        let parser = subcommand_config();
+        let content = "!config  prefix  set   \u{1f44d}  'a b'";
+
+        let mut command = parser.parse(content).unwrap();
+        let (arg, span) = command.arguments.next_with_span().unwrap();
+        assert_eq!("\u{1f44d}", arg);
+        assert_eq!(23..27, span);
+
+        let (_, span) = command.arguments.next_with_span().unwrap();
+        assert_eq!("a b", &content[span]);
+
+        let content = "<@123>\n\nping  ban <@1>  spam  links";
+        let parser = mention_config();
+        let mut command = parser.parse(content).unwrap();
+        assert_eq!(Some(("ban", 14..17)), command.arguments.next_with_span());
+        assert_eq!(Some("<@1>  spam  links"), command.arguments.remainder());
+
+        let error = command.arguments.next_parsed::<u64>().unwrap().unwrap_err();
+        assert_eq!("<@1>", &content[error.span]);
+    }
 }
@@ -30,7 +30,11 @@ impl Display for EmbedAuthorNameError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::Empty { .. } => f.write_str("the author name is empty"),
-            Self::TooLong { .. } => f.write_str("the author name is too long"),
+            Self::TooLong { name } => f.write_fmt(format_args!(
+                "the author name is {} characters long, but the limit is {}",
+                name.chars().count(),
+                EmbedAuthorBuilder::NAME_LENGTH_LIMIT,
+            )),
         }
     }
 }
@@ -194,6 +198,13 @@ mod tests {
             EmbedAuthorBuilder::new().name("a".repeat(257)),
             Err(EmbedAuthorNameError::TooLong { .. })
         ));
+        assert_eq!(
+            "the author name is 257 characters long, but the limit is 256",
+            EmbedAuthorBuilder::new()
+                .name("a".repeat(257))
+                .unwrap_err()
+                .to_string(),
+        );
     }
 
     #[test]
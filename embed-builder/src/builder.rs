@@ -37,8 +37,16 @@ pub enum EmbedBuildError {
 impl Display for EmbedBuildError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Self::ContentTooLarge { .. } => f.write_str("the content of the embed is too large"),
-            Self::TooManyFields { .. } => f.write_str("more than 25 fields were provided"),
+            Self::ContentTooLarge { length } => f.write_fmt(format_args!(
+                "the content of the embed is {} characters long, but the limit is {}",
+                length,
+                EmbedBuilder::EMBED_LENGTH_LIMIT,
+            )),
+            Self::TooManyFields { fields } => f.write_fmt(format_args!(
+                "{} fields were provided, but the limit is {}",
+                fields.len(),
+                EmbedBuilder::EMBED_FIELD_LIMIT,
+            )),
         }
     }
 }
@@ -64,7 +72,11 @@ pub enum EmbedColorError {
 impl Display for EmbedColorError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Self::NotRgb { color } => f.write_fmt(format_args!("the color {} is invalid", color)),
+            Self::NotRgb { color } => f.write_fmt(format_args!(
+                "the color {:#x} is invalid, as the maximum is {:#x}",
+                color,
+                EmbedBuilder::COLOR_MAXIMUM,
+            )),
             Self::Zero => f.write_str("the given color value is 0, which is not acceptable"),
         }
     }
@@ -95,7 +107,11 @@ impl Display for EmbedDescriptionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::Empty { .. } => f.write_str("the description is empty"),
-            Self::TooLong { .. } => f.write_str("the description is too long"),
+            Self::TooLong { description } => f.write_fmt(format_args!(
+                "the description is {} characters long, but the limit is {}",
+                description.chars().count(),
+                EmbedBuilder::DESCRIPTION_LENGTH_LIMIT,
+            )),
         }
     }
 }
@@ -124,7 +140,11 @@ impl Display for EmbedTitleError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::Empty { .. } => f.write_str("the title is empty"),
-            Self::TooLong { .. } => f.write_str("the title is too long"),
+            Self::TooLong { title } => f.write_fmt(format_args!(
+                "the title is {} characters long, but the limit is {}",
+                title.chars().count(),
+                EmbedBuilder::TITLE_LENGTH_LIMIT,
+            )),
         }
     }
 }
@@ -216,6 +236,50 @@ impl EmbedBuilder {
             });
         }
 
+        let total = self.length();
+
+        if total > Self::EMBED_LENGTH_LIMIT {
+            return Err(EmbedBuildError::ContentTooLarge { length: total });
+        }
+
+        Ok(self.0)
+    }
+
+    /// Total textual length of the embed so far.
+    ///
+    /// This is checked against [`EMBED_LENGTH_LIMIT`] when the embed is
+    /// [built], and can be used to check whether more content fits in the
+    /// embed before it's added.
+    ///
+    /// # Examples
+    ///
+    /// Add fields while they fit in the embed:
+    ///
+    /// ```rust
+    /// use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut embed = EmbedBuilder::new().title("Infractions")?;
+    ///
+    /// for idx in 1..=EmbedBuilder::EMBED_FIELD_LIMIT {
+    ///     let name = format!("Case {}", idx);
+    ///     let value = "a".repeat(500);
+    ///
+    ///     if embed.length() + name.len() + value.len() > EmbedBuilder::EMBED_LENGTH_LIMIT {
+    ///         break;
+    ///     }
+    ///
+    ///     embed = embed.field(EmbedFieldBuilder::new(name, value)?);
+    /// }
+    ///
+    /// assert!(embed.build().is_ok());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`EMBED_LENGTH_LIMIT`]: Self::EMBED_LENGTH_LIMIT
+    /// [built]: Self::build
+    #[must_use]
+    pub fn length(&self) -> usize {
         let mut total = 0;
 
         if let Some(name) = self
@@ -243,11 +307,7 @@ impl EmbedBuilder {
             total += title.chars().count();
         }
 
-        if total > Self::EMBED_LENGTH_LIMIT {
-            return Err(EmbedBuildError::ContentTooLarge { length: total });
-        }
-
-        Ok(self.0)
+        total
     }
 
     /// Set the author.
@@ -593,7 +653,10 @@ mod tests {
     use super::{
         EmbedBuildError, EmbedBuilder, EmbedColorError, EmbedDescriptionError, EmbedTitleError,
     };
-    use crate::{field::EmbedFieldBuilder, footer::EmbedFooterBuilder, image_source::ImageSource};
+    use crate::{
+        author::EmbedAuthorBuilder, field::EmbedFieldBuilder, footer::EmbedFooterBuilder,
+        image_source::ImageSource,
+    };
     use static_assertions::{assert_fields, assert_impl_all, const_assert};
     use std::{convert::TryFrom, error::Error, fmt::Debug};
     use twilight_model::channel::embed::{Embed, EmbedField, EmbedFooter};
@@ -700,6 +763,102 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_limits() -> Result<(), Box<dyn Error>> {
+        assert!(EmbedBuilder::new()
+            .color(EmbedBuilder::COLOR_MAXIMUM)
+            .is_ok());
+        assert!(matches!(
+            EmbedBuilder::new().color(EmbedBuilder::COLOR_MAXIMUM + 1),
+            Err(EmbedColorError::NotRgb { color }) if color == 0x01_00_00_00
+        ));
+        assert!(EmbedBuilder::new()
+            .description("a".repeat(EmbedBuilder::DESCRIPTION_LENGTH_LIMIT))
+            .is_ok());
+        assert!(EmbedBuilder::new()
+            .title("a".repeat(EmbedBuilder::TITLE_LENGTH_LIMIT))
+            .is_ok());
+        // Lengths are in characters, not bytes.
+        assert!(EmbedBuilder::new()
+            .title("é".repeat(EmbedBuilder::TITLE_LENGTH_LIMIT))
+            .is_ok());
+
+        let field = EmbedFieldBuilder::new("a", "b")?;
+        let mut builder = EmbedBuilder::new();
+
+        for _ in 0..EmbedBuilder::EMBED_FIELD_LIMIT {
+            builder = builder.field(field.clone());
+        }
+
+        assert!(builder.clone().build().is_ok());
+        assert!(matches!(
+            builder.field(field).build(),
+            Err(EmbedBuildError::TooManyFields { fields })
+            if fields.len() == EmbedBuilder::EMBED_FIELD_LIMIT + 1
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_length() -> Result<(), Box<dyn Error>> {
+        let author = EmbedAuthorBuilder::new().name("a".repeat(256))?;
+        let builder = EmbedBuilder::new()
+            .author(author)
+            .title("b".repeat(256))?
+            .description("c".repeat(2048))?
+            .footer(EmbedFooterBuilder::new("d".repeat(2048))?)
+            .field(EmbedFieldBuilder::new("e".repeat(256), "f".repeat(1024))?);
+        assert_eq!(5888, builder.length());
+
+        let fits = builder
+            .clone()
+            .field(EmbedFieldBuilder::new("g".repeat(12), "h".repeat(100))?);
+        assert_eq!(EmbedBuilder::EMBED_LENGTH_LIMIT, fits.length());
+        assert!(fits.build().is_ok());
+
+        let too_large = builder.field(EmbedFieldBuilder::new("g".repeat(13), "h".repeat(100))?);
+        assert_eq!(
+            EmbedBuildError::ContentTooLarge { length: 6001 },
+            too_large.build().unwrap_err(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            "the content of the embed is 6001 characters long, but the limit is 6000",
+            EmbedBuildError::ContentTooLarge { length: 6001 }.to_string(),
+        );
+        assert_eq!(
+            "0 fields were provided, but the limit is 25",
+            EmbedBuildError::TooManyFields { fields: Vec::new() }.to_string(),
+        );
+        assert_eq!(
+            "the color 0x1000000 is invalid, as the maximum is 0xffffff",
+            EmbedColorError::NotRgb {
+                color: 0x01_00_00_00
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "the description is 2049 characters long, but the limit is 2048",
+            EmbedBuilder::new()
+                .description("a".repeat(2049))
+                .unwrap_err()
+                .to_string(),
+        );
+        assert_eq!(
+            "the title is 257 characters long, but the limit is 256",
+            EmbedBuilder::new()
+                .title("a".repeat(257))
+                .unwrap_err()
+                .to_string(),
+        );
+    }
+
     #[test]
     fn test_builder() -> Result<(), Box<dyn Error>> {
         let footer_image = ImageSource::url(
@@ -48,9 +48,17 @@ impl Display for EmbedFieldError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::NameEmpty { .. } => f.write_str("the field name is empty"),
-            Self::NameTooLong { .. } => f.write_str("the field name is too long"),
+            Self::NameTooLong { name, .. } => f.write_fmt(format_args!(
+                "the field name is {} characters long, but the limit is {}",
+                name.chars().count(),
+                EmbedFieldBuilder::NAME_LENGTH_LIMIT,
+            )),
             Self::ValueEmpty { .. } => f.write_str("the field value is empty"),
-            Self::ValueTooLong { .. } => f.write_str("the field value is too long"),
+            Self::ValueTooLong { value, .. } => f.write_fmt(format_args!(
+                "the field value is {} characters long, but the limit is {}",
+                value.chars().count(),
+                EmbedFieldBuilder::VALUE_LENGTH_LIMIT,
+            )),
         }
     }
 }
@@ -216,6 +224,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_new_limits() {
+        assert!(EmbedFieldBuilder::new("a".repeat(256), "a".repeat(1024)).is_ok());
+        assert_eq!(
+            "the field name is 257 characters long, but the limit is 256",
+            EmbedFieldBuilder::new("a".repeat(257), "a")
+                .unwrap_err()
+                .to_string(),
+        );
+        assert_eq!(
+            "the field value is 1025 characters long, but the limit is 1024",
+            EmbedFieldBuilder::new("a", "a".repeat(1025))
+                .unwrap_err()
+                .to_string(),
+        );
+    }
+
     #[test]
     fn test_builder_inline() -> Result<(), Box<dyn Error>> {
         let expected = EmbedField {
@@ -30,7 +30,11 @@ impl Display for EmbedFooterTextError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::Empty { .. } => f.write_str("the footer text is empty"),
-            Self::TooLong { .. } => f.write_str("the footer text is too long"),
+            Self::TooLong { text } => f.write_fmt(format_args!(
+                "the footer text is {} characters long, but the limit is {}",
+                text.chars().count(),
+                EmbedFooterBuilder::TEXT_LENGTH_LIMIT,
+            )),
         }
     }
 }
@@ -159,6 +163,14 @@ mod tests {
             if text.len() == too_long_len
         ));
 
+        assert!(EmbedFooterBuilder::new("a".repeat(too_long_len - 1)).is_ok());
+        assert_eq!(
+            "the footer text is 2049 characters long, but the limit is 2048",
+            EmbedFooterBuilder::new("a".repeat(too_long_len))
+                .unwrap_err()
+                .to_string(),
+        );
+
         let expected = EmbedFooter {
             icon_url: None,
             proxy_icon_url: None,
@@ -36,6 +36,45 @@
 //! # Ok(()) }
 //! ```
 //!
+//! Build a moderation log embed, relaying the error if the reason is too
+//! long:
+//!
+//! ```rust
+//! use twilight_embed_builder::{
+//!     EmbedAuthorBuilder, EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder, ImageSource,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let reason = "Spamming invites in #general";
+//!
+//! let field = match EmbedFieldBuilder::new("Reason", reason) {
+//!     Ok(field) => field,
+//!     Err(source) => {
+//!         println!("the reason couldn't be logged: {}", source);
+//!
+//!         return Ok(());
+//!     }
+//! };
+//!
+//! let embed = EmbedBuilder::new()
+//!     .author(
+//!         EmbedAuthorBuilder::new()
+//!             .name("Twilight#0001")?
+//!             .icon_url(ImageSource::url("https://cdn.discordapp.com/embed/avatars/1.png")?),
+//!     )
+//!     .title("Member banned")?
+//!     .color(0xed_42_45)?
+//!     .field(EmbedFieldBuilder::new("Member", "<@123>")?.inline())
+//!     .field(EmbedFieldBuilder::new("Moderator", "<@456>")?.inline())
+//!     .field(field)
+//!     .footer(EmbedFooterBuilder::new("Case 42")?)
+//!     .timestamp("2021-07-01T00:00:00+00:00")
+//!     .build()?;
+//!
+//! assert_eq!(3, embed.fields.len());
+//! # Ok(()) }
+//! ```
+//!
 //! [`twilight-rs`]: https://github.com/twilight-rs/twilight
 //! [discord badge]: https://img.shields.io/discord/745809834183753828?color=%237289DA&label=discord%20server&logo=discord&style=for-the-badge
 //! [discord link]: https://discord.gg/7jj8n7D
@@ -1,13 +1,16 @@
+use super::{BudgetStore, DailyBudget};
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    time::Duration,
+    sync::{Arc, Mutex as StdMutex},
+    time::{Duration, SystemTime},
 };
 use tokio::{
     sync::Mutex,
     time::{self, Instant},
 };
 use twilight_http::Error as HttpError;
+use twilight_model::gateway::SessionStartLimit;
 
 /// Creating a day limiter queue failed.
 #[derive(Debug)]
@@ -38,19 +41,55 @@ impl Error for DayLimiterError {
     }
 }
 
+/// Daily identify budget of a bot, waiting for it to reset once it's used
+/// up.
 #[derive(Debug)]
-pub(crate) struct DayLimiter(pub(crate) Mutex<DayLimiterInner>);
+pub(crate) struct DayLimiter {
+    http: twilight_http::Client,
+    state: StdMutex<State>,
+    store: Option<Arc<dyn BudgetStore>>,
+    /// Lock held while waiting for the budget to reset, so that shards
+    /// identify in the order they requested to.
+    waiting: Mutex<()>,
+}
 
 #[derive(Debug)]
-pub(crate) struct DayLimiterInner {
-    pub http: twilight_http::Client,
-    pub last_check: Instant,
-    pub next_reset: Duration,
-    pub total: u64,
-    pub current: u64,
+struct State {
+    /// Number of identifies used since the last reset.
+    current: u64,
+    /// Time at which the budget resets.
+    reset_at: Instant,
+    /// Time at which the budget resets, as persisted by the store.
+    reset_at_system: SystemTime,
+    total: u64,
+}
+
+impl State {
+    fn new(limit: &SessionStartLimit) -> Self {
+        debug_assert!(limit.total >= limit.remaining);
+        let next_reset = Duration::from_millis(limit.reset_after);
+
+        Self {
+            current: limit.total.saturating_sub(limit.remaining),
+            reset_at: Instant::now() + next_reset,
+            reset_at_system: SystemTime::now() + next_reset,
+            total: limit.total,
+        }
+    }
+
+    fn budget(&self) -> DailyBudget {
+        DailyBudget {
+            remaining: self.total.saturating_sub(self.current),
+            reset_at: self.reset_at_system,
+            total: self.total,
+        }
+    }
 }
 
 impl DayLimiter {
+    /// Time between resets if the session start limit can't be retrieved.
+    const RESET_AFTER: Duration = Duration::from_secs(60 * 60 * 24);
+
     pub async fn new(http: &twilight_http::Client) -> Result<Self, DayLimiterError> {
         let info = http
             .gateway()
@@ -58,46 +97,171 @@ impl DayLimiter {
             .await
             .map_err(|source| DayLimiterError::RetrievingSessionAvailability { source })?;
 
-        let last_check = Instant::now();
+        Ok(Self::from_session_start_limit(
+            http,
+            &info.session_start_limit,
+        ))
+    }
+
+    pub fn from_session_start_limit(
+        http: &twilight_http::Client,
+        limit: &SessionStartLimit,
+    ) -> Self {
+        let state = State::new(limit);
+
+        tracing::info!(
+            "{}/{} identifies used before next reset in {:.2?}",
+            state.current,
+            state.total,
+            Duration::from_millis(limit.reset_after),
+        );
 
-        let next_reset = Duration::from_millis(info.session_start_limit.reset_after);
-        let total = info.session_start_limit.total;
-        let remaining = info.session_start_limit.remaining;
-        debug_assert!(total >= remaining);
-        let current = total - remaining;
-        Ok(DayLimiter(Mutex::new(DayLimiterInner {
+        Self {
             http: http.clone(),
-            last_check,
-            next_reset,
-            total: info.session_start_limit.total,
-            current,
-        })))
+            state: StdMutex::new(state),
+            store: None,
+            waiting: Mutex::new(()),
+        }
+    }
+
+    /// Persist the budget in a store, continuing from the budget saved in it
+    /// if that hasn't reset yet.
+    ///
+    /// The saved budget is only trusted as far as it's more conservative than
+    /// the current one, since another process may have identified since.
+    pub fn set_store(&mut self, store: Arc<dyn BudgetStore>) {
+        let state = self.state.get_mut().expect("day limiter state poisoned");
+
+        match store.load() {
+            Ok(Some(saved)) => {
+                if let Ok(until) = saved.reset_at.duration_since(SystemTime::now()) {
+                    let used = state.total.saturating_sub(saved.remaining);
+
+                    if used > state.current {
+                        state.current = used;
+                    }
+
+                    if saved.reset_at > state.reset_at_system {
+                        state.reset_at = Instant::now() + until;
+                        state.reset_at_system = saved.reset_at;
+                    }
+
+                    tracing::info!(
+                        "continuing from saved budget, {}/{} identifies used",
+                        state.current,
+                        state.total,
+                    );
+                }
+            }
+            Ok(None) => {}
+            Err(source) => tracing::warn!("loading the saved budget failed: {}", source),
+        }
+
+        Self::save(store.as_ref(), state.budget());
+        self.store = Some(store);
     }
 
+    /// Current budget of identifies.
+    ///
+    /// Once the reset time has passed the whole budget is available again,
+    /// even if it hasn't been retrieved yet.
+    pub fn budget(&self) -> DailyBudget {
+        let state = self.state.lock().expect("day limiter state poisoned");
+        let mut budget = state.budget();
+
+        if Instant::now() >= state.reset_at {
+            budget.remaining = budget.total;
+        }
+
+        budget
+    }
+
+    /// Wait until an identify is available, using it up.
+    ///
+    /// Once all of the identifies have been used then no identify is allowed
+    /// until the session start limit resets.
     pub async fn get(&self) {
-        let mut lock = self.0.lock().await;
-        if lock.current < lock.total {
-            lock.current += 1;
-        } else {
-            let wait = lock.last_check + lock.next_reset;
-            time::sleep_until(wait).await;
-            if let Ok(info) = lock.http.gateway().authed().await {
-                let last_check = Instant::now();
-                let next_reset = Duration::from_millis(info.session_start_limit.remaining);
-                tracing::info!("next session start limit reset in: {:.2?}", next_reset);
-                let total = info.session_start_limit.total;
-                let remaining = info.session_start_limit.remaining;
-                assert!(total >= remaining);
-                let current = total - remaining;
-                lock.last_check = last_check;
-                lock.next_reset = next_reset;
-                lock.total = total;
-                lock.current = current + 1;
+        let _waiting = self.waiting.lock().await;
+
+        // The lock on the state isn't held while saving the budget, which may
+        // take a while.
+        let used = {
+            let mut state = self.state.lock().expect("day limiter state poisoned");
+
+            if state.current < state.total && Instant::now() < state.reset_at {
+                state.current += 1;
+
+                Some(state.budget())
+            } else {
+                None
+            }
+        };
+
+        if let Some(budget) = used {
+            self.saved(budget).await;
+
+            return;
+        }
+
+        let reset_at = self
+            .state
+            .lock()
+            .expect("day limiter state poisoned")
+            .reset_at;
+        time::sleep_until(reset_at).await;
+        let info = self.http.gateway().authed().await;
+        let budget = {
+            let mut state = self.state.lock().expect("day limiter state poisoned");
+
+            if let Ok(info) = info {
+                let limit = info.session_start_limit;
+                tracing::info!(
+                    "next session start limit reset in: {:.2?}",
+                    Duration::from_millis(limit.reset_after),
+                );
+
+                *state = State::new(&limit);
             } else {
+                // The limit has reset, so assume that all of the identifies
+                // are available again rather than letting the shard through
+                // without counting it.
                 tracing::warn!(
-                    "unable to get new session limits, skipping (this may cause bad things)"
-                )
+                    "unable to get new session limits, assuming the limit reset in full"
+                );
+                state.current = 0;
+                state.reset_at = Instant::now() + Self::RESET_AFTER;
+                state.reset_at_system = SystemTime::now() + Self::RESET_AFTER;
+            }
+
+            state.current += 1;
+
+            state.budget()
+        };
+
+        self.saved(budget).await;
+    }
+
+    /// Save the budget in the store, if there is one.
+    ///
+    /// Stores may block, such as the [`FileStore`] writing to a file, so the
+    /// budget is saved on a thread where blocking is acceptable.
+    ///
+    /// [`FileStore`]: super::FileStore
+    async fn saved(&self, budget: DailyBudget) {
+        if let Some(store) = self.store.as_ref() {
+            let store = Arc::clone(store);
+
+            if let Err(source) =
+                tokio::task::spawn_blocking(move || Self::save(store.as_ref(), budget)).await
+            {
+                tracing::warn!("saving the budget failed: {}", source);
             }
         }
     }
+
+    fn save(store: &dyn BudgetStore, budget: DailyBudget) {
+        if let Err(source) = store.save(budget) {
+            tracing::warn!("saving the budget failed: {}", source);
+        }
+    }
 }
@@ -0,0 +1,181 @@
+use super::Queue;
+use hyper::{client::HttpConnector, Body, Client, Request};
+use std::{future::Future, pin::Pin, time::Duration};
+use tokio::time::sleep;
+
+/// Queue that waits for permission to identify from an external queue
+/// service over HTTP.
+///
+/// This allows shards in multiple processes or on multiple machines to share
+/// one queue, usually backed by a store like Redis, so that their identifies
+/// are serialized per bucket.
+///
+/// Each request is a `GET` request to the service's URL with the shard's ID
+/// and the total number of shards as the `shard` and `total` query
+/// parameters, such as `http://queue:8080/?shard=3&total=16`. The service
+/// must respond with a successful status once the shard may identify. If the
+/// request fails, such as because the service can't be reached or responds
+/// with an error status, then it's retried after the [`retry_delay`]; this
+/// means the service can also respond right away with an error status to
+/// have the shard poll it again.
+///
+/// Only plain HTTP is supported, since the service is expected to run
+/// alongside the shards.
+///
+/// # Examples
+///
+/// Use a queue service that's polled every half second while it doesn't allow
+/// the shard to identify:
+///
+/// ```
+/// use std::time::Duration;
+/// use twilight_gateway_queue::HttpQueue;
+///
+/// let queue = HttpQueue::new("http://queue:8080/").retry_delay(Duration::from_millis(500));
+/// ```
+///
+/// [`retry_delay`]: Self::retry_delay
+#[derive(Debug)]
+pub struct HttpQueue {
+    client: Client<HttpConnector>,
+    retry_delay: Duration,
+    url: Box<str>,
+}
+
+impl HttpQueue {
+    /// Create a new queue requesting permission from a queue service at a URL.
+    ///
+    /// Failed requests are retried after 1 second by default.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            retry_delay: Duration::from_secs(1),
+            url: url.into().into_boxed_str(),
+        }
+    }
+
+    /// Set the delay before a failed request is retried.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+
+        self
+    }
+
+    /// Make one request to the service, returning whether the shard may
+    /// identify.
+    async fn try_request(&self, uri: &str) -> bool {
+        let request = match Request::get(uri).body(Body::empty()) {
+            Ok(request) => request,
+            Err(source) => {
+                tracing::warn!("queue service url {} is invalid: {}", uri, source);
+
+                return false;
+            }
+        };
+
+        match self.client.request(request).await {
+            Ok(response) if response.status().is_success() => true,
+            Ok(response) => {
+                tracing::debug!("queue service responded with {}", response.status());
+
+                false
+            }
+            Err(source) => {
+                tracing::warn!("requesting from queue service failed: {}", source);
+
+                false
+            }
+        }
+    }
+}
+
+impl Queue for HttpQueue {
+    /// Request to be able to identify from the queue service, retrying until
+    /// the service allows the shard to identify.
+    fn request(&'_ self, [id, total]: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        let uri = format!("{}{}shard={}&total={}", self.url, separator, id, total);
+
+        Box::pin(async move {
+            tracing::info!("shard {}/{} waiting for allowance", id, total);
+
+            while !self.try_request(&uri).await {
+                sleep(self.retry_delay).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpQueue, Queue};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug, time::Duration};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    assert_impl_all!(HttpQueue: Debug, Queue, Send, Sync);
+
+    /// Accept a connection and respond to its request with a status,
+    /// returning the request's target.
+    async fn respond(listener: &TcpListener, status: &str) -> Result<String, Box<dyn Error>> {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await?;
+
+            if read == 0 {
+                return Err("connection closed before the request ended".into());
+            }
+
+            request.extend_from_slice(&buf[..read]);
+        }
+
+        let response = format!(
+            "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            status
+        );
+        stream.write_all(response.as_bytes()).await?;
+
+        let request = String::from_utf8(request)?;
+        let target = request.split(' ').nth(1).ok_or("request line invalid")?;
+
+        Ok(target.to_owned())
+    }
+
+    #[tokio::test]
+    async fn test_request_retries_until_allowed() -> Result<(), Box<dyn Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let queue = HttpQueue::new(format!("http://{}/identify", listener.local_addr()?))
+            .retry_delay(Duration::from_millis(10));
+
+        let server = async {
+            let denied = respond(&listener, "429 Too Many Requests").await?;
+            let allowed = respond(&listener, "200 OK").await?;
+
+            Ok::<_, Box<dyn Error>>((denied, allowed))
+        };
+        let (_, targets) = tokio::join!(queue.request([3, 16]), server);
+        let (denied, allowed) = targets?;
+
+        assert_eq!("/identify?shard=3&total=16", denied);
+        assert_eq!(denied, allowed);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_url_with_query() -> Result<(), Box<dyn Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let queue = HttpQueue::new(format!("http://{}/?bot=1", listener.local_addr()?));
+
+        let (_, target) = tokio::join!(queue.request([0, 1]), respond(&listener, "204 No Content"));
+        assert_eq!("/?bot=1&shard=0&total=1", target?);
+
+        Ok(())
+    }
+}
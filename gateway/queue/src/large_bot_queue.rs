@@ -1,11 +1,12 @@
-use super::{DayLimiter, Queue};
+use super::{BudgetStore, DailyBudget, DayLimiter, Queue};
 use futures_channel::{
     mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
     oneshot::{self, Sender},
 };
 use futures_util::{sink::SinkExt, stream::StreamExt};
-use std::{fmt::Debug, future::Future, pin::Pin, time::Duration};
+use std::{convert::TryFrom, fmt::Debug, future::Future, pin::Pin, sync::Arc, time::Duration};
 use tokio::time::sleep;
+use twilight_model::gateway::SessionStartLimit;
 
 /// Queue built for single-process clusters that require identifying via
 /// [Sharding for Very Large Bots].
@@ -27,7 +28,84 @@ impl LargeBotQueue {
     ///
     /// You must provide the number of buckets Discord requires your bot to
     /// connect with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if retrieving the bot's session start limit fails.
     pub async fn new(buckets: usize, http: &twilight_http::Client) -> Self {
+        let limiter = DayLimiter::new(http).await.expect(
+            "Getting the first session limits failed, \
+             Is network connection available?",
+        );
+
+        Self::with_limiter(buckets, limiter)
+    }
+
+    /// Create a new large bot queue from the bot's session start limit.
+    ///
+    /// The number of buckets is the limit's [`max_concurrency`]. Shards whose
+    /// IDs are in the same bucket identify one after another, while shards in
+    /// different buckets identify in parallel. Once the limit's remaining
+    /// identifies are used up no shard may identify until the limit resets.
+    ///
+    /// This is useful when the session start limit has already been retrieved
+    /// via [`Client::gateway`], such as to determine the number of shards to
+    /// use.
+    ///
+    /// [`Client::gateway`]: twilight_http::Client::gateway
+    /// [`max_concurrency`]: SessionStartLimit::max_concurrency
+    pub fn from_session_start_limit(
+        http: &twilight_http::Client,
+        limit: &SessionStartLimit,
+    ) -> Self {
+        let buckets = usize::try_from(limit.max_concurrency).unwrap_or(usize::MAX);
+
+        Self::with_limiter(buckets, DayLimiter::from_session_start_limit(http, limit))
+    }
+
+    /// Persist the daily identify budget in a store, so that a restarted
+    /// process doesn't forget how much of it was used.
+    ///
+    /// If the store holds a budget that hasn't reset yet, the queue continues
+    /// from it where it's more conservative than the session start limit the
+    /// queue was created with.
+    ///
+    /// # Examples
+    ///
+    /// Keep the budget in a file across restarts:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::env;
+    /// use twilight_gateway_queue::{FileStore, LargeBotQueue};
+    /// use twilight_http::Client;
+    ///
+    /// let http = Client::new(env::var("DISCORD_TOKEN")?);
+    /// let info = http.gateway().authed().await?;
+    /// let queue = LargeBotQueue::from_session_start_limit(&http, &info.session_start_limit)
+    ///     .with_store(FileStore::new("identify-budget"));
+    ///
+    /// println!("{} identifies left today", queue.budget().remaining);
+    /// # Ok(()) }
+    /// ```
+    pub fn with_store(mut self, store: impl BudgetStore + 'static) -> Self {
+        self.limiter.set_store(Arc::new(store));
+
+        self
+    }
+
+    /// Current daily budget of identifies, such as to display on a
+    /// dashboard.
+    ///
+    /// Once the reset time has passed the whole budget is reported as
+    /// available, even before the new budget has been retrieved.
+    pub fn budget(&self) -> DailyBudget {
+        self.limiter.budget()
+    }
+
+    fn with_limiter(buckets: usize, limiter: DayLimiter) -> Self {
+        // There's always at least one bucket.
+        let buckets = buckets.max(1);
         let mut queues = Vec::with_capacity(buckets);
         for _ in 0..buckets {
             let (tx, rx) = unbounded();
@@ -37,25 +115,6 @@ impl LargeBotQueue {
             queues.push(tx)
         }
 
-        let limiter = DayLimiter::new(http).await.expect(
-            "Getting the first session limits failed, \
-             Is network connection available?",
-        );
-
-        // The level_enabled macro does not turn off with the dynamic
-        // tracing levels. It is made for the static_max_level_xxx features
-        // And will return false if you do not use those features of if
-        // You use the feature but then dynamically set a lower feature.
-        if tracing::level_enabled!(tracing::Level::INFO) {
-            let lock = limiter.0.lock().await;
-            tracing::info!(
-                "{}/{} identifies used before next reset in {:.2?}",
-                lock.current,
-                lock.total,
-                lock.next_reset
-            );
-        }
-
         Self {
             buckets: queues,
             limiter,
@@ -94,13 +153,152 @@ impl Queue for LargeBotQueue {
             let _ = rx.await;
         })
     }
+
+    fn daily_budget(&self) -> Option<DailyBudget> {
+        Some(self.limiter.budget())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{LargeBotQueue, Queue};
+    use crate::{BudgetStore, DailyBudget, MemoryStore};
+    use futures_util::future;
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{
+        fmt::Debug,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+    use tokio::time::Instant;
+    use twilight_http::Client;
+    use twilight_model::gateway::SessionStartLimit;
 
     assert_impl_all!(LargeBotQueue: Debug, Queue, Send, Sync);
+
+    fn queue(max_concurrency: u64, remaining: u64, reset_after: u64) -> LargeBotQueue {
+        // Point the client at a closed port so that retrieving the session
+        // start limit again fails.
+        let http = Client::builder()
+            .proxy("127.0.0.1:1", true)
+            .ratelimiter(None)
+            .build();
+
+        LargeBotQueue::from_session_start_limit(
+            &http,
+            &SessionStartLimit {
+                max_concurrency,
+                remaining,
+                reset_after,
+                total: 1000,
+            },
+        )
+    }
+
+    /// Request to identify with every shard at once, returning the number of
+    /// seconds each shard waited for.
+    async fn identify(queue: &LargeBotQueue, shards: u64) -> Vec<u64> {
+        let start = Instant::now();
+
+        future::join_all((0..shards).map(|id| async move {
+            queue.request([id, shards]).await;
+
+            start.elapsed().as_secs()
+        }))
+        .await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_buckets_identify_in_parallel() {
+        let queue = queue(4, 1000, 86_400_000);
+
+        // Shards 0-3 are each first in their bucket, shards 4-7 second, and
+        // so on.
+        assert_eq!(
+            vec![0, 0, 0, 0, 6, 6, 6, 6, 12, 12, 12, 12, 18, 18, 18, 18],
+            identify(&queue, 16).await
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_remaining_exhausted() {
+        let queue = queue(4, 2, 60_000);
+
+        // After two identifies every shard waits for the limit to reset. The
+        // failed retrieval of the new limit may take some time, too.
+        let waited = identify(&queue, 4).await;
+        assert_eq!(vec![0, 0], waited[..2]);
+        assert!(waited[2] >= 60);
+        assert_eq!(waited[2], waited[3]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_budget_exhausted_mid_startup() {
+        let store = MemoryStore::new();
+        let queue = queue(1, 2, 60_000).with_store(store.clone());
+        assert_eq!(2, queue.budget().remaining);
+
+        // Two of three shards identify before the budget is used up.
+        queue.request([0, 3]).await;
+        queue.request([1, 3]).await;
+        assert_eq!(0, queue.budget().remaining);
+        assert_eq!(0, store.load().unwrap().unwrap().remaining);
+        assert_eq!(Some(queue.budget()), queue.daily_budget());
+
+        // The last shard identifies once the budget resets, using up one of
+        // the new budget.
+        let start = Instant::now();
+        queue.request([2, 3]).await;
+        assert!(start.elapsed().as_secs() >= 54);
+
+        let budget = queue.budget();
+        assert_eq!(999, budget.remaining);
+        assert!(budget.reset_at > SystemTime::now() + Duration::from_secs(60 * 60));
+        assert_eq!(budget, store.load().unwrap().unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_store_continues_after_restart() {
+        let store = MemoryStore::new();
+        store
+            .save(DailyBudget {
+                remaining: 0,
+                reset_at: SystemTime::now() + Duration::from_secs(60),
+                total: 1000,
+            })
+            .unwrap();
+
+        // The session start limit the queue is created with is stale, so the
+        // saved budget is used.
+        let queue = queue(1, 1000, 86_400_000).with_store(store);
+        assert_eq!(0, queue.budget().remaining);
+
+        let waited = identify(&queue, 1).await;
+        assert!(waited[0] >= 59);
+    }
+
+    #[tokio::test]
+    async fn test_store_reset_ignored() {
+        let store = MemoryStore::new();
+        store
+            .save(DailyBudget {
+                remaining: 0,
+                reset_at: UNIX_EPOCH,
+                total: 1000,
+            })
+            .unwrap();
+
+        let queue = queue(1, 1000, 86_400_000).with_store(store.clone());
+        assert_eq!(1000, queue.budget().remaining);
+
+        assert_eq!(vec![0], identify(&queue, 1).await);
+        assert_eq!(999, store.load().unwrap().unwrap().remaining);
+    }
+
+    #[tokio::test]
+    async fn test_buckets_minimum() {
+        let queue = queue(0, 1000, 86_400_000);
+
+        assert_eq!(1, queue.buckets.len());
+        assert_eq!(vec![0], identify(&queue, 1).await);
+    }
 }
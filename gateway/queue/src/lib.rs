@@ -16,9 +16,13 @@
 //! Most users only need the [`LocalQueue`]: it's a single-process queue for
 //! smaller bots. Larger bots need the [`LargeBotQueue`], which supports
 //! single-process [Sharding for Very Large Bots] through the use of bucket
-//! releasing.
+//! releasing. It also tracks the bot's daily identify budget, which it can
+//! persist through a [`BudgetStore`] such as the [`FileStore`] so that a
+//! restarted process doesn't forget how much of it was used.
 //!
-//! By default, the gateway's `Cluster` and `Shard`s use the [`LocalQueue`]. You
+//! By default, the gateway's `Cluster` and `Shard`s use the [`LocalQueue`]. A
+//! `Cluster` that automatically determines its number of shards instead uses a
+//! [`LargeBotQueue`] if the bot's maximum concurrency is greater than 1. You
 //! can override this in the `ClusterBuilder::queue` and `ShardBuilder::queue`
 //! configuration methods.
 //!
@@ -30,12 +34,22 @@
 //! all so a [`Queue`] trait is provided that shards can use to make requests to
 //! create sessions.
 //!
+//! The [`HttpQueue`] is a simple implementation of the [`Queue`] for this,
+//! requesting permission to identify from an external queue service over
+//! HTTP.
+//!
 //! [Sharding for Very Large Bots]: https://discord.com/developers/docs/topics/gateway#sharding-for-very-large-bots
 
 mod day_limiter;
+mod http_queue;
 mod large_bot_queue;
+mod store;
 
-pub use large_bot_queue::LargeBotQueue;
+pub use self::{
+    http_queue::HttpQueue,
+    large_bot_queue::LargeBotQueue,
+    store::{BudgetStore, DailyBudget, FileStore, FileStoreError, MemoryStore},
+};
 
 use day_limiter::DayLimiter;
 use futures_channel::{
@@ -61,6 +75,16 @@ pub trait Queue: Debug + Send + Sync {
     /// The returned future must resolve only when the shard can initiate the
     /// session.
     fn request<'a>(&'a self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+    /// Daily budget of identifies that requests are taken from, if the queue
+    /// tracks one.
+    ///
+    /// Shards check the budget before requesting to identify, so that they
+    /// can stop instead of waiting for it to reset once it's used up. Queues
+    /// that don't track the budget, such as the [`LocalQueue`], return `None`.
+    fn daily_budget(&self) -> Option<DailyBudget> {
+        None
+    }
 }
 
 /// A local, in-process implementation of a [`Queue`] which manages the
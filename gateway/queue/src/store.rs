@@ -0,0 +1,270 @@
+//! Persisting the daily identify budget of a [`LargeBotQueue`].
+//!
+//! [`LargeBotQueue`]: super::LargeBotQueue
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    fs,
+    io::Error as IoError,
+    num::ParseIntError,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Daily budget of identifies, which Discord calls the session start limit.
+///
+/// Discord allows a bot a number of identifies per day, usually 1000, and
+/// resets the budget a day after the first identify of the day.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DailyBudget {
+    /// Number of identifies left before the budget resets.
+    pub remaining: u64,
+    /// Time at which the budget resets.
+    pub reset_at: SystemTime,
+    /// Number of identifies allowed per day.
+    pub total: u64,
+}
+
+/// Store that the daily identify budget is persisted in, so that a restarted
+/// process doesn't forget how much of it was already used.
+///
+/// The budget is saved every time a shard identifies and every time it
+/// resets. Identifies save it on a blocking thread, so implementations may
+/// block. A [`MemoryStore`] and a [`FileStore`] are provided.
+pub trait BudgetStore: Debug + Send + Sync {
+    /// Load the saved budget, if one was saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget couldn't be loaded, in which case the
+    /// queue continues as if none was saved.
+    fn load(&self) -> Result<Option<DailyBudget>, Box<dyn Error + Send + Sync>>;
+
+    /// Save the budget, replacing the previously saved one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget couldn't be saved, which the queue
+    /// logs.
+    fn save(&self, budget: DailyBudget) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Store keeping the budget in memory.
+///
+/// Clones share the saved budget, so a queue created again within the same
+/// process, such as when a cluster is restarted, continues from it.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore(Arc<Mutex<Option<DailyBudget>>>);
+
+impl MemoryStore {
+    /// Create a new store without a saved budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BudgetStore for MemoryStore {
+    fn load(&self) -> Result<Option<DailyBudget>, Box<dyn Error + Send + Sync>> {
+        Ok(*self.0.lock().expect("memory store poisoned"))
+    }
+
+    fn save(&self, budget: DailyBudget) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.0
+            .lock()
+            .expect("memory store poisoned")
+            .replace(budget);
+
+        Ok(())
+    }
+}
+
+/// Loading or saving the budget of a [`FileStore`] failed.
+#[derive(Debug)]
+pub enum FileStoreError {
+    /// A line of the file isn't a number.
+    Parsing {
+        /// Position of the line, starting at 1.
+        line: usize,
+        /// Reason for the error.
+        source: ParseIntError,
+    },
+    /// Reading the file failed.
+    Reading {
+        /// Reason for the error.
+        source: IoError,
+    },
+    /// The file doesn't contain the three lines of a budget.
+    Truncated,
+    /// Writing the file failed.
+    Writing {
+        /// Reason for the error.
+        source: IoError,
+    },
+}
+
+impl Display for FileStoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Parsing { line, .. } => f.write_fmt(format_args!(
+                "line {} of the budget file isn't a number",
+                line
+            )),
+            Self::Reading { .. } => f.write_str("reading the budget file failed"),
+            Self::Truncated => f.write_str("the budget file is truncated"),
+            Self::Writing { .. } => f.write_str("writing the budget file failed"),
+        }
+    }
+}
+
+impl Error for FileStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parsing { source, .. } => Some(source),
+            Self::Reading { source } | Self::Writing { source } => Some(source),
+            Self::Truncated => None,
+        }
+    }
+}
+
+/// Store keeping the budget in a file, so that it survives restarts of the
+/// process.
+///
+/// The file holds the remaining identifies, the total identifies, and the
+/// reset time in milliseconds since the Unix epoch on one line each. It's
+/// replaced as a whole when the budget is saved, so a crash while saving
+/// leaves the previous budget intact.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// Create a store saving the budget at a path.
+    ///
+    /// The file is created when the budget is first saved.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn read(&self) -> Result<Option<DailyBudget>, FileStoreError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(source) if source.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(FileStoreError::Reading { source }),
+        };
+
+        let mut lines = contents.lines().enumerate().map(|(idx, line)| {
+            line.trim()
+                .parse::<u64>()
+                .map_err(|source| FileStoreError::Parsing {
+                    line: idx + 1,
+                    source,
+                })
+        });
+        let mut next = || lines.next().ok_or(FileStoreError::Truncated)?;
+
+        let remaining = next()?;
+        let total = next()?;
+        let reset_at = UNIX_EPOCH + Duration::from_millis(next()?);
+
+        Ok(Some(DailyBudget {
+            remaining,
+            reset_at,
+            total,
+        }))
+    }
+
+    fn write(&self, budget: DailyBudget) -> Result<(), FileStoreError> {
+        #[allow(clippy::cast_possible_truncation)]
+        let reset_at = budget
+            .reset_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        let contents = format!("{}\n{}\n{}\n", budget.remaining, budget.total, reset_at);
+
+        // Write to a temporary file first so that the file is never left
+        // half-written.
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+
+        fs::write(&temporary, contents)
+            .and_then(|_| fs::rename(&temporary, &self.path))
+            .map_err(|source| FileStoreError::Writing { source })
+    }
+}
+
+impl BudgetStore for FileStore {
+    fn load(&self) -> Result<Option<DailyBudget>, Box<dyn Error + Send + Sync>> {
+        self.read().map_err(From::from)
+    }
+
+    fn save(&self, budget: DailyBudget) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.write(budget).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BudgetStore, DailyBudget, FileStore, FileStoreError, MemoryStore};
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{
+        error::Error,
+        fmt::Debug,
+        fs,
+        hash::Hash,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    assert_impl_all!(DailyBudget: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(FileStore: BudgetStore, Clone, Debug, Send, Sync);
+    assert_impl_all!(FileStoreError: Debug, Error, Send, Sync);
+    assert_impl_all!(MemoryStore: BudgetStore, Clone, Debug, Default, Send, Sync);
+    assert_obj_safe!(BudgetStore);
+
+    fn budget() -> DailyBudget {
+        DailyBudget {
+            remaining: 998,
+            reset_at: UNIX_EPOCH + Duration::from_millis(1_600_000_000_123),
+            total: 1000,
+        }
+    }
+
+    #[test]
+    fn test_memory_store_shared() {
+        let store = MemoryStore::new();
+        assert_eq!(None, store.load().unwrap());
+
+        store.clone().save(budget()).unwrap();
+        assert_eq!(Some(budget()), store.load().unwrap());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let path = std::env::temp_dir().join(format!(
+            "twilight-gateway-queue-budget-{}",
+            std::process::id()
+        ));
+        let store = FileStore::new(&path);
+        assert_eq!(None, store.load().unwrap());
+
+        store.save(budget()).unwrap();
+        assert_eq!(
+            "998\n1000\n1600000000123\n",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(Some(budget()), FileStore::new(&path).load().unwrap());
+
+        fs::write(&path, "998\n").unwrap();
+        assert!(matches!(store.read(), Err(FileStoreError::Truncated)));
+
+        fs::write(&path, "998\nmany\n0\n").unwrap();
+        assert!(matches!(
+            store.read(),
+            Err(FileStoreError::Parsing { line: 2, .. })
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,7 +2,15 @@ use super::{
     config::Config as ClusterConfig,
     r#impl::{Cluster, ClusterStartError},
 };
-use crate::shard::{LargeThresholdError, ResumeSession, ShardBuilder};
+#[cfg(feature = "compression")]
+use crate::shard::ShrinkPolicy;
+use crate::{
+    shard::{
+        EventStreamOverflow, EventTypesError, LargeThresholdError, MalformedDispatch,
+        MalformedDispatchPolicy, ReconnectPolicy, ResumeSession, ShardBuilder,
+    },
+    EventTypeFlags,
+};
 use std::{
     collections::HashMap,
     convert::TryFrom,
@@ -12,7 +20,7 @@ use std::{
     sync::Arc,
 };
 use twilight_gateway_queue::{LocalQueue, Queue};
-use twilight_http::Client;
+use twilight_http::{token, Client};
 use twilight_model::gateway::{payload::update_status::UpdateStatusInfo, Intents};
 
 /// Starting a cluster failed.
@@ -81,6 +89,90 @@ pub enum ShardScheme {
         /// Total number of shards used by the bot.
         total: u64,
     },
+    /// Specifies to start every shard of a bucket.
+    ///
+    /// Shards are grouped into buckets according to the bot's [maximum
+    /// concurrency], which is the number of shards that may identify at the
+    /// same time. A shard is in the bucket with the ID of its shard ID modulo
+    /// the maximum concurrency. This is useful for very large bots that
+    /// distribute their shards so that every process identifies its shards in
+    /// parallel.
+    ///
+    /// No shards are started if the concurrency is 0.
+    ///
+    /// # Examples
+    ///
+    /// For example, if your bot uses 64 shards with a maximum concurrency of
+    /// 16 then the bucket with an ID of 3 contains shards 3, 19, 35, and 51:
+    ///
+    /// ```
+    /// use twilight_gateway::cluster::ShardScheme;
+    ///
+    /// let scheme = ShardScheme::Bucket {
+    ///     bucket_id: 3,
+    ///     concurrency: 16,
+    ///     total: 64,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![3, 19, 35, 51],
+    ///     scheme.shard_ids().unwrap().collect::<Vec<_>>(),
+    /// );
+    /// ```
+    ///
+    /// [maximum concurrency]: twilight_model::gateway::SessionStartLimit::max_concurrency
+    Bucket {
+        /// ID of the bucket to start the shards of.
+        bucket_id: u64,
+        /// Number of shards that may identify at the same time.
+        concurrency: u64,
+        /// Total number of shards used by the bot.
+        total: u64,
+    },
+}
+
+impl ShardScheme {
+    /// Iterator over the IDs of the shards of the scheme.
+    ///
+    /// Returns `None` if the scheme is [`Auto`], as the shards are only known
+    /// once the recommended number of shards has been retrieved.
+    ///
+    /// [`Auto`]: Self::Auto
+    pub fn shard_ids(&self) -> Option<ShardSchemeIter> {
+        match self {
+            Self::Auto => None,
+            Self::Range { from, to, .. } => Some(ShardSchemeIter {
+                next: Some(*from),
+                step: 1,
+                to: *to,
+            }),
+            Self::Bucket {
+                bucket_id,
+                concurrency,
+                total,
+            } => Some(ShardSchemeIter {
+                next: if *concurrency == 0 || *total == 0 {
+                    None
+                } else {
+                    Some(*bucket_id)
+                },
+                step: *concurrency,
+                to: total.saturating_sub(1),
+            }),
+        }
+    }
+
+    /// Total number of shards used by the bot.
+    ///
+    /// Returns `None` if the scheme is [`Auto`].
+    ///
+    /// [`Auto`]: Self::Auto
+    pub fn total(&self) -> Option<u64> {
+        match self {
+            Self::Auto => None,
+            Self::Range { total, .. } | Self::Bucket { total, .. } => Some(*total),
+        }
+    }
 }
 
 impl Default for ShardScheme {
@@ -89,12 +181,33 @@ impl Default for ShardScheme {
     }
 }
 
+/// Iterator over the IDs of the shards of a [`ShardScheme`].
+///
+/// Created via [`ShardScheme::shard_ids`].
+#[derive(Clone, Debug)]
+pub struct ShardSchemeIter {
+    next: Option<u64>,
+    step: u64,
+    to: u64,
+}
+
+impl Iterator for ShardSchemeIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next.filter(|id| *id <= self.to)?;
+        self.next = id.checked_add(self.step);
+
+        Some(id)
+    }
+}
+
 impl<T: RangeBounds<u64>> TryFrom<(T, u64)> for ShardScheme {
     type Error = ShardSchemeRangeError;
 
     fn try_from((range, total): (T, u64)) -> Result<Self, Self::Error> {
         let start = match range.start_bound() {
-            Bound::Excluded(num) => *num - 1,
+            Bound::Excluded(num) => *num + 1,
             Bound::Included(num) => *num,
             Bound::Unbounded => 0,
         };
@@ -104,7 +217,7 @@ impl<T: RangeBounds<u64>> TryFrom<(T, u64)> for ShardScheme {
             Bound::Unbounded => total - 1,
         };
 
-        if start > end {
+        if start > end || end >= total {
             return Err(ShardSchemeRangeError::IdTooLarge { end, start, total });
         }
 
@@ -143,29 +256,25 @@ pub struct ClusterBuilder(ClusterConfig, ShardBuilder);
 
 impl ClusterBuilder {
     /// Create a new builder to construct and configure a cluster.
+    ///
+    /// Refer to [`ShardBuilder::new`] for how the token is handled.
     pub fn new(token: impl Into<String>, intents: Intents) -> Self {
-        Self::_new(token.into(), intents)
+        Self::_new(&token.into(), intents)
     }
 
-    fn _new(mut token: String, intents: Intents) -> Self {
-        if !token.starts_with("Bot ") {
-            token.insert_str(0, "Bot ");
-        }
-
-        let token = token.into_boxed_str();
-
-        let http_client = Client::new(token.clone());
+    fn _new(token: &str, intents: Intents) -> Self {
+        let token = token::raw(token).to_owned().into_boxed_str();
 
-        let shard_config =
-            ShardBuilder::new(token.clone(), intents).http_client(http_client.clone());
+        let shard_config = ShardBuilder::new(token.clone(), intents);
 
         Self(
             ClusterConfig {
-                http_client,
                 shard_config: shard_config.0,
                 shard_scheme: ShardScheme::Auto,
                 queue: Arc::new(Box::new(LocalQueue::new())),
+                queue_configured: false,
                 resume_sessions: HashMap::new(),
+                wait_for_session_start_limit: false,
             },
             ShardBuilder::new(token, intents),
         )
@@ -177,39 +286,57 @@ impl ClusterBuilder {
     ///
     /// Returns [`ClusterStartError::RetrievingGatewayInfo`] if there was an
     /// HTTP error Retrieving the gateway information.
+    ///
+    /// Returns [`ClusterStartError::SessionStartLimitExhausted`] if the
+    /// [`Auto`] shard scheme is used and too few sessions can be started to
+    /// identify all of the shards. Refer to [`wait_for_session_start_limit`]
+    /// to wait for the limit to reset instead.
+    ///
+    /// [`Auto`]: ShardScheme::Auto
+    /// [`wait_for_session_start_limit`]: Self::wait_for_session_start_limit
     pub async fn build(mut self) -> Result<Cluster, ClusterStartError> {
-        if (self.1).0.gateway_url.is_none() {
-            let gateway_url = (self.1)
-                .0
-                .http_client
-                .gateway()
-                .authed()
-                .await
-                .ok()
-                .map(|s| s.url);
-
-            self = self.gateway_url(gateway_url);
-        }
-
         self.0.shard_config = (self.1).0;
 
         Cluster::new_with_config(self.0).await
     }
 
     /// Set the URL that will be used to connect to the gateway.
+    ///
+    /// Refer to the shard's [`ShardBuilder::gateway_url`] for more
+    /// information.
     pub fn gateway_url(mut self, gateway_url: Option<String>) -> Self {
         self.1 = self.1.gateway_url(gateway_url);
 
         self
     }
 
+    /// Set whether shards append the gateway version, encoding, and transport
+    /// compression to the gateway URL as query parameters.
+    ///
+    /// Refer to the shard's [`ShardBuilder::gateway_url_query`] for more
+    /// information.
+    pub fn gateway_url_query(mut self, gateway_url_query: bool) -> Self {
+        self.1 = self.1.gateway_url_query(gateway_url_query);
+
+        self
+    }
+
     /// Set the `twilight_http` Client used by the cluster and the shards it
     /// manages.
     ///
     /// This is needed so that the cluster and shards can retrieve gateway
     /// information.
     ///
-    /// Defaults to a new, default HTTP client is used.
+    /// This is also the client used to retrieve the recommended number of
+    /// shards when using the [`Auto`] shard scheme, so that it can share the
+    /// configuration of the client used by the rest of the bot, such as its
+    /// proxy.
+    ///
+    /// Default is `None`, meaning a new, unconfigured HTTP client is created
+    /// from the token when the cluster is created, which is then shared by
+    /// the shards.
+    ///
+    /// [`Auto`]: ShardScheme::Auto
     pub fn http_client(mut self, http_client: Client) -> Self {
         self.1 = self.1.http_client(http_client);
 
@@ -234,6 +361,103 @@ impl ClusterBuilder {
         Ok(self)
     }
 
+    /// Set whether shards request transport compression from the gateway.
+    ///
+    /// Refer to the shard's [`ShardBuilder::compression`] for more
+    /// information.
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.1 = self.1.compression(compression);
+
+        self
+    }
+
+    /// Set the policy for when shards shrink the buffers payloads are
+    /// decompressed with.
+    ///
+    /// Refer to the shard's [`ShardBuilder::inflater_shrink_policy`] for more
+    /// information.
+    #[cfg(feature = "compression")]
+    pub fn inflater_shrink_policy(mut self, inflater_shrink_policy: ShrinkPolicy) -> Self {
+        self.1 = self.1.inflater_shrink_policy(inflater_shrink_policy);
+
+        self
+    }
+
+    /// Set whether shards encode payloads with ETF instead of JSON.
+    ///
+    /// Refer to the shard's [`ShardBuilder::etf`] for more information.
+    #[cfg(feature = "etf")]
+    pub fn etf(mut self, etf: bool) -> Self {
+        self.1 = self.1.etf(etf);
+
+        self
+    }
+
+    /// Set the maximum number of events buffered per event stream of each
+    /// shard.
+    ///
+    /// Refer to the shard's [`ShardBuilder::event_stream_capacity`] for more
+    /// information.
+    pub fn event_stream_capacity(mut self, event_stream_capacity: usize) -> Self {
+        self.1 = self.1.event_stream_capacity(event_stream_capacity);
+
+        self
+    }
+
+    /// Set what shards do when the buffer of one of their event streams is
+    /// full.
+    ///
+    /// Refer to the shard's [`ShardBuilder::event_stream_overflow`] for more
+    /// information.
+    pub fn event_stream_overflow(mut self, event_stream_overflow: EventStreamOverflow) -> Self {
+        self.1 = self.1.event_stream_overflow(event_stream_overflow);
+
+        self
+    }
+
+    /// Set the event types that shards emit.
+    ///
+    /// Refer to the shard's [`ShardBuilder::event_types`] for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventTypesError::Undeliverable`] if some of the event types
+    /// aren't delivered under the cluster's intents.
+    pub fn event_types(mut self, event_types: EventTypeFlags) -> Result<Self, EventTypesError> {
+        self.1 = self.1.event_types(event_types)?;
+
+        Ok(self)
+    }
+
+    /// Set what shards do when a dispatch event can't be deserialized.
+    ///
+    /// Refer to the shard's [`ShardBuilder::malformed_dispatch_policy`] for
+    /// more information.
+    pub fn malformed_dispatch_policy(
+        mut self,
+        malformed_dispatch_policy: MalformedDispatchPolicy,
+    ) -> Self {
+        self.1 = self.1.malformed_dispatch_policy(malformed_dispatch_policy);
+
+        self
+    }
+
+    /// Set a callback that dispatch events which can't be deserialized by any
+    /// of the shards are passed to.
+    ///
+    /// Refer to the shard's [`ShardBuilder::dead_letter`] for more
+    /// information.
+    pub fn dead_letter(
+        mut self,
+        dead_letter: impl Fn(&MalformedDispatch<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.1 = self.1.dead_letter(dead_letter);
+
+        self
+    }
+
     /// Set the presence to use when identifying with the gateway.
     ///
     /// Refer to the shard's [`ShardBuilder::presence`] for more information.
@@ -243,6 +467,37 @@ impl ClusterBuilder {
         self
     }
 
+    /// Set whether shards ratelimit the commands they send.
+    ///
+    /// Refer to the shard's [`ShardBuilder::ratelimit_commands`] for more
+    /// information.
+    pub fn ratelimit_commands(mut self, ratelimit_commands: bool) -> Self {
+        self.1 = self.1.ratelimit_commands(ratelimit_commands);
+
+        self
+    }
+
+    /// Set the policy for how long shards wait between attempts to reconnect.
+    ///
+    /// Refer to the shard's [`ShardBuilder::reconnect_policy`] for more
+    /// information.
+    pub fn reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.1 = self.1.reconnect_policy(reconnect_policy);
+
+        self
+    }
+
+    /// Set whether shards stop when the session start limit is used up,
+    /// instead of waiting for it to reset.
+    ///
+    /// Refer to the shard's [`ShardBuilder::session_start_limit_fatal`] for
+    /// more information.
+    pub fn session_start_limit_fatal(mut self, session_start_limit_fatal: bool) -> Self {
+        self.1 = self.1.session_start_limit_fatal(session_start_limit_fatal);
+
+        self
+    }
+
     /// Set the scheme to use for shard managing.
     ///
     /// For example, [`ShardScheme::Auto`] means that the cluster will
@@ -284,17 +539,52 @@ impl ClusterBuilder {
     ///
     /// This is useful when you have a very large bot or when you have a more
     /// advanced setup with multiple processes connecting at the same time.
+    /// Shards across processes or machines can share a queue service via the
+    /// [`HttpQueue`].
     ///
     /// Refer to the [`queue`] module for more information.
     ///
+    /// The default value is a [`LocalQueue`]. When using the [`Auto`] shard
+    /// scheme and the bot's maximum concurrency is greater than 1, the default
+    /// is instead a [`LargeBotQueue`] created from the bot's session start
+    /// limit. Provide a [`LargeBotQueue`] configured via
+    /// [`LargeBotQueue::with_store`] to persist its daily identify budget
+    /// across restarts.
+    ///
+    /// [`Auto`]: ShardScheme::Auto
+    /// [`LargeBotQueue::with_store`]: crate::queue::LargeBotQueue::with_store
+    /// [`HttpQueue`]: crate::queue::HttpQueue
+    /// [`LargeBotQueue`]: crate::queue::LargeBotQueue
+    /// [`LocalQueue`]: crate::queue::LocalQueue
     /// [`queue`]: crate::queue
     pub fn queue(mut self, queue: Arc<Box<dyn Queue>>) -> Self {
         self.0.queue = Arc::clone(&queue);
+        self.0.queue_configured = true;
         self.1 = self.1.queue(queue);
 
         self
     }
 
+    /// Set whether to wait for the session start limit to reset when too few
+    /// sessions can be started to identify the shards.
+    ///
+    /// When using the [`Auto`] shard scheme, the number of sessions that can
+    /// still be started is checked against the number of shards that need to
+    /// identify. Shards that are [resumed] don't need to identify. If too few
+    /// sessions can be started then building the cluster will either wait for
+    /// the limit to reset or return
+    /// [`ClusterStartError::SessionStartLimitExhausted`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Auto`]: ShardScheme::Auto
+    /// [resumed]: Self::resume_sessions
+    pub fn wait_for_session_start_limit(mut self, wait: bool) -> Self {
+        self.0.wait_for_session_start_limit = wait;
+
+        self
+    }
+
     /// Set the session information to resume shards with.
     ///
     /// This requires having recovered the resume data when shutting down the
@@ -317,7 +607,7 @@ impl<T: Into<String>> From<(T, Intents)> for ClusterBuilder {
 
 #[cfg(test)]
 mod tests {
-    use super::{ClusterBuilder, ShardScheme, ShardSchemeRangeError};
+    use super::{ClusterBuilder, ShardScheme, ShardSchemeIter, ShardSchemeRangeError};
     use crate::Intents;
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{
@@ -325,9 +615,11 @@ mod tests {
         error::Error,
         fmt::{Debug, Display},
         hash::Hash,
+        ops::Bound,
     };
 
     assert_fields!(ShardSchemeRangeError::IdTooLarge: end, start, total);
+    assert_fields!(ShardScheme::Bucket: bucket_id, concurrency, total);
     assert_fields!(ShardScheme::Range: from, to, total);
     assert_impl_all!(ClusterBuilder: Debug, From<(String, Intents)>, Send, Sync);
     assert_impl_all!(ShardSchemeRangeError: Debug, Display, Error, Send, Sync);
@@ -341,6 +633,7 @@ mod tests {
         Send,
         Sync
     );
+    assert_impl_all!(ShardSchemeIter: Clone, Debug, Iterator<Item = u64>, Send, Sync);
 
     #[test]
     fn test_shard_scheme() -> Result<(), Box<dyn Error>> {
@@ -352,7 +645,72 @@ mod tests {
             },
             ShardScheme::try_from((0..=9, 10))?
         );
+        assert_eq!(
+            ShardScheme::Range {
+                from: 32,
+                to: 63,
+                total: 128,
+            },
+            ShardScheme::try_from((32..64, 128))?
+        );
+        assert!(matches!(
+            ShardScheme::try_from((0..=10, 10)),
+            Err(ShardSchemeRangeError::IdTooLarge {
+                end: 10,
+                start: 0,
+                total: 10,
+            })
+        ));
+        assert!(matches!(
+            ShardScheme::try_from(((Bound::Included(5), Bound::Included(4)), 10)),
+            Err(ShardSchemeRangeError::IdTooLarge { .. })
+        ));
 
         Ok(())
     }
+
+    #[test]
+    fn test_shard_scheme_shard_ids() {
+        assert!(ShardScheme::Auto.shard_ids().is_none());
+        assert!(ShardScheme::Auto.total().is_none());
+
+        let range = ShardScheme::Range {
+            from: 0,
+            to: 31,
+            total: 128,
+        };
+        assert_eq!(
+            (0..32).collect::<Vec<_>>(),
+            range.shard_ids().unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(128), range.total());
+
+        let bucket = ShardScheme::Bucket {
+            bucket_id: 1,
+            concurrency: 16,
+            total: 64,
+        };
+        assert_eq!(
+            vec![1, 17, 33, 49],
+            bucket.shard_ids().unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(64), bucket.total());
+
+        let last = ShardScheme::Range {
+            from: u64::MAX - 1,
+            to: u64::MAX,
+            total: u64::MAX,
+        };
+        assert_eq!(
+            vec![u64::MAX - 1, u64::MAX],
+            last.shard_ids().unwrap().collect::<Vec<_>>()
+        );
+
+        let no_concurrency = ShardScheme::Bucket {
+            bucket_id: 0,
+            concurrency: 0,
+            total: 64,
+        };
+        assert_eq!(0, no_concurrency.shard_ids().unwrap().count());
+    }
 }
@@ -9,22 +9,24 @@ use twilight_http::Client;
 /// [`Cluster`]: crate::Cluster
 #[derive(Debug)]
 pub struct Config {
-    pub(super) http_client: Client,
     pub(super) shard_config: ShardConfig,
     pub(super) shard_scheme: ShardScheme,
     pub(super) queue: Arc<Box<dyn Queue>>,
+    pub(super) queue_configured: bool,
     pub(super) resume_sessions: HashMap<u64, ResumeSession>,
+    pub(super) wait_for_session_start_limit: bool,
 }
 
 impl Config {
     /// Return an immutable reference to the `twilight_http` client used by the
     /// cluster and shards to get the gateway information.
     ///
+    /// This is `None` until the cluster is created if no client was provided.
     /// Refer to [`ClusterBuilder::http_client`] for the default value.
     ///
     /// [`ClusterBuilder::http_client`]: super::ClusterBuilder::http_client
-    pub fn http_client(&self) -> &Client {
-        &self.http_client
+    pub fn http_client(&self) -> Option<&Client> {
+        self.shard_config.http_client()
     }
 
     /// Return an immutable reference to the configuration used to create
@@ -51,6 +53,17 @@ impl Config {
     pub fn queue(&self) -> &Arc<Box<dyn Queue>> {
         &self.queue
     }
+
+    /// Return whether to wait for the session start limit to reset when too
+    /// few sessions can be started to identify the shards.
+    ///
+    /// Refer to [`ClusterBuilder::wait_for_session_start_limit`] for the
+    /// default value.
+    ///
+    /// [`ClusterBuilder::wait_for_session_start_limit`]: super::ClusterBuilder::wait_for_session_start_limit
+    pub fn wait_for_session_start_limit(&self) -> bool {
+        self.wait_for_session_start_limit
+    }
 }
 
 #[cfg(test)]
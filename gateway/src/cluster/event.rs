@@ -0,0 +1,157 @@
+//! Event streams of a cluster that follow shards coming and going.
+
+use crate::{
+    shard::{Events, Shard},
+    EventTypeFlags,
+};
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_util::stream::{SelectAll, Stream, StreamExt};
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+use twilight_model::gateway::event::Event;
+
+/// Events of a shard, paired with the shard's ID.
+struct ShardEvents {
+    events: Events,
+    id: u64,
+}
+
+impl Stream for ShardEvents {
+    type Item = (u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let id = self.id;
+
+        self.events.poll_next_unpin(cx).map(|e| e.map(|e| (id, e)))
+    }
+}
+
+/// Stream of the events of the shards of a cluster.
+///
+/// Shards brought up after the stream was created are added to it, and
+/// shards that are brought down end their part of it. The stream ends once
+/// the cluster is brought down and the events of all shards have been
+/// received.
+pub struct ClusterEvents {
+    rx: Option<UnboundedReceiver<ShardEvents>>,
+    shards: SelectAll<ShardEvents>,
+}
+
+impl Stream for ClusterEvents {
+    type Item = (u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while let Some(rx) = self.rx.as_mut() {
+            match rx.poll_next_unpin(cx) {
+                Poll::Ready(Some(events)) => self.shards.push(events),
+                Poll::Ready(None) => self.rx = None,
+                Poll::Pending => break,
+            }
+        }
+
+        match self.shards.poll_next_unpin(cx) {
+            // No shard is left, but more may still be brought up.
+            Poll::Ready(None) if self.rx.is_some() => Poll::Pending,
+            poll => poll,
+        }
+    }
+}
+
+/// Subscription of a stream to the events of shards brought up later.
+#[derive(Debug)]
+struct Subscription {
+    /// IDs of the shards the stream is limited to, if any.
+    shard_ids: Option<HashSet<u64>>,
+    tx: UnboundedSender<ShardEvents>,
+    types: EventTypeFlags,
+}
+
+/// Streams of a cluster to add the events of shards to when they're brought
+/// up.
+#[derive(Debug, Default)]
+pub struct Subscriptions(Mutex<Vec<Subscription>>);
+
+impl Subscriptions {
+    /// Create a stream of the events of some shards, subscribing it to the
+    /// shards brought up later.
+    ///
+    /// The stream is limited to the IDs of `filter` if it's provided.
+    pub fn stream<'a>(
+        &self,
+        shards: impl IntoIterator<Item = (u64, &'a Shard)>,
+        filter: Option<HashSet<u64>>,
+        types: EventTypeFlags,
+    ) -> ClusterEvents {
+        let (tx, rx) = mpsc::unbounded();
+        let mut stream = SelectAll::new();
+
+        for (id, shard) in shards {
+            if filter.as_ref().map_or(true, |ids| ids.contains(&id)) {
+                stream.push(ShardEvents {
+                    events: shard.some_events(types),
+                    id,
+                });
+            }
+        }
+
+        self.0
+            .lock()
+            .expect("subscriptions poisoned")
+            .push(Subscription {
+                shard_ids: filter,
+                tx,
+                types,
+            });
+
+        ClusterEvents {
+            rx: Some(rx),
+            shards: stream,
+        }
+    }
+
+    /// Add the events of a shard to the subscribed streams, forgetting the
+    /// streams that have been dropped.
+    pub fn add(&self, id: u64, shard: &Shard) {
+        self.0
+            .lock()
+            .expect("subscriptions poisoned")
+            .retain(|subscription| {
+                if subscription
+                    .shard_ids
+                    .as_ref()
+                    .map_or(false, |ids| !ids.contains(&id))
+                {
+                    return !subscription.tx.is_closed();
+                }
+
+                subscription
+                    .tx
+                    .unbounded_send(ShardEvents {
+                        events: shard.some_events(subscription.types),
+                        id,
+                    })
+                    .is_ok()
+            });
+    }
+
+    /// Unsubscribe all of the streams, so that they end once the events of
+    /// their shards have been received.
+    pub fn clear(&self) {
+        self.0.lock().expect("subscriptions poisoned").clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClusterEvents, Subscriptions};
+    use futures_util::stream::Stream;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+
+    assert_impl_all!(ClusterEvents: Send, Stream, Unpin);
+    assert_impl_all!(Subscriptions: Debug, Default, Send, Sync);
+}
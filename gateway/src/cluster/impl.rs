@@ -1,24 +1,54 @@
-use super::{
-    builder::{ClusterBuilder, ShardScheme},
-    config::Config,
-};
+use super::{builder::ClusterBuilder, config::Config, event::Subscriptions};
 use crate::{
-    shard::{raw_message::Message, CommandError, Information, ResumeSession, SendError, Shard},
+    queue::{LargeBotQueue, Queue},
+    shard::{
+        raw_message::{CloseFrame, Message},
+        Command, CommandError, Information, ResumeSession, SendError, Shard, ShardStartError,
+        TlsContainer, TlsError,
+    },
     EventTypeFlags, Intents,
 };
-use futures_util::{
-    future,
-    stream::{SelectAll, Stream, StreamExt},
-};
+use futures_util::{future, stream::Stream};
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    iter::FromIterator,
+    slice,
     sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::{sleep, timeout};
+use twilight_http::{
+    token::{self, TokenError},
+    Client as HttpClient, Error as HttpError,
 };
-use twilight_http::Error as HttpError;
-use twilight_model::gateway::event::Event;
+use twilight_model::{
+    gateway::{event::Event, payload::UpdateVoiceState, SessionStartLimit},
+    id::{ChannelId, GuildId},
+};
+
+/// Time bringing down a cluster waits for the shards' connections to close.
+const DOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Calculate the ID of the shard that receives the events of a guild.
+///
+/// Discord assigns guilds to shards with the formula
+/// `(guild_id >> 22) % total`, where `total` is the total number of shards
+/// used by the bot. A total of 0 is treated as 1.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_gateway::cluster::shard_id;
+/// use twilight_model::id::GuildId;
+///
+/// let guild_id = GuildId(41_771_983_427_338_241);
+/// assert_eq!(1, shard_id(guild_id, 2));
+/// assert_eq!(7, shard_id(guild_id, 16));
+/// ```
+pub fn shard_id(guild_id: GuildId, total: u64) -> u64 {
+    (guild_id.0 >> 22) % total.max(1)
+}
 
 /// Sending a command to a shard failed.
 #[derive(Debug)]
@@ -113,11 +143,36 @@ pub enum ClusterStartError {
     /// number of recommended number of shards to start fails, which can happen
     /// due to something like a network or response parsing issue.
     ///
-    /// [automatic sharding]: ShardScheme::Auto
+    /// [automatic sharding]: super::ShardScheme::Auto
     RetrievingGatewayInfo {
         /// Reason for the error.
         source: HttpError,
     },
+    /// Too few sessions can be started to identify the shards when using
+    /// [automatic sharding].
+    ///
+    /// [automatic sharding]: super::ShardScheme::Auto
+    SessionStartLimitExhausted {
+        /// Number of sessions that can still be started before the limit
+        /// resets.
+        remaining: u64,
+        /// Number of shards that need to identify.
+        required: u64,
+        /// Number of milliseconds until the limit resets.
+        reset_after: u64,
+        /// Total number of sessions that can be started after the limit resets.
+        total: u64,
+    },
+    /// Building the TLS configuration shared by the shards failed.
+    Tls {
+        /// Reason for the error.
+        source: TlsError,
+    },
+    /// The token doesn't have the structure of a bot token.
+    TokenInvalid {
+        /// Reason for the error.
+        source: TokenError,
+    },
 }
 
 impl Display for ClusterStartError {
@@ -126,6 +181,19 @@ impl Display for ClusterStartError {
             Self::RetrievingGatewayInfo { .. } => {
                 f.write_str("getting the bot's gateway info failed")
             }
+            Self::SessionStartLimitExhausted {
+                remaining,
+                required,
+                reset_after,
+                total,
+            } => f.write_fmt(format_args!(
+                "{} shards need to identify but {} of {} sessions can be started, resetting in {}ms",
+                required, remaining, total, reset_after,
+            )),
+            Self::Tls { source } => Display::fmt(source, f),
+            Self::TokenInvalid { source } => {
+                f.write_fmt(format_args!("the token is invalid: {}", source))
+            }
         }
     }
 }
@@ -134,6 +202,55 @@ impl Error for ClusterStartError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             Self::RetrievingGatewayInfo { source } => Some(source),
+            Self::SessionStartLimitExhausted { .. } => None,
+            Self::Tls { source } => Some(source),
+            Self::TokenInvalid { source } => Some(source),
+        }
+    }
+}
+
+/// Bringing up a shard of a running cluster failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClusterUpShardError {
+    /// The cluster already manages a shard with the provided ID.
+    ShardExists {
+        /// Provided shard ID.
+        id: u64,
+    },
+    /// Provided shard ID isn't less than the total number of shards used by
+    /// the bot.
+    ShardIdInvalid {
+        /// Provided shard ID.
+        id: u64,
+        /// Total number of shards used by the bot.
+        total: u64,
+    },
+    /// Starting the shard failed.
+    Starting {
+        /// Reason for the error.
+        source: ShardStartError,
+    },
+}
+
+impl Display for ClusterUpShardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ShardExists { id } => f.write_fmt(format_args!("shard {} already exists", id)),
+            Self::ShardIdInvalid { id, total } => f.write_fmt(format_args!(
+                "shard {} is invalid for a total of {} shards",
+                id, total,
+            )),
+            Self::Starting { source } => Display::fmt(source, f),
+        }
+    }
+}
+
+impl Error for ClusterUpShardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ShardExists { .. } | Self::ShardIdInvalid { .. } => None,
+            Self::Starting { source } => Some(source),
         }
     }
 }
@@ -141,9 +258,13 @@ impl Error for ClusterStartError {
 #[derive(Debug)]
 struct ClusterRef {
     config: Config,
-    shard_from: u64,
-    shard_to: u64,
+    /// IDs of the shards managed by the cluster, in the order to start them.
+    shard_ids: Vec<u64>,
     shards: Mutex<HashMap<u64, Shard>>,
+    /// Event streams to add the events of shards brought up later to.
+    subscriptions: Subscriptions,
+    /// Total number of shards used by the bot.
+    total: u64,
 }
 
 /// A manager for multiple shards.
@@ -181,19 +302,69 @@ impl Cluster {
     }
 
     pub(super) async fn new_with_config(mut config: Config) -> Result<Self, ClusterStartError> {
-        let [from, to, total] = match config.shard_scheme() {
-            ShardScheme::Auto => {
-                let http = config.http_client();
+        token::validate(config.shard_config().token())
+            .map_err(|source| ClusterStartError::TokenInvalid { source })?;
+
+        // Create the client here if none was provided, so that the shards
+        // share it instead of each creating their own.
+        let http_client = match config.http_client() {
+            Some(http_client) => http_client.clone(),
+            None => HttpClient::new(config.shard_config().token().to_owned()),
+        };
+        config.shard_config.http_client = Some(http_client.clone());
 
-                let gateway = http
+        // Likewise, the TLS configuration is built once and cloned into every
+        // shard's configuration.
+        if config.shard_config.tls.is_none() {
+            config.shard_config.tls =
+                Some(TlsContainer::new().map_err(|source| ClusterStartError::Tls { source })?);
+        }
+
+        let scheme = config.shard_scheme();
+
+        let (shard_ids, total) = if let Some((iter, total)) = scheme.shard_ids().zip(scheme.total())
+        {
+            // The URL isn't required, since shards retrieve it themselves.
+            if config.shard_config.gateway_url.is_none() {
+                config.shard_config.gateway_url = http_client
                     .gateway()
                     .authed()
                     .await
-                    .map_err(|source| ClusterStartError::RetrievingGatewayInfo { source })?;
+                    .ok()
+                    .map(|info| info.url.into_boxed_str());
+            }
+
+            (iter.collect::<Vec<_>>(), total)
+        } else {
+            let gateway = http_client
+                .gateway()
+                .authed()
+                .await
+                .map_err(|source| ClusterStartError::RetrievingGatewayInfo { source })?;
 
-                [0, gateway.shards - 1, gateway.shards]
+            // Shards in different buckets may identify in parallel.
+            if !config.queue_configured && gateway.session_start_limit.max_concurrency > 1 {
+                let queue: Arc<Box<dyn Queue>> =
+                    Arc::new(Box::new(LargeBotQueue::from_session_start_limit(
+                        &http_client,
+                        &gateway.session_start_limit,
+                    )));
+                config.shard_config.queue = Arc::clone(&queue);
+                config.queue = queue;
             }
-            ShardScheme::Range { from, to, total } => [*from, *to, *total],
+
+            let shard_ids = (0..gateway.shards).collect::<Vec<_>>();
+            let required = shard_ids
+                .iter()
+                .filter(|id| !config.resume_sessions.contains_key(id))
+                .count() as u64;
+            Self::session_start_limit(&config, &gateway.session_start_limit, required).await?;
+
+            if config.shard_config.gateway_url.is_none() {
+                config.shard_config.gateway_url = Some(gateway.url.into_boxed_str());
+            }
+
+            (shard_ids, gateway.shards)
         };
 
         #[cfg(feature = "metrics")]
@@ -203,8 +374,9 @@ impl Cluster {
             metrics::gauge!("Cluster-Shard-Count", total.try_into().unwrap_or(-1));
         }
 
-        let shards = (from..=to)
-            .map(|idx| {
+        let shards = shard_ids
+            .iter()
+            .map(|&idx| {
                 let mut shard_config = config.shard_config().clone();
                 shard_config.shard = [idx, total];
 
@@ -219,12 +391,44 @@ impl Cluster {
 
         Ok(Self(Arc::new(ClusterRef {
             config,
-            shard_from: from,
-            shard_to: to,
+            shard_ids,
             shards: Mutex::new(shards),
+            subscriptions: Subscriptions::default(),
+            total,
         })))
     }
 
+    /// Ensure that enough sessions can be started for the required number of
+    /// shards to identify, waiting for the limit to reset if configured to.
+    async fn session_start_limit(
+        config: &Config,
+        limit: &SessionStartLimit,
+        required: u64,
+    ) -> Result<(), ClusterStartError> {
+        if limit.remaining >= required {
+            return Ok(());
+        }
+
+        if config.wait_for_session_start_limit() && limit.total >= required {
+            tracing::info!(
+                remaining = limit.remaining,
+                required,
+                reset_after = limit.reset_after,
+                "waiting for the session start limit to reset",
+            );
+            sleep(Duration::from_millis(limit.reset_after)).await;
+
+            return Ok(());
+        }
+
+        Err(ClusterStartError::SessionStartLimitExhausted {
+            remaining: limit.remaining,
+            required,
+            reset_after: limit.reset_after,
+            total: limit.total,
+        })
+    }
+
     /// Create a builder to configure and construct a cluster.
     pub fn builder(token: impl Into<String>, intents: Intents) -> ClusterBuilder {
         ClusterBuilder::new(token, intents)
@@ -264,39 +468,156 @@ impl Cluster {
     /// ```
     pub async fn up(&self) {
         future::join_all(
-            (self.0.shard_from..=self.0.shard_to).map(|id| Self::start(Arc::clone(&self.0), id)),
+            self.0
+                .shard_ids
+                .iter()
+                .map(|id| Self::start(Arc::clone(&self.0), *id)),
         )
         .await;
     }
 
     /// Bring down the cluster, stopping all of the shards that it's managing.
-    pub fn down(&self) {
-        for shard in self.0.shards.lock().expect("shards poisoned").values() {
-            shard.shutdown();
-        }
+    ///
+    /// All of the shards are shut down at once, after which this waits until
+    /// their connections have closed, for up to 5 seconds. Refer to
+    /// [`Shard::shutdown`] for more information.
+    pub async fn down(&self) {
+        self.down_with(CloseFrame::NORMAL).await;
     }
 
     /// Bring down the cluster in a resumable way and returns all info needed
     /// for resuming.
     ///
+    /// Each shard's connection is closed with a restart close code rather
+    /// than a normal one, so Discord keeps the sessions alive.
+    ///
     /// The returned map is keyed by the shard's ID to the information needed
-    /// to resume. If a shard can't resume, then it is not included in the map.
+    /// to resume. If a shard can't resume, such as if it never finished
+    /// identifying, then it is not included in the map. Pass the map to
+    /// [`ClusterBuilder::resume_sessions`] to resume the shards in a new
+    /// cluster.
     ///
     /// **Note**: Discord only allows resuming for a few minutes after
     /// disconnection. You may also not be able to resume if you missed too many
     /// events already.
-    pub fn down_resumable(&self) -> HashMap<u64, ResumeSession> {
-        self.0
-            .shards
-            .lock()
-            .expect("shards poisoned")
-            .values()
-            .map(Shard::shutdown_resumable)
+    ///
+    /// Like [`down`], this waits for the connections to close.
+    ///
+    /// [`ClusterBuilder::resume_sessions`]: super::ClusterBuilder::resume_sessions
+    /// [`down`]: Self::down
+    pub async fn down_resumable(&self) -> HashMap<u64, ResumeSession> {
+        self.down_with(CloseFrame::RESUME).await
+    }
+
+    /// Bring down the cluster, closing the shards' connections with a close
+    /// frame of choice.
+    ///
+    /// The close code decides whether Discord keeps the sessions alive, in
+    /// which case the information needed to resume them is returned like with
+    /// [`down_resumable`]. Refer to [`Shard::shutdown_with`] for more
+    /// information.
+    ///
+    /// Like [`down`], this waits for the connections to close.
+    ///
+    /// [`down`]: Self::down
+    /// [`down_resumable`]: Self::down_resumable
+    pub async fn down_with(&self, close_frame: CloseFrame<'static>) -> HashMap<u64, ResumeSession> {
+        self.0.subscriptions.clear();
+        let shards = self.shards();
+        let sessions = shards
+            .iter()
+            .map(|shard| shard.shutdown_with(close_frame.clone()))
             .filter_map(|(id, session)| session.map(|s| (id, s)))
-            .collect()
+            .collect();
+
+        Self::wait_closed(&shards).await;
+
+        sessions
+    }
+
+    /// Bring up a shard that the cluster doesn't manage yet, adding it to
+    /// the cluster.
+    ///
+    /// The shard is configured like the other shards, and its events are
+    /// added to the event streams of the cluster, including the existing
+    /// ones. This can be used to replace a shard brought down with
+    /// [`down_shard`], or to start a shard that wasn't part of the cluster's
+    /// [`ShardScheme`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClusterUpShardError::ShardExists`] if the cluster already
+    /// manages a shard with the ID.
+    ///
+    /// Returns [`ClusterUpShardError::ShardIdInvalid`] if the ID isn't less
+    /// than the total number of shards used by the bot.
+    ///
+    /// Returns [`ClusterUpShardError::Starting`] if starting the shard failed,
+    /// in which case it's removed from the cluster again.
+    ///
+    /// [`ShardScheme`]: super::ShardScheme
+    /// [`down_shard`]: Self::down_shard
+    pub async fn up_shard(&self, id: u64) -> Result<Shard, ClusterUpShardError> {
+        let total = self.0.total;
+
+        if id >= total {
+            return Err(ClusterUpShardError::ShardIdInvalid { id, total });
+        }
+
+        let mut shard = {
+            let mut shards = self.0.shards.lock().expect("shards poisoned");
+            let entry = match shards.entry(id) {
+                Entry::Occupied(_) => return Err(ClusterUpShardError::ShardExists { id }),
+                Entry::Vacant(entry) => entry,
+            };
+
+            let mut shard_config = self.0.config.shard_config().clone();
+            shard_config.shard = [id, total];
+            let shard = entry.insert(Shard::new_with_config(shard_config)).clone();
+
+            // Subscribed while the shards are locked, so that streams created
+            // at the same time don't miss the shard.
+            self.0.subscriptions.add(id, &shard);
+
+            shard
+        };
+
+        if let Err(source) = shard.start().await {
+            self.0.shards.lock().expect("shards poisoned").remove(&id);
+            shard.shutdown();
+
+            return Err(ClusterUpShardError::Starting { source });
+        }
+
+        Ok(shard)
+    }
+
+    /// Bring down a shard, removing it from the cluster.
+    ///
+    /// The shard is shut down, ending its part of the cluster's event
+    /// streams, after which this waits until its connection has closed, like
+    /// [`down`]. The other shards keep running.
+    ///
+    /// Returns the shard, or `None` if the cluster doesn't manage a shard
+    /// with the ID.
+    ///
+    /// [`down`]: Self::down
+    pub async fn down_shard(&self, id: u64) -> Option<Shard> {
+        let shard = self.0.shards.lock().expect("shards poisoned").remove(&id)?;
+        shard.shutdown();
+
+        Self::wait_closed(slice::from_ref(&shard)).await;
+
+        Some(shard)
     }
 
     /// Return a Shard by its ID.
+    ///
+    /// The shard can be used to send commands, read its information, or shut
+    /// it down on its own. Refer to [`down_shard`] for also removing it from
+    /// the cluster.
+    ///
+    /// [`down_shard`]: Self::down_shard
     pub fn shard(&self, id: u64) -> Option<Shard> {
         self.0
             .shards
@@ -378,6 +699,53 @@ impl Cluster {
             .map_err(|source| ClusterCommandError::Sending { source })
     }
 
+    /// Send a typed command to the specified shard.
+    ///
+    /// Refer to [`Shard::command_typed`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`command`].
+    ///
+    /// [`command`]: Self::command
+    pub async fn command_typed(
+        &self,
+        id: u64,
+        command: Command,
+    ) -> Result<(), ClusterCommandError> {
+        self.command(id, &command).await
+    }
+
+    /// Join, move between, or leave voice channels of a guild via the shard
+    /// the guild is on.
+    ///
+    /// The shard is calculated with [`shard_id`]. Refer to
+    /// [`Shard::update_voice_state`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClusterCommandError::Sending`] if the shard exists, but
+    /// sending the command failed.
+    ///
+    /// Returns [`ClusterCommandError::ShardNonexistent`] if the guild's shard
+    /// isn't managed by the cluster.
+    pub async fn update_voice_state(
+        &self,
+        guild_id: impl Into<GuildId>,
+        channel_id: impl Into<Option<ChannelId>>,
+        self_deaf: bool,
+        self_mute: bool,
+    ) -> Result<(), ClusterCommandError> {
+        let guild_id = guild_id.into();
+        let id = shard_id(guild_id, self.0.total);
+
+        self.command(
+            id,
+            &UpdateVoiceState::new(guild_id, channel_id, self_deaf, self_mute),
+        )
+        .await
+    }
+
     /// Send a raw command to the specified shard.
     ///
     /// # Errors
@@ -445,6 +813,14 @@ impl Cluster {
     /// Each item in the stream contains both the shard's ID and the event
     /// itself.
     ///
+    /// The events of shards brought up with [`up_shard`] are added to the
+    /// stream, and the stream ends once the cluster is brought down.
+    ///
+    /// Events are buffered until the stream is polled for them, without
+    /// slowing down the shards; by default the buffer is unbounded. Refer to
+    /// [`ClusterBuilder::event_stream_capacity`] for bounding it, in which
+    /// case a full buffer slows down the shards by default.
+    ///
     /// **Note** that we *highly* recommend specifying only the events that you
     /// need via [`some_events`], especially if performance is a concern. This
     /// will ensure that events you don't care about aren't deserialized from
@@ -452,7 +828,9 @@ impl Cluster {
     /// categories of events, but using [`some_events`] will filter it further
     /// on the client side.
     ///
+    /// [`ClusterBuilder::event_stream_capacity`]: super::ClusterBuilder::event_stream_capacity
     /// [`some_events`]: Self::some_events
+    /// [`up_shard`]: Self::up_shard
     pub fn events(&self) -> impl Stream<Item = (u64, Event)> {
         self.some_events(EventTypeFlags::default())
     }
@@ -494,14 +872,84 @@ impl Cluster {
     ///
     /// [`events`]: Self::events
     pub fn some_events(&self, types: EventTypeFlags) -> impl Stream<Item = (u64, Event)> {
-        let shards = self.0.shards.lock().expect("shards poisoned").clone();
-        let stream = shards
-            .into_iter()
-            .map(|(id, shard)| shard.some_events(types).map(move |e| (id, e)));
+        let shards = self.0.shards.lock().expect("shards poisoned");
+
+        self.0
+            .subscriptions
+            .stream(shards.iter().map(|(id, shard)| (*id, shard)), None, types)
+    }
+
+    /// Like [`some_events`], but only receives the events of some of the
+    /// shards managed by this Cluster.
+    ///
+    /// This can be used to route the events of groups of shards to separate
+    /// workers. IDs of shards not managed by this Cluster are ignored until
+    /// shards with them are brought up with [`up_shard`].
+    ///
+    /// Each stream buffers its events separately, so a worker that's slow to
+    /// poll its stream doesn't slow down the other workers or the shards.
+    /// Refer to [`ClusterBuilder::event_stream_capacity`] for bounding the
+    /// buffers, and to [`ClusterBuilder::event_stream_overflow`] for what
+    /// happens once one is full.
+    ///
+    /// # Examples
+    ///
+    /// Handle the message creations of each half of the shards in its own
+    /// task:
+    ///
+    /// ```no_run
+    /// use twilight_gateway::{Cluster, EventTypeFlags, Event, Intents};
+    /// use futures::StreamExt;
+    /// use std::env;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let cluster = Cluster::new(env::var("DISCORD_TOKEN")?, Intents::GUILD_MESSAGES).await?;
+    /// cluster.up().await;
+    ///
+    /// let [_, total] = cluster.config().shard_config().shard();
+    /// let types = EventTypeFlags::MESSAGE_CREATE;
+    ///
+    /// for ids in vec![0..total / 2, total / 2..total] {
+    ///     let mut events = cluster.shard_events(ids, types);
+    ///
+    ///     tokio::spawn(async move {
+    ///         while let Some((shard_id, event)) = events.next().await {
+    ///             if let Event::MessageCreate(message) = event {
+    ///                 println!("Shard {} got message {}", shard_id, message.id);
+    ///             }
+    ///         }
+    ///     });
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ClusterBuilder::event_stream_capacity`]: super::ClusterBuilder::event_stream_capacity
+    /// [`ClusterBuilder::event_stream_overflow`]: super::ClusterBuilder::event_stream_overflow
+    /// [`some_events`]: Self::some_events
+    /// [`up_shard`]: Self::up_shard
+    pub fn shard_events(
+        &self,
+        shard_ids: impl IntoIterator<Item = u64>,
+        types: EventTypeFlags,
+    ) -> impl Stream<Item = (u64, Event)> {
+        let shards = self.0.shards.lock().expect("shards poisoned");
 
-        // Clippy recommends using bad code here.
-        #[allow(clippy::from_iter_instead_of_collect)]
-        SelectAll::from_iter(stream)
+        self.0.subscriptions.stream(
+            shards.iter().map(|(id, shard)| (*id, shard)),
+            Some(shard_ids.into_iter().collect()),
+            types,
+        )
+    }
+
+    /// Wait until the connections of shards have closed, giving up after
+    /// [`DOWN_TIMEOUT`].
+    async fn wait_closed(shards: &[Shard]) {
+        let closed = future::join_all(shards.iter().map(Shard::closed));
+
+        if timeout(DOWN_TIMEOUT, closed).await.is_err() {
+            tracing::warn!("shards' connections didn't close in time");
+        }
     }
 
     /// Queue a request to start a shard by ID and starts it once the queue
@@ -526,10 +974,16 @@ impl Cluster {
 
 #[cfg(test)]
 mod tests {
-    use super::{Cluster, ClusterCommandError, ClusterSendError, ClusterStartError};
+    use super::{
+        Cluster, ClusterCommandError, ClusterSendError, ClusterStartError, ClusterUpShardError,
+    };
+    use crate::{cluster::ShardScheme, Intents};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
 
+    /// Token with the structure of a bot token.
+    const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4.YAbC-d.abcdefghijklmnopqrstu_vwxyz";
+
     assert_fields!(ClusterCommandError::Sending: source);
     assert_fields!(ClusterCommandError::ShardNonexistent: id);
     assert_impl_all!(ClusterCommandError: Debug, Error, Send, Sync);
@@ -537,6 +991,134 @@ mod tests {
     assert_fields!(ClusterSendError::ShardNonexistent: id);
     assert_impl_all!(ClusterSendError: Debug, Error, Send, Sync);
     assert_fields!(ClusterStartError::RetrievingGatewayInfo: source);
+    assert_fields!(
+        ClusterStartError::SessionStartLimitExhausted: remaining,
+        required,
+        reset_after,
+        total
+    );
+    assert_fields!(ClusterStartError::Tls: source);
+    assert_fields!(ClusterStartError::TokenInvalid: source);
     assert_impl_all!(ClusterStartError: Debug, Error, Send, Sync);
+    assert_fields!(ClusterUpShardError::ShardExists: id);
+    assert_fields!(ClusterUpShardError::ShardIdInvalid: id, total);
+    assert_fields!(ClusterUpShardError::Starting: source);
+    assert_impl_all!(ClusterUpShardError: Debug, Error, Send, Sync);
     assert_impl_all!(Cluster: Clone, Debug, Send, Sync);
+
+    fn shards(cluster: &Cluster) -> Vec<[u64; 2]> {
+        let mut shards = cluster
+            .shards()
+            .iter()
+            .map(|shard| shard.config().shard())
+            .collect::<Vec<_>>();
+        shards.sort_unstable();
+
+        shards
+    }
+
+    #[tokio::test]
+    async fn test_shard_scheme_range() -> Result<(), Box<dyn Error>> {
+        let cluster = Cluster::builder(TOKEN, Intents::empty())
+            .shard_scheme(ShardScheme::Range {
+                from: 32,
+                to: 63,
+                total: 128,
+            })
+            .build()
+            .await?;
+
+        let expected = (32..64).map(|id| [id, 128]).collect::<Vec<_>>();
+        assert_eq!(expected, shards(&cluster));
+        assert!(cluster.shard(31).is_none());
+        assert!(cluster.shard(64).is_none());
+        assert_eq!([32, 128], cluster.shard(32).unwrap().config().shard());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shard_scheme_bucket() -> Result<(), Box<dyn Error>> {
+        let cluster = Cluster::builder(TOKEN, Intents::empty())
+            .shard_scheme(ShardScheme::Bucket {
+                bucket_id: 2,
+                concurrency: 16,
+                total: 64,
+            })
+            .build()
+            .await?;
+
+        assert_eq!(
+            vec![[2, 64], [18, 64], [34, 64], [50, 64]],
+            shards(&cluster)
+        );
+        assert!(cluster.shard(3).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tls_shared() -> Result<(), Box<dyn Error>> {
+        let cluster = Cluster::builder(TOKEN, Intents::empty())
+            .shard_scheme(ShardScheme::Range {
+                from: 0,
+                to: 7,
+                total: 8,
+            })
+            .build()
+            .await?;
+
+        let tls = cluster.config().shard_config().tls.as_ref().unwrap();
+
+        for shard in cluster.shards() {
+            assert!(shard.config().tls.as_ref().unwrap().ptr_eq(tls));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_up_shard_errors() -> Result<(), Box<dyn Error>> {
+        let cluster = Cluster::builder(TOKEN, Intents::empty())
+            .shard_scheme(ShardScheme::Range {
+                from: 0,
+                to: 1,
+                total: 4,
+            })
+            .build()
+            .await?;
+
+        assert!(matches!(
+            cluster.up_shard(1).await,
+            Err(ClusterUpShardError::ShardExists { id: 1 })
+        ));
+        assert!(matches!(
+            cluster.up_shard(4).await,
+            Err(ClusterUpShardError::ShardIdInvalid { id: 4, total: 4 })
+        ));
+        assert_eq!(vec![[0, 4], [1, 4]], shards(&cluster));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_down_shard() -> Result<(), Box<dyn Error>> {
+        let cluster = Cluster::builder(TOKEN, Intents::empty())
+            .shard_scheme(ShardScheme::Range {
+                from: 0,
+                to: 1,
+                total: 2,
+            })
+            .build()
+            .await?;
+
+        assert_eq!(
+            [1, 2],
+            cluster.down_shard(1).await.unwrap().config().shard()
+        );
+        assert!(cluster.down_shard(1).await.is_none());
+        assert_eq!(vec![[0, 2]], shards(&cluster));
+
+        Ok(())
+    }
 }
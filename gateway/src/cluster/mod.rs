@@ -48,7 +48,7 @@
 //!         Event::MessageCreate(msg) if msg.content == "!shutdown" => {
 //!             println!("Got a shutdown request from shard {}", shard_id);
 //!
-//!             cluster.down();
+//!             cluster.down().await;
 //!         },
 //!         _ => {},
 //!     }
@@ -57,10 +57,14 @@
 
 mod builder;
 mod config;
+mod event;
 mod r#impl;
 
 pub use self::{
-    builder::{ClusterBuilder, ShardScheme, ShardSchemeRangeError},
+    builder::{ClusterBuilder, ShardScheme, ShardSchemeIter, ShardSchemeRangeError},
     config::Config,
-    r#impl::{Cluster, ClusterCommandError, ClusterSendError, ClusterStartError},
+    r#impl::{
+        shard_id, Cluster, ClusterCommandError, ClusterSendError, ClusterStartError,
+        ClusterUpShardError,
+    },
 };
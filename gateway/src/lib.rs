@@ -44,31 +44,63 @@
 //! twilight-gateway = { default-features = false, features = ["rustls", "simd-json"], version = "0.2" }
 //! ```
 //!
+//! #### `etf`
+//!
+//! The `etf` feature enables support for the gateway's Erlang Term Format
+//! encoding, which is smaller and cheaper to decode than JSON. Shards keep
+//! using JSON unless ETF is enabled via `ShardBuilder::etf`. It is not enabled
+//! by default.
+//!
 //! ### TLS
 //!
 //! `twilight-gateway` has features to enable [`async-tungstenite`] and
-//! [`twilight-http`]'s TLS features. These features are mutually exclusive.
-//! `rustls` is enabled by default.
+//! [`twilight-http`]'s TLS features. If both `native-tls` and one of the
+//! `rustls` features are enabled, then [`async-tungstenite`] uses `native-tls`
+//! for the websocket connection. `rustls-native-roots` is enabled by default.
+//!
+//! The TLS configuration is built once per cluster and shared by all of its
+//! shards, and once per shard for shards created on their own.
 //!
-//! #### `native`
+//! #### `native-tls`
 //!
-//! The `native` feature enables [`async-tungstenite`]'s `tokio-native-tls`
-//! feature as well as [`twilight-http`]'s `native` feature which is mostly
-//! equivalent to using [`native-tls`].
+//! The `native-tls` feature enables [`async-tungstenite`]'s `tokio-native-tls`
+//! feature as well as [`twilight-http`]'s `native-tls` feature, which use
+//! [`native-tls`] and so the TLS implementation of the system, such as
+//! OpenSSL.
 //!
-//! To enable `native`, do something like this in your `Cargo.toml`:
+//! To enable `native-tls`, do something like this in your `Cargo.toml`:
 //!
 //! ```toml
 //! [dependencies]
-//! twilight-gateway = { default-features = false, features = ["native"], version = "0.2" }
+//! twilight-gateway = { default-features = false, features = ["native-tls"], version = "0.2" }
 //! ```
 //!
-//! #### `rustls`
+//! The `native` feature is an alias of this feature.
+//!
+//! #### `rustls-native-roots`
+//!
+//! The `rustls-native-roots` feature enables [`async-tungstenite`]'s
+//! `tokio-rustls` feature and [`twilight-http`]'s `rustls-native-roots`
+//! feature, which use [`rustls`] as the TLS backend, with the root
+//! certificates of the system.
+//!
+//! This is enabled by default. The `rustls` feature is an alias of this
+//! feature.
 //!
-//! The `rustls` feature enables [`async-tungstenite`]'s `tokio-rustls` feature and
-//! [`twilight-http`]'s `rustls` feature, which use [`rustls`] as the TLS backend.
+//! #### `rustls-webpki-roots`
 //!
-//! This is enabled by default.
+//! The `rustls-webpki-roots` feature enables [`async-tungstenite`]'s
+//! `tokio-rustls` feature and [`twilight-http`]'s `rustls-webpki-roots`
+//! feature, which use [`rustls`] as the TLS backend, with the root
+//! certificates of [`webpki-roots`] built into the binary.
+//!
+//! To enable `rustls-webpki-roots`, do something like this in your
+//! `Cargo.toml`:
+//!
+//! ```toml
+//! [dependencies]
+//! twilight-gateway = { default-features = false, features = ["rustls-webpki-roots"], version = "0.2" }
+//! ```
 //!
 //! ### zlib
 //!
@@ -76,6 +108,9 @@
 //! described below. Enabling any of the two features below will also enable
 //! `compression`. `compression` is enabled by default.
 //!
+//! With `compression` enabled shards request transport compression from the
+//! gateway, which can be turned off at runtime via `ShardBuilder::compression`.
+//!
 //! There are 2 zlib features `stock-zlib` and `simd-zlib` for the library to work
 //! one of them has to be enabled. If both are enabled it will use `stock-zlib`
 //!
@@ -87,9 +122,25 @@
 //! ### Metrics
 //!
 //! The `metrics` feature provides metrics information via the `metrics` crate.
-//! Some of the metrics logged are counters about received event counts and
-//! their types and gauges about the capacity and efficiency of the inflater of
-//! each shard.
+//! Metrics are labeled with the ID of the shard they're about as `shard`:
+//!
+//! - `GatewayEvent`: counter of received events, labeled by their opcode as
+//!   `GatewayEvent`
+//! - `GatewayDispatch`: counter of received dispatch events, labeled by their
+//!   type as `EventType`
+//! - `GatewayBytes`: counter of bytes received, labeled as `GatewayBytes`:
+//!   `Compressed` for bytes received with transport compression and
+//!   `Uncompressed` for payloads once decompressed
+//! - `GatewayHeartbeatLatency`: histogram of heartbeat latencies
+//! - `GatewayIdentify`: counter of identifies
+//! - `GatewayReconnect`: counter of reconnects, labeled as `GatewayReconnect`:
+//!   `Reconnect` for new sessions and `Resume` for resumed sessions
+//! - `GatewayCommand`: counter of commands and messages sent
+//! - `GatewayEventsDropped`: counter of events dropped because an event
+//!   stream's buffer was full
+//!
+//! Gauges about the capacity and efficiency of the inflater of each shard are
+//! also recorded. When the feature is disabled no metrics code is compiled.
 //!
 //! This is disabled by default.
 //!
@@ -99,6 +150,7 @@
 //! [`serde_json`]: https://crates.io/crates/serde_json
 //! [`simd-json`]: https://crates.io/crates/simd-json
 //! [`twilight-http`]: https://twilight-rs.github.io/twilight/twilight_http/index.html
+//! [`webpki-roots`]: https://crates.io/crates/webpki-roots
 //! [`zlib-ng`]: https://github.com/zlib-ng/zlib-ng
 //! [discord badge]: https://img.shields.io/discord/745809834183753828?color=%237289DA&label=discord%20server&logo=discord&style=for-the-badge
 //! [discord link]: https://discord.gg/7jj8n7D
@@ -129,11 +181,9 @@
 pub mod cluster;
 pub mod shard;
 
-mod event;
 mod listener;
 
-pub use self::event::EventTypeFlags;
-pub use twilight_model::gateway::Intents;
+pub use twilight_model::gateway::{event::EventTypeFlags, Intents};
 
 #[doc(no_inline)]
 pub use self::{
@@ -145,5 +195,11 @@ pub use twilight_gateway_queue as queue;
 #[doc(no_inline)]
 pub use twilight_model::gateway::event::{Event, EventType};
 
-#[cfg(not(any(feature = "native", feature = "rustls")))]
-compile_error!("Either the `native` or `rustls` feature must be enabled");
+#[cfg(not(any(
+    feature = "native-tls",
+    feature = "rustls-native-roots",
+    feature = "rustls-webpki-roots"
+)))]
+compile_error!(
+    "One of the `native-tls`, `rustls-native-roots`, or `rustls-webpki-roots` features must be enabled."
+);
@@ -1,15 +1,42 @@
-use crate::EventTypeFlags;
+use crate::{shard::EventStreamOverflow, EventTypeFlags};
 use dashmap::DashMap;
-use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
-use std::sync::{
-    atomic::{AtomicU64, Ordering},
-    Arc,
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
 };
+use tokio::sync::Notify;
+
+/// Items sent to a listener that it hasn't received yet.
+#[derive(Debug)]
+struct Buffer<T> {
+    /// Whether either half of the listener is gone.
+    closed: AtomicBool,
+    items: Mutex<VecDeque<T>>,
+    /// Waker of the receiver waiting for an item.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl<T> Buffer<T> {
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.wake();
+    }
+
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().expect("waker poisoned").take() {
+            waker.wake();
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Listener<T> {
+    buffer: Arc<Buffer<T>>,
     pub events: EventTypeFlags,
-    pub tx: UnboundedSender<T>,
 }
 
 impl<T> Listener<T> {
@@ -17,10 +44,128 @@ impl<T> Listener<T> {
     pub fn wants(&self, event_type: EventTypeFlags) -> bool {
         self.events.contains(event_type)
     }
+
+    /// Return whether the receiving half of the listener has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.buffer.closed.load(Ordering::Acquire)
+    }
+
+    /// Number of items sent to the listener that it hasn't received yet.
+    pub fn pending(&self) -> usize {
+        self.buffer.items.lock().expect("items poisoned").len()
+    }
+
+    /// Send an item to the listener, returning whether it was received by the
+    /// listener.
+    ///
+    /// If a capacity is given and as many items are pending then an item is
+    /// dropped as the overflow policy says, returning whether one was. When
+    /// applying backpressure the item is always sent, since the sender is
+    /// expected to have waited for room.
+    pub fn send(&self, item: T, capacity: Option<usize>, overflow: EventStreamOverflow) -> bool {
+        if self.is_closed() {
+            return false;
+        }
+
+        let mut dropped = false;
+
+        {
+            let mut items = self.buffer.items.lock().expect("items poisoned");
+
+            match capacity {
+                Some(capacity) if items.len() >= capacity => match overflow {
+                    EventStreamOverflow::Backpressure => items.push_back(item),
+                    EventStreamOverflow::DropNewest => dropped = true,
+                    EventStreamOverflow::DropOldest => {
+                        items.pop_front();
+                        items.push_back(item);
+                        dropped = true;
+                    }
+                },
+                _ => items.push_back(item),
+            }
+        }
+
+        self.buffer.wake();
+
+        dropped
+    }
+}
+
+impl<T> Drop for Listener<T> {
+    fn drop(&mut self) {
+        self.buffer.close();
+    }
+}
+
+/// Receiving half of a listener.
+///
+/// The listener is removed once its receiver is dropped, and the receiver
+/// ends once the listener is removed.
+#[derive(Debug)]
+pub struct ListenerReceiver<T> {
+    buffer: Arc<Buffer<T>>,
+    id: u64,
+    listeners: Listeners<T>,
+}
+
+impl<T> ListenerReceiver<T> {
+    /// Poll for the next item sent to the listener.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if let Some(item) = self.try_recv() {
+            return Poll::Ready(Some(item));
+        }
+
+        self.buffer
+            .waker
+            .lock()
+            .expect("waker poisoned")
+            .replace(cx.waker().clone());
+
+        // An item may have been sent, or the listener removed, before the
+        // waker was registered.
+        if let Some(item) = self.try_recv() {
+            return Poll::Ready(Some(item));
+        }
+
+        if self.buffer.closed.load(Ordering::Acquire) {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+
+    /// Receive the next item if one is available without waiting.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let item = self
+            .buffer
+            .items
+            .lock()
+            .expect("items poisoned")
+            .pop_front()?;
+
+        // The listener has room again, which a sender applying backpressure
+        // may be waiting for.
+        self.listeners.0.space.notify_waiters();
+
+        Some(item)
+    }
+}
+
+impl<T> Drop for ListenerReceiver<T> {
+    fn drop(&mut self) {
+        self.buffer.close();
+        self.listeners.remove(self.id);
+        self.listeners.0.space.notify_waiters();
+    }
 }
 
 #[derive(Debug)]
 struct ListenersRef<T> {
+    /// Maximum number of items pending per listener.
+    capacity: Option<usize>,
+    /// Number of items dropped because a listener was full.
+    dropped: AtomicU64,
     // Bitflags of the event types that all listeners combined want.
     //
     // If listener 1 wants message creates and listener 2 wants message deletes,
@@ -28,14 +173,22 @@ struct ListenersRef<T> {
     event_types: AtomicU64,
     id: AtomicU64,
     listeners: DashMap<u64, Listener<T>>,
+    /// What to do when a listener is full.
+    overflow: EventStreamOverflow,
+    /// Notified whenever a listener receives an item or is removed.
+    space: Notify,
 }
 
 impl<T> Default for ListenersRef<T> {
     fn default() -> Self {
         Self {
+            capacity: None,
+            dropped: AtomicU64::new(0),
             event_types: AtomicU64::new(0),
             id: AtomicU64::new(0),
             listeners: DashMap::new(),
+            overflow: EventStreamOverflow::default(),
+            space: Notify::new(),
         }
     }
 }
@@ -44,14 +197,38 @@ impl<T> Default for ListenersRef<T> {
 pub struct Listeners<T>(Arc<ListenersRef<T>>);
 
 impl<T> Listeners<T> {
-    pub fn add(&self, events: EventTypeFlags) -> UnboundedReceiver<T> {
+    /// Create a map of listeners, each with at most a number of pending items
+    /// beyond which the overflow policy applies.
+    pub fn new(capacity: Option<usize>, overflow: EventStreamOverflow) -> Self {
+        Self(Arc::new(ListenersRef {
+            capacity,
+            overflow,
+            ..ListenersRef::default()
+        }))
+    }
+
+    pub fn add(&self, events: EventTypeFlags) -> ListenerReceiver<T> {
         let id = self.0.id.fetch_add(1, Ordering::Release) + 1;
-        let (tx, rx) = mpsc::unbounded();
+        let buffer = Arc::new(Buffer {
+            closed: AtomicBool::new(false),
+            items: Mutex::new(VecDeque::new()),
+            waker: Mutex::new(None),
+        });
 
-        self.0.listeners.insert(id, Listener { events, tx });
+        self.0.listeners.insert(
+            id,
+            Listener {
+                buffer: Arc::clone(&buffer),
+                events,
+            },
+        );
         self.recalculate_event_types();
 
-        rx
+        ListenerReceiver {
+            buffer,
+            id,
+            listeners: Self(Arc::clone(&self.0)),
+        }
     }
 
     pub fn all(&self) -> &DashMap<u64, Listener<T>> {
@@ -68,11 +245,68 @@ impl<T> Listeners<T> {
         EventTypeFlags::from_bits_truncate(bits)
     }
 
+    /// Return the maximum number of items pending per listener.
+    pub fn capacity(&self) -> Option<usize> {
+        self.0.capacity
+    }
+
+    /// Count an item dropped because a listener was full.
+    pub fn count_dropped(&self) {
+        self.0.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Return the number of items dropped because a listener was full.
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Return what to do when a listener is full.
+    pub fn overflow(&self) -> EventStreamOverflow {
+        self.0.overflow
+    }
+
+    /// Return whether every listener has room for another item, or none of
+    /// them apply backpressure.
+    pub fn has_space(&self) -> bool {
+        let capacity = match (self.0.capacity, self.0.overflow) {
+            (Some(capacity), EventStreamOverflow::Backpressure) => capacity,
+            _ => return true,
+        };
+
+        self.0
+            .listeners
+            .iter()
+            .all(|listener| listener.is_closed() || listener.pending() < capacity)
+    }
+
+    /// Wait until every listener has room for another item if they apply
+    /// backpressure.
+    pub async fn wait_for_space(&self) {
+        loop {
+            // Created before checking, so that no notification is missed in
+            // between.
+            let notified = self.0.space.notified();
+
+            if self.has_space() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+
     /// Return the length of the listeners map.
     pub fn len(&self) -> usize {
         self.0.listeners.len()
     }
 
+    /// Remove a listener, if it hasn't been removed already.
+    pub fn remove(&self, id: u64) {
+        if self.0.listeners.remove(&id).is_some() {
+            self.recalculate_event_types();
+        }
+    }
+
     pub fn remove_all(&self) {
         self.0.listeners.clear();
         self.recalculate_event_types();
@@ -101,19 +335,20 @@ impl<T> Default for Listeners<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::{EventTypeFlags, Listener, Listeners};
+    use super::{EventTypeFlags, Listener, ListenerReceiver, Listeners};
     use static_assertions::assert_impl_all;
     use std::fmt::Debug;
 
     assert_impl_all!(Listeners<()>: Clone, Debug, Default, Send, Sync);
     assert_impl_all!(Listener<()>: Debug, Send, Sync);
+    assert_impl_all!(ListenerReceiver<()>: Debug, Send, Sync);
 
     #[test]
     fn test_total_event_types() {
         let listeners: Listeners<()> = Listeners::default();
-        listeners.add(EventTypeFlags::MESSAGE_CREATE);
+        let _creates = listeners.add(EventTypeFlags::MESSAGE_CREATE);
         assert_eq!(EventTypeFlags::MESSAGE_CREATE, listeners.event_types());
-        listeners.add(EventTypeFlags::MESSAGE_DELETE);
+        let _deletes = listeners.add(EventTypeFlags::MESSAGE_DELETE);
         assert_eq!(
             EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::MESSAGE_DELETE,
             listeners.event_types(),
@@ -121,4 +356,16 @@ mod tests {
         listeners.remove_all();
         assert!(listeners.event_types().is_empty());
     }
+
+    #[test]
+    fn test_dropped_receiver_removed() {
+        let listeners: Listeners<()> = Listeners::default();
+        let _creates = listeners.add(EventTypeFlags::MESSAGE_CREATE);
+        let deletes = listeners.add(EventTypeFlags::MESSAGE_DELETE);
+        assert_eq!(2, listeners.len());
+
+        drop(deletes);
+        assert_eq!(1, listeners.len());
+        assert_eq!(EventTypeFlags::MESSAGE_CREATE, listeners.event_types());
+    }
 }
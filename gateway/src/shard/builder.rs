@@ -1,13 +1,50 @@
-use super::{config::Config, Shard};
+#[cfg(feature = "compression")]
+use super::ShrinkPolicy;
+use super::{
+    config::Config, malformed::DeadLetter, EventStreamOverflow, MalformedDispatch,
+    MalformedDispatchPolicy, ReconnectPolicy, ResumeSession, Shard,
+};
+use crate::EventTypeFlags;
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
 use twilight_gateway_queue::{LocalQueue, Queue};
-use twilight_http::Client as HttpClient;
+use twilight_http::{token, Client as HttpClient};
 use twilight_model::gateway::{payload::update_status::UpdateStatusInfo, Intents};
 
+/// Event types configuration is invalid.
+///
+/// Returned by [`ShardBuilder::event_types`].
+#[derive(Debug)]
+pub enum EventTypesError {
+    /// Some of the provided event types aren't delivered under the configured
+    /// intents.
+    Undeliverable {
+        /// Provided event types that aren't delivered.
+        event_types: EventTypeFlags,
+        /// Intents that aren't configured but deliver these event types.
+        missing_intents: Intents,
+    },
+}
+
+impl Display for EventTypesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Undeliverable {
+                event_types,
+                missing_intents,
+            } => f.write_fmt(format_args!(
+                "event types {:?} aren't delivered without one of the intents {:?}",
+                event_types, missing_intents,
+            )),
+        }
+    }
+}
+
+impl Error for EventTypesError {}
+
 /// Large threshold configuration is invalid.
 ///
 /// Returned by [`ShardBuilder::large_threshold`].
@@ -95,24 +132,48 @@ pub struct ShardBuilder(pub(crate) Config);
 impl ShardBuilder {
     /// Create a new builder to configure and construct a shard.
     ///
+    /// The token may be padded with whitespace and may be prefixed with
+    /// `"Bot "`, which are removed when identifying with the gateway. Its
+    /// structure is validated when the shard is started. Refer to
+    /// [`twilight_http::token`] for more information.
+    ///
+    /// A warning is logged when the shard is started if any of the intents
+    /// are privileged, since the gateway rejects them unless they're enabled
+    /// for the bot. Refer to [`Intents::privileged`].
+    ///
     /// Refer to each method to learn their default values.
     pub fn new(token: impl Into<String>, intents: Intents) -> Self {
-        Self::_new(token.into(), intents)
+        Self::_new(&token.into(), intents)
     }
 
-    fn _new(mut token: String, intents: Intents) -> Self {
-        if !token.starts_with("Bot ") {
-            token.insert_str(0, "Bot ");
-        }
+    fn _new(token: &str, intents: Intents) -> Self {
+        let token = token::raw(token).to_owned();
 
         Self(Config {
+            #[cfg(feature = "compression")]
+            compression: true,
+            dead_letter: None,
+            #[cfg(feature = "etf")]
+            etf: false,
+            event_stream_capacity: None,
+            event_stream_overflow: EventStreamOverflow::default(),
+            event_types: EventTypeFlags::all(),
             gateway_url: None,
-            http_client: HttpClient::new(token.clone()),
+            gateway_url_query: true,
+            heartbeat_jitter: None,
+            http_client: None,
+            #[cfg(feature = "compression")]
+            inflater_shrink_policy: ShrinkPolicy::default(),
             intents,
             large_threshold: 250,
+            malformed_dispatch_policy: MalformedDispatchPolicy::default(),
             presence: None,
             queue: Arc::new(Box::new(LocalQueue::new())),
+            ratelimit_commands: true,
+            reconnect_policy: ReconnectPolicy::default(),
+            session_start_limit_fatal: false,
             shard: [0, 1],
+            tls: None,
             token: token.into_boxed_str(),
             session_id: None,
             sequence: None,
@@ -124,19 +185,262 @@ impl ShardBuilder {
         Shard::new_with_config(self.0)
     }
 
+    /// Set whether to request transport compression from the gateway.
+    ///
+    /// With transport compression the gateway sends all payloads over one
+    /// zlib stream, which greatly reduces the bandwidth used by the shard at
+    /// the cost of decompressing payloads. Refer to
+    /// [`Information::compressed_bytes`] and
+    /// [`Information::decompressed_bytes`] for how many bytes are saved.
+    ///
+    /// Default is `true`.
+    ///
+    /// [`Information::compressed_bytes`]: super::Information::compressed_bytes
+    /// [`Information::decompressed_bytes`]: super::Information::decompressed_bytes
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.0.compression = compression;
+
+        self
+    }
+
+    /// Set a callback that dispatch events which can't be deserialized are
+    /// passed to, such as to store them for inspection later.
+    ///
+    /// The callback receives the raw payload regardless of the
+    /// [`malformed_dispatch_policy`], and is called on the shard's task, so
+    /// it should return quickly. Payloads may contain personal information
+    /// such as message contents, which is why they're otherwise never logged
+    /// in full.
+    ///
+    /// Default is no callback.
+    ///
+    /// # Examples
+    ///
+    /// Print the event type and size of payloads that couldn't be
+    /// deserialized:
+    ///
+    /// ```no_run
+    /// use twilight_gateway::{shard::MalformedDispatchPolicy, Intents, Shard};
+    ///
+    /// let shard = Shard::builder("token", Intents::GUILD_MESSAGES)
+    ///     .malformed_dispatch_policy(MalformedDispatchPolicy::Skip)
+    ///     .dead_letter(|dispatch| {
+    ///         println!(
+    ///             "{:?} ({} bytes): {}",
+    ///             dispatch.event_type(),
+    ///             dispatch.payload().len(),
+    ///             dispatch.error(),
+    ///         );
+    ///     })
+    ///     .build();
+    /// ```
+    ///
+    /// [`malformed_dispatch_policy`]: Self::malformed_dispatch_policy
+    pub fn dead_letter(
+        mut self,
+        dead_letter: impl Fn(&MalformedDispatch<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.0.dead_letter = Some(DeadLetter::new(dead_letter));
+
+        self
+    }
+
+    /// Set whether to encode payloads with ETF instead of JSON.
+    ///
+    /// With ETF the gateway sends payloads in the Erlang Term Format, which is
+    /// smaller and cheaper to decode than JSON, and commands are sent in it
+    /// too. Payloads received via [`EventTypeFlags::SHARD_PAYLOAD`] are then
+    /// ETF terms.
+    ///
+    /// Default is `false`.
+    ///
+    /// [`EventTypeFlags::SHARD_PAYLOAD`]: crate::EventTypeFlags::SHARD_PAYLOAD
+    #[cfg(feature = "etf")]
+    pub fn etf(mut self, etf: bool) -> Self {
+        self.0.etf = etf;
+
+        self
+    }
+
+    /// Set the maximum number of events buffered per event stream.
+    ///
+    /// Events are buffered for each event stream, such as the ones returned by
+    /// [`Shard::events`], until the stream is polled for them. Without a
+    /// capacity, a stream that's polled slowly, or not at all, never slows
+    /// down the shard or other streams; instead its buffer grows. With a
+    /// capacity, [`event_stream_overflow`] decides what happens while a
+    /// stream's buffer is full.
+    ///
+    /// Default is `None`, meaning buffers are unbounded.
+    ///
+    /// [`event_stream_overflow`]: Self::event_stream_overflow
+    pub fn event_stream_capacity(mut self, event_stream_capacity: usize) -> Self {
+        self.0.event_stream_capacity = Some(event_stream_capacity);
+
+        self
+    }
+
+    /// Set what the shard does when the buffer of an event stream is full.
+    ///
+    /// This only applies if an [`event_stream_capacity`] is set. Applying
+    /// backpressure slows the shard down to the pace of its slowest stream,
+    /// while dropping events keeps the other streams going at the cost of
+    /// losing events for the full one, which are logged as warnings and
+    /// counted by [`Information::events_dropped`].
+    ///
+    /// Default is [`EventStreamOverflow::Backpressure`].
+    ///
+    /// [`event_stream_capacity`]: Self::event_stream_capacity
+    /// [`Information::events_dropped`]: super::Information::events_dropped
+    pub fn event_stream_overflow(mut self, event_stream_overflow: EventStreamOverflow) -> Self {
+        self.0.event_stream_overflow = event_stream_overflow;
+
+        self
+    }
+
+    /// Set the event types that the shard emits.
+    ///
+    /// Dispatch events of other types are detected from their event type and
+    /// dropped without being deserialized, so they never reach event streams
+    /// such as the one returned by [`Shard::events`]. This is cheaper than
+    /// only filtering events via [`Shard::some_events`].
+    ///
+    /// Events needed by the shard to operate, such as Hello, Invalid Session,
+    /// Reconnect, Ready, and Resumed, are always processed internally, but are
+    /// only emitted if their types are included.
+    ///
+    /// Default is [`EventTypeFlags::all`].
+    ///
+    /// # Examples
+    ///
+    /// Only emit message creations and the shard's connections:
+    ///
+    /// ```no_run
+    /// use twilight_gateway::{EventTypeFlags, Intents, Shard};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let shard = Shard::builder("token", Intents::GUILD_MESSAGES)
+    ///     .event_types(EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::SHARD_CONNECTED)?
+    ///     .build();
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventTypesError::Undeliverable`] if some of the event types
+    /// aren't delivered under the shard's intents, such as
+    /// [`EventTypeFlags::MEMBER_CHUNK`] without [`Intents::GUILD_MEMBERS`].
+    /// Refer to [`EventTypeFlags::undeliverable`].
+    pub fn event_types(mut self, event_types: EventTypeFlags) -> Result<Self, EventTypesError> {
+        let undeliverable = event_types.undeliverable(self.0.intents);
+
+        if !undeliverable.is_empty() {
+            return Err(EventTypesError::Undeliverable {
+                event_types: undeliverable,
+                missing_intents: undeliverable.intents() - self.0.intents,
+            });
+        }
+
+        self.0.event_types = event_types;
+
+        Ok(self)
+    }
+
     /// Set the URL used for connecting to Discord's gateway
+    ///
+    /// When set, the shard connects to the URL instead of retrieving the URL
+    /// of the gateway from Discord, so it doesn't make a request to
+    /// Discord's HTTP API when starting. This allows connecting through a
+    /// websocket proxy. URLs with the `ws` scheme are connected to without
+    /// TLS, while URLs with the `wss` scheme require the `native` or `rustls`
+    /// feature.
+    ///
+    /// Unless disabled via [`gateway_url_query`], the gateway version,
+    /// encoding, and transport compression are appended to the URL as query
+    /// parameters, after any parameters the URL already has.
+    ///
+    /// # Examples
+    ///
+    /// Connect through a proxy in the cluster's network:
+    ///
+    /// ```no_run
+    /// use twilight_gateway::{Intents, Shard};
+    ///
+    /// let shard = Shard::builder("token", Intents::GUILD_MESSAGES)
+    ///     .gateway_url(Some("ws://gateway-proxy:7878/?region=eu".to_owned()))
+    ///     .build();
+    /// ```
+    ///
+    /// [`gateway_url_query`]: Self::gateway_url_query
     pub fn gateway_url(mut self, gateway_url: Option<String>) -> Self {
         self.0.gateway_url = gateway_url.map(String::into_boxed_str);
 
         self
     }
 
+    /// Set whether to append the gateway version, encoding, and transport
+    /// compression to the gateway URL as query parameters.
+    ///
+    /// Disable this if the URL set via [`gateway_url`] already determines
+    /// them, such as when connecting through a proxy that doesn't accept the
+    /// parameters. The gateway must still send payloads in the encoding and
+    /// with the transport compression the shard is configured with.
+    ///
+    /// Default is `true`.
+    ///
+    /// [`gateway_url`]: Self::gateway_url
+    pub fn gateway_url_query(mut self, gateway_url_query: bool) -> Self {
+        self.0.gateway_url_query = gateway_url_query;
+
+        self
+    }
+
+    /// Set a fixed fraction of the heartbeat interval to wait before the
+    /// first heartbeat of a connection, between 0 and 1.
+    ///
+    /// By default a random fraction is picked for every connection, as
+    /// Discord asks, so that shards connecting at the same time don't all
+    /// heartbeat at once. A fixed fraction makes the timing of the first
+    /// heartbeat predictable, such as in tests.
+    pub fn heartbeat_jitter(mut self, heartbeat_jitter: f64) -> Self {
+        self.0.heartbeat_jitter = Some(heartbeat_jitter);
+
+        self
+    }
+
     /// Set the HTTP client to be used by the shard for getting gateway
     /// information.
     ///
-    /// Default is a new, unconfigured instance of an HTTP client.
+    /// This lets the shard share the configuration of the client used by the
+    /// rest of the bot, such as its proxy. Clients are reference counted, so
+    /// a clone shares the connection pool of the original.
+    ///
+    /// Default is `None`, meaning a new, unconfigured HTTP client is created
+    /// from the token when gateway information needs to be retrieved. None is
+    /// needed if a [`gateway_url`] is set.
+    ///
+    /// [`gateway_url`]: Self::gateway_url
     pub fn http_client(mut self, http_client: HttpClient) -> Self {
-        self.0.http_client = http_client;
+        self.0.http_client = Some(http_client);
+
+        self
+    }
+
+    /// Set the policy for when the buffers payloads are decompressed with
+    /// are shrunk.
+    ///
+    /// With transport compression the buffers grow to fit the largest
+    /// payload received, and are shrunk back once they haven't grown for a
+    /// while. Refer to [`Information::inflater_capacity`] for their current
+    /// capacity.
+    ///
+    /// Default is [`ShrinkPolicy::default`].
+    ///
+    /// [`Information::inflater_capacity`]: super::Information::inflater_capacity
+    #[cfg(feature = "compression")]
+    pub fn inflater_shrink_policy(mut self, inflater_shrink_policy: ShrinkPolicy) -> Self {
+        self.0.inflater_shrink_policy = inflater_shrink_policy;
 
         self
     }
@@ -179,10 +483,67 @@ impl ShardBuilder {
         Ok(self)
     }
 
+    /// Set what the shard does when a dispatch event can't be deserialized.
+    ///
+    /// Refer to [`MalformedDispatchPolicy`] for the available policies.
+    ///
+    /// Default is [`MalformedDispatchPolicy::FailFast`].
+    pub fn malformed_dispatch_policy(
+        mut self,
+        malformed_dispatch_policy: MalformedDispatchPolicy,
+    ) -> Self {
+        self.0.malformed_dispatch_policy = malformed_dispatch_policy;
+
+        self
+    }
+
     /// Set the presence to use automatically when starting a new session.
     ///
+    /// The presence is sent as part of every identify, including those made
+    /// when reconnecting, so the bot never appears with the default presence
+    /// and no separate status update has to be sent. It can still be changed
+    /// afterwards by sending an [`UpdateStatus`] via [`Shard::command`], but
+    /// the shard will go back to this presence when it next identifies.
+    ///
     /// Default is no presence, which defaults to strictly being "online"
     /// with no special qualities.
+    ///
+    /// # Examples
+    ///
+    /// Identify as "Playing twilight" while idle:
+    ///
+    /// ```no_run
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::gateway::{
+    ///     payload::update_status::UpdateStatusInfo,
+    ///     presence::{Activity, ActivityType, Status},
+    /// };
+    ///
+    /// let activity = Activity {
+    ///     application_id: None,
+    ///     assets: None,
+    ///     created_at: None,
+    ///     details: None,
+    ///     emoji: None,
+    ///     flags: None,
+    ///     id: None,
+    ///     instance: None,
+    ///     kind: ActivityType::Playing,
+    ///     name: "twilight".to_owned(),
+    ///     party: None,
+    ///     secrets: None,
+    ///     state: None,
+    ///     timestamps: None,
+    ///     url: None,
+    /// };
+    ///
+    /// let shard = Shard::builder("token", Intents::empty())
+    ///     .presence(UpdateStatusInfo::new(vec![activity], false, None, Status::Idle))
+    ///     .build();
+    /// ```
+    ///
+    /// [`Shard::command`]: super::Shard::command
+    /// [`UpdateStatus`]: twilight_model::gateway::payload::UpdateStatus
     pub fn presence(mut self, presence: UpdateStatusInfo) -> Self {
         self.0.presence.replace(presence);
 
@@ -206,6 +567,114 @@ impl ShardBuilder {
         self
     }
 
+    /// Set whether to ratelimit the commands sent by the shard.
+    ///
+    /// Discord closes the connection of shards sending more than 120 commands
+    /// per minute, heartbeats included. When enabled, [`Shard::send`] and
+    /// [`Shard::command`] wait once the ratelimit is exhausted, and capacity
+    /// is reserved for heartbeats, leaving around 115 commands per minute.
+    /// Refer to [`Information::command_ratelimit`] for the capacity currently
+    /// available.
+    ///
+    /// This can be disabled when connecting through a proxy that already
+    /// ratelimits commands.
+    ///
+    /// Default is `true`.
+    ///
+    /// [`Information::command_ratelimit`]: super::Information::command_ratelimit
+    /// [`Shard::command`]: super::Shard::command
+    /// [`Shard::send`]: super::Shard::send
+    pub fn ratelimit_commands(mut self, ratelimit_commands: bool) -> Self {
+        self.0.ratelimit_commands = ratelimit_commands;
+
+        self
+    }
+
+    /// Set the policy for how long to wait between attempts to reconnect to
+    /// the gateway.
+    ///
+    /// This is used when the shard has to reconnect, such as when its session
+    /// has been invalidated, and while reconnecting keeps failing. Setting a
+    /// maximum number of attempts makes the shard stop once they're used up,
+    /// instead of reconnecting forever.
+    ///
+    /// Default is [`ReconnectPolicy::default`].
+    pub fn reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.0.reconnect_policy = reconnect_policy;
+
+        self
+    }
+
+    /// Set whether the shard stops when the session start limit is used up,
+    /// instead of waiting for it to reset.
+    ///
+    /// Every time the shard identifies it starts a new session, and only so
+    /// many sessions can be started per day. Queues that track this daily
+    /// budget, such as the [`LargeBotQueue`], make the shard wait for the
+    /// limit to reset before it connects. If this is enabled the shard checks
+    /// the [queue's budget] before connecting instead, and if it's used up
+    /// the shard either fails to [start] with
+    /// [`ShardStartError::SessionStartLimitExhausted`] or, when reconnecting,
+    /// stops and emits a [`ShardStopped`] event with
+    /// [`StopReason::SessionStartLimitExhausted`] before its event streams
+    /// end.
+    ///
+    /// Default is `false`.
+    ///
+    /// [`LargeBotQueue`]: crate::queue::LargeBotQueue
+    /// [`ShardStartError::SessionStartLimitExhausted`]: super::ShardStartError::SessionStartLimitExhausted
+    /// [`ShardStopped`]: twilight_model::gateway::event::Event::ShardStopped
+    /// [`StopReason::SessionStartLimitExhausted`]: twilight_model::gateway::event::shard::StopReason::SessionStartLimitExhausted
+    /// [queue's budget]: crate::queue::Queue::daily_budget
+    /// [start]: super::Shard::start
+    pub fn session_start_limit_fatal(mut self, session_start_limit_fatal: bool) -> Self {
+        self.0.session_start_limit_fatal = session_start_limit_fatal;
+
+        self
+    }
+
+    /// Set the session information to resume the shard with.
+    ///
+    /// When set, the shard will resume the session on its first connection
+    /// instead of identifying, and so won't wait for the [`queue`]. This
+    /// requires having saved the session information of a previous shard,
+    /// such as via [`Shard::session`] or [`Shard::shutdown_resumable`].
+    ///
+    /// Note that this does not guarantee the shard will be able to resume. If
+    /// the session is invalid then the shard will identify to initialize a new
+    /// session.
+    ///
+    /// # Examples
+    ///
+    /// Resume a session saved by a previous process:
+    ///
+    /// ```no_run
+    /// use std::env;
+    /// use twilight_gateway::{shard::ResumeSession, Intents, Shard};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    ///
+    /// let mut shard = Shard::builder(token, Intents::GUILD_MESSAGES)
+    ///     .resume_session(ResumeSession {
+    ///         session_id: "session id".to_owned(),
+    ///         sequence: 42,
+    ///     })
+    ///     .build();
+    /// shard.start().await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Shard::session`]: super::Shard::session
+    /// [`Shard::shutdown_resumable`]: super::Shard::shutdown_resumable
+    /// [`queue`]: Self::queue
+    pub fn resume_session(mut self, session: ResumeSession) -> Self {
+        self.0.session_id = Some(session.session_id.into_boxed_str());
+        self.0.sequence = Some(session.sequence);
+
+        self
+    }
+
     /// Set the shard ID to connect as, and the total number of shards used by
     /// the bot.
     ///
@@ -260,11 +729,13 @@ impl<T: Into<String>> From<(T, Intents)> for ShardBuilder {
 
 #[cfg(test)]
 mod tests {
-    use super::{LargeThresholdError, ShardBuilder, ShardIdError};
-    use crate::Intents;
+    use super::{EventTypesError, LargeThresholdError, ShardBuilder, ShardIdError};
+    use crate::{shard::ResumeSession, EventTypeFlags, Intents};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
 
+    assert_fields!(EventTypesError::Undeliverable: event_types, missing_intents);
+    assert_impl_all!(EventTypesError: Debug, Error, Send, Sync);
     assert_fields!(LargeThresholdError::TooFew: value);
     assert_fields!(LargeThresholdError::TooMany: value);
     assert_impl_all!(LargeThresholdError: Debug, Error, Send, Sync);
@@ -277,4 +748,43 @@ mod tests {
     );
     assert_fields!(ShardIdError::IdTooLarge: id, total);
     assert_impl_all!(ShardIdError: Debug, Error, Send, Sync);
+
+    #[tokio::test]
+    async fn test_resume_session() {
+        let builder = ShardBuilder::new("token", Intents::empty());
+        assert!(builder.0.session_id.is_none());
+        assert!(builder.0.sequence.is_none());
+
+        let builder = builder.resume_session(ResumeSession {
+            session_id: "abc".to_owned(),
+            sequence: 42,
+        });
+        assert_eq!(Some("abc"), builder.0.session_id.as_deref());
+        assert_eq!(Some(42), builder.0.sequence);
+    }
+
+    #[tokio::test]
+    async fn test_event_types_undeliverable() {
+        let builder = ShardBuilder::new("token", Intents::GUILDS | Intents::GUILD_MESSAGES);
+        let event_types = EventTypeFlags::GUILD_CREATE
+            | EventTypeFlags::MEMBER_CHUNK
+            | EventTypeFlags::MEMBER_UPDATE
+            | EventTypeFlags::MESSAGE_CREATE;
+
+        assert!(matches!(
+            builder.clone().event_types(event_types),
+            Err(EventTypesError::Undeliverable {
+                event_types,
+                missing_intents: Intents::GUILD_MEMBERS,
+            }) if event_types == EventTypeFlags::MEMBER_CHUNK | EventTypeFlags::MEMBER_UPDATE
+        ));
+
+        let builder = builder
+            .event_types(EventTypeFlags::GUILD_CREATE | EventTypeFlags::READY)
+            .unwrap();
+        assert_eq!(
+            EventTypeFlags::GUILD_CREATE | EventTypeFlags::READY,
+            builder.0.event_types
+        );
+    }
 }
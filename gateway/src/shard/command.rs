@@ -0,0 +1,273 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use twilight_model::gateway::{
+    payload::{
+        identify::Identify, resume::Resume, update_status::UpdateStatus, Heartbeat,
+        RequestGuildMembers, UpdateVoiceState,
+    },
+    OpCode,
+};
+
+/// Command sent to the gateway, covering every opcode that clients send.
+///
+/// Unlike [`Shard::command`], which accepts anything serializable, a command
+/// is always serialized with the opcode of its variant, regardless of the
+/// `op` field of the payload it wraps. Send it via [`Shard::command_typed`].
+///
+/// # Examples
+///
+/// Request the members of a guild whose names start with "tw":
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_gateway::{shard::Command, Intents, Shard};
+/// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+///
+/// let mut shard = Shard::new("token", Intents::GUILD_MEMBERS);
+/// shard.start().await?;
+///
+/// let request = RequestGuildMembers::builder(GuildId(1)).query("tw", Some(10));
+/// shard.command_typed(Command::from(request)).await?;
+/// # Ok(()) }
+/// ```
+///
+/// [`Shard::command`]: super::Shard::command
+/// [`Shard::command_typed`]: super::Shard::command_typed
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Command {
+    /// Heartbeat with the sequence of the last received event.
+    Heartbeat(Heartbeat),
+    /// Start a new session.
+    Identify(Identify),
+    /// Update the presence of the bot.
+    PresenceUpdate(UpdateStatus),
+    /// Request the members of a guild.
+    RequestGuildMembers(RequestGuildMembers),
+    /// Resume a previous session.
+    Resume(Resume),
+    /// Join, move between, or leave voice channels of a guild.
+    VoiceStateUpdate(UpdateVoiceState),
+}
+
+impl Command {
+    /// Opcode the command is sent with.
+    pub fn op(&self) -> OpCode {
+        match self {
+            Self::Heartbeat(_) => OpCode::Heartbeat,
+            Self::Identify(_) => OpCode::Identify,
+            Self::PresenceUpdate(_) => OpCode::StatusUpdate,
+            Self::RequestGuildMembers(_) => OpCode::RequestGuildMembers,
+            Self::Resume(_) => OpCode::Resume,
+            Self::VoiceStateUpdate(_) => OpCode::VoiceStateUpdate,
+        }
+    }
+}
+
+impl From<Heartbeat> for Command {
+    fn from(heartbeat: Heartbeat) -> Self {
+        Self::Heartbeat(heartbeat)
+    }
+}
+
+impl From<Identify> for Command {
+    fn from(identify: Identify) -> Self {
+        Self::Identify(identify)
+    }
+}
+
+impl From<UpdateStatus> for Command {
+    fn from(update_status: UpdateStatus) -> Self {
+        Self::PresenceUpdate(update_status)
+    }
+}
+
+impl From<RequestGuildMembers> for Command {
+    fn from(request_guild_members: RequestGuildMembers) -> Self {
+        Self::RequestGuildMembers(request_guild_members)
+    }
+}
+
+impl From<Resume> for Command {
+    fn from(resume: Resume) -> Self {
+        Self::Resume(resume)
+    }
+}
+
+impl From<UpdateVoiceState> for Command {
+    fn from(update_voice_state: UpdateVoiceState) -> Self {
+        Self::VoiceStateUpdate(update_voice_state)
+    }
+}
+
+impl Serialize for Command {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Command", 2)?;
+
+        match self {
+            Self::Heartbeat(heartbeat) => state.serialize_field("d", &heartbeat.d)?,
+            Self::Identify(identify) => state.serialize_field("d", &identify.d)?,
+            Self::PresenceUpdate(update_status) => state.serialize_field("d", &update_status.d)?,
+            Self::RequestGuildMembers(request) => state.serialize_field("d", &request.d)?,
+            Self::Resume(resume) => state.serialize_field("d", &resume.d)?,
+            Self::VoiceStateUpdate(update) => state.serialize_field("d", &update.d)?,
+        }
+
+        state.serialize_field("op", &self.op())?;
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use serde::Serialize;
+    use serde_json::{json, Value};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        gateway::{
+            payload::{
+                identify::{Identify, IdentifyInfo, IdentifyProperties},
+                resume::Resume,
+                update_status::UpdateStatus,
+                Heartbeat, RequestGuildMembers, UpdateVoiceState,
+            },
+            presence::Status,
+            Intents, OpCode,
+        },
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    assert_impl_all!(Command: Clone, Debug, Eq, PartialEq, Send, Serialize, Sync);
+
+    fn value(command: impl Into<Command>) -> Value {
+        let command: Command = command.into();
+
+        serde_json::to_value(command).unwrap()
+    }
+
+    #[test]
+    fn test_heartbeat() {
+        assert_eq!(json!({ "d": 5, "op": 1 }), value(Heartbeat::new(5)));
+    }
+
+    #[test]
+    fn test_identify() {
+        let identify = Identify::new(IdentifyInfo {
+            compress: false,
+            intents: Intents::GUILDS | Intents::GUILD_MESSAGES,
+            large_threshold: 250,
+            presence: None,
+            properties: IdentifyProperties::new("twilight.rs", "twilight.rs", "linux", "", ""),
+            shard: Some([1, 2]),
+            token: "token".to_owned(),
+            v: 8,
+        });
+
+        assert_eq!(
+            json!({
+                "d": {
+                    "compress": false,
+                    "intents": 513,
+                    "large_threshold": 250,
+                    "presence": null,
+                    "properties": {
+                        "$browser": "twilight.rs",
+                        "$device": "twilight.rs",
+                        "$os": "linux",
+                        "$referrer": "",
+                        "$referring_domain": "",
+                    },
+                    "shard": [1, 2],
+                    "token": "token",
+                    "v": 8,
+                },
+                "op": 2,
+            }),
+            value(identify)
+        );
+    }
+
+    #[test]
+    fn test_presence_update() {
+        let update_status = UpdateStatus::new(Vec::new(), true, 1_600_000_000, Status::Idle);
+
+        assert_eq!(
+            json!({
+                "d": {
+                    "activities": [],
+                    "afk": true,
+                    "since": 1_600_000_000,
+                    "status": "idle",
+                },
+                "op": 3,
+            }),
+            value(update_status)
+        );
+    }
+
+    #[test]
+    fn test_voice_state_update() {
+        let update = UpdateVoiceState::new(GuildId(1), ChannelId(2), false, true);
+
+        assert_eq!(
+            json!({
+                "d": {
+                    "channel_id": "2",
+                    "guild_id": "1",
+                    "self_deaf": false,
+                    "self_mute": true,
+                },
+                "op": 4,
+            }),
+            value(update)
+        );
+    }
+
+    #[test]
+    fn test_resume() {
+        assert_eq!(
+            json!({
+                "d": {
+                    "seq": 7,
+                    "session_id": "abc",
+                    "token": "token",
+                },
+                "op": 6,
+            }),
+            value(Resume::new(7, "abc", "token"))
+        );
+    }
+
+    #[test]
+    fn test_request_guild_members() {
+        let request = RequestGuildMembers::builder(GuildId(1))
+            .nonce("a")
+            .presences(true)
+            .user_id(UserId(2));
+
+        assert_eq!(
+            json!({
+                "d": {
+                    "guild_id": "1",
+                    "nonce": "a",
+                    "presences": true,
+                    "user_ids": "2",
+                },
+                "op": 8,
+            }),
+            value(request)
+        );
+    }
+
+    #[test]
+    fn test_op_of_variant() {
+        let mut heartbeat = Heartbeat::new(5);
+        heartbeat.op = OpCode::Identify;
+        let command = Command::from(heartbeat);
+
+        assert_eq!(OpCode::Heartbeat, command.op());
+        assert_eq!(json!({ "d": 5, "op": 1 }), value(command));
+    }
+}
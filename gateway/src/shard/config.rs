@@ -1,3 +1,10 @@
+#[cfg(feature = "compression")]
+use super::ShrinkPolicy;
+use super::{
+    malformed::DeadLetter, tls::TlsContainer, EventStreamOverflow, MalformedDispatchPolicy,
+    ReconnectPolicy,
+};
+use crate::EventTypeFlags;
 use std::sync::Arc;
 use twilight_gateway_queue::Queue;
 use twilight_http::Client;
@@ -9,30 +16,135 @@ use twilight_model::gateway::{payload::update_status::UpdateStatusInfo, Intents}
 /// Use [`Shard::builder`] to start creating a configured shard.
 ///
 /// [`Shard::builder`]: super::Shard::builder
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug)]
 pub struct Config {
+    #[cfg(feature = "compression")]
+    pub(super) compression: bool,
+    /// Callback that dispatch events which can't be deserialized are passed
+    /// to.
+    pub(super) dead_letter: Option<DeadLetter>,
+    #[cfg(feature = "etf")]
+    pub(super) etf: bool,
+    pub(super) event_stream_capacity: Option<usize>,
+    pub(super) event_stream_overflow: EventStreamOverflow,
+    pub(super) event_types: EventTypeFlags,
     pub(crate) gateway_url: Option<Box<str>>,
-    pub(crate) http_client: Client,
+    pub(super) gateway_url_query: bool,
+    pub(super) heartbeat_jitter: Option<f64>,
+    pub(crate) http_client: Option<Client>,
+    #[cfg(feature = "compression")]
+    pub(super) inflater_shrink_policy: ShrinkPolicy,
     pub(super) intents: Intents,
     pub(super) large_threshold: u64,
+    pub(super) malformed_dispatch_policy: MalformedDispatchPolicy,
     pub(super) presence: Option<UpdateStatusInfo>,
-    pub(super) queue: Arc<Box<dyn Queue>>,
+    pub(crate) queue: Arc<Box<dyn Queue>>,
+    pub(super) ratelimit_commands: bool,
+    pub(super) reconnect_policy: ReconnectPolicy,
+    pub(super) session_start_limit_fatal: bool,
     pub(crate) shard: [u64; 2],
+    /// TLS configuration, built when the shard starts unless cloned from the
+    /// configuration of a cluster.
+    pub(crate) tls: Option<TlsContainer>,
     pub(super) token: Box<str>,
     pub(crate) session_id: Option<Box<str>>,
     pub(crate) sequence: Option<u64>,
 }
 
 impl Config {
+    /// Return whether transport compression is requested from the gateway.
+    ///
+    /// Refer to [`ShardBuilder::compression`] for the default value.
+    ///
+    /// [`ShardBuilder::compression`]: super::ShardBuilder::compression
+    #[cfg(feature = "compression")]
+    pub fn compression(&self) -> bool {
+        self.compression
+    }
+
+    /// Return whether payloads are encoded with ETF instead of JSON.
+    ///
+    /// Refer to [`ShardBuilder::etf`] for the default value.
+    ///
+    /// [`ShardBuilder::etf`]: super::ShardBuilder::etf
+    #[cfg(feature = "etf")]
+    pub fn etf(&self) -> bool {
+        self.etf
+    }
+
+    /// Return the maximum number of events buffered per event stream.
+    ///
+    /// Refer to [`ShardBuilder::event_stream_capacity`] for the default value.
+    ///
+    /// [`ShardBuilder::event_stream_capacity`]: super::ShardBuilder::event_stream_capacity
+    pub fn event_stream_capacity(&self) -> Option<usize> {
+        self.event_stream_capacity
+    }
+
+    /// Return what the shard does when the buffer of an event stream is full.
+    ///
+    /// Refer to [`ShardBuilder::event_stream_overflow`] for the default value.
+    ///
+    /// [`ShardBuilder::event_stream_overflow`]: super::ShardBuilder::event_stream_overflow
+    pub fn event_stream_overflow(&self) -> EventStreamOverflow {
+        self.event_stream_overflow
+    }
+
+    /// Return the event types that the shard emits.
+    ///
+    /// Refer to [`ShardBuilder::event_types`] for the default value.
+    ///
+    /// [`ShardBuilder::event_types`]: super::ShardBuilder::event_types
+    pub fn event_types(&self) -> EventTypeFlags {
+        self.event_types
+    }
+
     /// Return an immutable reference to the url used to connect to the gateway.
     pub fn gateway_url(&self) -> Option<&str> {
         self.gateway_url.as_deref()
     }
 
-    /// Return an immutable reference to the `twilight_http` client to be used
-    /// by the shard.
-    pub fn http_client(&self) -> &Client {
-        &self.http_client
+    /// Return whether the gateway version, encoding, and transport compression
+    /// are appended to the gateway URL as query parameters.
+    ///
+    /// Refer to [`ShardBuilder::gateway_url_query`] for the default value.
+    ///
+    /// [`ShardBuilder::gateway_url_query`]: super::ShardBuilder::gateway_url_query
+    pub fn gateway_url_query(&self) -> bool {
+        self.gateway_url_query
+    }
+
+    /// Return the fraction of the heartbeat interval to wait before the first
+    /// heartbeat of a connection, if it's fixed instead of random.
+    ///
+    /// Refer to [`ShardBuilder::heartbeat_jitter`] for the default value.
+    ///
+    /// [`ShardBuilder::heartbeat_jitter`]: super::ShardBuilder::heartbeat_jitter
+    pub fn heartbeat_jitter(&self) -> Option<f64> {
+        self.heartbeat_jitter
+    }
+
+    /// Return an immutable reference to the `twilight_http` client used by
+    /// the shard to retrieve gateway information, if one was provided.
+    ///
+    /// Refer to [`ShardBuilder::http_client`] for the default value.
+    ///
+    /// [`ShardBuilder::http_client`]: super::ShardBuilder::http_client
+    pub fn http_client(&self) -> Option<&Client> {
+        self.http_client.as_ref()
+    }
+
+    /// Return the policy for when the buffers payloads are decompressed with
+    /// are shrunk.
+    ///
+    /// Refer to [`ShardBuilder::inflater_shrink_policy`] for the default
+    /// value.
+    ///
+    /// [`ShardBuilder::inflater_shrink_policy`]: super::ShardBuilder::inflater_shrink_policy
+    #[cfg(feature = "compression")]
+    pub fn inflater_shrink_policy(&self) -> ShrinkPolicy {
+        self.inflater_shrink_policy
     }
 
     /// Return a copy of the intents that the gateway is using.
@@ -46,6 +158,17 @@ impl Config {
         self.large_threshold
     }
 
+    /// Return what the shard does when a dispatch event can't be
+    /// deserialized.
+    ///
+    /// Refer to [`ShardBuilder::malformed_dispatch_policy`] for the default
+    /// value.
+    ///
+    /// [`ShardBuilder::malformed_dispatch_policy`]: super::ShardBuilder::malformed_dispatch_policy
+    pub fn malformed_dispatch_policy(&self) -> MalformedDispatchPolicy {
+        self.malformed_dispatch_policy
+    }
+
     /// Return an immutable reference to the presence to set when identifying
     /// with the gateway.
     ///
@@ -55,6 +178,36 @@ impl Config {
         self.presence.as_ref()
     }
 
+    /// Return whether commands sent by the shard are ratelimited.
+    ///
+    /// Refer to [`ShardBuilder::ratelimit_commands`] for the default value.
+    ///
+    /// [`ShardBuilder::ratelimit_commands`]: super::ShardBuilder::ratelimit_commands
+    pub fn ratelimit_commands(&self) -> bool {
+        self.ratelimit_commands
+    }
+
+    /// Return an immutable reference to the policy for how long to wait
+    /// between attempts to reconnect.
+    ///
+    /// Refer to [`ShardBuilder::reconnect_policy`] for the default value.
+    ///
+    /// [`ShardBuilder::reconnect_policy`]: super::ShardBuilder::reconnect_policy
+    pub fn reconnect_policy(&self) -> &ReconnectPolicy {
+        &self.reconnect_policy
+    }
+
+    /// Return whether the shard stops when the session start limit is used up,
+    /// instead of waiting for it to reset.
+    ///
+    /// Refer to [`ShardBuilder::session_start_limit_fatal`] for the default
+    /// value.
+    ///
+    /// [`ShardBuilder::session_start_limit_fatal`]: super::ShardBuilder::session_start_limit_fatal
+    pub fn session_start_limit_fatal(&self) -> bool {
+        self.session_start_limit_fatal
+    }
+
     /// The shard's ID and the total number of shards used by the bot.
     pub fn shard(&self) -> [u64; 2] {
         self.shard
@@ -62,6 +215,9 @@ impl Config {
 
     /// Return an immutable reference to the token used to authenticate with
     /// when identifying with the gateway.
+    ///
+    /// This is the token without surrounding whitespace and without the
+    /// `"Bot "` prefix.
     pub fn token(&self) -> &str {
         &self.token
     }
@@ -0,0 +1,469 @@
+use super::{tag, EtfError};
+use serde::de::{
+    self,
+    value::{BorrowedStrDeserializer, SeqDeserializer, StringDeserializer},
+    DeserializeSeed, EnumAccess, IgnoredAny, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use std::{borrow::Cow, convert::TryFrom, str};
+
+/// Deserializer of values from an ETF term.
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    /// Create a deserializer over a term, checking its version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EtfError::VersionUnsupported`] if the term isn't of the
+    /// supported version of the format.
+    pub fn from_slice(input: &'de [u8]) -> Result<Self, EtfError> {
+        match input.split_first() {
+            Some((&tag::VERSION, input)) => Ok(Self { input }),
+            Some((&version, _)) => Err(EtfError::VersionUnsupported { version }),
+            None => Err(EtfError::Eof),
+        }
+    }
+
+    /// Check that the whole input has been deserialized.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EtfError::TrailingBytes`] if there are bytes remaining.
+    pub fn end(&self) -> Result<(), EtfError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(EtfError::TrailingBytes)
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8], EtfError> {
+        if self.input.len() < len {
+            return Err(EtfError::Eof);
+        }
+
+        let (taken, input) = self.input.split_at(len);
+        self.input = input;
+
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, EtfError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, EtfError> {
+        self.take(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, EtfError> {
+        self.take(4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn len(&mut self) -> Result<usize, EtfError> {
+        usize::try_from(self.u32()?).map_err(|_| EtfError::LengthTooLarge)
+    }
+
+    /// Read the length of an atom, which is stored in one byte for the small
+    /// variants.
+    fn small_len(&mut self, small: bool) -> Result<usize, EtfError> {
+        if small {
+            self.u8().map(usize::from)
+        } else {
+            self.u16().map(usize::from)
+        }
+    }
+
+    /// Read the name of an atom after its tag.
+    fn atom(&mut self, tag: u8) -> Result<Cow<'de, str>, EtfError> {
+        let len = self.small_len(matches!(tag, tag::SMALL_ATOM | tag::SMALL_ATOM_UTF8))?;
+        let bytes = self.take(len)?;
+
+        if matches!(tag, tag::ATOM_UTF8 | tag::SMALL_ATOM_UTF8) || bytes.is_ascii() {
+            return str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|source| EtfError::Utf8 { source });
+        }
+
+        // Atoms with the other tags are Latin-1, whose characters are the
+        // first 256 of Unicode.
+        Ok(Cow::Owned(bytes.iter().copied().map(char::from).collect()))
+    }
+
+    /// Consume the next term if it's the `nil` atom.
+    fn nil(&mut self) -> Result<bool, EtfError> {
+        let mut peek = Self { input: self.input };
+        let tag = peek.u8()?;
+
+        if tag::is_atom(tag) && peek.atom(tag)? == "nil" {
+            self.input = peek.input;
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Read a big integer after its length.
+    fn big<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, EtfError> {
+        let negative = self.u8()? != 0;
+        let digits = self.take(len)?;
+
+        // Digits are stored in little endian order.
+        if digits.iter().skip(8).any(|digit| *digit != 0) {
+            return Err(EtfError::IntegerOutOfRange);
+        }
+
+        let value = digits
+            .iter()
+            .take(8)
+            .rev()
+            .fold(0, |value, digit| (value << 8) | u64::from(*digit));
+
+        if !negative {
+            return visitor.visit_u64(value);
+        }
+
+        // The magnitude of `i64::MIN` doesn't fit in an `i64`.
+        let value = if value == 1u64 << 63 {
+            i64::MIN
+        } else {
+            -i64::try_from(value).map_err(|_| EtfError::IntegerOutOfRange)?
+        };
+
+        visitor.visit_i64(value)
+    }
+
+    /// Skip over the next term.
+    fn skip(&mut self) -> Result<(), EtfError> {
+        let tag = self.u8()?;
+
+        let len = match tag {
+            tag::SMALL_INTEGER => 1,
+            tag::INTEGER => 4,
+            tag::NEW_FLOAT => 8,
+            tag::FLOAT => 31,
+            tag::SMALL_BIG => usize::from(self.u8()?) + 1,
+            tag::LARGE_BIG => self.len()? + 1,
+            tag::ATOM | tag::ATOM_UTF8 | tag::STRING => usize::from(self.u16()?),
+            tag::SMALL_ATOM | tag::SMALL_ATOM_UTF8 => usize::from(self.u8()?),
+            tag::BINARY => self.len()?,
+            tag::NIL => 0,
+            tag::LIST | tag::SMALL_TUPLE | tag::LARGE_TUPLE | tag::MAP => {
+                let terms = match tag {
+                    // Lists end with a tail, which is usually nil.
+                    tag::LIST => self.len()? + 1,
+                    tag::SMALL_TUPLE => usize::from(self.u8()?),
+                    tag::LARGE_TUPLE => self.len()?,
+                    _ => self.len()? * 2,
+                };
+
+                for _ in 0..terms {
+                    self.skip()?;
+                }
+
+                0
+            }
+            tag => return Err(EtfError::TagUnsupported { tag }),
+        };
+
+        self.take(len).map(|_| ())
+    }
+
+    /// Visit the elements of a list or tuple, skipping those that weren't
+    /// visited.
+    fn seq<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, EtfError> {
+        let mut access = Access {
+            de: self,
+            remaining: len,
+        };
+        let value = visitor.visit_seq(&mut access)?;
+
+        for _ in 0..access.remaining {
+            self.skip()?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = EtfError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.u8()? {
+            tag::SMALL_INTEGER => visitor.visit_u64(u64::from(self.u8()?)),
+            tag::INTEGER => {
+                let value = self.u32()?.to_be_bytes();
+                let value = i32::from_be_bytes(value);
+
+                match u64::try_from(value) {
+                    Ok(value) => visitor.visit_u64(value),
+                    Err(_) => visitor.visit_i64(i64::from(value)),
+                }
+            }
+            tag::NEW_FLOAT => {
+                let bytes = self.take(8)?;
+                let mut value = [0; 8];
+                value.copy_from_slice(bytes);
+
+                visitor.visit_f64(f64::from_be_bytes(value))
+            }
+            tag::FLOAT => {
+                // Old floats are stored as a string padded with null bytes.
+                let bytes = self.take(31)?;
+                let value = str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|value| value.trim_end_matches('\0').parse().ok())
+                    .ok_or(EtfError::FloatInvalid)?;
+
+                visitor.visit_f64(value)
+            }
+            tag::SMALL_BIG => {
+                let len = usize::from(self.u8()?);
+
+                self.big(len, visitor)
+            }
+            tag::LARGE_BIG => {
+                let len = self.len()?;
+
+                self.big(len, visitor)
+            }
+            tag if tag::is_atom(tag) => match self.atom(tag)? {
+                Cow::Borrowed("nil") => visitor.visit_unit(),
+                Cow::Borrowed("true") => visitor.visit_bool(true),
+                Cow::Borrowed("false") => visitor.visit_bool(false),
+                Cow::Borrowed(name) => visitor.visit_borrowed_str(name),
+                Cow::Owned(name) => visitor.visit_string(name),
+            },
+            tag::BINARY => {
+                let len = self.len()?;
+                let bytes = self.take(len)?;
+
+                match str::from_utf8(bytes) {
+                    Ok(value) => visitor.visit_borrowed_str(value),
+                    Err(_) => visitor.visit_borrowed_bytes(bytes),
+                }
+            }
+            tag::STRING => {
+                // Lists of small integers may be sent as a string of bytes.
+                let len = usize::from(self.u16()?);
+                let bytes = self.take(len)?;
+
+                visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied()))
+            }
+            tag::NIL => self.seq(0, visitor),
+            tag::LIST => {
+                let len = self.len()?;
+                let value = self.seq(len, visitor)?;
+
+                // Skip the tail of the list, which is nil for proper lists.
+                self.skip()?;
+
+                Ok(value)
+            }
+            tag::SMALL_TUPLE => {
+                let len = usize::from(self.u8()?);
+
+                self.seq(len, visitor)
+            }
+            tag::LARGE_TUPLE => {
+                let len = self.len()?;
+
+                self.seq(len, visitor)
+            }
+            tag::MAP => {
+                let len = self.len()?;
+                let mut access = Access {
+                    de: self,
+                    remaining: len,
+                };
+                let value = visitor.visit_map(&mut access)?;
+
+                for _ in 0..access.remaining * 2 {
+                    self.skip()?;
+                }
+
+                Ok(value)
+            }
+            tag => Err(EtfError::TagUnsupported { tag }),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.input.first() == Some(&tag::BINARY) {
+            self.take(1)?;
+            let len = self.len()?;
+
+            return visitor.visit_borrowed_bytes(self.take(len)?);
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.input.first().copied() {
+            Some(tag::MAP) => {
+                self.take(1)?;
+
+                match self.len()? {
+                    1 => visitor.visit_enum(Enum { de: self }),
+                    len => Err(de::Error::invalid_length(len, &"a map with one entry")),
+                }
+            }
+            Some(tag) if tag::is_atom(tag) => {
+                self.take(1)?;
+
+                match self.atom(tag)? {
+                    Cow::Borrowed(name) => visitor.visit_enum(BorrowedStrDeserializer::new(name)),
+                    Cow::Owned(name) => visitor.visit_enum(StringDeserializer::new(name)),
+                }
+            }
+            Some(tag::BINARY) => {
+                self.take(1)?;
+                let len = self.len()?;
+                let name =
+                    str::from_utf8(self.take(len)?).map_err(|source| EtfError::Utf8 { source })?;
+
+                visitor.visit_enum(BorrowedStrDeserializer::new(name))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.skip()?;
+
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.nil()? {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string unit
+        unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// Access to the elements of a list or tuple, or the entries of a map.
+struct Access<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Access<'_, 'de> {
+    type Error = EtfError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> MapAccess<'de> for Access<'_, 'de> {
+    type Error = EtfError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Access to an enum variant stored as a map with one entry.
+struct Enum<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'_, 'de> {
+    type Error = EtfError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(&mut *self.de)?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Enum<'_, 'de> {
+    type Error = EtfError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.de).map(|IgnoredAny| ())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
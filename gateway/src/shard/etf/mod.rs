@@ -0,0 +1,406 @@
+//! Serde implementation of the External Term Format (ETF) as used by the
+//! gateway when connecting with `encoding=etf`.
+//!
+//! Only the terms that Discord sends and accepts are supported: integers,
+//! floats, atoms, binaries, lists, tuples, and maps. The atoms `nil`, `true`,
+//! and `false` are treated as null and booleans. Binaries are deserialized as
+//! strings if they're valid UTF-8.
+//!
+//! Values are serialized in the same shape as they would be in JSON, with
+//! strings and map keys serialized as binaries.
+
+mod de;
+mod ser;
+
+pub use self::{de::Deserializer, ser::Serializer};
+
+use super::json::GatewayEventParsingError;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::Utf8Error,
+};
+use twilight_model::gateway::event::{GatewayEvent, GatewayEventDeserializer};
+
+/// Tags of the supported terms.
+mod tag {
+    pub const VERSION: u8 = 131;
+
+    pub const NEW_FLOAT: u8 = 70;
+    pub const SMALL_INTEGER: u8 = 97;
+    pub const INTEGER: u8 = 98;
+    pub const FLOAT: u8 = 99;
+    pub const ATOM: u8 = 100;
+    pub const SMALL_TUPLE: u8 = 104;
+    pub const LARGE_TUPLE: u8 = 105;
+    pub const NIL: u8 = 106;
+    pub const STRING: u8 = 107;
+    pub const LIST: u8 = 108;
+    pub const BINARY: u8 = 109;
+    pub const SMALL_BIG: u8 = 110;
+    pub const LARGE_BIG: u8 = 111;
+    pub const SMALL_ATOM: u8 = 115;
+    pub const MAP: u8 = 116;
+    pub const ATOM_UTF8: u8 = 118;
+    pub const SMALL_ATOM_UTF8: u8 = 119;
+
+    /// Whether a tag is of any of the atom terms.
+    pub fn is_atom(tag: u8) -> bool {
+        matches!(tag, ATOM | SMALL_ATOM | ATOM_UTF8 | SMALL_ATOM_UTF8)
+    }
+}
+
+/// Serializing or deserializing a payload as ETF failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EtfError {
+    /// A serde implementation returned an error.
+    Custom {
+        /// Message of the error.
+        message: String,
+    },
+    /// The input ended in the middle of a term.
+    Eof,
+    /// A float term stored as a string couldn't be parsed.
+    FloatInvalid,
+    /// A big integer doesn't fit in 64 bits.
+    IntegerOutOfRange,
+    /// A list, map, or binary is too long to be serialized.
+    LengthTooLarge,
+    /// The type of a term isn't supported.
+    TagUnsupported {
+        /// Tag of the term.
+        tag: u8,
+    },
+    /// The input has bytes remaining after the term.
+    TrailingBytes,
+    /// An atom that should be UTF-8 isn't.
+    Utf8 {
+        /// Reason for the error.
+        source: Utf8Error,
+    },
+    /// The version of the format isn't supported.
+    VersionUnsupported {
+        /// Version of the format.
+        version: u8,
+    },
+}
+
+impl Display for EtfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Custom { message } => f.write_str(message),
+            Self::Eof => f.write_str("input ended in the middle of a term"),
+            Self::FloatInvalid => f.write_str("float term is invalid"),
+            Self::IntegerOutOfRange => f.write_str("big integer doesn't fit in 64 bits"),
+            Self::LengthTooLarge => f.write_str("term is too long to be serialized"),
+            Self::TagUnsupported { tag } => {
+                f.write_fmt(format_args!("term with tag {} is unsupported", tag))
+            }
+            Self::TrailingBytes => f.write_str("input has bytes remaining after the term"),
+            Self::Utf8 { source } => Display::fmt(source, f),
+            Self::VersionUnsupported { version } => {
+                f.write_fmt(format_args!("format version {} is unsupported", version))
+            }
+        }
+    }
+}
+
+impl Error for EtfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Utf8 { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl serde::de::Error for EtfError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom {
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl serde::ser::Error for EtfError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Deserialize a value from a complete ETF term.
+///
+/// # Errors
+///
+/// Returns an [`EtfError`] if the input isn't a valid term or has bytes
+/// remaining after it, or if the value failed to deserialize from it.
+pub fn from_slice<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, EtfError> {
+    let mut deserializer = Deserializer::from_slice(bytes)?;
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    Ok(value)
+}
+
+/// Serialize a value as an ETF term.
+///
+/// # Errors
+///
+/// Returns an [`EtfError`] if the value failed to serialize.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, EtfError> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.into_inner())
+}
+
+/// Parse a gateway event from an ETF term with its headers.
+///
+/// # Errors
+///
+/// Returns [`GatewayEventParsingError::DeserializingEtf`] if the payload
+/// failed to deserialize.
+pub fn parse_gateway_event(
+    op: u8,
+    sequence: Option<u64>,
+    event_type: Option<&str>,
+    bytes: &[u8],
+) -> Result<GatewayEvent, GatewayEventParsingError> {
+    let gateway_deserializer = GatewayEventDeserializer::new(op, sequence, event_type);
+
+    Deserializer::from_slice(bytes)
+        .and_then(|mut deserializer| {
+            let event = gateway_deserializer.deserialize(&mut deserializer)?;
+            deserializer.end()?;
+
+            Ok(event)
+        })
+        .map_err(|source| {
+            tracing::debug!(?bytes, "invalid ETF");
+
+            GatewayEventParsingError::DeserializingEtf { source }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_slice, parse_gateway_event, to_vec, EtfError};
+    use serde_json::{json, Value};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::{
+        gateway::{
+            event::{DispatchEvent, GatewayEvent},
+            payload::RequestGuildMembers,
+        },
+        guild::{GuildStatus, UnavailableGuild},
+        id::{ChannelId, GuildId, MessageId, UserId},
+    };
+
+    assert_fields!(EtfError::Custom: message);
+    assert_fields!(EtfError::TagUnsupported: tag);
+    assert_fields!(EtfError::Utf8: source);
+    assert_fields!(EtfError::VersionUnsupported: version);
+    assert_impl_all!(EtfError: Debug, Error, Send, Sync);
+
+    /// Encode a payload the way Discord does, with snowflakes as integers.
+    fn payload(op: u8, seq: u64, event_type: &str, d: &Value) -> Vec<u8> {
+        to_vec(&json!({
+            "d": d,
+            "op": op,
+            "s": seq,
+            "t": event_type,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_terms() {
+        // term_to_binary(#{op => 11, d => nil, s => -1, t => <<"a">>})
+        let bytes = [
+            131, 116, 0, 0, 0, 4, 119, 2, b'o', b'p', 97, 11, 100, 0, 1, b'd', 115, 3, b'n', b'i',
+            b'l', 119, 1, b's', 98, 255, 255, 255, 255, 119, 1, b't', 109, 0, 0, 0, 1, b'a',
+        ];
+
+        assert_eq!(
+            json!({ "d": null, "op": 11, "s": -1, "t": "a" }),
+            from_slice::<Value>(&bytes).unwrap()
+        );
+
+        // term_to_binary([{1, 2.5}, "ab", 18446744073709551615, true])
+        let bytes = [
+            131, 108, 0, 0, 0, 4, 104, 2, 97, 1, 70, 64, 4, 0, 0, 0, 0, 0, 0, 107, 0, 2, b'a',
+            b'b', 110, 8, 0, 255, 255, 255, 255, 255, 255, 255, 255, 119, 4, b't', b'r', b'u',
+            b'e', 106,
+        ];
+
+        assert_eq!(
+            json!([[1, 2.5], [97, 98], u64::MAX, true]),
+            from_slice::<Value>(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            from_slice::<Value>(&[130, 106]),
+            Err(EtfError::VersionUnsupported { version: 130 })
+        ));
+        assert!(matches!(
+            from_slice::<Value>(&[131, 109, 0, 0, 0, 2, b'a']),
+            Err(EtfError::Eof)
+        ));
+        assert!(matches!(
+            from_slice::<Value>(&[131, 106, 106]),
+            Err(EtfError::TrailingBytes)
+        ));
+        assert!(matches!(
+            from_slice::<Value>(&[131, 110, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            Err(EtfError::IntegerOutOfRange)
+        ));
+        assert!(matches!(
+            from_slice::<Value>(&[131, 88]),
+            Err(EtfError::TagUnsupported { tag: 88 })
+        ));
+    }
+
+    #[test]
+    fn test_encode_values() {
+        let value = json!({
+            "a": [],
+            "b": [1, -1, 300, -2_147_483_649_i64, u64::MAX],
+            "c": null,
+            "d": false,
+            "e": 0.5,
+        });
+        let bytes = to_vec(&value).unwrap();
+
+        assert_eq!(value, from_slice::<Value>(&bytes).unwrap());
+        // Empty lists are encoded as nil.
+        assert_eq!(
+            [131, 116, 0, 0, 0, 1, 109, 0, 0, 0, 1, b'a', 106],
+            to_vec(&json!({ "a": [] })).unwrap().as_slice()
+        );
+        assert_eq!(
+            [131, 110, 4, 1, 1, 0, 0, 128],
+            to_vec(&-2_147_483_649_i64).unwrap().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_ready() {
+        let bytes = payload(
+            0,
+            1,
+            "READY",
+            &json!({
+                "application": {
+                    "flags": 0,
+                    "id": 100_000_000_000_000_000_u64,
+                },
+                "guilds": [{
+                    "id": 200_000_000_000_000_000_u64,
+                    "unavailable": true,
+                }],
+                "private_channels": [],
+                "session_id": "foo",
+                "shard": [0, 1],
+                "user": {
+                    "avatar": null,
+                    "bot": true,
+                    "discriminator": "0001",
+                    "id": 100_000_000_000_000_000_u64,
+                    "mfa_enabled": true,
+                    "username": "bot",
+                    "verified": true,
+                },
+                "v": 8,
+            }),
+        );
+
+        let ready = match parse_gateway_event(0, Some(1), Some("READY"), &bytes).unwrap() {
+            GatewayEvent::Dispatch(1, event) => match *event {
+                DispatchEvent::Ready(ready) => ready,
+                other => panic!("not ready: {:?}", other),
+            },
+            other => panic!("not a dispatch: {:?}", other),
+        };
+
+        assert_eq!(
+            vec![GuildStatus::Offline(UnavailableGuild {
+                id: GuildId(200_000_000_000_000_000),
+                unavailable: true,
+            })],
+            ready.guilds
+        );
+        assert_eq!("foo", ready.session_id);
+        assert_eq!(Some([0, 1]), ready.shard);
+        assert_eq!(UserId(100_000_000_000_000_000), ready.user.id);
+        assert!(ready.user.avatar.is_none());
+    }
+
+    #[test]
+    fn test_message_create() {
+        let bytes = payload(
+            0,
+            2,
+            "MESSAGE_CREATE",
+            &json!({
+                "attachments": [],
+                "author": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": 300_000_000_000_000_000_u64,
+                    "username": "user",
+                },
+                "channel_id": 400_000_000_000_000_000_u64,
+                "content": "ping",
+                "edited_timestamp": null,
+                "embeds": [],
+                "guild_id": 200_000_000_000_000_000_u64,
+                "id": 500_000_000_000_000_000_u64,
+                "mention_everyone": false,
+                "mention_roles": [],
+                "mentions": [],
+                "pinned": false,
+                "timestamp": "2021-01-01T00:00:00.000000+00:00",
+                "tts": false,
+                "type": 0,
+            }),
+        );
+
+        let message = match parse_gateway_event(0, Some(2), Some("MESSAGE_CREATE"), &bytes) {
+            Ok(GatewayEvent::Dispatch(2, event)) => match *event {
+                DispatchEvent::MessageCreate(message) => message,
+                other => panic!("not a message: {:?}", other),
+            },
+            other => panic!("not a dispatch: {:?}", other),
+        };
+
+        assert_eq!(MessageId(500_000_000_000_000_000), message.id);
+        assert_eq!(ChannelId(400_000_000_000_000_000), message.channel_id);
+        assert_eq!(Some(GuildId(200_000_000_000_000_000)), message.guild_id);
+        assert_eq!(UserId(300_000_000_000_000_000), message.author.id);
+        assert_eq!("ping", message.content);
+    }
+
+    #[test]
+    fn test_request_guild_members() {
+        let command = RequestGuildMembers::builder(GuildId(1))
+            .nonce("nonce")
+            .user_ids(vec![UserId(2), UserId(3)])
+            .unwrap();
+        let bytes = to_vec(&command).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            from_slice::<Value>(&bytes).unwrap()
+        );
+
+        assert_eq!(command, from_slice::<RequestGuildMembers>(&bytes).unwrap());
+    }
+}
@@ -0,0 +1,429 @@
+use super::{tag, EtfError};
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use std::convert::TryFrom;
+
+/// Serializer of values into an ETF term.
+#[derive(Debug)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    /// Create a serializer with only the version of the format written.
+    pub fn new() -> Self {
+        Self {
+            output: vec![tag::VERSION],
+        }
+    }
+
+    /// Consume the serializer, returning the serialized term.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    // Only the short names of the special atoms are serialized, so they're
+    // stored as small atoms.
+    #[allow(clippy::cast_possible_truncation)]
+    fn atom(&mut self, name: &str) {
+        self.output.push(tag::SMALL_ATOM_UTF8);
+        self.output.push(name.len() as u8);
+        self.output.extend_from_slice(name.as_bytes());
+    }
+
+    // There are at most 8 digits.
+    #[allow(clippy::cast_possible_truncation)]
+    fn big(&mut self, negative: bool, value: u64) {
+        let digits = value.to_le_bytes();
+        let len = digits.len() - value.leading_zeros() as usize / 8;
+
+        self.output.push(tag::SMALL_BIG);
+        self.output.push(len as u8);
+        self.output.push(u8::from(negative));
+        self.output.extend_from_slice(&digits[..len]);
+    }
+
+    fn binary(&mut self, bytes: &[u8]) -> Result<(), EtfError> {
+        let len = u32::try_from(bytes.len()).map_err(|_| EtfError::LengthTooLarge)?;
+
+        self.output.push(tag::BINARY);
+        self.output.extend_from_slice(&len.to_be_bytes());
+        self.output.extend_from_slice(bytes);
+
+        Ok(())
+    }
+
+    /// Start a map with one entry keyed by the name of an enum variant.
+    fn variant(&mut self, variant: &str) -> Result<(), EtfError> {
+        self.output.push(tag::MAP);
+        self.output.extend_from_slice(&1_u32.to_be_bytes());
+
+        self.binary(variant.as_bytes())
+    }
+
+    /// Start a list or map whose length is written once it ends.
+    fn compound(&mut self, tag: u8) -> Compound<'_> {
+        let start = self.output.len();
+        self.output.push(tag);
+        self.output.extend_from_slice(&[0; 4]);
+
+        Compound {
+            len: 0,
+            ser: self,
+            start,
+        }
+    }
+}
+
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = EtfError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.atom(if v { "true" } else { "false" });
+
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        if let Ok(v) = u8::try_from(v) {
+            self.output.push(tag::SMALL_INTEGER);
+            self.output.push(v);
+        } else if let Ok(v) = i32::try_from(v) {
+            self.output.push(tag::INTEGER);
+            self.output.extend_from_slice(&v.to_be_bytes());
+        } else {
+            // Two's complement negation gives the magnitude of negative
+            // values, including `i64::MIN`.
+            #[allow(clippy::cast_sign_loss)]
+            let magnitude = if v < 0 {
+                (v as u64).wrapping_neg()
+            } else {
+                v as u64
+            };
+
+            self.big(v < 0, magnitude);
+        }
+
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        if let Ok(v) = i64::try_from(v) {
+            return self.serialize_i64(v);
+        }
+
+        self.big(false, v);
+
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        self.output.push(tag::NEW_FLOAT);
+        self.output.extend_from_slice(&v.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.binary(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.binary(v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.atom("nil");
+
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.variant(variant)?;
+
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.compound(tag::LIST))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.variant(variant)?;
+
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.compound(tag::MAP))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.variant(variant)?;
+
+        self.serialize_map(Some(len))
+    }
+}
+
+/// Serializer of the elements of a list or the entries of a map.
+///
+/// The number of elements or entries isn't always known upfront, so they're
+/// counted and the length is written when the list or map ends.
+#[derive(Debug)]
+pub struct Compound<'a> {
+    len: usize,
+    ser: &'a mut Serializer,
+    start: usize,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.len += 1;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        let output = &mut self.ser.output;
+
+        // Empty lists are nil rather than a list with no elements.
+        if output[self.start] == tag::LIST && self.len == 0 {
+            output.truncate(self.start);
+            output.push(tag::NIL);
+
+            return Ok(());
+        }
+
+        let len = u32::try_from(self.len).map_err(|_| EtfError::LengthTooLarge)?;
+        output[self.start + 1..self.start + 5].copy_from_slice(&len.to_be_bytes());
+
+        if output[self.start] == tag::LIST {
+            output.push(tag::NIL);
+        }
+
+        Ok(())
+    }
+}
+
+impl SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), EtfError> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        self.element(key)?;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        self.element(key)?;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        Compound::end(self)
+    }
+}
@@ -13,9 +13,8 @@
 //! [`EventType`]: ::twilight_model::gateway::event::EventType
 //! [`Shard::some_events`]: super::Shard::some_events
 
-use crate::EventTypeFlags;
-use futures_channel::mpsc::UnboundedReceiver;
-use futures_util::stream::{Stream, StreamExt};
+use crate::{listener::ListenerReceiver, EventTypeFlags};
+use futures_util::stream::Stream;
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -30,21 +29,64 @@ use twilight_model::gateway::event::Event;
 ///
 /// This implements [`futures::stream::Stream`].
 ///
+/// Events are buffered until they're received from the stream. By default
+/// the buffer is unbounded, so a stream that isn't polled doesn't slow down
+/// the shard or other streams. If a capacity is configured via
+/// [`ShardBuilder::event_stream_capacity`] then the
+/// [`EventStreamOverflow`] policy decides what happens while the buffer is
+/// full.
+///
 /// # Examples
 ///
 /// Refer to [`Shard::some_events`] for an example of how to use this.
 ///
+/// [`EventStreamOverflow`]: super::EventStreamOverflow
 /// [`Events::event_types`]: Self::event_types
 /// [`Shard`]: super::Shard
 /// [`Shard::some_events`]: super::Shard::some_events
+/// [`ShardBuilder::event_stream_capacity`]: super::ShardBuilder::event_stream_capacity
 /// [`futures::stream::Stream`]: https://docs.rs/futures/*/futures/stream/trait.Stream.html
 pub struct Events {
     event_types: EventTypeFlags,
-    rx: UnboundedReceiver<Event>,
+    rx: ListenerReceiver<Event>,
+}
+
+/// What a shard does when the buffer of an event stream is full.
+///
+/// This only applies if a capacity is configured via
+/// [`ShardBuilder::event_stream_capacity`].
+///
+/// [`ShardBuilder::event_stream_capacity`]: super::ShardBuilder::event_stream_capacity
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum EventStreamOverflow {
+    /// Stop reading from the socket until every stream has room again, so
+    /// that no events are lost.
+    ///
+    /// Discord buffers events for the shard meanwhile. Heartbeats are still
+    /// sent, so the connection stays alive while a stream catches up.
+    Backpressure,
+    /// Drop the new event for the full stream.
+    DropNewest,
+    /// Drop the oldest event buffered for the full stream to make room for the
+    /// new event.
+    ///
+    /// Dropped events are counted by [`Information::events_dropped`].
+    ///
+    /// [`Information::events_dropped`]: super::Information::events_dropped
+    DropOldest,
+}
+
+// Deriving `Default` for enums with `#[default]` needs Rust 1.62, which is
+// newer than the MSRV.
+impl Default for EventStreamOverflow {
+    fn default() -> Self {
+        Self::Backpressure
+    }
 }
 
 impl Events {
-    pub(super) fn new(event_types: EventTypeFlags, rx: UnboundedReceiver<Event>) -> Self {
+    pub(super) fn new(event_types: EventTypeFlags, rx: ListenerReceiver<Event>) -> Self {
         Self { event_types, rx }
     }
 
@@ -58,15 +100,27 @@ impl Stream for Events {
     type Item = Event;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.rx.poll_next_unpin(cx)
+        self.rx.poll_recv(cx)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Events;
+    use super::{EventStreamOverflow, Events};
     use futures_util::stream::Stream;
     use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
 
     assert_impl_all!(Events: Send, Stream, Sync);
+    assert_impl_all!(
+        EventStreamOverflow: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
 }
@@ -1,17 +1,20 @@
 use super::{
     builder::ShardBuilder,
+    command::Command,
     config::Config,
     event::Events,
-    json,
-    processor::{ConnectingError, Latency, Session, ShardProcessor},
-    raw_message::Message,
+    processor::{
+        ConnectingError, Counters, Encoding, Latency, Session, SessionSendError, ShardProcessor,
+    },
+    raw_message::{CloseFrame, Message},
     sink::ShardSink,
     stage::Stage,
+    tls::{TlsContainer, TlsError},
 };
 use crate::{listener::Listeners, EventTypeFlags, Intents};
 use async_tungstenite::tungstenite::{
-    protocol::{frame::coding::CloseCode, CloseFrame as TungsteniteCloseFrame},
-    Error as TungsteniteError, Message as TungsteniteMessage,
+    protocol::CloseFrame as TungsteniteCloseFrame, Error as TungsteniteError,
+    Message as TungsteniteMessage,
 };
 use futures_channel::mpsc::TrySendError;
 use futures_util::{
@@ -21,16 +24,34 @@ use futures_util::{
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use std::{
-    borrow::Cow,
+    convert::TryFrom,
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 use tokio::sync::watch::Receiver as WatchReceiver;
-use twilight_http::Error as HttpError;
-use twilight_model::gateway::event::Event;
+use twilight_http::{
+    token::{self, TokenError},
+    Client as HttpClient, Error as HttpError,
+};
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{RequestGuildMembers, UpdateVoiceState},
+        presence::Presence,
+    },
+    guild::Member,
+    id::{ChannelId, GuildId, UserId},
+};
 use url::ParseError as UrlParseError;
 
+#[cfg(feature = "etf")]
+use super::etf::EtfError;
+
 #[cfg(not(feature = "simd-json"))]
 use serde_json::Error as JsonError;
 #[cfg(feature = "simd-json")]
@@ -51,6 +72,12 @@ pub enum CommandError {
         /// Reason for the error.
         source: JsonError,
     },
+    /// Serializing the payload as ETF failed.
+    #[cfg(feature = "etf")]
+    SerializingEtf {
+        /// Reason for the error.
+        source: EtfError,
+    },
     /// Shard's session is inactive because the shard hasn't been started.
     SessionInactive {
         /// Reason for the error.
@@ -65,6 +92,15 @@ impl CommandError {
             SendError::SessionInactive { source } => Self::SessionInactive { source },
         }
     }
+
+    fn from_session_send(error: SessionSendError) -> Self {
+        match error {
+            SessionSendError::Sending { source } => Self::Sending { source },
+            SessionSendError::Serializing { source } => Self::Serializing { source },
+            #[cfg(feature = "etf")]
+            SessionSendError::SerializingEtf { source } => Self::SerializingEtf { source },
+        }
+    }
 }
 
 impl Display for CommandError {
@@ -78,11 +114,55 @@ impl Error for CommandError {
         match self {
             Self::Sending { source } => Some(source),
             Self::Serializing { source } => Some(source),
+            #[cfg(feature = "etf")]
+            Self::SerializingEtf { source } => Some(source),
             Self::SessionInactive { source } => Some(source),
         }
     }
 }
 
+/// Requesting guild members failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RequestMembersError {
+    /// Sending the request failed.
+    Sending {
+        /// Reason for the error.
+        source: CommandError,
+    },
+    /// Shard stopped before all of the member chunks were received.
+    Stopped,
+    /// Not all of the member chunks were received before the timeout.
+    TimedOut {
+        /// Number of chunks that were received.
+        received: u32,
+    },
+}
+
+impl Display for RequestMembersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Sending { source } => Display::fmt(source, f),
+            Self::Stopped => {
+                f.write_str("the shard stopped before all member chunks were received")
+            }
+            Self::TimedOut { received } => f.write_fmt(format_args!(
+                "timed out after receiving {} member chunks",
+                received,
+            )),
+        }
+    }
+}
+
+impl Error for RequestMembersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Sending { source } => Some(source),
+            Self::Stopped | Self::TimedOut { .. } => None,
+        }
+    }
+}
+
 /// Shard's session is inactive.
 ///
 /// This means that the shard has not yet been started.
@@ -120,6 +200,26 @@ pub enum ShardStartError {
         /// The reason for the error.
         source: HttpError,
     },
+    /// No more sessions can be started until the session start limit of the
+    /// [queue's budget] resets, and the shard is configured to
+    /// [stop instead of waiting].
+    ///
+    /// [queue's budget]: crate::queue::Queue::daily_budget
+    /// [stop instead of waiting]: super::ShardBuilder::session_start_limit_fatal
+    SessionStartLimitExhausted {
+        /// The number of milliseconds until the limit resets.
+        reset_after: u64,
+    },
+    /// Building the TLS configuration to connect to the gateway with failed.
+    Tls {
+        /// The reason for the error.
+        source: TlsError,
+    },
+    /// The token doesn't have the structure of a bot token.
+    TokenInvalid {
+        /// The reason for the error.
+        source: TokenError,
+    },
 }
 
 impl Display for ShardStartError {
@@ -133,6 +233,14 @@ impl Display for ShardStartError {
             Self::RetrievingGatewayUrl { .. } => {
                 f.write_str("retrieving the gateway URL via HTTP failed")
             }
+            Self::SessionStartLimitExhausted { reset_after } => f.write_fmt(format_args!(
+                "no more sessions can be started until the session start limit resets in {}ms",
+                reset_after,
+            )),
+            Self::Tls { source } => Display::fmt(source, f),
+            Self::TokenInvalid { source } => {
+                f.write_fmt(format_args!("the token is invalid: {}", source))
+            }
         }
     }
 }
@@ -143,6 +251,9 @@ impl Error for ShardStartError {
             Self::Establishing { source } => Some(source),
             Self::ParsingGatewayUrl { source, .. } => Some(source),
             Self::RetrievingGatewayUrl { source } => Some(source),
+            Self::SessionStartLimitExhausted { .. } => None,
+            Self::Tls { source } => Some(source),
+            Self::TokenInvalid { source } => Some(source),
         }
     }
 }
@@ -152,6 +263,9 @@ impl From<ConnectingError> for ShardStartError {
         match error {
             ConnectingError::Establishing { source } => Self::Establishing { source },
             ConnectingError::ParsingUrl { source, url } => Self::ParsingGatewayUrl { source, url },
+            ConnectingError::SessionStartLimitExhausted { reset_after } => {
+                Self::SessionStartLimitExhausted { reset_after }
+            }
         }
     }
 }
@@ -195,28 +309,100 @@ impl Error for SendError {
 /// and connection stage.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Information {
+    command_ratelimit: Option<(u8, u8)>,
+    compressed_bytes: u64,
+    decompressed_bytes: u64,
+    events_dropped: u64,
     id: u64,
+    inflater_capacity: usize,
     latency: Latency,
     session_id: Option<Box<str>>,
     seq: u64,
+    skipped_dispatches: u64,
     stage: Stage,
+    zombied_connections: u64,
 }
 
 impl Information {
+    /// Number of commands that can currently be sent without waiting on the
+    /// ratelimiter, and the maximum number that can be sent per minute.
+    ///
+    /// This is `None` if the command ratelimiter is disabled. Refer to
+    /// [`ShardBuilder::ratelimit_commands`] for more information.
+    ///
+    /// [`ShardBuilder::ratelimit_commands`]: super::ShardBuilder::ratelimit_commands
+    pub fn command_ratelimit(&self) -> Option<(u8, u8)> {
+        self.command_ratelimit
+    }
+
+    /// Number of compressed bytes received over the current connection.
+    ///
+    /// This is 0 if transport compression isn't used. Refer to
+    /// [`ShardBuilder::compression`] for more information.
+    ///
+    /// [`ShardBuilder::compression`]: super::ShardBuilder::compression
+    pub fn compressed_bytes(&self) -> u64 {
+        self.compressed_bytes
+    }
+
+    /// Number of bytes the compressed bytes received over the current
+    /// connection decompressed into.
+    ///
+    /// Comparing this to [`compressed_bytes`] shows how much bandwidth
+    /// transport compression saves.
+    ///
+    /// [`compressed_bytes`]: Self::compressed_bytes
+    pub fn decompressed_bytes(&self) -> u64 {
+        self.decompressed_bytes
+    }
+
+    /// Number of events dropped because an event stream's buffer was full.
+    ///
+    /// This is only ever non-zero if events are dropped when a buffer is full.
+    /// Refer to [`ShardBuilder::event_stream_overflow`] for more information.
+    ///
+    /// [`ShardBuilder::event_stream_overflow`]: super::ShardBuilder::event_stream_overflow
+    pub fn events_dropped(&self) -> u64 {
+        self.events_dropped
+    }
+
     /// Return the ID of the shard.
     pub fn id(&self) -> u64 {
         self.id
     }
 
+    /// Capacity in bytes of the buffers payloads are decompressed with.
+    ///
+    /// The buffers grow to fit the largest payload received and are shrunk
+    /// back according to [`ShardBuilder::inflater_shrink_policy`]. This is 0
+    /// if the `compression` feature is disabled.
+    ///
+    /// [`ShardBuilder::inflater_shrink_policy`]: super::ShardBuilder::inflater_shrink_policy
+    pub fn inflater_capacity(&self) -> usize {
+        self.inflater_capacity
+    }
+
     /// Return an immutable reference to the latency information for the shard.
     ///
-    /// This includes the average latency over all time, and the latency
-    /// information for the 5 most recent heartbeats.
+    /// This includes the average latency over all time, the latency
+    /// information for the 5 most recent heartbeats, and the number of
+    /// heartbeats sent and acknowledged.
     pub fn latency(&self) -> &Latency {
         &self.latency
     }
 
     /// Return an immutable reference to the session ID of the shard.
+    ///
+    /// This is `None` until the gateway sends the Ready event, and again once
+    /// the gateway invalidates the session without allowing it to be resumed.
+    ///
+    /// Along with [`seq`] this can be persisted and passed to
+    /// [`ShardBuilder::resume_session`] to resume the session later, such as
+    /// in another process. [`Shard::session`] returns both at once.
+    ///
+    /// [`ShardBuilder::resume_session`]: super::ShardBuilder::resume_session
+    /// [`Shard::session`]: super::Shard::session
+    /// [`seq`]: Self::seq
     pub fn session_id(&self) -> Option<&str> {
         self.session_id.as_deref()
     }
@@ -228,18 +414,53 @@ impl Information {
     /// been connected for a longer time, while a smaller number typically
     /// correlates to meaning that it's been connected for a less amount of
     /// time.
+    ///
+    /// The sequence is updated as each dispatch event is received, so it's
+    /// always that of the latest one without waiting on the shard to process
+    /// events.
     pub fn seq(&self) -> u64 {
         self.seq
     }
 
+    /// Number of dispatch events skipped because they couldn't be
+    /// deserialized.
+    ///
+    /// This is only ever non-zero if such events are skipped. Refer to
+    /// [`ShardBuilder::malformed_dispatch_policy`] for more information.
+    /// Like [`zombied_connections`], this counts the events over the lifetime
+    /// of the shard.
+    ///
+    /// [`ShardBuilder::malformed_dispatch_policy`]: super::ShardBuilder::malformed_dispatch_policy
+    /// [`zombied_connections`]: Self::zombied_connections
+    pub fn skipped_dispatches(&self) -> u64 {
+        self.skipped_dispatches
+    }
+
     /// Current stage of the shard.
     ///
     /// For example, once a shard is fully booted then it will be [`Connected`].
+    /// Changes of the stage are also emitted as shard events, such as
+    /// [`Event::ShardIdentifying`] and [`Event::ShardDisconnected`], which can
+    /// be received like any other event type.
     ///
     /// [`Connected`]: Stage::Connected
     pub fn stage(&self) -> Stage {
         self.stage
     }
+
+    /// Number of connections the shard closed because they were zombied.
+    ///
+    /// A connection is zombied when Discord doesn't acknowledge a heartbeat
+    /// before the next one is due, which usually means that the connection
+    /// died without being closed. The shard then closes the connection with
+    /// a close code of 4000, emitting [`Event::ShardDisconnected`], and
+    /// resumes the session over a new connection.
+    ///
+    /// This counts the connections over the lifetime of the shard, unlike
+    /// the other information which is about the current connection.
+    pub fn zombied_connections(&self) -> u64 {
+        self.zombied_connections
+    }
 }
 /// Details to resume a gateway session.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -250,12 +471,32 @@ pub struct ResumeSession {
     pub sequence: u64,
 }
 
+/// Members received in response to a guild members request.
+///
+/// Returned by [`Shard::request_members`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestedMembers {
+    /// Members of the guild, in the order of the chunks they were in.
+    pub members: Vec<Member>,
+    /// IDs of the requested users that aren't members of the guild.
+    pub not_found: Vec<UserId>,
+    /// Presences of the members, if they were requested.
+    pub presences: Vec<Presence>,
+}
+
 #[derive(Debug)]
 struct ShardRef {
     config: Arc<Config>,
     listeners: Listeners<Event>,
+    /// Number of guild members requests made, used to generate their nonces.
+    member_requests: AtomicU64,
     processor_handle: OnceCell<AbortHandle>,
     session: OnceCell<WatchReceiver<Arc<Session>>>,
+    /// Number of dispatch events skipped because they couldn't be
+    /// deserialized.
+    skipped_dispatches: Arc<AtomicU64>,
+    /// Number of connections closed because they were zombied.
+    zombied_connections: Arc<AtomicU64>,
 }
 
 /// Shard to run and manage a session with the gateway.
@@ -352,13 +593,20 @@ impl Shard {
     }
 
     pub(crate) fn new_with_config(config: Config) -> Self {
+        let listeners = Listeners::new(
+            config.event_stream_capacity(),
+            config.event_stream_overflow(),
+        );
         let config = Arc::new(config);
 
         Self(Arc::new(ShardRef {
             config,
-            listeners: Listeners::default(),
+            listeners,
+            member_requests: AtomicU64::new(0),
             processor_handle: OnceCell::new(),
             session: OnceCell::new(),
+            skipped_dispatches: Arc::new(AtomicU64::new(0)),
+            zombied_connections: Arc::new(AtomicU64::new(0)),
         }))
     }
 
@@ -387,13 +635,38 @@ impl Shard {
     ///
     /// Returns [`ShardStartError::RetrievingGatewayUrl`] if the gateway URL
     /// couldn't be retrieved from the HTTP API.
+    ///
+    /// Returns [`ShardStartError::SessionStartLimitExhausted`] if the queue's
+    /// daily identify budget is used up and the shard is configured to stop
+    /// instead of waiting for it to reset.
+    ///
+    /// Returns [`ShardStartError::TokenInvalid`] if the token doesn't have the
+    /// structure of a bot token.
     pub async fn start(&mut self) -> Result<(), ShardStartError> {
+        token::validate(self.0.config.token())
+            .map_err(|source| ShardStartError::TokenInvalid { source })?;
+
+        let intents = self.0.config.intents();
+
+        if intents.is_privileged() {
+            tracing::warn!(
+                shard_id = self.0.config.shard()[0],
+                privileged = ?(intents & Intents::privileged()),
+                "privileged intents are requested, the gateway closes the connection with close \
+                 code 4014 unless they're enabled in the developer portal",
+            );
+        }
+
         let url = if let Some(u) = self.0.config.gateway_url.clone() {
             u.into_string()
         } else {
-            self.0
-                .config
-                .http_client()
+            // A client is only created when none was provided, since it's
+            // only needed for this one request.
+            let http_client = match self.0.config.http_client() {
+                Some(http_client) => http_client.clone(),
+                None => HttpClient::new(self.0.config.token().to_owned()),
+            };
+            http_client
                 .gateway()
                 .authed()
                 .await
@@ -401,9 +674,18 @@ impl Shard {
                 .url
         };
 
+        let tls = match self.0.config.tls.clone() {
+            Some(tls) => tls,
+            None => TlsContainer::new().map_err(|source| ShardStartError::Tls { source })?,
+        };
+
         let config = Arc::clone(&self.0.config);
         let listeners = self.0.listeners.clone();
-        let (processor, wrx) = ShardProcessor::new(config, url, listeners)
+        let counters = Counters {
+            skipped_dispatches: Arc::clone(&self.0.skipped_dispatches),
+            zombied_connections: Arc::clone(&self.0.zombied_connections),
+        };
+        let (processor, wrx) = ShardProcessor::new(config, url, listeners, tls, counters)
             .await
             .map_err(ShardStartError::from)?;
         let (fut, handle) = future::abortable(processor.run());
@@ -488,20 +770,33 @@ impl Shard {
     }
 
     /// Retrieve information about the running of the shard, such as the current
-    /// connection stage.
+    /// connection stage and its latency.
+    ///
+    /// The information is a snapshot that's read without waiting on the
+    /// shard, so this is cheap to call.
     ///
     /// # Errors
     ///
     /// Returns a [`SessionInactiveError`] if the shard's session is inactive.
     pub fn info(&self) -> Result<Information, SessionInactiveError> {
-        let session = self.session()?;
+        let session = self.active_session()?;
 
         Ok(Information {
+            command_ratelimit: session
+                .ratelimit
+                .as_ref()
+                .map(|ratelimit| (ratelimit.available(), ratelimit.max())),
+            compressed_bytes: session.compressed_bytes.load(Ordering::Relaxed),
+            decompressed_bytes: session.decompressed_bytes.load(Ordering::Relaxed),
+            events_dropped: self.0.listeners.dropped(),
             id: self.config().shard()[0],
+            inflater_capacity: session.inflater_capacity.load(Ordering::Relaxed),
             latency: session.heartbeats.latency(),
             session_id: session.id(),
             seq: session.seq(),
+            skipped_dispatches: self.0.skipped_dispatches.load(Ordering::Relaxed),
             stage: session.stage(),
+            zombied_connections: self.0.zombied_connections.load(Ordering::Relaxed),
         })
     }
 
@@ -513,11 +808,16 @@ impl Shard {
     /// anything else happens that causes a need to create a new connection,
     /// then the sink will be invalidated.
     ///
+    /// Messages sent via the sink bypass the command ratelimiter used by
+    /// [`send`], so sending too many of them may get the connection closed.
+    ///
     /// # Errors
     ///
     /// Returns a [`SessionInactiveError`] if the shard's session is inactive.
+    ///
+    /// [`send`]: Self::send
     pub fn sink(&self) -> Result<ShardSink, SessionInactiveError> {
-        let session = self.session()?;
+        let session = self.active_session()?;
 
         Ok(ShardSink(session.tx.clone()))
     }
@@ -532,18 +832,198 @@ impl Shard {
     /// Returns [`CommandError::Serializing`] if the provided value failed to
     /// serialize into JSON.
     ///
+    /// Returns `CommandError::SerializingEtf` if the shard encodes payloads
+    /// with ETF and the provided value failed to serialize into it.
+    ///
     /// Returns [`CommandError::SessionInactive`] if the shard has not been
     /// started.
     pub async fn command(&self, value: &impl serde::Serialize) -> Result<(), CommandError> {
-        let json = json::to_vec(value).map_err(|source| CommandError::Serializing { source })?;
+        let bytes = Encoding::from_config(self.config())
+            .encode(value)
+            .map_err(CommandError::from_session_send)?;
 
-        self.send(Message::Binary(json))
+        self.send(Message::Binary(bytes))
             .await
             .map_err(CommandError::from_send)
     }
 
+    /// Send a typed command over the gateway.
+    ///
+    /// The command is serialized with the opcode of its variant, so a payload
+    /// can't be sent with the wrong opcode. It's sent via [`command`], so it
+    /// goes through the command ratelimiter.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`command`].
+    ///
+    /// [`command`]: Self::command
+    pub async fn command_typed(&self, command: Command) -> Result<(), CommandError> {
+        self.command(&command).await
+    }
+
+    /// Join, move between, or leave voice channels of a guild.
+    ///
+    /// A channel ID of `None` leaves the guild's voice channel. The command is
+    /// sent via [`command`], so it goes through the command ratelimiter.
+    ///
+    /// The guild must be on this shard. Refer to [`cluster::shard_id`] to
+    /// calculate the shard of a guild, or use [`Cluster::update_voice_state`]
+    /// to send the command via the correct shard of a cluster.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`command`].
+    ///
+    /// [`Cluster::update_voice_state`]: crate::Cluster::update_voice_state
+    /// [`cluster::shard_id`]: crate::cluster::shard_id
+    /// [`command`]: Self::command
+    pub async fn update_voice_state(
+        &self,
+        guild_id: impl Into<GuildId>,
+        channel_id: impl Into<Option<ChannelId>>,
+        self_deaf: bool,
+        self_mute: bool,
+    ) -> Result<(), CommandError> {
+        self.command(&UpdateVoiceState::new(
+            guild_id, channel_id, self_deaf, self_mute,
+        ))
+        .await
+    }
+
+    /// Request members of a guild and wait for all of the member chunks that
+    /// Discord sends in response.
+    ///
+    /// Chunks are matched to the request by its nonce, which is generated if
+    /// the request doesn't have one, and may arrive in any order. The command
+    /// is sent via [`command`], so it goes through the command ratelimiter.
+    ///
+    /// Dropping the returned future stops waiting for the chunks.
+    ///
+    /// # Examples
+    ///
+    /// Request the members whose usernames start with "a":
+    ///
+    /// ```no_run
+    /// use std::{env, time::Duration};
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut shard = Shard::new(env::var("DISCORD_TOKEN")?, Intents::GUILD_MEMBERS);
+    /// shard.start().await?;
+    ///
+    /// let request = RequestGuildMembers::builder(GuildId(1)).query("a", None);
+    /// let members = shard
+    ///     .request_members(request, Duration::from_secs(30))
+    ///     .await?;
+    /// println!("{} members start with \"a\"", members.members.len());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequestMembersError::Sending`] if sending the request
+    /// failed.
+    ///
+    /// Returns [`RequestMembersError::Stopped`] if the shard was shut down
+    /// before all of the chunks were received.
+    ///
+    /// Returns [`RequestMembersError::TimedOut`] if not all of the chunks
+    /// were received before the timeout.
+    ///
+    /// [`command`]: Self::command
+    pub async fn request_members(
+        &self,
+        mut request: RequestGuildMembers,
+        timeout: Duration,
+    ) -> Result<RequestedMembers, RequestMembersError> {
+        let nonce = request
+            .d
+            .nonce
+            .get_or_insert_with(|| {
+                let id = self.0.member_requests.fetch_add(1, Ordering::Relaxed);
+
+                format!("twilight-{}", id)
+            })
+            .clone();
+        let guild_id = request.d.guild_id;
+
+        // Listen before sending the request so that no chunk is missed.
+        let mut events = self.some_events(EventTypeFlags::MEMBER_CHUNK);
+
+        self.command(&request)
+            .await
+            .map_err(|source| RequestMembersError::Sending { source })?;
+
+        let mut chunks = Vec::new();
+        let mut received = 0;
+
+        let collect = async {
+            while let Some(event) = events.next().await {
+                let chunk = match event {
+                    Event::MemberChunk(chunk)
+                        if chunk.guild_id == guild_id
+                            && chunk.nonce.as_deref() == Some(nonce.as_str()) =>
+                    {
+                        chunk
+                    }
+                    _ => continue,
+                };
+
+                let count = chunk.chunk_count.max(1) as usize;
+                let index = chunk.chunk_index as usize;
+                chunks.resize_with(count, || None);
+
+                if let Some(slot @ None) = chunks.get_mut(index) {
+                    *slot = Some(chunk);
+                    received += 1;
+                }
+
+                if received >= count {
+                    return Ok(());
+                }
+            }
+
+            Err(RequestMembersError::Stopped)
+        };
+
+        match tokio::time::timeout(timeout, collect).await {
+            Ok(result) => result?,
+            Err(_) => {
+                return Err(RequestMembersError::TimedOut {
+                    received: u32::try_from(received).unwrap_or(u32::MAX),
+                })
+            }
+        }
+
+        let mut members = RequestedMembers {
+            members: Vec::new(),
+            not_found: Vec::new(),
+            presences: Vec::new(),
+        };
+
+        for chunk in chunks.into_iter().flatten() {
+            members.members.extend(chunk.members);
+            members.not_found.extend(chunk.not_found);
+            members.presences.extend(chunk.presences);
+        }
+
+        Ok(members)
+    }
+
     /// Send a raw websocket message.
     ///
+    /// This can be used to send payloads that aren't modeled by twilight,
+    /// such as commands with new opcodes. The message is sent as-is, so the
+    /// caller is responsible for it being a valid payload in the shard's
+    /// encoding; Discord closes the connection on invalid payloads.
+    ///
+    /// Unless disabled via [`ShardBuilder::ratelimit_commands`], messages are
+    /// ratelimited so that Discord's limit of 120 commands per minute isn't
+    /// exceeded, with capacity reserved for heartbeats. This waits until the
+    /// message can be sent if the ratelimit is exhausted.
+    ///
     /// # Examples
     ///
     /// Send a ping message:
@@ -561,6 +1041,22 @@ impl Shard {
     /// # Ok(()) }
     /// ```
     ///
+    /// Send a pre-serialized command requesting the members of a guild:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::env;
+    /// use twilight_gateway::{shard::{raw_message::Message, Shard}, Intents};
+    ///
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let mut shard = Shard::new(token, Intents::GUILD_MEMBERS);
+    /// shard.start().await?;
+    ///
+    /// let payload = r#"{"op":8,"d":{"guild_id":"1","query":"","limit":0}}"#;
+    /// shard.send(Message::Text(payload.to_owned())).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
     /// Send a normal close (you may prefer to use [`shutdown`]):
     ///
     /// ```no_run
@@ -592,16 +1088,27 @@ impl Shard {
     /// Returns [`SendError::SessionInactive`] when the shard has not been
     /// started.
     ///
+    /// [`ShardBuilder::ratelimit_commands`]: super::ShardBuilder::ratelimit_commands
     /// [`shutdown`]: Self::shutdown
     pub async fn send(&self, message: Message) -> Result<(), SendError> {
-        if let Ok(session) = self.session() {
-            // Tick ratelimiter.
-            session.ratelimit.lock().await.next().await;
+        if let Ok(session) = self.active_session() {
+            if let Some(ratelimit) = session.ratelimit.as_ref() {
+                ratelimit.acquire().await;
+            }
 
             session
                 .tx
                 .unbounded_send(message.into_tungstenite())
-                .map_err(|source| SendError::Sending { source })
+                .map_err(|source| SendError::Sending { source })?;
+
+            #[cfg(feature = "metrics")]
+            metrics::counter!(
+                "GatewayCommand",
+                1,
+                "shard" => self.config().shard()[0].to_string(),
+            );
+
+            Ok(())
         } else {
             Err(SendError::SessionInactive {
                 source: SessionInactiveError,
@@ -612,6 +1119,8 @@ impl Shard {
     /// Send a raw command over the gateway.
     ///
     /// This method should be used with caution, [`command`] should be preferred.
+    /// The bytes are sent as-is in a binary message, so the caller is
+    /// responsible for them being a valid payload.
     ///
     /// # Errors
     ///
@@ -637,21 +1146,12 @@ impl Shard {
     /// The shard will cleanly close the connection by sending a normal close
     /// code, causing Discord to show the bot as being offline. The session will
     /// not be resumable.
+    ///
+    /// The shard stops heartbeating and reconnecting, and its event streams end
+    /// once they've yielded the events they've already received. Shutting down
+    /// a shard that's already shut down does nothing.
     pub fn shutdown(&self) {
-        self.0.listeners.remove_all();
-
-        if let Some(processor_handle) = self.0.processor_handle.get() {
-            processor_handle.abort();
-        }
-
-        if let Ok(session) = self.session() {
-            // Since we're shutting down now, we don't care if it sends or not.
-            let _ = session.close(Some(TungsteniteCloseFrame {
-                code: CloseCode::Normal,
-                reason: "".into(),
-            }));
-            session.stop_heartbeater();
-        }
+        self.shutdown_with(CloseFrame::NORMAL);
     }
 
     /// Shut down the shard in a resumable fashion.
@@ -659,39 +1159,119 @@ impl Shard {
     /// The shard will cleanly close the connection by sending a restart close
     /// code, causing Discord to keep the bot as showing online. The connection
     /// will be resumable by using the provided session resume information
-    /// to [`ClusterBuilder::resume_sessions`].
+    /// to [`ClusterBuilder::resume_sessions`] or
+    /// [`ShardBuilder::resume_session`].
+    ///
+    /// Like [`shutdown`], this ends the event streams and is idempotent, so
+    /// calling it again returns the same session information without closing
+    /// the connection again.
     ///
     /// [`ClusterBuilder::resume_sessions`]: crate::cluster::ClusterBuilder::resume_sessions
+    /// [`ShardBuilder::resume_session`]: super::ShardBuilder::resume_session
+    /// [`shutdown`]: Self::shutdown
     pub fn shutdown_resumable(&self) -> (u64, Option<ResumeSession>) {
+        self.shutdown_with(CloseFrame::RESUME)
+    }
+
+    /// Shut down the shard, closing the connection with a close frame of
+    /// choice.
+    ///
+    /// The close code decides whether Discord keeps the session alive: the
+    /// normal and going away codes invalidate it, while any other keeps it
+    /// resumable, in which case its resume information is returned like with
+    /// [`shutdown_resumable`]. Refer to [`CloseFrame::is_resumable`].
+    ///
+    /// Like [`shutdown`], this ends the event streams and is idempotent, so
+    /// calling it again doesn't close the connection again.
+    ///
+    /// # Examples
+    ///
+    /// Shut down a shard in a resumable way for maintenance:
+    ///
+    /// ```no_run
+    /// use std::env;
+    /// use twilight_gateway::{shard::{raw_message::CloseFrame, Shard}, Intents};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let mut shard = Shard::new(token, Intents::GUILDS);
+    /// shard.start().await?;
+    ///
+    /// let (_, session) = shard.shutdown_with(CloseFrame::from((4000, "maintenance")));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`shutdown`]: Self::shutdown
+    /// [`shutdown_resumable`]: Self::shutdown_resumable
+    pub fn shutdown_with(&self, close_frame: CloseFrame<'static>) -> (u64, Option<ResumeSession>) {
+        let shard_id = self.config().shard()[0];
+        let resumable = close_frame.is_resumable();
+
+        let session = match self.close(close_frame.into_tungstenite()) {
+            Some(session) if resumable => session,
+            _ => return (shard_id, None),
+        };
+
+        let data = session.id().map(|id| ResumeSession {
+            session_id: id.into_string(),
+            sequence: session.seq(),
+        });
+
+        (shard_id, data)
+    }
+
+    /// Wait until the connection of the shard's current session has closed,
+    /// such as after shutting down.
+    pub(crate) async fn closed(&self) {
+        if let Ok(session) = self.active_session() {
+            session.closed().await;
+        }
+    }
+
+    /// Return the information needed to resume the current session.
+    ///
+    /// This can be saved before the process exits and passed to
+    /// [`ShardBuilder::resume_session`] to resume the session in another
+    /// process. Refer to [`shutdown_resumable`] to also close the connection
+    /// without invalidating the session.
+    ///
+    /// Returns `None` if the shard's session is inactive or if the gateway
+    /// hasn't provided a session ID yet.
+    ///
+    /// [`ShardBuilder::resume_session`]: super::ShardBuilder::resume_session
+    /// [`shutdown_resumable`]: Self::shutdown_resumable
+    pub fn session(&self) -> Option<ResumeSession> {
+        let session = self.active_session().ok()?;
+
+        Some(ResumeSession {
+            session_id: session.id()?.into_string(),
+            sequence: session.seq(),
+        })
+    }
+
+    /// Stop the shard's processor and event streams and close its connection
+    /// with a close frame, returning the session if the shard was started.
+    ///
+    /// The close frame isn't sent if the connection is already closed, such
+    /// as by an earlier shutdown.
+    fn close(&self, close_frame: TungsteniteCloseFrame<'static>) -> Option<Arc<Session>> {
         self.0.listeners.remove_all();
 
         if let Some(processor_handle) = self.0.processor_handle.get() {
             processor_handle.abort();
         }
 
-        let shard_id = self.config().shard()[0];
-
-        let session = match self.session() {
-            Ok(session) => session,
-            Err(_) => return (shard_id, None),
-        };
-
-        let _ = session.close(Some(TungsteniteCloseFrame {
-            code: CloseCode::Restart,
-            reason: Cow::from("Closing in a resumable way"),
-        }));
+        let session = self.active_session().ok()?;
 
-        let session_id = session.id();
-        let sequence = session.seq.load(Ordering::Relaxed);
+        if session.stage() != Stage::Disconnected {
+            // Since we're shutting down now, we don't care if it sends or not.
+            let _ = session.close(Some(close_frame));
+            session.set_stage(Stage::Disconnected);
+        }
 
         session.stop_heartbeater();
 
-        let data = session_id.map(|id| ResumeSession {
-            session_id: id.into_string(),
-            sequence,
-        });
-
-        (shard_id, data)
+        Some(session)
     }
 
     /// Return a handle to the current session.
@@ -699,7 +1279,7 @@ impl Shard {
     /// # Errors
     ///
     /// Returns a [`SessionInactiveError`] if the shard's session is inactive.
-    fn session(&self) -> Result<Arc<Session>, SessionInactiveError> {
+    fn active_session(&self) -> Result<Arc<Session>, SessionInactiveError> {
         let session = self.0.session.get().ok_or(SessionInactiveError)?;
 
         Ok(Arc::clone(&session.borrow()))
@@ -717,6 +1297,8 @@ mod tests {
 
     assert_fields!(CommandError::Sending: source);
     assert_fields!(CommandError::Serializing: source);
+    #[cfg(feature = "etf")]
+    assert_fields!(CommandError::SerializingEtf: source);
     assert_fields!(CommandError::SessionInactive: source);
     assert_impl_all!(CommandError: Debug, Error, Send, Sync);
     assert_impl_all!(Information: Clone, Debug, Send, Sync);
@@ -736,6 +1318,9 @@ mod tests {
     assert_fields!(ShardStartError::Establishing: source);
     assert_fields!(ShardStartError::ParsingGatewayUrl: source, url);
     assert_fields!(ShardStartError::RetrievingGatewayUrl: source);
+    assert_fields!(ShardStartError::SessionStartLimitExhausted: reset_after);
+    assert_fields!(ShardStartError::Tls: source);
+    assert_fields!(ShardStartError::TokenInvalid: source);
     assert_impl_all!(
         ShardStartError: Debug,
         Error,
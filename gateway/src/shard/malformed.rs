@@ -0,0 +1,172 @@
+//! Handling of dispatch events that can't be deserialized.
+
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+
+/// Maximum number of bytes of a payload that are logged when it's skipped.
+const PAYLOAD_LOG_LIMIT: usize = 64;
+
+/// What a shard does when a dispatch event can't be deserialized.
+///
+/// Discord sometimes sends events that don't match the models, such as when
+/// it adds a field before the models catch up. This only applies to dispatch
+/// events of event types that are wanted by an event stream. Refer to
+/// [`ShardBuilder::dead_letter`] to receive the payloads of such events under
+/// either policy.
+///
+/// [`ShardBuilder::dead_letter`]: super::ShardBuilder::dead_letter
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MalformedDispatchPolicy {
+    /// Log the error and reconnect, starting a new session.
+    FailFast,
+    /// Log the event type, the error, and the start of the payload, and
+    /// continue with the next event.
+    ///
+    /// Skipped events are counted by [`Information::skipped_dispatches`].
+    ///
+    /// [`Information::skipped_dispatches`]: super::Information::skipped_dispatches
+    Skip,
+}
+
+// Not derived: marking the default variant with `#[default]` is only stable
+// since Rust 1.62, after the MSRV of 1.48.
+impl Default for MalformedDispatchPolicy {
+    fn default() -> Self {
+        Self::FailFast
+    }
+}
+
+/// Dispatch event that couldn't be deserialized.
+///
+/// Passed to the callback set via [`ShardBuilder::dead_letter`].
+///
+/// [`ShardBuilder::dead_letter`]: super::ShardBuilder::dead_letter
+pub struct MalformedDispatch<'a> {
+    pub(super) error: &'a (dyn Error + Send + Sync + 'static),
+    pub(super) event_type: Option<&'a str>,
+    pub(super) payload: &'a [u8],
+    pub(super) seq: u64,
+    pub(super) shard_id: u64,
+}
+
+impl<'a> MalformedDispatch<'a> {
+    /// Reason the event couldn't be deserialized.
+    pub fn error(&self) -> &'a (dyn Error + Send + Sync + 'static) {
+        self.error
+    }
+
+    /// Type of the event, such as `"MESSAGE_CREATE"`.
+    pub fn event_type(&self) -> Option<&'a str> {
+        self.event_type
+    }
+
+    /// Raw bytes of the whole payload, encoded as JSON or ETF.
+    ///
+    /// With the `simd-json` feature the payload may have been modified in
+    /// place while it was being deserialized.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Sequence of the event.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// ID of the shard that received the event.
+    pub fn shard_id(&self) -> u64 {
+        self.shard_id
+    }
+}
+
+impl Debug for MalformedDispatch<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        // The payload may contain personal information, so only its length
+        // is shown.
+        f.debug_struct("MalformedDispatch")
+            .field("error", &self.error)
+            .field("event_type", &self.event_type)
+            .field("payload_len", &self.payload.len())
+            .field("seq", &self.seq)
+            .field("shard_id", &self.shard_id)
+            .finish()
+    }
+}
+
+/// Callback that dispatch events which can't be deserialized are passed to,
+/// shared with the other shards of a cluster.
+#[derive(Clone)]
+pub(crate) struct DeadLetter(Arc<dyn Fn(&MalformedDispatch<'_>) + Send + Sync>);
+
+impl DeadLetter {
+    pub fn new(callback: impl Fn(&MalformedDispatch<'_>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub fn call(&self, dispatch: &MalformedDispatch<'_>) {
+        (self.0)(dispatch);
+    }
+}
+
+impl Debug for DeadLetter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DeadLetter").finish()
+    }
+}
+
+/// Return the start of a payload to log, without copying the rest of it.
+pub(crate) fn truncated(payload: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(&payload[..payload.len().min(PAYLOAD_LOG_LIMIT)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeadLetter, MalformedDispatch, MalformedDispatchPolicy, PAYLOAD_LOG_LIMIT};
+    use static_assertions::assert_impl_all;
+    use std::{
+        fmt::{self, Debug},
+        hash::Hash,
+    };
+
+    assert_impl_all!(DeadLetter: Clone, Debug, Send, Sync);
+    assert_impl_all!(MalformedDispatch<'_>: Debug, Send, Sync);
+    assert_impl_all!(
+        MalformedDispatchPolicy: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_truncated() {
+        assert_eq!("{}", super::truncated(b"{}"));
+
+        let payload = vec![b'a'; PAYLOAD_LOG_LIMIT * 2];
+        assert_eq!(PAYLOAD_LOG_LIMIT, super::truncated(&payload).len());
+    }
+
+    #[test]
+    fn test_debug_omits_payload() {
+        let dispatch = MalformedDispatch {
+            error: &fmt::Error,
+            event_type: Some("MESSAGE_CREATE"),
+            payload: b"{\"content\":\"secret\"}",
+            seq: 2,
+            shard_id: 0,
+        };
+
+        let debug = format!("{:?}", dispatch);
+        assert!(debug.contains("payload_len: 20"));
+        assert!(!debug.contains("secret"));
+    }
+}
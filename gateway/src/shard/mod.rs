@@ -23,26 +23,46 @@ pub mod raw_message;
 pub mod stage;
 
 mod builder;
+mod command;
 mod config;
+#[cfg(feature = "etf")]
+mod etf;
 mod event;
 mod r#impl;
 mod json;
+mod malformed;
 mod processor;
+mod reconnect;
+#[cfg(feature = "compression")]
+mod shrink;
 mod sink;
+mod tls;
 
 pub use self::{
-    builder::{LargeThresholdError, ShardBuilder, ShardIdError},
+    builder::{EventTypesError, LargeThresholdError, ShardBuilder, ShardIdError},
+    command::Command,
     config::Config,
-    event::Events,
+    event::{EventStreamOverflow, Events},
+    malformed::{MalformedDispatch, MalformedDispatchPolicy},
     processor::heartbeat::Latency,
     r#impl::{
-        CommandError, Information, ResumeSession, SendError, SessionInactiveError, Shard,
-        ShardStartError,
+        CommandError, Information, RequestMembersError, RequestedMembers, ResumeSession, SendError,
+        SessionInactiveError, Shard, ShardStartError,
     },
+    reconnect::ReconnectPolicy,
     sink::ShardSink,
     stage::Stage,
+    tls::TlsError,
 };
 
+#[cfg(feature = "etf")]
+pub use self::etf::EtfError;
+
+#[cfg(feature = "compression")]
+pub use self::shrink::ShrinkPolicy;
+
+pub(crate) use self::tls::TlsContainer;
+
 use async_tungstenite::{tokio::ConnectStream, WebSocketStream};
 
 type ShardStream = WebSocketStream<ConnectStream>;
@@ -1,3 +1,5 @@
+#[cfg(feature = "etf")]
+use super::super::etf;
 use super::super::json::{self, GatewayEventParsingError};
 use crate::{listener::Listeners, EventTypeFlags};
 use std::{
@@ -5,7 +7,7 @@ use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
 };
-use twilight_model::gateway::event::{shard::Payload, Event};
+use twilight_model::gateway::event::{shard::Payload, Event, GatewayEvent};
 
 #[derive(Debug)]
 pub enum EmitJsonError {
@@ -49,13 +51,21 @@ impl Error for EmitJsonError {
 /// common operations.
 #[derive(Clone, Debug)]
 pub struct Emitter {
+    /// Event types configured to be emitted at all.
+    event_types: EventTypeFlags,
     listeners: Listeners<Event>,
+    /// ID of the shard, to label logs and metrics with.
+    shard_id: u64,
 }
 
 impl Emitter {
-    /// Create a new emitter for events and bytes.
-    pub fn new(listeners: Listeners<Event>) -> Self {
-        Self { listeners }
+    /// Create a new emitter for events and bytes of the configured types.
+    pub fn new(listeners: Listeners<Event>, event_types: EventTypeFlags, shard_id: u64) -> Self {
+        Self {
+            event_types,
+            listeners,
+            shard_id,
+        }
     }
 
     /// Consume the emitter, returning the inner listeners.
@@ -63,9 +73,22 @@ impl Emitter {
         self.listeners
     }
 
-    /// Determine if any of the listeners want a certain event type.
+    /// Determine if the event type is configured to be emitted and any of the
+    /// listeners want it.
     pub fn wants(&self, event_type: EventTypeFlags) -> bool {
-        self.listeners.event_types().contains(event_type)
+        self.event_types.contains(event_type) && self.listeners.event_types().contains(event_type)
+    }
+
+    /// Return whether every listener has room for another event, or none of
+    /// them apply backpressure.
+    pub fn has_space(&self) -> bool {
+        self.listeners.has_space()
+    }
+
+    /// Wait until every listener has room for another event if they apply
+    /// backpressure.
+    pub async fn wait_for_space(&self) {
+        self.listeners.wait_for_space().await;
     }
 
     /// Send some bytes to listeners that have subscribed to shard payloads.
@@ -124,6 +147,34 @@ impl Emitter {
         seq: Option<u64>,
         event_type: Option<&str>,
         json: &mut str,
+    ) -> Result<(), EmitJsonError> {
+        self.parsed(op, event_type, || {
+            json::parse_gateway_event(op, seq, event_type, json)
+        })
+    }
+
+    /// Emit an ETF payload that hasn't been deserialized yet, but only if at
+    /// least one of the listeners wants the event type.
+    #[cfg(feature = "etf")]
+    pub fn etf(
+        &self,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<(), EmitJsonError> {
+        self.parsed(op, event_type, || {
+            etf::parse_gateway_event(op, seq, event_type, bytes)
+        })
+    }
+
+    /// Parse and emit a gateway event if at least one of the listeners wants
+    /// the event type.
+    fn parsed(
+        &self,
+        op: u8,
+        event_type: Option<&str>,
+        parse: impl FnOnce() -> Result<GatewayEvent, GatewayEventParsingError>,
     ) -> Result<(), EmitJsonError> {
         let flag = EventTypeFlags::try_from((op, event_type)).map_err(|(op, event_type)| {
             EmitJsonError::EventTypeUnknown {
@@ -136,14 +187,15 @@ impl Emitter {
             return Ok(());
         }
 
-        let gateway_event = json::parse_gateway_event(op, seq, event_type, json)
-            .map_err(|source| EmitJsonError::Parsing { source })?;
+        let gateway_event = parse().map_err(|source| EmitJsonError::Parsing { source })?;
         self.event(Event::from(gateway_event));
 
         Ok(())
     }
 
     fn send(&self, event_type: EventTypeFlags, mut f: impl FnMut(usize) -> Event) {
+        let capacity = self.listeners.capacity();
+        let overflow = self.listeners.overflow();
         let listener_count = self.listeners.len();
         let mut idx = 0;
 
@@ -160,13 +212,36 @@ impl Emitter {
 
             idx += 1;
 
+            if listener.is_closed() {
+                return false;
+            }
+
             if !listener.wants(event_type) {
                 tracing::trace!("listener doesn't want event type");
 
-                return !listener.tx.is_closed();
+                return true;
             }
 
-            listener.tx.unbounded_send(f(idx)).is_ok()
+            if listener.send(f(idx), capacity, overflow) {
+                tracing::warn!(
+                    shard_id = self.shard_id,
+                    %id,
+                    ?event_type,
+                    ?overflow,
+                    "listener is full, dropped event",
+                );
+
+                self.listeners.count_dropped();
+
+                #[cfg(feature = "metrics")]
+                metrics::counter!(
+                    "GatewayEventsDropped",
+                    1,
+                    "shard" => self.shard_id.to_string(),
+                );
+            }
+
+            true
         });
     }
 }
@@ -174,25 +249,43 @@ impl Emitter {
 #[cfg(test)]
 mod tests {
     use super::Emitter;
-    use crate::{listener::Listeners, Event, EventTypeFlags};
+    use crate::{listener::Listeners, shard::EventStreamOverflow, Event, EventTypeFlags};
+    use twilight_model::gateway::event::shard::Resuming;
 
     #[test]
     fn test_bytes_send() {
         let listeners = Listeners::default();
         let mut rx = listeners.add(EventTypeFlags::SHARD_PAYLOAD);
-        let emitter = Emitter::new(listeners);
+        let emitter = Emitter::new(listeners, EventTypeFlags::all(), 0);
         emitter.bytes(&[1]);
         assert_eq!(1, emitter.listeners.len());
 
-        assert!(matches!(rx.try_next(), Ok(Some(_))));
-        assert!(rx.try_next().is_err());
+        assert!(rx.try_recv().is_some());
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_event_types_configured() {
+        let listeners = Listeners::default();
+        let mut rx = listeners.add(EventTypeFlags::default());
+        let emitter = Emitter::new(listeners, EventTypeFlags::MESSAGE_CREATE, 0);
+        assert!(emitter.wants(EventTypeFlags::MESSAGE_CREATE));
+        assert!(!emitter.wants(EventTypeFlags::TYPING_START));
+
+        // The payload is invalid, but it's dropped before being deserialized.
+        let mut json = r#"{"d":{},"op":0,"s":1,"t":"TYPING_START"}"#.to_owned();
+        emitter
+            .json(0, Some(1), Some("TYPING_START"), json.as_mut_str())
+            .unwrap();
+        emitter.event(Event::GatewayReconnect);
+        assert!(rx.try_recv().is_none());
     }
 
     #[test]
     fn test_event_removes_closed_channels() {
         let listeners = Listeners::default();
         listeners.add(EventTypeFlags::default());
-        let emitter = Emitter::new(listeners);
+        let emitter = Emitter::new(listeners, EventTypeFlags::all(), 0);
         emitter.event(Event::GatewayReconnect);
         assert!(emitter.listeners.all().is_empty());
     }
@@ -202,15 +295,85 @@ mod tests {
         let listeners = Listeners::default();
         let mut rx1 = listeners.add(EventTypeFlags::default());
         let mut rx2 = listeners.add(EventTypeFlags::default());
-        let emitter = Emitter::new(listeners);
+        let emitter = Emitter::new(listeners, EventTypeFlags::all(), 0);
         emitter.event(Event::GatewayReconnect);
         assert_eq!(2, emitter.listeners.len());
 
-        assert!(matches!(rx1.try_next(), Ok(Some(_))));
-        assert!(matches!(rx2.try_next(), Ok(Some(_))));
+        assert!(rx1.try_recv().is_some());
+        assert!(rx2.try_recv().is_some());
 
         // now check that they didn't send the event twice
-        assert!(rx1.try_next().is_err());
-        assert!(rx2.try_next().is_err());
+        assert!(rx1.try_recv().is_none());
+        assert!(rx2.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_event_capacity() {
+        let listeners = Listeners::new(Some(2), EventStreamOverflow::DropNewest);
+        let mut full = listeners.add(EventTypeFlags::default());
+        let mut drained = listeners.add(EventTypeFlags::default());
+        let emitter = Emitter::new(listeners, EventTypeFlags::all(), 0);
+
+        for _ in 0..3 {
+            emitter.event(Event::GatewayReconnect);
+            assert!(drained.try_recv().is_some());
+        }
+
+        // The full listener is kept, but the event past its capacity was
+        // dropped for it only.
+        assert_eq!(2, emitter.listeners.len());
+        assert!(full.try_recv().is_some());
+        assert!(full.try_recv().is_some());
+        assert!(full.try_recv().is_none());
+
+        emitter.event(Event::GatewayReconnect);
+        assert!(full.try_recv().is_some());
+    }
+
+    #[test]
+    fn test_event_capacity_drop_oldest() {
+        let listeners = Listeners::new(Some(2), EventStreamOverflow::DropOldest);
+        let mut rx = listeners.add(EventTypeFlags::default());
+        let emitter = Emitter::new(listeners, EventTypeFlags::all(), 0);
+
+        for seq in 1..=3 {
+            emitter.event(Event::ShardResuming(Resuming { seq, shard_id: 0 }));
+        }
+
+        assert_eq!(1, emitter.listeners.dropped());
+
+        for expected in 2..=3 {
+            match rx.try_recv() {
+                Some(Event::ShardResuming(Resuming { seq, .. })) => assert_eq!(expected, seq),
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_event_capacity_backpressure() {
+        let listeners = Listeners::new(Some(2), EventStreamOverflow::Backpressure);
+        let mut rx = listeners.add(EventTypeFlags::default());
+        let emitter = Emitter::new(listeners.clone(), EventTypeFlags::all(), 0);
+
+        emitter.event(Event::GatewayReconnect);
+        assert!(listeners.has_space());
+        emitter.event(Event::GatewayReconnect);
+        assert!(!listeners.has_space());
+
+        // Nothing is dropped, since the processor waits for space before
+        // emitting more.
+        emitter.event(Event::GatewayReconnect);
+        assert_eq!(0, listeners.dropped());
+
+        for _ in 0..2 {
+            assert!(rx.try_recv().is_some());
+        }
+
+        assert!(listeners.has_space());
+        assert!(rx.try_recv().is_some());
+        assert!(rx.try_recv().is_none());
     }
 }
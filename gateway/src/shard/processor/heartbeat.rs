@@ -1,12 +1,15 @@
-use super::{super::json, session::SessionSendError};
-use async_tungstenite::tungstenite::Message as TungsteniteMessage;
+use super::session::{Encoding, SessionSendError};
+use async_tungstenite::tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message as TungsteniteMessage,
+};
 use futures_channel::mpsc::UnboundedSender;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     convert::TryInto,
     sync::{
-        atomic::{AtomicU32, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
         Arc, Mutex,
     },
     time::{Duration, Instant},
@@ -21,6 +24,7 @@ use twilight_model::gateway::payload::Heartbeat;
 /// [`Shard::info`]: crate::shard::Shard::info
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Latency {
+    acknowledged: u32,
     average: Option<Duration>,
     heartbeats: u32,
     recent: VecDeque<Duration>,
@@ -31,6 +35,17 @@ pub struct Latency {
 }
 
 impl Latency {
+    /// The total number of heartbeats that have been acknowledged during this
+    /// session.
+    ///
+    /// If this is less than [`heartbeats`], then the most recent heartbeat is
+    /// awaiting its acknowledgement, or acknowledgements have been missed.
+    ///
+    /// [`heartbeats`]: Self::heartbeats
+    pub fn acknowledged(&self) -> u32 {
+        self.acknowledged
+    }
+
     /// The average time it took to receive an acknowledgement for every
     /// heartbeat sent over the duration of the session.
     ///
@@ -49,6 +64,11 @@ impl Latency {
         self.heartbeats
     }
 
+    /// The latency of the most recently acknowledged heartbeat.
+    pub fn latest(&self) -> Option<Duration> {
+        self.recent.back().copied()
+    }
+
     /// The 5 most recent latency times.
     ///
     /// Index 0 is the oldest, 4 is the most recent.
@@ -56,6 +76,19 @@ impl Latency {
         &self.recent
     }
 
+    /// The average of the 5 most recent latency times.
+    ///
+    /// Compared to [`average`] this reflects changes in the quality of the
+    /// connection quickly.
+    ///
+    /// [`average`]: Self::average
+    pub fn recent_average(&self) -> Option<Duration> {
+        #[allow(clippy::cast_possible_truncation)]
+        let len = self.recent.len() as u32;
+
+        self.recent.iter().sum::<Duration>().checked_div(len)
+    }
+
     /// When the last heartbeat acknowledgement was received.
     pub fn received(&self) -> Option<Instant> {
         self.received
@@ -67,13 +100,33 @@ impl Latency {
     }
 }
 
+/// Create the close frame that zombied connections are closed with.
+///
+/// Any code other than 1000 and 1001 keeps the session resumable.
+pub fn zombied_close_frame() -> CloseFrame<'static> {
+    CloseFrame {
+        code: CloseCode::from(4000),
+        reason: "Zombied connection".into(),
+    }
+}
+
 #[derive(Debug)]
 pub struct Heartbeats {
+    /// Whether the processor has stopped reading events, such as to apply
+    /// backpressure.
+    paused: AtomicBool,
+    /// Whether the processor was paused at any point since the last time the
+    /// heartbeater checked for a zombied connection.
+    paused_since_check: AtomicBool,
     received: Mutex<Option<Instant>>,
     recent: Mutex<VecDeque<u64>>,
     sent: Mutex<Option<Instant>>,
     total_iterations: AtomicU32,
+    total_sent: AtomicU32,
     total_time: AtomicU64,
+    /// Whether the connection was closed because a heartbeat wasn't
+    /// acknowledged.
+    zombied: AtomicBool,
 }
 
 impl Heartbeats {
@@ -89,8 +142,9 @@ impl Heartbeats {
             .collect();
 
         Latency {
+            acknowledged: iterations,
             average: self.total_time().checked_div(iterations),
-            heartbeats: iterations,
+            heartbeats: self.total_sent.load(Ordering::Relaxed),
             recent,
             received: self.received(),
             sent: self.sent(),
@@ -101,12 +155,48 @@ impl Heartbeats {
         self.received().is_some()
     }
 
+    /// Whether the connection was closed because a heartbeat wasn't
+    /// acknowledged before the next one was due.
+    pub fn zombied(&self) -> bool {
+        self.zombied.load(Ordering::Acquire)
+    }
+
+    /// Mark the processor as having stopped reading events.
+    ///
+    /// Acknowledgements aren't read meanwhile, so the connection isn't
+    /// considered zombied until the processor is unpaused and has had a
+    /// heartbeat interval to catch up.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Release);
+        self.paused_since_check.store(true, Ordering::Release);
+    }
+
+    /// Mark the processor as reading events again.
+    pub fn unpause(&self) {
+        self.paused.store(false, Ordering::Release);
+    }
+
+    /// Return whether the processor was paused at any point since this was
+    /// last called.
+    fn take_paused(&self) -> bool {
+        self.paused_since_check
+            .swap(self.paused.load(Ordering::Acquire), Ordering::AcqRel)
+    }
+
+    /// Mark the most recent heartbeat as acknowledged.
+    ///
+    /// Acknowledgements are assumed to be for the most recently sent
+    /// heartbeat, so an acknowledgement arriving after the next heartbeat has
+    /// been sent is measured against the next one. Only the first
+    /// acknowledgement after a heartbeat is used to measure the latency.
     pub fn receive(&self) {
-        self.set_received(Instant::now());
+        let now = Instant::now();
 
-        self.total_iterations.fetch_add(1, Ordering::SeqCst);
+        if self.set_received(now).is_some() {
+            return;
+        }
 
-        if let Some(dur) = self.sent().map(|s| s.elapsed()) {
+        if let Some(dur) = self.sent().map(|sent| now.saturating_duration_since(sent)) {
             let millis = if let Ok(millis) = dur.as_millis().try_into() {
                 millis
             } else {
@@ -115,6 +205,7 @@ impl Heartbeats {
                 return;
             };
 
+            self.total_iterations.fetch_add(1, Ordering::SeqCst);
             self.total_time.fetch_add(millis, Ordering::SeqCst);
 
             let mut recent = self.recent.lock().expect("recent poisoned");
@@ -128,6 +219,7 @@ impl Heartbeats {
     }
 
     pub fn send(&self) {
+        self.total_sent.fetch_add(1, Ordering::SeqCst);
         self.received.lock().expect("received poisoned").take();
         self.sent
             .lock()
@@ -139,11 +231,13 @@ impl Heartbeats {
         *self.received.lock().expect("received poisoned")
     }
 
-    fn set_received(&self, received: Instant) {
+    /// Set when the last acknowledgement was received, returning the previous
+    /// time if the heartbeat was already acknowledged.
+    fn set_received(&self, received: Instant) -> Option<Instant> {
         self.received
             .lock()
             .expect("received poisoned")
-            .replace(received);
+            .replace(received)
     }
 
     fn sent(&self) -> Option<Instant> {
@@ -162,18 +256,46 @@ impl Heartbeats {
 impl Default for Heartbeats {
     fn default() -> Self {
         Self {
+            paused: AtomicBool::new(false),
+            paused_since_check: AtomicBool::new(false),
             received: Mutex::new(None),
             recent: Mutex::new(VecDeque::with_capacity(5)),
             sent: Mutex::new(None),
             total_iterations: AtomicU32::new(0),
+            total_sent: AtomicU32::new(0),
             total_time: AtomicU64::new(0),
+            zombied: AtomicBool::new(false),
         }
     }
 }
 
+/// Delay before the first heartbeat of a connection.
+///
+/// Discord asks for the first heartbeat to be sent after a random fraction of
+/// the interval, so that the heartbeats of clients connecting at the same time
+/// are spread out.
+fn first_delay(interval: u64, jitter: f64) -> Duration {
+    // `f64::clamp` isn't available on the minimum supported Rust version.
+    #[allow(clippy::manual_clamp)]
+    let jitter = if jitter.is_nan() {
+        1.0
+    } else {
+        jitter.max(0.0).min(1.0)
+    };
+
+    // Intervals are well below 2^53 milliseconds.
+    #[allow(clippy::cast_precision_loss)]
+    let millis = interval as f64 * jitter;
+
+    Duration::from_secs_f64(millis / 1_000.0)
+}
+
 pub struct Heartbeater {
+    encoding: Encoding,
     heartbeats: Arc<Heartbeats>,
     interval: u64,
+    /// Fraction of the interval to wait before the first heartbeat.
+    jitter: f64,
     seq: Arc<AtomicU64>,
     tx: UnboundedSender<TungsteniteMessage>,
 }
@@ -182,12 +304,16 @@ impl Heartbeater {
     pub fn new(
         heartbeats: Arc<Heartbeats>,
         interval: u64,
+        jitter: f64,
         seq: Arc<AtomicU64>,
         tx: UnboundedSender<TungsteniteMessage>,
+        encoding: Encoding,
     ) -> Self {
         Self {
+            encoding,
             heartbeats,
             interval,
+            jitter,
             seq,
             tx,
         }
@@ -205,31 +331,38 @@ impl Heartbeater {
     // programmatic error.
     async fn try_run(self) -> Result<(), SessionSendError> {
         let duration = Duration::from_millis(self.interval);
-
-        let mut last = true;
+        let mut delay = first_delay(self.interval, self.jitter);
 
         loop {
-            tokio::time::sleep(duration).await;
+            tokio::time::sleep(delay).await;
+            delay = duration;
 
-            // Check if a heartbeat acknowledgement was received.
+            // If the previous heartbeat wasn't acknowledged then the
+            // connection is likely dead without having been closed, which
+            // Discord calls a zombied connection. Closing the connection
+            // makes the shard resume the session over a new one.
             //
-            // If so, then check if one was received last time.
-            //
-            // - if so, then mark that we didn't get one this time
-            // - if not, then end the heartbeater because something is off
-            // (connecting closed?)
-            if self.heartbeats.last_acked() {
-                last = true;
-            } else if last {
-                last = false;
-            } else {
+            // While the processor is paused the acknowledgement may simply not
+            // have been read yet.
+            if !self.heartbeats.take_paused()
+                && self.heartbeats.sent().is_some()
+                && !self.heartbeats.last_acked()
+            {
+                tracing::warn!("heartbeat wasn't acknowledged, closing zombied connection");
+                self.heartbeats.zombied.store(true, Ordering::Release);
+
+                let close = TungsteniteMessage::Close(Some(zombied_close_frame()));
+                let _ = self.tx.unbounded_send(close);
+                // The connection may be dead, so the socket forwarder
+                // shouldn't wait for Discord to close it too.
+                self.tx.close_channel();
+
                 return Ok(());
             }
 
             let seq = self.seq.load(Ordering::Acquire);
             let heartbeat = Heartbeat::new(seq);
-            let bytes = json::to_vec(&heartbeat)
-                .map_err(|source| SessionSendError::Serializing { source })?;
+            let bytes = self.encoding.encode(&heartbeat)?;
 
             tracing::debug!(seq, "sending heartbeat");
             self.tx
@@ -243,9 +376,122 @@ impl Heartbeater {
 
 #[cfg(test)]
 mod tests {
-    use super::Latency;
+    use super::{first_delay, Heartbeats, Latency};
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{fmt::Debug, thread, time::Duration};
 
     assert_impl_all!(Latency: Clone, Debug, Send, Sync);
+
+    const DELAY: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn test_first_delay() {
+        assert_eq!(Duration::from_millis(0), first_delay(45_000, 0.0));
+        assert_eq!(Duration::from_millis(11_250), first_delay(45_000, 0.25));
+        assert_eq!(Duration::from_secs(45), first_delay(45_000, 1.0));
+
+        // Fractions outside of the range are clamped, never waiting longer
+        // than the interval.
+        assert_eq!(Duration::from_millis(0), first_delay(45_000, -1.0));
+        assert_eq!(Duration::from_secs(45), first_delay(45_000, 2.0));
+        assert_eq!(Duration::from_secs(45), first_delay(45_000, f64::NAN));
+    }
+
+    #[test]
+    fn test_latency_empty() {
+        let latency = Heartbeats::default().latency();
+
+        assert_eq!(0, latency.acknowledged());
+        assert!(latency.average().is_none());
+        assert_eq!(0, latency.heartbeats());
+        assert!(latency.latest().is_none());
+        assert!(latency.received().is_none());
+        assert!(latency.recent_average().is_none());
+        assert!(latency.sent().is_none());
+    }
+
+    #[test]
+    fn test_latency_acked() {
+        let heartbeats = Heartbeats::default();
+
+        heartbeats.send();
+        thread::sleep(DELAY);
+        heartbeats.receive();
+
+        let latency = heartbeats.latency();
+        assert_eq!(1, latency.acknowledged());
+        assert_eq!(1, latency.heartbeats());
+        assert!(latency.latest().unwrap() >= DELAY);
+        assert_eq!(latency.latest(), latency.average());
+        assert_eq!(latency.latest(), latency.recent_average());
+        assert!(latency.received().unwrap() >= latency.sent().unwrap());
+        assert!(heartbeats.last_acked());
+    }
+
+    /// Acknowledgements are measured against the most recently sent heartbeat,
+    /// and repeated acknowledgements don't count.
+    #[test]
+    fn test_latency_late_ack() {
+        let heartbeats = Heartbeats::default();
+
+        // The first heartbeat isn't acknowledged before the next is sent.
+        heartbeats.send();
+        thread::sleep(DELAY);
+        heartbeats.send();
+        assert!(!heartbeats.last_acked());
+
+        heartbeats.receive();
+        heartbeats.receive();
+
+        let latency = heartbeats.latency();
+        assert_eq!(1, latency.acknowledged());
+        assert_eq!(2, latency.heartbeats());
+        assert!(latency.latest().unwrap() < DELAY);
+        assert_eq!(1, latency.recent().len());
+    }
+
+    #[test]
+    fn test_latency_receive_without_send() {
+        let heartbeats = Heartbeats::default();
+
+        heartbeats.receive();
+
+        let latency = heartbeats.latency();
+        assert_eq!(0, latency.acknowledged());
+        assert!(latency.received().is_some());
+        assert!(latency.latest().is_none());
+        assert!(heartbeats.last_acked());
+    }
+
+    #[test]
+    fn test_latency_recent() {
+        let heartbeats = Heartbeats::default();
+
+        for _ in 0..7 {
+            heartbeats.send();
+            heartbeats.receive();
+        }
+
+        let latency = heartbeats.latency();
+        assert_eq!(7, latency.acknowledged());
+        assert_eq!(7, latency.heartbeats());
+        assert_eq!(5, latency.recent().len());
+        assert!(latency.recent_average().unwrap() < DELAY);
+    }
+
+    #[test]
+    fn test_paused() {
+        let heartbeats = Heartbeats::default();
+        assert!(!heartbeats.take_paused());
+
+        // Checks are skipped for as long as the processor is paused.
+        heartbeats.pause();
+        assert!(heartbeats.take_paused());
+        assert!(heartbeats.take_paused());
+
+        // And for the first check after it's unpaused.
+        heartbeats.unpause();
+        assert!(heartbeats.take_paused());
+        assert!(!heartbeats.take_paused());
+    }
 }
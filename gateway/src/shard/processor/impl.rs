@@ -1,41 +1,54 @@
+#[cfg(feature = "etf")]
+use super::super::etf;
 #[cfg(feature = "compression")]
 use super::inflater::Inflater;
 use super::{
     super::{
         config::Config,
         json::{self, GatewayEventParsingError},
+        malformed::{self, MalformedDispatch, MalformedDispatchPolicy},
+        reconnect::random_fraction,
         stage::Stage,
+        tls::TlsContainer,
         ShardStream,
     },
     emitter::{EmitJsonError, Emitter},
-    session::{Session, SessionSendError},
+    heartbeat::zombied_close_frame,
+    session::{Encoding, Session, SessionSendError},
     socket_forwarder::SocketForwarder,
 };
-use crate::{event::EventTypeFlags, listener::Listeners};
+use crate::{listener::Listeners, EventTypeFlags};
 use async_tungstenite::tungstenite::{
     protocol::{frame::coding::CloseCode, CloseFrame},
     Error as TungsteniteError, Message,
 };
 #[cfg(feature = "compression")]
 use flate2::DecompressError;
-use futures_channel::mpsc::{TrySendError, UnboundedReceiver};
+use futures_channel::mpsc::{Receiver, TrySendError};
 use futures_util::stream::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    convert::TryFrom,
     env::consts::OS,
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
     str::{self, Utf8Error},
-    sync::{atomic::Ordering, Arc},
-    time::Duration,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
 };
 use tokio::sync::watch::{
     channel as watch_channel, Receiver as WatchReceiver, Sender as WatchSender,
 };
 use twilight_model::gateway::{
     event::{
-        shard::{Connected, Connecting, Disconnected, Identifying, Reconnecting, Resuming},
+        shard::{
+            Connected, Connecting, Disconnected, Identifying, Reconnecting, Resuming, StopReason,
+            Stopped,
+        },
         DispatchEvent, Event, GatewayEvent, GatewayEventDeserializer,
     },
     payload::{
@@ -53,6 +66,7 @@ use url::{ParseError as UrlParseError, Url};
 pub enum ConnectingError {
     Establishing { source: TungsteniteError },
     ParsingUrl { source: UrlParseError, url: String },
+    SessionStartLimitExhausted { reset_after: u64 },
 }
 
 impl Display for ConnectingError {
@@ -63,6 +77,10 @@ impl Display for ConnectingError {
                 "the gateway url `{}` is invalid: {}",
                 url, source,
             )),
+            Self::SessionStartLimitExhausted { reset_after } => f.write_fmt(format_args!(
+                "no more sessions can be started until the session start limit resets in {}ms",
+                reset_after,
+            )),
         }
     }
 }
@@ -72,12 +90,21 @@ impl Error for ConnectingError {
         match self {
             Self::Establishing { source } => Some(source),
             Self::ParsingUrl { source, .. } => Some(source),
+            Self::SessionStartLimitExhausted { .. } => None,
         }
     }
 }
 
 #[derive(Debug)]
 enum ProcessError {
+    /// A dispatch event couldn't be deserialized and the shard is configured
+    /// to fail fast.
+    DispatchMalformed {
+        /// Type of the event.
+        event_type: Option<String>,
+        /// Reason for the error.
+        source: GatewayEventParsingError,
+    },
     /// Provided event type and/or opcode combination doesn't match a known
     /// event type flag.
     EventTypeUnknown {
@@ -114,13 +141,20 @@ enum ProcessError {
 
 impl ProcessError {
     fn fatal(&self) -> bool {
-        matches!(self, Self::SendingClose { .. } | Self::SessionSend { .. })
+        matches!(
+            self,
+            Self::DispatchMalformed { .. } | Self::SendingClose { .. } | Self::SessionSend { .. }
+        )
     }
 }
 
 impl Display for ProcessError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
+            Self::DispatchMalformed { event_type, source } => f.write_fmt(format_args!(
+                "dispatch event {:?} couldn't be deserialized: {}",
+                event_type, source,
+            )),
             Self::EventTypeUnknown { event_type, op } => f.write_fmt(format_args!(
                 "provided event type ({:?})/op ({}) pair is unknown",
                 event_type, op,
@@ -139,7 +173,9 @@ impl Display for ProcessError {
 impl Error for ProcessError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            Self::ParsingPayload { source } => Some(source),
+            Self::DispatchMalformed { source, .. } | Self::ParsingPayload { source } => {
+                Some(source)
+            }
             Self::PayloadNotUtf8 { source } => Some(source),
             Self::SendingClose { source } => Some(source),
             Self::SessionSend { source } => Some(source),
@@ -183,13 +219,14 @@ enum ReceivingEventError {
 }
 
 impl ReceivingEventError {
-    fn fatal(&self) -> bool {
-        matches!(
-            self,
-            ReceivingEventError::AuthorizationInvalid { .. }
-                | ReceivingEventError::IntentsDisallowed { .. }
-                | ReceivingEventError::IntentsInvalid { .. }
-        )
+    /// Reason the shard has to stop, if the error is fatal.
+    fn stop_reason(&self) -> Option<StopReason> {
+        match self {
+            Self::AuthorizationInvalid { .. } => Some(StopReason::AuthorizationInvalid),
+            Self::IntentsDisallowed { .. } => Some(StopReason::IntentsDisallowed),
+            Self::IntentsInvalid { .. } => Some(StopReason::IntentsInvalid),
+            _ => None,
+        }
     }
 
     fn reconnectable(&self) -> bool {
@@ -237,21 +274,52 @@ struct ReadyMinimal {
     d: Ready,
 }
 
+/// Headers of an ETF payload, whose data is skipped.
+#[cfg(feature = "etf")]
+#[derive(Deserialize)]
+struct EtfHeaders<'a> {
+    op: u8,
+    s: Option<u64>,
+    #[serde(borrow)]
+    t: Option<Cow<'a, str>>,
+}
+
+/// Counters over the lifetime of a shard, shared between the shard and its
+/// processors.
+#[derive(Debug)]
+pub struct Counters {
+    /// Number of dispatch events skipped because they couldn't be
+    /// deserialized.
+    pub skipped_dispatches: Arc<AtomicU64>,
+    /// Number of connections closed because they were zombied.
+    pub zombied_connections: Arc<AtomicU64>,
+}
+
 /// Runs in the background and processes incoming events, and then broadcasts
 /// to all listeners.
 #[derive(Debug)]
 pub struct ShardProcessor {
     pub config: Arc<Config>,
+    /// Counters shared with the shard.
+    counters: Counters,
     pub emitter: Emitter,
     pub properties: IdentifyProperties,
-    pub rx: UnboundedReceiver<Message>,
+    pub rx: Receiver<Message>,
     pub session: Arc<Session>,
     #[cfg(feature = "compression")]
     inflater: Inflater,
     #[cfg(not(feature = "compression"))]
     buffer: Vec<u8>,
     url: Box<str>,
+    /// Number of attempts to reconnect since the last Ready or Resumed event.
+    reconnect_attempts: u32,
+    /// Reason the shard is stopping, such as the reconnect policy's attempts
+    /// having been used up.
+    stop: Option<StopReason>,
     resume: Option<(u64, Box<str>)>,
+    /// TLS configuration to connect with, shared with the other shards of a
+    /// cluster.
+    tls: TlsContainer,
     wtx: WatchSender<Arc<Session>>,
 }
 
@@ -263,12 +331,19 @@ impl ShardProcessor {
         config: Arc<Config>,
         mut url: String,
         listeners: Listeners<Event>,
+        tls: TlsContainer,
+        counters: Counters,
     ) -> Result<(Self, WatchReceiver<Arc<Session>>), ConnectingError> {
         //if we got resume info we don't need to wait
         let shard_id = config.shard();
         let resumable = config.sequence.is_some() && config.session_id.is_some();
         if !resumable {
             tracing::debug!("shard {:?} is not resumable", shard_id);
+
+            if let Some(reset_after) = Self::session_start_limit_exhausted(&config) {
+                return Err(ConnectingError::SessionStartLimitExhausted { reset_after });
+            }
+
             tracing::debug!("shard {:?} queued", shard_id);
             config.queue.request(shard_id).await;
             tracing::debug!("shard {:?} finished queue", config.shard());
@@ -276,50 +351,77 @@ impl ShardProcessor {
 
         let properties = IdentifyProperties::new("twilight.rs", "twilight.rs", OS, "", "");
 
-        #[cfg(feature = "compression")]
-        url.push_str("?v=8&compress=zlib-stream");
-        #[cfg(not(feature = "compression"))]
-        url.push_str("?v=8");
+        if config.gateway_url_query() {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str("v=8");
+            #[cfg(feature = "etf")]
+            if config.etf() {
+                url.push_str("&encoding=etf");
+            }
+            #[cfg(feature = "compression")]
+            if config.compression() {
+                url.push_str("&compress=zlib-stream");
+            }
+        }
 
-        let emitter = Emitter::new(listeners);
+        let emitter = Emitter::new(listeners, config.event_types(), config.shard()[0]);
         emitter.event(Event::ShardConnecting(Connecting {
             gateway: url.clone(),
             shard_id: config.shard()[0],
         }));
-        let stream = Self::connect(&url).await?;
+        let stream = Self::connect(&url, &tls).await?;
         let (forwarder, rx, tx) = SocketForwarder::new(stream);
+        let closed = forwarder.closed();
         tokio::spawn(async move {
             forwarder.run().await;
         });
 
-        let session = Arc::new(Session::new(tx));
-        if resumable {
-            session.set_id(config.session_id.clone().unwrap());
-            session
-                .seq
-                .store(config.sequence.unwrap(), Ordering::Relaxed)
-        }
+        let session = Arc::new(Session::new(tx, closed, &config));
+        session.set_stage(Stage::Handshaking);
+        let resume = match (config.sequence, config.session_id.clone()) {
+            (Some(seq), Some(id)) => {
+                session.set_id(id.clone());
+                session.seq.store(seq, Ordering::Relaxed);
+                session.set_stage(Stage::Resuming);
+
+                Some((seq, id))
+            }
+            _ => None,
+        };
 
         let (wtx, wrx) = watch_channel(Arc::clone(&session));
 
-        let mut processor = Self {
+        #[cfg(feature = "compression")]
+        let inflater = Inflater::new(shard_id, config.inflater_shrink_policy());
+
+        let processor = Self {
             config,
+            counters,
             emitter,
             properties,
             rx,
             session,
             #[cfg(feature = "compression")]
-            inflater: Inflater::new(shard_id),
+            inflater,
             #[cfg(not(feature = "compression"))]
             buffer: Vec::new(),
             url: url.into_boxed_str(),
-            resume: None,
+            reconnect_attempts: 0,
+            stop: None,
+            resume,
+            tls,
             wtx,
         };
 
-        if resumable {
+        // The connection is already established, so the resume payload is
+        // sent once the gateway says hello. If the session was invalidated in
+        // the meantime then the shard will reconnect and identify.
+        if let Some((seq, _)) = processor.resume.as_ref() {
             tracing::debug!("resuming shard {:?}", shard_id);
-            processor.resume().await;
+            processor.emitter.event(Event::ShardResuming(Resuming {
+                seq: *seq,
+                shard_id: shard_id[0],
+            }));
         }
 
         Ok((processor, wrx))
@@ -327,12 +429,22 @@ impl ShardProcessor {
 
     pub async fn run(mut self) {
         loop {
+            if let Some(reason) = self.stop.take() {
+                self.stopped(reason);
+
+                break;
+            }
+
+            self.backpressure().await;
+
             match self.next_payload().await {
                 Ok(v) => v,
                 Err(source) => {
                     tracing::warn!("{}", source);
 
-                    if source.fatal() {
+                    if let Some(reason) = source.stop_reason() {
+                        self.stopped(reason);
+
                         break;
                     }
 
@@ -341,6 +453,17 @@ impl ShardProcessor {
                     }
 
                     if source.resumable() {
+                        if self.session.heartbeats.zombied() {
+                            self.counters
+                                .zombied_connections
+                                .fetch_add(1, Ordering::Relaxed);
+                            self.disconnected(Some(&zombied_close_frame()));
+                        } else {
+                            // The connection was dropped without a close
+                            // frame.
+                            self.disconnected(None);
+                        }
+
                         self.resume().await;
                     }
 
@@ -367,143 +490,285 @@ impl ShardProcessor {
         self.emitter.into_listeners().remove_all();
     }
 
+    /// Wait until every event stream has room for more events if they're
+    /// full and apply backpressure.
+    ///
+    /// The socket isn't read meanwhile, but heartbeats are still sent, so the
+    /// connection isn't closed as zombied.
+    async fn backpressure(&self) {
+        if self.emitter.has_space() {
+            return;
+        }
+
+        tracing::debug!(
+            shard_id = self.config.shard()[0],
+            "event stream is full, applying backpressure",
+        );
+
+        self.session.heartbeats.pause();
+        self.emitter.wait_for_space().await;
+        self.session.heartbeats.unpause();
+    }
+
     async fn process(&mut self) -> Result<(), ProcessError> {
-        let (op, seq, event_type) = {
-            #[cfg(feature = "compression")]
-            let json = str::from_utf8_mut(self.inflater.buffer_mut())
-                .map_err(|source| ProcessError::PayloadNotUtf8 { source })?;
+        let (op, seq, event_type) = self.payload_headers()?;
+
+        // We can do a few little optimisation tricks here. For the
+        // "heartbeat ack" and "reconnect" opcodes we can construct
+        // the gateway events without needing to go through a serde
+        // context.
+        //
+        // Additionally, the processor cares about the "resumed"
+        // dispatch event type, which has no payload and can be constructed.
+        //
+        // This might not be shaving off entire milliseconds for these few
+        // events each time, but it certainly adds up.
+        if matches!(op, 1 | 7 | 9 | 10 | 11) {
+            // Have to use an if statement here if we want to use the OpCode
+            // enum, since matching with repr values isn't allowed.
+            let gateway_event = if op == OpCode::HeartbeatAck as u8 {
+                GatewayEvent::HeartbeatAck
+            } else if op == OpCode::Reconnect as u8 {
+                GatewayEvent::Reconnect
+            } else {
+                self.parse_gateway_event(op, seq, event_type.as_deref())
+                    .map_err(|source| ProcessError::ParsingPayload { source })?
+            };
 
-            #[cfg(not(feature = "compression"))]
-            let json = str::from_utf8_mut(self.buffer.as_mut_slice())
-                .map_err(|source| ProcessError::PayloadNotUtf8 { source })?;
-
-            tracing::trace!(%json, "Received JSON");
-            let emitter = self.emitter.clone();
-
-            let (op, seq, event_type) =
-                if let Some(deserializer) = GatewayEventDeserializer::from_json(json) {
-                    let (op, seq, event_type) = deserializer.into_parts();
-
-                    // Unfortunately lifetimes and mutability requirements
-                    // conflict here if we return an immutable reference to the
-                    // event type, so we're going to have to take ownership of
-                    // this if we don't want to do anything too dangerous. It
-                    // should be a good trade-off either way.
-                    (op, seq, event_type.map(ToOwned::to_owned))
-                } else {
-                    #[cfg(feature = "compression")]
-                    tracing::warn!(
-                        json = ?self.inflater.buffer_ref(),
-                        shard_id = self.config.shard()[0],
-                        shard_total = self.config.shard()[1],
-                        seq = self.session.seq(),
-                        stage = ?self.session.stage(),
-                        "received payload without opcode",
-                    );
-                    #[cfg(not(feature = "compression"))]
-                    tracing::warn!(
-                        json = ?self.buffer,
-                        shard_id = self.config.shard()[0],
-                        shard_total = self.config.shard()[1],
-                        seq = self.session.seq(),
-                        stage = ?self.session.stage(),
-                        "received payload without opcode",
-                    );
+            self.process_gateway_event(&gateway_event).await?;
+            self.emitter.event(Event::from(gateway_event));
 
-                    return Err(ProcessError::ParsingPayload {
-                        source: GatewayEventParsingError::PayloadInvalid,
-                    });
-                };
-
-            // We can do a few little optimisation tricks here. For the
-            // "heartbeat ack" and "reconnect" opcodes we can construct
-            // the gateway events without needing to go through a serde
-            // context.
-            //
-            // Additionally, the processor cares about the "resumed"
-            // dispatch event type, which has no payload and can be constructed.
-            //
-            // This might not be shaving off entire milliseconds for these few
-            // events each time, but it certainly adds up.
-            if matches!(op, 1 | 7 | 9 | 10 | 11) {
-                // Have to use an if statement here if we want to use the OpCode
-                // enum, since matching with repr values isn't allowed.
-                let gateway_event = if op == OpCode::HeartbeatAck as u8 {
-                    GatewayEvent::HeartbeatAck
-                } else if op == OpCode::Reconnect as u8 {
-                    GatewayEvent::Reconnect
-                } else {
-                    json::parse_gateway_event(op, seq, event_type.as_deref(), json)
-                        .map_err(|source| ProcessError::ParsingPayload { source })?
-                };
-
-                self.process_gateway_event(&gateway_event).await?;
-                emitter.event(Event::from(gateway_event));
-
-                if let Some(seq) = seq {
-                    self.session.set_seq(seq);
-                }
+            if let Some(seq) = seq {
+                self.session.set_seq(seq);
+            }
 
-                return Ok(());
+            return Ok(());
+        }
+
+        let seq = seq.ok_or(ProcessError::SequenceMissing)?;
+
+        #[cfg(feature = "metrics")]
+        {
+            let shard_id = self.config.shard()[0].to_string();
+
+            metrics::counter!(
+                "GatewayEvent",
+                1,
+                "GatewayEvent" => "Dispatch",
+                "shard" => shard_id.clone(),
+            );
+            metrics::counter!(
+                "GatewayDispatch",
+                1,
+                "EventType" => event_type.clone().unwrap_or_default(),
+                "shard" => shard_id,
+            );
+        }
+
+        if event_type.as_deref() == Some("RESUMED") {
+            self.process_resumed(seq);
+
+            if self.emitter.wants(EventTypeFlags::RESUMED) {
+                let gateway_event = GatewayEvent::Dispatch(seq, Box::new(DispatchEvent::Resumed));
+
+                self.emitter.event(Event::from(gateway_event));
             }
 
-            let seq = seq.ok_or(ProcessError::SequenceMissing)?;
+            return Ok(());
+        } else if event_type.as_deref() == Some("READY") {
+            let ready = self
+                .parse_ready()
+                .map_err(|source| ProcessError::ParsingPayload { source })?;
 
-            if event_type.as_deref() == Some("RESUMED") {
-                self.process_resumed(seq);
+            self.process_ready(&ready.d, seq);
+            self.emitter.event(Event::Ready(Box::new(ready.d)));
 
-                if emitter.wants(EventTypeFlags::RESUMED) {
-                    let gateway_event =
-                        GatewayEvent::Dispatch(seq, Box::new(DispatchEvent::Resumed));
+            return Ok(());
+        }
 
-                    emitter.event(Event::from(gateway_event));
-                }
+        self.session.set_seq(seq);
 
-                return Ok(());
-            } else if event_type.as_deref() == Some("READY") {
-                #[cfg(feature = "compression")]
-                let buf_ref = self.inflater.buffer_mut();
-                #[cfg(not(feature = "compression"))]
-                let buf_ref = self.buffer.as_mut_slice();
+        let emitter = self.emitter.clone();
+
+        let result = match self.session.encoding {
+            Encoding::Json => {
+                // We already know from earlier that the payload is valid
+                // UTF-8, so we can skip having to re-validate here since it
+                // hasn't been mutated.
+                let json = unsafe { str::from_utf8_unchecked_mut(self.buffer_mut()) };
 
-                let ready = json::from_slice::<ReadyMinimal>(buf_ref).map_err(|source| {
+                emitter.json(op, Some(seq), event_type.as_deref(), json)
+            }
+            #[cfg(feature = "etf")]
+            Encoding::Etf => emitter.etf(op, Some(seq), event_type.as_deref(), self.buffer_ref()),
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(EmitJsonError::Parsing { source }) => {
+                self.malformed_dispatch(seq, event_type, source)
+            }
+            Err(EmitJsonError::EventTypeUnknown { event_type, op }) => {
+                Err(ProcessError::EventTypeUnknown { event_type, op })
+            }
+        }
+    }
+
+    /// Handle a dispatch event that couldn't be deserialized according to the
+    /// configured policy, passing it to the dead letter callback if there is
+    /// one.
+    fn malformed_dispatch(
+        &self,
+        seq: u64,
+        event_type: Option<String>,
+        source: GatewayEventParsingError,
+    ) -> Result<(), ProcessError> {
+        let shard_id = self.config.shard()[0];
+
+        if let Some(dead_letter) = self.config.dead_letter.as_ref() {
+            dead_letter.call(&MalformedDispatch {
+                error: &source,
+                event_type: event_type.as_deref(),
+                payload: self.buffer_ref(),
+                seq,
+                shard_id,
+            });
+        }
+
+        match self.config.malformed_dispatch_policy() {
+            MalformedDispatchPolicy::FailFast => {
+                Err(ProcessError::DispatchMalformed { event_type, source })
+            }
+            MalformedDispatchPolicy::Skip => {
+                self.counters
+                    .skipped_dispatches
+                    .fetch_add(1, Ordering::Relaxed);
+
+                #[cfg(feature = "metrics")]
+                metrics::counter!(
+                    "GatewayDispatchSkipped",
+                    1,
+                    "EventType" => event_type.clone().unwrap_or_default(),
+                    "shard" => shard_id.to_string(),
+                );
+
+                tracing::warn!(
+                    shard_id,
+                    seq,
+                    event_type = ?event_type,
+                    payload = %malformed::truncated(self.buffer_ref()),
+                    "skipping dispatch that couldn't be deserialized: {}",
+                    source,
+                );
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Return the opcode, sequence, and event type of the current payload.
+    ///
+    /// JSON payloads are validated to be UTF-8.
+    fn payload_headers(&mut self) -> Result<(u8, Option<u64>, Option<String>), ProcessError> {
+        #[cfg(feature = "etf")]
+        if self.session.encoding == Encoding::Etf {
+            let headers =
+                etf::from_slice::<EtfHeaders<'_>>(self.buffer_ref()).map_err(|source| {
                     ProcessError::ParsingPayload {
-                        source: GatewayEventParsingError::Deserializing { source },
+                        source: GatewayEventParsingError::DeserializingEtf { source },
                     }
                 })?;
 
-                self.process_ready(&ready.d);
-                emitter.event(Event::Ready(Box::new(ready.d)));
+            return Ok((headers.op, headers.s, headers.t.map(Cow::into_owned)));
+        }
 
-                return Ok(());
+        let json = str::from_utf8_mut(self.buffer_mut())
+            .map_err(|source| ProcessError::PayloadNotUtf8 { source })?;
+
+        tracing::trace!(%json, "Received JSON");
+
+        if let Some(deserializer) = GatewayEventDeserializer::from_json(json) {
+            let (op, seq, event_type) = deserializer.into_parts();
+
+            // Unfortunately lifetimes and mutability requirements conflict
+            // here if we return an immutable reference to the event type, so
+            // we're going to have to take ownership of this if we don't want
+            // to do anything too dangerous. It should be a good trade-off
+            // either way.
+            return Ok((op, seq, event_type.map(ToOwned::to_owned)));
+        }
+
+        tracing::warn!(
+            json = ?self.buffer_ref(),
+            shard_id = self.config.shard()[0],
+            shard_total = self.config.shard()[1],
+            seq = self.session.seq(),
+            stage = ?self.session.stage(),
+            "received payload without opcode",
+        );
+
+        Err(ProcessError::ParsingPayload {
+            source: GatewayEventParsingError::PayloadInvalid,
+        })
+    }
+
+    /// Parse the current payload as a gateway event.
+    fn parse_gateway_event(
+        &mut self,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+    ) -> Result<GatewayEvent, GatewayEventParsingError> {
+        match self.session.encoding {
+            Encoding::Json => {
+                // Validated when the headers were parsed.
+                let json = unsafe { str::from_utf8_unchecked_mut(self.buffer_mut()) };
+
+                json::parse_gateway_event(op, seq, event_type, json)
             }
+            #[cfg(feature = "etf")]
+            Encoding::Etf => etf::parse_gateway_event(op, seq, event_type, self.buffer_ref()),
+        }
+    }
 
-            self.session.set_seq(seq);
+    /// Parse the current payload as a Ready event.
+    fn parse_ready(&mut self) -> Result<ReadyMinimal, GatewayEventParsingError> {
+        match self.session.encoding {
+            Encoding::Json => json::from_slice(self.buffer_mut())
+                .map_err(|source| GatewayEventParsingError::Deserializing { source }),
+            #[cfg(feature = "etf")]
+            Encoding::Etf => etf::from_slice(self.buffer_ref())
+                .map_err(|source| GatewayEventParsingError::DeserializingEtf { source }),
+        }
+    }
 
-            (op, seq, event_type)
-        };
+    /// Return an immutable reference to the buffer of the current payload.
+    #[cfg(feature = "compression")]
+    fn buffer_ref(&self) -> &[u8] {
+        self.inflater.buffer_ref()
+    }
 
-        // We already know from earlier that the payload is valid UTF-8, so we
-        // can skip having to re-validate here since it hasn't been mutated.
-        #[cfg(feature = "compression")]
-        let json = unsafe { str::from_utf8_unchecked_mut(self.inflater.buffer_mut()) };
-        #[cfg(not(feature = "compression"))]
-        let json = unsafe { str::from_utf8_unchecked_mut(self.buffer.as_mut_slice()) };
-
-        self.emitter
-            .json(op, Some(seq), event_type.as_deref(), json)
-            .map_err(|source| match source {
-                EmitJsonError::Parsing { source } => ProcessError::ParsingPayload { source },
-                EmitJsonError::EventTypeUnknown { event_type, op } => {
-                    ProcessError::EventTypeUnknown { event_type, op }
-                }
-            })
+    /// Return an immutable reference to the buffer of the current payload.
+    #[cfg(not(feature = "compression"))]
+    fn buffer_ref(&self) -> &[u8] {
+        self.buffer.as_slice()
     }
 
-    fn process_ready(&mut self, ready: &Ready) {
-        #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Dispatch");
+    /// Return a mutable reference to the buffer of the current payload.
+    #[cfg(feature = "compression")]
+    fn buffer_mut(&mut self) -> &mut [u8] {
+        self.inflater.buffer_mut()
+    }
+
+    /// Return a mutable reference to the buffer of the current payload.
+    #[cfg(not(feature = "compression"))]
+    fn buffer_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut_slice()
+    }
 
+    fn process_ready(&mut self, ready: &Ready, seq: u64) {
+        self.reconnect_attempts = 0;
+        self.session.set_seq(seq);
         self.session.set_stage(Stage::Connected);
         self.session
             .set_id(ready.session_id.clone().into_boxed_str());
@@ -514,10 +779,8 @@ impl ShardProcessor {
         }));
     }
 
-    fn process_resumed(&self, seq: u64) {
-        #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Dispatch");
-
+    fn process_resumed(&mut self, seq: u64) {
+        self.reconnect_attempts = 0;
         self.session.set_seq(seq);
         self.session.set_stage(Stage::Connected);
         self.emitter.event(Event::ShardConnected(Connected {
@@ -544,14 +807,33 @@ impl ShardProcessor {
 
     fn process_heartbeat_ack(&self) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "HeartbeatAck");
+        metrics::counter!(
+            "GatewayEvent",
+            1,
+            "GatewayEvent" => "HeartbeatAck",
+            "shard" => self.config.shard()[0].to_string(),
+        );
 
         self.session.heartbeats.receive();
+
+        #[cfg(feature = "metrics")]
+        if let Some(latency) = self.session.heartbeats.latency().latest() {
+            metrics::timing!(
+                "GatewayHeartbeatLatency",
+                latency,
+                "shard" => self.config.shard()[0].to_string(),
+            );
+        }
     }
 
     async fn process_heartbeat(&mut self, seq: u64) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Heartbeat");
+        metrics::counter!(
+            "GatewayEvent",
+            1,
+            "GatewayEvent" => "Heartbeat",
+            "shard" => self.config.shard()[0].to_string(),
+        );
 
         if seq > self.session.seq() + 1 {
             self.resume().await;
@@ -564,9 +846,22 @@ impl ShardProcessor {
         }
     }
 
+    /// Fraction of the heartbeat interval to wait before the first heartbeat
+    /// of the connection, random unless configured.
+    fn heartbeat_jitter(&self) -> f64 {
+        self.config
+            .heartbeat_jitter()
+            .unwrap_or_else(random_fraction)
+    }
+
     async fn process_hello(&mut self, interval: u64) -> Result<(), ProcessError> {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Hello");
+        metrics::counter!(
+            "GatewayEvent",
+            1,
+            "GatewayEvent" => "Hello",
+            "shard" => self.config.shard()[0].to_string(),
+        );
 
         tracing::debug!("got hello with interval {}", interval);
 
@@ -582,7 +877,7 @@ impl ShardProcessor {
 
             if interval > 0 {
                 self.session.set_heartbeat_interval(interval);
-                self.session.start_heartbeater();
+                self.session.start_heartbeater(self.heartbeat_jitter());
             }
 
             self.send(payload)
@@ -593,7 +888,7 @@ impl ShardProcessor {
 
             if interval > 0 {
                 self.session.set_heartbeat_interval(interval);
-                self.session.start_heartbeater();
+                self.session.start_heartbeater(self.heartbeat_jitter());
             }
 
             self.identify()
@@ -607,22 +902,49 @@ impl ShardProcessor {
     async fn process_invalidate_session(&mut self, resumable: bool) {
         if resumable {
             #[cfg(feature = "metrics")]
-            metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "InvalidateSessionTrue");
+            metrics::counter!(
+                "GatewayEvent",
+                1,
+                "GatewayEvent" => "InvalidateSessionTrue",
+                "shard" => self.config.shard()[0].to_string(),
+            );
 
             tracing::debug!("got request to resume the session");
             self.resume().await;
         } else {
             #[cfg(feature = "metrics")]
-            metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "InvalidateSessionFalse");
+            metrics::counter!(
+                "GatewayEvent",
+                1,
+                "GatewayEvent" => "InvalidateSessionFalse",
+                "shard" => self.config.shard()[0].to_string(),
+            );
 
             tracing::debug!("got request to invalidate the session and reconnect");
+
+            // The session can't be resumed, so it's identified anew after the
+            // random wait Discord requires.
+            self.resume = None;
+            self.session.clear_id();
+            tokio::time::sleep(Self::invalid_session_delay()).await;
             self.reconnect().await;
         }
     }
 
+    /// Calculate a random delay of 1 to 5 seconds to wait before identifying
+    /// after the session was invalidated.
+    fn invalid_session_delay() -> Duration {
+        Duration::from_secs_f64(1.0 + 4.0 * random_fraction())
+    }
+
     async fn process_reconnect(&mut self) -> Result<(), ProcessError> {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Reconnect");
+        metrics::counter!(
+            "GatewayEvent",
+            1,
+            "GatewayEvent" => "Reconnect",
+            "shard" => self.config.shard()[0].to_string(),
+        );
 
         tracing::debug!("got request to reconnect");
         let frame = CloseFrame {
@@ -662,7 +984,10 @@ impl ShardProcessor {
     /// is invalid.
     async fn next_payload(&mut self) -> Result<(), ReceivingEventError> {
         #[cfg(feature = "compression")]
-        self.inflater.clear();
+        {
+            self.inflater.clear();
+            self.session.set_inflater_capacity(self.inflater.capacity());
+        }
         #[cfg(not(feature = "compression"))]
         self.buffer.clear();
 
@@ -694,16 +1019,29 @@ impl ShardProcessor {
     ///
     /// If a ping or pong are received, then they are ignored.
     ///
-    /// Text messages aren't sent by Discord, so they are left unhandled.
+    /// Text messages are only sent by Discord when neither transport
+    /// compression nor ETF is used, and are otherwise left unhandled.
     async fn handle_message<'a>(
         &'a mut self,
         msg: &'a mut Message,
     ) -> Result<bool, ReceivingEventError> {
         match msg {
-            #[allow(unused_variables)]
             Message::Binary(bin) => {
                 #[cfg(feature = "compression")]
                 {
+                    // Without transport compression binary messages are
+                    // complete ETF payloads.
+                    if !self.config.compression() {
+                        #[cfg(feature = "metrics")]
+                        Self::bytes_metrics(self.config.shard()[0], "Uncompressed", bin.len());
+                        self.emitter.bytes(bin);
+                        self.inflater.uncompressed(bin);
+
+                        return Ok(true);
+                    }
+
+                    #[cfg(feature = "metrics")]
+                    Self::bytes_metrics(self.config.shard()[0], "Compressed", bin.len());
                     self.inflater.extend(&bin[..]);
 
                     let bytes = match self.inflater.msg() {
@@ -712,16 +1050,26 @@ impl ShardProcessor {
                         Err(source) => return Err(ReceivingEventError::Decompressing { source }),
                     };
 
+                    #[cfg(feature = "metrics")]
+                    Self::bytes_metrics(self.config.shard()[0], "Uncompressed", bytes.len());
                     self.emitter.bytes(bytes);
+                    self.session.set_compression_totals(
+                        self.inflater.total_in(),
+                        self.inflater.total_out(),
+                    );
 
                     Ok(true)
                 }
                 #[cfg(not(feature = "compression"))]
                 {
-                    /* Intentionally left empty as Discord should
-                     * not send binary payloads when not using compression
-                     */
-                    Ok(false)
+                    // Discord only sends binary messages without compression
+                    // when using ETF, as complete payloads.
+                    #[cfg(feature = "metrics")]
+                    Self::bytes_metrics(self.config.shard()[0], "Uncompressed", bin.len());
+                    self.emitter.bytes(bin);
+                    self.buffer.extend_from_slice(bin);
+
+                    Ok(true)
                 }
             }
             Message::Close(close_frame) => {
@@ -733,13 +1081,23 @@ impl ShardProcessor {
             Message::Text(json) => {
                 #[cfg(feature = "compression")]
                 {
-                    /* Empty intentionally as discord does not
-                     * send text when comppresion is enabled
-                     */
-                    Ok(false)
+                    // Discord only sends text when transport compression is
+                    // disabled.
+                    if self.config.compression() {
+                        return Ok(false);
+                    }
+
+                    #[cfg(feature = "metrics")]
+                    Self::bytes_metrics(self.config.shard()[0], "Uncompressed", json.len());
+                    self.emitter.bytes(json.as_bytes());
+                    self.inflater.uncompressed(json.as_bytes());
+
+                    Ok(true)
                 }
                 #[cfg(not(feature = "compression"))]
                 {
+                    #[cfg(feature = "metrics")]
+                    Self::bytes_metrics(self.config.shard()[0], "Uncompressed", json.len());
                     self.emitter.bytes(json.as_bytes());
                     self.buffer.extend_from_slice(json.as_bytes());
 
@@ -752,19 +1110,25 @@ impl ShardProcessor {
         }
     }
 
+    /// Count bytes received, either compressed as received or uncompressed
+    /// as they're processed.
+    #[cfg(feature = "metrics")]
+    fn bytes_metrics(shard_id: u64, kind: &'static str, len: usize) {
+        metrics::counter!(
+            "GatewayBytes",
+            u64::try_from(len).unwrap_or(u64::MAX),
+            "GatewayBytes" => kind,
+            "shard" => shard_id.to_string(),
+        );
+    }
+
     async fn handle_close(
         &mut self,
         close_frame: Option<&CloseFrame<'_>>,
     ) -> Result<(), ReceivingEventError> {
         tracing::info!("got close code: {:?}", close_frame);
 
-        self.emitter.event(Event::ShardDisconnected(Disconnected {
-            code: close_frame.as_ref().map(|frame| frame.code.into()),
-            reason: close_frame
-                .as_ref()
-                .map(|frame| frame.reason.clone().into()),
-            shard_id: self.config.shard()[0],
-        }));
+        self.disconnected(close_frame);
 
         if let Some(close_frame) = close_frame {
             match close_frame.code {
@@ -795,15 +1159,40 @@ impl ShardProcessor {
         Ok(())
     }
 
-    async fn connect(url: &str) -> Result<ShardStream, ConnectingError> {
+    /// Mark the shard as disconnected, emitting the close frame if there is
+    /// one.
+    fn disconnected(&self, close_frame: Option<&CloseFrame<'_>>) {
+        self.session.set_stage(Stage::Disconnected);
+
+        self.emitter.event(Event::ShardDisconnected(Disconnected {
+            code: close_frame.as_ref().map(|frame| frame.code.into()),
+            reason: close_frame
+                .as_ref()
+                .map(|frame| frame.reason.clone().into()),
+            shard_id: self.config.shard()[0],
+        }));
+    }
+
+    /// Mark the shard as stopped, emitting the reason.
+    fn stopped(&self, reason: StopReason) {
+        self.session.set_stage(Stage::Disconnected);
+
+        self.emitter.event(Event::ShardStopped(Stopped {
+            reason,
+            shard_id: self.config.shard()[0],
+        }));
+    }
+
+    async fn connect(url: &str, tls: &TlsContainer) -> Result<ShardStream, ConnectingError> {
         let url = Url::parse(url).map_err(|source| ConnectingError::ParsingUrl {
             source,
             url: url.to_owned(),
         })?;
 
-        let (stream, _) = async_tungstenite::tokio::connect_async(url)
-            .await
-            .map_err(|source| ConnectingError::Establishing { source })?;
+        let (stream, _) =
+            async_tungstenite::tokio::connect_async_with_tls_connector(url, Some(tls.connector()))
+                .await
+                .map_err(|source| ConnectingError::Establishing { source })?;
 
         tracing::debug!("Shook hands with remote");
 
@@ -824,6 +1213,14 @@ impl ShardProcessor {
             token: self.config.token().to_owned(),
             v: Self::GATEWAY_VERSION,
         });
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "GatewayIdentify",
+            1,
+            "shard" => self.config.shard()[0].to_string(),
+        );
+
         self.emitter.event(Event::ShardIdentifying(Identifying {
             shard_id: self.config.shard()[0],
             shard_total: self.config.shard()[1],
@@ -832,20 +1229,53 @@ impl ShardProcessor {
         self.send(identify).await
     }
 
+    /// Number of milliseconds until the session start limit resets, if the
+    /// queue's daily identify budget is used up and the shard is configured
+    /// to stop instead of waiting for it to reset.
+    ///
+    /// Queues that track the budget wait for it to reset themselves, so this
+    /// is checked before requesting to identify rather than after connecting.
+    fn session_start_limit_exhausted(config: &Config) -> Option<u64> {
+        if !config.session_start_limit_fatal() {
+            return None;
+        }
+
+        let budget = config.queue.daily_budget()?;
+
+        if budget.remaining > 0 {
+            return None;
+        }
+
+        let reset_after = budget
+            .reset_at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+
+        tracing::error!(
+            reset_after = ?reset_after,
+            shard_id = config.shard()[0],
+            shard_total = config.shard()[1],
+            "session start limit exhausted, stopping",
+        );
+
+        Some(u64::try_from(reset_after.as_millis()).unwrap_or(u64::MAX))
+    }
+
     /// Perform a full reconnect to the gateway, instantiating a new session.
     async fn reconnect(&mut self) {
         tracing::info!("reconnection started");
 
-        let mut wait = Duration::from_secs(1);
-
         loop {
-            tracing::debug!(
-                shard_id = self.config.shard()[0],
-                shard_total = self.config.shard()[1],
-                wait_in_seconds = wait.as_secs(),
-                "waiting before attempting a reconnect",
-            );
-            tokio::time::sleep(wait).await;
+            if !self.backoff().await {
+                return;
+            }
+
+            if let Some(reset_after) = Self::session_start_limit_exhausted(&self.config) {
+                self.stop = Some(StopReason::SessionStartLimitExhausted { reset_after });
+                self.session.set_stage(Stage::Disconnected);
+
+                return;
+            }
 
             // Await allowance when doing a full reconnect.
             self.config.queue.request(self.config.shard()).await;
@@ -854,20 +1284,24 @@ impl ShardProcessor {
                 shard_id: self.config.shard()[0],
             }));
 
-            let stream = match Self::connect(&self.url).await {
+            #[cfg(feature = "metrics")]
+            metrics::counter!(
+                "GatewayReconnect",
+                1,
+                "GatewayReconnect" => "Reconnect",
+                "shard" => self.config.shard()[0].to_string(),
+            );
+
+            let stream = match Self::connect(&self.url, &self.tls).await {
                 Ok(s) => s,
                 Err(why) => {
                     tracing::warn!("reconnecting failed: {:?}", why);
 
-                    if wait < Duration::from_secs(128) {
-                        wait *= 2;
-                    }
-
                     continue;
                 }
             };
 
-            self.set_session(stream, Stage::Connected);
+            self.set_session(stream, Stage::Handshaking);
 
             break;
         }
@@ -878,6 +1312,41 @@ impl ShardProcessor {
         }));
     }
 
+    /// Wait before attempting to connect as the reconnect policy says,
+    /// returning whether the attempt may be made.
+    ///
+    /// If the policy's attempts are used up then the shard is marked to stop.
+    async fn backoff(&mut self) -> bool {
+        let policy = self.config.reconnect_policy();
+
+        if !policy.allows(self.reconnect_attempts) {
+            tracing::error!(
+                attempts = self.reconnect_attempts,
+                shard_id = self.config.shard()[0],
+                shard_total = self.config.shard()[1],
+                "reconnect attempts exhausted, stopping",
+            );
+            self.stop = Some(StopReason::ReconnectsExhausted);
+            self.session.set_stage(Stage::Disconnected);
+
+            return false;
+        }
+
+        let wait = policy.delay(self.reconnect_attempts);
+        self.reconnect_attempts = self.reconnect_attempts.saturating_add(1);
+
+        tracing::debug!(
+            attempt = self.reconnect_attempts,
+            shard_id = self.config.shard()[0],
+            shard_total = self.config.shard()[1],
+            wait = ?wait,
+            "waiting before attempting a reconnect",
+        );
+        tokio::time::sleep(wait).await;
+
+        true
+    }
+
     /// Resume a session if possible, defaulting to instantiating a new
     /// connection.
     async fn resume(&mut self) {
@@ -912,15 +1381,37 @@ impl ShardProcessor {
 
     /// Attempt to resume a session.
     async fn try_resume(&mut self) -> Result<(), ConnectingError> {
+        // The first attempt to resume is immediate, but resuming repeatedly
+        // without the session being resumed is backed off.
+        if self.reconnect_attempts == 0 {
+            self.reconnect_attempts = 1;
+        } else if !self.backoff().await {
+            return Ok(());
+        }
+
         self.emitter.event(Event::ShardResuming(Resuming {
             seq: self.session.seq(),
             shard_id: self.config.shard()[0],
         }));
 
-        let stream = Self::connect(&self.url).await?;
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "GatewayReconnect",
+            1,
+            "GatewayReconnect" => "Resume",
+            "shard" => self.config.shard()[0].to_string(),
+        );
+
+        let stream = Self::connect(&self.url, &self.tls).await?;
 
         self.set_session(stream, Stage::Resuming);
 
+        // Keep the session's details, which the session is resumed with.
+        if let Some((seq, id)) = &self.resume {
+            self.session.set_id(id.clone());
+            self.session.seq.store(*seq, Ordering::Relaxed);
+        }
+
         Ok(())
     }
 
@@ -930,11 +1421,12 @@ impl ShardProcessor {
     /// new websocket connection.
     fn set_session(&mut self, stream: ShardStream, stage: Stage) {
         let (forwarder, rx, tx) = SocketForwarder::new(stream);
+        let closed = forwarder.closed();
 
         tokio::spawn(forwarder.run());
 
         self.rx = rx;
-        self.session = Arc::new(Session::new(tx));
+        self.session = Arc::new(Session::new(tx, closed, &self.config));
 
         if let Err(why) = self.wtx.send(Arc::clone(&self.session)) {
             tracing::error!("failed to broadcast new session: {:?}", why);
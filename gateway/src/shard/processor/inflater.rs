@@ -1,3 +1,4 @@
+use super::super::ShrinkPolicy;
 use flate2::{Decompress, DecompressError, FlushDecompress};
 use std::{convert::TryInto, mem, time::Instant};
 
@@ -8,23 +9,41 @@ const INTERNAL_BUFFER_SIZE: usize = 32 * 1024;
 pub struct Inflater {
     decompress: Decompress,
     compressed: Vec<u8>,
+    /// Scratch buffer each part of a message is decompressed into, reused for
+    /// every message.
     internal_buffer: Vec<u8>,
     buffer: Vec<u8>,
-    last_resize: Instant,
+    /// Highest capacity of the buffers since they were last shrunk.
+    peak: usize,
+    /// When the buffers last grew past [`peak`].
+    ///
+    /// [`peak`]: Self::peak
+    peak_at: Instant,
+    /// Number of messages since the buffers last grew past [`peak`].
+    ///
+    /// [`peak`]: Self::peak
+    since_peak: u64,
+    policy: ShrinkPolicy,
     shard: [u64; 2],
 }
 
 impl Inflater {
     /// Create a new inflater for a shard.
-    pub fn new(shard: [u64; 2]) -> Self {
-        Self {
+    pub fn new(shard: [u64; 2], policy: ShrinkPolicy) -> Self {
+        let mut inflater = Self {
             buffer: Vec::with_capacity(INTERNAL_BUFFER_SIZE),
             compressed: Vec::new(),
             decompress: Decompress::new(true),
             internal_buffer: Vec::with_capacity(INTERNAL_BUFFER_SIZE),
-            last_resize: Instant::now(),
+            peak: 0,
+            peak_at: Instant::now(),
+            since_peak: 0,
+            policy,
             shard,
-        }
+        };
+        inflater.peak = inflater.capacity();
+
+        inflater
     }
 
     /// Return an immutable reference to the buffer.
@@ -37,6 +56,32 @@ impl Inflater {
         self.buffer.as_mut_slice()
     }
 
+    /// Combined capacity of the buffers in bytes.
+    pub fn capacity(&self) -> usize {
+        self.compressed.capacity() + self.internal_buffer.capacity() + self.buffer.capacity()
+    }
+
+    /// Number of compressed bytes decompressed since the inflater was created
+    /// or reset.
+    pub fn total_in(&self) -> u64 {
+        self.decompress.total_in()
+    }
+
+    /// Number of bytes produced by decompressing since the inflater was
+    /// created or reset.
+    pub fn total_out(&self) -> u64 {
+        self.decompress.total_out()
+    }
+
+    /// Replace the buffer with a payload that isn't compressed.
+    ///
+    /// This is used when transport compression is disabled, so that the
+    /// buffer can still be accessed via `buffer_ref()` or `buffer_mut()`.
+    pub fn uncompressed(&mut self, payload: &[u8]) {
+        self.buffer.clear();
+        self.buffer.extend_from_slice(payload);
+    }
+
     /// Extend the internal compressed buffer with bytes.
     pub fn extend(&mut self, slice: &[u8]) {
         self.compressed.extend_from_slice(&slice);
@@ -94,13 +139,28 @@ impl Inflater {
         );
         self.compressed.clear();
 
+        let capacity = self.capacity();
+
+        if capacity > self.peak {
+            self.peak = capacity;
+            self.peak_at = Instant::now();
+            self.since_peak = 0;
+        } else {
+            self.since_peak += 1;
+        }
+
         // It doesn't matter if we lose precision for logging.
         #[allow(clippy::cast_precision_loss)]
         let saved_percentage =
             self.decompress.total_in() as f64 / self.decompress.total_out() as f64;
         let saved_percentage_readable = saved_percentage * 100.0;
 
-        let saved_kib = (self.decompress.total_out() - self.decompress.total_in()) / 1_024;
+        // Small payloads may be larger when compressed.
+        let saved_kib = self
+            .decompress
+            .total_out()
+            .saturating_sub(self.decompress.total_in())
+            / 1_024;
 
         tracing::trace!(
             saved_kib = saved_kib,
@@ -119,22 +179,19 @@ impl Inflater {
         Ok(Some(&mut self.buffer))
     }
 
-    /// Clear the buffer and shrink it if the capacity is too large.
-    ///
-    /// If the capacity is 4 times larger than the buffer length then the
-    /// capacity will be shrunk to the length.
+    /// Clear the buffers, shrinking them if the policy says so.
     #[tracing::instrument(level = "trace")]
     pub fn clear(&mut self) {
-        self.shrink();
-
         self.compressed.clear();
         self.internal_buffer.clear();
         self.buffer.clear();
+
+        self.shrink();
     }
 
     /// Reset the state of the inflater back to its default state.
     pub fn reset(&mut self) {
-        let _ = mem::replace(self, Self::new(self.shard));
+        let _ = mem::replace(self, Self::new(self.shard, self.policy));
     }
 
     /// Log metrics about the inflater.
@@ -154,15 +211,32 @@ impl Inflater {
         );
     }
 
-    /// Shrink the capacity of the compressed buffer and payload buffer if at
-    /// least 60 seconds have passed since the last shrink.
+    /// Shrink the capacity of the compressed buffer and payload buffer back
+    /// to the baseline if they haven't grown for enough messages or for long
+    /// enough.
+    ///
+    /// The buffers must be empty.
     fn shrink(&mut self) {
-        if self.last_resize.elapsed().as_secs() < 60 {
+        let baseline = self.policy.baseline;
+
+        if self.compressed.capacity() <= baseline && self.buffer.capacity() <= baseline {
+            return;
+        }
+
+        if self.since_peak < self.policy.messages && self.peak_at.elapsed() < self.policy.period {
             return;
         }
 
-        self.compressed.shrink_to_fit();
-        self.buffer.shrink_to_fit();
+        // Replacing the empty buffers avoids copying, and unlike
+        // `Vec::shrink_to` is available on the minimum supported Rust
+        // version.
+        if self.compressed.capacity() > baseline {
+            self.compressed = Vec::with_capacity(baseline);
+        }
+
+        if self.buffer.capacity() > baseline {
+            self.buffer = Vec::with_capacity(baseline);
+        }
 
         tracing::trace!(
             capacity = self.compressed.capacity(),
@@ -177,6 +251,182 @@ impl Inflater {
             "buffer capacity",
         );
 
-        self.last_resize = Instant::now();
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(
+            format!("Inflater-Capacity-{}", self.shard[0]),
+            self.buffer.capacity().try_into().unwrap_or(-1)
+        );
+
+        self.peak = self.capacity();
+        self.peak_at = Instant::now();
+        self.since_peak = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Inflater, ShrinkPolicy, ZLIB_SUFFIX};
+    use flate2::{Compress, Compression, FlushCompress};
+    use std::time::{Duration, Instant};
+
+    const HELLO: &[u8] = br#"{"t":null,"s":null,"op":10,"d":{"heartbeat_interval":41250}}"#;
+    const HEARTBEAT_ACK: &[u8] = br#"{"t":null,"s":null,"op":11,"d":null}"#;
+
+    /// Compress payloads into frames the way the gateway does, with all frames
+    /// being part of one zlib stream.
+    fn frames(payloads: &[&[u8]]) -> Vec<Vec<u8>> {
+        let mut compress = Compress::new(Compression::default(), true);
+
+        payloads
+            .iter()
+            .map(|payload| {
+                let mut frame = Vec::with_capacity(payload.len() + 64);
+                compress
+                    .compress_vec(payload, &mut frame, FlushCompress::Sync)
+                    .unwrap();
+                assert!(frame.ends_with(&ZLIB_SUFFIX));
+
+                frame
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_msg() {
+        let frames = frames(&[HELLO, HEARTBEAT_ACK]);
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+
+        inflater.extend(&frames[0]);
+        assert_eq!(Some(HELLO), inflater.msg().unwrap().as_deref());
+        assert_eq!(HELLO, inflater.buffer_ref());
+
+        // The second frame relies on the stream's state from the first.
+        inflater.clear();
+        inflater.extend(&frames[1]);
+        assert_eq!(Some(HEARTBEAT_ACK), inflater.msg().unwrap().as_deref());
+    }
+
+    #[test]
+    fn test_msg_partial() {
+        let frames = frames(&[HELLO]);
+        let (start, end) = frames[0].split_at(frames[0].len() / 2);
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+
+        inflater.extend(start);
+        assert!(inflater.msg().unwrap().is_none());
+        inflater.extend(end);
+        assert_eq!(Some(HELLO), inflater.msg().unwrap().as_deref());
+    }
+
+    #[test]
+    fn test_msg_large() {
+        // Larger than the internal buffer, so it's decompressed in parts.
+        let payload = format!(r#"{{"op":0,"d":"{}"}}"#, "a".repeat(100_000)).into_bytes();
+        let frames = frames(&[&payload]);
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+
+        inflater.extend(&frames[0]);
+        assert_eq!(Some(payload.as_slice()), inflater.msg().unwrap().as_deref());
+    }
+
+    #[test]
+    fn test_totals() {
+        let frames = frames(&[HELLO, HEARTBEAT_ACK]);
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+
+        for frame in &frames {
+            inflater.clear();
+            inflater.extend(frame);
+            inflater.msg().unwrap();
+        }
+
+        let compressed = frames.iter().map(Vec::len).sum::<usize>() as u64;
+        let decompressed = (HELLO.len() + HEARTBEAT_ACK.len()) as u64;
+        assert_eq!(compressed, inflater.total_in());
+        assert_eq!(decompressed, inflater.total_out());
+
+        inflater.reset();
+        assert_eq!(0, inflater.total_in());
+        assert_eq!(0, inflater.total_out());
+    }
+
+    #[test]
+    fn test_shrink_period() {
+        let payload = "a".repeat(100_000).into_bytes();
+        let frames = frames(&[&payload, HELLO]);
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+
+        inflater.extend(&frames[0]);
+        inflater.msg().unwrap();
+        inflater.clear();
+        assert!(inflater.buffer.capacity() >= payload.len());
+
+        // The buffers haven't grown for a minute.
+        inflater.peak_at = Instant::now().checked_sub(Duration::from_secs(61)).unwrap();
+        inflater.extend(&frames[1]);
+        inflater.msg().unwrap();
+        inflater.clear();
+        assert!(inflater.buffer.capacity() < payload.len());
+        assert!(inflater.peak_at.elapsed().as_secs() < 60);
+    }
+
+    /// Capacity of the buffers while receiving a burst of large payloads,
+    /// followed by small payloads.
+    #[test]
+    fn test_shrink_after_burst() {
+        let policy = ShrinkPolicy {
+            baseline: 16 * 1024,
+            messages: 10,
+            period: Duration::from_secs(1_000),
+        };
+        let large = (0..8)
+            .map(|index| {
+                format!(
+                    r#"{{"op":0,"d":"{}"}}"#,
+                    index.to_string().repeat(1_000_000)
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut payloads = large.iter().map(String::as_bytes).collect::<Vec<_>>();
+        payloads.extend((0..20).map(|_| HEARTBEAT_ACK));
+        let frames = frames(&payloads);
+        let mut inflater = Inflater::new([0, 1], policy);
+        let initial = inflater.capacity();
+
+        let capacities = frames
+            .iter()
+            .map(|frame| {
+                inflater.clear();
+                inflater.extend(frame);
+                inflater.msg().unwrap();
+
+                inflater.capacity()
+            })
+            .collect::<Vec<_>>();
+
+        // The buffers grow to fit the first payload of the burst and keep
+        // their capacity for the others, so that they're reused instead of
+        // reallocated.
+        let peak = capacities[0];
+        assert!(peak > 1_000_000, "{}", peak);
+        assert!(capacities[..11].iter().all(|capacity| *capacity == peak));
+
+        // Once the buffers haven't grown for 10 messages they're shrunk back
+        // to the baseline, which is enough for the small payloads.
+        let shrunk = capacities[11];
+        assert!(shrunk < initial, "{}", shrunk);
+        assert!(capacities[11..].iter().all(|capacity| *capacity == shrunk));
+        assert!(inflater.buffer.capacity() <= policy.baseline);
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        let mut inflater = Inflater::new([0, 1], ShrinkPolicy::default());
+        inflater.uncompressed(HELLO);
+        assert_eq!(HELLO, inflater.buffer_ref());
+
+        inflater.uncompressed(HEARTBEAT_ACK);
+        assert_eq!(HEARTBEAT_ACK, inflater.buffer_ref());
+        assert_eq!(0, inflater.total_in());
     }
 }
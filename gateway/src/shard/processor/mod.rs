@@ -4,12 +4,12 @@ mod emitter;
 mod r#impl;
 #[cfg(feature = "compression")]
 mod inflater;
+mod ratelimiter;
 mod session;
 mod socket_forwarder;
-mod throttle;
 
 pub use self::{
     heartbeat::Latency,
-    r#impl::{ConnectingError, ShardProcessor},
-    session::Session,
+    r#impl::{ConnectingError, Counters, ShardProcessor},
+    session::{Encoding, Session, SessionSendError},
 };
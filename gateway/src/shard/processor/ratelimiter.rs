@@ -0,0 +1,221 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::{self, Instant};
+
+/// Number of commands allowed to be sent per [`PERIOD`].
+const COMMANDS_PER_PERIOD: u8 = 120;
+
+/// Heartbeat interval assumed until Discord sends the real one, in
+/// milliseconds.
+const DEFAULT_HEARTBEAT_INTERVAL: u64 = 41_250;
+
+/// Length of the window in which at most [`COMMANDS_PER_PERIOD`] commands can
+/// be sent.
+const PERIOD: Duration = Duration::from_millis(PERIOD_MILLIS);
+
+/// Length of [`PERIOD`] in milliseconds.
+const PERIOD_MILLIS: u64 = 60_000;
+
+/// Number of commands reserved in addition to the heartbeats, to account for
+/// heartbeats requested by Discord and for the intervals not lining up with
+/// the window.
+const RESERVED_MARGIN: u8 = 3;
+
+/// Ratelimiter of the commands sent by the shard.
+///
+/// Discord closes connections that send more than [`COMMANDS_PER_PERIOD`]
+/// commands per [`PERIOD`], heartbeats included. Heartbeats don't go through
+/// the ratelimiter, so capacity is instead reserved for them based on the
+/// heartbeat interval.
+#[derive(Debug)]
+pub struct CommandRatelimiter {
+    /// Instants at which the commands in the current window were sent.
+    instants: Mutex<VecDeque<Instant>>,
+    /// Number of commands other than heartbeats allowed per window.
+    max: AtomicU8,
+}
+
+impl CommandRatelimiter {
+    /// Create a ratelimiter reserving capacity for a heartbeat interval in
+    /// milliseconds.
+    ///
+    /// An interval of 0 means the interval isn't known yet, in which case
+    /// the usual interval of Discord is assumed.
+    pub fn new(heartbeat_interval: u64) -> Self {
+        let max = Self::max_for(heartbeat_interval);
+
+        Self {
+            instants: Mutex::new(VecDeque::with_capacity(usize::from(max))),
+            max: AtomicU8::new(max),
+        }
+    }
+
+    /// Number of commands that can currently be sent without waiting.
+    pub fn available(&self) -> u8 {
+        let mut instants = self.instants.lock().expect("instants poisoned");
+        Self::clean(&mut instants, Instant::now());
+
+        // At most 120 instants are ever stored.
+        #[allow(clippy::cast_possible_truncation)]
+        let used = instants.len() as u8;
+
+        self.max().saturating_sub(used)
+    }
+
+    /// Maximum number of commands that can be sent per window.
+    pub fn max(&self) -> u8 {
+        self.max.load(Ordering::Relaxed)
+    }
+
+    /// Reserve capacity for a new heartbeat interval in milliseconds.
+    pub fn set_heartbeat_interval(&self, heartbeat_interval: u64) {
+        self.max
+            .store(Self::max_for(heartbeat_interval), Ordering::Release);
+    }
+
+    /// Wait until a command can be sent, and then reserve its place in the
+    /// window.
+    pub async fn acquire(&self) {
+        loop {
+            let next = {
+                let mut instants = self.instants.lock().expect("instants poisoned");
+                let now = Instant::now();
+                Self::clean(&mut instants, now);
+
+                if instants.len() < usize::from(self.max()) {
+                    instants.push_back(now);
+
+                    return;
+                }
+
+                // The window is full, so a command can be sent once the
+                // oldest one leaves it.
+                instants.front().map_or(now, |oldest| *oldest + PERIOD)
+            };
+
+            tracing::debug!("command ratelimit exhausted, waiting until {:?}", next);
+
+            time::sleep_until(next).await;
+        }
+    }
+
+    /// Remove the instants of the commands that have left the window.
+    fn clean(instants: &mut VecDeque<Instant>, now: Instant) {
+        while let Some(oldest) = instants.front() {
+            if now.duration_since(*oldest) < PERIOD {
+                break;
+            }
+
+            instants.pop_front();
+        }
+    }
+
+    /// Calculate the number of commands allowed per window when heartbeating
+    /// at an interval in milliseconds.
+    ///
+    /// This is at least 1.
+    fn max_for(heartbeat_interval: u64) -> u8 {
+        let heartbeat_interval = if heartbeat_interval == 0 {
+            DEFAULT_HEARTBEAT_INTERVAL
+        } else {
+            heartbeat_interval
+        };
+
+        // Round up, since a heartbeat may be sent at both ends of the window.
+        let heartbeats =
+            PERIOD_MILLIS / heartbeat_interval + u64::from(PERIOD_MILLIS % heartbeat_interval != 0);
+        let reserved = u8::try_from(heartbeats)
+            .unwrap_or(u8::MAX)
+            .saturating_add(RESERVED_MARGIN);
+
+        // Always allow one command per window, since commands could otherwise
+        // never be sent with very short intervals.
+        COMMANDS_PER_PERIOD.saturating_sub(reserved).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandRatelimiter, PERIOD};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, time::Duration};
+    use tokio::time::{self, Instant};
+
+    assert_impl_all!(CommandRatelimiter: Debug, Send, Sync);
+
+    #[test]
+    fn test_max() {
+        assert_eq!(115, CommandRatelimiter::new(0).max());
+        assert_eq!(115, CommandRatelimiter::new(41_250).max());
+        assert_eq!(112, CommandRatelimiter::new(12_000).max());
+        assert_eq!(1, CommandRatelimiter::new(1).max());
+
+        let ratelimiter = CommandRatelimiter::new(0);
+        ratelimiter.set_heartbeat_interval(30_000);
+        assert_eq!(115, ratelimiter.max());
+        ratelimiter.set_heartbeat_interval(20_000);
+        assert_eq!(114, ratelimiter.max());
+    }
+
+    #[tokio::test]
+    async fn test_excess_delayed_past_window() {
+        time::pause();
+        let ratelimiter = CommandRatelimiter::new(41_250);
+        let start = Instant::now();
+        let mut elapsed = Vec::with_capacity(130);
+
+        for _ in 0..130 {
+            ratelimiter.acquire().await;
+            elapsed.push(start.elapsed());
+        }
+
+        assert!(elapsed[..115].iter().all(|d| *d == Duration::from_secs(0)));
+        assert!(elapsed[115..].iter().all(|elapsed| *elapsed >= PERIOD));
+        assert_eq!(100, ratelimiter.available());
+
+        time::advance(PERIOD).await;
+        assert_eq!(115, ratelimiter.available());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_tiny_interval() {
+        time::pause();
+        let ratelimiter = CommandRatelimiter::new(1);
+        let start = Instant::now();
+
+        ratelimiter.acquire().await;
+        assert_eq!(Duration::from_secs(0), start.elapsed());
+        assert_eq!(0, ratelimiter.available());
+
+        ratelimiter.acquire().await;
+        assert!(start.elapsed() >= PERIOD);
+    }
+
+    #[tokio::test]
+    async fn test_available() {
+        time::pause();
+        let ratelimiter = CommandRatelimiter::new(41_250);
+        assert_eq!(115, ratelimiter.available());
+
+        for _ in 0..10 {
+            ratelimiter.acquire().await;
+        }
+
+        assert_eq!(105, ratelimiter.available());
+
+        time::advance(Duration::from_secs(30)).await;
+        ratelimiter.acquire().await;
+        assert_eq!(104, ratelimiter.available());
+
+        // The first ten commands leave the window, but not the last one.
+        time::advance(Duration::from_secs(30)).await;
+        assert_eq!(114, ratelimiter.available());
+    }
+}
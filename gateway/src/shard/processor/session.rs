@@ -1,25 +1,24 @@
+#[cfg(feature = "etf")]
+use super::super::etf::{self, EtfError};
 use super::{
-    super::{json, stage::Stage},
+    super::{config::Config, json, stage::Stage},
     heartbeat::{Heartbeater, Heartbeats},
-    throttle::Throttle,
+    ratelimiter::CommandRatelimiter,
 };
 use async_tungstenite::tungstenite::{protocol::CloseFrame, Message as TungsteniteMessage};
 use futures_channel::mpsc::{TrySendError, UnboundedSender};
-use futures_util::{
-    future::{self, AbortHandle},
-    lock::Mutex,
-};
+use futures_util::future::{self, AbortHandle};
 use serde::ser::Serialize;
 use std::{
     convert::TryFrom,
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
     sync::{
-        atomic::{AtomicU64, AtomicU8, Ordering},
+        atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering},
         Arc, Mutex as MutexSync,
     },
-    time::Duration,
 };
+use tokio::sync::watch::Receiver as WatchReceiver;
 use twilight_model::gateway::payload::Heartbeat;
 
 #[cfg(not(feature = "simd-json"))]
@@ -35,12 +34,18 @@ pub enum SessionSendError {
     Serializing {
         source: JsonError,
     },
+    #[cfg(feature = "etf")]
+    SerializingEtf {
+        source: EtfError,
+    },
 }
 
 impl Display for SessionSendError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Self::Serializing { source } => Display::fmt(source, f),
+            #[cfg(feature = "etf")]
+            Self::SerializingEtf { source } => Display::fmt(source, f),
             Self::Sending { source } => Display::fmt(source, f),
         }
     }
@@ -51,36 +56,99 @@ impl Error for SessionSendError {
         match self {
             Self::Sending { source } => Some(source),
             Self::Serializing { source } => Some(source),
+            #[cfg(feature = "etf")]
+            Self::SerializingEtf { source } => Some(source),
+        }
+    }
+}
+
+/// Encoding of the payloads sent over the connection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    Json,
+    #[cfg(feature = "etf")]
+    Etf,
+}
+
+impl Encoding {
+    /// Determine the encoding configured for the shard.
+    #[cfg_attr(not(feature = "etf"), allow(unused_variables))]
+    pub fn from_config(config: &Config) -> Self {
+        #[cfg(feature = "etf")]
+        if config.etf() {
+            return Self::Etf;
+        }
+
+        Self::Json
+    }
+
+    /// Serialize a payload in the encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SessionSendError::Serializing`] when the payload failed to
+    /// serialize.
+    pub fn encode(self, payload: &impl Serialize) -> Result<Vec<u8>, SessionSendError> {
+        match self {
+            Self::Json => {
+                json::to_vec(payload).map_err(|source| SessionSendError::Serializing { source })
+            }
+            #[cfg(feature = "etf")]
+            Self::Etf => {
+                etf::to_vec(payload).map_err(|source| SessionSendError::SerializingEtf { source })
+            }
         }
     }
 }
 
 #[derive(Debug)]
 pub struct Session {
+    /// Receiver whose sender is dropped once the connection has closed.
+    closed: WatchReceiver<()>,
+    /// Number of compressed bytes received over the connection.
+    pub compressed_bytes: AtomicU64,
+    /// Number of bytes the compressed bytes decompressed into.
+    pub decompressed_bytes: AtomicU64,
+    pub encoding: Encoding,
     // Needs to be Arc so it can be cloned in the `Drop` impl when spawned on
     // the runtime.
     pub heartbeater_handle: Arc<MutexSync<Option<AbortHandle>>>,
     pub heartbeats: Arc<Heartbeats>,
     pub heartbeat_interval: AtomicU64,
     pub id: MutexSync<Option<Box<str>>>,
+    /// Capacity of the buffers payloads are decompressed with.
+    pub inflater_capacity: AtomicUsize,
+    /// Ratelimiter of the commands sent by the user, if enabled.
+    pub ratelimit: Option<CommandRatelimiter>,
     pub seq: Arc<AtomicU64>,
     pub stage: AtomicU8,
     pub tx: UnboundedSender<TungsteniteMessage>,
-    pub ratelimit: Mutex<Throttle>,
 }
 
 impl Session {
-    pub fn new(tx: UnboundedSender<TungsteniteMessage>) -> Self {
+    pub fn new(
+        tx: UnboundedSender<TungsteniteMessage>,
+        closed: WatchReceiver<()>,
+        config: &Config,
+    ) -> Self {
         Self {
+            closed,
+            compressed_bytes: AtomicU64::new(0),
+            decompressed_bytes: AtomicU64::new(0),
+            encoding: Encoding::from_config(config),
             heartbeater_handle: Arc::new(MutexSync::new(None)),
             heartbeats: Arc::new(Heartbeats::default()),
             heartbeat_interval: AtomicU64::new(0),
             id: MutexSync::new(None),
+            inflater_capacity: AtomicUsize::new(0),
+            ratelimit: if config.ratelimit_commands() {
+                Some(CommandRatelimiter::new(0))
+            } else {
+                None
+            },
             seq: Arc::new(AtomicU64::new(0)),
             stage: AtomicU8::new(Stage::default() as u8),
             tx,
-            // 520 instead of 500 to make sure that it can heartbeat.
-            ratelimit: Mutex::new(Throttle::new(Duration::from_millis(520))),
         }
     }
 
@@ -95,8 +163,7 @@ impl Session {
     /// up. This will only happen when the shard has either not started or has
     /// already shutdown.
     pub fn send(&self, payload: impl Serialize) -> Result<(), SessionSendError> {
-        let bytes =
-            json::to_vec(&payload).map_err(|source| SessionSendError::Serializing { source })?;
+        let bytes = self.encoding.encode(&payload)?;
 
         self.tx
             .unbounded_send(TungsteniteMessage::Binary(bytes))
@@ -113,6 +180,14 @@ impl Session {
             .unbounded_send(TungsteniteMessage::Close(close_frame))
     }
 
+    /// Wait until the connection has closed.
+    pub async fn closed(&self) {
+        let mut closed = self.closed.clone();
+
+        // Nothing is ever sent, so this only returns once the sender drops.
+        while closed.changed().await.is_ok() {}
+    }
+
     pub fn heartbeat_interval(&self) -> u64 {
         self.heartbeat_interval.load(Ordering::Relaxed)
     }
@@ -120,6 +195,10 @@ impl Session {
     pub fn set_heartbeat_interval(&self, new_heartbeat_interval: u64) {
         self.heartbeat_interval
             .store(new_heartbeat_interval, Ordering::Release);
+
+        if let Some(ratelimit) = self.ratelimit.as_ref() {
+            ratelimit.set_heartbeat_interval(new_heartbeat_interval);
+        }
     }
 
     /// Returns the current sequence.
@@ -132,6 +211,21 @@ impl Session {
         self.seq.store(seq, Ordering::Release);
     }
 
+    /// Sets the number of compressed bytes received and the number of bytes
+    /// they decompressed into.
+    #[cfg(feature = "compression")]
+    pub fn set_compression_totals(&self, compressed: u64, decompressed: u64) {
+        self.compressed_bytes.store(compressed, Ordering::Release);
+        self.decompressed_bytes
+            .store(decompressed, Ordering::Release);
+    }
+
+    /// Sets the capacity of the buffers payloads are decompressed with.
+    #[cfg(feature = "compression")]
+    pub fn set_inflater_capacity(&self, capacity: usize) {
+        self.inflater_capacity.store(capacity, Ordering::Release);
+    }
+
     /// Returns the current shard stage.
     pub fn stage(&self) -> Stage {
         Stage::try_from(self.stage.load(Ordering::Relaxed)).unwrap_or_default()
@@ -143,7 +237,10 @@ impl Session {
     }
 
     pub fn heartbeat(&self) -> Result<(), SessionSendError> {
-        self.send(Heartbeat::new(self.seq()))
+        self.send(Heartbeat::new(self.seq()))?;
+        self.heartbeats.send();
+
+        Ok(())
     }
 
     pub fn id(&self) -> Option<Box<str>> {
@@ -154,6 +251,11 @@ impl Session {
         self.id.lock().expect("id poisoned").replace(new_id);
     }
 
+    /// Clears the ID of a session that was invalidated.
+    pub fn clear_id(&self) {
+        self.id.lock().expect("id poisoned").take();
+    }
+
     pub fn stop_heartbeater(&self) {
         if let Some(handle) = self
             .heartbeater_handle
@@ -165,12 +267,20 @@ impl Session {
         }
     }
 
-    pub fn start_heartbeater(&self) {
+    pub fn start_heartbeater(&self, jitter: f64) {
         let interval = self.heartbeat_interval();
         let seq = Arc::clone(&self.seq);
         let heartbeats = Arc::clone(&self.heartbeats);
 
-        let heartbeater = Heartbeater::new(heartbeats, interval, seq, self.tx.clone()).run();
+        let heartbeater = Heartbeater::new(
+            heartbeats,
+            interval,
+            jitter,
+            seq,
+            self.tx.clone(),
+            self.encoding,
+        )
+        .run();
         let (fut, handle) = future::abortable(heartbeater);
 
         tokio::spawn(fut);
@@ -1,27 +1,44 @@
 use super::super::ShardStream;
 use async_tungstenite::tungstenite::Message;
-use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_channel::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
 use futures_timer::Delay;
-use futures_util::{future::FutureExt, sink::SinkExt, stream::StreamExt};
-use std::time::Duration;
+use futures_util::{
+    future::{self, FutureExt},
+    sink::SinkExt,
+    stream::StreamExt,
+};
+use std::{task::Poll, time::Duration};
+use tokio::sync::watch::{self, Receiver as WatchReceiver, Sender as WatchSender};
 
 pub struct SocketForwarder {
+    /// Sender dropped once the forwarder stops, closing [`closed`].
+    ///
+    /// [`closed`]: Self::closed
+    _closed_tx: WatchSender<()>,
+    closed_rx: WatchReceiver<()>,
     rx: UnboundedReceiver<Message>,
     pub stream: ShardStream,
-    tx: UnboundedSender<Message>,
+    tx: Sender<Message>,
 }
 
 impl SocketForwarder {
+    /// Number of messages read from the socket ahead of the processor.
+    ///
+    /// Messages are only read once the processor has room for them, so that
+    /// a processor applying backpressure stops the socket from being read.
+    const BUFFER: usize = 1;
+
     const TIMEOUT: Duration = Duration::from_secs(90);
 
-    pub fn new(
-        stream: ShardStream,
-    ) -> (Self, UnboundedReceiver<Message>, UnboundedSender<Message>) {
-        let (to_user, from_forwarder) = mpsc::unbounded();
+    pub fn new(stream: ShardStream) -> (Self, Receiver<Message>, UnboundedSender<Message>) {
+        let (to_user, from_forwarder) = mpsc::channel(Self::BUFFER);
         let (to_forwarder, from_user) = mpsc::unbounded();
+        let (closed_tx, closed_rx) = watch::channel(());
 
         (
             Self {
+                _closed_tx: closed_tx,
+                closed_rx,
                 rx: from_user,
                 stream,
                 tx: to_user,
@@ -31,6 +48,12 @@ impl SocketForwarder {
         )
     }
 
+    /// Return a receiver whose sender is dropped once the forwarder stops,
+    /// which is when the connection has closed.
+    pub fn closed(&self) -> WatchReceiver<()> {
+        self.closed_rx.clone()
+    }
+
     pub async fn run(mut self) {
         tracing::debug!("starting driving loop");
 
@@ -39,7 +62,17 @@ impl SocketForwarder {
         #[allow(clippy::mut_mut)]
         loop {
             let mut rx = self.rx.next();
-            let mut stream = self.stream.next().fuse();
+            let socket = &mut self.stream;
+            let tx = &mut self.tx;
+            // Only read from the socket once the processor can take the
+            // message. If the processor is gone then the message read next
+            // resolves to an error instead.
+            let mut stream = future::poll_fn(|cx| match tx.poll_ready(cx) {
+                Poll::Ready(Ok(())) => socket.poll_next_unpin(cx).map(Ok),
+                Poll::Ready(Err(source)) => socket.poll_next_unpin(cx).map(|_| Err(source)),
+                Poll::Pending => Poll::Pending,
+            })
+            .fuse();
             let mut timeout = Delay::new(Self::TIMEOUT).fuse();
 
             futures_util::select! {
@@ -60,21 +93,22 @@ impl SocketForwarder {
                 },
                 try_msg = stream => {
                     match try_msg {
-                        Some(Ok(msg)) => {
-                            if self.tx.unbounded_send(msg).is_err() {
+                        Ok(Some(Ok(msg))) => {
+                            if self.tx.start_send(msg).is_err() {
                                 break;
                             }
                         },
-                        Some(Err(err)) => {
+                        Ok(Some(Err(err))) => {
                             tracing::warn!("socket errored, closing tx: {}", err);
                             self.tx.close_channel();
                             break;
                         },
-                        None => {
+                        Ok(None) => {
                             tracing::debug!("socket ended, closing tx");
                             self.tx.close_channel();
                             break;
-                        }
+                        },
+                        Err(_) => break,
                     }
                 },
                 _ = timeout => {
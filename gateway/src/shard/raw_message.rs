@@ -45,6 +45,52 @@ impl<'a, T: Into<Cow<'a, str>>> From<(u16, T)> for CloseFrame<'a> {
     }
 }
 
+impl CloseFrame<'static> {
+    /// Close with the normal close code, making Discord invalidate the
+    /// session and show the bot as offline.
+    pub const NORMAL: Self = Self {
+        code: 1000,
+        reason: Cow::Borrowed(""),
+    };
+
+    /// Close with a restart close code, making Discord keep the session alive
+    /// so that it can be resumed.
+    pub const RESUME: Self = Self {
+        code: 1012,
+        reason: Cow::Borrowed("Closing in a resumable way"),
+    };
+}
+
+impl CloseFrame<'_> {
+    /// Whether Discord keeps the session alive after closing the connection
+    /// with this frame.
+    ///
+    /// Only the normal (1000) and going away (1001) close codes invalidate the
+    /// session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_gateway::shard::raw_message::CloseFrame;
+    ///
+    /// assert!(!CloseFrame::NORMAL.is_resumable());
+    /// assert!(CloseFrame::RESUME.is_resumable());
+    /// assert!(CloseFrame::from((4000, "maintenance")).is_resumable());
+    /// ```
+    pub fn is_resumable(&self) -> bool {
+        !matches!(self.code, 1000 | 1001)
+    }
+}
+
+impl CloseFrame<'static> {
+    pub(super) fn into_tungstenite(self) -> TungsteniteCloseFrame<'static> {
+        TungsteniteCloseFrame {
+            code: CloseCode::from(self.code),
+            reason: self.reason,
+        }
+    }
+}
+
 /// Message to send over the connection to the remote.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -71,10 +117,7 @@ impl Message {
         match self {
             Self::Binary(bytes) => TungsteniteMessage::Binary(bytes),
             Self::Close(close) => {
-                TungsteniteMessage::Close(close.map(|close| TungsteniteCloseFrame {
-                    code: CloseCode::from(close.code),
-                    reason: close.reason,
-                }))
+                TungsteniteMessage::Close(close.map(CloseFrame::into_tungstenite))
             }
             Self::Ping(bytes) => TungsteniteMessage::Ping(bytes),
             Self::Pong(bytes) => TungsteniteMessage::Pong(bytes),
@@ -100,4 +143,10 @@ mod tests {
         PartialEq,
     );
     assert_impl_all!(Message: Clone, Debug, Eq, PartialEq);
+
+    #[test]
+    fn test_is_resumable() {
+        assert!(!CloseFrame::from((1001, "")).is_resumable());
+        assert!(CloseFrame::from((4100, "")).is_resumable());
+    }
 }
@@ -0,0 +1,183 @@
+use std::{
+    collections::hash_map::RandomState,
+    convert::TryFrom,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Policy for how long a shard waits between attempts to reconnect to the
+/// gateway.
+///
+/// The delay starts at [`initial_delay`] and is multiplied by
+/// [`multiplier`] after every attempt, up to [`max_delay`]. Attempts are
+/// counted until the shard receives a Ready or Resumed event, at which point
+/// the delay starts over.
+///
+/// # Examples
+///
+/// Wait between 5 and 10 seconds at first, up to 5 minutes, and give up after
+/// 10 attempts:
+///
+/// ```
+/// use std::time::Duration;
+/// use twilight_gateway::shard::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy {
+///     initial_delay: Duration::from_secs(10),
+///     jitter: 0.5,
+///     max_attempts: Some(10),
+///     max_delay: Duration::from_secs(300),
+///     multiplier: 2.0,
+/// };
+/// ```
+///
+/// [`initial_delay`]: Self::initial_delay
+/// [`max_delay`]: Self::max_delay
+/// [`multiplier`]: Self::multiplier
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Delay before the first attempt.
+    pub initial_delay: Duration,
+    /// Fraction of the delay by which the delay of each attempt is randomly
+    /// shortened, between 0 and 1.
+    ///
+    /// This spreads out the reconnects of shards that disconnected at the same
+    /// time, such as during an outage.
+    pub jitter: f64,
+    /// Number of attempts after which the shard gives up reconnecting.
+    ///
+    /// When it does, the shard emits a [`ShardStopped`] event and stops as if
+    /// it was shut down, so its event streams end. `None` means the shard
+    /// never gives up.
+    ///
+    /// [`ShardStopped`]: twilight_model::gateway::event::Event::ShardStopped
+    pub max_attempts: Option<u32>,
+    /// Maximum delay between attempts.
+    pub max_delay: Duration,
+    /// Factor the delay is multiplied by after every attempt.
+    ///
+    /// Factors below 1 are treated as 1.
+    pub multiplier: f64,
+}
+
+impl ReconnectPolicy {
+    /// Calculate the delay before an attempt, counting from 0.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let secs = self.initial_delay.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let secs = secs.min(self.max_delay.as_secs_f64());
+
+        // `f64::clamp` isn't available on the minimum supported Rust version.
+        #[allow(clippy::manual_clamp)]
+        let jitter = self.jitter.max(0.0).min(1.0);
+
+        if jitter.is_nan() || jitter <= 0.0 {
+            return Duration::from_secs_f64(secs);
+        }
+
+        Duration::from_secs_f64(secs * (1.0 - jitter * random_fraction()))
+    }
+
+    /// Whether the policy allows another attempt after a number of attempts.
+    pub(crate) fn allows(&self, attempts: u32) -> bool {
+        self.max_attempts.map_or(true, |max| attempts < max)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    /// Wait 1 second at first, doubling up to 128 seconds, without jitter and
+    /// without ever giving up.
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            jitter: 0.0,
+            max_attempts: None,
+            max_delay: Duration::from_secs(128),
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// Random number in `[0, 1)`.
+///
+/// The standard library's hashers are randomly seeded, which is random enough
+/// to spread out reconnects.
+pub(super) fn random_fraction() -> f64 {
+    let hasher = RandomState::new().build_hasher();
+
+    // Only the 53 bits that fit in the mantissa are used.
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64;
+
+    fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReconnectPolicy;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, time::Duration};
+
+    assert_impl_all!(ReconnectPolicy: Clone, Debug, Default, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_delay() {
+        let policy = ReconnectPolicy {
+            initial_delay: Duration::from_millis(500),
+            jitter: 0.0,
+            max_attempts: None,
+            max_delay: Duration::from_secs(3),
+            multiplier: 2.0,
+        };
+
+        let delays = (0..5)
+            .map(|attempt| policy.delay(attempt))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [500, 1000, 2000, 3000, 3000]
+                .iter()
+                .map(|millis| Duration::from_millis(*millis))
+                .collect::<Vec<_>>(),
+            delays
+        );
+        assert_eq!(Duration::from_secs(3), policy.delay(u32::MAX));
+    }
+
+    #[test]
+    fn test_delay_default() {
+        let policy = ReconnectPolicy::default();
+
+        assert_eq!(Duration::from_secs(1), policy.delay(0));
+        assert_eq!(Duration::from_secs(64), policy.delay(6));
+        assert_eq!(Duration::from_secs(128), policy.delay(7));
+        assert_eq!(Duration::from_secs(128), policy.delay(8));
+    }
+
+    #[test]
+    fn test_delay_jitter() {
+        let policy = ReconnectPolicy {
+            jitter: 0.25,
+            multiplier: 0.5,
+            ..ReconnectPolicy::default()
+        };
+
+        for attempt in 0..100 {
+            let delay = policy.delay(attempt);
+
+            assert!(delay <= Duration::from_secs(1), "{:?}", delay);
+            assert!(delay >= Duration::from_millis(750), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn test_allows() {
+        assert!(ReconnectPolicy::default().allows(u32::MAX));
+
+        let policy = ReconnectPolicy {
+            max_attempts: Some(2),
+            ..ReconnectPolicy::default()
+        };
+        assert!(policy.allows(1));
+        assert!(!policy.allows(2));
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+/// Default number of seconds since the buffers last grew after which they're
+/// shrunk.
+const PERIOD_SECS: u64 = 60;
+
+/// Policy for when a shard shrinks the buffers it decompresses payloads with.
+///
+/// The buffers grow to fit the largest payload received, such as the Guild
+/// Create of a big guild, which can be several megabytes. Once the buffers
+/// haven't grown for [`messages`] messages or for [`period`], they're
+/// shrunk back to [`baseline`].
+///
+/// This only applies when transport compression is used. Refer to
+/// [`Information::inflater_capacity`] for monitoring the capacity of the
+/// buffers.
+///
+/// # Examples
+///
+/// Keep 64 KiB around, shrinking after 100 messages or 10 seconds:
+///
+/// ```
+/// use std::time::Duration;
+/// use twilight_gateway::shard::ShrinkPolicy;
+///
+/// let policy = ShrinkPolicy {
+///     baseline: 64 * 1024,
+///     messages: 100,
+///     period: Duration::from_secs(10),
+/// };
+/// ```
+///
+/// [`Information::inflater_capacity`]: super::Information::inflater_capacity
+/// [`baseline`]: Self::baseline
+/// [`messages`]: Self::messages
+/// [`period`]: Self::period
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ShrinkPolicy {
+    /// Capacity in bytes that the buffers are shrunk back to.
+    pub baseline: usize,
+    /// Number of messages received since the buffers last grew after which
+    /// they're shrunk.
+    pub messages: u64,
+    /// Time since the buffers last grew after which they're shrunk.
+    pub period: Duration,
+}
+
+impl Default for ShrinkPolicy {
+    /// Keep 32 KiB, shrinking after 1000 messages or 60 seconds.
+    fn default() -> Self {
+        Self {
+            baseline: 32 * 1024,
+            messages: 1_000,
+            period: Duration::from_secs(PERIOD_SECS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShrinkPolicy;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_impl_all!(
+        ShrinkPolicy: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+}
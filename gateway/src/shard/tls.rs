@@ -0,0 +1,183 @@
+//! TLS configuration used to connect to the gateway.
+//!
+//! Building a TLS configuration loads every root certificate into memory, so
+//! it's built once per configuration and shared by the shards created from
+//! it, such as all shards of a cluster.
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+
+#[cfg(feature = "native-tls")]
+use native_tls_crate::TlsConnector as NativeTlsConnector;
+#[cfg(all(
+    any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+    not(feature = "native-tls")
+))]
+use tokio_rustls::rustls::ClientConfig;
+
+/// Connector the TLS stream to the gateway is created with.
+#[cfg(feature = "native-tls")]
+pub type Connector = tokio_native_tls::TlsConnector;
+
+/// Connector the TLS stream to the gateway is created with.
+#[cfg(all(
+    any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+    not(feature = "native-tls")
+))]
+pub type Connector = tokio_rustls::TlsConnector;
+
+/// Building the TLS configuration to connect to the gateway with failed.
+#[derive(Debug)]
+pub struct TlsError {
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl Display for TlsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("building the TLS configuration failed")
+    }
+}
+
+impl Error for TlsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// TLS configuration shared by the shards created from the same
+/// configuration.
+///
+/// Cloning the container shares the configuration instead of building it
+/// again.
+#[derive(Clone)]
+pub struct TlsContainer {
+    #[cfg(feature = "native-tls")]
+    tls: Arc<NativeTlsConnector>,
+    #[cfg(all(
+        any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+        not(feature = "native-tls")
+    ))]
+    tls: Arc<ClientConfig>,
+}
+
+impl TlsContainer {
+    /// Build the TLS configuration of the enabled TLS feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TlsError`] if the system's TLS implementation couldn't be
+    /// initialized, or if none of the system's root certificates could be
+    /// loaded.
+    #[cfg(feature = "native-tls")]
+    pub fn new() -> Result<Self, TlsError> {
+        let connector = NativeTlsConnector::new().map_err(|source| TlsError {
+            source: Box::new(source),
+        })?;
+
+        Ok(Self {
+            tls: Arc::new(connector),
+        })
+    }
+
+    /// Build the TLS configuration of the enabled TLS feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TlsError`] if the system's TLS implementation couldn't be
+    /// initialized, or if none of the system's root certificates could be
+    /// loaded.
+    #[cfg(all(
+        any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+        not(feature = "native-tls")
+    ))]
+    pub fn new() -> Result<Self, TlsError> {
+        let mut config = ClientConfig::new();
+
+        // Like with `twilight-http`, the system's roots take precedence.
+        #[cfg(feature = "rustls-native-roots")]
+        {
+            config.root_store = match rustls_native_certs::load_native_certs() {
+                Ok(store) => store,
+                Err((Some(store), source)) => {
+                    tracing::warn!("some root certificates couldn't be loaded: {}", source);
+
+                    store
+                }
+                Err((None, source)) => {
+                    return Err(TlsError {
+                        source: Box::new(source),
+                    })
+                }
+            };
+        }
+
+        #[cfg(not(feature = "rustls-native-roots"))]
+        config
+            .root_store
+            .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+
+        Ok(Self {
+            tls: Arc::new(config),
+        })
+    }
+
+    /// Create a connector using the shared configuration.
+    #[cfg(feature = "native-tls")]
+    pub fn connector(&self) -> Connector {
+        Connector::from(NativeTlsConnector::clone(&self.tls))
+    }
+
+    /// Create a connector using the shared configuration.
+    #[cfg(all(
+        any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+        not(feature = "native-tls")
+    ))]
+    pub fn connector(&self) -> Connector {
+        Connector::from(Arc::clone(&self.tls))
+    }
+
+    /// Return whether two containers share the same configuration.
+    #[cfg(test)]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.tls, &other.tls)
+    }
+}
+
+impl Debug for TlsContainer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TlsContainer").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TlsContainer, TlsError};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+
+    assert_impl_all!(TlsContainer: Clone, Debug, Send, Sync);
+    assert_impl_all!(TlsError: Debug, Error, Send, Sync);
+
+    #[test]
+    fn test_clone_shares_config() {
+        let tls = TlsContainer::new().unwrap();
+        let clone = tls.clone();
+
+        assert!(tls.ptr_eq(&clone));
+        assert!(!tls.ptr_eq(&TlsContainer::new().unwrap()));
+    }
+
+    #[cfg(all(
+        feature = "rustls-webpki-roots",
+        not(any(feature = "native-tls", feature = "rustls-native-roots"))
+    ))]
+    #[test]
+    fn test_rustls_roots() {
+        let tls = TlsContainer::new().unwrap();
+
+        assert!(!tls.tls.root_store.is_empty());
+    }
+}
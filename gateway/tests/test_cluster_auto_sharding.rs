@@ -0,0 +1,174 @@
+use serde_json::json;
+use std::{error::Error, net::SocketAddr};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use twilight_gateway::{
+    cluster::{ClusterStartError, ShardScheme},
+    Cluster, Intents,
+};
+use twilight_http::Client;
+
+/// Token with the structure of a bot token.
+const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4.YAbC-d.abcdefghijklmnopqrstu_vwxyz";
+
+/// Serve the bot's gateway information over HTTP to every request.
+async fn serve_gateway_info(
+    shards: u64,
+    max_concurrency: u64,
+    remaining: u64,
+    reset_after: u64,
+) -> Result<SocketAddr, Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let body = json!({
+        "session_start_limit": {
+            "max_concurrency": max_concurrency,
+            "remaining": remaining,
+            "reset_after": reset_after,
+            "total": 1000,
+        },
+        "shards": shards,
+        "url": "wss://gateway.discord.gg",
+    })
+    .to_string();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(respond(stream, body.clone()));
+        }
+    });
+
+    Ok(addr)
+}
+
+async fn respond(mut stream: TcpStream, body: String) {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+
+    while let Ok(read) = stream.read(&mut buf).await {
+        if read == 0 {
+            return;
+        }
+
+        request.extend_from_slice(&buf[..read]);
+
+        // Requests to retrieve the gateway information have no body.
+        if request.ends_with(b"\r\n\r\n") {
+            request.clear();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body,
+            );
+
+            if stream.write_all(response.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn http(addr: SocketAddr) -> Client {
+    Client::builder()
+        .token(TOKEN)
+        .proxy(addr.to_string(), true)
+        .ratelimiter(None)
+        .build()
+}
+
+#[tokio::test]
+async fn test_auto_sharding() -> Result<(), Box<dyn Error>> {
+    let addr = serve_gateway_info(8, 1, 1000, 0).await?;
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .http_client(http(addr))
+        .build()
+        .await?;
+
+    let mut shards = cluster
+        .shards()
+        .iter()
+        .map(|shard| shard.config().shard())
+        .collect::<Vec<_>>();
+    shards.sort_unstable();
+    assert_eq!((0..8).map(|id| [id, 8]).collect::<Vec<_>>(), shards);
+    assert_eq!(
+        Some("wss://gateway.discord.gg"),
+        cluster.shard(0).unwrap().config().gateway_url()
+    );
+    assert!(format!("{:?}", cluster.config().queue()).starts_with("LocalQueue"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auto_sharding_max_concurrency() -> Result<(), Box<dyn Error>> {
+    let addr = serve_gateway_info(16, 4, 1000, 0).await?;
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .http_client(http(addr))
+        .build()
+        .await?;
+
+    assert_eq!(16, cluster.shards().len());
+    assert!(format!("{:?}", cluster.config().queue()).starts_with("LargeBotQueue"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auto_sharding_session_start_limit() -> Result<(), Box<dyn Error>> {
+    let addr = serve_gateway_info(8, 1, 4, 1000).await?;
+    let result = Cluster::builder(TOKEN, Intents::empty())
+        .http_client(http(addr))
+        .build()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ClusterStartError::SessionStartLimitExhausted {
+            remaining: 4,
+            required: 8,
+            reset_after: 1000,
+            total: 1000,
+        })
+    ));
+
+    let addr = serve_gateway_info(8, 1, 0, 10).await?;
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .http_client(http(addr))
+        .wait_for_session_start_limit(true)
+        .build()
+        .await?;
+    assert_eq!(8, cluster.shards().len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_http_client_shared() -> Result<(), Box<dyn Error>> {
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .gateway_url(Some("wss://gateway.discord.gg".to_owned()))
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 1,
+            total: 2,
+        })
+        .build()
+        .await?;
+
+    // A client is created from the token for the cluster, and the shards
+    // share it instead of creating their own.
+    let http = cluster.config().http_client().ok_or("no client")?;
+    assert_eq!(Some(format!("Bot {}", TOKEN).as_str()), http.token());
+
+    for shard in cluster.shards() {
+        assert_eq!(
+            http.token(),
+            shard.config().http_client().and_then(Client::token)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,2822 @@
+use async_tungstenite::{
+    tokio::{accept_hdr_async, TokioAdapter},
+    tungstenite::{
+        handshake::server::{Request, Response},
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
+};
+use futures::{future, Future, SinkExt, Stream, StreamExt};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    error::Error,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        Semaphore,
+    },
+    time::{timeout, Instant},
+};
+use twilight_gateway::{
+    cluster::{Cluster, ClusterStartError, ShardScheme},
+    queue::{DailyBudget, Queue},
+    shard::{
+        raw_message::{CloseFrame as RawCloseFrame, Message as RawMessage},
+        EventStreamOverflow, Events, MalformedDispatchPolicy, ReconnectPolicy, ResumeSession,
+        SendError, ShardStartError, Stage,
+    },
+    Event, EventTypeFlags, Intents, Shard,
+};
+use twilight_http::Client as HttpClient;
+use twilight_model::gateway::event::shard::{Disconnected, StopReason, Stopped};
+
+/// Token with the structure of a bot token.
+const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4.YAbC-d.abcdefghijklmnopqrstu_vwxyz";
+
+/// Queue letting shards identify immediately.
+#[derive(Debug)]
+struct NoopQueue;
+
+impl Queue for NoopQueue {
+    fn request(&'_ self, _: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(future::ready(()))
+    }
+}
+
+/// Queue letting shards identify only once a permit is added, reporting the
+/// shards that requested to identify.
+#[derive(Debug)]
+struct PermitQueue {
+    permits: Arc<Semaphore>,
+    requests: UnboundedSender<[u64; 2]>,
+}
+
+impl Queue for PermitQueue {
+    fn request(&'_ self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let _ = self.requests.send(shard_id);
+
+            if let Ok(permit) = self.permits.acquire().await {
+                permit.forget();
+            }
+        })
+    }
+}
+
+/// Queue letting shards identify immediately while they're within a daily
+/// budget, which resets in a minute.
+#[derive(Debug)]
+struct BudgetQueue(AtomicU64);
+
+impl Queue for BudgetQueue {
+    fn request(&'_ self, _: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let _ = self
+            .0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| {
+                remaining.checked_sub(1)
+            });
+
+        Box::pin(future::ready(()))
+    }
+
+    fn daily_budget(&self) -> Option<DailyBudget> {
+        Some(DailyBudget {
+            remaining: self.0.load(Ordering::SeqCst),
+            reset_at: SystemTime::now() + Duration::from_secs(60),
+            total: 1000,
+        })
+    }
+}
+
+/// Connection of a shard to the mock gateway.
+struct Connection {
+    /// Stream to compress payloads with, if the shard requested transport
+    /// compression.
+    #[cfg(feature = "compression")]
+    compress: Option<flate2::Compress>,
+    /// Whether the shard requested payloads to be encoded with ETF.
+    etf: bool,
+    /// Path of the URL the shard connected with.
+    path: String,
+    /// Query of the URL the shard connected with.
+    query: String,
+    stream: WebSocketStream<TokioAdapter<TcpStream>>,
+}
+
+impl Connection {
+    async fn accept(listener: &TcpListener) -> Result<Self, Box<dyn Error>> {
+        let (stream, _) = timeout(Duration::from_secs(10), listener.accept()).await??;
+
+        let mut path = String::new();
+        let mut query = String::new();
+        // The error response is defined by tungstenite.
+        #[allow(clippy::result_large_err)]
+        let stream = accept_hdr_async(stream, |request: &Request, response: Response| {
+            path = request.uri().path().to_owned();
+            query = request.uri().query().unwrap_or_default().to_owned();
+
+            Ok(response)
+        })
+        .await?;
+
+        Ok(Self {
+            #[cfg(feature = "compression")]
+            compress: if query.contains("compress=zlib-stream") {
+                Some(flate2::Compress::new(flate2::Compression::default(), true))
+            } else {
+                None
+            },
+            etf: query.contains("encoding=etf"),
+            path,
+            query,
+            stream,
+        })
+    }
+
+    /// Send a payload the way the gateway would for the requested encoding
+    /// and transport compression.
+    async fn send(&mut self, payload: Value) -> Result<(), Box<dyn Error>> {
+        let bytes = if self.etf {
+            let mut bytes = vec![131];
+            encode_etf(&payload, &mut bytes);
+
+            bytes
+        } else {
+            serde_json::to_vec(&payload)?
+        };
+
+        #[cfg(feature = "compression")]
+        let message = if let Some(compress) = self.compress.as_mut() {
+            let mut compressed = Vec::with_capacity(bytes.len() + 64);
+            compress.compress_vec(&bytes, &mut compressed, flate2::FlushCompress::Sync)?;
+
+            Message::Binary(compressed)
+        } else if self.etf {
+            Message::Binary(bytes)
+        } else {
+            Message::Text(String::from_utf8(bytes)?)
+        };
+        #[cfg(not(feature = "compression"))]
+        let message = if self.etf {
+            Message::Binary(bytes)
+        } else {
+            Message::Text(String::from_utf8(bytes)?)
+        };
+
+        self.stream.send(message).await?;
+
+        Ok(())
+    }
+
+    /// Receive the next payload sent by the shard, skipping heartbeats.
+    async fn receive(&mut self) -> Result<Value, Box<dyn Error>> {
+        loop {
+            let message = timeout(Duration::from_secs(10), self.stream.next())
+                .await?
+                .ok_or("shard closed the connection")??;
+
+            let payload: Value = match message {
+                // Commands encoded with ETF aren't decoded, only checked to
+                // be ETF maps.
+                Message::Binary(bytes) if self.etf => {
+                    assert_eq!([131, 116], bytes[..2]);
+
+                    json!({ "etf": bytes })
+                }
+                Message::Binary(bytes) => serde_json::from_slice(&bytes)?,
+                Message::Text(text) => serde_json::from_str(&text)?,
+                _ => continue,
+            };
+
+            if payload["op"] != 1 {
+                return Ok(payload);
+            }
+        }
+    }
+
+    /// Receive the next heartbeat sent by the shard, returning its sequence.
+    async fn heartbeat(&mut self) -> Result<Value, Box<dyn Error>> {
+        loop {
+            let message = timeout(Duration::from_secs(10), self.stream.next())
+                .await?
+                .ok_or("shard closed the connection")??;
+
+            let mut payload: Value = match message {
+                Message::Binary(bytes) => serde_json::from_slice(&bytes)?,
+                Message::Text(text) => serde_json::from_str(&text)?,
+                _ => continue,
+            };
+
+            if payload["op"] == 1 {
+                return Ok(payload["d"].take());
+            }
+        }
+    }
+
+    /// Receive messages until the shard closes the connection, returning the
+    /// close code.
+    async fn close_code(&mut self) -> Result<Option<CloseCode>, Box<dyn Error>> {
+        Ok(self.close_frame().await?.map(|frame| frame.code))
+    }
+
+    /// Receive messages until the shard closes the connection, returning the
+    /// close frame.
+    async fn close_frame(&mut self) -> Result<Option<CloseFrame<'static>>, Box<dyn Error>> {
+        loop {
+            let message = timeout(Duration::from_secs(10), self.stream.next())
+                .await?
+                .ok_or("shard dropped the connection")??;
+
+            if let Message::Close(frame) = message {
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+/// Encode a JSON value as an ETF term the way the gateway does, with map keys
+/// as atoms and strings as binaries.
+fn encode_etf(value: &Value, bytes: &mut Vec<u8>) {
+    fn atom(name: &str, bytes: &mut Vec<u8>) {
+        bytes.push(119);
+        bytes.push(name.len() as u8);
+        bytes.extend_from_slice(name.as_bytes());
+    }
+
+    match value {
+        Value::Null => atom("nil", bytes),
+        Value::Bool(value) => atom(if *value { "true" } else { "false" }, bytes),
+        Value::Number(number) => {
+            let value = number.as_u64().expect("only unsigned integers are sent");
+            let digits = value.to_le_bytes();
+
+            bytes.extend_from_slice(&[110, 8, 0]);
+            bytes.extend_from_slice(&digits);
+        }
+        Value::String(value) => {
+            bytes.push(109);
+            bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(value.as_bytes());
+        }
+        Value::Array(values) => {
+            if !values.is_empty() {
+                bytes.push(108);
+                bytes.extend_from_slice(&(values.len() as u32).to_be_bytes());
+
+                for value in values {
+                    encode_etf(value, bytes);
+                }
+            }
+
+            bytes.push(106);
+        }
+        Value::Object(map) => {
+            bytes.push(116);
+            bytes.extend_from_slice(&(map.len() as u32).to_be_bytes());
+
+            for (key, value) in map {
+                atom(key, bytes);
+                encode_etf(value, bytes);
+            }
+        }
+    }
+}
+
+/// Act as the gateway for a shard of the cluster, returning the shard's ID
+/// and the code it closed the connection with.
+///
+/// Shard 0 is resumed, shard 1 identifies, and shard 2 never finishes
+/// identifying.
+async fn serve_cluster_shard(
+    mut connection: Connection,
+) -> Result<(u64, Option<CloseCode>), Box<dyn Error>> {
+    let shard_id = start_cluster_shard(&mut connection).await?;
+
+    Ok((shard_id, connection.close_code().await?))
+}
+
+/// Greet a shard of the cluster and answer its resume or identify the way
+/// [`serve_cluster_shard`] does, returning the shard's ID.
+async fn start_cluster_shard(connection: &mut Connection) -> Result<u64, Box<dyn Error>> {
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let payload = connection.receive().await?;
+
+    let shard_id = match payload["op"].as_u64() {
+        Some(6) => {
+            assert_eq!("abc", payload["d"]["session_id"]);
+            assert_eq!(5, payload["d"]["seq"]);
+            connection
+                .send(json!({ "op": 0, "s": 6, "t": "RESUMED", "d": {} }))
+                .await?;
+
+            0
+        }
+        Some(2) => {
+            let shard_id = payload["d"]["shard"][0].as_u64().ok_or("shard missing")?;
+
+            if shard_id == 1 {
+                connection
+                    .send(json!({
+                        "op": 0,
+                        "s": 1,
+                        "t": "READY",
+                        "d": {
+                            "guilds": [],
+                            "session_id": "def",
+                            "shard": [1, 3],
+                            "user": {
+                                "avatar": null,
+                                "discriminator": "0001",
+                                "id": "1",
+                                "mfa_enabled": false,
+                                "username": "bot",
+                            },
+                            "v": 8,
+                        },
+                    }))
+                    .await?;
+            }
+
+            shard_id
+        }
+        _ => return Err(format!("unexpected payload: {}", payload).into()),
+    };
+
+    Ok(shard_id)
+}
+
+#[tokio::test]
+async fn test_shard_resumes_saved_session() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .resume_session(ResumeSession {
+            session_id: "abc".to_owned(),
+            sequence: 42,
+        })
+        .build();
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    assert_eq!(
+        Some("abc"),
+        shard.session().as_ref().map(|s| s.session_id.as_str())
+    );
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let resume = connection.receive().await?;
+    assert_eq!(6, resume["op"]);
+    assert_eq!("abc", resume["d"]["session_id"]);
+    assert_eq!(42, resume["d"]["seq"]);
+    assert_eq!(TOKEN, resume["d"]["token"]);
+
+    // Rejecting the resume makes the shard identify on a new connection.
+    connection.send(json!({ "op": 9, "d": false })).await?;
+    let mut connection = Connection::accept(&listener).await?;
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let identify = connection.receive().await?;
+    assert_eq!(2, identify["op"]);
+    assert_eq!(TOKEN, identify["d"]["token"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_token_normalized() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(format!("  Bot {}\r\n", TOKEN), Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    assert_eq!(TOKEN, shard.config().token());
+    // No HTTP client is needed when the gateway URL is known.
+    assert!(shard.config().http_client().is_none());
+
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    // The gateway is identified with without the prefix and whitespace.
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let identify = connection.receive().await?;
+    assert_eq!(2, identify["op"]);
+    assert_eq!(TOKEN, identify["d"]["token"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Serve the bot's gateway information pointing to a gateway URL over HTTP
+/// to one request, sending the request's head.
+async fn serve_gateway_info(
+    url: String,
+) -> Result<(SocketAddr, UnboundedReceiver<String>), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let (tx, rx) = mpsc::unbounded_channel();
+    let body = json!({
+        "session_start_limit": {
+            "max_concurrency": 1,
+            "remaining": 1000,
+            "reset_after": 0,
+            "total": 1000,
+        },
+        "shards": 1,
+        "url": url,
+    })
+    .to_string();
+
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+
+        // Requests to retrieve the gateway information have no body.
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await?;
+
+            if read == 0 {
+                return Ok(());
+            }
+
+            request.extend_from_slice(&buf[..read]);
+        }
+
+        let _ = tx.send(String::from_utf8_lossy(&request).into_owned());
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body,
+        );
+        stream.write_all(response.as_bytes()).await?;
+        // Wait for the client to hang up, so the response isn't cut off.
+        let _ = stream.read(&mut buf).await;
+
+        Ok::<_, std::io::Error>(())
+    });
+
+    Ok((addr, rx))
+}
+
+#[tokio::test]
+async fn test_shard_http_client() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+    let (addr, mut requests) = serve_gateway_info(url).await?;
+
+    // The gateway URL is retrieved with the provided client, whose proxy is
+    // the mock HTTP server.
+    let http = HttpClient::builder()
+        .token(TOKEN)
+        .proxy(addr.to_string(), true)
+        .ratelimiter(None)
+        .build();
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .http_client(http)
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    assert!(shard.config().http_client().is_some());
+
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    let request = timeout(Duration::from_secs(10), requests.recv())
+        .await?
+        .ok_or("no request was made")?;
+    assert!(
+        request.starts_with("GET /api/v8/gateway/bot HTTP/1.1\r\n"),
+        "unexpected request: {}",
+        request
+    );
+    assert!(request
+        .to_lowercase()
+        .contains(&format!("authorization: bot {}", TOKEN.to_lowercase())));
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_token_invalid() -> Result<(), Box<dyn Error>> {
+    use twilight_http::token::TokenError;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cases = [
+        (" \n", TokenError::Empty),
+        ("Bot token", TokenError::PartCount { parts: 1 }),
+        ("abc..def", TokenError::PartEmpty { part: 2 }),
+        (
+            "abc.d/f.ghi",
+            TokenError::CharacterInvalid {
+                character: '/',
+                part: 2,
+            },
+        ),
+    ];
+
+    for (token, expected) in cases.iter() {
+        let mut shard = Shard::builder(*token, Intents::empty())
+            .gateway_url(Some(url.clone()))
+            .queue(Arc::new(Box::new(NoopQueue)))
+            .build();
+
+        match shard.start().await {
+            Err(ShardStartError::TokenInvalid { source }) => assert_eq!(expected, &source),
+            other => panic!("unexpected result for {:?}: {:?}", token, other),
+        }
+    }
+
+    // The tokens are rejected before connecting.
+    assert!(timeout(Duration::from_millis(100), listener.accept())
+        .await
+        .is_err());
+
+    let cluster = Cluster::builder("abc.def", Intents::empty())
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 0,
+            total: 1,
+        })
+        .build()
+        .await;
+    assert!(
+        matches!(
+            cluster,
+            Err(ClusterStartError::TokenInvalid {
+                source: TokenError::PartCount { parts: 2 }
+            })
+        ),
+        "unexpected result: {:?}",
+        cluster.map(|_| ())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cluster_down_with() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard_scheme(ShardScheme::Range {
+            from: 1,
+            to: 2,
+            total: 3,
+        })
+        .build()
+        .await?;
+    let mut events = cluster.some_events(EventTypeFlags::READY);
+
+    let server = async {
+        let mut handles = Vec::new();
+
+        for _ in 0..2 {
+            let mut connection = Connection::accept(&listener).await?;
+            handles.push(tokio::spawn(async move {
+                let result = async {
+                    let shard_id = start_cluster_shard(&mut connection).await?;
+                    let frame = connection.close_frame().await?;
+
+                    Ok::<_, Box<dyn Error>>((shard_id, frame))
+                };
+
+                result.await.map_err(|source| source.to_string())
+            }));
+        }
+
+        Ok::<_, Box<dyn Error>>(handles)
+    };
+    let (_, handles) = tokio::join!(cluster.up(), server);
+    let handles = handles?;
+
+    // Wait for shard 1 to be ready.
+    timeout(Duration::from_secs(10), events.next())
+        .await?
+        .ok_or("event stream ended")?;
+
+    let sessions = cluster
+        .down_with(RawCloseFrame::from((4100, "maintenance")))
+        .await;
+    assert_eq!(1, sessions.len());
+    assert_eq!("def", sessions[&1].session_id);
+
+    for handle in handles {
+        let (_, frame) = handle.await??;
+        let frame = frame.ok_or("close frame missing")?;
+        assert_eq!(CloseCode::Library(4100), frame.code);
+        assert_eq!("maintenance", frame.reason);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cluster_down_resumable() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut sessions = HashMap::new();
+    sessions.insert(
+        0,
+        ResumeSession {
+            session_id: "abc".to_owned(),
+            sequence: 5,
+        },
+    );
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .resume_sessions(sessions)
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 2,
+            total: 3,
+        })
+        .build()
+        .await?;
+    let mut events = cluster.some_events(EventTypeFlags::READY | EventTypeFlags::RESUMED);
+
+    let server = async {
+        let mut handles = Vec::new();
+
+        for _ in 0..3 {
+            let connection = Connection::accept(&listener).await?;
+            handles.push(tokio::spawn(async move {
+                serve_cluster_shard(connection)
+                    .await
+                    .map_err(|source| source.to_string())
+            }));
+        }
+
+        Ok::<_, Box<dyn Error>>(handles)
+    };
+    let (_, handles) = tokio::join!(cluster.up(), server);
+    let handles = handles?;
+
+    // Wait for shard 0 to resume and shard 1 to be ready.
+    for _ in 0..2 {
+        timeout(Duration::from_secs(10), events.next())
+            .await?
+            .ok_or("event stream ended")?;
+    }
+
+    let sessions = cluster.down_resumable().await;
+    assert_eq!(2, sessions.len());
+    assert_eq!("abc", sessions[&0].session_id);
+    assert_eq!(6, sessions[&0].sequence);
+    assert_eq!("def", sessions[&1].session_id);
+    assert_eq!(1, sessions[&1].sequence);
+    assert!(!sessions.contains_key(&2));
+
+    let mut closes = Vec::new();
+
+    for handle in handles {
+        closes.push(handle.await??);
+    }
+
+    closes.sort_unstable_by_key(|(shard_id, _)| *shard_id);
+    assert_eq!(
+        vec![
+            (0, Some(CloseCode::Restart)),
+            (1, Some(CloseCode::Restart)),
+            (2, Some(CloseCode::Restart)),
+        ],
+        closes
+    );
+
+    Ok(())
+}
+
+/// Message Create dispatch with a message ID.
+fn message_create(sequence: u64, id: u64) -> Value {
+    json!({
+        "op": 0,
+        "s": sequence,
+        "t": "MESSAGE_CREATE",
+        "d": {
+            "attachments": [],
+            "author": {
+                "avatar": null,
+                "discriminator": "0001",
+                "id": "3",
+                "username": "user",
+            },
+            "channel_id": "2",
+            "content": "ping",
+            "edited_timestamp": null,
+            "embeds": [],
+            "id": id.to_string(),
+            "mention_everyone": false,
+            "mention_roles": [],
+            "mentions": [],
+            "pinned": false,
+            "timestamp": "2021-01-01T00:00:00.000000+00:00",
+            "tts": false,
+            "type": 0,
+        },
+    })
+}
+
+/// Receive the next event of a cluster stream, returning the ID of the shard
+/// and of the created message.
+async fn next_message(
+    events: &mut (impl Stream<Item = (u64, Event)> + Unpin),
+) -> Result<(u64, u64), Box<dyn Error>> {
+    match timeout(Duration::from_secs(10), events.next()).await? {
+        Some((shard_id, Event::MessageCreate(message))) => Ok((shard_id, message.id.0)),
+        other => Err(format!("unexpected event: {:?}", other).into()),
+    }
+}
+
+#[tokio::test]
+async fn test_cluster_events_shard_ids() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cluster = Cluster::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 1,
+            total: 2,
+        })
+        .build()
+        .await?;
+    let mut all = cluster.some_events(EventTypeFlags::MESSAGE_CREATE);
+    // Shard 5 isn't managed by the cluster, so it's ignored.
+    let mut second = cluster.shard_events(vec![1, 5], EventTypeFlags::MESSAGE_CREATE);
+
+    let server = async {
+        let mut connections = HashMap::new();
+
+        for _ in 0..2 {
+            let mut connection = Connection::accept(&listener).await?;
+            connection
+                .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+                .await?;
+            let identify = connection.receive().await?;
+            let shard_id = identify["d"]["shard"][0].as_u64().ok_or("shard missing")?;
+            connections.insert(shard_id, connection);
+        }
+
+        Ok::<_, Box<dyn Error>>(connections)
+    };
+    let (_, connections) = tokio::join!(cluster.up(), server);
+    let mut connections = connections?;
+
+    // Interleave the messages of the shards, each message being received
+    // before the next is sent so that the order is deterministic.
+    for (shard_id, sequence, message_id) in &[(0, 1, 10), (1, 1, 11), (0, 2, 12), (1, 2, 13)] {
+        let connection = connections.get_mut(shard_id).ok_or("shard not connected")?;
+        connection
+            .send(message_create(*sequence, *message_id))
+            .await?;
+
+        assert_eq!((*shard_id, *message_id), next_message(&mut all).await?);
+    }
+
+    assert_eq!((1, 11), next_message(&mut second).await?);
+    assert_eq!((1, 13), next_message(&mut second).await?);
+    assert!(timeout(Duration::from_millis(100), second.next())
+        .await
+        .is_err());
+
+    cluster.down().await;
+
+    Ok(())
+}
+
+/// Accept the connection of a shard, returning it once the shard identified.
+async fn identified(listener: &TcpListener) -> Result<Connection, Box<dyn Error>> {
+    let mut connection = Connection::accept(listener).await?;
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    connection.receive().await?;
+
+    Ok(connection)
+}
+
+#[tokio::test]
+async fn test_cluster_up_shard() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cluster = Cluster::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 0,
+            total: 2,
+        })
+        .build()
+        .await?;
+    let mut all = cluster.some_events(EventTypeFlags::MESSAGE_CREATE);
+    // Shard 1 isn't managed by the cluster yet.
+    let mut second = cluster.shard_events(vec![1], EventTypeFlags::MESSAGE_CREATE);
+
+    let (_, first) = tokio::join!(cluster.up(), identified(&listener));
+    let mut first = first?;
+    first.send(message_create(1, 10)).await?;
+    assert_eq!((0, 10), next_message(&mut all).await?);
+
+    // The new shard's events are added to the existing streams.
+    let (shard, connection) = tokio::join!(cluster.up_shard(1), identified(&listener));
+    let (shard, mut connection) = (shard?, connection?);
+    assert_eq!([1, 2], shard.config().shard());
+    assert!(cluster.shard(1).is_some());
+
+    connection.send(message_create(1, 11)).await?;
+    assert_eq!((1, 11), next_message(&mut all).await?);
+    assert_eq!((1, 11), next_message(&mut second).await?);
+
+    // Bringing down the first shard leaves the streams working for the new
+    // one.
+    // The connections are dropped once they're closed, so that the shards
+    // don't wait for the close frame to be echoed.
+    let (shard, code) = tokio::join!(
+        cluster.down_shard(0),
+        async move { first.close_code().await }
+    );
+    assert_eq!([0, 2], shard.ok_or("shard missing")?.config().shard());
+    assert_eq!(Some(CloseCode::Normal), code?);
+    assert!(cluster.shard(0).is_none());
+
+    connection.send(message_create(2, 12)).await?;
+    assert_eq!((1, 12), next_message(&mut all).await?);
+    assert_eq!((1, 12), next_message(&mut second).await?);
+
+    let (_, code) = tokio::join!(cluster.down(), async move { connection.close_code().await });
+    assert_eq!(Some(CloseCode::Normal), code?);
+    assert!(timeout(Duration::from_secs(10), all.next())
+        .await?
+        .is_none());
+    assert!(timeout(Duration::from_secs(10), second.next())
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cluster_update_voice_state() -> Result<(), Box<dyn Error>> {
+    use twilight_model::id::{ChannelId, GuildId};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cluster = Cluster::builder(TOKEN, Intents::GUILD_VOICE_STATES)
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 1,
+            total: 2,
+        })
+        .build()
+        .await?;
+
+    let server = async {
+        let mut connections = HashMap::new();
+
+        for _ in 0..2 {
+            let mut connection = Connection::accept(&listener).await?;
+            connection
+                .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+                .await?;
+            let identify = connection.receive().await?;
+            let shard_id = identify["d"]["shard"][0].as_u64().ok_or("shard missing")?;
+            connections.insert(shard_id, connection);
+        }
+
+        Ok::<_, Box<dyn Error>>(connections)
+    };
+    let (_, connections) = tokio::join!(cluster.up(), server);
+    let mut connections = connections?;
+
+    // The guilds are on shards 1 and 0 respectively.
+    cluster
+        .update_voice_state(GuildId(41_771_983_427_338_241), ChannelId(5), false, true)
+        .await?;
+    cluster
+        .update_voice_state(GuildId(41_771_983_423_143_937), None, true, false)
+        .await?;
+
+    let join = connections
+        .get_mut(&1)
+        .ok_or("shard not connected")?
+        .receive()
+        .await?;
+    assert_eq!(
+        json!({
+            "op": 4,
+            "d": {
+                "channel_id": "5",
+                "guild_id": "41771983427338241",
+                "self_deaf": false,
+                "self_mute": true,
+            },
+        }),
+        join
+    );
+
+    // Shard 0 only receives the command for its own guild.
+    let leave = connections
+        .get_mut(&0)
+        .ok_or("shard not connected")?
+        .receive()
+        .await?;
+    assert_eq!(
+        json!({
+            "op": 4,
+            "d": {
+                "channel_id": null,
+                "guild_id": "41771983423143937",
+                "self_deaf": true,
+                "self_mute": false,
+            },
+        }),
+        leave
+    );
+
+    cluster.down().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_first_heartbeat_jittered() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .gateway_url(Some(url))
+        .heartbeat_jitter(0.01)
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::MESSAGE_CREATE);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 45_000 } }))
+        .await?;
+    let hello = Instant::now();
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    // A dispatch arriving before the first heartbeat is tracked by it.
+    connection.send(message_create(1, 10)).await?;
+    let event = timeout(Duration::from_secs(10), events.next()).await?;
+    assert!(
+        matches!(event, Some(Event::MessageCreate(_))),
+        "unexpected event: {:?}",
+        event
+    );
+
+    // The first heartbeat is sent after 1% of the interval instead of a
+    // whole one.
+    assert_eq!(1, connection.heartbeat().await?);
+    let elapsed = hello.elapsed();
+    assert!(elapsed >= Duration::from_millis(450), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_event_types() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .event_types(EventTypeFlags::MESSAGE_CREATE)?
+        .gateway_url(Some(url))
+        .heartbeat_jitter(1.0)
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events =
+        shard.some_events(EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::TYPING_START);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 100 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 1,
+            "t": "TYPING_START",
+            "d": {
+                "channel_id": "2",
+                "timestamp": 1_600_000_000,
+                "user_id": "3",
+            },
+        }))
+        .await?;
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 2,
+            "t": "MESSAGE_CREATE",
+            "d": {
+                "attachments": [],
+                "author": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": "3",
+                    "username": "user",
+                },
+                "channel_id": "2",
+                "content": "ping",
+                "edited_timestamp": null,
+                "embeds": [],
+                "id": "4",
+                "mention_everyone": false,
+                "mention_roles": [],
+                "mentions": [],
+                "pinned": false,
+                "timestamp": "2021-01-01T00:00:00.000000+00:00",
+                "tts": false,
+                "type": 0,
+            },
+        }))
+        .await?;
+
+    // The typing start is dropped, so the message is the first event.
+    let event = timeout(Duration::from_secs(10), events.next()).await?;
+    assert!(
+        matches!(event, Some(Event::MessageCreate(_))),
+        "unexpected event: {:?}",
+        event
+    );
+
+    // The shard keeps heartbeating with the sequence of the latest event.
+    assert_eq!(2, connection.heartbeat().await?);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_payload() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    // Message Create events aren't emitted, but their payloads still are.
+    let mut shard = Shard::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .event_types(EventTypeFlags::SHARD_PAYLOAD)?
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::SHARD_PAYLOAD);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    let hello = json!({ "op": 10, "d": { "heartbeat_interval": 41_250 } });
+    connection.send(hello.clone()).await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    let message = message_create(1, 4);
+    connection.send(message.clone()).await?;
+
+    for payload in [hello, message].iter() {
+        match timeout(Duration::from_secs(10), events.next()).await? {
+            Some(Event::ShardPayload(received)) => {
+                assert_eq!(serde_json::to_vec(payload)?, received.bytes);
+            }
+            other => return Err(format!("unexpected event: {:?}", other).into()),
+        }
+    }
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_identify_presence() -> Result<(), Box<dyn Error>> {
+    use twilight_model::gateway::{
+        payload::update_status::UpdateStatusInfo,
+        presence::{Activity, ActivityType, Status},
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let activity = Activity {
+        application_id: None,
+        assets: None,
+        created_at: None,
+        details: None,
+        emoji: None,
+        flags: None,
+        id: None,
+        instance: None,
+        kind: ActivityType::Watching,
+        name: "the gateway".to_owned(),
+        party: None,
+        secrets: None,
+        state: None,
+        timestamps: None,
+        url: None,
+    };
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .presence(UpdateStatusInfo::new(
+            vec![activity],
+            false,
+            None,
+            Status::DoNotDisturb,
+        ))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    // The presence is sent again when identifying after the session is
+    // invalidated.
+    for _ in 0..2_u8 {
+        connection
+            .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+            .await?;
+        let identify = connection.receive().await?;
+        assert_eq!(2, identify["op"]);
+        assert_eq!(
+            json!({
+                "activities": [{ "created_at": null, "name": "the gateway", "type": 3 }],
+                "afk": false,
+                "since": null,
+                "status": "dnd",
+            }),
+            identify["d"]["presence"]
+        );
+
+        connection.send(json!({ "op": 9, "d": false })).await?;
+        connection = Connection::accept(&listener).await?;
+    }
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Receive the next event emitted by the shard.
+async fn next_event(events: &mut Events) -> Result<Event, Box<dyn Error>> {
+    Ok(timeout(Duration::from_secs(10), events.next())
+        .await?
+        .ok_or("shard stopped")?)
+}
+
+#[tokio::test]
+async fn test_shard_lifecycle() -> Result<(), Box<dyn Error>> {
+    use twilight_gateway::shard::Stage;
+    use twilight_model::gateway::event::EventType;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(
+        EventTypeFlags::SHARD_CONNECTED
+            | EventTypeFlags::SHARD_CONNECTING
+            | EventTypeFlags::SHARD_DISCONNECTED
+            | EventTypeFlags::SHARD_IDENTIFYING
+            | EventTypeFlags::SHARD_RECONNECTING
+            | EventTypeFlags::SHARD_RESUMING,
+    );
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+    assert_eq!(
+        EventType::ShardConnecting,
+        next_event(&mut events).await?.kind()
+    );
+    assert_eq!(Stage::Handshaking, shard.info()?.stage());
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    assert_eq!(
+        EventType::ShardIdentifying,
+        next_event(&mut events).await?.kind()
+    );
+    assert_eq!(Stage::Identifying, shard.info()?.stage());
+
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 1,
+            "t": "READY",
+            "d": {
+                "guilds": [],
+                "session_id": "abc",
+                "shard": [0, 1],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": "1",
+                    "mfa_enabled": false,
+                    "username": "bot",
+                },
+                "v": 8,
+            },
+        }))
+        .await?;
+    assert_eq!(
+        EventType::ShardConnected,
+        next_event(&mut events).await?.kind()
+    );
+    assert_eq!(Stage::Connected, shard.info()?.stage());
+
+    // Closing the connection makes the shard resume on a new one.
+    connection
+        .stream
+        .send(Message::Close(Some(CloseFrame {
+            code: CloseCode::Library(4000),
+            reason: "unknown error".into(),
+        })))
+        .await?;
+    let event = next_event(&mut events).await?;
+    assert!(
+        matches!(
+            &event,
+            Event::ShardDisconnected(Disconnected {
+                code: Some(4000),
+                reason: Some(reason),
+                shard_id: 0,
+            }) if reason == "unknown error"
+        ),
+        "unexpected event: {:?}",
+        event
+    );
+    assert_eq!(
+        EventType::ShardResuming,
+        next_event(&mut events).await?.kind()
+    );
+
+    let mut connection = Connection::accept(&listener).await?;
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(6, connection.receive().await?["op"]);
+    assert_eq!(Stage::Resuming, shard.info()?.stage());
+
+    connection
+        .send(json!({ "op": 0, "s": 2, "t": "RESUMED", "d": {} }))
+        .await?;
+    assert_eq!(
+        EventType::ShardConnected,
+        next_event(&mut events).await?.kind()
+    );
+    assert_eq!(Stage::Connected, shard.info()?.stage());
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Accept connections from the shard and drop them before the websocket
+/// handshake, returning when each was accepted.
+async fn refuse(listener: &TcpListener, count: usize) -> Result<Vec<Instant>, Box<dyn Error>> {
+    let mut accepted = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let (stream, _) = timeout(Duration::from_secs(10), listener.accept()).await??;
+        accepted.push(Instant::now());
+        drop(stream);
+    }
+
+    Ok(accepted)
+}
+
+/// Start a shard with a reconnect policy and invalidate its session, making it
+/// reconnect.
+async fn invalidated_shard(
+    listener: &TcpListener,
+    reconnect_policy: ReconnectPolicy,
+) -> Result<(Shard, Instant), Box<dyn Error>> {
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .reconnect_policy(reconnect_policy)
+        .build();
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection.send(json!({ "op": 9, "d": false })).await?;
+
+    Ok((shard, Instant::now()))
+}
+
+#[tokio::test]
+async fn test_shard_reconnect_policy() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, invalidated) = invalidated_shard(
+        &listener,
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(100),
+            jitter: 0.0,
+            max_attempts: None,
+            max_delay: Duration::from_millis(300),
+            multiplier: 2.0,
+        },
+    )
+    .await?;
+
+    // The first two attempts fail, and the third one succeeds.
+    let refused = refuse(&listener, 2).await?;
+    let mut connection = Connection::accept(&listener).await?;
+    let accepted = Instant::now();
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    let delays = [
+        refused[0] - invalidated,
+        refused[1] - refused[0],
+        accepted - refused[1],
+    ];
+    assert!(delays[0] >= Duration::from_millis(100), "{:?}", delays);
+    assert!(delays[1] >= Duration::from_millis(200), "{:?}", delays);
+    // The delay is capped instead of doubling to 400ms.
+    assert!(delays[2] >= Duration::from_millis(300), "{:?}", delays);
+    assert!(delays[2] < Duration::from_millis(400), "{:?}", delays);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_reconnect_policy_max_attempts() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, _) = invalidated_shard(
+        &listener,
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            jitter: 0.0,
+            max_attempts: Some(3),
+            max_delay: Duration::from_millis(10),
+            multiplier: 1.0,
+        },
+    )
+    .await?;
+    let mut events = shard.events();
+
+    refuse(&listener, 3).await?;
+
+    // The shard stops once its attempts are used up, ending its events.
+    let remaining = remaining_events(&mut events).await?;
+    assert!(
+        matches!(
+            remaining.last(),
+            Some(Event::ShardStopped(Stopped {
+                reason: StopReason::ReconnectsExhausted,
+                shard_id: 0,
+            }))
+        ),
+        "unexpected events: {:?}",
+        remaining
+    );
+    assert!(timeout(Duration::from_millis(100), listener.accept())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+/// Close the connection of a ready shard with a code, returning the events
+/// emitted until the shard stops.
+///
+/// The shard must stop instead of reconnecting.
+async fn closed_shard(code: u16) -> Result<Vec<Event>, Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+    let mut events = shard.some_events(
+        EventTypeFlags::SHARD_DISCONNECTED
+            | EventTypeFlags::SHARD_RECONNECTING
+            | EventTypeFlags::SHARD_RESUMING
+            | EventTypeFlags::SHARD_STOPPED,
+    );
+
+    connection
+        .stream
+        .send(Message::Close(Some(CloseFrame {
+            code: CloseCode::Library(code),
+            reason: "fatal".into(),
+        })))
+        .await?;
+    let remaining = remaining_events(&mut events).await?;
+    assert!(timeout(Duration::from_millis(100), listener.accept())
+        .await
+        .is_err());
+    assert_eq!(Stage::Disconnected, shard.info()?.stage());
+
+    Ok(remaining)
+}
+
+#[tokio::test]
+async fn test_shard_close_authorization_invalid() -> Result<(), Box<dyn Error>> {
+    let events = closed_shard(4004).await?;
+
+    assert!(
+        matches!(
+            events.as_slice(),
+            [
+                Event::ShardDisconnected(Disconnected {
+                    code: Some(4004),
+                    ..
+                }),
+                Event::ShardStopped(Stopped {
+                    reason: StopReason::AuthorizationInvalid,
+                    shard_id: 0,
+                }),
+            ]
+        ),
+        "unexpected events: {:?}",
+        events
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_close_intents_invalid() -> Result<(), Box<dyn Error>> {
+    let events = closed_shard(4013).await?;
+
+    assert!(
+        matches!(
+            events.as_slice(),
+            [
+                Event::ShardDisconnected(Disconnected {
+                    code: Some(4013),
+                    ..
+                }),
+                Event::ShardStopped(Stopped {
+                    reason: StopReason::IntentsInvalid,
+                    shard_id: 0,
+                }),
+            ]
+        ),
+        "unexpected events: {:?}",
+        events
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_close_intents_disallowed() -> Result<(), Box<dyn Error>> {
+    let events = closed_shard(4014).await?;
+
+    assert!(
+        matches!(
+            events.as_slice(),
+            [
+                Event::ShardDisconnected(Disconnected {
+                    code: Some(4014),
+                    reason: Some(reason),
+                    shard_id: 0,
+                }),
+                Event::ShardStopped(Stopped {
+                    reason: StopReason::IntentsDisallowed,
+                    shard_id: 0,
+                }),
+            ] if reason == "fatal"
+        ),
+        "unexpected events: {:?}",
+        events
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_session_start_limit_fatal_start() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(BudgetQueue(AtomicU64::new(0)))))
+        .session_start_limit_fatal(true)
+        .build();
+
+    // The budget is checked before connecting.
+    let result = shard.start().await;
+    assert!(
+        matches!(
+            result,
+            Err(ShardStartError::SessionStartLimitExhausted { reset_after })
+                if reset_after > 50_000 && reset_after <= 60_000
+        ),
+        "unexpected result: {:?}",
+        result
+    );
+    assert!(timeout(Duration::from_millis(100), listener.accept())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_session_start_limit_fatal_reconnect() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(BudgetQueue(AtomicU64::new(1)))))
+        .reconnect_policy(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            ..ReconnectPolicy::default()
+        })
+        .session_start_limit_fatal(true)
+        .build();
+    let mut events = shard.some_events(
+        EventTypeFlags::SHARD_RECONNECTING
+            | EventTypeFlags::SHARD_IDENTIFYING
+            | EventTypeFlags::SHARD_STOPPED,
+    );
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    // The only identify of the budget is used up, so the shard stops instead
+    // of reconnecting to identify again.
+    connection.send(json!({ "op": 9, "d": false })).await?;
+    let remaining = remaining_events(&mut events).await?;
+    assert!(
+        matches!(
+            remaining.as_slice(),
+            [
+                Event::ShardIdentifying(_),
+                Event::ShardStopped(Stopped {
+                    reason: StopReason::SessionStartLimitExhausted { .. },
+                    shard_id: 0,
+                }),
+            ]
+        ),
+        "unexpected events: {:?}",
+        remaining
+    );
+    assert!(timeout(Duration::from_millis(100), listener.accept())
+        .await
+        .is_err());
+    assert_eq!(Stage::Disconnected, shard.info()?.stage());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_queue_permit() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+    let permits = Arc::new(Semaphore::new(0));
+    let (requests_tx, mut requests) = mpsc::unbounded_channel();
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(PermitQueue {
+            permits: Arc::clone(&permits),
+            requests: requests_tx,
+        })))
+        .reconnect_policy(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            ..ReconnectPolicy::default()
+        })
+        .build();
+
+    // The shard doesn't connect, let alone identify, until it's given a
+    // permit.
+    let server = async {
+        assert_eq!(Some([0, 1]), requests.recv().await);
+        assert!(timeout(Duration::from_millis(200), listener.accept())
+            .await
+            .is_err());
+        permits.add_permits(1);
+
+        Connection::accept(&listener).await
+    };
+    let (started, connection) = tokio::join!(shard.start(), server);
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    // Identifying again after the session is invalidated waits for another
+    // permit.
+    connection.send(json!({ "op": 9, "d": false })).await?;
+    assert_eq!(
+        Some([0, 1]),
+        timeout(Duration::from_secs(10), requests.recv()).await?
+    );
+    assert!(timeout(Duration::from_millis(200), listener.accept())
+        .await
+        .is_err());
+    permits.add_permits(1);
+
+    let mut connection = Connection::accept(&listener).await?;
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Query parameters appended to the gateway URL under the default
+/// configuration.
+fn default_query() -> &'static str {
+    if cfg!(feature = "compression") {
+        "v=8&compress=zlib-stream"
+    } else {
+        "v=8"
+    }
+}
+
+/// Start a shard connecting to a gateway URL, returning the URL it reported
+/// dialing and the result of starting.
+async fn dial(
+    url: &str,
+    gateway_url_query: bool,
+) -> (
+    Result<String, Box<dyn Error>>,
+    Shard,
+    Result<(), ShardStartError>,
+) {
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url.to_owned()))
+        .gateway_url_query(gateway_url_query)
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::SHARD_CONNECTING);
+    let started = shard.start().await;
+
+    let dialed = match next_event(&mut events).await {
+        Ok(Event::ShardConnecting(connecting)) => Ok(connecting.gateway),
+        Ok(other) => Err(format!("unexpected event: {:?}", other).into()),
+        Err(source) => Err(source),
+    };
+
+    (dialed, shard, started)
+}
+
+#[tokio::test]
+async fn test_shard_gateway_url_ws() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/proxy?region=eu", listener.local_addr()?);
+
+    let ((dialed, shard, started), connection) =
+        tokio::join!(dial(&url, true), Connection::accept(&listener));
+    started?;
+    let connection = connection?;
+    assert_eq!(format!("{}&{}", url, default_query()), dialed?);
+    assert_eq!("/proxy", connection.path);
+    assert_eq!(format!("region=eu&{}", default_query()), connection.query);
+    shard.shutdown();
+
+    // The URL is dialed as-is without the query parameters.
+    let ((dialed, shard, started), connection) =
+        tokio::join!(dial(&url, false), Connection::accept(&listener));
+    started?;
+    let connection = connection?;
+    assert_eq!(url, dialed?);
+    assert_eq!("/proxy", connection.path);
+    assert_eq!("region=eu", connection.query);
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_gateway_url_wss() -> Result<(), Box<dyn Error>> {
+    // Nothing listens on the port, so connecting fails after dialing.
+    let url = "wss://127.0.0.1:1/gateway";
+
+    let (dialed, _, started) = dial(url, true).await;
+    assert_eq!(format!("{}?{}", url, default_query()), dialed?);
+    assert!(matches!(started, Err(ShardStartError::Establishing { .. })));
+
+    let (dialed, _, _) = dial(url, false).await;
+    assert_eq!(url, dialed?);
+
+    Ok(())
+}
+
+/// Ready dispatch for a session of shard 0.
+fn ready(session_id: &str, sequence: u64) -> Value {
+    json!({
+        "op": 0,
+        "s": sequence,
+        "t": "READY",
+        "d": {
+            "guilds": [],
+            "session_id": session_id,
+            "shard": [0, 1],
+            "user": {
+                "avatar": null,
+                "discriminator": "0001",
+                "id": "1",
+                "mfa_enabled": false,
+                "username": "bot",
+            },
+            "v": 8,
+        },
+    })
+}
+
+/// Start a shard and make it ready, returning it along with its connection.
+async fn ready_shard(listener: &TcpListener) -> Result<(Shard, Connection), Box<dyn Error>> {
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .reconnect_policy(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            ..ReconnectPolicy::default()
+        })
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::READY);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection.send(ready("abc", 3)).await?;
+    assert!(matches!(next_event(&mut events).await?, Event::Ready(_)));
+
+    Ok((shard, connection))
+}
+
+#[tokio::test]
+async fn test_shard_invalid_session_resumable() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+
+    connection.send(json!({ "op": 9, "d": true })).await?;
+    let mut connection = Connection::accept(&listener).await?;
+
+    // The session is kept while resuming.
+    let session = shard.session().ok_or("session missing")?;
+    assert_eq!("abc", session.session_id);
+    assert_eq!(3, session.sequence);
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let resume = connection.receive().await?;
+    assert_eq!(6, resume["op"]);
+    assert_eq!("abc", resume["d"]["session_id"]);
+    assert_eq!(3, resume["d"]["seq"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_invalid_session_not_resumable() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+
+    connection.send(json!({ "op": 9, "d": false })).await?;
+    let invalidated = Instant::now();
+    let mut connection = Connection::accept(&listener).await?;
+
+    // The shard waits 1 to 5 seconds, plus the reconnect policy's delay.
+    let waited = invalidated.elapsed();
+    assert!(waited >= Duration::from_secs(1), "{:?}", waited);
+    assert!(waited < Duration::from_secs(6), "{:?}", waited);
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    // The new session has yet to be given an ID.
+    assert!(shard.session().is_none());
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_info_session() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+    let mut events = shard.some_events(EventTypeFlags::MESSAGE_CREATE);
+
+    let info = shard.info()?;
+    assert_eq!(Some("abc"), info.session_id());
+    assert_eq!(3, info.seq());
+
+    for sequence in 4..7 {
+        connection.send(message_create(sequence, sequence)).await?;
+        assert!(matches!(
+            next_event(&mut events).await?,
+            Event::MessageCreate(_)
+        ));
+        assert_eq!(sequence, shard.info()?.seq());
+    }
+
+    // The session ID is cleared as soon as the session is invalidated, before
+    // the shard waits to identify again.
+    connection.send(json!({ "op": 9, "d": false })).await?;
+    timeout(Duration::from_secs(1), async {
+        while shard.info()?.session_id().is_some() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        Ok::<_, Box<dyn Error>>(())
+    })
+    .await??;
+    assert!(shard.session().is_none());
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Member Chunk dispatch for guild 1 with a member and a user not found.
+fn member_chunk(nonce: &str, index: u32, count: u32, user_id: u64) -> Value {
+    json!({
+        "op": 0,
+        "s": 4,
+        "t": "GUILD_MEMBERS_CHUNK",
+        "d": {
+            "chunk_count": count,
+            "chunk_index": index,
+            "guild_id": "1",
+            "members": [{
+                "deaf": false,
+                "hoisted_role": null,
+                "joined_at": "2021-01-01T00:00:00.000000+00:00",
+                "mute": false,
+                "roles": [],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": user_id.to_string(),
+                    "username": "user",
+                },
+            }],
+            "nonce": nonce,
+            "not_found": [(user_id + 100).to_string()],
+        },
+    })
+}
+
+#[tokio::test]
+async fn test_shard_request_members() -> Result<(), Box<dyn Error>> {
+    use twilight_gateway::shard::RequestMembersError;
+    use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+
+    let request = RequestGuildMembers::builder(GuildId(1)).query("", None);
+    let server = async {
+        let request = connection.receive().await?;
+        assert_eq!(8, request["op"]);
+        let nonce = request["d"]["nonce"].as_str().ok_or("nonce missing")?;
+
+        // Chunks of other requests are ignored, and the chunks of the request
+        // are collected in order.
+        connection.send(member_chunk("other", 0, 1, 20)).await?;
+        for index in [2, 0, 1].iter().copied() {
+            connection
+                .send(member_chunk(nonce, index, 3, 10 + u64::from(index)))
+                .await?;
+        }
+
+        Ok::<_, Box<dyn Error>>(())
+    };
+    let (members, served) = tokio::join!(
+        shard.request_members(request, Duration::from_secs(10)),
+        server
+    );
+    served?;
+    let members = members?;
+
+    assert_eq!(
+        vec![10, 11, 12],
+        members
+            .members
+            .iter()
+            .map(|member| member.user.id.0)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![110, 111, 112],
+        members.not_found.iter().map(|id| id.0).collect::<Vec<_>>()
+    );
+
+    // The request times out if not all chunks are received, and a nonce
+    // provided with the request is kept.
+    let request = RequestGuildMembers::builder(GuildId(1))
+        .nonce("abc")
+        .query("", None);
+    let server = async {
+        let request = connection.receive().await?;
+        assert_eq!("abc", request["d"]["nonce"]);
+        connection.send(member_chunk("abc", 1, 2, 10)).await?;
+
+        Ok::<_, Box<dyn Error>>(())
+    };
+    let (members, served) = tokio::join!(
+        shard.request_members(request, Duration::from_millis(500)),
+        server
+    );
+    served?;
+    assert!(
+        matches!(members, Err(RequestMembersError::TimedOut { received: 1 })),
+        "{:?}",
+        members
+    );
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_zombied_connection() -> Result<(), Box<dyn Error>> {
+    const INTERVAL: Duration = Duration::from_millis(200);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .heartbeat_jitter(1.0)
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::READY | EventTypeFlags::SHARD_DISCONNECTED);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 200 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection.send(ready("abc", 1)).await?;
+    assert!(matches!(next_event(&mut events).await?, Event::Ready(_)));
+    let readied = Instant::now();
+
+    // The heartbeat isn't acknowledged, so the connection is closed once the
+    // next heartbeat is due and the session is resumed.
+    assert_eq!(1, connection.heartbeat().await?);
+    assert_eq!(Some(CloseCode::from(4000)), connection.close_code().await?);
+    let mut connection = Connection::accept(&listener).await?;
+    assert!(readied.elapsed() < INTERVAL * 2 + INTERVAL / 2);
+
+    let event = next_event(&mut events).await?;
+    assert!(
+        matches!(
+            &event,
+            Event::ShardDisconnected(Disconnected {
+                code: Some(4000),
+                reason: Some(reason),
+                shard_id: 0,
+            }) if reason == "Zombied connection"
+        ),
+        "unexpected event: {:?}",
+        event
+    );
+    assert_eq!(1, shard.info()?.zombied_connections());
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let resume = connection.receive().await?;
+    assert_eq!(6, resume["op"]);
+    assert_eq!("abc", resume["d"]["session_id"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Start a shard buffering at most 16 events per event stream, and send it 64
+/// messages while its event stream isn't polled.
+async fn flooded_shard(
+    listener: &TcpListener,
+    overflow: EventStreamOverflow,
+) -> Result<(Shard, Events, Connection), Box<dyn Error>> {
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .event_stream_capacity(16)
+        .event_stream_overflow(overflow)
+        .event_types(EventTypeFlags::MESSAGE_CREATE)?
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let events = shard.events();
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 200 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    for id in 1..=64 {
+        connection.send(message_create(id, id)).await?;
+    }
+
+    Ok((shard, events, connection))
+}
+
+/// Receive the IDs of the messages of the next events.
+async fn message_ids(events: &mut Events, count: usize) -> Result<Vec<u64>, Box<dyn Error>> {
+    let mut ids = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        match next_event(events).await? {
+            Event::MessageCreate(message) => ids.push(message.id.0),
+            other => return Err(format!("unexpected event: {:?}", other).into()),
+        }
+    }
+
+    Ok(ids)
+}
+
+#[tokio::test]
+async fn test_shard_event_stream_backpressure() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut events, mut connection) =
+        flooded_shard(&listener, EventStreamOverflow::Backpressure).await?;
+
+    // The shard stops reading once the stream is full, yet keeps heartbeating
+    // without closing the connection as zombied although heartbeats aren't
+    // acknowledged.
+    connection.heartbeat().await?;
+    assert_eq!(16, connection.heartbeat().await?);
+    assert_eq!(16, shard.info()?.seq());
+
+    // Every event is received once the stream is polled.
+    assert_eq!(
+        (1..=64).collect::<Vec<_>>(),
+        message_ids(&mut events, 64).await?
+    );
+    assert_eq!(0, shard.info()?.events_dropped());
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_event_stream_drop_oldest() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut events, mut connection) =
+        flooded_shard(&listener, EventStreamOverflow::DropOldest).await?;
+
+    // The shard keeps reading, dropping the oldest events of the full stream.
+    connection.heartbeat().await?;
+    connection.send(json!({ "op": 11 })).await?;
+    assert_eq!(64, connection.heartbeat().await?);
+    assert_eq!(48, shard.info()?.events_dropped());
+
+    assert_eq!(
+        (49..=64).collect::<Vec<_>>(),
+        message_ids(&mut events, 16).await?
+    );
+
+    connection.send(message_create(65, 65)).await?;
+    assert_eq!(vec![65], message_ids(&mut events, 1).await?);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_send_raw() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let payload = json!({
+        "op": 8,
+        "d": {
+            "guild_id": "1",
+            "limit": 0,
+            "nonce": "raw",
+            "query": "",
+        },
+    });
+
+    // Raw messages can't be sent until the shard is started.
+    let result = shard.send(RawMessage::Text(payload.to_string())).await;
+    assert!(matches!(result, Err(SendError::SessionInactive { .. })));
+
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    shard.send(RawMessage::Text(payload.to_string())).await?;
+    assert_eq!(payload, connection.receive().await?);
+
+    // Raw messages go through the command ratelimiter, unlike the identify.
+    let (available, max) = shard.info()?.command_ratelimit().unwrap();
+    assert_eq!(max - 1, available);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Receive the remaining events of a stream until it ends.
+async fn remaining_events(events: &mut Events) -> Result<Vec<Event>, Box<dyn Error>> {
+    let mut remaining = Vec::new();
+
+    while let Some(event) = timeout(Duration::from_secs(10), events.next()).await? {
+        remaining.push(event);
+    }
+
+    Ok(remaining)
+}
+
+#[tokio::test]
+async fn test_shard_shutdown() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.events();
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+
+    shard.shutdown();
+    shard.shutdown();
+    assert_eq!(Some(CloseCode::Normal), connection.close_code().await?);
+    assert_eq!(Stage::Disconnected, shard.info()?.stage());
+
+    // Events received before shutting down are still yielded before the
+    // stream ends.
+    let remaining = remaining_events(&mut events).await?;
+    assert!(
+        matches!(remaining.first(), Some(Event::ShardConnecting(_))),
+        "unexpected events: {:?}",
+        remaining
+    );
+
+    Ok(())
+}
+
+/// Event types and payload lengths of the dispatches passed to a dead letter
+/// callback.
+type DeadLetters = Arc<Mutex<Vec<(String, usize)>>>;
+
+/// Start a ready shard with a policy for malformed dispatches, and send it a
+/// Message Create without a valid ID followed by a valid one.
+async fn malformed_dispatch_shard(
+    listener: &TcpListener,
+    policy: MalformedDispatchPolicy,
+) -> Result<(Shard, Connection, Events, DeadLetters), Box<dyn Error>> {
+    let url = format!("ws://{}", listener.local_addr()?);
+    let dead_letters = DeadLetters::default();
+    let dead_letters_clone = Arc::clone(&dead_letters);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .malformed_dispatch_policy(policy)
+        .dead_letter(move |dispatch| {
+            dead_letters_clone.lock().unwrap().push((
+                dispatch.event_type().unwrap_or_default().to_owned(),
+                dispatch.payload().len(),
+            ));
+        })
+        .reconnect_policy(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            ..ReconnectPolicy::default()
+        })
+        .build();
+    let mut events = shard.some_events(
+        EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::READY | EventTypeFlags::SHARD_RECONNECTING,
+    );
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection.send(ready("abc", 1)).await?;
+    assert!(matches!(next_event(&mut events).await?, Event::Ready(_)));
+
+    let mut malformed = message_create(2, 4);
+    malformed["d"]["id"] = json!("not a snowflake");
+    connection.send(malformed).await?;
+    connection.send(message_create(3, 5)).await?;
+
+    Ok((shard, connection, events, dead_letters))
+}
+
+#[tokio::test]
+async fn test_shard_malformed_dispatch_skip() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, _connection, mut events, dead_letters) =
+        malformed_dispatch_shard(&listener, MalformedDispatchPolicy::Skip).await?;
+
+    // The stream continues with the next event.
+    match next_event(&mut events).await? {
+        Event::MessageCreate(message) => assert_eq!(5, message.id.0),
+        other => return Err(format!("unexpected event: {:?}", other).into()),
+    }
+
+    let info = shard.info()?;
+    assert_eq!(1, info.skipped_dispatches());
+    assert_eq!(3, info.seq());
+    assert_eq!(Stage::Connected, info.stage());
+
+    let dead_letters = dead_letters.lock().unwrap().clone();
+    assert_eq!(1, dead_letters.len());
+    assert_eq!("MESSAGE_CREATE", dead_letters[0].0);
+    assert!(dead_letters[0].1 > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_malformed_dispatch_fail_fast() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, _connection, mut events, dead_letters) =
+        malformed_dispatch_shard(&listener, MalformedDispatchPolicy::FailFast).await?;
+
+    // The shard reconnects instead of emitting the next event.
+    let (reconnecting, connection) = tokio::join!(
+        next_event(&mut events),
+        timeout(Duration::from_secs(10), Connection::accept(&listener)),
+    );
+    assert!(matches!(reconnecting?, Event::ShardReconnecting(_)));
+    connection??;
+
+    assert_eq!(0, shard.info()?.skipped_dispatches());
+    assert_eq!(1, dead_letters.lock().unwrap().len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_shutdown_with() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+
+    let (shard_id, session) = shard.shutdown_with(RawCloseFrame::from((4100, "maintenance")));
+    assert_eq!(0, shard_id);
+    let session = session.ok_or("session missing")?;
+    assert_eq!("abc", session.session_id);
+    assert_eq!(3, session.sequence);
+
+    let frame = connection
+        .close_frame()
+        .await?
+        .ok_or("close frame missing")?;
+    assert_eq!(CloseCode::Library(4100), frame.code);
+    assert_eq!("maintenance", frame.reason);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let (shard, mut connection) = ready_shard(&listener).await?;
+
+    let (_, session) = shard.shutdown_with(RawCloseFrame::from((1001, "going away")));
+    assert!(session.is_none());
+    assert_eq!(Some(CloseCode::Away), connection.close_code().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_shutdown_resumable() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::READY);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 1,
+            "t": "READY",
+            "d": {
+                "guilds": [],
+                "session_id": "abc",
+                "shard": [0, 1],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": "1",
+                    "mfa_enabled": false,
+                    "username": "bot",
+                },
+                "v": 8,
+            },
+        }))
+        .await?;
+    assert!(matches!(next_event(&mut events).await?, Event::Ready(_)));
+
+    let (shard_id, session) = shard.shutdown_resumable();
+    assert_eq!(0, shard_id);
+    let session = session.ok_or("session missing")?;
+    assert_eq!("abc", session.session_id);
+    assert_eq!(1, session.sequence);
+
+    // Shutting down again returns the same session without closing again.
+    let (_, again) = shard.shutdown_resumable();
+    let again = again.ok_or("session missing")?;
+    assert_eq!(session.session_id, again.session_id);
+    assert_eq!(session.sequence, again.sequence);
+    assert_eq!(Some(CloseCode::Restart), connection.close_code().await?);
+
+    assert!(remaining_events(&mut events).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cluster_down() -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let cluster = Cluster::builder(TOKEN, Intents::empty())
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard_scheme(ShardScheme::Range {
+            from: 0,
+            to: 1,
+            total: 2,
+        })
+        .build()
+        .await?;
+    let mut events = cluster.events();
+
+    let server = async {
+        let mut handles = Vec::new();
+
+        for _ in 0..2 {
+            let mut connection = Connection::accept(&listener).await?;
+            connection
+                .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+                .await?;
+            connection.receive().await?;
+
+            handles.push(tokio::spawn(async move {
+                connection
+                    .close_code()
+                    .await
+                    .map_err(|source| source.to_string())
+            }));
+        }
+
+        Ok::<_, Box<dyn Error>>(handles)
+    };
+    let (_, handles) = tokio::join!(cluster.up(), server);
+    let handles = handles?;
+
+    // Every shard is shut down, and their connections close well before the
+    // timeout.
+    let start = Instant::now();
+    cluster.down().await;
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    for handle in handles {
+        assert_eq!(Some(CloseCode::Normal), handle.await??);
+    }
+
+    while timeout(Duration::from_secs(10), events.next())
+        .await?
+        .is_some()
+    {}
+
+    Ok(())
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_shard_transport_compression() -> Result<(), Box<dyn Error>> {
+    for compression in [true, false].iter().copied() {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?);
+
+        let mut shard = Shard::builder(TOKEN, Intents::empty())
+            .compression(compression)
+            .gateway_url(Some(url))
+            .queue(Arc::new(Box::new(NoopQueue)))
+            .build();
+        let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+        started?;
+        let mut connection = connection?;
+        assert_eq!(
+            compression,
+            connection.query.contains("compress=zlib-stream")
+        );
+
+        connection
+            .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+            .await?;
+        assert_eq!(2, connection.receive().await?["op"]);
+
+        let info = shard.info()?;
+        assert_eq!(compression, info.compressed_bytes() > 0);
+        assert_eq!(compression, info.decompressed_bytes() > 0);
+        assert!(info.inflater_capacity() > 0);
+
+        shard.shutdown();
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "etf")]
+#[tokio::test]
+async fn test_shard_etf() -> Result<(), Box<dyn Error>> {
+    use twilight_model::id::{MessageId, UserId};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::empty())
+        .etf(true)
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::READY | EventTypeFlags::MESSAGE_CREATE);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+    assert!(connection.query.contains("encoding=etf"));
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    let identify = serde_json::from_value::<Vec<u8>>(connection.receive().await?["etf"].take())?;
+    assert!(identify
+        .windows(TOKEN.len())
+        .any(|window| window == TOKEN.as_bytes()));
+
+    // Snowflakes are sent as integers.
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 1,
+            "t": "READY",
+            "d": {
+                "guilds": [],
+                "session_id": "abc",
+                "shard": [0, 1],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": 100_000_000_000_000_000_u64,
+                    "mfa_enabled": false,
+                    "username": "bot",
+                },
+                "v": 8,
+            },
+        }))
+        .await?;
+    connection
+        .send(json!({
+            "op": 0,
+            "s": 2,
+            "t": "MESSAGE_CREATE",
+            "d": {
+                "attachments": [],
+                "author": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": 300_000_000_000_000_000_u64,
+                    "username": "user",
+                },
+                "channel_id": 400_000_000_000_000_000_u64,
+                "content": "ping",
+                "edited_timestamp": null,
+                "embeds": [],
+                "id": 500_000_000_000_000_000_u64,
+                "mention_everyone": false,
+                "mention_roles": [],
+                "mentions": [],
+                "pinned": false,
+                "timestamp": "2021-01-01T00:00:00.000000+00:00",
+                "tts": false,
+                "type": 0,
+            },
+        }))
+        .await?;
+
+    match timeout(Duration::from_secs(10), events.next()).await? {
+        Some(Event::Ready(ready)) => {
+            assert_eq!("abc", ready.session_id);
+            assert_eq!(UserId(100_000_000_000_000_000), ready.user.id);
+        }
+        other => panic!("not ready: {:?}", other),
+    }
+
+    match timeout(Duration::from_secs(10), events.next()).await? {
+        Some(Event::MessageCreate(message)) => {
+            assert_eq!(MessageId(500_000_000_000_000_000), message.id);
+            assert_eq!("ping", message.content);
+        }
+        other => panic!("not a message: {:?}", other),
+    }
+
+    assert_eq!(2, shard.info()?.seq());
+    shard.shutdown();
+
+    Ok(())
+}
+
+/// Recorder of metrics, keyed by their names and labels.
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct MetricsRecorder {
+    counters: std::sync::Mutex<HashMap<String, u64>>,
+    histograms: std::sync::Mutex<HashMap<String, Vec<u64>>>,
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder {
+    /// Format a key as its name followed by its sorted labels.
+    fn format(name: &str, labels: impl Iterator<Item = (String, String)>) -> String {
+        let mut labels = labels
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>();
+        labels.sort();
+
+        format!("{}{{{}}}", name, labels.join(","))
+    }
+
+    fn key(key: &metrics::Key) -> String {
+        Self::format(
+            &key.name(),
+            key.labels()
+                .map(|label| (label.key().to_owned(), label.value().to_owned())),
+        )
+    }
+
+    fn counter(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        let key = Self::format(
+            name,
+            labels
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned())),
+        );
+
+        self.counters
+            .lock()
+            .expect("counters poisoned")
+            .get(&key)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Vec<u64> {
+        let key = Self::format(
+            name,
+            labels
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned())),
+        );
+
+        self.histograms
+            .lock()
+            .expect("histograms poisoned")
+            .get(&key)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl metrics::Recorder for MetricsRecorder {
+    fn increment_counter(&self, key: metrics::Key, value: u64) {
+        *self
+            .counters
+            .lock()
+            .expect("counters poisoned")
+            .entry(Self::key(&key))
+            .or_default() += value;
+    }
+
+    fn update_gauge(&self, _: metrics::Key, _: i64) {}
+
+    fn record_histogram(&self, key: metrics::Key, value: u64) {
+        self.histograms
+            .lock()
+            .expect("histograms poisoned")
+            .entry(Self::key(&key))
+            .or_default()
+            .push(value);
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[tokio::test]
+async fn test_shard_metrics() -> Result<(), Box<dyn Error>> {
+    use twilight_model::id::GuildId;
+
+    // Other tests record metrics too, so this shard has its own ID.
+    const SHARD: (&str, &str) = ("shard", "3");
+
+    let recorder: &'static MetricsRecorder = Box::leak(Box::new(MetricsRecorder::default()));
+    metrics::set_recorder(recorder).map_err(|_| "recorder already set")?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+
+    let mut shard = Shard::builder(TOKEN, Intents::GUILD_MESSAGES)
+        .gateway_url(Some(url))
+        .queue(Arc::new(Box::new(NoopQueue)))
+        .shard(3, 4)?
+        .build();
+    let mut events = shard.some_events(EventTypeFlags::MESSAGE_CREATE);
+    let (started, connection) = tokio::join!(shard.start(), Connection::accept(&listener));
+    started?;
+    let mut connection = connection?;
+
+    connection
+        .send(json!({ "op": 10, "d": { "heartbeat_interval": 41250 } }))
+        .await?;
+    assert_eq!(2, connection.receive().await?["op"]);
+    connection.send(ready("abc", 1)).await?;
+    // Request a heartbeat instead of waiting for the interval.
+    connection.send(json!({ "op": 1, "d": 1 })).await?;
+    connection.heartbeat().await?;
+    connection.send(json!({ "op": 11 })).await?;
+    shard
+        .update_voice_state(GuildId(1), None, false, false)
+        .await?;
+    assert_eq!(4, connection.receive().await?["op"]);
+
+    // Payloads are processed in order, so the others have been by the time
+    // the message is received.
+    for sequence in 2..4 {
+        connection.send(message_create(sequence, sequence)).await?;
+        assert!(matches!(
+            next_event(&mut events).await?,
+            Event::MessageCreate(_)
+        ));
+    }
+
+    assert_eq!(1, recorder.counter("GatewayIdentify", &[SHARD]));
+    assert_eq!(
+        1,
+        recorder.counter("GatewayEvent", &[("GatewayEvent", "Hello"), SHARD])
+    );
+    assert_eq!(
+        1,
+        recorder.counter("GatewayEvent", &[("GatewayEvent", "HeartbeatAck"), SHARD])
+    );
+    assert_eq!(
+        3,
+        recorder.counter("GatewayEvent", &[("GatewayEvent", "Dispatch"), SHARD])
+    );
+    assert_eq!(
+        1,
+        recorder.counter("GatewayDispatch", &[("EventType", "READY"), SHARD])
+    );
+    assert_eq!(
+        2,
+        recorder.counter("GatewayDispatch", &[("EventType", "MESSAGE_CREATE"), SHARD])
+    );
+    assert_eq!(1, recorder.counter("GatewayCommand", &[SHARD]));
+    assert_eq!(
+        1,
+        recorder
+            .histogram("GatewayHeartbeatLatency", &[SHARD])
+            .len()
+    );
+    assert!(recorder.counter("GatewayBytes", &[("GatewayBytes", "Uncompressed"), SHARD]) > 0);
+    assert_eq!(
+        cfg!(feature = "compression"),
+        recorder.counter("GatewayBytes", &[("GatewayBytes", "Compressed"), SHARD]) > 0
+    );
+
+    // Asking the shard to reconnect makes it resume.
+    connection.send(json!({ "op": 7 })).await?;
+    Connection::accept(&listener).await?;
+    assert_eq!(
+        1,
+        recorder.counter("GatewayReconnect", &[("GatewayReconnect", "Resume"), SHARD])
+    );
+
+    shard.shutdown();
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
-use super::{Client, HttpsConnector, State};
-use crate::{ratelimiting::Ratelimiter, request::channel::allowed_mentions::AllowedMentions};
+use super::{Client, HttpClient, HttpsConnector, State};
+use crate::{
+    ratelimiting::Ratelimiter, request::channel::allowed_mentions::AllowedMentions, token,
+};
 use hyper::{
-    client::{Client as HyperClient, HttpConnector},
+    client::{connect::Connect, Client as HyperClient, HttpConnector},
     header::HeaderMap,
 };
 use std::{
@@ -15,11 +17,12 @@ pub struct ClientBuilder {
     pub(crate) default_allowed_mentions: Option<AllowedMentions>,
     pub(crate) proxy: Option<Box<str>>,
     pub(crate) ratelimiter: Option<Ratelimiter>,
-    pub(crate) hyper_client: Option<HyperClient<HttpsConnector<HttpConnector>>>,
+    pub(crate) hyper_client: Option<Arc<dyn HttpClient>>,
     pub(crate) default_headers: Option<HeaderMap>,
     pub(crate) timeout: Duration,
     pub(crate) token: Option<Box<str>>,
     pub(crate) use_http: bool,
+    pub(crate) validation: bool,
 }
 
 impl ClientBuilder {
@@ -31,12 +34,17 @@ impl ClientBuilder {
     /// Build the [`Client`].
     pub fn build(self) -> Client {
         let http = self.hyper_client.unwrap_or_else(|| {
-            #[cfg(feature = "hyper-rustls")]
+            #[cfg(feature = "rustls-native-roots")]
             let connector = hyper_rustls::HttpsConnector::with_native_roots();
-            #[cfg(all(feature = "hyper-tls", not(feature = "hyper-rustls")))]
+            #[cfg(all(feature = "rustls-webpki-roots", not(feature = "rustls-native-roots")))]
+            let connector = hyper_rustls::HttpsConnector::with_webpki_roots();
+            #[cfg(all(
+                feature = "native-tls",
+                not(any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"))
+            ))]
             let connector = {
                 // Workaround for https://github.com/hyperium/hyper-tls/pull/85
-                let tls = native_tls::TlsConnector::builder()
+                let tls = native_tls_crate::TlsConnector::builder()
                     .request_alpns(&["h2", "http/1.1"])
                     .build()
                     .expect("TlsConnector::new() failure");
@@ -45,7 +53,7 @@ impl ClientBuilder {
                 hyper_tls::HttpsConnector::from((http_conn, tls.into()))
             };
 
-            hyper::client::Builder::default().build(connector)
+            Arc::new(hyper::client::Builder::default().build(connector))
         });
 
         Client {
@@ -59,6 +67,7 @@ impl ClientBuilder {
                 token: self.token,
                 default_allowed_mentions: self.default_allowed_mentions,
                 use_http: self.use_http,
+                validation: self.validation,
             }),
         }
     }
@@ -78,7 +87,37 @@ impl ClientBuilder {
     ///
     /// The default client uses Rustls as its TLS backend.
     pub fn hyper_client(mut self, client: HyperClient<HttpsConnector<HttpConnector>>) -> Self {
-        self.hyper_client.replace(client);
+        self.hyper_client.replace(Arc::new(client));
+
+        self
+    }
+
+    /// Set a custom connector to make connections with.
+    ///
+    /// This is useful for setups that the enabled TLS feature doesn't
+    /// support, such as connecting through a proxy over a Unix socket. The
+    /// connector is responsible for TLS, if any.
+    ///
+    /// This overrides any client set via [`hyper_client`].
+    ///
+    /// # Examples
+    ///
+    /// Use a plain HTTP connector, such as for a local proxy:
+    ///
+    /// ```rust
+    /// use hyper::client::HttpConnector;
+    /// use twilight_http::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .connector(HttpConnector::new())
+    ///     .proxy("localhost:3000", true)
+    ///     .build();
+    /// ```
+    ///
+    /// [`hyper_client`]: Self::hyper_client
+    pub fn connector<C: Connect + Clone + Send + Sync + 'static>(mut self, connector: C) -> Self {
+        let client = hyper::client::Builder::default().build::<_, hyper::Body>(connector);
+        self.hyper_client.replace(Arc::new(client));
 
         self
     }
@@ -132,6 +171,19 @@ impl ClientBuilder {
         self
     }
 
+    /// Set whether requests are validated client-side.
+    ///
+    /// Validation checks input against Discord's limits, such as the maximum
+    /// length of message content, before a request is sent. Disable it to
+    /// leave the validation up to Discord.
+    ///
+    /// The default is `true`.
+    pub fn validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+
+        self
+    }
+
     /// Set a group headers which are sent in every request.
     pub fn default_headers(mut self, headers: HeaderMap) -> Self {
         self.default_headers.replace(headers);
@@ -140,19 +192,23 @@ impl ClientBuilder {
     }
 
     /// Set the token to use for HTTP requests.
+    ///
+    /// The token is assumed to be a bot token and is prefixed with `"Bot "`
+    /// unless it is already prefixed with either `"Bot "` or `"Bearer "`.
     pub fn token(mut self, token: impl Into<String>) -> Self {
-        let mut token = token.into();
-
-        let is_bot = token.starts_with("Bot ");
-        let is_bearer = token.starts_with("Bearer ");
+        self.token
+            .replace(token::bot(&token.into()).into_boxed_str());
 
-        // Make sure it is either a bot or bearer token, and assume it's a bot
-        // token if no prefix is given
-        if !is_bot && !is_bearer {
-            token.insert_str(0, "Bot ");
-        }
+        self
+    }
 
-        self.token.replace(token.into_boxed_str());
+    /// Set a Bearer token to use for HTTP requests, such as an OAuth2 access
+    /// token.
+    ///
+    /// The token is prefixed with `"Bearer "` unless it is already prefixed.
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.token
+            .replace(token::bearer(&token.into()).into_boxed_str());
 
         self
     }
@@ -169,6 +225,72 @@ impl Default for ClientBuilder {
             timeout: Duration::from_secs(10),
             token: None,
             use_http: false,
+            validation: true,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ClientBuilder;
+    use hyper::client::HttpConnector;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+
+    assert_impl_all!(ClientBuilder: Debug, Default, Send, Sync);
+
+    #[cfg(feature = "native-tls")]
+    #[test]
+    fn test_native_tls() {
+        let client = ClientBuilder::new().token("token").build();
+
+        assert_eq!(Some("Bot token"), client.token());
+    }
+
+    #[cfg(feature = "rustls-native-roots")]
+    #[test]
+    fn test_rustls_native_roots() {
+        let client = ClientBuilder::new().token("token").build();
+
+        assert_eq!(Some("Bot token"), client.token());
+    }
+
+    #[cfg(feature = "rustls-webpki-roots")]
+    #[test]
+    fn test_rustls_webpki_roots() {
+        let client = ClientBuilder::new().token("token").build();
+
+        assert_eq!(Some("Bot token"), client.token());
+    }
+
+    #[test]
+    fn test_token() {
+        let bot = ClientBuilder::new().token("token").build();
+        assert_eq!(Some("Bot token"), bot.token());
+
+        let prefixed = ClientBuilder::new().token("Bot token").build();
+        assert_eq!(Some("Bot token"), prefixed.token());
+
+        let bearer = ClientBuilder::new().token("Bearer token").build();
+        assert_eq!(Some("Bearer token"), bearer.token());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let bearer = ClientBuilder::new().bearer_token("token").build();
+        assert_eq!(Some("Bearer token"), bearer.token());
+
+        let prefixed = ClientBuilder::new().bearer_token("Bearer token").build();
+        assert_eq!(Some("Bearer token"), prefixed.token());
+    }
+
+    #[test]
+    fn test_connector() {
+        let client = ClientBuilder::new()
+            .connector(HttpConnector::new())
+            .token("token")
+            .build();
+
+        assert_eq!(Some("Bot token"), client.token());
+    }
+}
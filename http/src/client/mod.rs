@@ -12,12 +12,13 @@ use crate::{
         prelude::*,
         GetUserApplicationInfo, Request,
     },
-    API_VERSION,
+    routing::Path,
+    token, API_VERSION,
 };
 use bytes::Bytes;
 use hyper::{
     body::{self, Buf},
-    client::{Client as HyperClient, HttpConnector},
+    client::{connect::Connect, Client as HyperClient, HttpConnector, ResponseFuture},
     header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT},
     Body, Method, Response, StatusCode,
 };
@@ -43,8 +44,21 @@ type HttpsConnector<T> = hyper_rustls::HttpsConnector<T>;
 #[cfg(all(feature = "hyper-tls", not(feature = "hyper-rustls")))]
 type HttpsConnector<T> = hyper_tls::HttpsConnector<T>;
 
+/// Hyper client with its connector type erased.
+///
+/// This allows clients to be built with custom connectors.
+pub(crate) trait HttpClient: Debug + Send + Sync {
+    fn request(&self, request: hyper::Request<Body>) -> ResponseFuture;
+}
+
+impl<C: Connect + Clone + Send + Sync + 'static> HttpClient for HyperClient<C, Body> {
+    fn request(&self, request: hyper::Request<Body>) -> ResponseFuture {
+        HyperClient::request(self, request)
+    }
+}
+
 struct State {
-    http: HyperClient<HttpsConnector<HttpConnector>, Body>,
+    http: Arc<dyn HttpClient>,
     default_headers: Option<HeaderMap>,
     proxy: Option<Box<str>>,
     ratelimiter: Option<Ratelimiter>,
@@ -52,6 +66,7 @@ struct State {
     token_invalid: AtomicBool,
     token: Option<Box<str>>,
     use_http: bool,
+    validation: bool,
     pub(crate) default_allowed_mentions: Option<AllowedMentions>,
 }
 
@@ -64,6 +79,7 @@ impl Debug for State {
             .field("ratelimiter", &self.ratelimiter)
             .field("token", &self.token)
             .field("use_http", &self.use_http)
+            .field("validation", &self.validation)
             .finish()
     }
 }
@@ -90,6 +106,13 @@ impl Debug for State {
 /// # Ok(()) }
 /// ```
 ///
+/// Alternatively use [`ClientBuilder::bearer_token`], which adds the prefix
+/// if it's missing.
+///
+/// When a client serves many users, such as an OAuth2 dashboard, use
+/// [`Client::with_bearer_token`] to make requests on behalf of each user
+/// while sharing the connection pool of one client.
+///
 /// # Cloning
 ///
 /// The client internally wraps its data within an Arc. This means that the
@@ -140,7 +163,9 @@ pub struct Client {
 
 impl Client {
     /// Create a new `hyper-rustls` or `hyper-tls` backed client with a token.
-    #[cfg_attr(docsrs, doc(cfg(any(feature = "hyper-rustls", feature = "hyper-tls"))))]
+    ///
+    /// Refer to the crate's documentation on TLS features for which TLS
+    /// backend is used.
     pub fn new(token: impl Into<String>) -> Self {
         ClientBuilder::default().token(token).build()
     }
@@ -160,6 +185,61 @@ impl Client {
         self.state.token.as_deref()
     }
 
+    /// Create a client that authenticates with another token, treating it in
+    /// the same way as [`ClientBuilder::token`].
+    ///
+    /// Refer to [`with_bearer_token`] for more information.
+    ///
+    /// [`with_bearer_token`]: Self::with_bearer_token
+    pub fn with_token(&self, token: impl Into<String>) -> Self {
+        self.with_formatted_token(token::bot(&token.into()).into_boxed_str())
+    }
+
+    /// Create a client that authenticates with a Bearer token, treating it
+    /// in the same way as [`ClientBuilder::bearer_token`].
+    ///
+    /// The returned client shares this client's connection pool and
+    /// configuration. Ratelimits are tracked per token, so the returned
+    /// client has its own ratelimiter if ratelimiting is enabled. Re-use the
+    /// returned client for all of the requests made with a token so that its
+    /// ratelimits are respected.
+    ///
+    /// # Examples
+    ///
+    /// Get the guilds of a user that has authorized the application:
+    ///
+    /// ```rust,no_run
+    /// use twilight_http::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("my token");
+    ///
+    /// let user_client = client.with_bearer_token("user access token");
+    /// let guilds = user_client.current_user_guilds().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn with_bearer_token(&self, token: impl Into<String>) -> Self {
+        self.with_formatted_token(token::bearer(&token.into()).into_boxed_str())
+    }
+
+    fn with_formatted_token(&self, token: Box<str>) -> Self {
+        Self {
+            state: Arc::new(State {
+                http: Arc::clone(&self.state.http),
+                default_headers: self.state.default_headers.clone(),
+                proxy: self.state.proxy.clone(),
+                ratelimiter: self.state.ratelimiter.as_ref().map(|_| Ratelimiter::new()),
+                timeout: self.state.timeout,
+                token_invalid: AtomicBool::new(false),
+                token: Some(token),
+                use_http: self.state.use_http,
+                validation: self.state.validation,
+                default_allowed_mentions: self.state.default_allowed_mentions.clone(),
+            }),
+        }
+    }
+
     /// Get the default allowed mentions for sent messages.
     ///
     /// Refer to [`allowed_mentions`] for more information.
@@ -169,6 +249,13 @@ impl Client {
         self.state.default_allowed_mentions.clone()
     }
 
+    /// Whether requests are validated client-side.
+    ///
+    /// Refer to [`ClientBuilder::validation`] for more information.
+    pub fn validation(&self) -> bool {
+        self.state.validation
+    }
+
     /// Get the Ratelimiter used by the client internally.
     ///
     /// This will return `None` only if ratelimit handling
@@ -395,6 +482,9 @@ impl Client {
         GetChannelMessages::new(self, channel_id)
     }
 
+    /// Clear the permissions for a role or a user in a channel.
+    ///
+    /// Refer to [`DeleteChannelPermission`] for more information.
     pub fn delete_channel_permission(&self, channel_id: ChannelId) -> DeleteChannelPermission<'_> {
         DeleteChannelPermission::new(self, channel_id)
     }
@@ -563,9 +653,11 @@ impl Client {
     ///
     /// The emoji must be a Data URI, in the form of `data:image/{type};base64,{data}` where
     /// `{type}` is the image MIME type and `{data}` is the base64-encoded image.  Refer to [the
-    /// discord docs] for more information about image data.
+    /// discord docs] for more information about image data. Use [`ImageData::emoji`] to encode
+    /// the raw bytes of an image.
     ///
     /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+    /// [`ImageData::emoji`]: crate::request::ImageData::emoji
     pub fn create_emoji(
         &self,
         guild_id: GuildId,
@@ -796,15 +888,20 @@ impl Client {
     /// #
     /// let guild_id = GuildId(100);
     /// let user_id = UserId(3000);
-    /// let members = client.guild_members(guild_id).after(user_id).await?;
+    /// let members = client.guild_members(guild_id).after(user_id)?.await?;
     /// # Ok(()) }
     /// ```
     ///
     /// # Errors
     ///
-    /// Returns [`GetGuildMembersError::LimitInvalid`] if the limit is invalid.
+    /// Returns a [`GetGuildMembersErrorType::AfterInvalid`] error type if the
+    /// user ID to get members after is 0.
     ///
-    /// [`GetGuildMembersError::LimitInvalid`]: crate::request::guild::member::get_guild_members::GetGuildMembersError::LimitInvalid
+    /// Returns a [`GetGuildMembersErrorType::LimitInvalid`] error type if the
+    /// limit is invalid.
+    ///
+    /// [`GetGuildMembersErrorType::AfterInvalid`]: crate::request::guild::member::get_guild_members::GetGuildMembersErrorType::AfterInvalid
+    /// [`GetGuildMembersErrorType::LimitInvalid`]: crate::request::guild::member::get_guild_members::GetGuildMembersErrorType::LimitInvalid
     pub fn guild_members(&self, guild_id: GuildId) -> GetGuildMembers<'_> {
         GetGuildMembers::new(self, guild_id)
     }
@@ -976,7 +1073,7 @@ impl Client {
     /// let channel_id = ChannelId(123);
     /// let invite = client
     ///     .create_invite(channel_id)
-    ///     .max_uses(3)
+    ///     .max_uses(3)?
     ///     .await?;
     /// # Ok(()) }
     /// ```
@@ -1017,18 +1114,18 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// The method [`content`] returns [`CreateMessageError::ContentInvalid`] if the content is
-    /// over 2000 UTF-16 characters.
+    /// The method [`content`] returns a [`CreateMessageErrorType::ContentInvalid`] error type if
+    /// the content is over 2000 UTF-16 characters.
     ///
-    /// The method [`embed`] returns [`CreateMessageError::EmbedTooLarge`] if the length of the
-    /// embed is over 6000 characters.
+    /// The method [`embed`] returns a [`CreateMessageErrorType::EmbedTooLarge`] error type if the
+    /// length of the embed is over 6000 characters.
     ///
     /// [`content`]: crate::request::channel::message::create_message::CreateMessage::content
     /// [`embed`]: crate::request::channel::message::create_message::CreateMessage::embed
-    /// [`CreateMessageError::ContentInvalid`]:
-    /// crate::request::channel::message::create_message::CreateMessageError::ContentInvalid
-    /// [`CreateMessageError::EmbedTooLarge`]:
-    /// crate::request::channel::message::create_message::CreateMessageError::EmbedTooLarge
+    /// [`CreateMessageErrorType::ContentInvalid`]:
+    /// crate::request::channel::message::create_message::CreateMessageErrorType::ContentInvalid
+    /// [`CreateMessageErrorType::EmbedTooLarge`]:
+    /// crate::request::channel::message::create_message::CreateMessageErrorType::EmbedTooLarge
     pub fn create_message(&self, channel_id: ChannelId) -> CreateMessage<'_> {
         CreateMessage::new(self, channel_id)
     }
@@ -1219,9 +1316,9 @@ impl Client {
         CreateTypingTrigger::new(self, channel_id)
     }
 
-    /// Create a group DM.
+    /// Create a DM channel with a user.
     ///
-    /// This endpoint is limited to 10 active group DMs.
+    /// Refer to [`CreatePrivateChannel`] for more information.
     pub fn create_private_channel(&self, recipient_id: UserId) -> CreatePrivateChannel<'_> {
         CreatePrivateChannel::new(self, recipient_id)
     }
@@ -1424,12 +1521,59 @@ impl Client {
     ///
     /// Returns [`Error::Unauthorized`] if the configured token has become
     /// invalid due to expiration, revokation, etc.
-    #[allow(clippy::too_many_lines)]
     pub async fn raw(&self, request: Request) -> Result<Response<Body>> {
         if self.state.token_invalid.load(Ordering::Relaxed) {
             return Err(Error::Unauthorized);
         }
 
+        let (req, bucket) = self.hyper_request(request)?;
+        let inner = self.state.http.request(req);
+        let fut = time::timeout(self.state.timeout, inner);
+
+        let ratelimiter = match self.state.ratelimiter.as_ref() {
+            Some(ratelimiter) => ratelimiter,
+            None => {
+                return fut
+                    .await
+                    .map_err(|source| Error::RequestTimedOut { source })?
+                    .map_err(|source| Error::RequestError { source })
+            }
+        };
+
+        let rx = ratelimiter.get(bucket).await;
+        let tx = rx
+            .await
+            .map_err(|source| Error::RequestCanceled { source })?;
+
+        let resp = fut
+            .await
+            .map_err(|source| Error::RequestTimedOut { source })?
+            .map_err(|source| Error::RequestError { source })?;
+
+        // If the API sent back an Unauthorized response, then the client's
+        // configured token is permanently invalid and future requests must be
+        // ignored to avoid API bans.
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            self.state.token_invalid.store(true, Ordering::Relaxed);
+        }
+
+        match RatelimitHeaders::try_from(resp.headers()) {
+            Ok(v) => {
+                let _ = tx.send(Some(v));
+            }
+            Err(why) => {
+                tracing::warn!("header parsing failed: {:?}; {:?}", why, resp);
+
+                let _ = tx.send(None);
+            }
+        }
+
+        Ok(resp)
+    }
+
+    /// Build the hyper request to send, returning it along with the
+    /// ratelimiting bucket of the request.
+    pub(crate) fn hyper_request(&self, request: Request) -> Result<(hyper::Request<Body>, Path)> {
         let Request {
             body,
             form,
@@ -1526,48 +1670,7 @@ impl Client {
                 .map_err(|source| Error::BuildingRequest { source })?
         };
 
-        let inner = self.state.http.request(req);
-        let fut = time::timeout(self.state.timeout, inner);
-
-        let ratelimiter = match self.state.ratelimiter.as_ref() {
-            Some(ratelimiter) => ratelimiter,
-            None => {
-                return fut
-                    .await
-                    .map_err(|source| Error::RequestTimedOut { source })?
-                    .map_err(|source| Error::RequestError { source })
-            }
-        };
-
-        let rx = ratelimiter.get(bucket).await;
-        let tx = rx
-            .await
-            .map_err(|source| Error::RequestCanceled { source })?;
-
-        let resp = fut
-            .await
-            .map_err(|source| Error::RequestTimedOut { source })?
-            .map_err(|source| Error::RequestError { source })?;
-
-        // If the API sent back an Unauthorized response, then the client's
-        // configured token is permanently invalid and future requests must be
-        // ignored to avoid API bans.
-        if resp.status() == StatusCode::UNAUTHORIZED {
-            self.state.token_invalid.store(true, Ordering::Relaxed);
-        }
-
-        match RatelimitHeaders::try_from(resp.headers()) {
-            Ok(v) => {
-                let _ = tx.send(Some(v));
-            }
-            Err(why) => {
-                tracing::warn!("header parsing failed: {:?}; {:?}", why, resp);
-
-                let _ = tx.send(None);
-            }
-        }
-
-        Ok(resp)
+        Ok((req, bucket))
     }
 
     /// Execute a request, chunking and deserializing the response.
@@ -1667,7 +1770,7 @@ impl From<HyperClient<HttpsConnector<HttpConnector>>> for Client {
     fn from(hyper_client: HyperClient<HttpsConnector<HttpConnector>>) -> Self {
         Self {
             state: Arc::new(State {
-                http: hyper_client,
+                http: Arc::new(hyper_client),
                 default_headers: None,
                 proxy: None,
                 ratelimiter: Some(Ratelimiter::new()),
@@ -1675,8 +1778,81 @@ impl From<HyperClient<HttpsConnector<HttpConnector>>> for Client {
                 token_invalid: AtomicBool::new(false),
                 token: None,
                 use_http: false,
+                validation: true,
                 default_allowed_mentions: None,
             }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Client;
+    use crate::{request::Request, routing::Route};
+    use hyper::header::{HeaderValue, AUTHORIZATION};
+
+    fn authorization(client: &Client) -> Option<HeaderValue> {
+        let (request, _) = client
+            .hyper_request(Request::from(Route::GetUser {
+                target_user: "@me".to_owned(),
+            }))
+            .expect("failed to build request");
+
+        request.headers().get(AUTHORIZATION).cloned()
+    }
+
+    #[test]
+    fn test_authorization_bot() {
+        let client = Client::new("token");
+
+        assert_eq!(
+            Some(HeaderValue::from_static("Bot token")),
+            authorization(&client)
+        );
+    }
+
+    #[test]
+    fn test_authorization_bearer() {
+        let client = Client::builder().bearer_token("token").build();
+
+        assert_eq!(
+            Some(HeaderValue::from_static("Bearer token")),
+            authorization(&client)
+        );
+    }
+
+    #[test]
+    fn test_authorization_none() {
+        let client = Client::builder().build();
+
+        assert!(authorization(&client).is_none());
+    }
+
+    #[test]
+    fn test_with_bearer_token() {
+        let client = Client::new("token");
+        let user = client.with_bearer_token("user");
+
+        assert_eq!(
+            Some(HeaderValue::from_static("Bearer user")),
+            authorization(&user)
+        );
+        assert_eq!(
+            Some(HeaderValue::from_static("Bot token")),
+            authorization(&client)
+        );
+        assert!(user.ratelimiter().is_some());
+    }
+
+    #[test]
+    fn test_with_token_without_ratelimiter() {
+        let client = Client::builder().token("token").ratelimiter(None).build();
+        let other = client.with_token("other");
+
+        assert_eq!(
+            Some(HeaderValue::from_static("Bot other")),
+            authorization(&other)
+        );
+        assert!(other.ratelimiter().is_none());
+    }
+}
@@ -71,6 +71,15 @@ pub enum Error {
     /// This can occur if a bot token is invalidated or an access token expires
     /// or is revoked. Recreate the client to configure a new token.
     Unauthorized,
+    /// Request failed client-side validation when it was about to be sent.
+    ///
+    /// The source is the error of the request, such as a
+    /// [`CreateMessageError`].
+    ///
+    /// [`CreateMessageError`]: crate::request::channel::message::create_message::CreateMessageError
+    Validation {
+        source: Box<dyn StdError + Send + Sync>,
+    },
 }
 
 impl From<FmtError> for Error {
@@ -113,6 +122,7 @@ impl Display for Error {
                 f.write_str("api may be temporarily unavailable (received a 503)")
             }
             Self::Unauthorized => f.write_str("token in use is invalid, expired, or is revoked"),
+            Self::Validation { .. } => f.write_str("request failed validation"),
         }
     }
 }
@@ -129,6 +139,7 @@ impl StdError for Error {
             Self::ChunkingResponse { source } | Self::RequestError { source } => Some(source),
             Self::RequestTimedOut { source } => Some(source),
             Self::Response { .. } | Self::ServiceUnavailable { .. } | Self::Unauthorized => None,
+            Self::Validation { source } => Some(&**source),
         }
     }
 }
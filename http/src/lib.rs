@@ -38,33 +38,63 @@
 //!
 //! ### TLS
 //!
-//! `twilight-http` has features to enable [`hyper`]'s TLS features. These
-//! features are mutually exclusive. `rustls` is enabled by default.
+//! `twilight-http` has features to enable [`hyper`]'s TLS features. If
+//! multiple of these features are enabled, then `rustls-native-roots` takes
+//! precedence over `rustls-webpki-roots`, which takes precedence over
+//! `native-tls`. `rustls-native-roots` is enabled by default.
 //!
-//! #### `native`
+//! A custom connector may also be provided via
+//! [`ClientBuilder::connector`], in which case the connector is
+//! responsible for TLS.
 //!
-//! The `native` feature enables [`hyper`]'s `default-tls`
-//! feature, which is mostly equivalent to using [`native-tls`].
+//! #### `native-tls`
 //!
-//! To enable `native`, do something like this in your `Cargo.toml`:
+//! The `native-tls` feature uses a [`hyper-tls`] connector, which uses
+//! [`native-tls`] and so the TLS implementation of the system, such as
+//! OpenSSL.
+//!
+//! To enable `native-tls`, do something like this in your `Cargo.toml`:
 //!
 //! ```toml
 //! [dependencies]
-//! twilight-http = { default-features = false, features = ["native"], version = "0.2" }
+//! twilight-http = { default-features = false, features = ["native-tls"], version = "0.2" }
 //! ```
 //!
-//! #### `rustls`
+//! The `native` feature is an alias of this feature.
+//!
+//! #### `rustls-native-roots`
+//!
+//! The `rustls-native-roots` feature uses a [`hyper-rustls`] connector, which
+//! uses [`rustls`] as the TLS backend, with the root certificates of the
+//! system.
+//!
+//! This is enabled by default. The `rustls` feature is an alias of this
+//! feature.
+//!
+//! #### `rustls-webpki-roots`
 //!
-//! The `rustls` feature enables [`hyper`]'s `rustls` feature, which uses
-//! [`rustls`] as the TLS backend.
+//! The `rustls-webpki-roots` feature uses a [`hyper-rustls`] connector, which
+//! uses [`rustls`] as the TLS backend, with the root certificates of
+//! [`webpki-roots`] built into the binary. This is useful for environments
+//! without a system certificate store, such as distroless containers.
 //!
-//! This is enabled by default.
+//! To enable `rustls-webpki-roots`, do something like this in your
+//! `Cargo.toml`:
+//!
+//! ```toml
+//! [dependencies]
+//! twilight-http = { default-features = false, features = ["rustls-webpki-roots"], version = "0.2" }
+//! ```
 //!
+//! [`ClientBuilder::connector`]: crate::client::ClientBuilder::connector
 //! [`native-tls`]: https://crates.io/crates/native-tls
 //! [`hyper`]: https://crates.io/crates/hyper
+//! [`hyper-rustls`]: https://crates.io/crates/hyper-rustls
+//! [`hyper-tls`]: https://crates.io/crates/hyper-tls
 //! [`rustls`]: https://crates.io/crates/rustls
 //! [`serde_json`]: https://crates.io/crates/serde_json
 //! [`simd-json`]: https://crates.io/crates/simd-json
+//! [`webpki-roots`]: https://crates.io/crates/webpki-roots
 //! [discord badge]: https://img.shields.io/discord/745809834183753828?color=%237289DA&label=discord%20server&logo=discord&style=for-the-badge
 //! [discord link]: https://discord.gg/7jj8n7D
 //! [github badge]: https://img.shields.io/badge/github-twilight-6f42c1.svg?style=for-the-badge&logo=github
@@ -98,6 +128,7 @@ pub mod error;
 pub mod ratelimiting;
 pub mod request;
 pub mod routing;
+pub mod token;
 
 /// Discord API version used by this crate.
 pub const API_VERSION: u8 = 8;
@@ -124,5 +155,11 @@ pub(crate) use serde_json::to_vec as json_to_vec;
 #[cfg(feature = "simd-json")]
 pub(crate) use simd_json::to_vec as json_to_vec;
 
-#[cfg(not(any(feature = "native", feature = "rustls")))]
-compile_error!("Either the `native` or `rustls` feature must be enabled.");
+#[cfg(not(any(
+    feature = "native-tls",
+    feature = "rustls-native-roots",
+    feature = "rustls-webpki-roots"
+)))]
+compile_error!(
+    "One of the `native-tls`, `rustls-native-roots`, or `rustls-webpki-roots` features must be enabled."
+);
@@ -1,19 +1,22 @@
-use super::{headers::RatelimitHeaders, GlobalLockPair};
+use super::{
+    headers::RatelimitHeaders, BucketHashes, BucketInfo, BucketKey, Buckets, ExhaustedSubscribers,
+    GlobalLockPair,
+};
 use crate::routing::Path;
 use futures_channel::{
     mpsc::{self, UnboundedReceiver, UnboundedSender},
-    oneshot::{self, Sender},
+    oneshot::{self, Receiver, Sender},
 };
 use futures_util::{lock::Mutex, stream::StreamExt};
 use std::{
-    collections::HashMap,
+    collections::hash_map::Entry,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
-    time::{Duration, Instant},
+    time::Duration,
 };
-use tokio::time::{sleep, timeout};
+use tokio::time::{sleep, timeout, Instant};
 
 #[derive(Clone, Debug)]
 pub enum TimeRemaining {
@@ -24,6 +27,8 @@ pub enum TimeRemaining {
 
 #[derive(Debug)]
 pub struct Bucket {
+    /// Hash of the bucket as given by Discord, if known.
+    pub hash: Mutex<Option<String>>,
     pub limit: AtomicU64,
     pub path: Path,
     pub queue: BucketQueue,
@@ -33,8 +38,9 @@ pub struct Bucket {
 }
 
 impl Bucket {
-    pub fn new(path: Path) -> Self {
+    pub fn new(path: Path, hash: Option<String>) -> Self {
         Self {
+            hash: Mutex::new(hash),
             limit: AtomicU64::new(u64::max_value()),
             path,
             queue: BucketQueue::default(),
@@ -56,6 +62,45 @@ impl Bucket {
         self.reset_after.load(Ordering::Relaxed)
     }
 
+    pub async fn info(&self) -> BucketInfo {
+        // The limit is only known once headers have been received.
+        let limit = Some(self.limit()).filter(|limit| *limit != u64::max_value());
+        let time_remaining = match self.time_remaining().await {
+            TimeRemaining::Finished | TimeRemaining::NotStarted => None,
+            TimeRemaining::Some(duration) => Some(duration),
+        };
+
+        BucketInfo {
+            bucket: self.hash.lock().await.clone(),
+            limit,
+            path: self.path.clone(),
+            remaining: limit.map(|_| self.remaining()),
+            reset_after: limit.map(|_| Duration::from_millis(self.reset_after())),
+            time_remaining,
+        }
+    }
+
+    /// Whether requests can be made without waiting for the response of the
+    /// previous request.
+    ///
+    /// This is the case once the limit of the bucket is known and the current
+    /// cycle has been started by a response, so that the number of remaining
+    /// requests is accurate.
+    pub async fn can_burst(&self) -> bool {
+        self.limit() != u64::max_value()
+            && self.remaining() > 0
+            && self.started_at.lock().await.is_some()
+    }
+
+    /// Reserve one of the remaining requests of the current cycle.
+    pub fn reserve(&self) {
+        let _ = self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(1)
+            });
+    }
+
     pub async fn time_remaining(&self) -> TimeRemaining {
         let reset_after = self.reset_after();
         let started_at = match *self.started_at.lock().await {
@@ -87,25 +132,22 @@ impl Bucket {
     }
 
     pub async fn update(&self, ratelimits: Option<(u64, u64, u64)>) {
-        let bucket_limit = self.limit();
-
-        {
-            let mut started_at = self.started_at.lock().await;
-
-            if started_at.is_none() {
-                started_at.replace(Instant::now());
-            }
-        }
+        let mut started_at = self.started_at.lock().await;
 
         if let Some((limit, remaining, reset_after)) = ratelimits {
-            if bucket_limit != limit && bucket_limit == u64::max_value() {
-                self.reset_after.store(reset_after, Ordering::SeqCst);
-                self.limit.store(limit, Ordering::SeqCst);
-            }
-
-            self.remaining.store(remaining, Ordering::Relaxed);
-        } else {
-            self.remaining.fetch_sub(1, Ordering::Relaxed);
+            // The reset after header is relative to when the response was
+            // sent, which avoids relying on the system clock being in sync
+            // with Discord's.
+            started_at.replace(Instant::now());
+            self.reset_after.store(reset_after, Ordering::SeqCst);
+            self.limit.store(limit, Ordering::SeqCst);
+
+            // Requests may be in flight while the response to an earlier
+            // request is received, which have already been reserved locally,
+            // so only ever lower the count.
+            self.remaining.fetch_min(remaining, Ordering::SeqCst);
+        } else if started_at.is_none() {
+            started_at.replace(Instant::now());
         }
     }
 }
@@ -148,10 +190,13 @@ impl Default for BucketQueue {
     }
 }
 
+#[derive(Clone)]
 pub(super) struct BucketQueueTask {
     bucket: Arc<Bucket>,
-    buckets: Arc<Mutex<HashMap<Path, Arc<Bucket>>>>,
+    buckets: Buckets,
+    exhausted: ExhaustedSubscribers,
     global: Arc<GlobalLockPair>,
+    hashes: BucketHashes,
     path: Path,
 }
 
@@ -160,14 +205,18 @@ impl BucketQueueTask {
 
     pub fn new(
         bucket: Arc<Bucket>,
-        buckets: Arc<Mutex<HashMap<Path, Arc<Bucket>>>>,
+        buckets: Buckets,
+        exhausted: ExhaustedSubscribers,
         global: Arc<GlobalLockPair>,
+        hashes: BucketHashes,
         path: Path,
     ) -> Self {
         Self {
             bucket,
             buckets,
+            exhausted,
             global,
+            hashes,
             path,
         }
     }
@@ -182,25 +231,46 @@ impl BucketQueueTask {
                 self.global.0.lock().await;
             }
 
-            let _sent = queue_tx.send(tx);
+            // Check before sending the ticket so that the response can't
+            // update the bucket in the meantime.
+            let burst = self.bucket.can_burst().await;
 
-            tracing::debug!(parent: &span, "starting to wait for response headers",);
+            if queue_tx.send(tx).is_err() {
+                tracing::debug!(parent: &span, "request was dropped before its turn");
 
-            // TODO: Find a better way of handling nested types.
-            match timeout(Self::WAIT, rx).await {
-                Ok(Ok(Some(headers))) => self.handle_headers(&headers).await,
-                // - None was sent through the channel (request aborted)
-                // - channel was closed
-                // - timeout reached
-                Ok(Err(_)) | Err(_) | Ok(Ok(None)) => {
-                    tracing::debug!(parent: &span, "receiver timed out");
-                }
+                continue;
+            }
+
+            self.bucket.reserve();
+
+            if burst {
+                tracing::debug!(parent: &span, "waiting for response headers in the background");
+
+                let task = self.clone();
+                tokio::spawn(async move { task.wait_for_headers(rx).await });
+            } else {
+                tracing::debug!(parent: &span, "starting to wait for response headers");
+
+                self.wait_for_headers(rx).await;
             }
         }
 
         tracing::debug!(parent: &span, "bucket appears finished, removing");
 
-        self.buckets.lock().await.remove(&self.path);
+        self.remove().await;
+    }
+
+    async fn wait_for_headers(&self, rx: Receiver<Option<RatelimitHeaders>>) {
+        // TODO: Find a better way of handling nested types.
+        match timeout(Self::WAIT, rx).await {
+            Ok(Ok(Some(headers))) => self.handle_headers(&headers).await,
+            // - None was sent through the channel (request aborted)
+            // - channel was closed
+            // - timeout reached
+            Ok(Err(_)) | Err(_) | Ok(Ok(None)) => {
+                tracing::debug!(path=?self.path, "receiver timed out");
+            }
+        }
     }
 
     async fn handle_headers(&self, headers: &RatelimitHeaders) {
@@ -212,6 +282,7 @@ impl BucketQueueTask {
             }
             RatelimitHeaders::None => return,
             RatelimitHeaders::Present {
+                bucket,
                 global,
                 limit,
                 remaining,
@@ -222,12 +293,107 @@ impl BucketQueueTask {
                     self.lock_global(*reset_after).await;
                 }
 
+                if let Some(hash) = bucket {
+                    self.record_hash(hash).await;
+                }
+
                 Some((*limit, *remaining, *reset_after))
             }
         };
 
         tracing::debug!(path=?self.path, "updating bucket");
         self.bucket.update(ratelimits).await;
+
+        if let Some((_, 0, _)) = ratelimits {
+            self.notify_exhausted().await;
+        }
+    }
+
+    /// Record the hash of the bucket that the path belongs to.
+    ///
+    /// Future requests to the path will use the bucket of the hash. If there
+    /// is no bucket for the hash yet then this bucket becomes it.
+    async fn record_hash(&self, hash: &str) {
+        let mut current = self.bucket.hash.lock().await;
+
+        if current.is_some() {
+            return;
+        }
+
+        let mut buckets = self.buckets.lock().await;
+        let key = BucketKey::Hash {
+            hash: hash.to_owned(),
+            major: self.path.major_parameter(),
+        };
+
+        if let Entry::Vacant(entry) = buckets.entry(key) {
+            tracing::debug!(path=?self.path, %hash, "bucket is now keyed by hash");
+
+            entry.insert(Arc::clone(&self.bucket));
+            current.replace(hash.to_owned());
+        }
+
+        // Future requests to the path use the bucket of the hash, even if it
+        // already existed, so the bucket keyed by the path is no longer used.
+        // Requests already queued in it are still handled by its task.
+        let key = BucketKey::Path(self.path.clone());
+
+        if self.is_bucket(buckets.get(&key)) {
+            buckets.remove(&key);
+        }
+
+        if let Ok(mut hashes) = self.hashes.lock() {
+            hashes.insert(self.path.clone(), hash.to_owned());
+        }
+    }
+
+    /// Remove the bucket from the ratelimiter.
+    ///
+    /// If the bucket is keyed by a hash, then the paths known to belong to it
+    /// are forgotten, so that the next requests to them probe the bucket
+    /// again.
+    async fn remove(&self) {
+        let hash = self.bucket.hash.lock().await.clone();
+        let mut buckets = self.buckets.lock().await;
+        let major = self.path.major_parameter();
+
+        let key = match &hash {
+            Some(hash) => BucketKey::Hash {
+                hash: hash.clone(),
+                major,
+            },
+            None => BucketKey::Path(self.path.clone()),
+        };
+
+        if !self.is_bucket(buckets.get(&key)) {
+            return;
+        }
+
+        buckets.remove(&key);
+
+        if let Some(hash) = hash {
+            if let Ok(mut hashes) = self.hashes.lock() {
+                hashes.retain(|path, value| *value != hash || path.major_parameter() != major);
+            }
+        }
+    }
+
+    fn is_bucket(&self, bucket: Option<&Arc<Bucket>>) -> bool {
+        match bucket {
+            Some(bucket) => Arc::ptr_eq(bucket, &self.bucket),
+            None => false,
+        }
+    }
+
+    async fn notify_exhausted(&self) {
+        tracing::debug!(path=?self.path, "bucket exhausted");
+
+        let info = self.bucket.info().await;
+
+        // Remove subscribers whose receivers have been dropped.
+        if let Ok(mut subscribers) = self.exhausted.lock() {
+            subscribers.retain(|tx| tx.unbounded_send(info.clone()).is_ok());
+        }
     }
 
     async fn lock_global(&self, wait: u64) {
@@ -243,9 +409,13 @@ impl BucketQueueTask {
     async fn next(&self) -> Option<Sender<Sender<Option<RatelimitHeaders>>>> {
         tracing::debug!(path=?self.path, "starting to get next in queue");
 
+        let queue_tx = self.bucket.queue.pop(Self::WAIT).await?;
+
+        // Check only once a request is queued, since responses may have been
+        // received while waiting.
         self.wait_if_needed().await;
 
-        self.bucket.queue.pop(Self::WAIT).await
+        Some(queue_tx)
     }
 
     async fn wait_if_needed(&self) {
@@ -70,8 +70,10 @@ impl TryFrom<&'_ HeaderMap<HeaderValue>> for RatelimitHeaders {
                 if headers.iter().any(|k| map.contains_key(*k)) {
                     Err(why)
                 } else if map.contains_key("x-ratelimit-global") {
+                    let reset_after = header_float(map, "x-ratelimit-reset-after")?;
+
                     Ok(Self::GlobalLimited {
-                        reset_after: header_int(map, "x-ratelimit-reset-after")?,
+                        reset_after: millis(reset_after),
                     })
                 } else {
                     Ok(Self::None)
@@ -81,7 +83,6 @@ impl TryFrom<&'_ HeaderMap<HeaderValue>> for RatelimitHeaders {
     }
 }
 
-#[allow(clippy::cast_possible_truncation)]
 fn parse_map(map: &HeaderMap<HeaderValue>) -> RatelimitResult<RatelimitHeaders> {
     let bucket = header_str(map, "x-ratelimit-bucket")
         .ok()
@@ -89,12 +90,8 @@ fn parse_map(map: &HeaderMap<HeaderValue>) -> RatelimitResult<RatelimitHeaders>
     let global = header_bool(map, "x-ratelimit-global").unwrap_or(false);
     let limit = header_int(map, "x-ratelimit-limit")?;
     let remaining = header_int(map, "x-ratelimit-remaining")?;
-    let reset = header_float(map, "x-ratelimit-reset")?;
-    #[allow(clippy::cast_sign_loss)]
-    let reset = (reset * 1000.).ceil() as u64;
-    let reset_after = header_float(map, "x-ratelimit-reset-after")?;
-    #[allow(clippy::cast_sign_loss)]
-    let reset_after = (reset_after * 1000.).ceil() as u64;
+    let reset = millis(header_float(map, "x-ratelimit-reset")?);
+    let reset_after = millis(header_float(map, "x-ratelimit-reset-after")?);
 
     Ok(RatelimitHeaders::Present {
         bucket,
@@ -106,6 +103,12 @@ fn parse_map(map: &HeaderMap<HeaderValue>) -> RatelimitResult<RatelimitHeaders>
     })
 }
 
+/// Convert seconds with a fractional part to milliseconds, rounding up.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn millis(seconds: f64) -> u64 {
+    (seconds * 1000.).ceil() as u64
+}
+
 fn header_bool(map: &HeaderMap<HeaderValue>, name: &'static str) -> RatelimitResult<bool> {
     let value = map
         .get(name)
@@ -193,3 +196,67 @@ fn header_str<'a>(map: &'a HeaderMap<HeaderValue>, name: &'static str) -> Rateli
 
     Ok(text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RatelimitHeaders;
+    use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+    use std::convert::TryFrom;
+
+    fn map(headers: &[(&'static str, &'static str)]) -> HeaderMap<HeaderValue> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_present() {
+        let headers = RatelimitHeaders::try_from(&map(&[
+            ("x-ratelimit-bucket", "abcd"),
+            ("x-ratelimit-limit", "5"),
+            ("x-ratelimit-remaining", "4"),
+            ("x-ratelimit-reset", "1560704880.423"),
+            ("x-ratelimit-reset-after", "1.0005"),
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            headers,
+            RatelimitHeaders::Present {
+                bucket: Some(ref bucket),
+                global: false,
+                limit: 5,
+                remaining: 4,
+                reset: 1_560_704_880_423,
+                reset_after: 1001,
+            } if bucket == "abcd"
+        ));
+    }
+
+    #[test]
+    fn test_global_limited() {
+        let headers = RatelimitHeaders::try_from(&map(&[
+            ("x-ratelimit-global", "true"),
+            ("x-ratelimit-reset-after", "1.5"),
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            headers,
+            RatelimitHeaders::GlobalLimited { reset_after: 1500 }
+        ));
+    }
+
+    #[test]
+    fn test_none() {
+        let headers = RatelimitHeaders::try_from(&HeaderMap::new()).unwrap();
+
+        assert!(matches!(headers, RatelimitHeaders::None));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::routing::Path;
+use std::time::Duration;
+
+/// Snapshot of the state of a ratelimit bucket at a point in time.
+///
+/// Values such as the limit are only known once a response with ratelimit
+/// headers has been received for the bucket. Refer to
+/// [`Ratelimiter::buckets`] to retrieve the information of all buckets.
+///
+/// [`Ratelimiter::buckets`]: super::Ratelimiter::buckets
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BucketInfo {
+    /// Hash of the bucket as given by Discord.
+    ///
+    /// Paths with the same hash and major parameter share a bucket. This is
+    /// `None` if the hash isn't known yet.
+    pub bucket: Option<String>,
+    /// Total number of requests that can be made in one cycle of the bucket.
+    ///
+    /// This is `None` if no ratelimit headers have been received yet.
+    pub limit: Option<u64>,
+    /// Ratelimiting path of the bucket.
+    ///
+    /// If the bucket is shared by several paths, then this is the path of the
+    /// request that created it.
+    pub path: Path,
+    /// Number of requests remaining in the current cycle of the bucket.
+    ///
+    /// This is `None` if no ratelimit headers have been received yet.
+    pub remaining: Option<u64>,
+    /// Time until the bucket resets, as given by the most recent response.
+    ///
+    /// This is `None` if no ratelimit headers have been received yet.
+    pub reset_after: Option<Duration>,
+    /// Time remaining until the current cycle of the bucket resets.
+    ///
+    /// This is `None` if the bucket has not started a cycle or if the cycle
+    /// has already finished.
+    pub time_remaining: Option<Duration>,
+}
+
+impl BucketInfo {
+    /// Whether the bucket is exhausted for its current cycle.
+    ///
+    /// Requests made to an exhausted bucket will be queued until the bucket
+    /// resets.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
@@ -2,25 +2,50 @@ pub mod error;
 
 mod bucket;
 mod headers;
+mod info;
 
 pub use self::{
     error::{RatelimitError, RatelimitResult},
     headers::RatelimitHeaders,
+    info::BucketInfo,
 };
 
 use crate::routing::Path;
 use bucket::{Bucket, BucketQueueTask, TimeRemaining};
-use futures_channel::oneshot::{self, Receiver, Sender};
+use futures_channel::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot::{self, Receiver, Sender},
+};
 use futures_util::lock::Mutex;
 use std::{
     collections::hash_map::{Entry, HashMap},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex as StdMutex,
     },
     time::Duration,
 };
 
+/// Hashes of the buckets that paths are known to belong to.
+type BucketHashes = Arc<StdMutex<HashMap<Path, String>>>;
+
+/// Buckets currently in use, by key.
+type Buckets = Arc<Mutex<HashMap<BucketKey, Arc<Bucket>>>>;
+
+/// Senders to notify when a bucket has been exhausted.
+type ExhaustedSubscribers = Arc<StdMutex<Vec<UnboundedSender<BucketInfo>>>>;
+
+/// Key of a bucket.
+///
+/// Paths are keyed by themselves until a response has told us the hash of the
+/// bucket they belong to. From then on paths with the same hash and major
+/// parameter share a bucket.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum BucketKey {
+    Hash { hash: String, major: Option<u64> },
+    Path(Path),
+}
+
 /// Global lock. We use a pair to avoid actually locking the mutex every check.
 /// This allows futures to only wait on the global lock when a global ratelimit
 /// is in place by, in turn, waiting for a guard, and then each immediately
@@ -42,10 +67,20 @@ impl GlobalLockPair {
     }
 }
 
+/// Ratelimiter for requests to Discord's API.
+///
+/// The ratelimiter is driven by the ratelimit headers of responses. The first
+/// request to a path is made on its own to learn of the bucket it belongs to,
+/// after which paths sharing a bucket are queued together, while still being
+/// separated by their major parameter such as the channel or guild ID. Once the
+/// limit of a bucket is known, requests are allowed to be made concurrently up
+/// to the number of requests remaining in the bucket.
 #[derive(Clone, Debug, Default)]
 pub struct Ratelimiter {
-    buckets: Arc<Mutex<HashMap<Path, Arc<Bucket>>>>,
+    buckets: Buckets,
+    exhausted: ExhaustedSubscribers,
     global: Arc<GlobalLockPair>,
+    hashes: BucketHashes,
 }
 
 impl Ratelimiter {
@@ -68,7 +103,9 @@ impl Ratelimiter {
                 BucketQueueTask::new(
                     bucket,
                     Arc::clone(&self.buckets),
+                    Arc::clone(&self.exhausted),
                     Arc::clone(&self.global),
+                    Arc::clone(&self.hashes),
                     path,
                 )
                 .run(),
@@ -78,6 +115,65 @@ impl Ratelimiter {
         rx
     }
 
+    /// Retrieve a snapshot of the state of all current buckets.
+    ///
+    /// Buckets are removed once they have been unused for some time, so only
+    /// recently used paths are included.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use twilight_http::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("my token");
+    ///
+    /// if let Some(ratelimiter) = client.ratelimiter() {
+    ///     for bucket in ratelimiter.buckets().await {
+    ///         println!("{:?}: {:?}/{:?}", bucket.path, bucket.remaining, bucket.limit);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn buckets(&self) -> Vec<BucketInfo> {
+        // Clone the buckets so that the map isn't locked while the state of
+        // each bucket is read.
+        let buckets = self
+            .buckets
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut infos = Vec::with_capacity(buckets.len());
+
+        for bucket in buckets {
+            infos.push(bucket.info().await);
+        }
+
+        infos
+    }
+
+    /// Subscribe to notifications of buckets becoming exhausted.
+    ///
+    /// A bucket is exhausted when no requests remain in its current cycle,
+    /// meaning new requests will wait until the bucket resets. The received
+    /// [`BucketInfo`] is a snapshot of the bucket at the time it was
+    /// exhausted.
+    ///
+    /// Dropping the receiver will unsubscribe.
+    pub fn subscribe_exhausted(&self) -> UnboundedReceiver<BucketInfo> {
+        let (tx, rx) = mpsc::unbounded();
+
+        if let Ok(mut subscribers) = self.exhausted.lock() {
+            subscribers.push(tx);
+        }
+
+        rx
+    }
+
     /// Provide an estimate for the time left until a path can be used
     /// without being ratelimited.
     ///
@@ -85,7 +181,7 @@ impl Ratelimiter {
     /// None if either no ratelimit is known or buckets are remaining.
     pub async fn time_until_available(&self, path: &Path) -> Option<Duration> {
         let buckets = self.buckets.lock().await;
-        match buckets.get(path)?.time_remaining().await {
+        match buckets.get(&self.key(path))?.time_remaining().await {
             TimeRemaining::Finished | TimeRemaining::NotStarted => None,
             TimeRemaining::Some(duration) => Some(duration),
         }
@@ -98,8 +194,9 @@ impl Ratelimiter {
     ) -> (Arc<Bucket>, bool) {
         // nb: not realisically point of contention
         let mut buckets = self.buckets.lock().await;
+        let key = self.key(&path);
 
-        match buckets.entry(path.clone()) {
+        match buckets.entry(key) {
             Entry::Occupied(bucket) => {
                 tracing::debug!("got existing bucket: {:?}", path);
 
@@ -111,7 +208,11 @@ impl Ratelimiter {
             }
             Entry::Vacant(entry) => {
                 tracing::debug!("making new bucket for path: {:?}", path);
-                let bucket = Bucket::new(path.clone());
+                let hash = match entry.key() {
+                    BucketKey::Hash { hash, .. } => Some(hash.clone()),
+                    BucketKey::Path(_) => None,
+                };
+                let bucket = Bucket::new(path.clone(), hash);
                 bucket.queue.push(tx);
 
                 let bucket = Arc::new(bucket);
@@ -121,4 +222,303 @@ impl Ratelimiter {
             }
         }
     }
+
+    /// Key of the bucket of a path.
+    ///
+    /// The buckets should be locked to avoid the hash of a bucket being
+    /// recorded in the meantime.
+    fn key(&self, path: &Path) -> BucketKey {
+        let hash = self
+            .hashes
+            .lock()
+            .ok()
+            .and_then(|hashes| hashes.get(path).cloned());
+
+        match hash {
+            Some(hash) => BucketKey::Hash {
+                hash,
+                major: path.major_parameter(),
+            },
+            None => BucketKey::Path(path.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BucketInfo, RatelimitHeaders, Ratelimiter};
+    use crate::routing::Path;
+    use futures_channel::oneshot::Sender;
+    use futures_util::stream::StreamExt;
+    use hyper::{
+        header::{HeaderMap, HeaderName, HeaderValue},
+        Method,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{convert::TryFrom, fmt::Debug, time::Duration};
+    use tokio::time::{self, timeout};
+
+    assert_impl_all!(BucketInfo: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(Ratelimiter: Clone, Debug, Default, Send, Sync);
+
+    /// Time to wait for a ticket that shouldn't be granted.
+    ///
+    /// Time is paused in tests, so this only elapses once every task is
+    /// waiting.
+    const NOT_GRANTED: Duration = Duration::from_millis(100);
+
+    /// Create a ratelimiter for a test, pausing time so that the test doesn't
+    /// depend on how fast it runs.
+    fn ratelimiter() -> Ratelimiter {
+        time::pause();
+
+        Ratelimiter::new()
+    }
+
+    fn headers(limit: u64, remaining: u64, reset_after: &'static str) -> RatelimitHeaders {
+        bucket_headers("abcd", limit, remaining, reset_after)
+    }
+
+    fn bucket_headers(
+        bucket: &'static str,
+        limit: u64,
+        remaining: u64,
+        reset_after: &'static str,
+    ) -> RatelimitHeaders {
+        let mut map = HeaderMap::new();
+
+        for (name, value) in &[
+            ("x-ratelimit-bucket", bucket),
+            ("x-ratelimit-limit", &*limit.to_string()),
+            ("x-ratelimit-remaining", &*remaining.to_string()),
+            ("x-ratelimit-reset", "1560704880.423"),
+            ("x-ratelimit-reset-after", reset_after),
+        ] {
+            map.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+
+        RatelimitHeaders::try_from(&map).unwrap()
+    }
+
+    /// Mimic the client making a request and receiving the headers of its
+    /// response.
+    async fn request(ratelimiter: &Ratelimiter, path: Path, headers: RatelimitHeaders) {
+        ticket(ratelimiter, path).await.send(Some(headers)).unwrap();
+    }
+
+    /// Wait for the turn of a request to a path to come.
+    async fn ticket(ratelimiter: &Ratelimiter, path: Path) -> Sender<Option<RatelimitHeaders>> {
+        ratelimiter.get(path).await.await.unwrap()
+    }
+
+    /// Whether a request to a path doesn't get its turn for some time.
+    async fn is_queued(ratelimiter: &Ratelimiter, path: Path) -> bool {
+        timeout(NOT_GRANTED, ticket(ratelimiter, path))
+            .await
+            .is_err()
+    }
+
+    async fn bucket(ratelimiter: &Ratelimiter, hash: &str) -> Vec<BucketInfo> {
+        ratelimiter
+            .buckets()
+            .await
+            .into_iter()
+            .filter(|bucket| bucket.bucket.as_deref() == Some(hash))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_buckets_empty() {
+        let ratelimiter = ratelimiter();
+
+        assert!(ratelimiter.buckets().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_buckets() {
+        let ratelimiter = ratelimiter();
+        let path = Path::ChannelsIdMessages(1);
+
+        request(&ratelimiter, path.clone(), headers(5, 4, "10")).await;
+
+        // Wait for a second ticket, which is only granted once the headers of
+        // the first request have been processed.
+        let _tx = ticket(&ratelimiter, path.clone()).await;
+
+        let buckets = ratelimiter.buckets().await;
+        assert_eq!(1, buckets.len());
+
+        let bucket = &buckets[0];
+        assert_eq!(Some("abcd"), bucket.bucket.as_deref());
+        assert_eq!(path, bucket.path);
+        assert_eq!(Some(5), bucket.limit);
+        // The second request has been reserved.
+        assert_eq!(Some(3), bucket.remaining);
+        assert_eq!(Some(Duration::from_secs(10)), bucket.reset_after);
+        assert!(bucket.time_remaining.unwrap() <= Duration::from_secs(10));
+        assert!(!bucket.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_exhausted() {
+        let ratelimiter = ratelimiter();
+        let mut exhausted = ratelimiter.subscribe_exhausted();
+        let path = Path::ChannelsIdMessages(1);
+
+        request(&ratelimiter, path.clone(), headers(5, 0, "10")).await;
+
+        let bucket = exhausted.next().await.unwrap();
+        assert_eq!(path, bucket.path);
+        assert_eq!(Some(5), bucket.limit);
+        assert_eq!(Some(0), bucket.remaining);
+        assert!(bucket.is_exhausted());
+
+        let buckets = ratelimiter.buckets().await;
+        assert_eq!(1, buckets.len());
+        assert!(buckets[0].is_exhausted());
+    }
+
+    /// The first request to a path is made on its own, while requests after
+    /// it may be made concurrently up to the remaining count.
+    #[tokio::test]
+    async fn test_burst() {
+        let ratelimiter = ratelimiter();
+        let path = Path::ChannelsIdMessages(1);
+
+        let probe = ticket(&ratelimiter, path.clone()).await;
+        assert!(is_queued(&ratelimiter, path.clone()).await);
+        probe.send(Some(headers(5, 4, "10"))).unwrap();
+
+        // The queued request above was dropped, the next four requests may be
+        // in flight at the same time.
+        let mut in_flight = Vec::new();
+
+        for _ in 0..4 {
+            in_flight.push(ticket(&ratelimiter, path.clone()).await);
+        }
+
+        assert!(is_queued(&ratelimiter, path.clone()).await);
+        assert!(bucket(&ratelimiter, "abcd").await[0].is_exhausted());
+
+        // Responses arriving out of order don't give back remaining requests.
+        for (tx, remaining) in in_flight.into_iter().zip(&[1, 3, 0, 2]) {
+            tx.send(Some(headers(5, *remaining, "10"))).unwrap();
+        }
+
+        assert!(is_queued(&ratelimiter, path).await);
+        assert_eq!(Some(0), bucket(&ratelimiter, "abcd").await[0].remaining);
+    }
+
+    /// The limit and reset of a bucket are updated by every response, such as
+    /// when the limit of a bucket is lowered by Discord.
+    #[tokio::test]
+    async fn test_limit_reduced() {
+        let ratelimiter = ratelimiter();
+        let mut exhausted = ratelimiter.subscribe_exhausted();
+        let path = Path::ChannelsIdMessages(1);
+
+        request(&ratelimiter, path.clone(), headers(5, 4, "10")).await;
+        let first = ticket(&ratelimiter, path.clone()).await;
+        let second = ticket(&ratelimiter, path.clone()).await;
+
+        // The response is handled in the background while the second request
+        // is in flight.
+        first.send(Some(headers(2, 0, "0.3"))).unwrap();
+        exhausted.next().await.unwrap();
+        assert!(is_queued(&ratelimiter, path.clone()).await);
+
+        let info = &bucket(&ratelimiter, "abcd").await[0];
+        assert_eq!(Some(2), info.limit);
+        assert_eq!(Some(0), info.remaining);
+        assert_eq!(Some(Duration::from_millis(300)), info.reset_after);
+
+        second.send(None).unwrap();
+        time::advance(Duration::from_millis(300)).await;
+
+        // Once the bucket has reset, only the new limit is available: the
+        // first request probes the new cycle and the second may be made
+        // concurrently.
+        let probe = ticket(&ratelimiter, path.clone()).await;
+        probe.send(Some(headers(2, 1, "0.3"))).unwrap();
+        let _tx = ticket(&ratelimiter, path.clone()).await;
+
+        assert!(is_queued(&ratelimiter, path).await);
+    }
+
+    /// Paths that Discord reports to be in the same bucket share the bucket.
+    #[tokio::test]
+    async fn test_bucket_hash_shared() {
+        let ratelimiter = ratelimiter();
+        let create = Path::ChannelsIdMessages(1);
+        let delete = Path::ChannelsIdMessagesId(Method::DELETE, 1);
+
+        request(&ratelimiter, create.clone(), headers(2, 1, "10")).await;
+        // Learning the bucket of the path also requires a request.
+        request(&ratelimiter, delete.clone(), headers(2, 1, "10")).await;
+
+        let _tx = ticket(&ratelimiter, create.clone()).await;
+
+        // The first path used up the last remaining request.
+        assert!(is_queued(&ratelimiter, delete).await);
+
+        // The bucket the second path was probed with was removed once its
+        // hash was known.
+        let buckets = ratelimiter.buckets().await;
+        assert_eq!(1, buckets.len());
+        assert_eq!(Some("abcd"), buckets[0].bucket.as_deref());
+        assert_eq!(create, buckets[0].path);
+    }
+
+    /// Paths in the same bucket with different major parameters don't share
+    /// the bucket.
+    #[tokio::test]
+    async fn test_bucket_hash_major_parameter() {
+        let ratelimiter = ratelimiter();
+
+        for id in 1..=2 {
+            let path = Path::ChannelsIdMessages(id);
+            request(&ratelimiter, path.clone(), headers(1, 0, "10")).await;
+
+            assert!(is_queued(&ratelimiter, path).await);
+        }
+
+        let mut paths = bucket(&ratelimiter, "abcd")
+            .await
+            .into_iter()
+            .map(|bucket| bucket.path)
+            .collect::<Vec<_>>();
+        paths.sort_by_key(Path::major_parameter);
+
+        assert_eq!(
+            vec![Path::ChannelsIdMessages(1), Path::ChannelsIdMessages(2)],
+            paths
+        );
+    }
+
+    /// Paths in different buckets don't delay each other.
+    #[tokio::test]
+    async fn test_bucket_hash_different() {
+        let ratelimiter = ratelimiter();
+        let messages = Path::ChannelsIdMessages(1);
+        let pins = Path::ChannelsIdPins(1);
+
+        request(
+            &ratelimiter,
+            messages.clone(),
+            bucket_headers("abcd", 1, 0, "10"),
+        )
+        .await;
+        request(
+            &ratelimiter,
+            pins.clone(),
+            bucket_headers("efgh", 1, 1, "10"),
+        )
+        .await;
+
+        assert!(is_queued(&ratelimiter, messages).await);
+        assert!(!is_queued(&ratelimiter, pins).await);
+    }
 }
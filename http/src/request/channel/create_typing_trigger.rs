@@ -2,6 +2,20 @@ use crate::request::prelude::*;
 use twilight_model::id::ChannelId;
 
 /// Fire a Typing Start event in the channel.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twilight_http::Client;
+/// use twilight_model::id::ChannelId;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// client.create_typing_trigger(ChannelId(123)).await?;
+/// # Ok(()) }
+/// ```
 pub struct CreateTypingTrigger<'a> {
     channel_id: ChannelId,
     fut: Option<Pending<'a, ()>>,
@@ -17,15 +31,59 @@ impl<'a> CreateTypingTrigger<'a> {
         }
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::CreateTypingTrigger {
+            channel_id: self.channel_id.0,
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.verify(Request::from(
-            Route::CreateTypingTrigger {
-                channel_id: self.channel_id.0,
-            },
-        ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.verify(request)));
 
         Ok(())
     }
 }
 
 poll_req!(CreateTypingTrigger<'_>, ());
+
+#[cfg(test)]
+mod tests {
+    use super::CreateTypingTrigger;
+    use crate::{routing::Path, Client};
+    use hyper::{
+        header::{HeaderValue, CONTENT_LENGTH},
+        Method,
+    };
+    use twilight_model::id::ChannelId;
+
+    #[test]
+    fn test_request() {
+        let client = Client::new("foo");
+        let builder = CreateTypingTrigger::new(&client, ChannelId(123));
+        let actual = builder.request();
+
+        assert!(actual.body.is_none());
+        assert!(actual.form.is_none());
+        assert_eq!(Method::POST, actual.method);
+        assert_eq!(Path::ChannelsIdTyping(123), actual.path);
+        assert_eq!("channels/123/typing", actual.path_str);
+    }
+
+    /// Hyper doesn't set a content length for empty bodies, which Discord
+    /// requires for POST, PUT, and PATCH requests.
+    #[test]
+    fn test_request_content_length() {
+        let client = Client::new("foo");
+        let builder = CreateTypingTrigger::new(&client, ChannelId(123));
+        let (request, _) = client
+            .hyper_request(builder.request())
+            .expect("failed to build request");
+
+        assert_eq!(&Method::POST, request.method());
+        assert_eq!(
+            Some(&HeaderValue::from_static("0")),
+            request.headers().get(CONTENT_LENGTH)
+        );
+    }
+}
@@ -1,10 +1,33 @@
 use super::DeleteChannelPermissionConfigured;
 use crate::request::prelude::*;
-use twilight_model::id::{ChannelId, RoleId, UserId};
+use twilight_model::{
+    channel::permission_overwrite::PermissionOverwriteType,
+    id::{ChannelId, RoleId, UserId},
+};
 
 /// Clear the permissions for a target ID in a channel.
 ///
 /// The target ID must be set with one of the associated methods.
+///
+/// # Examples
+///
+/// Delete the permission overwrite of a role in a channel:
+///
+/// ```rust,no_run
+/// use twilight_http::{request::AuditLogReason, Client};
+/// use twilight_model::id::{ChannelId, RoleId};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// client
+///     .delete_channel_permission(ChannelId(123))
+///     .role(RoleId(432))
+///     .reason("role no longer needs access")?
+///     .await?;
+/// # Ok(()) }
+/// ```
 pub struct DeleteChannelPermission<'a> {
     channel_id: ChannelId,
     http: &'a Client,
@@ -25,6 +48,17 @@ impl<'a> DeleteChannelPermission<'a> {
         self.configure(role_id.into().0)
     }
 
+    /// Delete the override of a target, such as the target of an existing
+    /// [`PermissionOverwrite`].
+    ///
+    /// [`PermissionOverwrite`]: twilight_model::channel::permission_overwrite::PermissionOverwrite
+    pub fn target(self, target: &PermissionOverwriteType) -> DeleteChannelPermissionConfigured<'a> {
+        match target {
+            PermissionOverwriteType::Member(user_id) => self.member(*user_id),
+            PermissionOverwriteType::Role(role_id) => self.role(*role_id),
+        }
+    }
+
     fn configure(self, target_id: u64) -> DeleteChannelPermissionConfigured<'a> {
         DeleteChannelPermissionConfigured::new(self.http, self.channel_id, target_id)
     }
@@ -23,8 +23,8 @@ impl<'a> DeleteChannelPermissionConfigured<'a> {
         }
     }
 
-    fn start(&mut self) -> Result<()> {
-        let request = if let Some(reason) = &self.reason {
+    fn request(&self) -> Result<Request> {
+        Ok(if let Some(reason) = &self.reason {
             let headers = audit_header(&reason)?;
             Request::from((
                 headers,
@@ -38,7 +38,11 @@ impl<'a> DeleteChannelPermissionConfigured<'a> {
                 channel_id: self.channel_id.0,
                 target_id: self.target_id,
             })
-        };
+        })
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let request = self.request()?;
 
         self.fut.replace(Box::pin(self.http.verify(request)));
 
@@ -56,3 +60,47 @@ impl<'a> AuditLogReason for DeleteChannelPermissionConfigured<'a> {
 }
 
 poll_req!(DeleteChannelPermissionConfigured<'_>, ());
+
+#[cfg(test)]
+mod tests {
+    use crate::{request::AuditLogReason, routing::Path, Client};
+    use hyper::Method;
+    use twilight_model::{
+        channel::permission_overwrite::PermissionOverwriteType,
+        id::{ChannelId, UserId},
+    };
+
+    #[test]
+    fn test_request() {
+        let client = Client::new("foo");
+        let builder = client
+            .delete_channel_permission(ChannelId(1))
+            .target(&PermissionOverwriteType::Member(UserId(2)));
+        let request = builder.request().expect("failed to create request");
+
+        assert_eq!(Method::DELETE, request.method);
+        assert_eq!(Path::ChannelsIdPermissionsOverwriteId(1), request.path);
+        assert_eq!("channels/1/permissions/2", request.path_str);
+        assert!(request.headers.is_none());
+    }
+
+    #[test]
+    fn test_reason() {
+        let client = Client::new("foo");
+        let builder = client
+            .delete_channel_permission(ChannelId(1))
+            .member(UserId(2))
+            .reason("sync")
+            .expect("reason is valid");
+        let request = builder.request().expect("failed to create request");
+
+        assert_eq!(
+            "sync",
+            request
+                .headers
+                .expect("request has headers")
+                .get("x-audit-log-reason")
+                .expect("reason header is set")
+        );
+    }
+}
@@ -1,9 +1,88 @@
 use crate::request::prelude::*;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
 use twilight_model::{
-    id::{ChannelId, UserId},
-    invite::{Invite, TargetUserType},
+    id::{ApplicationId, ChannelId, UserId},
+    invite::{Invite, TargetType},
 };
 
+/// The error created when an invite can not be created as configured.
+#[derive(Debug)]
+pub struct CreateInviteError {
+    kind: CreateInviteErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl CreateInviteError {
+    /// Immutable reference to the type of error that occurred.
+    pub const fn kind(&self) -> &CreateInviteErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    pub fn into_parts(self) -> (CreateInviteErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+
+    const fn new(kind: CreateInviteErrorType) -> Self {
+        Self { kind, source: None }
+    }
+}
+
+impl Display for CreateInviteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            CreateInviteErrorType::MaxAgeInvalid { max_age } => write!(
+                f,
+                "the max age is {}, but it must be at most 604800 seconds",
+                max_age
+            ),
+            CreateInviteErrorType::MaxUsesInvalid { max_uses } => write!(
+                f,
+                "the max uses is {}, but it must be at most 100",
+                max_uses
+            ),
+            CreateInviteErrorType::TargetConflict => {
+                f.write_str("the invite can't target both a user and an application")
+            }
+        }
+    }
+}
+
+impl Error for CreateInviteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`CreateInviteError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CreateInviteErrorType {
+    /// The maximum age is more than 604800 seconds, or 7 days.
+    MaxAgeInvalid {
+        /// Provided maximum age.
+        max_age: u64,
+    },
+    /// The maximum number of uses is more than 100.
+    MaxUsesInvalid {
+        /// Provided maximum number of uses.
+        max_uses: u64,
+    },
+    /// A target user was set when a target application already was, or the
+    /// other way around.
+    TargetConflict,
+}
+
 #[derive(Default, Serialize)]
 struct CreateInviteFields {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,19 +90,23 @@ struct CreateInviteFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_uses: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    temporary: Option<bool>,
+    target_application_id: Option<ApplicationId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    unique: Option<bool>,
+    target_type: Option<TargetType>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    target_user: Option<String>,
+    target_user_id: Option<UserId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    target_user_type: Option<TargetUserType>,
+    temporary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
 }
 
 /// Create an invite, with options.
 ///
 /// # Examples
 ///
+/// Create a unique invite that can only be used once:
+///
 /// ```rust,no_run
 /// use twilight_http::Client;
 /// use twilight_model::id::ChannelId;
@@ -35,7 +118,8 @@ struct CreateInviteFields {
 /// let channel_id = ChannelId(123);
 /// let invite = client
 ///     .create_invite(channel_id)
-///     .max_uses(3)
+///     .max_uses(1)?
+///     .unique(true)
 ///     .await?;
 /// # Ok(()) }
 /// ```
@@ -61,20 +145,42 @@ impl<'a> CreateInvite<'a> {
     /// Set the maximum age for an invite.
     ///
     /// If no age is specified, Discord sets the age to 86400 seconds, or 24 hours.
-    /// Set to 0 to never expire.
-    pub fn max_age(mut self, max_age: u64) -> Self {
+    /// Set to 0 to never expire. The maximum is 604800 seconds, or 7 days.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateInviteErrorType::MaxAgeInvalid`] error type if the
+    /// age is more than 604800 seconds.
+    pub fn max_age(mut self, max_age: u64) -> Result<Self, CreateInviteError> {
+        if !validate::invite_max_age(max_age) {
+            return Err(CreateInviteError::new(
+                CreateInviteErrorType::MaxAgeInvalid { max_age },
+            ));
+        }
+
         self.fields.max_age.replace(max_age);
 
-        self
+        Ok(self)
     }
 
     /// Set the maximum uses for an invite, or 0 for infinite.
     ///
-    /// Discord defaults this to 0, or infinite.
-    pub fn max_uses(mut self, max_uses: u64) -> Self {
+    /// Discord defaults this to 0, or infinite. The maximum is 100.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateInviteErrorType::MaxUsesInvalid`] error type if the
+    /// number of uses is more than 100.
+    pub fn max_uses(mut self, max_uses: u64) -> Result<Self, CreateInviteError> {
+        if !validate::invite_max_uses(max_uses) {
+            return Err(CreateInviteError::new(
+                CreateInviteErrorType::MaxUsesInvalid { max_uses },
+            ));
+        }
+
         self.fields.max_uses.replace(max_uses);
 
-        self
+        Ok(self)
     }
 
     /// Specify true if the invite should grant temporary membership. Defaults to false.
@@ -96,22 +202,57 @@ impl<'a> CreateInvite<'a> {
         self
     }
 
-    /// Set the target user for this invite.
-    pub fn target_user(mut self, target_user: UserId) -> Self {
-        self.fields.target_user.replace(target_user.0.to_string());
+    /// Set the embedded application to open for this invite to a voice
+    /// channel.
+    ///
+    /// The application must have the `EMBEDDED` flag.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateInviteErrorType::TargetConflict`] error type if a
+    /// target user has already been set.
+    pub fn target_application(
+        mut self,
+        application_id: ApplicationId,
+    ) -> Result<Self, CreateInviteError> {
+        if self.fields.target_user_id.is_some() {
+            return Err(CreateInviteError::new(
+                CreateInviteErrorType::TargetConflict,
+            ));
+        }
 
-        self
+        self.fields.target_application_id.replace(application_id);
+        self.fields
+            .target_type
+            .replace(TargetType::EmbeddedApplication);
+
+        Ok(self)
     }
 
-    /// Set the target user type for this invite.
-    pub fn target_user_type(mut self, target_user_type: TargetUserType) -> Self {
-        self.fields.target_user_type.replace(target_user_type);
+    /// Set the user whose stream to display for this invite to a voice
+    /// channel.
+    ///
+    /// The user must be streaming in the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateInviteErrorType::TargetConflict`] error type if a
+    /// target application has already been set.
+    pub fn target_user(mut self, user_id: UserId) -> Result<Self, CreateInviteError> {
+        if self.fields.target_application_id.is_some() {
+            return Err(CreateInviteError::new(
+                CreateInviteErrorType::TargetConflict,
+            ));
+        }
 
-        self
+        self.fields.target_user_id.replace(user_id);
+        self.fields.target_type.replace(TargetType::Stream);
+
+        Ok(self)
     }
 
-    fn start(&mut self) -> Result<()> {
-        let request = if let Some(reason) = &self.reason {
+    fn request(&self) -> Result<Request> {
+        Ok(if let Some(reason) = &self.reason {
             let headers = audit_header(&reason)?;
             Request::from((
                 crate::json_to_vec(&self.fields)?,
@@ -127,8 +268,11 @@ impl<'a> CreateInvite<'a> {
                     channel_id: self.channel_id.0,
                 },
             ))
-        };
+        })
+    }
 
+    fn start(&mut self) -> Result<()> {
+        let request = self.request()?;
         self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
@@ -145,3 +289,127 @@ impl<'a> AuditLogReason for CreateInvite<'a> {
 }
 
 poll_req!(CreateInvite<'_>, Invite);
+
+#[cfg(test)]
+mod tests {
+    use super::{CreateInvite, CreateInviteError, CreateInviteErrorType};
+    use crate::{request::AuditLogReason, Client};
+    use std::error::Error;
+    use twilight_model::id::{ApplicationId, ChannelId, UserId};
+
+    fn body(builder: &CreateInvite<'_>) -> String {
+        let request = builder.request().expect("failed to create request");
+
+        String::from_utf8(request.body.expect("request has a body")).unwrap()
+    }
+
+    fn kind(result: Result<CreateInvite<'_>, CreateInviteError>) -> CreateInviteErrorType {
+        result.map(|_| ()).unwrap_err().into_parts().0
+    }
+
+    #[test]
+    fn test_max_age() {
+        let client = Client::new("foo");
+
+        assert!(CreateInvite::new(&client, ChannelId(1)).max_age(0).is_ok());
+        assert!(CreateInvite::new(&client, ChannelId(1))
+            .max_age(604_800)
+            .is_ok());
+        assert!(matches!(
+            kind(CreateInvite::new(&client, ChannelId(1)).max_age(604_801)),
+            CreateInviteErrorType::MaxAgeInvalid { max_age: 604_801 }
+        ));
+    }
+
+    #[test]
+    fn test_max_uses() {
+        let client = Client::new("foo");
+
+        assert!(CreateInvite::new(&client, ChannelId(1)).max_uses(0).is_ok());
+        assert!(CreateInvite::new(&client, ChannelId(1))
+            .max_uses(100)
+            .is_ok());
+        assert!(matches!(
+            kind(CreateInvite::new(&client, ChannelId(1)).max_uses(101)),
+            CreateInviteErrorType::MaxUsesInvalid { max_uses: 101 }
+        ));
+    }
+
+    #[test]
+    fn test_target_conflict() -> Result<(), Box<dyn Error>> {
+        let client = Client::new("foo");
+
+        assert!(matches!(
+            kind(
+                CreateInvite::new(&client, ChannelId(1))
+                    .target_user(UserId(2))?
+                    .target_application(ApplicationId(3))
+            ),
+            CreateInviteErrorType::TargetConflict
+        ));
+        assert!(matches!(
+            kind(
+                CreateInvite::new(&client, ChannelId(1))
+                    .target_application(ApplicationId(3))?
+                    .target_user(UserId(2))
+            ),
+            CreateInviteErrorType::TargetConflict
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_single_use() -> Result<(), Box<dyn Error>> {
+        let client = Client::new("foo");
+        let builder = CreateInvite::new(&client, ChannelId(1))
+            .max_uses(1)?
+            .unique(true);
+
+        assert_eq!(r#"{"max_uses":1,"unique":true}"#, body(&builder));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_targets() -> Result<(), Box<dyn Error>> {
+        let client = Client::new("foo");
+
+        let stream = CreateInvite::new(&client, ChannelId(1))
+            .max_age(0)?
+            .temporary(true)
+            .target_user(UserId(2))?;
+        assert_eq!(
+            r#"{"max_age":0,"target_type":1,"target_user_id":"2","temporary":true}"#,
+            body(&stream)
+        );
+
+        let application =
+            CreateInvite::new(&client, ChannelId(1)).target_application(ApplicationId(3))?;
+        assert_eq!(
+            r#"{"target_application_id":"3","target_type":2}"#,
+            body(&application)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reason() -> Result<(), Box<dyn Error>> {
+        let client = Client::new("foo");
+        let builder = CreateInvite::new(&client, ChannelId(1)).reason("verification")?;
+        let request = builder.request()?;
+
+        assert_eq!(
+            "verification",
+            request
+                .headers
+                .expect("request has headers")
+                .get("x-audit-log-reason")
+                .expect("reason header is set")
+        );
+        assert_eq!("channels/1/invites", request.path_str);
+
+        Ok(())
+    }
+}
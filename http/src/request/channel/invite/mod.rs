@@ -1,4 +1,4 @@
-mod create_invite;
+pub mod create_invite;
 mod delete_invite;
 mod get_channel_invites;
 mod get_invite;
@@ -0,0 +1,369 @@
+use crate::request::prelude::*;
+use futures_util::stream::Stream;
+use std::{
+    cmp::Reverse,
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use twilight_model::{
+    channel::Message,
+    id::{ChannelId, MessageId},
+};
+
+/// Direction to walk through the history of a channel in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Direction {
+    /// From newer to older messages.
+    Backwards,
+    /// From older to newer messages.
+    Forwards,
+}
+
+/// Stream of the messages in a channel's history, requesting pages of
+/// messages as needed.
+///
+/// By default the history is walked backwards from the most recent message,
+/// but it can instead be walked backwards from [`before`] or forwards from
+/// [`after`] a message. The stream ends once the start or end of the history
+/// is reached, or once a cutoff set via [`until`] or [`max_messages`] is
+/// reached.
+///
+/// Requests are made through the client and so respect its ratelimiter. If a
+/// request fails, then the error is yielded and the stream ends.
+///
+/// Created via [`GetChannelMessages::history`].
+///
+/// # Examples
+///
+/// Collect the IDs of the last 1000 messages of a channel:
+///
+/// ```rust,no_run
+/// use futures_util::stream::TryStreamExt;
+/// use twilight_http::Client;
+/// use twilight_model::id::ChannelId;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// let ids = client
+///     .channel_messages(ChannelId(123))
+///     .history()
+///     .max_messages(1000)
+///     .map_ok(|message| message.id)
+///     .try_collect::<Vec<_>>()
+///     .await?;
+/// # Ok(()) }
+/// ```
+///
+/// [`GetChannelMessages::history`]: super::GetChannelMessages::history
+/// [`after`]: Self::after
+/// [`before`]: Self::before
+/// [`max_messages`]: Self::max_messages
+/// [`until`]: Self::until
+pub struct ChannelHistory<'a> {
+    buffer: VecDeque<Message>,
+    channel_id: ChannelId,
+    cursor: Option<MessageId>,
+    direction: Direction,
+    finished: bool,
+    fut: Option<Pending<'a, Vec<Message>>>,
+    http: &'a Client,
+    page_size: u64,
+    remaining: Option<u64>,
+    requested: u64,
+    until: Option<MessageId>,
+}
+
+impl<'a> ChannelHistory<'a> {
+    /// Maximum number of messages that can be retrieved per request.
+    const PAGE_SIZE: u64 = 100;
+
+    pub(crate) fn new(http: &'a Client, channel_id: ChannelId, page_size: Option<u64>) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            channel_id,
+            cursor: None,
+            direction: Direction::Backwards,
+            finished: false,
+            fut: None,
+            http,
+            page_size: page_size.unwrap_or(Self::PAGE_SIZE),
+            remaining: None,
+            requested: 0,
+            until: None,
+        }
+    }
+
+    /// Walk forwards through the history, starting after a message.
+    ///
+    /// This replaces a previously set [`before`].
+    ///
+    /// [`before`]: Self::before
+    pub fn after(mut self, message_id: MessageId) -> Self {
+        self.cursor.replace(message_id);
+        self.direction = Direction::Forwards;
+
+        self
+    }
+
+    /// Walk backwards through the history, starting before a message.
+    ///
+    /// This replaces a previously set [`after`].
+    ///
+    /// [`after`]: Self::after
+    pub fn before(mut self, message_id: MessageId) -> Self {
+        self.cursor.replace(message_id);
+        self.direction = Direction::Backwards;
+
+        self
+    }
+
+    /// Set the maximum number of messages to yield.
+    ///
+    /// Fewer messages are requested on the last page when fewer are needed.
+    pub fn max_messages(mut self, max_messages: u64) -> Self {
+        self.remaining.replace(max_messages);
+
+        self
+    }
+
+    /// Stop once a message is reached, without yielding it.
+    ///
+    /// When walking backwards this is the case for messages older than or
+    /// equal to the message, and when walking forwards messages newer than or
+    /// equal to it.
+    pub fn until(mut self, message_id: MessageId) -> Self {
+        self.until.replace(message_id);
+
+        self
+    }
+
+    /// Request to retrieve the next page of messages, if there is one.
+    fn next_request(&mut self) -> Option<Request> {
+        if self.finished {
+            return None;
+        }
+
+        let limit = self
+            .remaining
+            .map_or(self.page_size, |remaining| remaining.min(self.page_size));
+
+        if limit == 0 {
+            self.finished = true;
+
+            return None;
+        }
+
+        self.requested = limit;
+        let cursor = self.cursor.map(|id| id.0);
+
+        let (after, before) = match self.direction {
+            Direction::Backwards => (None, cursor),
+            Direction::Forwards => (cursor, None),
+        };
+
+        Some(Request::from(Route::GetMessages {
+            after,
+            around: None,
+            before,
+            channel_id: self.channel_id.0,
+            limit: Some(limit),
+        }))
+    }
+
+    /// Buffer a page of messages to be yielded and move the cursor past it.
+    fn push_page(&mut self, mut page: Vec<Message>) {
+        // A page shorter than requested means the end of the history.
+        if (page.len() as u64) < self.requested {
+            self.finished = true;
+        }
+
+        match self.direction {
+            Direction::Backwards => page.sort_unstable_by_key(|message| Reverse(message.id)),
+            Direction::Forwards => page.sort_unstable_by_key(|message| message.id),
+        }
+
+        for message in page {
+            let cutoff = match (self.direction, self.until) {
+                (Direction::Backwards, Some(until)) => message.id <= until,
+                (Direction::Forwards, Some(until)) => message.id >= until,
+                (_, None) => false,
+            };
+
+            if cutoff || self.remaining == Some(0) {
+                self.finished = true;
+
+                break;
+            }
+
+            if let Some(remaining) = self.remaining.as_mut() {
+                *remaining -= 1;
+            }
+
+            self.cursor.replace(message.id);
+            self.buffer.push_back(message);
+        }
+    }
+}
+
+impl Stream for ChannelHistory<'_> {
+    type Item = Result<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(message) = self.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(message)));
+            }
+
+            if let Some(fut) = self.fut.as_mut() {
+                let result = match fut.as_mut().poll(cx) {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => return Poll::Pending,
+                };
+
+                self.fut = None;
+
+                match result {
+                    Ok(page) => self.push_page(page),
+                    Err(source) => {
+                        self.finished = true;
+
+                        return Poll::Ready(Some(Err(source)));
+                    }
+                }
+
+                continue;
+            }
+
+            let request = match self.next_request() {
+                Some(request) => request,
+                None => return Poll::Ready(None),
+            };
+
+            let http = self.http;
+            self.fut.replace(Box::pin(http.request(request)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelHistory;
+    use crate::Client;
+    use serde_json::json;
+    use twilight_model::{
+        channel::Message,
+        id::{ChannelId, MessageId},
+    };
+
+    fn page(ids: &[u64]) -> Vec<Message> {
+        ids.iter()
+            .map(|id| {
+                serde_json::from_value(json!({
+                    "attachments": [],
+                    "author": {
+                        "avatar": null,
+                        "discriminator": "0001",
+                        "id": "2",
+                        "username": "test",
+                    },
+                    "channel_id": "1",
+                    "content": "",
+                    "edited_timestamp": null,
+                    "embeds": [],
+                    "id": id.to_string(),
+                    "mention_everyone": false,
+                    "mention_roles": [],
+                    "mentions": [],
+                    "pinned": false,
+                    "timestamp": "2021-01-01T00:00:00.000000+00:00",
+                    "tts": false,
+                    "type": 0,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn next_path(history: &mut ChannelHistory<'_>) -> Option<String> {
+        history
+            .next_request()
+            .map(|request| request.path_str.into_owned())
+    }
+
+    fn buffered(history: &ChannelHistory<'_>) -> Vec<u64> {
+        history.buffer.iter().map(|message| message.id.0).collect()
+    }
+
+    #[test]
+    fn test_walk_backwards() {
+        let client = Client::new("foo");
+        let mut history = ChannelHistory::new(&client, ChannelId(1), Some(2));
+
+        assert_eq!(
+            Some("channels/1/messages?&limit=2"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&[10, 9]));
+        assert_eq!(
+            Some("channels/1/messages?&before=9&limit=2"),
+            next_path(&mut history).as_deref()
+        );
+        // Pages may not be sorted.
+        history.push_page(page(&[7, 8]));
+        assert_eq!(
+            Some("channels/1/messages?&before=7&limit=2"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&[6]));
+
+        assert!(next_path(&mut history).is_none());
+        assert_eq!(vec![10, 9, 8, 7, 6], buffered(&history));
+    }
+
+    #[test]
+    fn test_walk_forwards_until() {
+        let client = Client::new("foo");
+        let mut history = ChannelHistory::new(&client, ChannelId(1), Some(2))
+            .after(MessageId(5))
+            .until(MessageId(9));
+
+        assert_eq!(
+            Some("channels/1/messages?after=5&limit=2"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&[7, 6]));
+        assert_eq!(
+            Some("channels/1/messages?after=7&limit=2"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&[9, 8]));
+
+        assert!(next_path(&mut history).is_none());
+        assert_eq!(vec![6, 7, 8], buffered(&history));
+    }
+
+    #[test]
+    fn test_max_messages() {
+        let client = Client::new("foo");
+        let mut history = ChannelHistory::new(&client, ChannelId(1), None)
+            .before(MessageId(500))
+            .max_messages(150);
+
+        assert_eq!(
+            Some("channels/1/messages?&before=500&limit=100"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&(400..500).rev().collect::<Vec<_>>()));
+        assert_eq!(
+            Some("channels/1/messages?&before=400&limit=50"),
+            next_path(&mut history).as_deref()
+        );
+        history.push_page(page(&(350..400).rev().collect::<Vec<_>>()));
+
+        assert!(next_path(&mut history).is_none());
+        assert_eq!(150, history.buffer.len());
+    }
+}
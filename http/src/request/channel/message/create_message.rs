@@ -1,57 +1,145 @@
 use super::super::allowed_mentions::{AllowedMentions, AllowedMentionsBuilder, Unspecified};
-use crate::request::{multipart::Form, prelude::*};
+use crate::{
+    error::Error,
+    request::{multipart::Form, prelude::*},
+};
 use std::{
     collections::HashMap,
-    error::Error,
+    error::Error as StdError,
     fmt::{Display, Formatter, Result as FmtResult},
 };
 use twilight_model::{
-    channel::{embed::Embed, message::MessageReference, Message},
+    channel::{
+        embed::Embed,
+        message::{sticker::StickerId, MessageReference},
+        Message,
+    },
     id::{ChannelId, MessageId},
 };
 
 /// The error created when a messsage can not be created as configured.
-#[derive(Clone, Debug)]
-#[non_exhaustive]
-pub enum CreateMessageError {
-    /// Returned when the content is over 2000 UTF-16 characters.
-    ContentInvalid {
-        /// Provided content.
-        content: String,
-    },
-    /// Returned when the length of the embed is over 6000 characters.
-    EmbedTooLarge {
-        /// Provided embed.
-        embed: Box<Embed>,
-        /// The source of the error.
-        source: EmbedValidationError,
-    },
+#[derive(Debug)]
+pub struct CreateMessageError {
+    kind: CreateMessageErrorType,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+}
+
+impl CreateMessageError {
+    /// The maximum length of the content in codepoints.
+    pub const CONTENT_LENGTH: usize = 2000;
+
+    /// The maximum number of embeds in a message.
+    pub const EMBED_COUNT: usize = 10;
+
+    /// The maximum number of stickers in a message.
+    pub const STICKER_COUNT: usize = 3;
+
+    /// Immutable reference to the type of error that occurred.
+    pub const fn kind(&self) -> &CreateMessageErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    pub fn into_source(self) -> Option<Box<dyn StdError + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    pub fn into_parts(
+        self,
+    ) -> (
+        CreateMessageErrorType,
+        Option<Box<dyn StdError + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
 }
 
 impl Display for CreateMessageError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        match self {
-            Self::ContentInvalid { .. } => f.write_str("the message content is invalid"),
-            Self::EmbedTooLarge { .. } => f.write_str("the embed's contents are too long"),
+        match &self.kind {
+            CreateMessageErrorType::ContentInvalid { chars, .. } => write!(
+                f,
+                "the message content is {} characters long, but the max is {}",
+                chars,
+                Self::CONTENT_LENGTH
+            ),
+            CreateMessageErrorType::EmbedTooLarge { .. } => {
+                f.write_str("the embed's contents are too long")
+            }
+            CreateMessageErrorType::MessageEmpty => {
+                f.write_str("the message has no content, embeds, stickers, or attachments")
+            }
+            CreateMessageErrorType::TooManyEmbeds { embeds } => write!(
+                f,
+                "there are {} embeds, but the maximum amount is {}",
+                embeds.len(),
+                Self::EMBED_COUNT
+            ),
+            CreateMessageErrorType::TooManyStickers { sticker_ids } => write!(
+                f,
+                "there are {} stickers, but the maximum amount is {}",
+                sticker_ids.len(),
+                Self::STICKER_COUNT
+            ),
         }
     }
 }
 
-impl Error for CreateMessageError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match self {
-            Self::ContentInvalid { .. } => None,
-            Self::EmbedTooLarge { source, .. } => Some(source),
-        }
+impl StdError for CreateMessageError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn StdError + 'static))
     }
 }
 
+/// Type of [`CreateMessageError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CreateMessageErrorType {
+    /// Returned when the content is over [`CreateMessageError::CONTENT_LENGTH`]
+    /// characters.
+    ContentInvalid {
+        /// Provided content.
+        content: String,
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// Returned when an embed is too large, or the combined length of the
+    /// embeds is over 6000 characters.
+    ///
+    /// The source of the error is an [`EmbedValidationError`] containing the
+    /// violated limit.
+    EmbedTooLarge {
+        /// Provided embeds.
+        embeds: Vec<Embed>,
+    },
+    /// Returned when the message has no content, embeds, stickers, or
+    /// attachments.
+    ///
+    /// This is returned when the request is sent.
+    MessageEmpty,
+    /// Returned when there are more than [`CreateMessageError::EMBED_COUNT`]
+    /// embeds.
+    TooManyEmbeds {
+        /// Provided embeds.
+        embeds: Vec<Embed>,
+    },
+    /// Returned when there are more than
+    /// [`CreateMessageError::STICKER_COUNT`] stickers.
+    TooManyStickers {
+        /// Provided sticker IDs.
+        sticker_ids: Vec<StickerId>,
+    },
+}
+
 #[derive(Default, Serialize)]
 pub(crate) struct CreateMessageFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    embed: Option<Embed>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<Embed>,
     #[serde(skip_serializing_if = "Option::is_none")]
     message_reference: Option<MessageReference>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,12 +148,20 @@ pub(crate) struct CreateMessageFields {
     payload_json: Option<Vec<u8>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sticker_ids: Vec<StickerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tts: Option<bool>,
 }
 
 /// Send a message to a channel.
 ///
+/// The message must have at least one of content, embeds, stickers, or
+/// attachments. The limits of these are validated client-side unless the
+/// client has disabled validation via [`ClientBuilder::validation`].
+///
+/// [`ClientBuilder::validation`]: crate::client::ClientBuilder::validation
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -90,6 +186,7 @@ pub struct CreateMessage<'a> {
     pub(crate) fields: CreateMessageFields,
     fut: Option<Pending<'a, Message>>,
     http: &'a Client,
+    validation: bool,
 }
 
 impl<'a> CreateMessage<'a> {
@@ -103,6 +200,7 @@ impl<'a> CreateMessage<'a> {
             },
             fut: None,
             http,
+            validation: http.validation(),
         }
     }
 
@@ -140,15 +238,20 @@ impl<'a> CreateMessage<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`CreateMessageError::ContentInvalid`] if the content length is
-    /// too long.
+    /// Returns a [`CreateMessageErrorType::ContentInvalid`] error type if the
+    /// content length is too long.
     pub fn content(self, content: impl Into<String>) -> Result<Self, CreateMessageError> {
         self._content(content.into())
     }
 
     fn _content(mut self, content: String) -> Result<Self, CreateMessageError> {
-        if !validate::content_limit(&content) {
-            return Err(CreateMessageError::ContentInvalid { content });
+        if self.validation && !validate::content_limit(&content) {
+            let chars = content.chars().count();
+
+            return Err(CreateMessageError {
+                kind: CreateMessageErrorType::ContentInvalid { content, chars },
+                source: None,
+            });
         }
 
         self.fields.content.replace(content);
@@ -156,7 +259,7 @@ impl<'a> CreateMessage<'a> {
         Ok(self)
     }
 
-    /// Set the embed of the message.
+    /// Set the embed of the message, replacing any existing embeds.
     ///
     /// Embed total character length must not exceed 6000 characters. Additionally, the internal
     /// fields also have character limits. Refer to [the discord docs] for more information.
@@ -167,19 +270,48 @@ impl<'a> CreateMessage<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`CreateMessageError::EmbedTooLarge`] if the embed is too large.
+    /// Returns a [`CreateMessageErrorType::EmbedTooLarge`] error type if the
+    /// embed is too large.
     ///
     /// [the discord docs]: https://discord.com/developers/docs/resources/channel#embed-limits
     /// [`EmbedBuilder`]: https://docs.rs/twilight-embed-builder/*/twilight_embed_builder
-    pub fn embed(mut self, embed: Embed) -> Result<Self, CreateMessageError> {
-        if let Err(source) = validate::embed(&embed) {
-            return Err(CreateMessageError::EmbedTooLarge {
-                embed: Box::new(embed),
-                source,
-            });
+    pub fn embed(self, embed: Embed) -> Result<Self, CreateMessageError> {
+        self.embeds(vec![embed])
+    }
+
+    /// Set the embeds of the message.
+    ///
+    /// There may be at most 10 embeds, and the combined character length of
+    /// all of the embeds must not exceed 6000 characters. Refer to [`embed`]
+    /// for the limits of each embed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateMessageErrorType::TooManyEmbeds`] error type if
+    /// there are too many embeds.
+    ///
+    /// Returns a [`CreateMessageErrorType::EmbedTooLarge`] error type if an
+    /// embed is too large or the embeds are too large combined.
+    ///
+    /// [`embed`]: Self::embed
+    pub fn embeds(mut self, embeds: Vec<Embed>) -> Result<Self, CreateMessageError> {
+        if self.validation {
+            if embeds.len() > CreateMessageError::EMBED_COUNT {
+                return Err(CreateMessageError {
+                    kind: CreateMessageErrorType::TooManyEmbeds { embeds },
+                    source: None,
+                });
+            }
+
+            if let Err(source) = validate::embeds(&embeds) {
+                return Err(CreateMessageError {
+                    kind: CreateMessageErrorType::EmbedTooLarge { embeds },
+                    source: Some(Box::new(source)),
+                });
+            }
         }
 
-        self.fields.embed.replace(embed);
+        self.fields.embeds = embeds;
 
         Ok(self)
     }
@@ -214,6 +346,27 @@ impl<'a> CreateMessage<'a> {
         self
     }
 
+    /// Set the stickers of the message.
+    ///
+    /// There may be at most 3 stickers.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CreateMessageErrorType::TooManyStickers`] error type if
+    /// there are too many stickers.
+    pub fn sticker_ids(mut self, sticker_ids: Vec<StickerId>) -> Result<Self, CreateMessageError> {
+        if self.validation && sticker_ids.len() > CreateMessageError::STICKER_COUNT {
+            return Err(CreateMessageError {
+                kind: CreateMessageErrorType::TooManyStickers { sticker_ids },
+                source: None,
+            });
+        }
+
+        self.fields.sticker_ids = sticker_ids;
+
+        Ok(self)
+    }
+
     /// Specify true if the message is TTS.
     pub fn tts(mut self, tts: bool) -> Self {
         self.fields.tts.replace(tts);
@@ -221,36 +374,228 @@ impl<'a> CreateMessage<'a> {
         self
     }
 
+    fn request(&mut self) -> Result<Request> {
+        // The payload may contain any of the fields, so it can't be
+        // determined whether the message is empty.
+        let empty = self.fields.content.is_none()
+            && self.fields.embeds.is_empty()
+            && self.fields.sticker_ids.is_empty()
+            && self.fields.payload_json.is_none()
+            && self.attachments.is_empty();
+
+        if self.validation && empty {
+            return Err(Error::Validation {
+                source: Box::new(CreateMessageError {
+                    kind: CreateMessageErrorType::MessageEmpty,
+                    source: None,
+                }),
+            });
+        }
+
+        Ok(if self.attachments.is_empty() {
+            Request::from((
+                crate::json_to_vec(&self.fields)?,
+                Route::CreateMessage {
+                    channel_id: self.channel_id.0,
+                },
+            ))
+        } else {
+            let mut multipart = Form::new();
+
+            for (index, (name, file)) in self.attachments.drain().enumerate() {
+                multipart.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
+            }
+
+            let body = crate::json_to_vec(&self.fields)?;
+            multipart.part(b"payload_json", &body);
+
+            Request::from((
+                multipart,
+                Route::CreateMessage {
+                    channel_id: self.channel_id.0,
+                },
+            ))
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(
-            if self.attachments.is_empty() {
-                Request::from((
-                    crate::json_to_vec(&self.fields)?,
-                    Route::CreateMessage {
-                        channel_id: self.channel_id.0,
-                    },
-                ))
-            } else {
-                let mut multipart = Form::new();
-
-                for (index, (name, file)) in self.attachments.drain().enumerate() {
-                    multipart.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-                }
-
-                let body = crate::json_to_vec(&self.fields)?;
-                multipart.part(b"payload_json", &body);
-
-                Request::from((
-                    multipart,
-                    Route::CreateMessage {
-                        channel_id: self.channel_id.0,
-                    },
-                ))
-            },
-        )));
+        let request = self.request()?;
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(CreateMessage<'_>, Message);
+
+#[cfg(test)]
+mod tests {
+    use super::{CreateMessage, CreateMessageError, CreateMessageErrorType};
+    use crate::{error::Error, request::validate::EmbedValidationError, Client};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error as StdError, fmt::Debug};
+    use twilight_model::{
+        channel::{embed::Embed, message::sticker::StickerId},
+        id::ChannelId,
+    };
+
+    assert_impl_all!(CreateMessageError: Debug, StdError, Send, Sync);
+    assert_impl_all!(CreateMessageErrorType: Debug, Send, Sync);
+
+    fn embed(description: usize) -> Embed {
+        Embed {
+            author: None,
+            color: None,
+            description: Some("a".repeat(description)),
+            fields: Vec::new(),
+            footer: None,
+            image: None,
+            kind: "rich".to_owned(),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: None,
+            url: None,
+            video: None,
+        }
+    }
+
+    #[test]
+    fn test_content_limit() {
+        let client = Client::new("foo");
+
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .content("a".repeat(2000))
+            .is_ok());
+
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .content("a".repeat(2001))
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CreateMessageErrorType::ContentInvalid { chars: 2001, .. }
+        ));
+    }
+
+    #[test]
+    fn test_embed_count_limit() {
+        let client = Client::new("foo");
+
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .embeds(vec![embed(1); 10])
+            .is_ok());
+
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .embeds(vec![embed(1); 11])
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CreateMessageErrorType::TooManyEmbeds { embeds } if embeds.len() == 11
+        ));
+    }
+
+    #[test]
+    fn test_embed_combined_limit() {
+        let client = Client::new("foo");
+
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .embeds(vec![embed(2000); 3])
+            .is_ok());
+
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .embeds(vec![embed(2000), embed(2000), embed(2001)])
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CreateMessageErrorType::EmbedTooLarge { .. }
+        ));
+
+        let source = error.into_source().expect("source should exist");
+        assert!(matches!(
+            source.downcast_ref::<EmbedValidationError>(),
+            Some(EmbedValidationError::EmbedTooLarge { chars: 6001 })
+        ));
+    }
+
+    #[test]
+    fn test_embed_limit() {
+        let client = Client::new("foo");
+
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .embed(embed(2049))
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CreateMessageErrorType::EmbedTooLarge { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sticker_limit() {
+        let client = Client::new("foo");
+
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .sticker_ids(vec![StickerId(1), StickerId(2), StickerId(3)])
+            .is_ok());
+
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .sticker_ids(vec![StickerId(1), StickerId(2), StickerId(3), StickerId(4)])
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CreateMessageErrorType::TooManyStickers { sticker_ids } if sticker_ids.len() == 4
+        ));
+    }
+
+    #[test]
+    fn test_message_empty() {
+        let client = Client::new("foo");
+        let error = CreateMessage::new(&client, ChannelId(1))
+            .request()
+            .map(|_| ())
+            .unwrap_err();
+
+        let source = match error {
+            Error::Validation { source } => source,
+            other => panic!("unexpected error: {:?}", other),
+        };
+        let error = source
+            .downcast_ref::<CreateMessageError>()
+            .expect("source should be a create message error");
+        assert!(matches!(error.kind(), CreateMessageErrorType::MessageEmpty));
+    }
+
+    #[test]
+    fn test_message_not_empty() {
+        let client = Client::new("foo");
+
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .attachment("twilight.png", b"png".to_vec())
+            .request()
+            .is_ok());
+        assert!(CreateMessage::new(&client, ChannelId(1))
+            .sticker_ids(vec![StickerId(1)])
+            .unwrap()
+            .request()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_without_validation() {
+        let client = Client::builder().token("foo").validation(false).build();
+
+        let mut builder = CreateMessage::new(&client, ChannelId(1))
+            .content("a".repeat(2001))
+            .unwrap()
+            .embeds(vec![embed(1); 11])
+            .unwrap();
+        assert!(builder.request().is_ok());
+
+        assert!(CreateMessage::new(&client, ChannelId(1)).request().is_ok());
+    }
+}
@@ -1,4 +1,4 @@
-use super::GetChannelMessagesConfigured;
+use super::{ChannelHistory, GetChannelMessagesConfigured};
 use crate::request::prelude::*;
 use std::{
     error::Error,
@@ -40,6 +40,8 @@ struct GetChannelMessagesFields {
 /// Only one of [`after`], [`around`], and [`before`] can be specified at a time.
 /// Once these are specified, the type returned is [`GetChannelMessagesConfigured`].
 ///
+/// To walk through more messages than fit in one request use [`history`].
+///
 /// If [`limit`] is unspecified, the default set by Discord is 50.
 ///
 /// # Examples
@@ -71,6 +73,7 @@ struct GetChannelMessagesFields {
 /// [`around`]: Self::around
 /// [`before`]: Self::before
 /// [`GetChannelMessagesConfigured`]: super::GetChannelMessagesConfigured
+/// [`history`]: Self::history
 /// [`limit`]: Self::limit
 pub struct GetChannelMessages<'a> {
     channel_id: ChannelId,
@@ -122,6 +125,17 @@ impl<'a> GetChannelMessages<'a> {
         )
     }
 
+    /// Walk through the history of the channel, starting from its most
+    /// recent message.
+    ///
+    /// Messages are requested in pages of the [`limit`], or 100 if it's
+    /// unspecified. Refer to [`ChannelHistory`] for more information.
+    ///
+    /// [`limit`]: Self::limit
+    pub fn history(self) -> ChannelHistory<'a> {
+        ChannelHistory::new(self.http, self.channel_id, self.fields.limit)
+    }
+
     /// Set the maximum number of messages to retrieve.
     ///
     /// The minimum is 1 and the maximum is 100.
@@ -140,19 +154,57 @@ impl<'a> GetChannelMessages<'a> {
         Ok(self)
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::GetMessages {
+            after: None,
+            around: None,
+            before: None,
+            channel_id: self.channel_id.0,
+            limit: self.fields.limit,
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from(
-            Route::GetMessages {
-                after: None,
-                around: None,
-                before: None,
-                channel_id: self.channel_id.0,
-                limit: self.fields.limit,
-            },
-        ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(GetChannelMessages<'_>, Vec<Message>);
+
+#[cfg(test)]
+mod tests {
+    use super::{GetChannelMessages, GetChannelMessagesError};
+    use crate::Client;
+    use twilight_model::id::{ChannelId, MessageId};
+
+    #[test]
+    fn test_request() -> Result<(), GetChannelMessagesError> {
+        let client = Client::new("foo");
+        let builder = GetChannelMessages::new(&client, ChannelId(1)).limit(100)?;
+
+        assert_eq!("channels/1/messages?&limit=100", builder.request().path_str);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_invalid() {
+        let client = Client::new("foo");
+
+        assert!(matches!(
+            GetChannelMessages::new(&client, ChannelId(1)).limit(0),
+            Err(GetChannelMessagesError::LimitInvalid { limit: 0 })
+        ));
+        assert!(matches!(
+            GetChannelMessages::new(&client, ChannelId(1)).limit(101),
+            Err(GetChannelMessagesError::LimitInvalid { limit: 101 })
+        ));
+        assert!(GetChannelMessages::new(&client, ChannelId(1))
+            .before(MessageId(2))
+            .limit(101)
+            .is_err());
+    }
+}
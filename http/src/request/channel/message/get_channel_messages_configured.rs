@@ -36,6 +36,20 @@ struct GetChannelMessagesConfiguredFields {
 /// This struct is returned when one of `after`, `around`, or `before` is specified in
 /// [`GetChannelMessages`].
 ///
+/// Only one of them can be specified, so specifying another doesn't compile:
+///
+/// ```compile_fail
+/// use twilight_http::Client;
+/// use twilight_model::id::{ChannelId, MessageId};
+///
+/// let client = Client::new("my token");
+///
+/// let request = client
+///     .channel_messages(ChannelId(1))
+///     .after(MessageId(2))
+///     .before(MessageId(3));
+/// ```
+///
 /// [`GetChannelMessages`]: super::GetChannelMessages
 // nb: after, around, and before are mutually exclusive, so we use this
 // "configured" request to utilize the type system to prevent these from being
@@ -77,7 +91,7 @@ impl<'a> GetChannelMessagesConfigured<'a> {
     /// # Errors
     ///
     /// Returns [`GetChannelMessagesConfiguredError::LimitInvalid`] if the
-    /// amount is greater than 21600.
+    /// amount is less than 1 or greater than 100.
     pub fn limit(mut self, limit: u64) -> Result<Self, GetChannelMessagesConfiguredError> {
         if !validate::get_channel_messages_limit(limit) {
             return Err(GetChannelMessagesConfiguredError::LimitInvalid { limit });
@@ -88,19 +102,42 @@ impl<'a> GetChannelMessagesConfigured<'a> {
         Ok(self)
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::GetMessages {
+            after: self.after.map(|x| x.0),
+            around: self.around.map(|x| x.0),
+            before: self.before.map(|x| x.0),
+            channel_id: self.channel_id.0,
+            limit: self.fields.limit,
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from(
-            Route::GetMessages {
-                after: self.after.map(|x| x.0),
-                around: self.around.map(|x| x.0),
-                before: self.before.map(|x| x.0),
-                channel_id: self.channel_id.0,
-                limit: self.fields.limit,
-            },
-        ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(GetChannelMessagesConfigured<'_>, Vec<Message>);
+
+#[cfg(test)]
+mod tests {
+    use crate::{request::channel::message::GetChannelMessages, Client};
+    use twilight_model::id::{ChannelId, MessageId};
+
+    #[test]
+    fn test_request() {
+        let client = Client::new("foo");
+        let builder = GetChannelMessages::new(&client, ChannelId(1))
+            .around(MessageId(2))
+            .limit(10)
+            .unwrap();
+
+        assert_eq!(
+            "channels/1/messages?&around=2&limit=10",
+            builder.request().path_str
+        );
+    }
+}
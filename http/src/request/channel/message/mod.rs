@@ -1,3 +1,4 @@
+pub mod channel_history;
 pub mod create_message;
 pub mod crosspost_message;
 pub mod get_channel_messages;
@@ -9,9 +10,9 @@ mod delete_messages;
 mod get_message;
 
 pub use self::{
-    create_message::CreateMessage, crosspost_message::CrosspostMessage,
-    delete_message::DeleteMessage, delete_messages::DeleteMessages,
-    get_channel_messages::GetChannelMessages,
+    channel_history::ChannelHistory, create_message::CreateMessage,
+    crosspost_message::CrosspostMessage, delete_message::DeleteMessage,
+    delete_messages::DeleteMessages, get_channel_messages::GetChannelMessages,
     get_channel_messages_configured::GetChannelMessagesConfigured, get_message::GetMessage,
     update_message::UpdateMessage,
 };
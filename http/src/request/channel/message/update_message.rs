@@ -9,41 +9,94 @@ use twilight_model::{
 };
 
 /// The error created when a message can not be updated as configured.
-#[derive(Clone, Debug)]
-#[non_exhaustive]
-pub enum UpdateMessageError {
-    /// Returned when the content is over 2000 UTF-16 characters.
-    ContentInvalid {
-        /// Provided content.
-        content: String,
-    },
-    /// Returned when the length of the embed is over 6000 characters.
-    EmbedTooLarge {
-        /// Provided embed.
-        embed: Box<Embed>,
-        /// The source of the error.
-        source: EmbedValidationError,
-    },
+#[derive(Debug)]
+pub struct UpdateMessageError {
+    kind: UpdateMessageErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl UpdateMessageError {
+    /// The maximum length of the content in codepoints.
+    pub const CONTENT_LENGTH: usize = 2000;
+
+    /// The maximum number of embeds in a message.
+    pub const EMBED_COUNT: usize = 10;
+
+    /// Immutable reference to the type of error that occurred.
+    pub const fn kind(&self) -> &UpdateMessageErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    pub fn into_parts(self) -> (UpdateMessageErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
 }
 
 impl Display for UpdateMessageError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        match self {
-            Self::ContentInvalid { .. } => f.write_str("the message content is invalid"),
-            Self::EmbedTooLarge { .. } => f.write_str("the embed's contents are too long"),
+        match &self.kind {
+            UpdateMessageErrorType::ContentInvalid { chars, .. } => write!(
+                f,
+                "the message content is {} characters long, but the max is {}",
+                chars,
+                Self::CONTENT_LENGTH
+            ),
+            UpdateMessageErrorType::EmbedTooLarge { .. } => {
+                f.write_str("the embed's contents are too long")
+            }
+            UpdateMessageErrorType::TooManyEmbeds { embeds } => write!(
+                f,
+                "there are {} embeds, but the maximum amount is {}",
+                embeds.len(),
+                Self::EMBED_COUNT
+            ),
         }
     }
 }
 
 impl Error for UpdateMessageError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match self {
-            Self::ContentInvalid { .. } => None,
-            Self::EmbedTooLarge { source, .. } => Some(source),
-        }
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
     }
 }
 
+/// Type of [`UpdateMessageError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UpdateMessageErrorType {
+    /// Returned when the content is over [`UpdateMessageError::CONTENT_LENGTH`]
+    /// characters.
+    ContentInvalid {
+        /// Provided content.
+        content: String,
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// Returned when an embed is too large, or the combined length of the
+    /// embeds is over 6000 characters.
+    ///
+    /// The source of the error is an [`EmbedValidationError`] containing the
+    /// violated limit.
+    EmbedTooLarge {
+        /// Provided embeds.
+        embeds: Vec<Embed>,
+    },
+    /// Returned when there are more than [`UpdateMessageError::EMBED_COUNT`]
+    /// embeds.
+    TooManyEmbeds {
+        /// Provided embeds.
+        embeds: Vec<Embed>,
+    },
+}
+
 #[derive(Default, Serialize)]
 struct UpdateMessageFields {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,9 +113,8 @@ struct UpdateMessageFields {
     #[allow(clippy::option_option)]
     #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<Option<String>>,
-    #[allow(clippy::option_option)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    embed: Option<Option<Embed>>,
+    embeds: Option<Vec<Embed>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     flags: Option<MessageFlags>,
 }
@@ -110,6 +162,7 @@ pub struct UpdateMessage<'a> {
     fut: Option<Pending<'a, Message>>,
     http: &'a Client,
     message_id: MessageId,
+    validation: bool,
 }
 
 impl<'a> UpdateMessage<'a> {
@@ -120,6 +173,7 @@ impl<'a> UpdateMessage<'a> {
             fut: None,
             http,
             message_id,
+            validation: http.validation(),
         }
     }
 
@@ -134,17 +188,23 @@ impl<'a> UpdateMessage<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`UpdateMessageError::ContentInvalid`] if the content length is
-    /// too long.
+    /// Returns an [`UpdateMessageErrorType::ContentInvalid`] error type if the
+    /// content length is too long.
     pub fn content(self, content: impl Into<Option<String>>) -> Result<Self, UpdateMessageError> {
         self._content(content.into())
     }
 
     fn _content(mut self, content: Option<String>) -> Result<Self, UpdateMessageError> {
         if let Some(content_ref) = content.as_ref() {
-            if !validate::content_limit(content_ref) {
-                return Err(UpdateMessageError::ContentInvalid {
-                    content: content.expect("content is known to be some"),
+            if self.validation && !validate::content_limit(content_ref) {
+                let chars = content_ref.chars().count();
+
+                return Err(UpdateMessageError {
+                    kind: UpdateMessageErrorType::ContentInvalid {
+                        content: content.expect("content is known to be some"),
+                        chars,
+                    },
+                    source: None,
                 });
             }
         }
@@ -154,27 +214,56 @@ impl<'a> UpdateMessage<'a> {
         Ok(self)
     }
 
-    /// Set the embed of the message.
+    /// Set the embed of the message, replacing any existing embeds.
     ///
-    /// Pass `None` if you want to remove the message embed.
+    /// Pass `None` if you want to remove the message embeds.
     ///
     /// Note that if there is no content then you will not be
     /// able to remove the embed of the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UpdateMessageErrorType::EmbedTooLarge`] error type if the
+    /// embed is too large.
     pub fn embed(self, embed: impl Into<Option<Embed>>) -> Result<Self, UpdateMessageError> {
-        self._embed(embed.into())
+        self.embeds(embed.into().into_iter().collect())
     }
 
-    fn _embed(mut self, embed: Option<Embed>) -> Result<Self, UpdateMessageError> {
-        if let Some(embed_ref) = embed.as_ref() {
-            if let Err(source) = validate::embed(&embed_ref) {
-                return Err(UpdateMessageError::EmbedTooLarge {
-                    embed: Box::new(embed.expect("embed is known to be some")),
-                    source,
+    /// Set the embeds of the message, replacing any existing embeds.
+    ///
+    /// Pass an empty list if you want to remove the message embeds.
+    ///
+    /// There may be at most 10 embeds, and the combined character length of
+    /// all of the embeds must not exceed 6000 characters. Refer to [`embed`]
+    /// for the limits of each embed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UpdateMessageErrorType::TooManyEmbeds`] error type if
+    /// there are too many embeds.
+    ///
+    /// Returns an [`UpdateMessageErrorType::EmbedTooLarge`] error type if an
+    /// embed is too large or the embeds are too large combined.
+    ///
+    /// [`embed`]: Self::embed
+    pub fn embeds(mut self, embeds: Vec<Embed>) -> Result<Self, UpdateMessageError> {
+        if self.validation {
+            if embeds.len() > UpdateMessageError::EMBED_COUNT {
+                return Err(UpdateMessageError {
+                    kind: UpdateMessageErrorType::TooManyEmbeds { embeds },
+                    source: None,
+                });
+            }
+
+            if let Err(source) = validate::embeds(&embeds) {
+                return Err(UpdateMessageError {
+                    kind: UpdateMessageErrorType::EmbedTooLarge { embeds },
+                    source: Some(Box::new(source)),
                 });
             }
         }
 
-        self.fields.embed.replace(embed);
+        self.fields.embeds.replace(embeds);
 
         Ok(self)
     }
@@ -204,17 +293,190 @@ impl<'a> UpdateMessage<'a> {
         self
     }
 
-    fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from((
+    fn request(&self) -> Result<Request> {
+        Ok(Request::from((
             crate::json_to_vec(&self.fields)?,
             Route::UpdateMessage {
                 channel_id: self.channel_id.0,
                 message_id: self.message_id.0,
             },
-        )))));
+        )))
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let request = self.request()?;
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(UpdateMessage<'_>, Message);
+
+#[cfg(test)]
+mod tests {
+    use super::{UpdateMessage, UpdateMessageErrorType};
+    use crate::{request::validate::EmbedValidationError, Client};
+    use serde_json::{json, Value};
+    use twilight_model::{
+        channel::embed::Embed,
+        id::{ChannelId, MessageId},
+    };
+
+    fn embed(description: usize) -> Embed {
+        Embed {
+            author: None,
+            color: None,
+            description: Some("a".repeat(description)),
+            fields: Vec::new(),
+            footer: None,
+            image: None,
+            kind: "rich".to_owned(),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: None,
+            url: None,
+            video: None,
+        }
+    }
+
+    #[test]
+    fn test_content_limit() {
+        let client = Client::new("foo");
+
+        assert!(UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .content("a".repeat(2000))
+            .is_ok());
+
+        let error = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .content("a".repeat(2001))
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            UpdateMessageErrorType::ContentInvalid { chars: 2001, .. }
+        ));
+    }
+
+    #[test]
+    fn test_content_limit_without_validation() {
+        let client = Client::builder().token("foo").validation(false).build();
+        let builder = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .content("a".repeat(2001))
+            .unwrap();
+        let actual = builder.request().expect("failed to create request");
+
+        assert!(actual.body.is_some());
+    }
+
+    #[test]
+    fn test_embed_count_limit() {
+        let client = Client::new("foo");
+
+        assert!(UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(1); 10])
+            .is_ok());
+
+        let error = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(1); 11])
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            UpdateMessageErrorType::TooManyEmbeds { embeds } if embeds.len() == 11
+        ));
+    }
+
+    #[test]
+    fn test_embed_combined_limit() {
+        let client = Client::new("foo");
+
+        assert!(UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(2000); 3])
+            .is_ok());
+
+        let error = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(2000), embed(2000), embed(2001)])
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            UpdateMessageErrorType::EmbedTooLarge { .. }
+        ));
+
+        let source = error.into_source().expect("source should exist");
+        assert!(matches!(
+            source.downcast_ref::<EmbedValidationError>(),
+            Some(EmbedValidationError::EmbedTooLarge { chars: 6001 })
+        ));
+    }
+
+    #[test]
+    fn test_embed_limit() {
+        let client = Client::new("foo");
+
+        let error = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embed(embed(2049))
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            UpdateMessageErrorType::EmbedTooLarge { embeds } if embeds.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_embed_replaces_embeds() {
+        let client = Client::new("foo");
+        let builder = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embed(embed(1))
+            .unwrap()
+            .embeds(vec![embed(2), embed(3)])
+            .unwrap();
+        let actual = builder.request().expect("failed to create request");
+        let body: Value = serde_json::from_slice(&actual.body.unwrap()).unwrap();
+
+        assert_eq!(json!({ "embeds": [embed(2), embed(3)] }), body);
+
+        let builder = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(1), embed(2)])
+            .unwrap()
+            .embed(None)
+            .unwrap();
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(Some(br#"{"embeds":[]}"#.to_vec()), actual.body);
+    }
+
+    #[test]
+    fn test_embed_count_limit_without_validation() {
+        let client = Client::builder().token("foo").validation(false).build();
+
+        assert!(UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(vec![embed(1); 11])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_remove_embeds() {
+        let client = Client::new("foo");
+        let builder = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .embeds(Vec::new())
+            .unwrap();
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(Some(br#"{"embeds":[]}"#.to_vec()), actual.body);
+    }
+
+    #[test]
+    fn test_remove_content() {
+        let client = Client::new("foo");
+        let builder = UpdateMessage::new(&client, ChannelId(1), MessageId(2))
+            .content(None)
+            .unwrap();
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(Some(br#"{"content":null}"#.to_vec()), actual.body);
+    }
+}
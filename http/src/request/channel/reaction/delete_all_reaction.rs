@@ -2,6 +2,24 @@ use crate::request::prelude::*;
 use twilight_model::id::{ChannelId, MessageId};
 
 /// Remove all reactions of a specified emoji from a message.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twilight_http::{request::channel::reaction::RequestReactionType, Client};
+/// use twilight_model::id::{ChannelId, MessageId};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// let emoji = RequestReactionType::Unicode { name: String::from("🌃") };
+///
+/// client
+///     .delete_all_reaction(ChannelId(123), MessageId(456), emoji)
+///     .await?;
+/// # Ok(()) }
+/// ```
 pub struct DeleteAllReaction<'a> {
     channel_id: ChannelId,
     emoji: String,
@@ -26,17 +44,45 @@ impl<'a> DeleteAllReaction<'a> {
         }
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::DeleteMessageSpecficReaction {
+            channel_id: self.channel_id.0,
+            message_id: self.message_id.0,
+            emoji: self.emoji.clone(),
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.verify(Request::from(
-            Route::DeleteMessageSpecficReaction {
-                channel_id: self.channel_id.0,
-                message_id: self.message_id.0,
-                emoji: self.emoji.clone(),
-            },
-        ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.verify(request)));
 
         Ok(())
     }
 }
 
 poll_req!(DeleteAllReaction<'_>, ());
+
+#[cfg(test)]
+mod tests {
+    use super::DeleteAllReaction;
+    use crate::{request::channel::reaction::RequestReactionType, routing::Path, Client};
+    use hyper::Method;
+    use twilight_model::id::{ChannelId, MessageId};
+
+    #[test]
+    fn test_request() {
+        let client = Client::new("foo");
+        let emoji = RequestReactionType::Unicode {
+            name: "\u{1f303}".to_owned(),
+        };
+        let builder = DeleteAllReaction::new(&client, ChannelId(123), MessageId(456), emoji);
+        let actual = builder.request();
+
+        assert_eq!(Method::DELETE, actual.method);
+        assert_eq!(Path::ChannelsIdMessagesIdReactions(123), actual.path);
+        assert_eq!(
+            "channels/123/messages/456/reactions/%F0%9F%8C%83",
+            actual.path_str
+        );
+    }
+}
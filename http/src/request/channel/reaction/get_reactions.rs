@@ -40,6 +40,37 @@ struct GetReactionsFields {
 ///
 /// This endpoint is limited to 100 users maximum, so if a message has more than 100 reactions,
 /// requests must be chained until all reactions are retireved.
+///
+/// # Examples
+///
+/// Get the first 100 users who reacted with an emoji, and then the next 100:
+///
+/// ```rust,no_run
+/// use twilight_http::{request::channel::reaction::RequestReactionType, Client};
+/// use twilight_model::id::{ChannelId, MessageId};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// let channel_id = ChannelId(123);
+/// let message_id = MessageId(456);
+/// let emoji = RequestReactionType::Unicode { name: String::from("🌃") };
+///
+/// let users = client
+///     .reactions(channel_id, message_id, emoji.clone())
+///     .limit(100)?
+///     .await?;
+///
+/// if let Some(last) = users.last() {
+///     let next = client
+///         .reactions(channel_id, message_id, emoji)
+///         .after(last.id)
+///         .limit(100)?
+///         .await?;
+/// }
+/// # Ok(()) }
+/// ```
 pub struct GetReactions<'a> {
     channel_id: ChannelId,
     emoji: String,
@@ -87,7 +118,7 @@ impl<'a> GetReactions<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`GetReactionsError::LimitInvalid`] if the amount is greater than 100.
+    /// Returns [`GetReactionsError::LimitInvalid`] if the amount is 0 or greater than 100.
     pub fn limit(mut self, limit: u64) -> Result<Self, GetReactionsError> {
         if !validate::get_reactions_limit(limit) {
             return Err(GetReactionsError::LimitInvalid { limit });
@@ -98,20 +129,67 @@ impl<'a> GetReactions<'a> {
         Ok(self)
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::GetReactionUsers {
+            after: self.fields.after.map(|x| x.0),
+            before: self.fields.before.map(|x| x.0),
+            channel_id: self.channel_id.0,
+            emoji: self.emoji.clone(),
+            limit: self.fields.limit,
+            message_id: self.message_id.0,
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from(
-            Route::GetReactionUsers {
-                after: self.fields.after.map(|x| x.0),
-                before: self.fields.before.map(|x| x.0),
-                channel_id: self.channel_id.0,
-                emoji: self.emoji.to_owned(),
-                limit: self.fields.limit,
-                message_id: self.message_id.0,
-            },
-        ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(GetReactions<'_>, Vec<User>);
+
+#[cfg(test)]
+mod tests {
+    use super::{GetReactions, GetReactionsError};
+    use crate::{request::channel::reaction::RequestReactionType, Client};
+    use twilight_model::id::{ChannelId, EmojiId, MessageId, UserId};
+
+    fn emoji() -> RequestReactionType {
+        RequestReactionType::Custom {
+            id: EmojiId(1),
+            name: Some("rarity".to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_request_pagination() -> Result<(), GetReactionsError> {
+        let client = Client::new("foo");
+        let builder = GetReactions::new(&client, ChannelId(2), MessageId(3), emoji())
+            .after(UserId(4))
+            .limit(100)?;
+        let actual = builder.request();
+
+        assert_eq!(
+            "channels/2/messages/3/reactions/rarity:1?after=4&limit=100",
+            actual.path_str
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_invalid() {
+        let client = Client::new("foo");
+
+        assert!(matches!(
+            GetReactions::new(&client, ChannelId(2), MessageId(3), emoji()).limit(0),
+            Err(GetReactionsError::LimitInvalid { limit: 0 })
+        ));
+        assert!(matches!(
+            GetReactions::new(&client, ChannelId(2), MessageId(3), emoji()).limit(101),
+            Err(GetReactionsError::LimitInvalid { limit: 101 })
+        ));
+    }
+}
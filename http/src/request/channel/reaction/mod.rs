@@ -14,10 +14,26 @@ use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::fmt::Write;
 use twilight_model::{channel::ReactionType, id::EmojiId};
 
-#[derive(Eq, PartialEq)]
+/// Emoji to use in a reaction request.
+///
+/// Unicode emojis are percent-encoded and custom emojis are formatted as
+/// `name:id` when used in a route.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum RequestReactionType {
-    Custom { id: EmojiId, name: Option<String> },
-    Unicode { name: String },
+    /// Custom emoji of a guild.
+    Custom {
+        /// ID of the custom emoji.
+        id: EmojiId,
+        /// Name of the custom emoji.
+        ///
+        /// This is not required, but is recommended.
+        name: Option<String>,
+    },
+    /// Unicode emoji, such as `"🌃"`.
+    Unicode {
+        /// Unicode emoji.
+        name: String,
+    },
 }
 
 impl From<ReactionType> for RequestReactionType {
@@ -45,3 +61,79 @@ fn format_emoji(emoji: RequestReactionType) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format_emoji, RequestReactionType};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{channel::ReactionType, id::EmojiId};
+
+    assert_impl_all!(
+        RequestReactionType: Clone,
+        Debug,
+        Eq,
+        From<ReactionType>,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_format_custom() {
+        let emoji = RequestReactionType::Custom {
+            id: EmojiId(123),
+            name: Some("rarity".to_owned()),
+        };
+
+        assert_eq!("rarity:123", format_emoji(emoji));
+    }
+
+    #[test]
+    fn test_format_custom_without_name() {
+        let emoji = RequestReactionType::Custom {
+            id: EmojiId(123),
+            name: None,
+        };
+
+        assert_eq!("e:123", format_emoji(emoji));
+    }
+
+    #[test]
+    fn test_format_unicode() {
+        let emoji = RequestReactionType::Unicode {
+            name: "\u{1f303}".to_owned(),
+        };
+
+        assert_eq!("%F0%9F%8C%83", format_emoji(emoji));
+    }
+
+    /// Test a thumbs up emoji with a skin tone modifier, which consists of
+    /// multiple codepoints.
+    #[test]
+    fn test_format_unicode_multiple_codepoints() {
+        let emoji = RequestReactionType::Unicode {
+            name: "\u{1f44d}\u{1f3fd}".to_owned(),
+        };
+
+        assert_eq!("%F0%9F%91%8D%F0%9F%8F%BD", format_emoji(emoji));
+    }
+
+    #[test]
+    fn test_from_reaction_type() {
+        let reaction = ReactionType::Custom {
+            animated: false,
+            id: EmojiId(123),
+            name: Some("rarity".to_owned()),
+        };
+
+        assert_eq!(
+            RequestReactionType::Custom {
+                id: EmojiId(123),
+                name: Some("rarity".to_owned()),
+            },
+            RequestReactionType::from(reaction)
+        );
+    }
+}
@@ -8,6 +8,9 @@ use twilight_model::{
 
 /// Update the permissions for a role or a user in a channel.
 ///
+/// The target of the overwrite is set by calling [`member`], [`role`], or
+/// [`target`].
+///
 /// # Examples:
 ///
 /// Create permission overrides for a role to view the channel, but not send messages:
@@ -31,6 +34,10 @@ use twilight_model::{
 ///     .await?;
 /// # Ok(()) }
 /// ```
+///
+/// [`member`]: Self::member
+/// [`role`]: Self::role
+/// [`target`]: Self::target
 pub struct UpdateChannelPermission<'a> {
     allow: Permissions,
     channel_id: ChannelId,
@@ -55,15 +62,19 @@ impl<'a> UpdateChannelPermission<'a> {
 
     /// Specify this override to be for a member.
     pub fn member(self, user_id: impl Into<UserId>) -> UpdateChannelPermissionConfigured<'a> {
-        self.configure(&PermissionOverwriteType::Member(user_id.into()))
+        self.target(&PermissionOverwriteType::Member(user_id.into()))
     }
 
     /// Specify this override to be for a role.
     pub fn role(self, role_id: impl Into<RoleId>) -> UpdateChannelPermissionConfigured<'a> {
-        self.configure(&PermissionOverwriteType::Role(role_id.into()))
+        self.target(&PermissionOverwriteType::Role(role_id.into()))
     }
 
-    fn configure(self, target: &PermissionOverwriteType) -> UpdateChannelPermissionConfigured<'a> {
+    /// Specify the target of this override, such as the target of an existing
+    /// [`PermissionOverwrite`].
+    ///
+    /// [`PermissionOverwrite`]: twilight_model::channel::permission_overwrite::PermissionOverwrite
+    pub fn target(self, target: &PermissionOverwriteType) -> UpdateChannelPermissionConfigured<'a> {
         UpdateChannelPermissionConfigured::new(
             self.http,
             self.channel_id,
@@ -13,7 +13,11 @@ struct UpdateChannelPermissionConfiguredFields {
     kind: PermissionOverwriteTargetType,
 }
 
-/// Created when either `member` or `role` is called on a `DeleteChannelPermission` struct.
+/// Created when either `member`, `role`, or `target` is called on an
+/// `UpdateChannelPermission` struct.
+///
+/// The type of the target is sent as an integer, with the permissions as
+/// strings, as expected by the API version in use.
 pub struct UpdateChannelPermissionConfigured<'a> {
     channel_id: ChannelId,
     fields: UpdateChannelPermissionConfiguredFields,
@@ -99,13 +103,68 @@ poll_req!(UpdateChannelPermissionConfigured<'_>, ());
 #[cfg(test)]
 mod tests {
     use super::{UpdateChannelPermissionConfigured, UpdateChannelPermissionConfiguredFields};
-    use crate::{request::Request, routing::Route, Client};
+    use crate::{
+        request::{AuditLogReason, Request},
+        routing::Route,
+        Client,
+    };
     use twilight_model::{
         channel::permission_overwrite::{PermissionOverwriteTargetType, PermissionOverwriteType},
         guild::Permissions,
-        id::{ChannelId, UserId},
+        id::{ChannelId, RoleId, UserId},
     };
 
+    fn body(target: &PermissionOverwriteType) -> String {
+        let client = Client::new("foo");
+        let builder = UpdateChannelPermissionConfigured::new(
+            &client,
+            ChannelId(1),
+            Permissions::VIEW_CHANNEL,
+            Permissions::SEND_MESSAGES,
+            target,
+        );
+        let request = builder.request().expect("failed to create request");
+
+        String::from_utf8(request.body.expect("request has a body")).unwrap()
+    }
+
+    #[test]
+    fn test_body_member() {
+        assert_eq!(
+            r#"{"allow":"1024","deny":"2048","type":1}"#,
+            body(&PermissionOverwriteType::Member(UserId(2)))
+        );
+    }
+
+    #[test]
+    fn test_body_role() {
+        assert_eq!(
+            r#"{"allow":"1024","deny":"2048","type":0}"#,
+            body(&PermissionOverwriteType::Role(RoleId(2)))
+        );
+    }
+
+    #[test]
+    fn test_reason() {
+        let client = Client::new("foo");
+        let builder = client
+            .update_channel_permission(ChannelId(1), Permissions::empty(), Permissions::empty())
+            .role(RoleId(2))
+            .reason("sync")
+            .expect("reason is valid");
+        let request = builder.request().expect("failed to create request");
+
+        assert_eq!(
+            "sync",
+            request
+                .headers
+                .expect("request has headers")
+                .get("x-audit-log-reason")
+                .expect("reason header is set")
+        );
+        assert_eq!("channels/1/permissions/2", request.path_str);
+    }
+
     #[test]
     fn test_request() {
         let client = Client::new("foo");
@@ -52,9 +52,10 @@ impl<'a> CreateWebhook<'a> {
     ///
     /// This must be a Data URI, in the form of `data:image/{type};base64,{data}` where `{type}` is
     /// the image MIME type and `{data}` is the base64-encoded image. Refer to [the discord docs]
-    /// for more information.
+    /// for more information. Use [`ImageData`] to encode the raw bytes of an image.
     ///
     /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+    /// [`ImageData`]: crate::request::ImageData
     pub fn avatar(mut self, avatar: impl Into<String>) -> Self {
         self.fields.avatar.replace(avatar.into());
 
@@ -42,9 +42,10 @@ impl<'a> UpdateWebhook<'a> {
     ///
     /// See [Discord Docs/Image Data] for more information. This must be a Data URI, in the form of
     /// `data:image/{type};base64,{data}` where `{type}` is the image MIME type and `{data}` is the
-    /// base64-encoded image.
+    /// base64-encoded image. Use [`ImageData`] to encode the raw bytes of an image.
     ///
     /// [Discord Docs/Image Data]: https://discord.com/developers/docs/reference#image-data
+    /// [`ImageData`]: crate::request::ImageData
     pub fn avatar(mut self, avatar: impl Into<Option<String>>) -> Self {
         self.fields.avatar.replace(avatar.into());
 
@@ -4,7 +4,24 @@ use twilight_model::gateway::connection_info::BotConnectionInfo;
 /// Get information about the gateway, authenticated as a bot user.
 ///
 /// Returns additional information: the recommended number of shards to use, and information on
-/// the current session start limit.
+/// the current session start limit, including the maximum concurrency of identifies.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twilight_http::Client;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// let info = client.gateway().authed().await?;
+/// println!(
+///     "{} shards, identifying {} at a time",
+///     info.shards, info.session_start_limit.max_concurrency,
+/// );
+/// # Ok(()) }
+/// ```
 pub struct GetGatewayAuthed<'a> {
     fut: Option<Pending<'a, BotConnectionInfo>>,
     http: &'a Client,
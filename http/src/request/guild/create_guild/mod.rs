@@ -323,9 +323,10 @@ impl<'a> CreateGuild<'a> {
     ///
     /// This must be a Data URI, in the form of `data:image/{type};base64,{data}` where `{type}` is
     /// the image MIME type and `{data}` is the base64-encoded image. Refer to [the discord docs]
-    /// for more information.
+    /// for more information. Use [`ImageData`] to encode the raw bytes of an image.
     ///
     /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+    /// [`ImageData`]: crate::request::ImageData
     pub fn icon(mut self, icon: impl Into<String>) -> Self {
         self.fields.icon.replace(icon.into());
 
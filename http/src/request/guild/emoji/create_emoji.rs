@@ -16,9 +16,11 @@ struct CreateEmojiFields {
 ///
 /// The emoji must be a Data URI, in the form of `data:image/{type};base64,{data}` where `{type}`
 /// is the image MIME type and `{data}` is the base64-encoded image.  Refer to [the discord docs]
-/// for more information about image data.
+/// for more information about image data. Use [`ImageData::emoji`] to encode the raw bytes of an
+/// image, which also checks that it isn't too large for an emoji.
 ///
 /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+/// [`ImageData::emoji`]: crate::request::ImageData::emoji
 pub struct CreateEmoji<'a> {
     fut: Option<Pending<'a, Emoji>>,
     fields: CreateEmojiFields,
@@ -19,25 +19,72 @@ use serde_json::Value;
 use simd_json::value::OwnedValue as Value;
 
 /// The error created when the members can not be fetched as configured.
-#[derive(Clone, Debug)]
-#[non_exhaustive]
-pub enum GetGuildMembersError {
-    /// The limit is either 0 or more than 1000.
-    LimitInvalid {
-        /// Provided limit.
-        limit: u64,
-    },
+#[derive(Debug)]
+pub struct GetGuildMembersError {
+    kind: GetGuildMembersErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl GetGuildMembersError {
+    /// Immutable reference to the type of error that occurred.
+    pub const fn kind(&self) -> &GetGuildMembersErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    pub fn into_parts(
+        self,
+    ) -> (
+        GetGuildMembersErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
 }
 
 impl Display for GetGuildMembersError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        match self {
-            Self::LimitInvalid { .. } => f.write_str("the limit is invalid"),
+        match &self.kind {
+            GetGuildMembersErrorType::AfterInvalid { .. } => {
+                f.write_str("the user id to get members after is 0")
+            }
+            GetGuildMembersErrorType::LimitInvalid { limit } => write!(
+                f,
+                "the limit is {}, but it must be between 1 and 1000",
+                limit
+            ),
         }
     }
 }
 
-impl Error for GetGuildMembersError {}
+impl Error for GetGuildMembersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`GetGuildMembersError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GetGuildMembersErrorType {
+    /// The user ID to get members after is 0.
+    AfterInvalid {
+        /// Provided user ID.
+        after: UserId,
+    },
+    /// The limit is either 0 or more than 1000.
+    LimitInvalid {
+        /// Provided limit.
+        limit: u64,
+    },
+}
 
 #[derive(Default)]
 struct GetGuildMembersFields {
@@ -65,13 +112,23 @@ struct GetGuildMembersFields {
 ///
 /// let guild_id = GuildId(100);
 /// let user_id = UserId(3000);
-/// let members = client.guild_members(guild_id).after(user_id).await?;
+/// let members = client
+///     .guild_members(guild_id)
+///     .after(user_id)?
+///     .limit(500)?
+///     .await?;
 /// # Ok(()) }
 /// ```
 ///
+/// The returned members have the ID of the guild set.
+///
 /// # Errors
 ///
-/// Returns [`GetGuildMembersError::LimitInvalid`] if the limit is invalid.
+/// Returns a [`GetGuildMembersErrorType::AfterInvalid`] error type if the
+/// user ID to get members after is 0.
+///
+/// Returns a [`GetGuildMembersErrorType::LimitInvalid`] error type if the
+/// limit is invalid.
 pub struct GetGuildMembers<'a> {
     fields: GetGuildMembersFields,
     fut: Option<Pending<'a, Bytes>>,
@@ -90,23 +147,38 @@ impl<'a> GetGuildMembers<'a> {
     }
 
     /// Sets the user ID to get members after.
-    pub fn after(mut self, after: UserId) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GetGuildMembersErrorType::AfterInvalid`] error type if the
+    /// user ID is 0.
+    pub fn after(mut self, after: UserId) -> Result<Self, GetGuildMembersError> {
+        if after.0 == 0 {
+            return Err(GetGuildMembersError {
+                kind: GetGuildMembersErrorType::AfterInvalid { after },
+                source: None,
+            });
+        }
+
         self.fields.after.replace(after);
 
-        self
+        Ok(self)
     }
 
     /// Sets the number of members to retrieve per request.
     ///
-    /// The limit must be greater than 0 and less than 1000.
+    /// The limit must be greater than 0 and at most 1000.
     ///
     /// # Errors
     ///
-    /// Returns [`GetGuildMembersError::LimitInvalid`] if the limit is 0 or
-    /// greater than 1000.
+    /// Returns a [`GetGuildMembersErrorType::LimitInvalid`] error type if the
+    /// limit is 0 or greater than 1000.
     pub fn limit(mut self, limit: u64) -> Result<Self, GetGuildMembersError> {
         if !validate::get_guild_members_limit(limit) {
-            return Err(GetGuildMembersError::LimitInvalid { limit });
+            return Err(GetGuildMembersError {
+                kind: GetGuildMembersErrorType::LimitInvalid { limit },
+                source: None,
+            });
         }
 
         self.fields.limit.replace(limit);
@@ -121,21 +193,38 @@ impl<'a> GetGuildMembers<'a> {
         self
     }
 
+    fn request(&self) -> Request {
+        Request::from(Route::GetGuildMembers {
+            after: self.fields.after.map(|x| x.0),
+            guild_id: self.guild_id.0,
+            limit: self.fields.limit,
+            presences: self.fields.presences,
+        })
+    }
+
     fn start(&mut self) -> Result<()> {
-        self.fut
-            .replace(Box::pin(self.http.request_bytes(Request::from(
-                Route::GetGuildMembers {
-                    after: self.fields.after.map(|x| x.0),
-                    guild_id: self.guild_id.0,
-                    limit: self.fields.limit,
-                    presences: self.fields.presences,
-                },
-            ))));
+        let request = self.request();
+        self.fut.replace(Box::pin(self.http.request_bytes(request)));
 
         Ok(())
     }
 }
 
+/// Deserialize members, setting the ID of the guild they're in.
+fn members(guild_id: GuildId, bytes: &[u8]) -> Result<Vec<Member>> {
+    let mut bytes = bytes.to_vec();
+    let values = crate::json_from_slice::<Vec<Value>>(&mut bytes)?;
+
+    let mut members = Vec::with_capacity(values.len());
+
+    for value in values {
+        let member_deserializer = MemberDeserializer::new(guild_id);
+        members.push(member_deserializer.deserialize(value)?);
+    }
+
+    Ok(members)
+}
+
 impl Future for GetGuildMembers<'_> {
     type Output = Result<Vec<Member>>;
 
@@ -149,19 +238,94 @@ impl Future for GetGuildMembers<'_> {
         match fut.as_mut().poll(cx) {
             Poll::Ready(res) => {
                 let bytes = res?;
-                let mut members = Vec::new();
 
-                let mut bytes = bytes.as_ref().to_vec();
-                let values = crate::json_from_slice::<Vec<Value>>(&mut bytes)?;
-
-                for value in values {
-                    let member_deserializer = MemberDeserializer::new(self.guild_id);
-                    members.push(member_deserializer.deserialize(value)?);
-                }
-
-                Poll::Ready(Ok(members))
+                Poll::Ready(members(self.guild_id, bytes.as_ref()))
             }
             Poll::Pending => Poll::Pending,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{members, GetGuildMembers, GetGuildMembersError, GetGuildMembersErrorType};
+    use crate::Client;
+    use twilight_model::id::{GuildId, UserId};
+
+    #[test]
+    fn test_request() -> Result<(), GetGuildMembersError> {
+        let client = Client::new("foo");
+        let builder = GetGuildMembers::new(&client, GuildId(1))
+            .after(UserId(2))?
+            .limit(1000)?;
+
+        assert_eq!(
+            "guilds/1/members?after=2&limit=1000",
+            builder.request().path_str
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_after_invalid() {
+        let client = Client::new("foo");
+
+        assert!(matches!(
+            GetGuildMembers::new(&client, GuildId(1))
+                .after(UserId(0))
+                .map(|_| ())
+                .unwrap_err()
+                .kind(),
+            GetGuildMembersErrorType::AfterInvalid { after: UserId(0) }
+        ));
+    }
+
+    #[test]
+    fn test_limit() {
+        let client = Client::new("foo");
+
+        for limit in &[0, 1001] {
+            assert!(matches!(
+                GetGuildMembers::new(&client, GuildId(1))
+                    .limit(*limit)
+                    .map(|_| ())
+                    .unwrap_err()
+                    .kind(),
+                GetGuildMembersErrorType::LimitInvalid { limit: l } if l == limit
+            ));
+        }
+
+        assert!(GetGuildMembers::new(&client, GuildId(1)).limit(1).is_ok());
+        assert!(GetGuildMembers::new(&client, GuildId(1))
+            .limit(1000)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_members_guild_id() -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = br#"[{
+            "deaf": false,
+            "hoisted_role": null,
+            "joined_at": "2021-01-01T00:00:00.000000+00:00",
+            "mute": false,
+            "nick": null,
+            "pending": false,
+            "roles": [],
+            "user": {
+                "avatar": null,
+                "discriminator": "0001",
+                "id": "2",
+                "username": "test"
+            }
+        }]"#;
+
+        let members = members(GuildId(1), bytes)?;
+
+        assert_eq!(1, members.len());
+        assert_eq!(GuildId(1), members[0].guild_id);
+        assert_eq!(UserId(2), members[0].user.id);
+
+        Ok(())
+    }
+}
@@ -119,7 +119,9 @@ impl<'a> UpdateGuild<'a> {
     /// Set the banner.
     ///
     /// This is a base64 encoded 16:9 PNG or JPEG image. Pass `None` to remove
-    /// the banner.
+    /// the banner. Use [`ImageData`] to encode the raw bytes of an image.
+    ///
+    /// [`ImageData`]: crate::request::ImageData
     ///
     /// The server must have the `BANNER` feature.
     pub fn banner(mut self, banner: impl Into<Option<String>>) -> Self {
@@ -159,9 +161,10 @@ impl<'a> UpdateGuild<'a> {
     ///
     /// This must be a Data URI, in the form of `data:image/{type};base64,{data}` where `{type}` is
     /// the image MIME type and `{data}` is the base64-encoded image. Refer to [the discord docs]
-    /// for more information.
+    /// for more information. Use [`ImageData`] to encode the raw bytes of an image.
     ///
     /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+    /// [`ImageData`]: crate::request::ImageData
     pub fn icon(mut self, icon: impl Into<Option<String>>) -> Self {
         self.fields.icon.replace(icon.into());
 
@@ -212,7 +215,10 @@ impl<'a> UpdateGuild<'a> {
 
     /// Set the guild's splash image.
     ///
-    /// Requires the guild to have the `INVITE_SPLASH` feature enabled.
+    /// Requires the guild to have the `INVITE_SPLASH` feature enabled. Use
+    /// [`ImageData`] to encode the raw bytes of an image.
+    ///
+    /// [`ImageData`]: crate::request::ImageData
     pub fn splash(mut self, splash: impl Into<Option<String>>) -> Self {
         self.fields.splash.replace(splash.into());
 
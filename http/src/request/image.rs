@@ -0,0 +1,359 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Error creating or decoding [`ImageData`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ImageDataError {
+    /// Format of the image couldn't be detected from its contents.
+    ///
+    /// Only GIFs, JPEGs and PNGs are detected.
+    FormatUnknown,
+    /// Image is larger than the limit of where it's uploaded.
+    TooLarge {
+        /// Maximum size in bytes.
+        limit: usize,
+        /// Size of the image in bytes.
+        size: usize,
+    },
+    /// Data URI isn't a base64-encoded image of a supported format.
+    UriInvalid,
+}
+
+impl Display for ImageDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::FormatUnknown => f.write_str("the format of the image is unknown"),
+            Self::TooLarge { limit, size } => f.write_fmt(format_args!(
+                "the image is {} bytes, but the limit is {} bytes",
+                size, limit,
+            )),
+            Self::UriInvalid => f.write_str("the data uri is invalid"),
+        }
+    }
+}
+
+impl Error for ImageDataError {}
+
+/// Format of an image uploaded as part of a request.
+///
+/// Images, such as a user's avatar, are sent to Discord as [Data URIs]. The
+/// format is used as the MIME type of the URI.
+///
+/// [Data URIs]: https://discord.com/developers/docs/reference#image-data
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ImageFormat {
+    /// The image is a GIF.
+    Gif,
+    /// The image is a JPEG.
+    Jpeg,
+    /// The image is a PNG.
+    Png,
+}
+
+impl ImageFormat {
+    /// Detect the format of an image from its contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_http::request::ImageFormat;
+    ///
+    /// assert_eq!(Some(ImageFormat::Gif), ImageFormat::detect(b"GIF89a..."));
+    /// assert!(ImageFormat::detect(b"not an image").is_none());
+    /// ```
+    pub fn detect(image: &[u8]) -> Option<Self> {
+        if image.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if image.starts_with(b"\xff\xd8\xff") {
+            Some(Self::Jpeg)
+        } else if image.starts_with(b"GIF87a") || image.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else {
+            None
+        }
+    }
+
+    /// Format with a MIME type, if any.
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type {
+            "image/gif" => Some(Self::Gif),
+            "image/jpeg" => Some(Self::Jpeg),
+            "image/png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+
+    /// MIME type of the format.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Gif => "image/gif",
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+
+    /// Encode an image of this format into a Data URI.
+    ///
+    /// The produced URI is in the form of `data:{type};base64,{data}`, where
+    /// `{type}` is the [MIME type] and `{data}` is the base64-encoded image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_http::request::ImageFormat;
+    ///
+    /// let uri = ImageFormat::Png.data_uri(b"image");
+    ///
+    /// assert_eq!("data:image/png;base64,aW1hZ2U=", uri);
+    /// ```
+    ///
+    /// [MIME type]: Self::mime_type
+    pub fn data_uri(self, image: impl AsRef<[u8]>) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.mime_type(),
+            base64::encode(image.as_ref())
+        )
+    }
+}
+
+/// Image encoded as a Data URI, to be uploaded as part of a request.
+///
+/// Request builders that take images as strings accept this, so images may be
+/// given either as raw bytes encoded via this type or as already encoded
+/// Data URIs.
+///
+/// # Examples
+///
+/// Create an emoji from the bytes of an image, whose format is detected:
+///
+/// ```rust,no_run
+/// use twilight_http::{request::ImageData, Client};
+/// use twilight_model::id::GuildId;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+/// let image = ImageData::emoji(std::fs::read("blobwave.png")?)?;
+///
+/// client.create_emoji(GuildId(1), "blobwave", image).await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ImageData {
+    format: ImageFormat,
+    uri: String,
+}
+
+impl ImageData {
+    /// Maximum size of the image of an emoji in bytes.
+    pub const EMOJI_SIZE_LIMIT: usize = 256 * 1024;
+
+    /// Encode an image of a format.
+    pub fn new(format: ImageFormat, image: impl AsRef<[u8]>) -> Self {
+        Self {
+            format,
+            uri: format.data_uri(image),
+        }
+    }
+
+    /// Encode an image, detecting its format from its contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageDataError::FormatUnknown`] if the format of the image
+    /// couldn't be detected.
+    pub fn from_bytes(image: impl AsRef<[u8]>) -> Result<Self, ImageDataError> {
+        let image = image.as_ref();
+        let format = ImageFormat::detect(image).ok_or(ImageDataError::FormatUnknown)?;
+
+        Ok(Self::new(format, image))
+    }
+
+    /// Encode the image of an emoji, detecting its format from its contents.
+    ///
+    /// Refer to [`EMOJI_SIZE_LIMIT`] for the maximum size of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageDataError::FormatUnknown`] if the format of the image
+    /// couldn't be detected.
+    ///
+    /// Returns [`ImageDataError::TooLarge`] if the image is larger than
+    /// [`EMOJI_SIZE_LIMIT`].
+    ///
+    /// [`EMOJI_SIZE_LIMIT`]: Self::EMOJI_SIZE_LIMIT
+    pub fn emoji(image: impl AsRef<[u8]>) -> Result<Self, ImageDataError> {
+        let image = image.as_ref();
+
+        if image.len() > Self::EMOJI_SIZE_LIMIT {
+            return Err(ImageDataError::TooLarge {
+                limit: Self::EMOJI_SIZE_LIMIT,
+                size: image.len(),
+            });
+        }
+
+        Self::from_bytes(image)
+    }
+
+    /// Decode a Data URI into the format and bytes of its image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_http::request::{ImageData, ImageFormat};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (format, image) = ImageData::decode("data:image/png;base64,aW1hZ2U=")?;
+    ///
+    /// assert_eq!(ImageFormat::Png, format);
+    /// assert_eq!(b"image", image.as_slice());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageDataError::UriInvalid`] if the URI isn't a
+    /// base64-encoded GIF, JPEG or PNG.
+    pub fn decode(uri: &str) -> Result<(ImageFormat, Vec<u8>), ImageDataError> {
+        let rest = uri
+            .strip_prefix("data:")
+            .ok_or(ImageDataError::UriInvalid)?;
+        let separator = rest.find(";base64,").ok_or(ImageDataError::UriInvalid)?;
+        let format =
+            ImageFormat::from_mime_type(&rest[..separator]).ok_or(ImageDataError::UriInvalid)?;
+        let image = base64::decode(&rest[separator + ";base64,".len()..])
+            .map_err(|_| ImageDataError::UriInvalid)?;
+
+        Ok((format, image))
+    }
+
+    /// Format of the image.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Data URI of the image.
+    pub fn as_str(&self) -> &str {
+        &self.uri
+    }
+}
+
+impl From<ImageData> for String {
+    fn from(data: ImageData) -> Self {
+        data.uri
+    }
+}
+
+impl From<ImageData> for Option<String> {
+    fn from(data: ImageData) -> Self {
+        Some(data.uri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageData, ImageDataError, ImageFormat};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, hash::Hash};
+
+    assert_fields!(ImageDataError::TooLarge: limit, size);
+    assert_impl_all!(ImageData: Clone, Debug, Eq, Hash, Into<String>, Into<Option<String>>, PartialEq, Send, Sync);
+    assert_impl_all!(ImageDataError: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(ImageFormat: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    /// A 1x1 transparent PNG.
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!("image/gif", ImageFormat::Gif.mime_type());
+        assert_eq!("image/jpeg", ImageFormat::Jpeg.mime_type());
+        assert_eq!("image/png", ImageFormat::Png.mime_type());
+    }
+
+    #[test]
+    fn test_data_uri_png() {
+        assert_eq!(
+            "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR4nGMAAQAABQABDQottAAAAABJRU5ErkJggg==",
+            ImageFormat::Png.data_uri(PNG),
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Some(ImageFormat::Png), ImageFormat::detect(PNG));
+        assert_eq!(
+            Some(ImageFormat::Jpeg),
+            ImageFormat::detect(&[0xff, 0xd8, 0xff, 0xe0])
+        );
+        assert_eq!(Some(ImageFormat::Gif), ImageFormat::detect(b"GIF87a"));
+        assert_eq!(Some(ImageFormat::Gif), ImageFormat::detect(b"GIF89a"));
+        assert!(ImageFormat::detect(b"GIF").is_none());
+        assert!(ImageFormat::detect(&PNG[1..]).is_none());
+        assert!(ImageFormat::detect(&[]).is_none());
+    }
+
+    #[test]
+    fn test_image_data() -> Result<(), ImageDataError> {
+        let data = ImageData::from_bytes(PNG)?;
+        assert_eq!(ImageFormat::Png, data.format());
+        assert_eq!(ImageFormat::Png.data_uri(PNG), data.as_str());
+        assert_eq!(ImageData::new(ImageFormat::Png, PNG), data);
+        assert_eq!(ImageData::emoji(PNG)?, data);
+
+        let (format, image) = ImageData::decode(data.as_str())?;
+        assert_eq!(ImageFormat::Png, format);
+        assert_eq!(PNG, image.as_slice());
+
+        assert_eq!(Some(data.as_str().to_owned()), Option::<String>::from(data),);
+        assert_eq!(
+            ImageDataError::FormatUnknown,
+            ImageData::from_bytes(b"image").unwrap_err(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_data_emoji_too_large() {
+        let mut image = PNG.to_vec();
+        image.resize(ImageData::EMOJI_SIZE_LIMIT, 0);
+        assert!(ImageData::emoji(&image).is_ok());
+
+        image.push(0);
+        assert_eq!(
+            ImageDataError::TooLarge {
+                limit: 262_144,
+                size: 262_145,
+            },
+            ImageData::emoji(&image).unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        for uri in &[
+            "",
+            "image/png;base64,aW1hZ2U=",
+            "data:image/png,aW1hZ2U=",
+            "data:image/webp;base64,aW1hZ2U=",
+            "data:image/png;base64,not base64",
+        ] {
+            assert_eq!(
+                ImageDataError::UriInvalid,
+                ImageData::decode(uri).unwrap_err()
+            );
+        }
+    }
+}
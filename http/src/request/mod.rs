@@ -72,6 +72,7 @@ mod get_gateway;
 mod get_gateway_authed;
 mod get_user_application;
 mod get_voice_regions;
+mod image;
 mod multipart;
 mod validate;
 
@@ -81,6 +82,7 @@ pub use self::{
     get_gateway_authed::GetGatewayAuthed,
     get_user_application::GetUserApplicationInfo,
     get_voice_regions::GetVoiceRegions,
+    image::{ImageData, ImageDataError, ImageFormat},
 };
 
 use self::multipart::Form;
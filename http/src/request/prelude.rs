@@ -6,6 +6,7 @@ pub use super::{
     get_gateway_authed::GetGatewayAuthed,
     get_voice_regions::GetVoiceRegions,
     guild::{ban::*, emoji::*, integration::*, member::*, role::*, *},
+    image::ImageFormat,
     user::*,
 };
 pub(super) use crate::{client::Client, error::Result, routing::Route};
@@ -6,9 +6,30 @@ struct CreatePrivateChannelFields {
     recipient_id: UserId,
 }
 
-/// Create a group DM.
+/// Create a DM channel with a user.
 ///
-/// This endpoint is limited to 10 active group DMs.
+/// If a DM channel with the user already exists then it is returned instead.
+/// The returned channel's `recipients` contains the recipient.
+///
+/// # Examples
+///
+/// Create a DM channel with a user and send them a message:
+///
+/// ```rust,no_run
+/// use twilight_http::Client;
+/// use twilight_model::id::UserId;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// let channel = client.create_private_channel(UserId(123)).await?;
+/// client
+///     .create_message(channel.id)
+///     .content("You have received a warning.")?
+///     .await?;
+/// # Ok(()) }
+/// ```
 pub struct CreatePrivateChannel<'a> {
     fields: CreatePrivateChannelFields,
     fut: Option<Pending<'a, PrivateChannel>>,
@@ -23,14 +44,89 @@ impl<'a> CreatePrivateChannel<'a> {
             http,
         }
     }
-    fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from((
+
+    fn request(&self) -> Result<Request> {
+        Ok(Request::from((
             crate::json_to_vec(&self.fields)?,
             Route::CreatePrivateChannel,
-        )))));
+        )))
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let request = self.request()?;
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(CreatePrivateChannel<'_>, PrivateChannel);
+
+#[cfg(test)]
+mod tests {
+    use super::CreatePrivateChannel;
+    use crate::{
+        api_error::{ApiError, ErrorCode},
+        routing::Path,
+        Client,
+    };
+    use hyper::Method;
+    use twilight_model::{
+        channel::{ChannelType, PrivateChannel},
+        id::{ChannelId, UserId},
+    };
+
+    #[test]
+    fn test_request() {
+        let client = Client::new("foo");
+        let builder = CreatePrivateChannel::new(&client, UserId(123));
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(Some(br#"{"recipient_id":"123"}"#.to_vec()), actual.body);
+        assert_eq!(Method::POST, actual.method);
+        assert_eq!(Path::UsersIdChannels, actual.path);
+        assert_eq!("users/@me/channels", actual.path_str);
+    }
+
+    #[test]
+    fn test_response_recipients() {
+        let mut body = br#"{
+            "id": "1",
+            "last_message_id": null,
+            "type": 1,
+            "recipients": [{
+                "id": "123",
+                "username": "twilight",
+                "avatar": null,
+                "discriminator": "0001"
+            }]
+        }"#
+        .to_vec();
+
+        let channel = crate::json_from_slice::<PrivateChannel>(&mut body)
+            .expect("failed to deserialize private channel");
+
+        assert_eq!(ChannelId(1), channel.id);
+        assert_eq!(ChannelType::Private, channel.kind);
+        assert_eq!(1, channel.recipients.len());
+        assert_eq!(UserId(123), channel.recipients[0].id);
+    }
+
+    /// Sending a message to a user that has DMs closed fails after the DM
+    /// channel itself was successfully created.
+    #[test]
+    fn test_cannot_send_message_to_user() {
+        let mut body = br#"{"code":50007,"message":"Cannot send messages to this user"}"#.to_vec();
+
+        let error =
+            crate::json_from_slice::<ApiError>(&mut body).expect("failed to deserialize error");
+
+        match error {
+            ApiError::General(general) => {
+                assert_eq!(ErrorCode::CannotSendMessageToUser, general.code);
+                assert_eq!("Cannot send messages to this user", general.message);
+            }
+            other => panic!("unexpected api error: {:?}", other),
+        }
+    }
+}
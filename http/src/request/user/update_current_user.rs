@@ -10,7 +10,7 @@ use twilight_model::user::User;
 #[non_exhaustive]
 pub enum UpdateCurrentUserError {
     /// The length of the username is either fewer than 2 UTF-16 characters or more than 32 UTF-16
-    /// characters.
+    /// characters, or the username contains a banned substring such as `@` or `discord`.
     UsernameInvalid {
         /// Provided username.
         username: String,
@@ -20,7 +20,7 @@ pub enum UpdateCurrentUserError {
 impl Display for UpdateCurrentUserError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Self::UsernameInvalid { .. } => f.write_str("the username length is invalid"),
+            Self::UsernameInvalid { .. } => f.write_str("the username is invalid"),
         }
     }
 }
@@ -40,6 +40,25 @@ struct UpdateCurrentUserFields {
 ///
 /// All paramaters are optional. If the username is changed, it may cause the discriminator to be
 /// rnadomized.
+///
+/// # Examples
+///
+/// Change the current user's username and remove their avatar:
+///
+/// ```rust,no_run
+/// use twilight_http::Client;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let client = Client::new("my token");
+///
+/// client
+///     .update_current_user()
+///     .username("twilight")?
+///     .avatar(None)
+///     .await?;
+/// # Ok(()) }
+/// ```
 pub struct UpdateCurrentUser<'a> {
     fields: UpdateCurrentUserFields,
     fut: Option<Pending<'a, User>>,
@@ -61,13 +80,41 @@ impl<'a> UpdateCurrentUser<'a> {
     /// the image MIME type and `{data}` is the base64-encoded image. Refer to [the discord docs]
     /// for more information.
     ///
+    /// Use [`avatar_image`] to encode raw image bytes, and pass `None` to
+    /// remove the avatar.
+    ///
     /// [the discord docs]: https://discord.com/developers/docs/reference#image-data
+    /// [`avatar_image`]: Self::avatar_image
     pub fn avatar(mut self, avatar: impl Into<Option<String>>) -> Self {
         self.fields.avatar.replace(avatar.into());
 
         self
     }
 
+    /// Set the user's avatar from the raw bytes of an image.
+    ///
+    /// The image is encoded into a Data URI of the given format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use twilight_http::{request::ImageFormat, Client};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = Client::new("my token");
+    /// let image = std::fs::read("avatar.png")?;
+    ///
+    /// client
+    ///     .update_current_user()
+    ///     .avatar_image(ImageFormat::Png, image)
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn avatar_image(self, format: ImageFormat, image: impl AsRef<[u8]>) -> Self {
+        self.avatar(format.data_uri(image))
+    }
+
     /// Set the username.
     ///
     /// The minimum length is 2 UTF-16 characters and the maximum is 32 UTF-16 characters.
@@ -75,7 +122,7 @@ impl<'a> UpdateCurrentUser<'a> {
     /// # Errors
     ///
     /// Returns [`UpdateCurrentUserError::UsernameInvalid`] if the username length is too short or
-    /// too long.
+    /// too long, or if the username contains a banned substring.
     pub fn username(self, username: impl Into<String>) -> Result<Self, UpdateCurrentUserError> {
         self._username(username.into())
     }
@@ -90,14 +137,81 @@ impl<'a> UpdateCurrentUser<'a> {
         Ok(self)
     }
 
-    fn start(&mut self) -> Result<()> {
-        self.fut.replace(Box::pin(self.http.request(Request::from((
+    fn request(&self) -> Result<Request> {
+        Ok(Request::from((
             crate::json_to_vec(&self.fields)?,
             Route::UpdateCurrentUser,
-        )))));
+        )))
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let request = self.request()?;
+        self.fut.replace(Box::pin(self.http.request(request)));
 
         Ok(())
     }
 }
 
 poll_req!(UpdateCurrentUser<'_>, User);
+
+#[cfg(test)]
+mod tests {
+    use super::{UpdateCurrentUser, UpdateCurrentUserError};
+    use crate::{
+        request::{ImageData, ImageFormat},
+        Client,
+    };
+
+    #[test]
+    fn test_avatar_image() {
+        let client = Client::new("foo");
+        let builder = UpdateCurrentUser::new(&client).avatar_image(ImageFormat::Png, b"png");
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(
+            Some(br#"{"avatar":"data:image/png;base64,cG5n"}"#.to_vec()),
+            actual.body
+        );
+    }
+
+    #[test]
+    fn test_avatar_image_data() {
+        let client = Client::new("foo");
+        let image = ImageData::new(ImageFormat::Png, b"png");
+        let builder = UpdateCurrentUser::new(&client).avatar(image);
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(
+            Some(br#"{"avatar":"data:image/png;base64,cG5n"}"#.to_vec()),
+            actual.body
+        );
+    }
+
+    #[test]
+    fn test_avatar_remove() {
+        let client = Client::new("foo");
+        let builder = UpdateCurrentUser::new(&client).avatar(None);
+        let actual = builder.request().expect("failed to create request");
+
+        assert_eq!(Some(br#"{"avatar":null}"#.to_vec()), actual.body);
+    }
+
+    #[test]
+    fn test_username_invalid() {
+        let client = Client::new("foo");
+
+        assert!(matches!(
+            UpdateCurrentUser::new(&client).username("a"),
+            Err(UpdateCurrentUserError::UsernameInvalid { username }) if username == "a"
+        ));
+        assert!(matches!(
+            UpdateCurrentUser::new(&client).username("twilight#0001"),
+            Err(UpdateCurrentUserError::UsernameInvalid { .. })
+        ));
+        assert!(matches!(
+            UpdateCurrentUser::new(&client).username("Discord Bot"),
+            Err(UpdateCurrentUserError::UsernameInvalid { .. })
+        ));
+        assert!(UpdateCurrentUser::new(&client).username("twilight").is_ok());
+    }
+}
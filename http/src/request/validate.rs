@@ -189,6 +189,29 @@ fn _content_limit(value: &str) -> bool {
 }
 
 pub fn embed(embed: &Embed) -> Result<(), EmbedValidationError> {
+    embed_chars(embed).map(|_| ())
+}
+
+/// Validate multiple embeds of a message.
+///
+/// In addition to the limits of each embed, the combined length of all of the
+/// embeds must not exceed [`EmbedValidationError::EMBED_TOTAL_LENGTH`].
+pub fn embeds(embeds: &[Embed]) -> Result<(), EmbedValidationError> {
+    let mut total = 0;
+
+    for embed in embeds {
+        total += embed_chars(embed)?;
+    }
+
+    if total > EmbedValidationError::EMBED_TOTAL_LENGTH {
+        return Err(EmbedValidationError::EmbedTooLarge { chars: total });
+    }
+
+    Ok(())
+}
+
+/// Validate an embed, returning its combined length in codepoints.
+fn embed_chars(embed: &Embed) -> Result<usize, EmbedValidationError> {
     let mut total = 0;
 
     if embed.fields.len() > EmbedValidationError::FIELD_COUNT {
@@ -261,7 +284,7 @@ pub fn embed(embed: &Embed) -> Result<(), EmbedValidationError> {
         return Err(EmbedValidationError::EmbedTooLarge { chars: total });
     }
 
-    Ok(())
+    Ok(total)
 }
 
 pub fn get_audit_log_limit(value: u64) -> bool {
@@ -305,6 +328,16 @@ pub fn guild_prune_days(value: u64) -> bool {
     value > 0 && value <= 30
 }
 
+pub fn invite_max_age(value: u64) -> bool {
+    // <https://discord.com/developers/docs/resources/channel#create-channel-invite-json-params>
+    value <= 604_800
+}
+
+pub fn invite_max_uses(value: u64) -> bool {
+    // <https://discord.com/developers/docs/resources/channel#create-channel-invite-json-params>
+    value <= 100
+}
+
 pub fn nickname(value: impl AsRef<str>) -> bool {
     _nickname(value.as_ref())
 }
@@ -322,9 +355,16 @@ pub fn username(value: impl AsRef<str>) -> bool {
 }
 
 fn _username(value: &str) -> bool {
+    const BANNED_NAMES: &[&str] = &["everyone", "here"];
+    const BANNED_SUBSTRINGS: &[&str] = &["@", "#", ":", "```", "discord"];
+
     let len = value.chars().count();
+    // Discord rejects banned names and substrings regardless of their case.
+    let lowercase = value.to_ascii_lowercase();
 
     (2..=32).contains(&len)
+        && !BANNED_NAMES.contains(&lowercase.as_str())
+        && !BANNED_SUBSTRINGS.iter().any(|s| lowercase.contains(s))
 }
 
 #[cfg(test)]
@@ -441,6 +481,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_embeds_combined_limit() {
+        let mut embed = base_embed();
+        embed.description.replace(str::repeat("a", 2000));
+        assert!(embeds(&[embed.clone(), embed.clone(), embed.clone()]).is_ok());
+
+        let mut title = embed.clone();
+        title.title.replace("a".to_owned());
+        assert!(matches!(
+            embeds(&[embed.clone(), embed.clone(), title]),
+            Err(EmbedValidationError::EmbedTooLarge { chars: 6001 })
+        ));
+
+        embed.description.replace(str::repeat("a", 2049));
+        assert!(matches!(
+            embeds(&[base_embed(), embed]),
+            Err(EmbedValidationError::DescriptionTooLarge { chars: 2049 })
+        ));
+    }
+
     #[test]
     fn test_embed_field_count_limit() {
         let mut embed = base_embed();
@@ -628,6 +688,23 @@ mod tests {
         assert!(!guild_prune_days(100));
     }
 
+    #[test]
+    fn test_invite_max_age() {
+        assert!(invite_max_age(0));
+        assert!(invite_max_age(86_400));
+        assert!(invite_max_age(604_800));
+
+        assert!(!invite_max_age(604_801));
+    }
+
+    #[test]
+    fn test_invite_max_uses() {
+        assert!(invite_max_uses(0));
+        assert!(invite_max_uses(100));
+
+        assert!(!invite_max_uses(101));
+    }
+
     #[test]
     fn test_nickname() {
         assert!(nickname("a"));
@@ -644,5 +721,18 @@ mod tests {
 
         assert!(!username("a"));
         assert!(!username("a".repeat(33)));
+
+        assert!(!username("everyone"));
+        assert!(!username("here"));
+        assert!(!username("twi@light"));
+        assert!(!username("twi#light"));
+        assert!(!username("twi:light"));
+        assert!(!username("twi```light"));
+        assert!(!username("discord bot"));
+        assert!(!username("Discord Bot"));
+        assert!(!username("DISCORD"));
+        assert!(!username("my DiScOrD bot"));
+        assert!(!username("Everyone"));
+        assert!(username("everyone else"));
     }
 }
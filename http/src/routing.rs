@@ -135,6 +135,58 @@ pub enum Path {
     WebhooksId(u64),
 }
 
+impl Path {
+    /// ID of the major parameter of the path, if it has one.
+    ///
+    /// Discord tracks the ratelimits of paths with a major parameter, being a
+    /// channel, guild, or webhook ID, separately for each of its values.
+    pub(crate) fn major_parameter(&self) -> Option<u64> {
+        match self {
+            Self::ChannelsId(id)
+            | Self::ChannelsIdInvites(id)
+            | Self::ChannelsIdMessages(id)
+            | Self::ChannelsIdMessagesBulkDelete(id)
+            | Self::ChannelsIdMessagesIdCrosspost(id)
+            | Self::ChannelsIdMessagesIdReactions(id)
+            | Self::ChannelsIdMessagesIdReactionsUserIdType(id)
+            | Self::ChannelsIdPermissionsOverwriteId(id)
+            | Self::ChannelsIdPins(id)
+            | Self::ChannelsIdPinsMessageId(id)
+            | Self::ChannelsIdTyping(id)
+            | Self::ChannelsIdWebhooks(id)
+            | Self::ChannelsIdFollowers(id)
+            | Self::GuildsId(id)
+            | Self::GuildsIdBans(id)
+            | Self::GuildsIdBansId(id)
+            | Self::GuildsIdAuditLogs(id)
+            | Self::GuildsIdBansUserId(id)
+            | Self::GuildsIdChannels(id)
+            | Self::GuildsIdWidget(id)
+            | Self::GuildsIdEmojis(id)
+            | Self::GuildsIdEmojisId(id)
+            | Self::GuildsIdIntegrations(id)
+            | Self::GuildsIdIntegrationsId(id)
+            | Self::GuildsIdIntegrationsIdSync(id)
+            | Self::GuildsIdInvites(id)
+            | Self::GuildsIdMembers(id)
+            | Self::GuildsIdMembersId(id)
+            | Self::GuildsIdMembersIdRolesId(id)
+            | Self::GuildsIdMembersMeNick(id)
+            | Self::GuildsIdPreview(id)
+            | Self::GuildsIdPrune(id)
+            | Self::GuildsIdRegions(id)
+            | Self::GuildsIdRoles(id)
+            | Self::GuildsIdRolesId(id)
+            | Self::GuildsIdVanityUrl(id)
+            | Self::GuildsIdWebhooks(id)
+            | Self::WebhooksIdTokenMessageId(id)
+            | Self::WebhooksId(id)
+            | Self::ChannelsIdMessagesId(_, id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
 impl FromStr for Path {
     type Err = PathParseError;
 
@@ -1351,7 +1403,7 @@ impl Route {
                 }
 
                 if let Some(before) = before {
-                    let _ = write!(path, "before={}", before);
+                    let _ = write!(path, "&before={}", before);
                 }
 
                 if let Some(limit) = limit {
@@ -1562,4 +1614,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_path_major_parameter() {
+        assert_eq!(Some(123), Path::ChannelsIdMessages(123).major_parameter());
+        assert_eq!(
+            Some(123),
+            Path::ChannelsIdMessagesId(Method::DELETE, 123).major_parameter()
+        );
+        assert_eq!(Some(123), Path::GuildsIdMembersId(123).major_parameter());
+        assert_eq!(Some(123), Path::WebhooksId(123).major_parameter());
+        assert!(Path::UsersIdGuilds.major_parameter().is_none());
+    }
 }
@@ -0,0 +1,258 @@
+//! Normalizing and validating tokens, shared with the gateway.
+//!
+//! Tokens copied from the developer portal may be padded with whitespace, and
+//! may or may not carry the `"Bot "` prefix. HTTP requests need the prefix in
+//! the `Authorization` header, while identifying with the gateway needs the
+//! token without it, so both are derived from the same trimmed token.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Prefix of bot tokens in the `Authorization` header.
+pub const BOT_PREFIX: &str = "Bot ";
+
+/// Prefix of Bearer tokens in the `Authorization` header.
+pub const BEARER_PREFIX: &str = "Bearer ";
+
+/// The error created when a bot token doesn't have the structure of one.
+///
+/// A bot token consists of three parts of URL-safe base64 separated by
+/// periods.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TokenError {
+    /// The token isn't a bot token but a Bearer token.
+    Bearer,
+    /// A part of the token contains a character that isn't URL-safe base64.
+    CharacterInvalid {
+        /// The invalid character.
+        character: char,
+        /// Position of the part, starting at 1.
+        part: usize,
+    },
+    /// The token is empty or only whitespace.
+    Empty,
+    /// The token doesn't consist of three parts separated by periods.
+    PartCount {
+        /// The number of parts of the token.
+        parts: usize,
+    },
+    /// A part of the token is empty.
+    PartEmpty {
+        /// Position of the part, starting at 1.
+        part: usize,
+    },
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Bearer => f.write_str("the token is a Bearer token instead of a bot token"),
+            Self::CharacterInvalid { character, part } => f.write_fmt(format_args!(
+                "part {} of the token contains {:?}, which isn't a base64 character",
+                part, character,
+            )),
+            Self::Empty => f.write_str("the token is empty"),
+            Self::PartCount { parts } => f.write_fmt(format_args!(
+                "the token has {} parts instead of 3 separated by periods",
+                parts,
+            )),
+            Self::PartEmpty { part } => {
+                f.write_fmt(format_args!("part {} of the token is empty", part))
+            }
+        }
+    }
+}
+
+impl Error for TokenError {}
+
+/// Format a token as a bot token, unless it already has a prefix.
+///
+/// Surrounding whitespace is trimmed, and the token is prefixed with `"Bot "`
+/// unless it's already prefixed with either `"Bot "` or `"Bearer "`.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_http::token;
+///
+/// assert_eq!("Bot abc", token::bot(" abc\n"));
+/// assert_eq!("Bot abc", token::bot("Bot abc"));
+/// assert_eq!("Bearer abc", token::bot("Bearer abc"));
+/// ```
+pub fn bot(token: &str) -> String {
+    let token = token.trim();
+
+    if token.starts_with(BOT_PREFIX) || token.starts_with(BEARER_PREFIX) {
+        token.to_owned()
+    } else {
+        format!("{}{}", BOT_PREFIX, token)
+    }
+}
+
+/// Format a token as a Bearer token, unless it already has the prefix.
+///
+/// Surrounding whitespace is trimmed.
+pub fn bearer(token: &str) -> String {
+    let token = token.trim();
+
+    if token.starts_with(BEARER_PREFIX) {
+        token.to_owned()
+    } else {
+        format!("{}{}", BEARER_PREFIX, token)
+    }
+}
+
+/// Return a token without surrounding whitespace and without the `"Bot "`
+/// prefix, as used when identifying with the gateway.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_http::token;
+///
+/// assert_eq!("abc", token::raw(" Bot abc "));
+/// assert_eq!("abc", token::raw("abc"));
+/// ```
+pub fn raw(token: &str) -> &str {
+    let token = token.trim();
+
+    token
+        .strip_prefix(BOT_PREFIX)
+        .map_or(token, str::trim_start)
+}
+
+/// Validate that a bot token, with or without the `"Bot "` prefix, has the
+/// structure of one.
+///
+/// Only the structure is checked, so a valid token may still be rejected by
+/// Discord.
+///
+/// # Errors
+///
+/// Returns [`TokenError::Empty`] if the token is empty.
+///
+/// Returns [`TokenError::Bearer`] if the token is a Bearer token.
+///
+/// Returns [`TokenError::PartCount`] if the token doesn't have three parts
+/// separated by periods.
+///
+/// Returns [`TokenError::PartEmpty`] if a part is empty.
+///
+/// Returns [`TokenError::CharacterInvalid`] if a part contains a character
+/// that isn't URL-safe base64.
+pub fn validate(token: &str) -> Result<(), TokenError> {
+    let token = raw(token);
+
+    if token.is_empty() {
+        return Err(TokenError::Empty);
+    }
+
+    if token.starts_with(BEARER_PREFIX) {
+        return Err(TokenError::Bearer);
+    }
+
+    let parts = token.split('.').count();
+
+    if parts != 3 {
+        return Err(TokenError::PartCount { parts });
+    }
+
+    for (idx, part) in token.split('.').enumerate() {
+        if part.is_empty() {
+            return Err(TokenError::PartEmpty { part: idx + 1 });
+        }
+
+        if let Some(character) = part
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+        {
+            return Err(TokenError::CharacterInvalid {
+                character,
+                part: idx + 1,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenError;
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+
+    assert_impl_all!(TokenError: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
+
+    const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4.YAbC-d.abcdefghijklmnopqrstu_vwxyz";
+
+    #[test]
+    fn test_bot() {
+        assert_eq!(format!("Bot {}", TOKEN), super::bot(TOKEN));
+        assert_eq!(
+            format!("Bot {}", TOKEN),
+            super::bot(&format!("Bot {}", TOKEN))
+        );
+        assert_eq!(
+            format!("Bot {}", TOKEN),
+            super::bot(&format!("  {}\r\n", TOKEN))
+        );
+        assert_eq!("Bearer token", super::bot("\tBearer token "));
+    }
+
+    #[test]
+    fn test_bearer() {
+        assert_eq!("Bearer token", super::bearer("token"));
+        assert_eq!("Bearer token", super::bearer(" Bearer token\n"));
+    }
+
+    #[test]
+    fn test_raw() {
+        assert_eq!(TOKEN, super::raw(TOKEN));
+        assert_eq!(TOKEN, super::raw(&format!("Bot {}", TOKEN)));
+        assert_eq!(TOKEN, super::raw(&format!("\n Bot  {} \n", TOKEN)));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(super::validate(TOKEN).is_ok());
+        assert!(super::validate(&format!("Bot {}", TOKEN)).is_ok());
+        assert!(super::validate(&format!(" {}\n", TOKEN)).is_ok());
+
+        assert_eq!(Err(TokenError::Empty), super::validate(""));
+        assert_eq!(Err(TokenError::Empty), super::validate("  \n"));
+        assert_eq!(
+            Err(TokenError::Bearer),
+            super::validate("Bearer abc.def.ghi")
+        );
+        assert_eq!(
+            Err(TokenError::PartCount { parts: 1 }),
+            super::validate("token")
+        );
+        assert_eq!(
+            Err(TokenError::PartCount { parts: 4 }),
+            super::validate("a.b.c.d")
+        );
+        assert_eq!(
+            Err(TokenError::PartEmpty { part: 2 }),
+            super::validate("abc..def")
+        );
+        assert_eq!(
+            Err(TokenError::CharacterInvalid {
+                character: ' ',
+                part: 1
+            }),
+            super::validate("ab c.def.ghi")
+        );
+        assert_eq!(
+            Err(TokenError::CharacterInvalid {
+                character: '+',
+                part: 3
+            }),
+            super::validate("abc.def.g+hi")
+        );
+    }
+}
@@ -1,8 +1,8 @@
 //! Client to manage nodes and players.
 
 use crate::{
-    model::{IncomingEvent, OutgoingEvent, VoiceUpdate},
-    node::{Node, NodeConfig, NodeError, Resume},
+    model::{Destroy, IncomingEvent, OutgoingEvent, SlimVoiceServerUpdate, VoiceUpdate},
+    node::{Backoff, Node, NodeConfig, NodeError, Resume},
     player::{Player, PlayerManager},
 };
 use dashmap::{mapref::one::Ref, DashMap};
@@ -14,11 +14,8 @@ use std::{
     sync::Arc,
 };
 use twilight_model::{
-    gateway::{
-        event::Event,
-        payload::{VoiceServerUpdate, VoiceStateUpdate},
-    },
-    id::{GuildId, UserId},
+    gateway::{event::Event, payload::VoiceServerUpdate},
+    id::{ChannelId, GuildId, UserId},
 };
 
 /// An error that can occur while interacting with the client.
@@ -27,8 +24,8 @@ use twilight_model::{
 pub enum ClientError {
     /// A node isn't configured, so the operation isn't possible to fulfill.
     NodesUnconfigured,
-    /// Sending a voice update event to the node failed because the node's
-    /// connection was shutdown.
+    /// Sending a voice update or destroy event to the node failed because the
+    /// node's connection was shutdown.
     SendingVoiceUpdate {
         /// The source of the error.
         source: TrySendError<OutgoingEvent>,
@@ -53,10 +50,28 @@ impl Error for ClientError {
     }
 }
 
-#[derive(Debug)]
-enum VoiceStateHalf {
-    Server(VoiceServerUpdate),
-    State(Box<VoiceStateUpdate>),
+/// The halves of the voice connection of the bot in a guild, as last received
+/// from Discord.
+#[derive(Debug, Default)]
+struct VoiceHalves {
+    /// The voice server of the guild, if one is allocated.
+    server: Option<VoiceServerUpdate>,
+    /// The channel and session ID of the bot's voice state.
+    state: Option<(ChannelId, String)>,
+}
+
+impl VoiceHalves {
+    /// Create the voice update to send if both halves are known.
+    fn update(&self, guild_id: GuildId) -> Option<VoiceUpdate> {
+        match (&self.server, &self.state) {
+            (Some(server), Some((_, session_id))) => Some(VoiceUpdate::new(
+                guild_id,
+                session_id,
+                SlimVoiceServerUpdate::from(server.clone()),
+            )),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -67,7 +82,7 @@ struct LavalinkRef {
     resume: Option<Resume>,
     shard_count: u64,
     user_id: UserId,
-    waiting: DashMap<GuildId, VoiceStateHalf>,
+    voice: DashMap<GuildId, VoiceHalves>,
 }
 
 /// The lavalink client that manages nodes, players, and processes events from
@@ -132,7 +147,7 @@ impl Lavalink {
             resume,
             shard_count,
             user_id,
-            waiting: DashMap::new(),
+            voice: DashMap::new(),
         }))
     }
 
@@ -146,20 +161,29 @@ impl Lavalink {
     /// nodes. For simplicity in some applications' event loops, any event can
     /// be provided, but they will just be ignored.
     ///
-    /// The Ready event can optionally be provided to do some cleaning of
-    /// stalled voice states that never received their voice server update half
-    /// or vice versa. It is recommended that you process Ready events.
+    /// The last voice server and voice state of the bot are kept for each
+    /// guild, and a voice update is sent whenever either of them changes
+    /// while both are known, such as when the bot is moved to another channel
+    /// or the voice server of the guild changes. When the bot disconnects from
+    /// a guild's voice channel, its player is destroyed.
+    ///
+    /// The Ready event can optionally be provided to clear the voice states
+    /// and servers of the guilds of the shard, as they belong to its previous
+    /// session. It is recommended that you process Ready events.
     ///
     /// # Errors
     ///
     /// Returns [`ClientError::NodesUnconfigured`] if no nodes have been added
     /// to the client when attempting to retrieve a guild's player.
     ///
+    /// Returns [`ClientError::SendingVoiceUpdate`] if sending the voice update
+    /// or destroying the player failed.
+    ///
     /// [crate documentation]: crate#examples
     pub async fn process(&self, event: &Event) -> Result<(), ClientError> {
         tracing::trace!("processing event: {:?}", event);
 
-        let (guild_id, half) = match event {
+        let (guild_id, update) = match event {
             Event::Ready(e) => {
                 let shard_id = e.shard.map_or(0, |[id, _]| id);
 
@@ -167,7 +191,14 @@ impl Lavalink {
 
                 return Ok(());
             }
-            Event::VoiceServerUpdate(e) => (e.guild_id, VoiceStateHalf::Server(e.clone())),
+            Event::VoiceServerUpdate(e) => match e.guild_id {
+                Some(guild_id) => (guild_id, self.voice_server(guild_id, e)),
+                None => {
+                    tracing::trace!("event has no guild ID: {:?}", event);
+
+                    return Ok(());
+                }
+            },
             Event::VoiceStateUpdate(e) => {
                 if e.0.user_id != self.0.user_id {
                     tracing::trace!("got voice state update from another user");
@@ -175,87 +206,35 @@ impl Lavalink {
                     return Ok(());
                 }
 
-                (e.0.guild_id, VoiceStateHalf::State(e.clone()))
+                let guild_id = match e.0.guild_id {
+                    Some(guild_id) => guild_id,
+                    None => {
+                        tracing::trace!("event has no guild ID: {:?}", event);
+
+                        return Ok(());
+                    }
+                };
+
+                match e.0.channel_id {
+                    Some(channel_id) => (
+                        guild_id,
+                        self.voice_state(guild_id, channel_id, &e.0.session_id),
+                    ),
+                    None => return self.disconnect(guild_id),
+                }
             }
             _ => return Ok(()),
         };
 
-        tracing::debug!(
-            "got voice server/state update for {:?}: {:?}",
-            guild_id,
-            half
-        );
-
-        let guild_id = match guild_id {
-            Some(guild_id) => guild_id,
+        let update = match update {
+            Some(update) => update,
             None => {
-                tracing::trace!("event has no guild ID: {:?}", event);
+                tracing::debug!("guild {} has no voice update to send", guild_id);
 
                 return Ok(());
             }
         };
 
-        let update = {
-            let existing_half = match self.0.waiting.get(&guild_id) {
-                Some(existing_half) => existing_half,
-                None => {
-                    tracing::debug!(
-                        "guild {} is now waiting for other half; got: {:?}",
-                        guild_id,
-                        half
-                    );
-                    self.0.waiting.insert(guild_id, half);
-
-                    return Ok(());
-                }
-            };
-            tracing::debug!(
-                "got both halves for {}: {:?}; {:?}",
-                guild_id,
-                half,
-                existing_half.value()
-            );
-
-            match (existing_half.value(), half) {
-                (VoiceStateHalf::Server(_), VoiceStateHalf::Server(server)) => {
-                    // We got the same half twice... weird, but let's just replace
-                    // the existing one.
-                    tracing::debug!(
-                        "got the same server half twice for guild {}: {:?}",
-                        guild_id,
-                        server
-                    );
-                    self.0
-                        .waiting
-                        .insert(guild_id, VoiceStateHalf::Server(server));
-
-                    return Ok(());
-                }
-                (VoiceStateHalf::Server(ref server), VoiceStateHalf::State(ref state)) => {
-                    VoiceUpdate::new(guild_id, &state.0.session_id, From::from(server.clone()))
-                }
-                (VoiceStateHalf::State(_), VoiceStateHalf::State(state)) => {
-                    // Just like above, we got the same half twice...
-                    tracing::debug!(
-                        "got the same state half twice for guild {}: {:?}",
-                        guild_id,
-                        state
-                    );
-                    self.0
-                        .waiting
-                        .insert(guild_id, VoiceStateHalf::State(state));
-
-                    return Ok(());
-                }
-                (VoiceStateHalf::State(ref state), VoiceStateHalf::Server(ref server)) => {
-                    VoiceUpdate::new(guild_id, &state.0.session_id, From::from(server.clone()))
-                }
-            }
-        };
-
-        tracing::debug!("removing guild {} from waiting list", guild_id);
-        self.0.waiting.remove(&guild_id);
-
         tracing::debug!("getting player for guild {}", guild_id);
         let player = self.player(guild_id).await?;
         tracing::debug!("sending voice update for guild {}: {:?}", guild_id, update);
@@ -267,6 +246,71 @@ impl Lavalink {
         Ok(())
     }
 
+    /// Update the voice server of a guild, returning the voice update to send
+    /// if it changed.
+    fn voice_server(&self, guild_id: GuildId, server: &VoiceServerUpdate) -> Option<VoiceUpdate> {
+        let mut halves = self.0.voice.entry(guild_id).or_default();
+
+        // Voice servers without an endpoint are being reallocated, and a new
+        // voice server will follow.
+        if server.endpoint.is_none() {
+            tracing::debug!("voice server of guild {} is being reallocated", guild_id);
+            halves.server.take();
+
+            return None;
+        }
+
+        if halves.server.as_ref() == Some(server) {
+            tracing::debug!("got the same voice server twice for guild {}", guild_id);
+
+            return None;
+        }
+
+        halves.server.replace(server.clone());
+
+        halves.update(guild_id)
+    }
+
+    /// Update the voice state of the bot in a guild, returning the voice update
+    /// to send if its channel or session changed.
+    ///
+    /// Voice states are also updated when the bot is muted or deafened, which
+    /// doesn't require sending a new voice update.
+    fn voice_state(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        session_id: &str,
+    ) -> Option<VoiceUpdate> {
+        let mut halves = self.0.voice.entry(guild_id).or_default();
+
+        if let Some((current_channel_id, current_session_id)) = halves.state.as_ref() {
+            if *current_channel_id == channel_id && current_session_id == session_id {
+                tracing::trace!("voice state of guild {} is unchanged", guild_id);
+
+                return None;
+            }
+        }
+
+        halves.state.replace((channel_id, session_id.to_owned()));
+
+        halves.update(guild_id)
+    }
+
+    /// Forget the voice connection of a guild after the bot disconnected from
+    /// its voice channel, destroying its player.
+    fn disconnect(&self, guild_id: GuildId) -> Result<(), ClientError> {
+        tracing::debug!("bot disconnected from voice in guild {}", guild_id);
+        self.0.voice.remove(&guild_id);
+
+        match self.players().get(&guild_id) {
+            Some(player) => player
+                .send(Destroy::new(guild_id))
+                .map_err(|source| ClientError::SendingVoiceUpdate { source }),
+            None => Ok(()),
+        }
+    }
+
     /// Add a new node to be managed by the Lavalink client.
     ///
     /// If a node already exists with the provided address, then it will be
@@ -275,10 +319,34 @@ impl Lavalink {
         &self,
         address: SocketAddr,
         authorization: impl Into<String>,
+    ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
+        self._add(address, authorization.into(), Backoff::default())
+            .await
+    }
+
+    /// Like [`add`], but allows you to specify how connecting to the node is
+    /// retried, including when reconnecting after the connection closed.
+    ///
+    /// [`add`]: Self::add
+    pub async fn add_with_backoff(
+        &self,
+        address: SocketAddr,
+        authorization: impl Into<String>,
+        backoff: Backoff,
+    ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
+        self._add(address, authorization.into(), backoff).await
+    }
+
+    async fn _add(
+        &self,
+        address: SocketAddr,
+        authorization: String,
+        backoff: Backoff,
     ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
         let config = NodeConfig {
             address,
-            authorization: authorization.into(),
+            authorization,
+            backoff,
             resume: self.0.resume.clone(),
             shard_count: self.0.shard_count,
             user_id: self.0.user_id,
@@ -310,19 +378,20 @@ impl Lavalink {
     ///
     /// [`Node::penalty`]: crate::node::Node::penalty
     pub async fn best(&self) -> Result<Node, ClientError> {
-        let mut lowest = i32::MAX;
-        let mut best = None;
+        let mut best: Option<(i32, Node)> = None;
 
         for node in self.0.nodes.iter() {
             let penalty = node.value().penalty().await;
 
-            if penalty < lowest {
-                lowest = penalty;
-                best.replace(node.clone());
+            // Nodes without stats have the maximum penalty, but may still be
+            // the only nodes available.
+            if best.as_ref().map_or(true, |(lowest, _)| penalty < *lowest) {
+                best.replace((penalty, node.clone()));
             }
         }
 
-        best.ok_or(ClientError::NodesUnconfigured)
+        best.map(|(_, node)| node)
+            .ok_or(ClientError::NodesUnconfigured)
     }
 
     /// Retrieve an immutable reference to the player manager.
@@ -353,35 +422,27 @@ impl Lavalink {
         Ok(self.players().get_or_insert(guild_id, node).downgrade())
     }
 
-    /// Clear out the map of guild states/updates for a shard that are waiting
-    /// for their other half.
+    /// Clear out the voice states and servers of the guilds of a shard.
     ///
-    /// We can do this by iterating over the map and removing the ones that we
-    /// can calculate came from a shard.
-    ///
-    /// This map should be small or empty, and if it isn't, then it needs to be
-    /// cleared out anyway.
+    /// We can do this by removing the guilds that we can calculate belong to
+    /// the shard.
     fn clear_shard_states(&self, shard_id: u64) {
         let shard_count = self.0.shard_count;
 
-        for r in self.0.waiting.iter() {
-            let guild_id = r.key();
-
-            if (guild_id.0 >> 22) % shard_count == shard_id {
-                self.0.waiting.remove(guild_id);
-            }
-        }
+        self.0
+            .voice
+            .retain(|guild_id, _| (guild_id.0 >> 22) % shard_count != shard_id);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ClientError, Lavalink, VoiceStateHalf};
+    use super::{ClientError, Lavalink, VoiceHalves};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
 
     assert_fields!(ClientError::SendingVoiceUpdate: source);
     assert_impl_all!(ClientError: Clone, Debug, Error, PartialEq, Send, Sync);
     assert_impl_all!(Lavalink: Clone, Debug, Send, Sync);
-    assert_impl_all!(VoiceStateHalf: Debug, Send, Sync);
+    assert_impl_all!(VoiceHalves: Debug, Default, Send, Sync);
 }
@@ -1,13 +1,17 @@
 //! Models to deserialize responses into and functions to create `http` crate
 //! requests.
 
+use crate::model::TrackExceptionSeverity;
 use http::{
-    header::{HeaderValue, AUTHORIZATION},
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE},
     Error as HttpError, Request,
 };
 use percent_encoding::NON_ALPHANUMERIC;
-use serde::{Deserialize, Serialize};
-use std::net::{IpAddr, SocketAddr};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    convert::TryFrom,
+    net::{IpAddr, SocketAddr},
+};
 
 /// The type of search result given.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -63,24 +67,58 @@ pub struct TrackInfo {
 }
 
 /// Information about a playlist from a search result.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+///
+/// Results that aren't playlists have no information.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct PlaylistInfo {
     /// The name of the playlist, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    /// The selected track, if one was selected.
+    /// The index of the selected track within the playlist's tracks, if one
+    /// was selected.
+    ///
+    /// Nodes send -1 when no track was selected.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_selected_track",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub selected_track: Option<u64>,
 }
 
+fn deserialize_selected_track<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    let index = Option::<i64>::deserialize(deserializer)?;
+
+    Ok(index.and_then(|index| u64::try_from(index).ok()))
+}
+
+/// The reason that loading the results of a query failed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct LoadException {
+    /// The message of the exception, if any.
+    pub message: Option<String>,
+    /// How severe the exception is.
+    pub severity: TrackExceptionSeverity,
+}
+
 /// Possible track results for a query.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct LoadedTracks {
+    /// The reason that loading the results failed, if the load type is
+    /// [`LoadType::LoadFailed`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exception: Option<LoadException>,
     /// The type of search result, such as a list of tracks or a playlist.
     pub load_type: LoadType,
     /// Information about the playlist, if provided.
+    #[serde(default)]
     pub playlist_info: PlaylistInfo,
     /// The list of tracks returned for the search query.
     pub tracks: Vec<Track>,
@@ -252,6 +290,56 @@ pub fn load_track(
     req.body(b"")
 }
 
+/// Search YouTube for tracks matching a query.
+///
+/// The response will include a body which can be deserialized into a
+/// [`LoadedTracks`] with a [`LoadType::SearchResult`].
+pub fn search_youtube(
+    address: SocketAddr,
+    query: impl AsRef<str>,
+    authorization: impl AsRef<str>,
+) -> Result<Request<&'static [u8]>, HttpError> {
+    load_track(
+        address,
+        format!("ytsearch:{}", query.as_ref()),
+        authorization,
+    )
+}
+
+/// Search SoundCloud for tracks matching a query.
+///
+/// The response will include a body which can be deserialized into a
+/// [`LoadedTracks`] with a [`LoadType::SearchResult`].
+pub fn search_soundcloud(
+    address: SocketAddr,
+    query: impl AsRef<str>,
+    authorization: impl AsRef<str>,
+) -> Result<Request<&'static [u8]>, HttpError> {
+    load_track(
+        address,
+        format!("scsearch:{}", query.as_ref()),
+        authorization,
+    )
+}
+
+/// Decode base64 track strings into their information.
+///
+/// The response will include a body which can be deserialized into a list of
+/// [`Track`]s, in the same order as the track strings.
+pub fn decode_tracks(
+    address: SocketAddr,
+    tracks: Vec<String>,
+    authorization: impl AsRef<str>,
+) -> Result<Request<Vec<u8>>, HttpError> {
+    let mut req = Request::post(format!("http://{}/decodetracks", address));
+
+    let auth_value = HeaderValue::from_str(authorization.as_ref())?;
+    req = req.header(AUTHORIZATION, auth_value);
+    req = req.header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    req.body(serde_json::to_vec(&tracks).unwrap())
+}
+
 /// Get the configured route planner for a node by address.
 ///
 /// The response will include a body which can be deserialized into a
@@ -292,14 +380,24 @@ pub fn unmark_failed_address(
 #[cfg(test)]
 mod tests {
     use super::{
-        FailingAddress, IpBlock, IpBlockType, LoadType, LoadedTracks, NanoIpDetails,
+        FailingAddress, IpBlock, IpBlockType, LoadException, LoadType, LoadedTracks, NanoIpDetails,
         NanoIpRoutePlanner, PlaylistInfo, RotatingIpDetails, RotatingIpRoutePlanner,
         RotatingNanoIpDetails, RotatingNanoIpRoutePlanner, RoutePlanner, RoutePlannerType, Track,
         TrackInfo,
     };
+    use crate::model::TrackExceptionSeverity;
+    use http::{header::CONTENT_TYPE, Method};
     use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::fmt::Debug;
+    use std::{
+        fmt::Debug,
+        net::{Ipv4Addr, SocketAddr},
+    };
+
+    fn address() -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, 2333))
+    }
 
     assert_fields!(FailingAddress: address, failing_timestamp, failing_time);
     assert_impl_all!(
@@ -343,7 +441,18 @@ mod tests {
         Serialize,
         Sync,
     );
-    assert_fields!(LoadedTracks: load_type, playlist_info, tracks);
+    assert_fields!(LoadException: message, severity);
+    assert_impl_all!(
+        LoadException: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(LoadedTracks: exception, load_type, playlist_info, tracks);
     assert_impl_all!(
         LoadedTracks: Clone,
         Debug,
@@ -384,6 +493,7 @@ mod tests {
     assert_impl_all!(
         PlaylistInfo: Clone,
         Debug,
+        Default,
         Deserialize<'static>,
         Eq,
         PartialEq,
@@ -497,4 +607,236 @@ mod tests {
         Serialize,
         Sync
     );
+
+    fn track(identifier: &str, title: &str) -> (Value, Track) {
+        let json = json!({
+            "info": {
+                "author": "Rick Astley",
+                "identifier": identifier,
+                "isSeekable": true,
+                "isStream": false,
+                "length": 212_000,
+                "position": 0,
+                "title": title,
+                "uri": format!("https://www.youtube.com/watch?v={}", identifier),
+            },
+            "track": "track",
+        });
+        let track = Track {
+            info: TrackInfo {
+                author: Some("Rick Astley".to_owned()),
+                identifier: identifier.to_owned(),
+                is_seekable: true,
+                is_stream: false,
+                length: 212_000,
+                position: 0,
+                title: Some(title.to_owned()),
+                uri: format!("https://www.youtube.com/watch?v={}", identifier),
+            },
+            track: "track".to_owned(),
+        };
+
+        (json, track)
+    }
+
+    fn assert_loaded_tracks(json: Value, value: LoadedTracks) {
+        assert_eq!(json, serde_json::to_value(&value).unwrap());
+        assert_eq!(value, serde_json::from_value::<LoadedTracks>(json).unwrap());
+    }
+
+    #[test]
+    fn test_track_loaded() {
+        let (track_json, track) = track("dQw4w9WgXcQ", "Never Gonna Give You Up");
+
+        assert_loaded_tracks(
+            json!({
+                "loadType": "TRACK_LOADED",
+                "playlistInfo": {},
+                "tracks": [track_json],
+            }),
+            LoadedTracks {
+                exception: None,
+                load_type: LoadType::TrackLoaded,
+                playlist_info: PlaylistInfo::default(),
+                tracks: vec![track],
+            },
+        );
+    }
+
+    #[test]
+    fn test_playlist_loaded() {
+        let (first_json, first) = track("dQw4w9WgXcQ", "Never Gonna Give You Up");
+        let (second_json, second) = track("yPYZpwSpKmA", "Together Forever");
+
+        assert_loaded_tracks(
+            json!({
+                "loadType": "PLAYLIST_LOADED",
+                "playlistInfo": {
+                    "name": "Rick Astley",
+                    "selectedTrack": 1,
+                },
+                "tracks": [first_json, second_json],
+            }),
+            LoadedTracks {
+                exception: None,
+                load_type: LoadType::PlaylistLoaded,
+                playlist_info: PlaylistInfo {
+                    name: Some("Rick Astley".to_owned()),
+                    selected_track: Some(1),
+                },
+                tracks: vec![first, second],
+            },
+        );
+    }
+
+    #[test]
+    fn test_playlist_loaded_no_selected_track() {
+        let json = json!({
+            "loadType": "PLAYLIST_LOADED",
+            "playlistInfo": {
+                "name": "Rick Astley",
+                "selectedTrack": -1,
+            },
+            "tracks": [],
+        });
+        let value = LoadedTracks {
+            exception: None,
+            load_type: LoadType::PlaylistLoaded,
+            playlist_info: PlaylistInfo {
+                name: Some("Rick Astley".to_owned()),
+                selected_track: None,
+            },
+            tracks: Vec::new(),
+        };
+
+        assert_eq!(value, serde_json::from_value::<LoadedTracks>(json).unwrap());
+
+        // Playlists without a selected track are serialized without it.
+        assert_eq!(
+            json!({
+                "loadType": "PLAYLIST_LOADED",
+                "playlistInfo": {
+                    "name": "Rick Astley",
+                },
+                "tracks": [],
+            }),
+            serde_json::to_value(&value).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_search_result() {
+        let (first_json, first) = track("dQw4w9WgXcQ", "Never Gonna Give You Up");
+        let (second_json, second) = track("yPYZpwSpKmA", "Together Forever");
+
+        assert_loaded_tracks(
+            json!({
+                "loadType": "SEARCH_RESULT",
+                "playlistInfo": {},
+                "tracks": [first_json, second_json],
+            }),
+            LoadedTracks {
+                exception: None,
+                load_type: LoadType::SearchResult,
+                playlist_info: PlaylistInfo::default(),
+                tracks: vec![first, second],
+            },
+        );
+    }
+
+    #[test]
+    fn test_no_matches() {
+        assert_loaded_tracks(
+            json!({
+                "loadType": "NO_MATCHES",
+                "playlistInfo": {},
+                "tracks": [],
+            }),
+            LoadedTracks {
+                exception: None,
+                load_type: LoadType::NoMatches,
+                playlist_info: PlaylistInfo::default(),
+                tracks: Vec::new(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_failed() {
+        assert_loaded_tracks(
+            json!({
+                "exception": {
+                    "message": "The uploader has not made this video available in your country.",
+                    "severity": "COMMON",
+                },
+                "loadType": "LOAD_FAILED",
+                "playlistInfo": {},
+                "tracks": [],
+            }),
+            LoadedTracks {
+                exception: Some(LoadException {
+                    message: Some(
+                        "The uploader has not made this video available in your country."
+                            .to_owned(),
+                    ),
+                    severity: TrackExceptionSeverity::Common,
+                }),
+                load_type: LoadType::LoadFailed,
+                playlist_info: PlaylistInfo::default(),
+                tracks: Vec::new(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_missing_playlist_info() {
+        let value = serde_json::from_value::<LoadedTracks>(json!({
+            "loadType": "NO_MATCHES",
+            "tracks": [],
+        }))
+        .unwrap();
+
+        assert_eq!(PlaylistInfo::default(), value.playlist_info);
+    }
+
+    #[test]
+    fn test_load_track() {
+        let req = super::load_track(address(), "https://youtu.be/dQw4w9WgXcQ", "password").unwrap();
+
+        assert_eq!(Method::GET, req.method());
+        assert_eq!(
+            "http://127.0.0.1:2333/loadtracks?identifier=https%3A%2F%2Fyoutu%2Ebe%2FdQw4w9WgXcQ",
+            req.uri()
+        );
+        assert_eq!("password", req.headers()["Authorization"]);
+    }
+
+    #[test]
+    fn test_search() {
+        let req = super::search_youtube(address(), "never gonna", "password").unwrap();
+        assert_eq!(
+            "http://127.0.0.1:2333/loadtracks?identifier=ytsearch%3Anever%20gonna",
+            req.uri()
+        );
+
+        let req = super::search_soundcloud(address(), "never gonna", "password").unwrap();
+        assert_eq!(
+            "http://127.0.0.1:2333/loadtracks?identifier=scsearch%3Anever%20gonna",
+            req.uri()
+        );
+    }
+
+    #[test]
+    fn test_decode_tracks() {
+        let tracks = vec!["first".to_owned(), "second".to_owned()];
+        let req = super::decode_tracks(address(), tracks, "password").unwrap();
+
+        assert_eq!(Method::POST, req.method());
+        assert_eq!("http://127.0.0.1:2333/decodetracks", req.uri());
+        assert_eq!("application/json", req.headers()[CONTENT_TYPE]);
+        assert_eq!(
+            json!(["first", "second"]),
+            serde_json::from_slice::<Value>(req.body()).unwrap()
+        );
+    }
 }
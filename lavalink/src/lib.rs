@@ -6,8 +6,8 @@
 //! ecosystem.
 //!
 //! It includes support for managing multiple nodes, a player manager for
-//! conveniently using players to send events and retrieve information for each
-//! guild, and an HTTP module for creating requests using the [`http`] crate and
+//! conveniently using players to send events, queue tracks, and retrieve
+//! information for each guild, and an HTTP module for creating requests using the [`http`] crate and
 //! providing models to deserialize their responses. It will automatically
 //! handle sending voice channel updates to Lavalink by processing events via
 //! the [client's `process` method][`Lavalink::process`], which you must call
@@ -125,6 +125,8 @@ pub mod client;
 pub mod model;
 pub mod node;
 pub mod player;
+pub mod queue;
+pub mod track;
 
 #[cfg(feature = "http")]
 pub mod http;
@@ -8,12 +8,16 @@ use serde::{Deserialize, Serialize};
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub enum Opcode {
+    /// Configure the key and timeout for resuming the session.
+    ConfigureResuming,
     /// Destroy a player from a node.
     Destroy,
     /// Equalize a player.
     Equalizer,
     /// Meta information about a track starting or ending.
     Event,
+    /// Apply filters to the audio of a player.
+    Filters,
     /// Pause a player.
     Pause,
     /// Play a track.
@@ -37,6 +41,11 @@ pub mod outgoing {
 
     use super::Opcode;
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::{
+        error::Error,
+        fmt::{Display, Formatter, Result as FmtResult},
+    };
     use twilight_model::{gateway::payload::VoiceServerUpdate, id::GuildId};
 
     /// An outgoing event to send to Lavalink.
@@ -44,10 +53,14 @@ pub mod outgoing {
     #[non_exhaustive]
     #[serde(untagged)]
     pub enum OutgoingEvent {
+        /// Configure the key and timeout for resuming the session.
+        ConfigureResuming(ConfigureResuming),
         /// Destroy a player for a guild.
         Destroy(Destroy),
         /// Equalize a player.
         Equalizer(Equalizer),
+        /// Apply filters to the audio of a player.
+        Filters(Filters),
         /// Pause or unpause a player.
         Pause(Pause),
         /// Play a track.
@@ -60,6 +73,17 @@ pub mod outgoing {
         VoiceUpdate(VoiceUpdate),
         /// Set the volume of a player.
         Volume(Volume),
+        /// A payload that the crate doesn't model, such as an op of a plugin.
+        ///
+        /// This is last so that modeled payloads keep deserializing into their
+        /// typed variants.
+        Raw(Value),
+    }
+
+    impl From<ConfigureResuming> for OutgoingEvent {
+        fn from(event: ConfigureResuming) -> OutgoingEvent {
+            Self::ConfigureResuming(event)
+        }
     }
 
     impl From<Destroy> for OutgoingEvent {
@@ -74,6 +98,12 @@ pub mod outgoing {
         }
     }
 
+    impl From<Filters> for OutgoingEvent {
+        fn from(event: Filters) -> OutgoingEvent {
+            Self::Filters(event)
+        }
+    }
+
     impl From<Pause> for OutgoingEvent {
         fn from(event: Pause) -> OutgoingEvent {
             Self::Pause(event)
@@ -110,6 +140,34 @@ pub mod outgoing {
         }
     }
 
+    /// Configure the key and timeout for resuming the session.
+    ///
+    /// Once configured, the session can be resumed by connecting with the key
+    /// within the timeout after a disconnect, keeping the session's players.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct ConfigureResuming {
+        /// The key to resume the session with.
+        pub key: String,
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// The number of seconds after a disconnect that the session can be
+        /// resumed for.
+        pub timeout: u64,
+    }
+
+    impl ConfigureResuming {
+        /// Create a new configure resuming event.
+        pub fn new(key: impl Into<String>, timeout: u64) -> Self {
+            Self {
+                key: key.into(),
+                op: Opcode::ConfigureResuming,
+                timeout,
+            }
+        }
+    }
+
     /// Destroy a player from a node.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[non_exhaustive]
@@ -182,10 +240,49 @@ pub mod outgoing {
     }
 
     impl EqualizerBand {
+        /// The number of bands of the equalizer.
+        pub const BANDS: i64 = 15;
+
+        /// The lowest gain of a band, muting it.
+        pub const MIN_GAIN: f64 = -0.25;
+
+        /// The highest gain of a band, doubling it.
+        pub const MAX_GAIN: f64 = 1.0;
+
         /// Create a new equalizer band.
+        ///
+        /// The band and gain aren't validated. Use [`try_new`] to ensure they
+        /// are in range.
+        ///
+        /// [`try_new`]: Self::try_new
         pub fn new(band: i64, gain: f64) -> Self {
             Self::from((band, gain))
         }
+
+        /// Create a new equalizer band, ensuring the band and gain are in
+        /// range.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`EqualizerBandError::BandOutOfRange`] if the band isn't
+        /// between 0 and 14.
+        ///
+        /// Returns [`EqualizerBandError::GainOutOfRange`] if the gain isn't
+        /// between [`MIN_GAIN`] and [`MAX_GAIN`].
+        ///
+        /// [`MAX_GAIN`]: Self::MAX_GAIN
+        /// [`MIN_GAIN`]: Self::MIN_GAIN
+        pub fn try_new(band: i64, gain: f64) -> Result<Self, EqualizerBandError> {
+            if !(0..Self::BANDS).contains(&band) {
+                return Err(EqualizerBandError::BandOutOfRange { band });
+            }
+
+            if !(Self::MIN_GAIN..=Self::MAX_GAIN).contains(&gain) {
+                return Err(EqualizerBandError::GainOutOfRange { gain });
+            }
+
+            Ok(Self::new(band, gain))
+        }
     }
 
     impl From<(i64, f64)> for EqualizerBand {
@@ -194,6 +291,437 @@ pub mod outgoing {
         }
     }
 
+    /// Creating an [`EqualizerBand`] failed.
+    #[derive(Clone, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum EqualizerBandError {
+        /// The band isn't one of the 15 bands of the equalizer.
+        BandOutOfRange {
+            /// The provided band.
+            band: i64,
+        },
+        /// The gain isn't between [`EqualizerBand::MIN_GAIN`] and
+        /// [`EqualizerBand::MAX_GAIN`].
+        GainOutOfRange {
+            /// The provided gain.
+            gain: f64,
+        },
+    }
+
+    impl Display for EqualizerBandError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            match self {
+                Self::BandOutOfRange { band } => write!(
+                    f,
+                    "the band is {}, but must be between 0 and {}",
+                    band,
+                    EqualizerBand::BANDS - 1
+                ),
+                Self::GainOutOfRange { gain } => write!(
+                    f,
+                    "the gain is {}, but must be between {} and {}",
+                    gain,
+                    EqualizerBand::MIN_GAIN,
+                    EqualizerBand::MAX_GAIN
+                ),
+            }
+        }
+    }
+
+    impl Error for EqualizerBandError {}
+
+    /// Apply filters to the audio of a player.
+    ///
+    /// Only the configured filters are sent, and each event replaces the
+    /// filters of the previous one. Use [`FiltersBuilder`] to create one.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Filters {
+        /// Mix of the left and right channels.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub channel_mix: Option<ChannelMix>,
+        /// Distortion of the audio.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub distortion: Option<Distortion>,
+        /// Bands of the equalizer.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub equalizer: Option<Vec<EqualizerBand>>,
+        /// The guild ID of the player.
+        pub guild_id: GuildId,
+        /// Removal of the vocals.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub karaoke: Option<Karaoke>,
+        /// Suppression of the higher frequencies.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub low_pass: Option<LowPass>,
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// Rotation of the audio around the listener.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub rotation: Option<Rotation>,
+        /// Change of the speed, pitch, and rate of the audio.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub timescale: Option<Timescale>,
+        /// Oscillation of the volume.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub tremolo: Option<Tremolo>,
+        /// Oscillation of the pitch.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub vibrato: Option<Vibrato>,
+        /// The volume, from 0 to 5, with 1 leaving the volume unchanged.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub volume: Option<f64>,
+    }
+
+    impl Filters {
+        /// Create a new filters event without any filters, removing the
+        /// filters of the player.
+        pub fn new(guild_id: GuildId) -> Self {
+            Self::from(guild_id)
+        }
+    }
+
+    impl From<GuildId> for Filters {
+        fn from(guild_id: GuildId) -> Self {
+            Self {
+                channel_mix: None,
+                distortion: None,
+                equalizer: None,
+                guild_id,
+                karaoke: None,
+                low_pass: None,
+                op: Opcode::Filters,
+                rotation: None,
+                timescale: None,
+                tremolo: None,
+                vibrato: None,
+                volume: None,
+            }
+        }
+    }
+
+    /// Create a [`Filters`] event with the filters to apply.
+    ///
+    /// # Examples
+    ///
+    /// Slow a player's audio down, keeping its pitch:
+    ///
+    /// ```
+    /// use twilight_lavalink::model::{FiltersBuilder, Timescale};
+    /// use twilight_model::id::GuildId;
+    ///
+    /// let mut timescale = Timescale::default();
+    /// timescale.speed = 0.8;
+    ///
+    /// let filters = FiltersBuilder::new(GuildId(1)).timescale(timescale).build();
+    /// ```
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct FiltersBuilder(Filters);
+
+    impl FiltersBuilder {
+        /// Create a new builder of a filters event for a guild's player.
+        pub fn new(guild_id: GuildId) -> Self {
+            Self(Filters::new(guild_id))
+        }
+
+        /// Build the filters event.
+        pub fn build(self) -> Filters {
+            self.0
+        }
+
+        /// Set the mix of the left and right channels.
+        pub fn channel_mix(mut self, channel_mix: ChannelMix) -> Self {
+            self.0.channel_mix.replace(channel_mix);
+
+            self
+        }
+
+        /// Set the distortion of the audio.
+        pub fn distortion(mut self, distortion: Distortion) -> Self {
+            self.0.distortion.replace(distortion);
+
+            self
+        }
+
+        /// Set the bands of the equalizer.
+        pub fn equalizer(mut self, bands: Vec<EqualizerBand>) -> Self {
+            self.0.equalizer.replace(bands);
+
+            self
+        }
+
+        /// Set the removal of the vocals.
+        pub fn karaoke(mut self, karaoke: Karaoke) -> Self {
+            self.0.karaoke.replace(karaoke);
+
+            self
+        }
+
+        /// Set the suppression of the higher frequencies.
+        pub fn low_pass(mut self, low_pass: LowPass) -> Self {
+            self.0.low_pass.replace(low_pass);
+
+            self
+        }
+
+        /// Set the rotation of the audio around the listener.
+        pub fn rotation(mut self, rotation: Rotation) -> Self {
+            self.0.rotation.replace(rotation);
+
+            self
+        }
+
+        /// Set the change of the speed, pitch, and rate of the audio.
+        pub fn timescale(mut self, timescale: Timescale) -> Self {
+            self.0.timescale.replace(timescale);
+
+            self
+        }
+
+        /// Set the oscillation of the volume.
+        pub fn tremolo(mut self, tremolo: Tremolo) -> Self {
+            self.0.tremolo.replace(tremolo);
+
+            self
+        }
+
+        /// Set the oscillation of the pitch.
+        pub fn vibrato(mut self, vibrato: Vibrato) -> Self {
+            self.0.vibrato.replace(vibrato);
+
+            self
+        }
+
+        /// Set the volume, from 0 to 5, with 1 leaving the volume unchanged.
+        pub fn volume(mut self, volume: f64) -> Self {
+            self.0.volume.replace(volume);
+
+            self
+        }
+    }
+
+    /// Mix of the left and right channels of a [`Filters`] event.
+    ///
+    /// Each factor is between 0 and 1. The default leaves the channels
+    /// unchanged.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct ChannelMix {
+        /// Factor of the left channel in the left channel.
+        pub left_to_left: f64,
+        /// Factor of the left channel in the right channel.
+        pub left_to_right: f64,
+        /// Factor of the right channel in the left channel.
+        pub right_to_left: f64,
+        /// Factor of the right channel in the right channel.
+        pub right_to_right: f64,
+    }
+
+    impl Default for ChannelMix {
+        fn default() -> Self {
+            Self {
+                left_to_left: 1.0,
+                left_to_right: 0.0,
+                right_to_left: 0.0,
+                right_to_right: 1.0,
+            }
+        }
+    }
+
+    /// Distortion of the audio of a [`Filters`] event.
+    ///
+    /// Samples are distorted by the sine, cosine, and tangent of their
+    /// offset and scaled values. The default leaves the audio unchanged.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Distortion {
+        /// Offset of the cosine.
+        pub cos_offset: f64,
+        /// Scale of the cosine.
+        pub cos_scale: f64,
+        /// Offset of the samples.
+        pub offset: f64,
+        /// Scale of the samples.
+        pub scale: f64,
+        /// Offset of the sine.
+        pub sin_offset: f64,
+        /// Scale of the sine.
+        pub sin_scale: f64,
+        /// Offset of the tangent.
+        pub tan_offset: f64,
+        /// Scale of the tangent.
+        pub tan_scale: f64,
+    }
+
+    impl Default for Distortion {
+        fn default() -> Self {
+            Self {
+                cos_offset: 0.0,
+                cos_scale: 1.0,
+                offset: 0.0,
+                scale: 1.0,
+                sin_offset: 0.0,
+                sin_scale: 1.0,
+                tan_offset: 0.0,
+                tan_scale: 1.0,
+            }
+        }
+    }
+
+    /// Removal of the vocals of a [`Filters`] event.
+    ///
+    /// Vocals are removed from a band of frequencies, by default the band
+    /// of most voices.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Karaoke {
+        /// The center of the band of frequencies, in Hz.
+        pub filter_band: f64,
+        /// The width of the band of frequencies, in Hz.
+        pub filter_width: f64,
+        /// The level of the removal, from 0 to 1.
+        pub level: f64,
+        /// The level of the removal of the mono signal, from 0 to 1.
+        pub mono_level: f64,
+    }
+
+    impl Default for Karaoke {
+        fn default() -> Self {
+            Self {
+                filter_band: 220.0,
+                filter_width: 100.0,
+                level: 1.0,
+                mono_level: 1.0,
+            }
+        }
+    }
+
+    /// Suppression of the higher frequencies of a [`Filters`] event.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct LowPass {
+        /// The smoothing of the audio, higher values suppressing more
+        /// frequencies. Values of 1 or less leave the audio unchanged.
+        pub smoothing: f64,
+    }
+
+    impl LowPass {
+        /// Create a new low pass filter.
+        pub fn new(smoothing: f64) -> Self {
+            Self { smoothing }
+        }
+    }
+
+    impl Default for LowPass {
+        fn default() -> Self {
+            Self::new(20.0)
+        }
+    }
+
+    /// Rotation of the audio around the listener of a [`Filters`] event.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    pub struct Rotation {
+        /// The frequency of the rotation, in Hz.
+        #[serde(rename = "rotationHz")]
+        pub rotation_hz: f64,
+    }
+
+    impl Rotation {
+        /// Create a new rotation filter.
+        pub fn new(rotation_hz: f64) -> Self {
+            Self { rotation_hz }
+        }
+    }
+
+    impl Default for Rotation {
+        fn default() -> Self {
+            Self::new(0.0)
+        }
+    }
+
+    /// Change of the speed, pitch, and rate of the audio of a [`Filters`]
+    /// event.
+    ///
+    /// Each value is a factor greater than 0, 1 leaving the audio unchanged.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Timescale {
+        /// Factor of the pitch.
+        pub pitch: f64,
+        /// Factor of the rate, changing both the speed and the pitch.
+        pub rate: f64,
+        /// Factor of the speed, keeping the pitch.
+        pub speed: f64,
+    }
+
+    impl Timescale {
+        /// Create a new timescale filter.
+        pub fn new(speed: f64, pitch: f64, rate: f64) -> Self {
+            Self { pitch, rate, speed }
+        }
+    }
+
+    impl Default for Timescale {
+        fn default() -> Self {
+            Self::new(1.0, 1.0, 1.0)
+        }
+    }
+
+    /// Oscillation of the volume of a [`Filters`] event.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Tremolo {
+        /// The depth of the oscillation, from 0 to 1.
+        pub depth: f64,
+        /// The frequency of the oscillation, in Hz.
+        pub frequency: f64,
+    }
+
+    impl Tremolo {
+        /// Create a new tremolo filter.
+        pub fn new(frequency: f64, depth: f64) -> Self {
+            Self { depth, frequency }
+        }
+    }
+
+    impl Default for Tremolo {
+        fn default() -> Self {
+            Self::new(2.0, 0.5)
+        }
+    }
+
+    /// Oscillation of the pitch of a [`Filters`] event.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct Vibrato {
+        /// The depth of the oscillation, from 0 to 1.
+        pub depth: f64,
+        /// The frequency of the oscillation, in Hz, up to 14.
+        pub frequency: f64,
+    }
+
+    impl Vibrato {
+        /// Create a new vibrato filter.
+        pub fn new(frequency: f64, depth: f64) -> Self {
+            Self { depth, frequency }
+        }
+    }
+
+    impl Default for Vibrato {
+        fn default() -> Self {
+            Self::new(2.0, 0.5)
+        }
+    }
+
     /// Pause or unpause a player.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[non_exhaustive]
@@ -463,21 +991,90 @@ pub mod incoming {
 
     use super::Opcode;
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::net::SocketAddr;
     use twilight_model::id::GuildId;
 
     /// An incoming event from a Lavalink node.
+    ///
+    /// The [`NodeDisconnected`] and [`NodeReconnected`] events aren't sent by
+    /// nodes, but by the node's connection to notify of its lifecycle.
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     #[non_exhaustive]
     #[serde(untagged)]
     pub enum IncomingEvent {
+        /// The connection to a node closed, and is being re-established.
+        #[serde(skip_deserializing)]
+        NodeDisconnected(NodeDisconnected),
+        /// The connection to a node was re-established.
+        #[serde(skip_deserializing)]
+        NodeReconnected(NodeReconnected),
         /// An update about the information of a player.
         PlayerUpdate(PlayerUpdate),
         /// New statistics about a node and its host.
         Stats(Stats),
         /// A track ended.
         TrackEnd(TrackEnd),
+        /// A track threw an exception while playing.
+        TrackException(TrackException),
+        /// A track got stuck while playing.
+        TrackStuck(TrackStuck),
+        /// The voice websocket connection of a player to Discord closed.
+        WebSocketClosed(WebSocketClosed),
         /// A track started.
+        ///
+        /// This is after the other track events as they have the same fields
+        /// and more.
         TrackStart(TrackStart),
+        /// An event with an op that the crate doesn't know, such as one sent by
+        /// a plugin of the node.
+        ///
+        /// Events with a known op whose payload doesn't match its model, such
+        /// as track events of unknown types, are also unknown.
+        Unknown {
+            /// The op of the event.
+            op: String,
+            /// The other fields of the event.
+            #[serde(flatten)]
+            data: Value,
+        },
+    }
+
+    impl IncomingEvent {
+        /// Return the guild ID of the player that the event is about.
+        ///
+        /// Returns `None` for [`Stats`] and the lifecycle events of the node,
+        /// which are about the whole node, and for unknown events without a
+        /// `guildId` field.
+        pub fn guild_id(&self) -> Option<GuildId> {
+            match self {
+                Self::NodeDisconnected(_) | Self::NodeReconnected(_) => None,
+                Self::PlayerUpdate(event) => Some(event.guild_id),
+                Self::Stats(_) => None,
+                Self::TrackEnd(event) => Some(event.guild_id),
+                Self::TrackException(event) => Some(event.guild_id),
+                Self::TrackStuck(event) => Some(event.guild_id),
+                Self::WebSocketClosed(event) => Some(event.guild_id),
+                Self::TrackStart(event) => Some(event.guild_id),
+                Self::Unknown { data, .. } => data
+                    .get("guildId")
+                    .and_then(Value::as_str)
+                    .and_then(|id| id.parse().ok())
+                    .map(GuildId),
+            }
+        }
+    }
+
+    impl From<NodeDisconnected> for IncomingEvent {
+        fn from(event: NodeDisconnected) -> IncomingEvent {
+            Self::NodeDisconnected(event)
+        }
+    }
+
+    impl From<NodeReconnected> for IncomingEvent {
+        fn from(event: NodeReconnected) -> IncomingEvent {
+            Self::NodeReconnected(event)
+        }
     }
 
     impl From<PlayerUpdate> for IncomingEvent {
@@ -492,6 +1089,60 @@ pub mod incoming {
         }
     }
 
+    impl From<TrackEnd> for IncomingEvent {
+        fn from(event: TrackEnd) -> IncomingEvent {
+            Self::TrackEnd(event)
+        }
+    }
+
+    impl From<TrackException> for IncomingEvent {
+        fn from(event: TrackException) -> IncomingEvent {
+            Self::TrackException(event)
+        }
+    }
+
+    impl From<TrackStart> for IncomingEvent {
+        fn from(event: TrackStart) -> IncomingEvent {
+            Self::TrackStart(event)
+        }
+    }
+
+    impl From<TrackStuck> for IncomingEvent {
+        fn from(event: TrackStuck) -> IncomingEvent {
+            Self::TrackStuck(event)
+        }
+    }
+
+    impl From<WebSocketClosed> for IncomingEvent {
+        fn from(event: WebSocketClosed) -> IncomingEvent {
+            Self::WebSocketClosed(event)
+        }
+    }
+
+    /// The connection to a node closed, and is being re-established.
+    ///
+    /// Events sent to the node are sent once the connection is re-established.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    pub struct NodeDisconnected {
+        /// The address of the node.
+        pub address: SocketAddr,
+    }
+
+    /// The connection to a node was re-established.
+    ///
+    /// If the session wasn't resumed, the players of the node were restored:
+    /// their current track is played again from its last known position.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    pub struct NodeReconnected {
+        /// The address of the node.
+        pub address: SocketAddr,
+        /// Whether the new session resumed the previous session, keeping its
+        /// players playing.
+        pub resumed: bool,
+    }
+
     /// An update about the information of a player.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[non_exhaustive]
@@ -505,7 +1156,7 @@ pub mod incoming {
         pub state: PlayerUpdateState,
     }
 
-    /// New statistics about a node and its host.
+    /// The new state of a player.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[non_exhaustive]
     #[serde(rename_all = "camelCase")]
@@ -552,16 +1203,18 @@ pub mod incoming {
         pub system_load: f64,
     }
 
-    /// CPU information about a node and its host.
+    /// Statistics about the audio frames sent by a node, averaged over the
+    /// last minute.
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     #[non_exhaustive]
     #[serde(rename_all = "camelCase")]
     pub struct StatsFrames {
-        /// The number of CPU cores.
+        /// The number of frames sent to Discord.
         pub sent: u64,
-        /// The load of the Lavalink server.
+        /// The number of frames that were nulled.
         pub nulled: u64,
-        /// The load of the system as a whole.
+        /// The number of frames missing to play the audio without
+        /// interruptions.
         pub deficit: u64,
     }
 
@@ -587,9 +1240,46 @@ pub mod incoming {
         /// A track for a player ended.
         #[serde(rename = "TrackEndEvent")]
         End,
+        /// A track for a player threw an exception.
+        #[serde(rename = "TrackExceptionEvent")]
+        Exception,
         /// A track for a player started.
         #[serde(rename = "TrackStartEvent")]
         Start,
+        /// A track for a player got stuck.
+        #[serde(rename = "TrackStuckEvent")]
+        Stuck,
+        /// The voice websocket connection of a player closed.
+        #[serde(rename = "WebSocketClosedEvent")]
+        WebSocketClosed,
+    }
+
+    /// The reason that a track ended.
+    #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TrackEndReason {
+        /// The player was cleaned up, such as after being inactive.
+        Cleanup,
+        /// The track played until its end.
+        Finished,
+        /// The track failed to load.
+        LoadFailed,
+        /// The track was replaced by another track.
+        Replaced,
+        /// The player was stopped.
+        Stopped,
+    }
+
+    impl TrackEndReason {
+        /// Whether the next track of a queue should be started.
+        ///
+        /// This is the case when the track finished or failed to load, as
+        /// otherwise the player was stopped or is already playing another
+        /// track.
+        pub fn may_start_next(self) -> bool {
+            matches!(self, Self::Finished | Self::LoadFailed)
+        }
     }
 
     /// A track ended.
@@ -605,18 +1295,18 @@ pub mod incoming {
         /// The opcode of the event.
         pub op: Opcode,
         /// The reason that the track ended.
-        ///
-        /// For example, this may be `"FINISHED"`.
-        pub reason: String,
+        pub reason: TrackEndReason,
         /// The base64 track that was affected.
         pub track: String,
     }
 
-    /// A track started.
+    /// A track threw an exception while playing.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[non_exhaustive]
     #[serde(rename_all = "camelCase")]
-    pub struct TrackStart {
+    pub struct TrackException {
+        /// The exception that the track threw.
+        pub exception: TrackExceptionDetails,
         /// The guild ID of the player.
         pub guild_id: GuildId,
         /// The type of track event.
@@ -627,38 +1317,162 @@ pub mod incoming {
         /// The base64 track that was affected.
         pub track: String,
     }
-}
 
-pub use self::{
-    incoming::{
-        IncomingEvent, PlayerUpdate, PlayerUpdateState, Stats, StatsCpu, StatsFrames, StatsMemory,
-        TrackEnd, TrackEventType, TrackStart,
-    },
-    outgoing::{
-        Destroy, Equalizer, EqualizerBand, OutgoingEvent, Pause, Play, Seek, SlimVoiceServerUpdate,
-        Stop, VoiceUpdate, Volume,
-    },
-};
+    /// An exception that a track threw.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct TrackExceptionDetails {
+        /// The cause of the exception.
+        pub cause: String,
+        /// The message of the exception, if any.
+        pub message: Option<String>,
+        /// How severe the exception is.
+        pub severity: TrackExceptionSeverity,
+    }
+
+    /// How severe an exception that a track threw is.
+    #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TrackExceptionSeverity {
+        /// The cause is known and expected, such as the track being
+        /// unavailable.
+        Common,
+        /// The cause might not be exactly known, but is possibly caused by
+        /// outside factors, such as the source of the track changing.
+        Suspicious,
+        /// The cause is a bug of the node, or is unknown.
+        Fault,
+    }
+
+    /// A track started.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct TrackStart {
+        /// The guild ID of the player.
+        pub guild_id: GuildId,
+        /// The type of track event.
+        #[serde(rename = "type")]
+        pub kind: TrackEventType,
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// The base64 track that was affected.
+        pub track: String,
+    }
+
+    /// A track got stuck while playing, not providing any audio.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct TrackStuck {
+        /// The guild ID of the player.
+        pub guild_id: GuildId,
+        /// The type of track event.
+        #[serde(rename = "type")]
+        pub kind: TrackEventType,
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// The number of milliseconds without audio after which the track was
+        /// considered stuck.
+        pub threshold_ms: u64,
+        /// The base64 track that was affected.
+        pub track: String,
+    }
+
+    /// The voice websocket connection of a player to Discord closed.
+    ///
+    /// The close code tells why the connection closed. For example, 4006
+    /// means that the voice session is no longer valid, and 4014 that the
+    /// bot was disconnected from the voice channel, such as by being moved
+    /// or kicked.
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    pub struct WebSocketClosed {
+        /// Whether Discord closed the connection, rather than the node.
+        pub by_remote: bool,
+        /// The close code of the connection.
+        pub code: u16,
+        /// The guild ID of the player.
+        pub guild_id: GuildId,
+        /// The type of event.
+        #[serde(rename = "type")]
+        pub kind: TrackEventType,
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// The reason of the close.
+        pub reason: String,
+    }
+}
+
+pub use self::{
+    incoming::{
+        IncomingEvent, NodeDisconnected, NodeReconnected, PlayerUpdate, PlayerUpdateState, Stats,
+        StatsCpu, StatsFrames, StatsMemory, TrackEnd, TrackEndReason, TrackEventType,
+        TrackException, TrackExceptionDetails, TrackExceptionSeverity, TrackStart, TrackStuck,
+        WebSocketClosed,
+    },
+    outgoing::{
+        ChannelMix, ConfigureResuming, Destroy, Distortion, Equalizer, EqualizerBand,
+        EqualizerBandError, Filters, FiltersBuilder, Karaoke, LowPass, OutgoingEvent, Pause, Play,
+        Rotation, Seek, SlimVoiceServerUpdate, Stop, Timescale, Tremolo, Vibrato, VoiceUpdate,
+        Volume,
+    },
+};
 
 #[cfg(test)]
 mod tests {
     use super::{
         incoming::{
-            IncomingEvent, PlayerUpdate, PlayerUpdateState, Stats, StatsCpu, StatsFrames,
-            StatsMemory, TrackEnd, TrackEventType, TrackStart,
+            IncomingEvent, NodeDisconnected, NodeReconnected, PlayerUpdate, PlayerUpdateState,
+            Stats, StatsCpu, StatsFrames, StatsMemory, TrackEnd, TrackEndReason, TrackEventType,
+            TrackException, TrackExceptionDetails, TrackExceptionSeverity, TrackStart, TrackStuck,
+            WebSocketClosed,
         },
         outgoing::{
-            Destroy, Equalizer, EqualizerBand, OutgoingEvent, Pause, Play, Seek,
-            SlimVoiceServerUpdate, Stop, VoiceUpdate, Volume,
+            ChannelMix, ConfigureResuming, Destroy, Distortion, Equalizer, EqualizerBand,
+            EqualizerBandError, Filters, FiltersBuilder, Karaoke, LowPass, OutgoingEvent, Pause,
+            Play, Rotation, Seek, SlimVoiceServerUpdate, Stop, Timescale, Tremolo, Vibrato,
+            VoiceUpdate, Volume,
         },
         Opcode,
     };
     use serde::{Deserialize, Serialize};
+    use serde_json::json;
     use serde_test::Token;
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::fmt::Debug;
+    use std::{error::Error, fmt::Debug, hash::Hash};
     use twilight_model::id::GuildId;
 
+    assert_fields!(
+        ChannelMix: left_to_left,
+        left_to_right,
+        right_to_left,
+        right_to_right
+    );
+    assert_impl_all!(
+        ChannelMix: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(ConfigureResuming: key, op, timeout);
+    assert_impl_all!(
+        ConfigureResuming: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(Destroy: guild_id, op);
     assert_impl_all!(
         Destroy: Clone,
@@ -671,6 +1485,27 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(
+        Distortion: cos_offset,
+        cos_scale,
+        offset,
+        scale,
+        sin_offset,
+        sin_scale,
+        tan_offset,
+        tan_scale
+    );
+    assert_impl_all!(
+        Distortion: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_impl_all!(EqualizerBandError: Clone, Debug, Error, PartialEq, Send, Sync);
     assert_fields!(EqualizerBand: band, gain);
     assert_impl_all!(
         EqualizerBand: Clone,
@@ -693,12 +1528,44 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(
+        Filters: channel_mix,
+        distortion,
+        equalizer,
+        guild_id,
+        karaoke,
+        low_pass,
+        op,
+        rotation,
+        timescale,
+        tremolo,
+        vibrato,
+        volume
+    );
+    assert_impl_all!(
+        Filters: Clone,
+        Debug,
+        Deserialize<'static>,
+        From<GuildId>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_impl_all!(FiltersBuilder: Clone, Debug, PartialEq, Send, Sync);
     assert_impl_all!(
         IncomingEvent: Clone,
         Debug,
         Deserialize<'static>,
+        From<NodeDisconnected>,
+        From<NodeReconnected>,
         From<PlayerUpdate>,
         From<Stats>,
+        From<TrackEnd>,
+        From<TrackException>,
+        From<TrackStart>,
+        From<TrackStuck>,
+        From<WebSocketClosed>,
         PartialEq,
         Send,
         Serialize,
@@ -710,6 +1577,7 @@ mod tests {
         Deserialize<'static>,
         From<Destroy>,
         From<Equalizer>,
+        From<Filters>,
         From<Pause>,
         From<Play>,
         From<Seek>,
@@ -721,6 +1589,28 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(Karaoke: filter_band, filter_width, level, mono_level);
+    assert_impl_all!(
+        Karaoke: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(LowPass: smoothing);
+    assert_impl_all!(
+        LowPass: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(Pause: guild_id, op, pause);
     assert_impl_all!(
         Pause: Clone,
@@ -733,6 +1623,28 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(NodeDisconnected: address);
+    assert_impl_all!(
+        NodeDisconnected: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(NodeReconnected: address, resumed);
+    assert_impl_all!(
+        NodeReconnected: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(PlayerUpdateState: position, time);
     assert_impl_all!(
         PlayerUpdateState: Clone,
@@ -777,6 +1689,17 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(Rotation: rotation_hz);
+    assert_impl_all!(
+        Rotation: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(Seek: guild_id, op, position);
     assert_impl_all!(
         Seek: Clone,
@@ -860,6 +1783,17 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(Timescale: pitch, rate, speed);
+    assert_impl_all!(
+        Timescale: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(TrackEnd: guild_id, kind, op, reason, track);
     assert_impl_all!(
         TrackEnd: Clone,
@@ -870,6 +1804,52 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_impl_all!(
+        TrackEndReason: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(TrackException: exception, guild_id, kind, op, track);
+    assert_impl_all!(
+        TrackException: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(TrackExceptionDetails: cause, message, severity);
+    assert_impl_all!(
+        TrackExceptionDetails: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_impl_all!(
+        TrackExceptionSeverity: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_impl_all!(
         TrackEventType: Clone,
         Copy,
@@ -890,6 +1870,57 @@ mod tests {
         Serialize,
         Sync,
     );
+    assert_fields!(TrackStuck: guild_id, kind, op, threshold_ms, track);
+    assert_impl_all!(
+        TrackStuck: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(Tremolo: depth, frequency);
+    assert_impl_all!(
+        Tremolo: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(Vibrato: depth, frequency);
+    assert_impl_all!(
+        Vibrato: Clone,
+        Debug,
+        Default,
+        Deserialize<'static>,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
+    assert_fields!(
+        WebSocketClosed: by_remote,
+        code,
+        guild_id,
+        kind,
+        op,
+        reason
+    );
+    assert_impl_all!(
+        WebSocketClosed: Clone,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync,
+    );
     assert_fields!(VoiceUpdate: event, guild_id, op, session_id);
     assert_impl_all!(
         VoiceUpdate: Clone,
@@ -914,6 +1945,414 @@ mod tests {
         Sync,
     );
 
+    #[test]
+    fn test_configure_resuming() {
+        let value = ConfigureResuming::new("key", 60);
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "ConfigureResuming",
+                    len: 3,
+                },
+                Token::Str("key"),
+                Token::Str("key"),
+                Token::Str("op"),
+                Token::UnitVariant {
+                    name: "Opcode",
+                    variant: "configureResuming",
+                },
+                Token::Str("timeout"),
+                Token::U64(60),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_equalizer_band_validation() {
+        assert_eq!(
+            Ok(EqualizerBand::new(14, -0.25)),
+            EqualizerBand::try_new(14, -0.25)
+        );
+        assert_eq!(
+            Ok(EqualizerBand::new(0, 1.0)),
+            EqualizerBand::try_new(0, 1.0)
+        );
+        assert_eq!(
+            Err(EqualizerBandError::BandOutOfRange { band: 15 }),
+            EqualizerBand::try_new(15, 0.0)
+        );
+        assert_eq!(
+            Err(EqualizerBandError::BandOutOfRange { band: -1 }),
+            EqualizerBand::try_new(-1, 0.0)
+        );
+        assert_eq!(
+            Err(EqualizerBandError::GainOutOfRange { gain: -0.26 }),
+            EqualizerBand::try_new(0, -0.26)
+        );
+        assert_eq!(
+            Err(EqualizerBandError::GainOutOfRange { gain: 1.01 }),
+            EqualizerBand::try_new(0, 1.01)
+        );
+        assert!(matches!(
+            EqualizerBand::try_new(0, f64::NAN),
+            Err(EqualizerBandError::GainOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_equalizer() {
+        let bands = vec![
+            EqualizerBand::try_new(0, 0.25).unwrap(),
+            EqualizerBand::try_new(1, -0.25).unwrap(),
+        ];
+        let value = Equalizer::new(GuildId(1), bands);
+
+        assert_eq!(
+            json!({
+                "bands": [
+                    { "band": 0, "gain": 0.25 },
+                    { "band": 1, "gain": -0.25 },
+                ],
+                "guildId": "1",
+                "op": "equalizer",
+            }),
+            serde_json::to_value(&value).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_filters_only_configured() {
+        let value = FiltersBuilder::new(GuildId(1))
+            .timescale(Timescale::new(0.8, 1.0, 1.0))
+            .volume(0.5)
+            .build();
+
+        assert_eq!(
+            json!({
+                "guildId": "1",
+                "op": "filters",
+                "timescale": {
+                    "pitch": 1.0,
+                    "rate": 1.0,
+                    "speed": 0.8,
+                },
+                "volume": 0.5,
+            }),
+            serde_json::to_value(&value).unwrap(),
+        );
+
+        assert_eq!(
+            json!({
+                "guildId": "1",
+                "op": "filters",
+            }),
+            serde_json::to_value(Filters::new(GuildId(1))).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_filters_all() {
+        let value = FiltersBuilder::new(GuildId(1))
+            .channel_mix(ChannelMix::default())
+            .distortion(Distortion::default())
+            .equalizer(vec![EqualizerBand::try_new(2, 0.5).unwrap()])
+            .karaoke(Karaoke::default())
+            .low_pass(LowPass::new(10.0))
+            .rotation(Rotation::new(0.2))
+            .timescale(Timescale::default())
+            .tremolo(Tremolo::new(4.0, 0.75))
+            .vibrato(Vibrato::default())
+            .volume(1.0)
+            .build();
+
+        let json = json!({
+            "channelMix": {
+                "leftToLeft": 1.0,
+                "leftToRight": 0.0,
+                "rightToLeft": 0.0,
+                "rightToRight": 1.0,
+            },
+            "distortion": {
+                "cosOffset": 0.0,
+                "cosScale": 1.0,
+                "offset": 0.0,
+                "scale": 1.0,
+                "sinOffset": 0.0,
+                "sinScale": 1.0,
+                "tanOffset": 0.0,
+                "tanScale": 1.0,
+            },
+            "equalizer": [{ "band": 2, "gain": 0.5 }],
+            "guildId": "1",
+            "karaoke": {
+                "filterBand": 220.0,
+                "filterWidth": 100.0,
+                "level": 1.0,
+                "monoLevel": 1.0,
+            },
+            "lowPass": { "smoothing": 10.0 },
+            "op": "filters",
+            "rotation": { "rotationHz": 0.2 },
+            "timescale": {
+                "pitch": 1.0,
+                "rate": 1.0,
+                "speed": 1.0,
+            },
+            "tremolo": {
+                "depth": 0.75,
+                "frequency": 4.0,
+            },
+            "vibrato": {
+                "depth": 0.5,
+                "frequency": 2.0,
+            },
+            "volume": 1.0,
+        });
+
+        assert_eq!(json, serde_json::to_value(&value).unwrap());
+        assert_eq!(value, serde_json::from_value::<Filters>(json).unwrap());
+    }
+
+    /// Base64 track of the captured events.
+    const TRACK: &str = "QAAAjQIAJVJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAADlJpY2tBc3RsZXlWRVZPAAAAAAADPCAAC2RRdzR3OVdnWGNRAAEAK2h0dHBzOi8vd3d3LnlvdXR1YmUuY29tL3dhdGNoP3Y9ZFF3NHc5V2dYY1EAB3lvdXR1YmUAAAAAAAAAAA==";
+
+    fn incoming(payload: &str) -> IncomingEvent {
+        serde_json::from_str(&payload.replace("TRACK", TRACK)).unwrap()
+    }
+
+    #[test]
+    fn test_incoming_player_update() {
+        let event = incoming(
+            r#"{"op":"playerUpdate","guildId":"1","state":{"time":1610000000000,"position":4500}}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::PlayerUpdate(PlayerUpdate {
+                guild_id: GuildId(1),
+                op: Opcode::PlayerUpdate,
+                state: PlayerUpdateState {
+                    position: 4500,
+                    time: 1_610_000_000_000,
+                },
+            }),
+            event
+        );
+    }
+
+    #[test]
+    fn test_incoming_stats() {
+        let event = incoming(
+            r#"{"playingPlayers":1,"op":"stats","memory":{"reservable":4294967296,"used":35249984,"free":27664576,"allocated":62914560},"frameStats":{"sent":3000,"deficit":0,"nulled":10},"players":2,"cpu":{"cores":4,"systemLoad":0.25,"lavalinkLoad":0.125},"uptime":18589}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::Stats(Stats {
+                cpu: StatsCpu {
+                    cores: 4,
+                    lavalink_load: 0.125,
+                    system_load: 0.25,
+                },
+                frames: Some(StatsFrames {
+                    deficit: 0,
+                    nulled: 10,
+                    sent: 3000,
+                }),
+                memory: StatsMemory {
+                    allocated: 62_914_560,
+                    free: 27_664_576,
+                    reservable: 4_294_967_296,
+                    used: 35_249_984,
+                },
+                op: Opcode::Stats,
+                players: 2,
+                playing_players: 1,
+                uptime: 18589,
+            }),
+            event
+        );
+    }
+
+    #[test]
+    fn test_incoming_track_start() {
+        let event =
+            incoming(r#"{"op":"event","type":"TrackStartEvent","guildId":"1","track":"TRACK"}"#);
+
+        assert_eq!(
+            IncomingEvent::TrackStart(TrackStart {
+                guild_id: GuildId(1),
+                kind: TrackEventType::Start,
+                op: Opcode::Event,
+                track: TRACK.to_owned(),
+            }),
+            event
+        );
+    }
+
+    #[test]
+    fn test_incoming_track_end() {
+        let reasons = [
+            ("CLEANUP", TrackEndReason::Cleanup),
+            ("FINISHED", TrackEndReason::Finished),
+            ("LOAD_FAILED", TrackEndReason::LoadFailed),
+            ("REPLACED", TrackEndReason::Replaced),
+            ("STOPPED", TrackEndReason::Stopped),
+        ];
+
+        for (name, reason) in reasons.iter() {
+            let event = incoming(&format!(
+                r#"{{"op":"event","reason":"{}","type":"TrackEndEvent","track":"TRACK","guildId":"1"}}"#,
+                name
+            ));
+
+            assert_eq!(
+                IncomingEvent::TrackEnd(TrackEnd {
+                    guild_id: GuildId(1),
+                    kind: TrackEventType::End,
+                    op: Opcode::Event,
+                    reason: *reason,
+                    track: TRACK.to_owned(),
+                }),
+                event
+            );
+        }
+
+        assert!(TrackEndReason::Finished.may_start_next());
+        assert!(TrackEndReason::LoadFailed.may_start_next());
+        assert!(!TrackEndReason::Replaced.may_start_next());
+    }
+
+    #[test]
+    fn test_incoming_track_exception() {
+        let event = incoming(
+            r#"{"op":"event","type":"TrackExceptionEvent","guildId":"1","track":"TRACK","error":"This video is unavailable","exception":{"severity":"COMMON","cause":"com.sedmelluq.discord.lavaplayer.tools.FriendlyException: This video is unavailable","message":"This video is unavailable"}}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::TrackException(TrackException {
+                exception: TrackExceptionDetails {
+                    cause: "com.sedmelluq.discord.lavaplayer.tools.FriendlyException: This video is unavailable".to_owned(),
+                    message: Some("This video is unavailable".to_owned()),
+                    severity: TrackExceptionSeverity::Common,
+                },
+                guild_id: GuildId(1),
+                kind: TrackEventType::Exception,
+                op: Opcode::Event,
+                track: TRACK.to_owned(),
+            }),
+            event
+        );
+
+        let event = incoming(
+            r#"{"op":"event","type":"TrackExceptionEvent","guildId":"1","track":"TRACK","exception":{"severity":"FAULT","cause":"java.lang.NullPointerException","message":null}}"#,
+        );
+
+        match event {
+            IncomingEvent::TrackException(event) => {
+                assert_eq!(None, event.exception.message);
+                assert_eq!(TrackExceptionSeverity::Fault, event.exception.severity);
+            }
+            other => panic!("not a track exception: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_incoming_track_stuck() {
+        let event = incoming(
+            r#"{"op":"event","type":"TrackStuckEvent","guildId":"1","track":"TRACK","thresholdMs":10000}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::TrackStuck(TrackStuck {
+                guild_id: GuildId(1),
+                kind: TrackEventType::Stuck,
+                op: Opcode::Event,
+                threshold_ms: 10_000,
+                track: TRACK.to_owned(),
+            }),
+            event
+        );
+    }
+
+    #[test]
+    fn test_incoming_websocket_closed() {
+        let event = incoming(
+            r#"{"op":"event","type":"WebSocketClosedEvent","guildId":"1","code":4006,"reason":"Your session is no longer valid.","byRemote":true}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::WebSocketClosed(WebSocketClosed {
+                by_remote: true,
+                code: 4006,
+                guild_id: GuildId(1),
+                kind: TrackEventType::WebSocketClosed,
+                op: Opcode::Event,
+                reason: "Your session is no longer valid.".to_owned(),
+            }),
+            event
+        );
+    }
+
+    #[test]
+    fn test_incoming_unknown() {
+        let event = incoming(
+            r#"{"op":"segmentsLoaded","guildId":"1","segments":[{"category":"sponsor","start":1000,"end":5000}]}"#,
+        );
+
+        assert_eq!(
+            IncomingEvent::Unknown {
+                op: "segmentsLoaded".to_owned(),
+                data: json!({
+                    "guildId": "1",
+                    "segments": [{"category": "sponsor", "start": 1000, "end": 5000}],
+                }),
+            },
+            event
+        );
+        assert_eq!(Some(GuildId(1)), event.guild_id());
+        assert_eq!(
+            json!({
+                "guildId": "1",
+                "op": "segmentsLoaded",
+                "segments": [{"category": "sponsor", "start": 1000, "end": 5000}],
+            }),
+            serde_json::to_value(&event).unwrap()
+        );
+
+        // Track events of unknown types are unknown too, rather than track
+        // starts.
+        let event =
+            incoming(r#"{"op":"event","type":"SegmentSkipped","guildId":"1","track":"TRACK"}"#);
+        assert!(matches!(event, IncomingEvent::Unknown { ref op, .. } if op == "event"));
+
+        let event = incoming(r#"{"op":"pluginInfo","version":"1.0.0"}"#);
+        assert_eq!(None, event.guild_id());
+    }
+
+    #[test]
+    fn test_outgoing_raw() {
+        let payload = json!({
+            "categories": ["sponsor"],
+            "guildId": "1",
+            "op": "segmentsToSkip",
+        });
+        let event = OutgoingEvent::Raw(payload.clone());
+        assert_eq!(payload, serde_json::to_value(&event).unwrap());
+
+        // Modeled payloads keep deserializing into their typed variants.
+        assert_eq!(
+            OutgoingEvent::Destroy(Destroy::new(GuildId(1))),
+            serde_json::from_value(json!({
+                "guildId": "1",
+                "op": "destroy",
+            }))
+            .unwrap()
+        );
+        assert_eq!(event, serde_json::from_value(payload).unwrap());
+    }
+
     #[test]
     fn stats_frames_not_provided() {
         let expected = Stats {
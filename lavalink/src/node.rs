@@ -18,7 +18,10 @@
 //! [`Lavalink`]: crate::client::Lavalink
 
 use crate::{
-    model::{IncomingEvent, Opcode, OutgoingEvent, PlayerUpdate, Stats, StatsCpu, StatsMemory},
+    model::{
+        ConfigureResuming, IncomingEvent, NodeDisconnected, NodeReconnected, OutgoingEvent,
+        PlayerUpdate, Stats, TrackEnd,
+    },
     player::PlayerManager,
 };
 use async_tungstenite::{
@@ -34,12 +37,15 @@ use futures_util::{
     stream::StreamExt,
 };
 use http::{header::HeaderName, Error as HttpError, Request, Response, StatusCode};
-use serde_json::Error as JsonError;
+use serde_json::{Error as JsonError, Value};
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
     net::SocketAddr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
     time::Duration,
 };
 use tokio::time as tokio_time;
@@ -55,7 +61,8 @@ pub enum NodeError {
         /// The source of the error from the `http` crate.
         source: HttpError,
     },
-    /// Connecting to the Lavalink server failed after several backoff attempts.
+    /// Connecting to the Lavalink server failed after the number of attempts
+    /// of the node's [`Backoff`].
     Connecting {
         /// The source of the error from the `tungstenite` crate.
         source: TungsteniteError,
@@ -114,6 +121,9 @@ pub struct NodeConfig {
     pub address: SocketAddr,
     /// The password to use when authenticating.
     pub authorization: String,
+    /// How connecting to the node is retried, including when reconnecting
+    /// after the connection closed.
+    pub backoff: Backoff,
     /// The details for resuming a Lavalink session, if any.
     ///
     /// Set this to `None` to disable resume capability.
@@ -124,10 +134,59 @@ pub struct NodeConfig {
     pub user_id: UserId,
 }
 
+/// Configuration for retrying to connect to a node.
+///
+/// The delay between attempts starts at the initial delay and doubles after
+/// each failed attempt, up to the maximum delay.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Backoff {
+    /// The number of attempts to connect before giving up, or `None` to retry
+    /// forever.
+    ///
+    /// The default is 8.
+    pub attempts: Option<u32>,
+    /// The delay before the second attempt.
+    ///
+    /// The default is 1 second.
+    pub initial: Duration,
+    /// The longest delay between two attempts.
+    ///
+    /// The default is 64 seconds.
+    pub max: Duration,
+}
+
+impl Backoff {
+    /// Configure retrying to connect, providing the initial and maximum delays
+    /// between attempts and the number of attempts, if limited.
+    pub fn new(initial: Duration, max: Duration, attempts: impl Into<Option<u32>>) -> Self {
+        Self {
+            attempts: attempts.into(),
+            initial,
+            max,
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1), Duration::from_secs(64), 8)
+    }
+}
+
 /// Configuration for a session which can be resumed.
+///
+/// Resuming a session keeps its players playing on the Lavalink server across
+/// disconnects, including restarts of the bot's process as long as they're
+/// shorter than the timeout.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct Resume {
+    /// The key to resume the session with.
+    ///
+    /// Keys must be unique to the bot across the clients of the Lavalink
+    /// server. Defaults to the address of the node if `None`.
+    pub key: Option<String>,
     /// The number of seconds that the Lavalink server will allow the session to
     /// be resumed for after a disconnect.
     ///
@@ -139,13 +198,31 @@ impl Resume {
     /// Configure resume capability, providing the number of seconds that the
     /// Lavalink server should queue events for when the connection is resumed.
     pub fn new(seconds: u64) -> Self {
-        Self { timeout: seconds }
+        Self {
+            key: None,
+            timeout: seconds,
+        }
+    }
+
+    /// Configure resume capability with a key to resume the session with,
+    /// providing the number of seconds that the Lavalink server should queue
+    /// events for when the connection is resumed.
+    pub fn with_key(key: impl Into<String>, seconds: u64) -> Self {
+        Self {
+            key: Some(key.into()),
+            timeout: seconds,
+        }
+    }
+
+    /// Key to resume the session with the node at an address with.
+    fn key(&self, address: SocketAddr) -> String {
+        self.key.clone().unwrap_or_else(|| address.to_string())
     }
 }
 
 impl Default for Resume {
     fn default() -> Self {
-        Self { timeout: 60 }
+        Self::new(60)
     }
 }
 
@@ -153,10 +230,14 @@ impl NodeConfig {
     /// Create a new configuration for connecting to a node via
     /// [`Node::connect`].
     ///
+    /// The node uses the default [`Backoff`], which can be replaced by
+    /// setting the [`backoff`] field.
+    ///
     /// If adding a node through the [`Lavalink`] client then you don't need to
     /// do this yourself.
     ///
     /// [`Lavalink`]: crate::client::Lavalink
+    /// [`backoff`]: Self::backoff
     pub fn new(
         user_id: UserId,
         shard_count: u64,
@@ -183,6 +264,7 @@ impl NodeConfig {
         Self {
             address,
             authorization,
+            backoff: Backoff::default(),
             resume,
             shard_count,
             user_id,
@@ -195,7 +277,10 @@ struct NodeRef {
     config: NodeConfig,
     lavalink_tx: UnboundedSender<OutgoingEvent>,
     players: PlayerManager,
-    stats: BiLock<Stats>,
+    /// Whether the current session resumed a previous session.
+    resumed: AtomicBool,
+    /// Most recent stats sent by the node, if any.
+    stats: BiLock<Option<Stats>>,
 }
 
 /// A connection to a single Lavalink server. It receives events and forwards
@@ -221,40 +306,37 @@ impl Node {
         config: NodeConfig,
         players: PlayerManager,
     ) -> Result<(Self, UnboundedReceiver<IncomingEvent>), NodeError> {
-        let (bilock_left, bilock_right) = BiLock::new(Stats {
-            cpu: StatsCpu {
-                cores: 0,
-                lavalink_load: 0f64,
-                system_load: 0f64,
-            },
-            frames: None,
-            memory: StatsMemory {
-                allocated: 0,
-                free: 0,
-                used: 0,
-                reservable: 0,
-            },
-            players: 0,
-            playing_players: 0,
-            op: Opcode::Stats,
-            uptime: 0,
-        });
+        let (bilock_left, bilock_right) = BiLock::new(None);
+        let (to_node, from_lavalink) = mpsc::unbounded();
+        let (to_lavalink, from_node) = mpsc::unbounded();
+
+        let node = Self(Arc::new(NodeRef {
+            config: config.clone(),
+            lavalink_tx: to_lavalink,
+            players: players.clone(),
+            resumed: AtomicBool::new(false),
+            stats: bilock_left,
+        }));
+
         tracing::debug!("starting connection to {}", config.address);
-        let (conn_loop, lavalink_tx, lavalink_rx) =
-            Connection::connect(config.clone(), players.clone(), bilock_right).await?;
-        tracing::debug!("started connection to {}", config.address);
+        let conn_loop = Connection::connect(
+            config,
+            Arc::downgrade(&node.0),
+            from_node,
+            to_node,
+            players,
+            bilock_right,
+        )
+        .await?;
+        tracing::debug!("started connection to {}", node.config().address);
 
-        tokio::spawn(conn_loop.run());
+        tokio::spawn(async move {
+            if let Err(source) = conn_loop.run().await {
+                tracing::warn!("connection to node ended: {}", source);
+            }
+        });
 
-        Ok((
-            Self(Arc::new(NodeRef {
-                config,
-                lavalink_tx,
-                players,
-                stats: bilock_left,
-            })),
-            lavalink_rx,
-        ))
+        Ok((node, from_lavalink))
     }
 
     /// Retrieve an immutable reference to the node's configuration.
@@ -275,6 +357,35 @@ impl Node {
         self.sender().unbounded_send(event)
     }
 
+    /// Send a raw payload to the node, such as an op of a plugin that the
+    /// crate doesn't model.
+    ///
+    /// Raw payloads don't update player states, even if they're about a
+    /// player.
+    ///
+    /// # Examples
+    ///
+    /// Skip the sponsored segments of tracks with the SponsorBlock plugin:
+    ///
+    /// ```no_run
+    /// # use twilight_lavalink::{node::{Node, NodeConfig}, player::PlayerManager};
+    /// # use twilight_model::id::UserId;
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = NodeConfig::new(UserId(1), 1, ([127, 0, 0, 1], 2333), "", None);
+    /// # let (node, _) = Node::connect(config, PlayerManager::default()).await?;
+    /// use serde_json::json;
+    ///
+    /// node.send_raw(json!({
+    ///     "categories": ["sponsor", "selfpromo"],
+    ///     "guildId": "1",
+    ///     "op": "segmentsToSkip",
+    /// }))?;
+    /// # Ok(()) }
+    /// ```
+    pub fn send_raw(&self, payload: Value) -> Result<(), TrySendError<OutgoingEvent>> {
+        self.send(OutgoingEvent::Raw(payload))
+    }
+
     /// Retrieve a unique sender to send events to the Lavalink server.
     ///
     /// Note that sending player events through the node's sender won't update
@@ -283,8 +394,20 @@ impl Node {
         self.0.lavalink_tx.clone()
     }
 
-    /// Retrieve a copy of the node's stats.
-    pub async fn stats(&self) -> Stats {
+    /// Whether the current session with the node resumed a previous session,
+    /// in which case the players of the previous session were kept.
+    ///
+    /// Players of a resumed session that aren't in the player manager, such
+    /// as after the bot's process restarted, are added to it as the node
+    /// reports their state.
+    pub fn resumed(&self) -> bool {
+        self.0.resumed.load(Ordering::Relaxed)
+    }
+
+    /// Retrieve a copy of the node's most recent stats.
+    ///
+    /// Returns `None` if the node hasn't sent its stats yet.
+    pub async fn stats(&self) -> Option<Stats> {
         (*self.0.stats.lock().await).clone()
     }
 
@@ -292,65 +415,85 @@ impl Node {
     ///
     /// This score can be used to calculate how loaded the server is. A higher
     /// number means it is more heavily loaded.
+    ///
+    /// The penalty is calculated from the node's most recent stats: one point
+    /// per playing player, and penalties growing exponentially with the
+    /// system's CPU load and the number of audio frames that were deficit or
+    /// nulled in the last minute. Nodes that haven't sent their stats yet have
+    /// the maximum penalty, since how loaded they are is unknown.
     pub async fn penalty(&self) -> i32 {
-        let stats = self.0.stats.lock().await;
-        let cpu = 1.05f64.powf(100f64 * stats.cpu.system_load) * 10f64 - 10f64;
-
-        let (deficit_frame, null_frame) = (
-            1.03f64
-                .powf(500f64 * (stats.frames.as_ref().map_or(0, |f| f.deficit) as f64 / 3000f64))
-                * 300f64
-                - 300f64,
-            (1.03f64
-                .powf(500f64 * (stats.frames.as_ref().map_or(0, |f| f.nulled) as f64 / 3000f64))
-                * 300f64
-                - 300f64)
-                * 2f64,
-        );
-
-        stats.playing_players as i32 + cpu as i32 + deficit_frame as i32 + null_frame as i32
+        penalty(self.0.stats.lock().await.as_ref())
     }
 }
 
+/// Calculate the penalty of a node from its most recent stats, if any.
+fn penalty(stats: Option<&Stats>) -> i32 {
+    let stats = match stats {
+        Some(stats) => stats,
+        None => return i32::MAX,
+    };
+
+    let players = stats.playing_players.min(i32::MAX as u64) as i32;
+    let cpu = 1.05f64.powf(100f64 * stats.cpu.system_load) * 10f64 - 10f64;
+
+    let (deficit_frame, null_frame) = match stats.frames.as_ref() {
+        Some(frames) => (
+            1.03f64.powf(500f64 * (frames.deficit as f64 / 3000f64)) * 600f64 - 600f64,
+            (1.03f64.powf(500f64 * (frames.nulled as f64 / 3000f64)) * 300f64 - 300f64) * 2f64,
+        ),
+        // Nodes don't send frame stats before they've played audio.
+        None => (0f64, 0f64),
+    };
+
+    // Casts of floats to integers saturate, as do the additions, so heavily
+    // loaded nodes don't overflow into low penalties.
+    players
+        .saturating_add(cpu as i32)
+        .saturating_add(deficit_frame as i32)
+        .saturating_add(null_frame as i32)
+}
+
 struct Connection {
     config: NodeConfig,
     connection: WebSocketStream<ConnectStream>,
+    /// Node of the connection, used to add the players of resumed sessions.
+    node: Weak<NodeRef>,
     node_from: UnboundedReceiver<OutgoingEvent>,
     node_to: UnboundedSender<IncomingEvent>,
     players: PlayerManager,
-    stats: BiLock<Stats>,
+    stats: BiLock<Option<Stats>>,
 }
 
 impl Connection {
     async fn connect(
         config: NodeConfig,
+        node: Weak<NodeRef>,
+        node_from: UnboundedReceiver<OutgoingEvent>,
+        node_to: UnboundedSender<IncomingEvent>,
         players: PlayerManager,
-        stats: BiLock<Stats>,
-    ) -> Result<
-        (
-            Self,
-            UnboundedSender<OutgoingEvent>,
-            UnboundedReceiver<IncomingEvent>,
-        ),
-        NodeError,
-    > {
-        let connection = reconnect(&config).await?;
+        stats: BiLock<Option<Stats>>,
+    ) -> Result<Self, NodeError> {
+        let (connection, resumed) = reconnect(&config).await?;
+
+        let connection = Self {
+            config,
+            connection,
+            node,
+            node_from,
+            node_to,
+            players,
+            stats,
+        };
+        connection.set_resumed(resumed);
 
-        let (to_node, from_lavalink) = mpsc::unbounded();
-        let (to_lavalink, from_node) = mpsc::unbounded();
+        Ok(connection)
+    }
 
-        Ok((
-            Self {
-                config,
-                connection,
-                node_from: from_node,
-                node_to: to_node,
-                players,
-                stats,
-            },
-            to_lavalink,
-            from_lavalink,
-        ))
+    /// Record whether the current session resumed a previous session.
+    fn set_resumed(&self, resumed: bool) {
+        if let Some(node) = self.node.upgrade() {
+            node.resumed.store(resumed, Ordering::Relaxed);
+        }
     }
 
     async fn run(mut self) -> Result<(), NodeError> {
@@ -362,10 +505,7 @@ impl Connection {
                 Either::Left((Some(Ok(incoming)), _)) => {
                     self.incoming(incoming).await?;
                 }
-                Either::Left((_, _)) => {
-                    tracing::debug!("connection to {} closed, reconnecting", self.config.address);
-                    self.connection = reconnect(&self.config).await?;
-                }
+                Either::Left((_, _)) => self.reestablish().await?,
                 Either::Right((Some(outgoing), _)) => {
                     tracing::debug!(
                         "forwarding event to {}: {:?}",
@@ -380,7 +520,17 @@ impl Connection {
                         }
                     })?;
                     let msg = Message::Text(payload);
-                    self.connection.send(msg).await.unwrap();
+
+                    // Events about players are covered by restoring the
+                    // players after reconnecting.
+                    if let Err(source) = self.connection.send(msg).await {
+                        tracing::debug!(
+                            "failed to send event to {}: {}",
+                            self.config.address,
+                            source
+                        );
+                        self.reestablish().await?;
+                    }
                 }
                 Either::Right((_, _)) => {
                     tracing::debug!("node {} closed, ending connection", self.config.address);
@@ -393,6 +543,90 @@ impl Connection {
         Ok(())
     }
 
+    /// Reconnect to the node after the connection closed, restoring the
+    /// players of the node if the session didn't resume.
+    ///
+    /// Sends the lifecycle events of the node to the node's stream of events.
+    async fn reestablish(&mut self) -> Result<(), NodeError> {
+        tracing::debug!("connection to {} closed, reconnecting", self.config.address);
+        self.emit(
+            NodeDisconnected {
+                address: self.config.address,
+            }
+            .into(),
+        );
+
+        let (connection, resumed) = reconnect(&self.config).await?;
+        self.connection = connection;
+        self.set_resumed(resumed);
+
+        if !resumed {
+            self.restore_players().await;
+        }
+
+        self.emit(
+            NodeReconnected {
+                address: self.config.address,
+                resumed,
+            }
+            .into(),
+        );
+
+        Ok(())
+    }
+
+    /// Send the events restoring the players of the node over the new
+    /// session.
+    async fn restore_players(&mut self) {
+        let address = self.config.address;
+
+        // The events are collected first so that the player manager isn't
+        // locked while sending them.
+        let events = self
+            .players
+            .players
+            .iter()
+            .filter(|player| player.node().config().address == address)
+            .flat_map(|player| player.restore())
+            .collect::<Vec<_>>();
+
+        tracing::debug!(
+            "restoring players of {} with {} events",
+            address,
+            events.len()
+        );
+
+        for event in events {
+            let payload = match serde_json::to_string(&event) {
+                Ok(payload) => payload,
+                Err(source) => {
+                    tracing::warn!(
+                        "failed to serialize restoring event {:?}: {}",
+                        event,
+                        source
+                    );
+
+                    continue;
+                }
+            };
+
+            if let Err(source) = self.connection.send(Message::Text(payload)).await {
+                tracing::warn!("failed to restore players of {}: {}", address, source);
+
+                return;
+            }
+        }
+    }
+
+    /// Send an event to the node's stream of events.
+    fn emit(&self, event: IncomingEvent) {
+        // It's fine if the rx end dropped, often users don't need to care about
+        // these events.
+        if !self.node_to.is_closed() {
+            let _ = self.node_to.unbounded_send(event);
+        }
+    }
+
     async fn incoming(&mut self, incoming: Message) -> Result<bool, NodeError> {
         tracing::debug!(
             "received message from {}: {:?}",
@@ -427,7 +661,9 @@ impl Connection {
         let event = match serde_json::from_str(&text) {
             Ok(event) => event,
             Err(_) => {
-                tracing::warn!("unknown message from lavalink node: {}", text);
+                // Unknown ops are deserialized as unknown events, so this is
+                // only a payload without an op.
+                tracing::warn!("invalid message from lavalink node: {}", text);
 
                 return Ok(true);
             }
@@ -436,43 +672,64 @@ impl Connection {
         match event {
             IncomingEvent::PlayerUpdate(ref update) => self.player_update(update).await?,
             IncomingEvent::Stats(ref stats) => self.stats(stats).await?,
+            IncomingEvent::TrackEnd(ref end) => self.track_end(end),
             _ => {}
         }
 
-        // It's fine if the rx end dropped, often users don't need to care about
-        // these events.
-        if !self.node_to.is_closed() {
-            let _ = self.node_to.unbounded_send(event);
+        if let Some(player) = event.guild_id().and_then(|id| self.players.get(&id)) {
+            player.dispatch(&event);
         }
 
+        self.emit(event);
+
         Ok(true)
     }
 
     async fn player_update(&self, update: &PlayerUpdate) -> Result<(), NodeError> {
-        let mut player = match self.players.get_mut(&update.guild_id) {
+        let player = match self.players.get_mut(&update.guild_id) {
             Some(player) => player,
-            None => {
-                tracing::warn!(
-                    "invalid player update for guild {}: {:?}",
-                    update.guild_id,
-                    update,
-                );
+            // Players of resumed sessions may not be in the player manager,
+            // such as after the bot's process restarted.
+            None => match self.node.upgrade() {
+                Some(node) => {
+                    tracing::debug!(
+                        "adding player for guild {} reported by node {}",
+                        update.guild_id,
+                        self.config.address,
+                    );
 
-                return Ok(());
-            }
+                    self.players.get_or_insert(update.guild_id, Node(node))
+                }
+                None => return Ok(()),
+            },
         };
 
-        *player.value_mut().position_mut() = update.state.position;
-        *player.value_mut().time_mut() = update.state.time;
+        player.set_position(update.state.position);
+        player.set_time(update.state.time);
 
         Ok(())
     }
 
     async fn stats(&self, stats: &Stats) -> Result<(), NodeError> {
-        *self.stats.lock().await = stats.clone();
+        self.stats.lock().await.replace(stats.clone());
 
         Ok(())
     }
+
+    fn track_end(&self, end: &TrackEnd) {
+        let player = match self.players.get(&end.guild_id) {
+            Some(player) => player,
+            None => return,
+        };
+
+        if let Err(source) = player.track_end(end) {
+            tracing::warn!(
+                "failed to play next track of guild {}: {}",
+                end.guild_id,
+                source
+            );
+        }
+    }
 }
 
 fn connect_request(state: &NodeConfig) -> Result<Request<()>, NodeError> {
@@ -481,8 +738,8 @@ fn connect_request(state: &NodeConfig) -> Result<Request<()>, NodeError> {
     builder = builder.header("Num-Shards", state.shard_count);
     builder = builder.header("User-Id", state.user_id.0);
 
-    if state.resume.is_some() {
-        builder = builder.header("Resume-Key", state.address.to_string());
+    if let Some(resume) = state.resume.as_ref() {
+        builder = builder.header("Resume-Key", resume.key(state.address));
     }
 
     builder
@@ -490,39 +747,52 @@ fn connect_request(state: &NodeConfig) -> Result<Request<()>, NodeError> {
         .map_err(|source| NodeError::BuildingConnectionRequest { source })
 }
 
-async fn reconnect(config: &NodeConfig) -> Result<WebSocketStream<ConnectStream>, NodeError> {
+/// Connect to a node, configuring resuming of the session if it didn't resume
+/// a previous session.
+///
+/// Returns the connection and whether the session was resumed.
+async fn reconnect(
+    config: &NodeConfig,
+) -> Result<(WebSocketStream<ConnectStream>, bool), NodeError> {
     let (mut stream, res) = backoff(config).await?;
 
-    let headers = res.headers();
-
-    if let Some(resume) = config.resume.as_ref() {
-        let header = HeaderName::from_static("session-resumed");
-
-        if let Some(value) = headers.get(header) {
-            if value.as_bytes() == b"false" {
-                tracing::debug!("session to node {} didn't resume", config.address);
-
-                let payload = serde_json::json!({
-                    "op": "configureResuming",
-                    "key": config.address,
-                    "timeout": resume.timeout,
-                });
-                let msg = Message::Text(serde_json::to_string(&payload).unwrap());
-
-                stream.send(msg).await.unwrap();
-            } else {
-                tracing::debug!("session to {} resumed", config.address);
-            }
-        }
+    let resume = match config.resume.as_ref() {
+        Some(resume) => resume,
+        None => return Ok((stream, false)),
+    };
+
+    let header = HeaderName::from_static("session-resumed");
+    let resumed = res
+        .headers()
+        .get(header)
+        .map_or(false, |value| value.as_bytes() == b"true");
+
+    if resumed {
+        tracing::debug!("session to {} resumed", config.address);
+    } else {
+        tracing::debug!("session to node {} didn't resume", config.address);
+
+        let event = OutgoingEvent::from(ConfigureResuming::new(
+            resume.key(config.address),
+            resume.timeout,
+        ));
+        let payload =
+            serde_json::to_string(&event).map_err(|source| NodeError::SerializingMessage {
+                message: event.clone(),
+                source,
+            })?;
+
+        stream.send(Message::Text(payload)).await.unwrap();
     }
 
-    Ok(stream)
+    Ok((stream, resumed))
 }
 
 async fn backoff(
     config: &NodeConfig,
 ) -> Result<(WebSocketStream<ConnectStream>, Response<()>), NodeError> {
-    let mut seconds = 1;
+    let mut attempts = 0;
+    let mut delay = config.backoff.initial;
 
     loop {
         let req = connect_request(config)?;
@@ -530,7 +800,7 @@ async fn backoff(
         match async_tungstenite::tokio::connect_async(req).await {
             Ok((stream, res)) => return Ok((stream, res)),
             Err(source) => {
-                tracing::warn!("failed to connect to node {}: {:?}", source, config.address);
+                tracing::warn!("failed to connect to node {}: {:?}", config.address, source);
 
                 if matches!(source, TungsteniteError::Http(status) if status == StatusCode::UNAUTHORIZED)
                 {
@@ -540,20 +810,22 @@ async fn backoff(
                     });
                 }
 
-                if seconds > 64 {
+                attempts += 1;
+
+                if config.backoff.attempts.map_or(false, |max| attempts >= max) {
                     tracing::debug!("no longer trying to connect to node {}", config.address);
 
                     return Err(NodeError::Connecting { source });
                 }
 
                 tracing::debug!(
-                    "waiting {} sceonds before attempting to connect to node {} again",
-                    seconds,
+                    "waiting {:?} before attempting to connect to node {} again",
+                    delay,
                     config.address,
                 );
-                tokio_time::sleep(Duration::from_secs(seconds)).await;
+                tokio_time::sleep(delay).await;
 
-                seconds *= 2;
+                delay = (delay * 2).min(config.backoff.max);
 
                 continue;
             }
@@ -563,13 +835,17 @@ async fn backoff(
 
 #[cfg(test)]
 mod tests {
-    use super::{Node, NodeConfig, NodeError, Resume};
+    use super::{Backoff, Node, NodeConfig, NodeError, Resume};
+    use crate::model::{Opcode, Stats, StatsCpu, StatsFrames, StatsMemory};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
 
+    assert_fields!(Backoff: attempts, initial, max);
+    assert_impl_all!(Backoff: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
     assert_fields!(
         NodeConfig: address,
         authorization,
+        backoff,
         resume,
         shard_count,
         user_id
@@ -581,6 +857,78 @@ mod tests {
     assert_fields!(NodeError::Unauthorized: address, authorization);
     assert_impl_all!(NodeError: Debug, Error, Send, Sync);
     assert_impl_all!(Node: Clone, Debug, Send, Sync);
-    assert_fields!(Resume: timeout);
+    assert_fields!(Resume: key, timeout);
     assert_impl_all!(Resume: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+
+    fn stats(playing_players: u64, system_load: f64, frames: Option<(u64, u64)>) -> Stats {
+        Stats {
+            cpu: StatsCpu {
+                cores: 4,
+                lavalink_load: system_load / 2f64,
+                system_load,
+            },
+            frames: frames.map(|(deficit, nulled)| StatsFrames {
+                sent: 3000,
+                nulled,
+                deficit,
+            }),
+            memory: StatsMemory {
+                allocated: 0,
+                free: 0,
+                used: 0,
+                reservable: 0,
+            },
+            players: playing_players,
+            playing_players,
+            op: Opcode::Stats,
+            uptime: 1000,
+        }
+    }
+
+    #[test]
+    fn test_penalty() {
+        assert_eq!(i32::MAX, super::penalty(None));
+        assert_eq!(0, super::penalty(Some(&stats(0, 0f64, None))));
+        assert_eq!(5, super::penalty(Some(&stats(5, 0f64, Some((0, 0))))));
+
+        // 10 players, and 1.05^(100 * 0.1) * 10 - 10 for the CPU load.
+        assert_eq!(10 + 6, super::penalty(Some(&stats(10, 0.1, None))));
+
+        // 1.03^(500 * 60 / 3000) * 600 - 600 for deficit frames, and
+        // (1.03^(500 * 60 / 3000) * 300 - 300) * 2 for nulled frames.
+        assert_eq!(206, super::penalty(Some(&stats(0, 0f64, Some((60, 0))))));
+        assert_eq!(206, super::penalty(Some(&stats(0, 0f64, Some((0, 60))))));
+
+        // Heavily loaded nodes saturate instead of overflowing.
+        assert_eq!(
+            i32::MAX,
+            super::penalty(Some(&stats(u64::MAX, 1f64, Some((3000, 3000))))),
+        );
+    }
+
+    /// Test that nodes are ordered by their penalty, preferring idle nodes
+    /// over nodes with fewer players but a high CPU load, and nodes with stats
+    /// over nodes without.
+    #[test]
+    fn test_penalty_order() {
+        let nodes = [
+            ("unknown", None),
+            ("high cpu", Some(stats(5, 0.8, Some((0, 0))))),
+            ("idle", Some(stats(0, 0.05, Some((0, 0))))),
+            ("busy", Some(stats(40, 0.2, Some((0, 0))))),
+            ("dropping frames", Some(stats(10, 0.2, Some((300, 150))))),
+        ];
+
+        let mut order = nodes
+            .iter()
+            .map(|(name, stats)| (super::penalty(stats.as_ref()), *name))
+            .collect::<Vec<_>>();
+        order.sort();
+
+        let names = order.into_iter().map(|(_, name)| name).collect::<Vec<_>>();
+        assert_eq!(
+            ["idle", "busy", "high cpu", "dropping frames", "unknown"],
+            names.as_slice(),
+        );
+    }
 }
@@ -5,22 +5,33 @@
 //! use those players to do things like [send events] or [read the position] of
 //! the active audio.
 //!
+//! Players also have a [queue] of tracks, played one after the other.
+//!
 //! [players]: Player
+//! [queue]: Player::enqueue
 //! [send events]: Player::send
 //! [read the position]: Player::position
 
-use crate::{model::*, node::Node};
+use crate::{
+    model::*,
+    node::Node,
+    queue::{LoopMode, Queue},
+    track,
+};
 use dashmap::{
     mapref::one::{Ref, RefMut},
     DashMap,
 };
-use futures_channel::mpsc::TrySendError;
+use futures_channel::mpsc::{self, TrySendError, UnboundedReceiver, UnboundedSender};
 use std::{
-    fmt::Debug,
+    convert::TryFrom,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicI64, AtomicU16, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
     },
+    time::Duration,
 };
 use twilight_model::id::{ChannelId, GuildId};
 
@@ -58,6 +69,62 @@ impl PlayerManager {
     }
 }
 
+/// Length of the current track when it isn't known, such as for streams.
+const UNKNOWN_LENGTH: u64 = u64::MAX;
+
+/// Sending a command to a [`Player`] failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PlayerError {
+    /// The position to seek to is past the end of the current track.
+    SeekPastEnd {
+        /// The length of the current track.
+        length: Duration,
+        /// The position to seek to.
+        position: Duration,
+    },
+    /// Sending the command to the player's node failed, as the node has been
+    /// removed.
+    Sending {
+        /// The source of the error from the `futures_channel` crate.
+        source: TrySendError<OutgoingEvent>,
+    },
+    /// The volume is higher than [`Player::MAX_VOLUME`].
+    VolumeOutOfRange {
+        /// The provided volume.
+        volume: u16,
+    },
+}
+
+impl Display for PlayerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::SeekPastEnd { length, position } => write!(
+                f,
+                "the position {}ms is past the end of the track at {}ms",
+                position.as_millis(),
+                length.as_millis()
+            ),
+            Self::Sending { .. } => f.write_str("failed to send the command to the node"),
+            Self::VolumeOutOfRange { volume } => write!(
+                f,
+                "the volume is {}, but must be at most {}",
+                volume,
+                Player::MAX_VOLUME
+            ),
+        }
+    }
+}
+
+impl Error for PlayerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Sending { source } => Some(source),
+            Self::SeekPastEnd { .. } | Self::VolumeOutOfRange { .. } => None,
+        }
+    }
+}
+
 /// A player for a guild connected to a node.
 ///
 /// This can be used to send events over a node and to read the details of a
@@ -65,26 +132,40 @@ impl PlayerManager {
 #[derive(Debug)]
 pub struct Player {
     channel_id: Option<ChannelId>,
+    events: Mutex<Vec<UnboundedSender<IncomingEvent>>>,
     guild_id: GuildId,
     node: Node,
     paused: AtomicBool,
-    playing: Option<()>,
-    position: i64,
-    time: i64,
-    volume: u16,
+    /// Track currently playing, if any.
+    playing: Mutex<Option<String>>,
+    position: AtomicI64,
+    queue: Mutex<Queue>,
+    time: AtomicI64,
+    track_length: AtomicU64,
+    /// Last voice update sent to the node, if any.
+    voice_update: Mutex<Option<VoiceUpdate>>,
+    volume: AtomicU16,
 }
 
 impl Player {
+    /// The highest volume of a player, 100 leaving the volume of tracks
+    /// unchanged.
+    pub const MAX_VOLUME: u16 = 1000;
+
     pub(crate) fn new(guild_id: GuildId, node: Node) -> Self {
         Self {
             channel_id: None,
+            events: Mutex::new(Vec::new()),
             guild_id,
             node,
             paused: AtomicBool::new(false),
-            playing: None,
-            position: 0,
-            time: 0,
-            volume: 0,
+            playing: Mutex::new(None),
+            position: AtomicI64::new(0),
+            queue: Mutex::new(Queue::new()),
+            time: AtomicI64::new(0),
+            track_length: AtomicU64::new(UNKNOWN_LENGTH),
+            voice_update: Mutex::new(None),
+            volume: AtomicU16::new(100),
         }
     }
 
@@ -126,13 +207,287 @@ impl Player {
             event
         );
 
-        if let OutgoingEvent::Pause(ref event) = event {
-            self.paused.store(event.pause, Ordering::Release);
+        match event {
+            OutgoingEvent::Destroy(_) => {
+                self.track_length.store(UNKNOWN_LENGTH, Ordering::Release);
+                lock(&self.playing).take();
+                lock(&self.voice_update).take();
+                self.subscribers().clear();
+            }
+            OutgoingEvent::Stop(_) => {
+                self.track_length.store(UNKNOWN_LENGTH, Ordering::Release);
+                lock(&self.playing).take();
+            }
+            OutgoingEvent::Pause(ref event) => {
+                self.paused.store(event.pause, Ordering::Release);
+            }
+            OutgoingEvent::Play(ref event) => {
+                let start_time = event.start_time.unwrap_or(0);
+
+                self.position.store(
+                    i64::try_from(start_time).unwrap_or(i64::MAX),
+                    Ordering::Release,
+                );
+                self.track_length
+                    .store(track_length(&event.track), Ordering::Release);
+                lock(&self.playing).replace(event.track.clone());
+            }
+            OutgoingEvent::Seek(ref event) => {
+                self.position.store(event.position, Ordering::Release);
+            }
+            OutgoingEvent::Volume(ref event) => {
+                let volume = u16::try_from(event.volume.max(0)).unwrap_or(u16::MAX);
+
+                self.volume.store(volume, Ordering::Release);
+            }
+            OutgoingEvent::VoiceUpdate(ref event) => {
+                lock(&self.voice_update).replace(event.clone());
+            }
+            _ => {}
         }
 
         self.node.send(event)
     }
 
+    /// Return a stream of the events about the player received by its node.
+    ///
+    /// This includes updates of the player and the events of its tracks, but
+    /// not the [`Stats`] of the node. The events are also received by the
+    /// node's stream of events.
+    ///
+    /// The stream ends when the player is destroyed via a [`Destroy`] event.
+    ///
+    /// # Examples
+    ///
+    /// Wait for the current track of a player to end:
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use twilight_lavalink::{model::IncomingEvent, Lavalink};
+    /// # use twilight_model::id::{GuildId, UserId};
+    /// # #[tokio::main] async fn main() {
+    /// # let (guild_id, user_id) = (GuildId(1), UserId(2));
+    ///
+    /// let lavalink = Lavalink::new(user_id, 10);
+    /// let players = lavalink.players();
+    ///
+    /// let mut events = match players.get(&guild_id) {
+    ///     Some(player) => player.events(),
+    ///     None => return,
+    /// };
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     if let IncomingEvent::TrackEnd(end) = event {
+    ///         println!("track ended: {:?}", end.reason);
+    ///
+    ///         break;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`Destroy`]: crate::model::outgoing::Destroy
+    /// [`Stats`]: crate::model::incoming::Stats
+    pub fn events(&self) -> UnboundedReceiver<IncomingEvent> {
+        let (tx, rx) = mpsc::unbounded();
+        self.subscribers().push(tx);
+
+        rx
+    }
+
+    /// Send an event received by the node to the player's subscribers,
+    /// removing those that dropped their stream.
+    pub(crate) fn dispatch(&self, event: &IncomingEvent) {
+        self.subscribers()
+            .retain(|tx| tx.unbounded_send(event.clone()).is_ok());
+    }
+
+    fn subscribers(&self) -> MutexGuard<'_, Vec<UnboundedSender<IncomingEvent>>> {
+        lock(&self.events)
+    }
+
+    /// Create the events restoring the player on a new session of its node.
+    ///
+    /// The voice update is sent again, followed by the current track from
+    /// the last known position, the volume, and whether it's paused.
+    pub(crate) fn restore(&self) -> Vec<OutgoingEvent> {
+        let mut events = Vec::new();
+
+        let voice_update = match lock(&self.voice_update).clone() {
+            Some(voice_update) => voice_update,
+            // Players that never connected have nothing to play on.
+            None => return events,
+        };
+        events.push(voice_update.into());
+
+        if let Some(track) = lock(&self.playing).clone() {
+            let position = u64::try_from(self.position().max(0)).unwrap_or(0);
+
+            events.push(Play::new(self.guild_id, track, Some(position), None, false).into());
+        }
+
+        let volume = self.volume_ref();
+
+        if volume != 100 {
+            events.push(Volume::from((self.guild_id, i64::from(volume))).into());
+        }
+
+        if self.paused() {
+            events.push(Pause::from((self.guild_id, true)).into());
+        }
+
+        events
+    }
+
+    /// Seek the current track to a position.
+    ///
+    /// The position is checked against the length of the current track if
+    /// it's known, which it is for tracks played through this player that
+    /// aren't streams.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::SeekPastEnd`] if the position is past the end of
+    /// the current track.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node has been removed.
+    pub fn seek(&self, position: Duration) -> Result<(), PlayerError> {
+        if let Some(length) = self.track_length() {
+            if position > length {
+                return Err(PlayerError::SeekPastEnd { length, position });
+            }
+        }
+
+        let millis = i64::try_from(position.as_millis()).unwrap_or(i64::MAX);
+
+        self.command(Seek::from((self.guild_id, millis)))
+    }
+
+    /// Pause or unpause the player.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node has been removed.
+    pub fn set_pause(&self, pause: bool) -> Result<(), PlayerError> {
+        self.command(Pause::from((self.guild_id, pause)))
+    }
+
+    /// Set the volume of the player, from 0 to [`MAX_VOLUME`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::VolumeOutOfRange`] if the volume is higher than
+    /// [`MAX_VOLUME`].
+    ///
+    /// Returns [`PlayerError::Sending`] if the node has been removed.
+    ///
+    /// [`MAX_VOLUME`]: Self::MAX_VOLUME
+    pub fn set_volume(&self, volume: u16) -> Result<(), PlayerError> {
+        if volume > Self::MAX_VOLUME {
+            return Err(PlayerError::VolumeOutOfRange { volume });
+        }
+
+        self.command(Volume::from((self.guild_id, i64::from(volume))))
+    }
+
+    fn command(&self, event: impl Into<OutgoingEvent>) -> Result<(), PlayerError> {
+        self.send(event)
+            .map_err(|source| PlayerError::Sending { source })
+    }
+
+    /// Add a track to the end of the player's queue.
+    ///
+    /// The track is played immediately if no track of the queue is playing.
+    /// Otherwise, it's played once the tracks before it finished.
+    ///
+    /// Returns a `futures_channel` `TrySendError` if the node has been removed.
+    ///
+    /// # Examples
+    ///
+    /// Queue two tracks, looping over them:
+    ///
+    /// ```
+    /// use twilight_lavalink::{queue::LoopMode, Lavalink};
+    /// # use twilight_model::id::{GuildId, UserId};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let (guild_id, user_id) = (GuildId(1), UserId(2));
+    /// # let (first, second) = (String::new(), String::new());
+    ///
+    /// let lavalink = Lavalink::new(user_id, 10);
+    /// let players = lavalink.players();
+    ///
+    /// if let Some(player) = players.get(&guild_id) {
+    ///     player.set_loop_mode(LoopMode::Queue);
+    ///     player.enqueue(first)?;
+    ///     player.enqueue(second)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn enqueue(&self, track: impl Into<String>) -> Result<(), TrySendError<OutgoingEvent>> {
+        let next = self.queue().enqueue(track.into()).map(ToOwned::to_owned);
+
+        match next {
+            Some(track) => self.play(track),
+            None => Ok(()),
+        }
+    }
+
+    /// Skip the current track of the player's queue, playing the next one.
+    ///
+    /// The current track is skipped even if the [`LoopMode`] is
+    /// [`LoopMode::Track`]. If the queue has no more tracks, the player is
+    /// stopped.
+    ///
+    /// Returns a `futures_channel` `TrySendError` if the node has been removed.
+    pub fn skip(&self) -> Result<(), TrySendError<OutgoingEvent>> {
+        let next = self.queue().skip().map(ToOwned::to_owned);
+
+        match next {
+            Some(track) => self.play(track),
+            None => self.send(Stop::from(self.guild_id)),
+        }
+    }
+
+    /// Remove the upcoming tracks of the player's queue.
+    ///
+    /// The current track keeps playing.
+    pub fn clear_queue(&self) {
+        self.queue().clear();
+    }
+
+    /// Set how the tracks of the player's queue are repeated.
+    pub fn set_loop_mode(&self, loop_mode: LoopMode) {
+        self.queue().set_loop_mode(loop_mode);
+    }
+
+    /// Return a guard to the player's queue.
+    ///
+    /// The node can't advance the queue while the guard is held, so it should
+    /// be dropped quickly.
+    pub fn queue(&self) -> MutexGuard<'_, Queue> {
+        lock(&self.queue)
+    }
+
+    /// Advance the player's queue after a track ended.
+    pub(crate) fn track_end(&self, event: &TrackEnd) -> Result<(), TrySendError<OutgoingEvent>> {
+        // Replaced tracks were replaced by a track that is now playing.
+        if event.reason != TrackEndReason::Replaced {
+            lock(&self.playing).take();
+        }
+
+        let next = self.queue().track_end(event.reason).map(ToOwned::to_owned);
+
+        match next {
+            Some(track) => self.play(track),
+            None => Ok(()),
+        }
+    }
+
+    /// Play a track of the player's queue, replacing the current track.
+    fn play(&self, track: String) -> Result<(), TrySendError<OutgoingEvent>> {
+        self.send(Play::new(self.guild_id, track, None, None, false))
+    }
+
     /// Return an immutable reference to the node linked to the player.
     pub fn node(&self) -> &Node {
         &self.node
@@ -154,37 +509,66 @@ impl Player {
     }
 
     /// Return a copy of the player's position.
+    ///
+    /// This is the position of the last seek or player update, in
+    /// milliseconds.
     pub fn position(&self) -> i64 {
-        self.position
+        self.position.load(Ordering::Acquire)
     }
 
-    /// Return a mmutable reference to the player's channel ID.
-    pub(crate) fn position_mut(&mut self) -> &mut i64 {
-        &mut self.position
+    /// Set the player's position, as reported by a player update.
+    pub(crate) fn set_position(&self, position: i64) {
+        self.position.store(position, Ordering::Release);
     }
 
     /// Return a copy of the player's time.
     pub fn time_ref(&mut self) -> i64 {
-        self.time
+        self.time.load(Ordering::Acquire)
     }
 
-    /// Return a mutable reference to the player's channel ID.
-    pub(crate) fn time_mut(&mut self) -> &mut i64 {
-        &mut self.time
+    /// Set the player's time, as reported by a player update.
+    pub(crate) fn set_time(&self, time: i64) {
+        self.time.store(time, Ordering::Release);
+    }
+
+    /// Return the length of the current track, if it's known.
+    ///
+    /// The length is known for tracks played through this player that aren't
+    /// streams.
+    pub fn track_length(&self) -> Option<Duration> {
+        match self.track_length.load(Ordering::Acquire) {
+            UNKNOWN_LENGTH => None,
+            length => Some(Duration::from_millis(length)),
+        }
     }
 
     /// Return a copy of the player's volume.
     pub fn volume_ref(&self) -> u16 {
-        self.volume
+        self.volume.load(Ordering::Acquire)
+    }
+}
+
+/// Lock a mutex of a player, ignoring poisoning as the state stays usable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Length of a track in milliseconds, or [`UNKNOWN_LENGTH`] if it can't be
+/// decoded or is a stream.
+fn track_length(track: &str) -> u64 {
+    match track::decode(track) {
+        Ok(info) if !info.is_stream => info.length,
+        _ => UNKNOWN_LENGTH,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Player, PlayerManager};
+    use super::{Player, PlayerError, PlayerManager};
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{error::Error, fmt::Debug};
 
+    assert_impl_all!(PlayerError: Debug, Error, Send, Sync);
     assert_impl_all!(PlayerManager: Clone, Debug, Default, Send, Sync);
     assert_impl_all!(Player: Debug, Send, Sync);
 }
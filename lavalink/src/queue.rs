@@ -0,0 +1,235 @@
+//! Queues of tracks played by a [`Player`].
+//!
+//! Tracks are added to the queue of a player via [`Player::enqueue`], and are
+//! played one after the other: when a track finishes, the node plays the next
+//! track of the queue, according to the queue's [`LoopMode`].
+//!
+//! Tracks played by sending [`Play`] events to the player directly aren't
+//! part of its queue.
+//!
+//! [`Play`]: crate::model::Play
+//! [`Player`]: crate::player::Player
+//! [`Player::enqueue`]: crate::player::Player::enqueue
+
+use crate::model::TrackEndReason;
+use std::collections::{vec_deque::Iter, VecDeque};
+
+/// How the tracks of a queue are repeated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LoopMode {
+    /// Tracks are played once.
+    Off,
+    /// The current track is played again when it finishes.
+    Track,
+    /// Tracks are added back to the end of the queue when they finish.
+    Queue,
+}
+
+// Implemented by hand because `#[default]` variants need Rust 1.62 and the
+// MSRV is 1.48.
+impl Default for LoopMode {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+/// Queue of tracks of a player.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Queue {
+    current: Option<String>,
+    loop_mode: LoopMode,
+    tracks: VecDeque<String>,
+}
+
+impl Queue {
+    /// Create a new, empty queue.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the track of the queue currently playing, if any.
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Return whether there are no upcoming tracks.
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Return the number of upcoming tracks.
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Return how the tracks of the queue are repeated.
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Return an iterator over the upcoming tracks, in the order they will be
+    /// played.
+    pub fn tracks(&self) -> Iter<'_, String> {
+        self.tracks.iter()
+    }
+
+    /// Add a track to the end of the queue.
+    ///
+    /// Returns the track to play if no track of the queue was playing.
+    pub(crate) fn enqueue(&mut self, track: String) -> Option<&str> {
+        self.tracks.push_back(track);
+
+        if self.current.is_some() {
+            return None;
+        }
+
+        self.current = self.tracks.pop_front();
+
+        self.current()
+    }
+
+    /// Remove the upcoming tracks, leaving the current one playing.
+    pub(crate) fn clear(&mut self) {
+        self.tracks.clear();
+    }
+
+    /// Set how the tracks of the queue are repeated.
+    pub(crate) fn set_loop_mode(&mut self, loop_mode: LoopMode) {
+        self.loop_mode = loop_mode;
+    }
+
+    /// Move on to the next track, even if the current track is looped.
+    ///
+    /// Returns the track to play, or `None` if the queue ran out of tracks.
+    pub(crate) fn skip(&mut self) -> Option<&str> {
+        let previous = self.current.take();
+
+        if let (LoopMode::Queue, Some(previous)) = (self.loop_mode, previous) {
+            self.tracks.push_back(previous);
+        }
+
+        self.current = self.tracks.pop_front();
+
+        self.current()
+    }
+
+    /// Update the queue after the current track ended with a given reason.
+    ///
+    /// Returns the track to play. Only tracks that finished or failed to load
+    /// move the queue on to the next track: tracks that were replaced leave
+    /// the replacing track playing, and tracks that were stopped or cleaned
+    /// up leave nothing playing.
+    pub(crate) fn track_end(&mut self, reason: TrackEndReason) -> Option<&str> {
+        match reason {
+            TrackEndReason::Finished if self.loop_mode == LoopMode::Track => self.current(),
+            TrackEndReason::Finished => self.skip(),
+            // Tracks that can't be loaded aren't looped, as they would fail
+            // again.
+            TrackEndReason::LoadFailed => {
+                self.current = self.tracks.pop_front();
+
+                self.current()
+            }
+            TrackEndReason::Replaced => None,
+            _ => {
+                self.current = None;
+
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoopMode, Queue};
+    use crate::model::TrackEndReason;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_impl_all!(LoopMode: Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Queue: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+
+    fn queue(loop_mode: LoopMode, tracks: &[&str]) -> Queue {
+        let mut queue = Queue::new();
+        queue.set_loop_mode(loop_mode);
+
+        for track in tracks {
+            queue.enqueue((*track).to_owned());
+        }
+
+        queue
+    }
+
+    #[test]
+    fn test_enqueue() {
+        let mut queue = Queue::new();
+        assert_eq!(Some("a"), queue.enqueue("a".to_owned()));
+        assert_eq!(None, queue.enqueue("b".to_owned()));
+        assert_eq!(Some("a"), queue.current());
+        assert_eq!(vec!["b"], queue.tracks().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_finished() {
+        let mut queue = queue(LoopMode::Off, &["a", "b"]);
+        assert_eq!(Some("b"), queue.track_end(TrackEndReason::Finished));
+        assert_eq!(None, queue.track_end(TrackEndReason::Finished));
+        assert_eq!(None, queue.current());
+
+        // Tracks enqueued once the queue ran out are played immediately.
+        assert_eq!(Some("c"), queue.enqueue("c".to_owned()));
+    }
+
+    #[test]
+    fn test_finished_loop_track() {
+        let mut queue = queue(LoopMode::Track, &["a", "b"]);
+        assert_eq!(Some("a"), queue.track_end(TrackEndReason::Finished));
+        assert_eq!(Some("a"), queue.track_end(TrackEndReason::Finished));
+
+        // Skipping moves on even if the current track is looped.
+        assert_eq!(Some("b"), queue.skip());
+        assert_eq!(Some("b"), queue.track_end(TrackEndReason::Finished));
+    }
+
+    #[test]
+    fn test_finished_loop_queue() {
+        let mut queue = queue(LoopMode::Queue, &["a", "b"]);
+        assert_eq!(Some("b"), queue.track_end(TrackEndReason::Finished));
+        assert_eq!(Some("a"), queue.track_end(TrackEndReason::Finished));
+        assert_eq!(Some("b"), queue.skip());
+        assert_eq!(vec!["a"], queue.tracks().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_load_failed() {
+        let mut queue = queue(LoopMode::Queue, &["a", "b"]);
+        assert_eq!(Some("b"), queue.track_end(TrackEndReason::LoadFailed));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_no_advance() {
+        for reason in [TrackEndReason::Stopped, TrackEndReason::Cleanup].iter() {
+            let mut queue = queue(LoopMode::Queue, &["a", "b"]);
+            assert_eq!(None, queue.track_end(*reason));
+            assert_eq!(None, queue.current());
+            assert_eq!(1, queue.len());
+        }
+
+        // The replacing track is left playing.
+        let mut queue = queue(LoopMode::Off, &["a", "b"]);
+        assert_eq!(Some("b"), queue.skip());
+        assert_eq!(None, queue.track_end(TrackEndReason::Replaced));
+        assert_eq!(Some("b"), queue.current());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut queue = queue(LoopMode::Off, &["a", "b", "c"]);
+        queue.clear();
+        assert_eq!(Some("a"), queue.current());
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.skip());
+    }
+}
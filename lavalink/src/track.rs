@@ -0,0 +1,564 @@
+//! Decoding and encoding the base64 track strings of Lavalink.
+//!
+//! Tracks loaded by a node are represented by an opaque base64 string, which
+//! is what's sent in [`Play`] events. The string embeds the metadata of the
+//! track in the message format of Lavaplayer, which [`decode`] reads without
+//! having to ask a node to decode the track.
+//!
+//! Versions 1 to 3 of the format are supported.
+//!
+//! [`Play`]: crate::model::Play
+
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Flag of the message header set when the message starts with its version.
+const VERSIONED: u32 = 1;
+
+/// Mask of the message header containing the size of the message.
+const MESSAGE_SIZE_MASK: u32 = 0x3FFF_FFFF;
+
+/// Decoding a track failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The track isn't valid base64.
+    Base64 {
+        /// The source of the error from the `base64` crate.
+        source: base64::DecodeError,
+    },
+    /// A string of the track isn't valid modified UTF-8.
+    InvalidString,
+    /// The track ended before all of its fields were read.
+    UnexpectedEnd,
+    /// The version of the track's format isn't supported.
+    UnsupportedVersion {
+        /// The version of the format.
+        version: u8,
+    },
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Base64 { .. } => f.write_str("the track isn't valid base64"),
+            Self::InvalidString => f.write_str("a string of the track isn't valid modified utf-8"),
+            Self::UnexpectedEnd => f.write_str("the track ended unexpectedly"),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "version {} of the track format isn't supported", version)
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Base64 { source } => Some(source),
+            Self::InvalidString | Self::UnexpectedEnd | Self::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+/// Encoding a track failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// The track is longer than the format allows.
+    MessageTooLong {
+        /// The length of the track in bytes.
+        len: usize,
+    },
+    /// A string of the track is longer than the 65535 bytes the format
+    /// allows.
+    StringTooLong {
+        /// The length of the string in bytes, once encoded.
+        len: usize,
+    },
+    /// The version of the track's format isn't supported.
+    UnsupportedVersion {
+        /// The version of the format.
+        version: u8,
+    },
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MessageTooLong { len } => write!(f, "the track is too long ({} bytes)", len),
+            Self::StringTooLong { len } => {
+                write!(f, "a string of the track is too long ({} bytes)", len)
+            }
+            Self::UnsupportedVersion { version } => {
+                write!(f, "version {} of the track format isn't supported", version)
+            }
+        }
+    }
+}
+
+impl Error for EncodeError {}
+
+/// Metadata of a track, decoded from its base64 string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TrackInfo {
+    /// The URL of the track's artwork.
+    ///
+    /// This is only present in version 3 of the format.
+    pub artwork_url: Option<String>,
+    /// The name of the author.
+    pub author: String,
+    /// The identifier of the track within its source.
+    pub identifier: String,
+    /// Whether the track is a stream.
+    pub is_stream: bool,
+    /// The International Standard Recording Code of the track.
+    ///
+    /// This is only present in version 3 of the format.
+    pub isrc: Option<String>,
+    /// The length of the track in milliseconds.
+    pub length: u64,
+    /// The position in milliseconds to start the track from.
+    pub position: u64,
+    /// The name of the source of the track, such as `"youtube"` or `"http"`.
+    pub source: String,
+    /// Data specific to the source of the track, such as the probed format
+    /// of HTTP tracks.
+    ///
+    /// This is left undecoded, as its format depends on the source.
+    pub source_details: Vec<u8>,
+    /// The title of the track.
+    pub title: String,
+    /// The URI of the track.
+    ///
+    /// This is only present from version 2 of the format.
+    pub uri: Option<String>,
+    /// The version of the format the track was encoded with.
+    pub version: u8,
+}
+
+/// Decode the metadata of a base64 track string.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let track = "QAAAjQIAJVJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAADlJpY2tBc3RsZXl\
+///     WRVZPAAAAAAADPCAAC2RRdzR3OVdnWGNRAAEAK2h0dHBzOi8vd3d3LnlvdXR1YmUuY29tL3dhdGNoP3Y9ZFF\
+///     3NHc5V2dYY1EAB3lvdXR1YmUAAAAAAAAAAA==";
+///
+/// let info = twilight_lavalink::track::decode(track)?;
+/// assert_eq!("Rick Astley - Never Gonna Give You Up", info.title);
+/// assert_eq!(212_000, info.length);
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DecodeError::Base64`] if the track isn't valid base64.
+///
+/// Returns [`DecodeError::InvalidString`] if a string of the track isn't valid
+/// modified UTF-8.
+///
+/// Returns [`DecodeError::UnexpectedEnd`] if the track is missing fields.
+///
+/// Returns [`DecodeError::UnsupportedVersion`] if the track was encoded with a
+/// version of the format other than 1 to 3.
+pub fn decode(track: &str) -> Result<TrackInfo, DecodeError> {
+    let bytes = base64::decode(track).map_err(|source| DecodeError::Base64 { source })?;
+    let mut reader = Reader(&bytes);
+
+    let header = reader.read_u32()?;
+    let size = (header & MESSAGE_SIZE_MASK) as usize;
+    let mut reader = Reader(reader.take(size)?);
+
+    let version = if header >> 30 & VERSIONED == VERSIONED {
+        reader.read_u8()?
+    } else {
+        1
+    };
+
+    if version == 0 || version > 3 {
+        return Err(DecodeError::UnsupportedVersion { version });
+    }
+
+    let title = reader.read_utf()?;
+    let author = reader.read_utf()?;
+    let length = reader.read_u64()?;
+    let identifier = reader.read_utf()?;
+    let is_stream = reader.read_bool()?;
+
+    let uri = if version >= 2 {
+        reader.read_nullable_utf()?
+    } else {
+        None
+    };
+
+    let (artwork_url, isrc) = if version >= 3 {
+        (reader.read_nullable_utf()?, reader.read_nullable_utf()?)
+    } else {
+        (None, None)
+    };
+
+    let source = reader.read_utf()?;
+
+    // The details specific to the source are followed by the position, which
+    // ends the message.
+    let details_len = reader
+        .0
+        .len()
+        .checked_sub(8)
+        .ok_or(DecodeError::UnexpectedEnd)?;
+    let source_details = reader.take(details_len)?.to_vec();
+    let position = reader.read_u64()?;
+
+    Ok(TrackInfo {
+        artwork_url,
+        author,
+        identifier,
+        is_stream,
+        isrc,
+        length,
+        position,
+        source,
+        source_details,
+        title,
+        uri,
+        version,
+    })
+}
+
+/// Encode the metadata of a track as a base64 track string.
+///
+/// This is the inverse of [`decode`]. The track is encoded with the
+/// [version] of the format of the metadata, leaving out the fields that
+/// aren't part of that version.
+///
+/// # Errors
+///
+/// Returns [`EncodeError::MessageTooLong`] if the encoded track is longer than
+/// the format allows.
+///
+/// Returns [`EncodeError::StringTooLong`] if a string of the track is longer
+/// than 65535 bytes once encoded.
+///
+/// Returns [`EncodeError::UnsupportedVersion`] if the version of the format
+/// isn't 1 to 3.
+///
+/// [version]: TrackInfo::version
+pub fn encode(info: &TrackInfo) -> Result<String, EncodeError> {
+    let version = info.version;
+
+    if version == 0 || version > 3 {
+        return Err(EncodeError::UnsupportedVersion { version });
+    }
+
+    let mut message = Vec::new();
+
+    if version >= 2 {
+        message.push(version);
+    }
+
+    write_utf(&mut message, &info.title)?;
+    write_utf(&mut message, &info.author)?;
+    message.extend_from_slice(&info.length.to_be_bytes());
+    write_utf(&mut message, &info.identifier)?;
+    message.push(u8::from(info.is_stream));
+
+    if version >= 2 {
+        write_nullable_utf(&mut message, info.uri.as_deref())?;
+    }
+
+    if version >= 3 {
+        write_nullable_utf(&mut message, info.artwork_url.as_deref())?;
+        write_nullable_utf(&mut message, info.isrc.as_deref())?;
+    }
+
+    write_utf(&mut message, &info.source)?;
+    message.extend_from_slice(&info.source_details);
+    message.extend_from_slice(&info.position.to_be_bytes());
+
+    let size = match u32::try_from(message.len()) {
+        Ok(size) if size <= MESSAGE_SIZE_MASK => size,
+        _ => {
+            return Err(EncodeError::MessageTooLong { len: message.len() });
+        }
+    };
+
+    let flags = if version >= 2 { VERSIONED } else { 0 };
+    let mut bytes = (flags << 30 | size).to_be_bytes().to_vec();
+    bytes.append(&mut message);
+
+    Ok(base64::encode(bytes))
+}
+
+/// Reader of the fields of a message.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.0.len() < len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(taken)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, DecodeError> {
+        self.read_u8().map(|byte| byte != 0)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.take(2)?);
+
+        Ok(u16::from_be_bytes(bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DecodeError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    fn read_nullable_utf(&mut self) -> Result<Option<String>, DecodeError> {
+        if self.read_bool()? {
+            self.read_utf().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Read a string prefixed by its length, in the modified UTF-8 of Java's
+    /// `DataInput`.
+    ///
+    /// Modified UTF-8 encodes each UTF-16 code unit separately, and encodes
+    /// the null character with two bytes.
+    fn read_utf(&mut self) -> Result<String, DecodeError> {
+        let len = usize::from(self.read_u16()?);
+        let mut bytes = self.take(len)?.iter().copied();
+        let mut units = Vec::with_capacity(len);
+
+        while let Some(first) = bytes.next() {
+            let unit = match first {
+                0x00..=0x7F => u16::from(first),
+                0xC0..=0xDF => u16::from(first & 0x1F) << 6 | continuation(bytes.next())?,
+                0xE0..=0xEF => {
+                    let second = continuation(bytes.next())?;
+
+                    u16::from(first & 0x0F) << 12 | second << 6 | continuation(bytes.next())?
+                }
+                _ => return Err(DecodeError::InvalidString),
+            };
+
+            units.push(unit);
+        }
+
+        String::from_utf16(&units).map_err(|_| DecodeError::InvalidString)
+    }
+}
+
+/// Return the bits of a continuation byte of modified UTF-8.
+fn continuation(byte: Option<u8>) -> Result<u16, DecodeError> {
+    match byte {
+        Some(byte) if byte & 0xC0 == 0x80 => Ok(u16::from(byte & 0x3F)),
+        _ => Err(DecodeError::InvalidString),
+    }
+}
+
+fn write_nullable_utf(message: &mut Vec<u8>, value: Option<&str>) -> Result<(), EncodeError> {
+    match value {
+        Some(value) => {
+            message.push(1);
+
+            write_utf(message, value)
+        }
+        None => {
+            message.push(0);
+
+            Ok(())
+        }
+    }
+}
+
+/// Write a string prefixed by its length, in the modified UTF-8 of Java's
+/// `DataOutput`.
+fn write_utf(message: &mut Vec<u8>, value: &str) -> Result<(), EncodeError> {
+    let mut bytes = Vec::with_capacity(value.len());
+
+    for unit in value.encode_utf16() {
+        match unit {
+            0x01..=0x7F => bytes.push(unit as u8),
+            0x00 | 0x80..=0x07FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | (unit >> 6 & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+
+    let len =
+        u16::try_from(bytes.len()).map_err(|_| EncodeError::StringTooLong { len: bytes.len() })?;
+    message.extend_from_slice(&len.to_be_bytes());
+    message.append(&mut bytes);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, DecodeError, EncodeError, TrackInfo};
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+
+    assert_impl_all!(DecodeError: Debug, Error, Send, Sync);
+    assert_impl_all!(EncodeError: Debug, Error, Send, Sync);
+    assert_impl_all!(TrackInfo: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    /// Track loaded from YouTube by Lavalink 3, in version 2 of the format,
+    /// as shown in Lavalink's documentation.
+    const YOUTUBE: &str = "QAAAjQIAJVJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAADlJpY2tBc3RsZXlWRVZPAAAAAAADPCAAC2RRdzR3OVdnWGNRAAEAK2h0dHBzOi8vd3d3LnlvdXR1YmUuY29tL3dhdGNoP3Y9ZFF3NHc5V2dYY1EAB3lvdXR1YmUAAAAAAAAAAA==";
+
+    /// Track of a `TrackEndEvent` sent by a Lavalink 3 node once the track
+    /// finished, with the position it finished at.
+    const YOUTUBE_FINISHED: &str = "QAAAjAIAKk5pZ2h0d2lzaCAtIFRoZSBJc2xhbmRlciAoTGl2ZSBBdCBUYW1wZXJlKQAIRWRkIEpvc3MAAAAAAAV2cAALWm84bmNLXzVremMAAQAraHR0cHM6Ly93d3cueW91dHViZS5jb20vd2F0Y2g/dj1abzhuY0tfNWt6YwAHeW91dHViZQAAAAAABXKc";
+
+    #[test]
+    fn test_decode_youtube() {
+        let info = decode(YOUTUBE).unwrap();
+        assert_eq!(None, info.artwork_url);
+        assert_eq!("RickAstleyVEVO", info.author);
+        assert_eq!("dQw4w9WgXcQ", info.identifier);
+        assert!(!info.is_stream);
+        assert_eq!(None, info.isrc);
+        assert_eq!(212_000, info.length);
+        assert_eq!(0, info.position);
+        assert_eq!("youtube", info.source);
+        assert!(info.source_details.is_empty());
+        assert_eq!("Rick Astley - Never Gonna Give You Up", info.title);
+        assert_eq!(
+            Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            info.uri.as_deref()
+        );
+        assert_eq!(2, info.version);
+    }
+
+    #[test]
+    fn test_decode_position() {
+        let info = decode(YOUTUBE_FINISHED).unwrap();
+        assert_eq!("Edd Joss", info.author);
+        assert_eq!("Zo8ncK_5kzc", info.identifier);
+        assert_eq!(358_000, info.length);
+        assert_eq!(357_020, info.position);
+        assert_eq!("Nightwish - The Islander (Live At Tampere)", info.title);
+        assert_eq!(2, info.version);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        for track in [YOUTUBE, YOUTUBE_FINISHED].iter() {
+            assert_eq!(*track, encode(&decode(track).unwrap()).unwrap());
+        }
+    }
+
+    /// Versions 1 and 3 are only checked against the encoder, as no tracks
+    /// encoded by Lavalink in these versions are available as fixtures.
+    #[test]
+    fn test_roundtrip_versions() {
+        let mut info = decode(YOUTUBE).unwrap();
+        info.uri = None;
+        info.version = 1;
+        assert_eq!(info, decode(&encode(&info).unwrap()).unwrap());
+
+        let mut info = decode(YOUTUBE).unwrap();
+        info.artwork_url = Some("https://i.ytimg.com/vi/dQw4w9WgXcQ/maxresdefault.jpg".to_owned());
+        info.position = 30_000;
+        info.version = 3;
+        assert_eq!(info, decode(&encode(&info).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_modified_utf8() {
+        let mut info = decode(YOUTUBE).unwrap();
+        info.title = "caf\u{e9} \u{0} \u{1f600}".to_owned();
+
+        let track = encode(&info).unwrap();
+        assert_eq!(info, decode(&track).unwrap());
+
+        // The null character is encoded with two bytes, and characters
+        // outside of the basic multilingual plane as two surrogates of three
+        // bytes each.
+        let bytes = base64::decode(&track).unwrap();
+        let title = b"\0\x0fcaf\xc3\xa9 \xc0\x80 \xed\xa0\xbd\xed\xb8\x80";
+        assert!(bytes.windows(title.len()).any(|window| window == title));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            decode("not base64!"),
+            Err(DecodeError::Base64 { .. })
+        ));
+
+        let mut bytes = base64::decode(YOUTUBE).unwrap();
+        bytes.truncate(bytes.len() - 4);
+        assert!(matches!(
+            decode(&base64::encode(&bytes)),
+            Err(DecodeError::UnexpectedEnd)
+        ));
+
+        let mut bytes = base64::decode(YOUTUBE).unwrap();
+        bytes[4] = 4;
+        assert!(matches!(
+            decode(&base64::encode(&bytes)),
+            Err(DecodeError::UnsupportedVersion { version: 4 })
+        ));
+
+        // The first byte of the title is a lone continuation byte.
+        let mut bytes = base64::decode(YOUTUBE).unwrap();
+        bytes[7] = 0x80;
+        assert!(matches!(
+            decode(&base64::encode(&bytes)),
+            Err(DecodeError::InvalidString)
+        ));
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let mut info = decode(YOUTUBE).unwrap();
+        info.title = "a".repeat(65_536);
+        assert!(matches!(
+            encode(&info),
+            Err(EncodeError::StringTooLong { len: 65_536 })
+        ));
+
+        let mut info = decode(YOUTUBE).unwrap();
+        info.version = 0;
+        assert!(matches!(
+            encode(&info),
+            Err(EncodeError::UnsupportedVersion { version: 0 })
+        ));
+    }
+}
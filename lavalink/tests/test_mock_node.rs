@@ -0,0 +1,757 @@
+use async_tungstenite::{
+    tokio::{accept_hdr_async, TokioAdapter},
+    tungstenite::{
+        handshake::server::{Request, Response},
+        http::HeaderValue,
+        Message,
+    },
+    WebSocketStream,
+};
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::{net::SocketAddr, time::Duration};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+use twilight_lavalink::{
+    model::{Destroy, IncomingEvent, Play, SlimVoiceServerUpdate, Stop, VoiceUpdate},
+    node::{Backoff, Node, NodeConfig, Resume},
+    player::{PlayerError, PlayerManager},
+    queue::LoopMode,
+    Lavalink,
+};
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{VoiceServerUpdate, VoiceStateUpdate},
+    },
+    id::{ChannelId, GuildId, UserId},
+    voice::VoiceState,
+};
+
+/// Track of YouTube with a length of 212 seconds.
+const TRACK: &str = "QAAAjQIAJVJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAADlJpY2tBc3RsZXlWRVZPAAAAAAADPCAAC2RRdzR3OVdnWGNRAAEAK2h0dHBzOi8vd3d3LnlvdXR1YmUuY29tL3dhdGNoP3Y9ZFF3NHc5V2dYY1EAB3lvdXR1YmUAAAAAAAAAAA==";
+
+/// Mock node accepting a single connection.
+struct MockNode {
+    address: SocketAddr,
+    listener: TcpListener,
+}
+
+impl MockNode {
+    async fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        Self {
+            address: listener.local_addr().unwrap(),
+            listener,
+        }
+    }
+
+    /// Accept a connection, reporting whether its session was resumed.
+    ///
+    /// Returns the connection and the resume key the client sent, if any.
+    async fn accept(
+        &self,
+        resumed: bool,
+    ) -> (WebSocketStream<TokioAdapter<TcpStream>>, Option<String>) {
+        let (stream, _) = self.listener.accept().await.unwrap();
+        let mut key = None;
+
+        // The error response is defined by tungstenite.
+        #[allow(clippy::result_large_err)]
+        let connection = accept_hdr_async(stream, |req: &Request, mut res: Response| {
+            key = req
+                .headers()
+                .get("Resume-Key")
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+            res.headers_mut().insert(
+                "Session-Resumed",
+                HeaderValue::from_static(if resumed { "true" } else { "false" }),
+            );
+
+            Ok(res)
+        })
+        .await
+        .unwrap();
+
+        (connection, key)
+    }
+}
+
+/// Receive the next JSON payload sent by the client.
+async fn next_payload(connection: &mut WebSocketStream<TokioAdapter<TcpStream>>) -> Value {
+    loop {
+        let message = timeout(Duration::from_secs(5), connection.next())
+            .await
+            .expect("no payload received")
+            .expect("connection closed")
+            .unwrap();
+
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+/// Send a track end event for the player of guild 2.
+async fn track_end(connection: &mut WebSocketStream<TokioAdapter<TcpStream>>, reason: &str) {
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "event",
+                "reason": reason,
+                "track": "track",
+                "type": "TrackEndEvent",
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+}
+
+fn play(track: &str) -> Value {
+    json!({
+        "guildId": "2",
+        "noReplace": false,
+        "op": "play",
+        "track": track,
+    })
+}
+
+/// Create a voice state update of the bot in guild 2.
+fn voice_state(channel_id: Option<u64>, session_id: &str, self_mute: bool) -> Event {
+    Event::VoiceStateUpdate(Box::new(VoiceStateUpdate(VoiceState {
+        channel_id: channel_id.map(ChannelId),
+        deaf: false,
+        guild_id: Some(GuildId(2)),
+        member: None,
+        mute: false,
+        self_deaf: false,
+        self_mute,
+        self_stream: false,
+        session_id: session_id.to_owned(),
+        suppress: false,
+        token: None,
+        user_id: UserId(1),
+    })))
+}
+
+/// Create a voice server update of guild 2.
+fn voice_server(endpoint: &str, token: &str) -> Event {
+    Event::VoiceServerUpdate(VoiceServerUpdate {
+        channel_id: None,
+        endpoint: Some(endpoint.to_owned()),
+        guild_id: Some(GuildId(2)),
+        token: token.to_owned(),
+    })
+}
+
+fn voice_update(session_id: &str, endpoint: &str, token: &str) -> Value {
+    json!({
+        "event": {
+            "endpoint": endpoint,
+            "guild_id": "2",
+            "token": token,
+        },
+        "guildId": "2",
+        "op": "voiceUpdate",
+        "sessionId": session_id,
+    })
+}
+
+fn config(address: SocketAddr, resume: impl Into<Option<Resume>>) -> NodeConfig {
+    let mut config = NodeConfig::new(UserId(1), 1, address, "password", resume);
+    config.backoff = Backoff::new(Duration::from_millis(10), Duration::from_millis(10), 10);
+
+    config
+}
+
+/// Receive the next event of a node.
+async fn next_event(events: &mut UnboundedReceiver<IncomingEvent>) -> IncomingEvent {
+    timeout(Duration::from_secs(5), events.next())
+        .await
+        .expect("no event received")
+        .expect("events ended")
+}
+
+#[tokio::test]
+async fn test_configure_resuming() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, Resume::with_key("key", 30));
+
+    let (node, (mut connection, key)) = tokio::join!(
+        Node::connect(config, PlayerManager::default()),
+        mock.accept(false),
+    );
+    let (node, _events) = node.unwrap();
+
+    assert_eq!(Some("key"), key.as_deref());
+    assert!(!node.resumed());
+    assert_eq!(
+        json!({
+            "key": "key",
+            "op": "configureResuming",
+            "timeout": 30,
+        }),
+        next_payload(&mut connection).await,
+    );
+}
+
+#[tokio::test]
+async fn test_resumed_session() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, Resume::new(30));
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, key)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(true),);
+    let (node, mut events) = node.unwrap();
+
+    // The resume key defaults to the address of the node.
+    assert_eq!(Some(mock.address.to_string()), key);
+    assert!(node.resumed());
+
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "playerUpdate",
+                "state": {
+                    "position": 1000,
+                    "time": 1500,
+                },
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let event = timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(event, IncomingEvent::PlayerUpdate(_)));
+
+    // The player of the resumed session is added to the player manager.
+    let player = players.get(&GuildId(2)).expect("player not added");
+    assert_eq!(1000, player.position());
+    drop(player);
+
+    // Resumed sessions aren't configured for resuming again, so the first
+    // payload received is the one sent through the node.
+    node.send(Destroy::new(GuildId(2)).into()).unwrap();
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "destroy",
+        }),
+        next_payload(&mut connection).await,
+    );
+}
+
+#[tokio::test]
+async fn test_queue() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, None);
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    drop(players.get_or_insert(GuildId(2), node));
+    let player = players.get(&GuildId(2)).unwrap();
+
+    player.set_loop_mode(LoopMode::Queue);
+    player.enqueue("a").unwrap();
+    player.enqueue("b").unwrap();
+    assert_eq!(play("a"), next_payload(&mut connection).await);
+    drop(player);
+
+    // Replaced tracks don't advance the queue, otherwise "b" would be played
+    // before "a" is played again.
+    for reason in ["REPLACED", "FINISHED", "FINISHED"].iter() {
+        track_end(&mut connection, reason).await;
+        timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    assert_eq!(play("b"), next_payload(&mut connection).await);
+    assert_eq!(play("a"), next_payload(&mut connection).await);
+
+    // Stopped tracks leave nothing playing, and skipping plays the next track.
+    track_end(&mut connection, "STOPPED").await;
+    timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    players.get(&GuildId(2)).unwrap().skip().unwrap();
+    assert_eq!(play("b"), next_payload(&mut connection).await);
+}
+
+#[tokio::test]
+async fn test_player_commands() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, None);
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    drop(players.get_or_insert(GuildId(2), node));
+    let player = players.get(&GuildId(2)).unwrap();
+
+    assert!(matches!(
+        player.set_volume(1001),
+        Err(PlayerError::VolumeOutOfRange { volume: 1001 })
+    ));
+    assert_eq!(100, player.volume_ref());
+    player.set_volume(50).unwrap();
+    assert_eq!(50, player.volume_ref());
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "volume",
+            "volume": 50,
+        }),
+        next_payload(&mut connection).await,
+    );
+
+    player.set_pause(true).unwrap();
+    assert!(player.paused());
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "pause",
+            "pause": true,
+        }),
+        next_payload(&mut connection).await,
+    );
+
+    // The length of the track is known once it's played.
+    assert_eq!(None, player.track_length());
+    player.send(Play::from((GuildId(2), TRACK))).unwrap();
+    next_payload(&mut connection).await;
+    assert_eq!(Some(Duration::from_secs(212)), player.track_length());
+
+    assert!(matches!(
+        player.seek(Duration::from_secs(213)),
+        Err(PlayerError::SeekPastEnd { .. })
+    ));
+    player.seek(Duration::from_secs(100)).unwrap();
+    assert_eq!(100_000, player.position());
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "seek",
+            "position": 100_000,
+        }),
+        next_payload(&mut connection).await,
+    );
+    drop(player);
+
+    // Player updates replace the position of the last seek.
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "playerUpdate",
+                "state": {
+                    "position": 101_000,
+                    "time": 1500,
+                },
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+    timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(101_000, players.get(&GuildId(2)).unwrap().position());
+}
+
+#[tokio::test]
+async fn test_player_events() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, None);
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    let mut first = players.get_or_insert(GuildId(1), node.clone()).events();
+    let mut second = players.get_or_insert(GuildId(2), node).events();
+
+    let payloads = [
+        json!({
+            "guildId": "1",
+            "op": "event",
+            "track": "one",
+            "type": "TrackStartEvent",
+        }),
+        json!({
+            "guildId": "2",
+            "op": "event",
+            "track": "two",
+            "type": "TrackStartEvent",
+        }),
+        json!({
+            "cpu": { "cores": 4, "lavalinkLoad": 0.1, "systemLoad": 0.1 },
+            "memory": { "allocated": 0, "free": 0, "reservable": 0, "used": 0 },
+            "op": "stats",
+            "players": 2,
+            "playingPlayers": 2,
+            "uptime": 1000,
+        }),
+        json!({
+            "guildId": "1",
+            "op": "event",
+            "reason": "FINISHED",
+            "track": "one",
+            "type": "TrackEndEvent",
+        }),
+    ];
+
+    for payload in payloads.iter() {
+        connection
+            .send(Message::Text(payload.to_string()))
+            .await
+            .unwrap();
+    }
+
+    // Every event is received by the node's stream.
+    for _ in 0..payloads.len() {
+        timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    match first.next().await {
+        Some(IncomingEvent::TrackStart(start)) => assert_eq!("one", start.track),
+        other => panic!("not the first track start: {:?}", other),
+    }
+    match first.next().await {
+        Some(IncomingEvent::TrackEnd(end)) => assert_eq!("one", end.track),
+        other => panic!("not the first track end: {:?}", other),
+    }
+    match second.next().await {
+        Some(IncomingEvent::TrackStart(start)) => assert_eq!("two", start.track),
+        other => panic!("not the second track start: {:?}", other),
+    }
+
+    // Destroying a player ends its stream, but not the streams of the other
+    // players.
+    players
+        .get(&GuildId(1))
+        .unwrap()
+        .send(Destroy::new(GuildId(1)))
+        .unwrap();
+    assert!(first.next().await.is_none());
+
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "event",
+                "code": 4014,
+                "reason": "Disconnected.",
+                "byRemote": true,
+                "type": "WebSocketClosedEvent",
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+
+    match timeout(Duration::from_secs(5), second.next())
+        .await
+        .unwrap()
+    {
+        Some(IncomingEvent::WebSocketClosed(closed)) => assert_eq!(4014, closed.code),
+        other => panic!("not the second websocket close: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_voice_updates() {
+    let mock = MockNode::bind().await;
+    let lavalink = Lavalink::new(UserId(1), 1);
+
+    let (node, (mut connection, _)) =
+        tokio::join!(lavalink.add(mock.address, "password"), mock.accept(false),);
+    node.unwrap();
+
+    // Nothing is sent until both halves are known.
+    lavalink
+        .process(&voice_state(Some(3), "a", false))
+        .await
+        .unwrap();
+    assert!(lavalink.players().get(&GuildId(2)).is_none());
+    lavalink
+        .process(&voice_server("us-east.discord.media", "token"))
+        .await
+        .unwrap();
+    assert_eq!(
+        voice_update("a", "us-east.discord.media", "token"),
+        next_payload(&mut connection).await,
+    );
+
+    // Moving to another channel sends the voice update again.
+    lavalink
+        .process(&voice_state(Some(4), "a", false))
+        .await
+        .unwrap();
+    assert_eq!(
+        voice_update("a", "us-east.discord.media", "token"),
+        next_payload(&mut connection).await,
+    );
+
+    // Muting doesn't change the voice connection, so the next payload is the
+    // one sent through the player.
+    lavalink
+        .process(&voice_state(Some(4), "a", true))
+        .await
+        .unwrap();
+    lavalink
+        .players()
+        .get(&GuildId(2))
+        .unwrap()
+        .send(Stop::from(GuildId(2)))
+        .unwrap();
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "stop",
+        }),
+        next_payload(&mut connection).await,
+    );
+
+    // Changing the voice region allocates another voice server.
+    lavalink
+        .process(&voice_server("eu-west.discord.media", "other"))
+        .await
+        .unwrap();
+    assert_eq!(
+        voice_update("a", "eu-west.discord.media", "other"),
+        next_payload(&mut connection).await,
+    );
+
+    // Disconnecting destroys the player and forgets the voice connection, so
+    // reconnecting waits for both halves again.
+    lavalink
+        .process(&voice_state(None, "a", false))
+        .await
+        .unwrap();
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "destroy",
+        }),
+        next_payload(&mut connection).await,
+    );
+    lavalink
+        .process(&voice_state(Some(3), "b", false))
+        .await
+        .unwrap();
+    lavalink
+        .process(&voice_server("us-east.discord.media", "token"))
+        .await
+        .unwrap();
+    assert_eq!(
+        voice_update("b", "us-east.discord.media", "token"),
+        next_payload(&mut connection).await,
+    );
+}
+
+#[tokio::test]
+async fn test_reconnect() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, None);
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    let player = players.get_or_insert(GuildId(2), node);
+
+    let server = SlimVoiceServerUpdate::from(VoiceServerUpdate {
+        channel_id: None,
+        endpoint: Some("us-east.discord.media".to_owned()),
+        guild_id: Some(GuildId(2)),
+        token: "token".to_owned(),
+    });
+    player
+        .send(VoiceUpdate::new(GuildId(2), "a", server))
+        .unwrap();
+    player
+        .send(Play::new(GuildId(2), TRACK, None, None, false))
+        .unwrap();
+    player.set_pause(true).unwrap();
+    drop(player);
+
+    for _ in 0..3 {
+        next_payload(&mut connection).await;
+    }
+
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "playerUpdate",
+                "state": {
+                    "position": 5000,
+                    "time": 1500,
+                },
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_event(&mut events).await,
+        IncomingEvent::PlayerUpdate(_)
+    ));
+
+    // The node reconnects after the connection drops.
+    drop(connection);
+    match next_event(&mut events).await {
+        IncomingEvent::NodeDisconnected(event) => assert_eq!(mock.address, event.address),
+        other => panic!("not a disconnect: {:?}", other),
+    }
+
+    // The session didn't resume, so the player is restored, playing its track
+    // from the last known position.
+    let (mut connection, _) = mock.accept(false).await;
+    assert_eq!(
+        voice_update("a", "us-east.discord.media", "token"),
+        next_payload(&mut connection).await,
+    );
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "noReplace": false,
+            "op": "play",
+            "startTime": 5000,
+            "track": TRACK,
+        }),
+        next_payload(&mut connection).await,
+    );
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "pause",
+            "pause": true,
+        }),
+        next_payload(&mut connection).await,
+    );
+    match next_event(&mut events).await {
+        IncomingEvent::NodeReconnected(event) => {
+            assert_eq!(mock.address, event.address);
+            assert!(!event.resumed);
+        }
+        other => panic!("not a reconnect: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_reconnect_resumed() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, Resume::new(30));
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    players
+        .get_or_insert(GuildId(2), node.clone())
+        .send(Play::new(GuildId(2), TRACK, None, None, false))
+        .unwrap();
+
+    // Resuming is configured, then the track is played.
+    next_payload(&mut connection).await;
+    next_payload(&mut connection).await;
+
+    drop(connection);
+    assert!(matches!(
+        next_event(&mut events).await,
+        IncomingEvent::NodeDisconnected(_)
+    ));
+
+    // The session resumed, so the node kept the player and it isn't restored.
+    let (mut connection, _) = mock.accept(true).await;
+    match next_event(&mut events).await {
+        IncomingEvent::NodeReconnected(event) => {
+            assert_eq!(mock.address, event.address);
+            assert!(event.resumed);
+        }
+        other => panic!("not a reconnect: {:?}", other),
+    }
+    assert!(node.resumed());
+
+    node.send(Destroy::new(GuildId(2)).into()).unwrap();
+    assert_eq!(
+        json!({
+            "guildId": "2",
+            "op": "destroy",
+        }),
+        next_payload(&mut connection).await,
+    );
+}
+
+#[tokio::test]
+async fn test_plugin_ops() {
+    let mock = MockNode::bind().await;
+    let config = config(mock.address, None);
+    let players = PlayerManager::default();
+
+    let (node, (mut connection, _)) =
+        tokio::join!(Node::connect(config, players.clone()), mock.accept(false),);
+    let (node, mut events) = node.unwrap();
+    let mut player_events = players.get_or_insert(GuildId(2), node.clone()).events();
+
+    let payload = json!({
+        "categories": ["sponsor"],
+        "guildId": "2",
+        "op": "segmentsToSkip",
+    });
+    node.send_raw(payload.clone()).unwrap();
+    assert_eq!(payload, next_payload(&mut connection).await);
+
+    // Unknown ops are received by the node's stream and by the player they're
+    // about.
+    connection
+        .send(Message::Text(
+            json!({
+                "guildId": "2",
+                "op": "segmentsLoaded",
+                "segments": [],
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+
+    for event in [
+        next_event(&mut events).await,
+        next_event(&mut player_events).await,
+    ]
+    .iter()
+    {
+        match event {
+            IncomingEvent::Unknown { op, data } => {
+                assert_eq!("segmentsLoaded", op);
+                assert_eq!(json!({ "guildId": "2", "segments": [] }), *data);
+            }
+            other => panic!("not an unknown event: {:?}", other),
+        }
+    }
+}
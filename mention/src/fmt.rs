@@ -1,5 +1,6 @@
 //! Formatters for creating mentions.
 
+use crate::timestamp::Timestamp;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use twilight_model::{
     channel::{
@@ -46,6 +47,17 @@ impl Display for MentionFormat<RoleId> {
     }
 }
 
+/// Mention a timestamp. This will format as `<t:UNIX>` without a style or
+/// as `<t:UNIX:STYLE>` with one.
+impl Display for MentionFormat<Timestamp> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.0.style() {
+            Some(style) => f.write_fmt(format_args!("<t:{}:{}>", self.0.unix(), style)),
+            None => f.write_fmt(format_args!("<t:{}>", self.0.unix())),
+        }
+    }
+}
+
 /// Mention a user. This will format as `<@ID>`.
 impl Display for MentionFormat<UserId> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
@@ -256,6 +268,20 @@ impl Mention<ChannelId> for &'_ TextChannel {
     }
 }
 
+/// Mention a timestamp. This will format as `<t:UNIX>` or `<t:UNIX:STYLE>`.
+impl Mention<Timestamp> for Timestamp {
+    fn mention(&self) -> MentionFormat<Timestamp> {
+        MentionFormat(*self)
+    }
+}
+
+/// Mention a timestamp. This will format as `<t:UNIX>` or `<t:UNIX:STYLE>`.
+impl Mention<Timestamp> for &'_ Timestamp {
+    fn mention(&self) -> MentionFormat<Timestamp> {
+        (*self).mention()
+    }
+}
+
 /// Mention a user ID. This will format as `<&ID>`.
 impl Mention<UserId> for UserId {
     fn mention(&self) -> MentionFormat<UserId> {
@@ -301,6 +327,7 @@ impl Mention<ChannelId> for &'_ VoiceChannel {
 #[cfg(test)]
 mod tests {
     use super::{Mention, MentionFormat};
+    use crate::timestamp::{Timestamp, TimestampStyle};
     use static_assertions::assert_impl_all;
     use std::fmt::{Debug, Display};
     use twilight_model::{
@@ -317,6 +344,7 @@ mod tests {
     assert_impl_all!(MentionFormat<ChannelId>: Clone, Copy, Debug, Display, Eq, PartialEq, Send, Sync);
     assert_impl_all!(MentionFormat<EmojiId>: Clone, Copy, Debug, Display, Eq, PartialEq, Send, Sync);
     assert_impl_all!(MentionFormat<RoleId>: Clone, Copy, Debug, Display, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(MentionFormat<Timestamp>: Clone, Copy, Debug, Display, Eq, PartialEq, Send, Sync);
     assert_impl_all!(MentionFormat<UserId>: Clone, Copy, Debug, Display, Eq, PartialEq, Send, Sync);
     assert_impl_all!(ChannelId: Mention<ChannelId>);
     assert_impl_all!(&'static ChannelId: Mention<ChannelId>);
@@ -343,6 +371,8 @@ mod tests {
     assert_impl_all!(Role: Mention<RoleId>);
     assert_impl_all!(&'static Role: Mention<RoleId>);
     assert_impl_all!(TextChannel: Mention<ChannelId>);
+    assert_impl_all!(Timestamp: Mention<Timestamp>);
+    assert_impl_all!(&'static Timestamp: Mention<Timestamp>);
     assert_impl_all!(&'static TextChannel: Mention<ChannelId>);
     assert_impl_all!(UserId: Mention<UserId>);
     assert_impl_all!(&'static UserId: Mention<UserId>);
@@ -366,6 +396,20 @@ mod tests {
         assert_eq!("<@&123>", RoleId(123).mention().to_string());
     }
 
+    #[test]
+    fn test_mention_format_timestamp() {
+        assert_eq!(
+            "<t:1625097600>",
+            Timestamp::new(1_625_097_600, None).mention().to_string()
+        );
+        assert_eq!(
+            "<t:1625097600:R>",
+            Timestamp::new(1_625_097_600, Some(TimestampStyle::RelativeTime))
+                .mention()
+                .to_string()
+        );
+    }
+
     #[test]
     fn test_mention_format_user_id() {
         assert_eq!("<@123>", UserId(123).mention().to_string());
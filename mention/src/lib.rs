@@ -46,6 +46,7 @@
 pub mod fmt;
 
 pub mod parse;
+pub mod timestamp;
 
 #[doc(no_inline)]
 pub use fmt::{Mention, MentionFormat};
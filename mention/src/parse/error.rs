@@ -19,6 +19,15 @@ pub enum ParseMentionError<'a> {
         /// Character that was instead found where the leading arrow should be.
         found: Option<char>,
     },
+    /// One of the parts of the mention is invalid.
+    ///
+    /// For example, IDs with a sign or leading zeros, names of emojis with
+    /// characters other than alphanumerics and underscores, and unknown
+    /// styles of timestamps are invalid.
+    PartInvalid {
+        /// Part of the mention that is invalid.
+        found: &'a str,
+    },
     /// One or more parts of the mention are missing.
     ///
     /// For example, an emoji mention - `<:name:id>` - has two parts: the `name`
@@ -44,6 +53,11 @@ pub enum ParseMentionError<'a> {
         /// Character that was instead found where the trailing arrow should be.
         found: Option<char>,
     },
+    /// Content is present after the trailing arrow (`>`).
+    TrailingContent {
+        /// Content after the trailing arrow.
+        found: &'a str,
+    },
 }
 
 impl Display for ParseMentionError<'_> {
@@ -62,6 +76,9 @@ impl Display for ParseMentionError<'_> {
                     f.write_str("found nothing")
                 }
             }
+            Self::PartInvalid { found } => {
+                f.write_fmt(format_args!("part ('{}') of mention is invalid", found,))
+            }
             Self::PartMissing { expected, found } => f.write_fmt(format_args!(
                 "
                     expected {} parts but only found {}",
@@ -95,6 +112,10 @@ impl Display for ParseMentionError<'_> {
                     f.write_str("found nothing")
                 }
             }
+            Self::TrailingContent { found } => f.write_fmt(format_args!(
+                "expected the mention to end at its trailing arrow ('>') but found '{}' after it",
+                found,
+            )),
         }
     }
 }
@@ -104,9 +125,11 @@ impl Error for ParseMentionError<'_> {
         match self {
             Self::IdNotU64 { source, .. } => Some(source),
             Self::LeadingArrow { .. }
+            | Self::PartInvalid { .. }
             | Self::PartMissing { .. }
             | Self::Sigil { .. }
-            | Self::TrailingArrow { .. } => None,
+            | Self::TrailingArrow { .. }
+            | Self::TrailingContent { .. } => None,
         }
     }
 }
@@ -119,8 +142,10 @@ mod tests {
 
     assert_fields!(ParseMentionError::IdNotU64: found, source);
     assert_fields!(ParseMentionError::LeadingArrow: found);
+    assert_fields!(ParseMentionError::PartInvalid: found);
     assert_fields!(ParseMentionError::Sigil: expected, found);
     assert_fields!(ParseMentionError::TrailingArrow: found);
+    assert_fields!(ParseMentionError::TrailingContent: found);
     assert_impl_all!(ParseMentionError<'_>: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
 
     #[test]
@@ -146,6 +171,12 @@ mod tests {
             ParseMentionError::LeadingArrow { found: None }.to_string(),
         );
 
+        expected = "part ('0123') of mention is invalid";
+        assert_eq!(
+            expected,
+            ParseMentionError::PartInvalid { found: "0123" }.to_string(),
+        );
+
         expected = "expected to find a mention sigil ('@') but instead found '#'";
         assert_eq!(
             expected,
@@ -197,5 +228,11 @@ mod tests {
             expected,
             ParseMentionError::TrailingArrow { found: None }.to_string(),
         );
+
+        expected = "expected the mention to end at its trailing arrow ('>') but found 'a' after it";
+        assert_eq!(
+            expected,
+            ParseMentionError::TrailingContent { found: "a" }.to_string(),
+        );
     }
 }
@@ -1,5 +1,5 @@
 use super::{MentionIter, MentionType, ParseMentionError};
-use std::str::Chars;
+use crate::timestamp::{Timestamp, TimestampStyle};
 use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 
 /// Parse mentions out of buffers.
@@ -40,15 +40,66 @@ pub trait ParseMention: private::Sealed {
     /// Returns [`ParseMentionError::LeadingArrow`] if the leading arrow is not
     /// present.
     ///
+    /// Returns [`ParseMentionError::PartInvalid`] if a part of the mention,
+    /// such as the ID, is invalid.
+    ///
     /// Returns [`ParseMentionError::Sigil`] if the mention type's sigil is not
     /// present after the leading arrow.
     ///
     /// Returns [`ParseMentionError::TrailingArrow`] if the trailing arrow is
     /// not present after the ID.
+    ///
+    /// Returns [`ParseMentionError::TrailingContent`] if there is anything
+    /// after the trailing arrow.
     fn parse(buf: &str) -> Result<Self, ParseMentionError<'_>>
     where
         Self: Sized;
 
+    /// Parse a mention or a bare ID out of a buffer.
+    ///
+    /// This is useful for command arguments and configuration files, where
+    /// users may provide IDs rather than mentions. Timestamps may be
+    /// provided as bare Unix timestamps, but [`MentionType`]s must be
+    /// mentions, as the type of a bare ID is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_mention::ParseMention;
+    /// use twilight_model::id::RoleId;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert_eq!(RoleId(123), RoleId::parse_lenient("<@&123>")?);
+    /// assert_eq!(RoleId(123), RoleId::parse_lenient("123")?);
+    /// assert!(RoleId::parse("123").is_err());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseMentionError::IdNotU64`] if the buffer starts with a
+    /// digit but isn't a u64.
+    ///
+    /// Returns [`ParseMentionError::PartInvalid`] if the bare ID has leading
+    /// zeros.
+    ///
+    /// Otherwise returns the same errors as [`parse`].
+    ///
+    /// [`MentionType`]: super::MentionType
+    /// [`parse`]: Self::parse
+    fn parse_lenient(buf: &str) -> Result<Self, ParseMentionError<'_>>
+    where
+        Self: Sized,
+    {
+        if buf.starts_with(|c: char| c.is_ascii_digit()) {
+            if let Some(result) = Self::from_digits(buf) {
+                return result;
+            }
+        }
+
+        Self::parse(buf)
+    }
+
     /// Search a buffer for mentions and parse out any that are encountered.
     ///
     /// Unlike [`parse`], this will not error if anything that is indicative of
@@ -77,7 +128,10 @@ impl ParseMention for ChannelId {
 }
 
 impl ParseMention for EmojiId {
-    const SIGILS: &'static [&'static str] = &[":"];
+    /// Sigils for emoji mentions.
+    ///
+    /// Animated emojis have the sigil `a:`, and other emojis the sigil `:`.
+    const SIGILS: &'static [&'static str] = &[":", "a:"];
 
     fn parse(buf: &str) -> Result<Self, ParseMentionError<'_>>
     where
@@ -91,39 +145,25 @@ impl ParseMention for MentionType {
     /// Sigils for any type of mention.
     ///
     /// Contains all of the sigils of every other type of mention.
-    const SIGILS: &'static [&'static str] = &["#", ":", "@&", "@!", "@"];
+    const SIGILS: &'static [&'static str] = &["#", ":", "a:", "@&", "@!", "@", "t:"];
 
     fn parse(buf: &str) -> Result<Self, ParseMentionError<'_>>
     where
         Self: Sized,
     {
-        let (id, found) = parse_id(buf, Self::SIGILS)?;
-
-        for sigil in ChannelId::SIGILS {
-            if *sigil == found {
-                return Ok(MentionType::Channel(ChannelId(id)));
-            }
-        }
-
-        for sigil in EmojiId::SIGILS {
-            if *sigil == found {
-                return Ok(MentionType::Emoji(EmojiId(id)));
-            }
-        }
-
-        for sigil in RoleId::SIGILS {
-            if *sigil == found {
-                return Ok(MentionType::Role(RoleId(id)));
-            }
-        }
-
-        for sigil in UserId::SIGILS {
-            if *sigil == found {
-                return Ok(MentionType::User(UserId(id)));
-            }
+        let found = sigil(buf, Self::SIGILS)?;
+
+        if ChannelId::SIGILS.contains(&found) {
+            ChannelId::parse(buf).map(MentionType::Channel)
+        } else if EmojiId::SIGILS.contains(&found) {
+            EmojiId::parse(buf).map(MentionType::Emoji)
+        } else if RoleId::SIGILS.contains(&found) {
+            RoleId::parse(buf).map(MentionType::Role)
+        } else if Timestamp::SIGILS.contains(&found) {
+            Timestamp::parse(buf).map(MentionType::Timestamp)
+        } else {
+            UserId::parse(buf).map(MentionType::User)
         }
-
-        unreachable!("mention type must have been found");
     }
 }
 
@@ -152,81 +192,163 @@ impl ParseMention for UserId {
     }
 }
 
+impl ParseMention for Timestamp {
+    const SIGILS: &'static [&'static str] = &["t:"];
+
+    fn parse(buf: &str) -> Result<Self, ParseMentionError<'_>>
+    where
+        Self: Sized,
+    {
+        let found = sigil(buf, Self::SIGILS)?;
+        let rest = &buf[1 + found.len()..];
+        let inner = inner(rest)?;
+
+        let (unix, style) = match inner.find(':') {
+            Some(idx) => (&inner[..idx], Some(&inner[idx + 1..])),
+            None => (inner, None),
+        };
+
+        let unix = parse_digits(unix)?;
+        let style = match style {
+            Some(style) => {
+                let mut chars = style.chars();
+
+                match (
+                    chars.next().and_then(TimestampStyle::from_letter),
+                    chars.next(),
+                ) {
+                    (Some(style), None) => Some(style),
+                    _ => return Err(ParseMentionError::PartInvalid { found: style }),
+                }
+            }
+            None => None,
+        };
+
+        Ok(Timestamp::new(unix, style))
+    }
+}
+
+/// Find the sigil of a mention after its leading arrow.
+///
+/// # Errors
+///
+/// Returns [`ParseMentionError::LeadingArrow`] if the leading arrow is not
+/// present.
+///
+/// Returns [`ParseMentionError::Sigil`] if none of the sigils are present
+/// after the leading arrow.
+fn sigil<'a>(buf: &'a str, sigils: &'a [&'a str]) -> Result<&'a str, ParseMentionError<'a>> {
+    let rest = match buf.strip_prefix('<') {
+        Some(rest) => rest,
+        None => {
+            return Err(ParseMentionError::LeadingArrow {
+                found: buf.chars().next(),
+            })
+        }
+    };
+
+    sigils
+        .iter()
+        .find(|sigil| rest.starts_with(*sigil))
+        .copied()
+        .ok_or_else(|| ParseMentionError::Sigil {
+            expected: sigils,
+            found: rest.chars().next(),
+        })
+}
+
+/// Parse the digits of an ID or timestamp.
+///
+/// Only digits without leading zeros are valid, so that the digits are the
+/// same as those of the ID when it's formatted.
+///
+/// # Errors
+///
+/// Returns [`ParseMentionError::IdNotU64`] if the digits don't make a u64.
+///
+/// Returns [`ParseMentionError::PartInvalid`] if the digits have a sign or
+/// leading zeros.
+fn parse_digits(digits: &str) -> Result<u64, ParseMentionError<'_>> {
+    let id = digits
+        .parse()
+        .map_err(|source| ParseMentionError::IdNotU64 {
+            found: digits,
+            source,
+        })?;
+
+    if digits.starts_with('+') || (digits.len() > 1 && digits.starts_with('0')) {
+        return Err(ParseMentionError::PartInvalid { found: digits });
+    }
+
+    Ok(id)
+}
+
+/// Find the inside of a mention up to its trailing arrow.
+///
+/// # Errors
+///
+/// Returns [`ParseMentionError::TrailingArrow`] if the trailing arrow is not
+/// present.
+///
+/// Returns [`ParseMentionError::TrailingContent`] if there is anything after
+/// the trailing arrow.
+fn inner(rest: &str) -> Result<&str, ParseMentionError<'_>> {
+    let idx = rest
+        .find('>')
+        .ok_or(ParseMentionError::TrailingArrow { found: None })?;
+    let trailing = &rest[idx + 1..];
+
+    if !trailing.is_empty() {
+        return Err(ParseMentionError::TrailingContent { found: trailing });
+    }
+
+    Ok(&rest[..idx])
+}
+
 /// # Errors
 ///
 /// Returns [`ParseMentionError::LeadingArrow`] if the leading arrow is not
 /// present.
 ///
+/// Returns [`ParseMentionError::PartInvalid`] if the ID or the name of an
+/// emoji is invalid.
+///
+/// Returns [`ParseMentionError::PartMissing`] if the name of an emoji isn't
+/// followed by a colon.
+///
 /// Returns [`ParseMentionError::Sigil`] if the mention type's sigil is not
 /// present after the leading arrow.
 ///
 /// Returns [`ParseMentionError::TrailingArrow`] if the trailing arrow is not
 /// present after the ID.
+///
+/// Returns [`ParseMentionError::TrailingContent`] if there is anything after
+/// the trailing arrow.
 fn parse_id<'a>(
     buf: &'a str,
     sigils: &'a [&'a str],
 ) -> Result<(u64, &'a str), ParseMentionError<'a>> {
-    let mut chars = buf.chars();
-
-    let c = chars.next();
-
-    if c.map_or(true, |c| c != '<') {
-        return Err(ParseMentionError::LeadingArrow { found: c });
-    }
-
-    let maybe_sigil = sigils.iter().find(|sigil| {
-        if chars.as_str().starts_with(*sigil) {
-            for _ in 0..sigil.chars().count() {
-                chars.next();
-            }
-
-            return true;
-        }
+    let sigil = sigil(buf, sigils)?;
+    let mut rest = &buf[1 + sigil.len()..];
 
-        false
-    });
-
-    let sigil = if let Some(sigil) = maybe_sigil {
-        *sigil
-    } else {
-        return Err(ParseMentionError::Sigil {
-            expected: sigils,
-            found: chars.next(),
-        });
-    };
-
-    if sigil == ":" && !emoji_sigil_present(&mut chars) {
-        return Err(ParseMentionError::PartMissing {
+    // Emojis have a name between their sigil and their ID.
+    if sigil.ends_with(':') {
+        let idx = rest.find(':').ok_or(ParseMentionError::PartMissing {
             found: 1,
             expected: 2,
-        });
-    }
+        })?;
+        let name = &rest[..idx];
 
-    let remaining = chars
-        .as_str()
-        .find('>')
-        .and_then(|idx| chars.as_str().get(..idx))
-        .ok_or(ParseMentionError::TrailingArrow { found: None })?;
-
-    remaining
-        .parse()
-        .map(|id| (id, sigil))
-        .map_err(|source| ParseMentionError::IdNotU64 {
-            found: remaining,
-            source,
-        })
-}
-
-// Don't use `Iterator::skip_while` so we can mutate `chars` in-place;
-// `skip_while` is consuming.
-fn emoji_sigil_present(chars: &mut Chars<'_>) -> bool {
-    for c in chars {
-        if c == ':' {
-            return true;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(ParseMentionError::PartInvalid { found: name });
         }
+
+        rest = &rest[idx + 1..];
     }
 
-    false
+    let remaining = inner(rest)?;
+
+    parse_digits(remaining).map(|id| (id, sigil))
 }
 
 /// Rust doesn't allow leaking private implementations, but if we make the trait
@@ -237,16 +359,45 @@ fn emoji_sigil_present(chars: &mut Chars<'_>) -> bool {
 ///
 /// <https://rust-lang.github.io/api-guidelines/future-proofing.html>
 mod private {
-    use super::super::MentionType;
+    use super::super::{MentionType, ParseMentionError};
+    use crate::timestamp::Timestamp;
     use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 
-    pub trait Sealed {}
+    pub trait Sealed {
+        /// Parse a bare ID, if the type can be created from one.
+        fn from_digits(digits: &str) -> Option<Result<Self, ParseMentionError<'_>>>
+        where
+            Self: Sized;
+    }
+
+    /// Implement [`Sealed`] for ID newtypes wrapping a `u64`.
+    macro_rules! impl_sealed {
+        ($($id:ident),* $(,)?) => {
+            $(
+                impl Sealed for $id {
+                    fn from_digits(
+                        digits: &str,
+                    ) -> Option<Result<Self, ParseMentionError<'_>>> {
+                        Some(super::parse_digits(digits).map($id))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_sealed!(ChannelId, EmojiId, RoleId, UserId);
+
+    impl Sealed for MentionType {
+        fn from_digits(_: &str) -> Option<Result<Self, ParseMentionError<'_>>> {
+            None
+        }
+    }
 
-    impl Sealed for ChannelId {}
-    impl Sealed for EmojiId {}
-    impl Sealed for MentionType {}
-    impl Sealed for RoleId {}
-    impl Sealed for UserId {}
+    impl Sealed for Timestamp {
+        fn from_digits(digits: &str) -> Option<Result<Self, ParseMentionError<'_>>> {
+            Some(super::parse_digits(digits).map(|unix| Timestamp::new(unix, None)))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -256,6 +407,7 @@ mod tests {
         private::Sealed,
         ParseMention,
     };
+    use crate::timestamp::{Timestamp, TimestampStyle};
     use static_assertions::assert_impl_all;
     use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 
@@ -263,14 +415,19 @@ mod tests {
     assert_impl_all!(EmojiId: ParseMention, Sealed);
     assert_impl_all!(MentionType: ParseMention, Sealed);
     assert_impl_all!(RoleId: ParseMention, Sealed);
+    assert_impl_all!(Timestamp: ParseMention, Sealed);
     assert_impl_all!(UserId: ParseMention, Sealed);
 
     #[test]
     fn test_sigils() {
         assert_eq!(&["#"], ChannelId::SIGILS);
-        assert_eq!(&[":"], EmojiId::SIGILS);
-        assert_eq!(&["#", ":", "@&", "@!", "@"], MentionType::SIGILS);
+        assert_eq!(&[":", "a:"], EmojiId::SIGILS);
+        assert_eq!(
+            &["#", ":", "a:", "@&", "@!", "@", "t:"],
+            MentionType::SIGILS
+        );
         assert_eq!(&["@&"], RoleId::SIGILS);
+        assert_eq!(&["t:"], Timestamp::SIGILS);
         assert_eq!(&["@!", "@"], UserId::SIGILS);
     }
 
@@ -289,13 +446,80 @@ mod tests {
     #[test]
     fn test_parse_emoji_id() {
         assert_eq!(EmojiId(123), EmojiId::parse("<:name:123>").unwrap());
+        assert_eq!(EmojiId(123), EmojiId::parse("<a:name_2:123>").unwrap());
         assert_eq!(
             ParseMentionError::Sigil {
-                expected: &[":"],
+                expected: &[":", "a:"],
                 found: Some('@'),
             },
             EmojiId::parse("<@123>").unwrap_err(),
         );
+        assert_eq!(
+            ParseMentionError::PartMissing {
+                expected: 2,
+                found: 1,
+            },
+            EmojiId::parse("<:name>").unwrap_err(),
+        );
+
+        for name in &["", "a name", "<@1>"] {
+            let buf = format!("<:{}:123>", name);
+            assert_eq!(
+                ParseMentionError::PartInvalid { found: name },
+                EmojiId::parse(&buf).unwrap_err(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(
+            Timestamp::new(1_624_047_064, None),
+            Timestamp::parse("<t:1624047064>").unwrap()
+        );
+        assert_eq!(
+            Timestamp::new(1_624_047_064, Some(TimestampStyle::RelativeTime)),
+            Timestamp::parse("<t:1624047064:R>").unwrap()
+        );
+
+        for style in &["", "r", "RR", "R:"] {
+            let buf = format!("<t:1:{}>", style);
+            assert_eq!(
+                ParseMentionError::PartInvalid { found: style },
+                Timestamp::parse(&buf).unwrap_err(),
+            );
+        }
+
+        assert_eq!(
+            ParseMentionError::TrailingArrow { found: None },
+            Timestamp::parse("<t:1:R").unwrap_err(),
+        );
+        assert!(matches!(
+            Timestamp::parse("<t:-1>"),
+            Err(ParseMentionError::IdNotU64 { found: "-1", .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_id_digits() {
+        assert_eq!(UserId(0), UserId::parse("<@0>").unwrap());
+
+        for digits in &["0123", "00", "+123"] {
+            let buf = format!("<@{}>", digits);
+            assert_eq!(
+                ParseMentionError::PartInvalid { found: digits },
+                UserId::parse(&buf).unwrap_err(),
+            );
+        }
+
+        assert!(matches!(
+            ChannelId::parse("<#>"),
+            Err(ParseMentionError::IdNotU64 { found: "", .. })
+        ));
+        assert!(matches!(
+            ChannelId::parse("<#18446744073709551616>"),
+            Err(ParseMentionError::IdNotU64 { .. })
+        ));
     }
 
     #[test]
@@ -308,6 +532,14 @@ mod tests {
             MentionType::Emoji(EmojiId(123)),
             MentionType::parse("<:name:123>").unwrap()
         );
+        assert_eq!(
+            MentionType::Emoji(EmojiId(123)),
+            MentionType::parse("<a:name:123>").unwrap()
+        );
+        assert_eq!(
+            MentionType::Timestamp(Timestamp::new(123, Some(TimestampStyle::ShortDate))),
+            MentionType::parse("<t:123:d>").unwrap()
+        );
         assert_eq!(
             MentionType::Role(RoleId(123)),
             MentionType::parse("<@&123>").unwrap()
@@ -318,7 +550,7 @@ mod tests {
         );
         assert_eq!(
             ParseMentionError::Sigil {
-                expected: &["#", ":", "@&", "@!", "@"],
+                expected: &["#", ":", "a:", "@&", "@!", "@", "t:"],
                 found: Some(';'),
             },
             MentionType::parse("<;123>").unwrap_err(),
@@ -349,6 +581,123 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_exact() {
+        // Each type only parses its own mentions.
+        let mentions = ["<#1>", "<:name:1>", "<@&1>", "<@!1>", "<@1>"];
+        let table: &[(&str, [bool; 5])] = &[
+            ("channel", [true, false, false, false, false]),
+            ("emoji", [false, true, false, false, false]),
+            ("role", [false, false, true, false, false]),
+            ("user", [false, false, false, true, true]),
+        ];
+
+        for (kind, parses) in table {
+            for (mention, parses) in mentions.iter().zip(parses.iter()) {
+                let parsed = match *kind {
+                    "channel" => ChannelId::parse(mention).is_ok(),
+                    "emoji" => EmojiId::parse(mention).is_ok(),
+                    "role" => RoleId::parse(mention).is_ok(),
+                    _ => UserId::parse(mention).is_ok(),
+                };
+
+                assert_eq!(*parses, parsed, "{} parsing {}", kind, mention);
+            }
+        }
+
+        assert_eq!(
+            ParseMentionError::Sigil {
+                expected: &["@!", "@"],
+                found: Some('#'),
+            },
+            UserId::parse("<#1>").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::Sigil {
+                expected: &["@&"],
+                found: Some('@'),
+            },
+            RoleId::parse("<@!1>").unwrap_err(),
+        );
+        assert!(matches!(
+            UserId::parse("<@!>"),
+            Err(ParseMentionError::IdNotU64 { found: "", .. })
+        ));
+        assert!(matches!(
+            UserId::parse("<@!abc>"),
+            Err(ParseMentionError::IdNotU64 { found: "abc", .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_trailing_content() {
+        assert_eq!(
+            ParseMentionError::TrailingContent { found: " " },
+            UserId::parse("<@!123> ").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::TrailingContent { found: "<@456>" },
+            UserId::parse("<@123><@456>").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::TrailingContent { found: ">" },
+            ChannelId::parse("<#123>>").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::TrailingContent { found: "abc" },
+            EmojiId::parse("<:name:123>abc").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::TrailingContent { found: "." },
+            Timestamp::parse("<t:123:R>.").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::LeadingArrow { found: Some(' ') },
+            RoleId::parse(" <@&123>").unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        assert_eq!(ChannelId(123), ChannelId::parse_lenient("123").unwrap());
+        assert_eq!(ChannelId(123), ChannelId::parse_lenient("<#123>").unwrap());
+        assert_eq!(EmojiId(123), EmojiId::parse_lenient("123").unwrap());
+        assert_eq!(RoleId(123), RoleId::parse_lenient("123").unwrap());
+        assert_eq!(UserId(123), UserId::parse_lenient("123").unwrap());
+        assert_eq!(UserId(123), UserId::parse_lenient("<@!123>").unwrap());
+        assert_eq!(
+            Timestamp::new(123, None),
+            Timestamp::parse_lenient("123").unwrap()
+        );
+
+        assert!(matches!(
+            UserId::parse_lenient("123abc"),
+            Err(ParseMentionError::IdNotU64 {
+                found: "123abc",
+                ..
+            })
+        ));
+        assert_eq!(
+            ParseMentionError::PartInvalid { found: "0123" },
+            UserId::parse_lenient("0123").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::LeadingArrow { found: Some('1') },
+            MentionType::parse_lenient("123").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::Sigil {
+                expected: &["@&"],
+                found: Some('@'),
+            },
+            RoleId::parse_lenient("<@123>").unwrap_err(),
+        );
+        assert_eq!(
+            ParseMentionError::LeadingArrow { found: Some('a') },
+            UserId::parse_lenient("abc").unwrap_err(),
+        );
+    }
+
     #[test]
     fn test_parse_id_wrong_sigil() {
         assert_eq!(
@@ -1,5 +1,5 @@
 use super::ParseMention;
-use std::{iter::Iterator, marker::PhantomData, str::CharIndices};
+use std::{iter::Iterator, marker::PhantomData};
 
 /// Iterator of mentions within a buffer.
 ///
@@ -11,6 +11,9 @@ use std::{iter::Iterator, marker::PhantomData, str::CharIndices};
 /// followed by the starting index and ending index of the mention's source in
 /// the buffer.
 ///
+/// A `<` that doesn't start a valid mention is skipped over, so an unclosed
+/// or invalid mention doesn't hide the mentions after it.
+///
 /// # Examples
 ///
 /// Iterate over all of the mentioned users:
@@ -28,18 +31,19 @@ use std::{iter::Iterator, marker::PhantomData, str::CharIndices};
 #[derive(Clone, Debug)]
 pub struct MentionIter<'a, T> {
     buf: &'a str,
-    chars: CharIndices<'a>,
+    idx: usize,
     phantom: PhantomData<T>,
 }
 
 impl<'a, T> MentionIter<'a, T> {
-    #[must_use]
-    pub(in crate::parse) fn new(buf: &'a str) -> Self {
-        let chars = buf.char_indices();
-
+    /// Create an iterator of the mentions within a buffer.
+    ///
+    /// This is the same as [`ParseMention::iter`].
+    #[must_use = "you must use the iterator to lazily parse mentions"]
+    pub const fn new(buf: &'a str) -> Self {
         Self {
             buf,
-            chars,
+            idx: 0,
             phantom: PhantomData,
         }
     }
@@ -59,40 +63,31 @@ impl<'a, T: ParseMention + std::fmt::Debug> Iterator for MentionIter<'a, T> {
     type Item = (T, usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
-        // We want to take care of our input and make sure we're working with
-        // chars here and not just individual bytes. We also want to not use
-        // consuming methods of the iterator, so this will get a little weird.
         loop {
-            let start = match self.chars.next()? {
-                (idx, '<') => idx,
-                _ => continue,
-            };
+            let rest = self.buf.get(self.idx..)?;
+            let start = self.idx + rest.find('<')?;
 
-            let mut found = false;
+            // A mention ends at the first `>` after its start, unless another
+            // `<` is found first, in which case there can only be a mention
+            // starting at the latter.
+            let after = &self.buf[start + 1..];
+            let end = match after.find(&['<', '>'][..]) {
+                Some(idx) if after[idx..].starts_with('>') => start + 1 + idx,
+                Some(idx) => {
+                    self.idx = start + 1 + idx;
 
-            for sigil in T::SIGILS {
-                if self.chars.as_str().starts_with(sigil) {
-                    found = true;
-
-                    for _ in 0..sigil.chars().count() {
-                        self.chars.next();
-                    }
+                    continue;
                 }
-            }
-
-            if !found {
-                continue;
-            }
-
-            let end = match self.chars.find(|c| c.1 == '>') {
-                Some((idx, _)) => idx,
-                None => continue,
+                None => return None,
             };
-            let buf = self.buf.get(start..=end)?;
 
-            if let Ok(id) = T::parse(buf) {
-                return Some((id, start, end));
+            if let Ok(mention) = T::parse(&self.buf[start..=end]) {
+                self.idx = end + 1;
+
+                return Some((mention, start, end));
             }
+
+            self.idx = start + 1;
         }
     }
 }
@@ -103,6 +98,7 @@ mod tests {
         super::{MentionType, ParseMention},
         MentionIter,
     };
+    use crate::timestamp::{Timestamp, TimestampStyle};
     use static_assertions::{assert_impl_all, assert_obj_safe};
     use std::fmt::Debug;
     use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
@@ -111,6 +107,7 @@ mod tests {
     assert_impl_all!(MentionIter<'_, EmojiId>: Clone, Debug, Iterator, Send, Sync);
     assert_impl_all!(MentionIter<'_, MentionType>: Clone, Debug, Iterator, Send, Sync);
     assert_impl_all!(MentionIter<'_, RoleId>: Clone, Debug, Iterator, Send, Sync);
+    assert_impl_all!(MentionIter<'_, Timestamp>: Clone, Debug, Iterator, Send, Sync);
     assert_impl_all!(MentionIter<'_, UserId>: Clone, Debug, Iterator, Send, Sync);
     assert_obj_safe!(
         MentionIter<'_, ChannelId>,
@@ -190,6 +187,73 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn test_iter_unclosed() {
+        let buf = "<@abc <@123> < <@456 <#7> <@789>";
+        let mut iter = UserId::iter(buf);
+        assert_eq!((UserId(123), 6, 11), iter.next().unwrap());
+        assert_eq!((UserId(789), 26, 31), iter.next().unwrap());
+        assert!(iter.next().is_none());
+
+        assert!(UserId::iter("<@1").next().is_none());
+        assert!(UserId::iter("<").next().is_none());
+        assert!(UserId::iter("<<<>>>").next().is_none());
+    }
+
+    #[test]
+    fn test_iter_invalid() {
+        let buf = "<@0123> <@+1> <@-1> <@18446744073709551616> <@1 > <@0>";
+        let mut iter = UserId::iter(buf);
+        assert_eq!(UserId(0), iter.next().unwrap().0);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_emojis_and_mentions() {
+        let buf = "🦀<:smile:1><@2><a:wave:3> 👋 <:bad name:4> <@!5>🦀";
+        let mut iter = MentionType::iter(buf);
+        assert_eq!(
+            (MentionType::Emoji(EmojiId(1)), 4, 13),
+            iter.next().unwrap()
+        );
+        assert_eq!((MentionType::User(UserId(2)), 14, 17), iter.next().unwrap());
+        assert_eq!(
+            (MentionType::Emoji(EmojiId(3)), 18, 27),
+            iter.next().unwrap()
+        );
+
+        let (mention, start, end) = iter.next().unwrap();
+        assert_eq!(MentionType::User(UserId(5)), mention);
+        assert_eq!("<@!5>", &buf[start..=end]);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_timestamps() {
+        let buf = "<t:1> <t:2:R> <t:3:x> <t:4:d>";
+        let mut iter = Timestamp::iter(buf);
+        assert_eq!(Timestamp::new(1, None), iter.next().unwrap().0);
+        assert_eq!(
+            Timestamp::new(2, Some(TimestampStyle::RelativeTime)),
+            iter.next().unwrap().0
+        );
+        assert_eq!(
+            Timestamp::new(4, Some(TimestampStyle::ShortDate)),
+            iter.next().unwrap().0
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_parse() {
+        let buf = "<@1> <@&2> <@!1> <@3>";
+        assert_eq!(
+            vec![UserId(1), UserId(1), UserId(3)],
+            super::super::parse::<UserId>(buf)
+        );
+        assert!(super::super::parse::<ChannelId>(buf).is_empty());
+    }
+
     #[test]
     fn test_iter_as_str() {
         let buf = "a buf";
@@ -25,6 +25,16 @@
 //! # Ok(()) }
 //! ```
 //!
+//! Parse all of the user mentions in a buffer:
+//!
+//! ```
+//! use twilight_mention::parse;
+//! use twilight_model::id::UserId;
+//!
+//! let users = parse::parse::<UserId>("<@123> and <@!456>, but not <@&789>");
+//! assert_eq!(vec![UserId(123), UserId(456)], users);
+//! ```
+//!
 //! Iterate over the user mentions in a buffer:
 //!
 //! ```
@@ -43,9 +53,34 @@ mod iter;
 
 pub use self::{error::ParseMentionError, iter::MentionIter, r#impl::ParseMention};
 
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::timestamp::Timestamp;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 
+/// Parse all of the mentions of a type in a buffer, in the order they're in.
+///
+/// Mentions of the same ID are returned as many times as they're in the
+/// buffer. Refer to [`MentionIter`] for how mentions are found.
+///
+/// # Examples
+///
+/// Count the distinct users mentioned in a message:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use twilight_mention::parse;
+/// use twilight_model::id::UserId;
+///
+/// let content = "<@1> <:blobwave:2> hi <@!1> and <@3>";
+/// let users = parse::parse::<UserId>(content);
+/// assert_eq!(vec![UserId(1), UserId(1), UserId(3)], users);
+/// assert_eq!(2, users.into_iter().collect::<HashSet<_>>().len());
+/// ```
+#[must_use]
+pub fn parse<T: ParseMention + Debug>(buf: &str) -> Vec<T> {
+    T::iter(buf).map(|(mention, _, _)| mention).collect()
+}
+
 /// Any type of mention.
 ///
 /// Contains variants for every possible kind of mention. Can be used with
@@ -72,13 +107,14 @@ use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 /// use twilight_mention::parse::{MentionType, ParseMention};
 /// use twilight_model::id::{ChannelId, EmojiId, RoleId, UserId};
 ///
-/// let buf = "channel <#12> emoji <:name:34> role <@&56> user <@78>";
+/// let buf = "channel <#12> emoji <:name:34> role <@&56> user <@78> time <t:90>";
 ///
 /// let mut iter = MentionType::iter(buf);
 /// assert!(matches!(iter.next(), Some((MentionType::Channel(ChannelId(12)), _, _))));
 /// assert!(matches!(iter.next(), Some((MentionType::Emoji(EmojiId(34)), _, _))));
 /// assert!(matches!(iter.next(), Some((MentionType::Role(RoleId(56)), _, _))));
 /// assert!(matches!(iter.next(), Some((MentionType::User(UserId(78)), _, _))));
+/// assert!(matches!(iter.next(), Some((MentionType::Timestamp(_), _, _))));
 /// assert!(iter.next().is_none());
 /// ```
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -90,6 +126,8 @@ pub enum MentionType {
     Emoji(EmojiId),
     /// Role mention.
     Role(RoleId),
+    /// Timestamp mention.
+    Timestamp(Timestamp),
     /// User mention.
     User(UserId),
 }
@@ -100,6 +138,7 @@ impl Display for MentionType {
             Self::Channel(id) => Display::fmt(id, f),
             Self::Emoji(id) => Display::fmt(id, f),
             Self::Role(id) => Display::fmt(id, f),
+            Self::Timestamp(timestamp) => Display::fmt(&timestamp.unix(), f),
             Self::User(id) => Display::fmt(id, f),
         }
     }
@@ -0,0 +1,190 @@
+//! Timestamps that are displayed in the local time of the viewer.
+//!
+//! Timestamps are mentioned as `<t:unix>` or `<t:unix:style>`, where `unix`
+//! is a Unix timestamp in seconds and `style` is a [`TimestampStyle`].
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Timestamp displayed in the local time of the viewer.
+///
+/// # Examples
+///
+/// Mention a timestamp displayed as a relative time:
+///
+/// ```
+/// use twilight_mention::{
+///     timestamp::{Timestamp, TimestampStyle},
+///     Mention,
+/// };
+///
+/// let expires = Timestamp::new(1_625_097_600, Some(TimestampStyle::RelativeTime));
+/// assert_eq!(
+///     "expires <t:1625097600:R>",
+///     format!("expires {}", expires.mention()),
+/// );
+/// ```
+///
+/// Parse a timestamp displayed as a relative time:
+///
+/// ```
+/// use twilight_mention::{
+///     timestamp::{Timestamp, TimestampStyle},
+///     ParseMention,
+/// };
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let timestamp = Timestamp::parse("<t:1624047064:R>")?;
+/// assert_eq!(1_624_047_064, timestamp.unix());
+/// assert_eq!(Some(TimestampStyle::RelativeTime), timestamp.style());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Timestamp {
+    style: Option<TimestampStyle>,
+    unix: u64,
+}
+
+impl Timestamp {
+    /// Create a timestamp from a Unix timestamp in seconds, with a style or
+    /// the default style of Discord clients.
+    #[must_use]
+    pub const fn new(unix: u64, style: Option<TimestampStyle>) -> Self {
+        Self { style, unix }
+    }
+
+    /// Return the style of the timestamp, if any.
+    ///
+    /// Discord clients display timestamps without a style as
+    /// [`TimestampStyle::ShortDateTime`].
+    #[must_use]
+    pub const fn style(&self) -> Option<TimestampStyle> {
+        self.style
+    }
+
+    /// Return the Unix timestamp in seconds.
+    #[must_use]
+    pub const fn unix(&self) -> u64 {
+        self.unix
+    }
+}
+
+/// How a [`Timestamp`] is displayed.
+///
+/// The examples are of the English locale of Discord clients.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TimestampStyle {
+    /// Long date and time, such as "Tuesday, 20 April 2021 16:20".
+    LongDateTime,
+    /// Long date, such as "20 April 2021".
+    LongDate,
+    /// Long time, such as "16:20:30".
+    LongTime,
+    /// Relative time, such as "2 months ago".
+    RelativeTime,
+    /// Short date and time, such as "20 April 2021 16:20".
+    ShortDateTime,
+    /// Short date, such as "20/04/2021".
+    ShortDate,
+    /// Short time, such as "16:20".
+    ShortTime,
+}
+
+impl TimestampStyle {
+    /// Return the letter of the style in mentions.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        match self {
+            Self::LongDateTime => 'F',
+            Self::LongDate => 'D',
+            Self::LongTime => 'T',
+            Self::RelativeTime => 'R',
+            Self::ShortDateTime => 'f',
+            Self::ShortDate => 'd',
+            Self::ShortTime => 't',
+        }
+    }
+
+    /// Return the style with a letter in mentions, if any.
+    #[must_use]
+    pub const fn from_letter(letter: char) -> Option<Self> {
+        Some(match letter {
+            'F' => Self::LongDateTime,
+            'D' => Self::LongDate,
+            'T' => Self::LongTime,
+            'R' => Self::RelativeTime,
+            'f' => Self::ShortDateTime,
+            'd' => Self::ShortDate,
+            't' => Self::ShortTime,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for TimestampStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_fmt(format_args!("{}", self.letter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Timestamp, TimestampStyle};
+    use crate::{Mention, ParseMention};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_impl_all!(Timestamp: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(TimestampStyle: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    const STYLES: [(TimestampStyle, char); 7] = [
+        (TimestampStyle::LongDateTime, 'F'),
+        (TimestampStyle::LongDate, 'D'),
+        (TimestampStyle::LongTime, 'T'),
+        (TimestampStyle::RelativeTime, 'R'),
+        (TimestampStyle::ShortDateTime, 'f'),
+        (TimestampStyle::ShortDate, 'd'),
+        (TimestampStyle::ShortTime, 't'),
+    ];
+
+    #[test]
+    fn test_letters() {
+        for (style, letter) in &STYLES {
+            assert_eq!(*letter, style.letter());
+            assert_eq!(letter.to_string(), style.to_string());
+            assert_eq!(Some(*style), TimestampStyle::from_letter(*letter));
+        }
+
+        assert_eq!(None, TimestampStyle::from_letter('r'));
+        assert_eq!(None, TimestampStyle::from_letter(':'));
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let timestamp = Timestamp::new(1, Some(TimestampStyle::ShortDate));
+        assert_eq!(1, timestamp.unix());
+        assert_eq!(Some(TimestampStyle::ShortDate), timestamp.style());
+        assert_eq!(None, Timestamp::new(1, None).style());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let styles = STYLES.iter().map(|(style, _)| Some(*style));
+
+        for style in styles.chain(Some(None)) {
+            for unix in &[0, 1_625_097_600, u64::MAX] {
+                let timestamp = Timestamp::new(*unix, style);
+                let mention = timestamp.mention().to_string();
+
+                assert_eq!(timestamp, Timestamp::parse(&mention).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mention_letters() {
+        for (style, letter) in &STYLES {
+            let mention = Timestamp::new(1, Some(*style)).mention().to_string();
+            assert_eq!(format!("<t:1:{}>", letter), mention);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    application::component::Component,
+    channel::{
+        embed::Embed,
+        message::{AllowedMentions, MessageFlags},
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Message sent or updated by an [`InteractionResponse`].
+///
+/// Fields that are `None` are left unchanged when updating a message, so
+/// components or embeds are removed by setting them to an empty list.
+///
+/// [`InteractionResponse`]: super::InteractionResponse
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CallbackData {
+    /// Mentions in the content that notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// Components of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    /// Content of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Embeds of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Flags of the message, such as [`MessageFlags::EPHEMERAL`] to only show
+    /// it to the user who invoked the interaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallbackData;
+    use crate::{
+        application::component::{ActionRow, Button, ButtonStyle, Component},
+        channel::message::{AllowedMentions, MessageFlags},
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_empty() {
+        serde_test::assert_tokens(
+            &CallbackData::default(),
+            &[
+                Token::Struct {
+                    name: "CallbackData",
+                    len: 0,
+                },
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn test_callback_data() {
+        let value = CallbackData {
+            allowed_mentions: Some(AllowedMentions::default()),
+            components: Some(vec![Component::ActionRow(ActionRow {
+                components: vec![Component::Button(Button {
+                    custom_id: Some("confirm".to_owned()),
+                    disabled: false,
+                    emoji: None,
+                    label: Some("Confirm".to_owned()),
+                    style: ButtonStyle::Danger,
+                    url: None,
+                })],
+            })]),
+            content: Some("ban user 1?".to_owned()),
+            embeds: Some(Vec::new()),
+            flags: Some(MessageFlags::EPHEMERAL),
+            tts: Some(false),
+        };
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CallbackData",
+                    len: 6,
+                },
+                Token::Str("allowed_mentions"),
+                Token::Some,
+                Token::Struct {
+                    name: "AllowedMentions",
+                    len: 1,
+                },
+                Token::Str("parse"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::Str("components"),
+                Token::Some,
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Component",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(1),
+                Token::Str("components"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Component",
+                    len: 5,
+                },
+                Token::Str("type"),
+                Token::U8(2),
+                Token::Str("custom_id"),
+                Token::Str("confirm"),
+                Token::Str("disabled"),
+                Token::Bool(false),
+                Token::Str("label"),
+                Token::Str("Confirm"),
+                Token::Str("style"),
+                Token::U8(4),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("content"),
+                Token::Some,
+                Token::Str("ban user 1?"),
+                Token::Str("embeds"),
+                Token::Some,
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("flags"),
+                Token::Some,
+                Token::U64(64),
+                Token::Str("tts"),
+                Token::Some,
+                Token::Bool(false),
+                Token::StructEnd,
+            ],
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Responses to interactions, sent to Discord's interaction callback endpoint.
+
+mod callback_data;
+
+pub use self::callback_data::CallbackData;
+
+use serde::{
+    de::{Deserializer, Error as DeError},
+    ser::SerializeStruct,
+    Deserialize, Serialize, Serializer,
+};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Response to an interaction.
+///
+/// Messages are only updated in response to [`MessageComponent`]
+/// interactions.
+///
+/// [`MessageComponent`]: crate::application::interaction::InteractionType::MessageComponent
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InteractionResponse {
+    /// Acknowledge a [`Ping`].
+    ///
+    /// [`Ping`]: crate::application::interaction::InteractionType::Ping
+    Pong,
+    /// Respond with a message.
+    ChannelMessageWithSource(CallbackData),
+    /// Acknowledge the interaction, showing a loading state, and respond with
+    /// a message later.
+    ///
+    /// Only the [`flags`] of the data are used, such as to make the later
+    /// message ephemeral.
+    ///
+    /// [`flags`]: CallbackData::flags
+    DeferredChannelMessageWithSource(CallbackData),
+    /// Acknowledge a component interaction and update its message later.
+    DeferredUpdateMessage,
+    /// Update the message of a component interaction.
+    UpdateMessage(CallbackData),
+}
+
+impl InteractionResponse {
+    /// Type of the response.
+    pub fn kind(&self) -> InteractionResponseType {
+        match self {
+            Self::Pong => InteractionResponseType::Pong,
+            Self::ChannelMessageWithSource(_) => InteractionResponseType::ChannelMessageWithSource,
+            Self::DeferredChannelMessageWithSource(_) => {
+                InteractionResponseType::DeferredChannelMessageWithSource
+            }
+            Self::DeferredUpdateMessage => InteractionResponseType::DeferredUpdateMessage,
+            Self::UpdateMessage(_) => InteractionResponseType::UpdateMessage,
+        }
+    }
+
+    /// Data of the response, if its type has any.
+    pub fn data(&self) -> Option<&CallbackData> {
+        match self {
+            Self::ChannelMessageWithSource(data)
+            | Self::DeferredChannelMessageWithSource(data)
+            | Self::UpdateMessage(data) => Some(data),
+            Self::Pong | Self::DeferredUpdateMessage => None,
+        }
+    }
+}
+
+/// Type of an [`InteractionResponse`].
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum InteractionResponseType {
+    /// Acknowledge a ping.
+    Pong = 1,
+    /// Respond with a message.
+    ChannelMessageWithSource = 4,
+    /// Acknowledge the interaction and respond with a message later.
+    DeferredChannelMessageWithSource = 5,
+    /// Acknowledge a component interaction and update its message later.
+    DeferredUpdateMessage = 6,
+    /// Update the message of a component interaction.
+    UpdateMessage = 7,
+}
+
+#[derive(Deserialize)]
+struct InteractionResponseData {
+    data: Option<CallbackData>,
+    #[serde(rename = "type")]
+    kind: InteractionResponseType,
+}
+
+impl<'de> Deserialize<'de> for InteractionResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let response = InteractionResponseData::deserialize(deserializer)?;
+
+        Ok(match response.kind {
+            InteractionResponseType::Pong => Self::Pong,
+            InteractionResponseType::ChannelMessageWithSource => Self::ChannelMessageWithSource(
+                response
+                    .data
+                    .ok_or_else(|| DeError::missing_field("data"))?,
+            ),
+            InteractionResponseType::DeferredChannelMessageWithSource => {
+                Self::DeferredChannelMessageWithSource(response.data.unwrap_or_default())
+            }
+            InteractionResponseType::DeferredUpdateMessage => Self::DeferredUpdateMessage,
+            InteractionResponseType::UpdateMessage => Self::UpdateMessage(
+                response
+                    .data
+                    .ok_or_else(|| DeError::missing_field("data"))?,
+            ),
+        })
+    }
+}
+
+impl Serialize for InteractionResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = self.data();
+        let mut state =
+            serializer.serialize_struct("InteractionResponse", 1 + usize::from(data.is_some()))?;
+        state.serialize_field("type", &self.kind())?;
+
+        if let Some(data) = data {
+            state.serialize_field("data", data)?;
+        }
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CallbackData, InteractionResponse, InteractionResponseType};
+    use crate::channel::message::MessageFlags;
+    use serde_test::Token;
+
+    #[test]
+    fn test_response_type_variants() {
+        serde_test::assert_tokens(&InteractionResponseType::Pong, &[Token::U8(1)]);
+        serde_test::assert_tokens(
+            &InteractionResponseType::ChannelMessageWithSource,
+            &[Token::U8(4)],
+        );
+        serde_test::assert_tokens(
+            &InteractionResponseType::DeferredChannelMessageWithSource,
+            &[Token::U8(5)],
+        );
+        serde_test::assert_tokens(
+            &InteractionResponseType::DeferredUpdateMessage,
+            &[Token::U8(6)],
+        );
+        serde_test::assert_tokens(&InteractionResponseType::UpdateMessage, &[Token::U8(7)]);
+    }
+
+    #[test]
+    fn test_pong() {
+        let value = InteractionResponse::Pong;
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionResponse",
+                    len: 1,
+                },
+                Token::Str("type"),
+                Token::U8(1),
+                Token::StructEnd,
+            ],
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_channel_message_with_source() {
+        let value = InteractionResponse::ChannelMessageWithSource(CallbackData {
+            allowed_mentions: None,
+            components: None,
+            content: Some("banned".to_owned()),
+            embeds: None,
+            flags: Some(MessageFlags::EPHEMERAL),
+            tts: None,
+        });
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionResponse",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(4),
+                Token::Str("data"),
+                Token::Struct {
+                    name: "CallbackData",
+                    len: 2,
+                },
+                Token::Str("content"),
+                Token::Some,
+                Token::Str("banned"),
+                Token::Str("flags"),
+                Token::Some,
+                Token::U64(64),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deferred_update_message() {
+        let value = InteractionResponse::DeferredUpdateMessage;
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionResponse",
+                    len: 1,
+                },
+                Token::Str("type"),
+                Token::U8(6),
+                Token::StructEnd,
+            ],
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_data() {
+        assert!(serde_json::from_str::<InteractionResponse>(r#"{"type":4}"#).is_err());
+        assert!(serde_json::from_str::<InteractionResponse>(r#"{"type":7}"#).is_err());
+        assert_eq!(
+            InteractionResponse::DeferredChannelMessageWithSource(CallbackData::default()),
+            serde_json::from_str(r#"{"type":5}"#).unwrap()
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// Predefined value of a string or integer [`CommandOption`] that users pick
+/// from.
+///
+/// [`CommandOption`]: super::CommandOption
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum CommandOptionChoice {
+    /// Choice of a string option.
+    String {
+        /// Name of the choice shown to users.
+        name: String,
+        /// Value of the choice.
+        value: String,
+    },
+    /// Choice of an integer option.
+    Int {
+        /// Name of the choice shown to users.
+        name: String,
+        /// Value of the choice.
+        value: i64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandOptionChoice;
+    use serde_test::Token;
+
+    #[test]
+    fn test_string() {
+        let value = CommandOptionChoice::String {
+            name: "Spam".to_owned(),
+            value: "spam".to_owned(),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CommandOptionChoice",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::Str("Spam"),
+                Token::Str("value"),
+                Token::Str("spam"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_int() {
+        let value = CommandOptionChoice::Int {
+            name: "One day".to_owned(),
+            value: 86_400,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CommandOptionChoice",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::Str("One day"),
+                Token::Str("value"),
+                Token::I64(86_400),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
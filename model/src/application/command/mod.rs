@@ -0,0 +1,144 @@
+//! Slash commands and their options.
+
+mod choice;
+mod option;
+mod option_type;
+
+pub use self::{
+    choice::CommandOptionChoice, option::CommandOption, option_type::CommandOptionType,
+};
+
+use crate::id::{ApplicationId, CommandId, GuildId};
+use serde::{Deserialize, Serialize};
+
+/// Slash command of an application.
+///
+/// The ID and application ID are only present on commands returned by
+/// Discord, and are omitted when registering a command.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Command {
+    /// ID of the application the command belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<ApplicationId>,
+    /// Whether the command is enabled for everyone when it's added to a
+    /// guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_permission: Option<bool>,
+    /// Description of the command, between 1 and 100 characters long.
+    pub description: String,
+    /// ID of the guild the command is registered in, if it isn't global.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// Unique ID of the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<CommandId>,
+    /// Name of the command, between 1 and 32 lowercase characters long.
+    pub name: String,
+    /// Options of the command, such as its arguments or sub-commands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Command, CommandOption, CommandOptionType};
+    use crate::id::{ApplicationId, CommandId, GuildId};
+    use serde_test::Token;
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn test_command() {
+        let value = Command {
+            application_id: Some(ApplicationId(1)),
+            default_permission: Some(false),
+            description: "ban a user".to_owned(),
+            guild_id: Some(GuildId(2)),
+            id: Some(CommandId(3)),
+            name: "ban".to_owned(),
+            options: vec![CommandOption {
+                choices: Vec::new(),
+                description: "user to ban".to_owned(),
+                kind: CommandOptionType::User,
+                name: "user".to_owned(),
+                options: Vec::new(),
+                required: true,
+            }],
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Command",
+                    len: 7,
+                },
+                Token::Str("application_id"),
+                Token::Some,
+                Token::NewtypeStruct {
+                    name: "ApplicationId",
+                },
+                Token::Str("1"),
+                Token::Str("default_permission"),
+                Token::Some,
+                Token::Bool(false),
+                Token::Str("description"),
+                Token::Str("ban a user"),
+                Token::Str("guild_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "GuildId" },
+                Token::Str("2"),
+                Token::Str("id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "CommandId" },
+                Token::Str("3"),
+                Token::Str("name"),
+                Token::Str("ban"),
+                Token::Str("options"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "CommandOption",
+                    len: 4,
+                },
+                Token::Str("description"),
+                Token::Str("user to ban"),
+                Token::Str("type"),
+                Token::U8(6),
+                Token::Str("name"),
+                Token::Str("user"),
+                Token::Str("required"),
+                Token::Bool(true),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_command_registration() {
+        let value = Command {
+            application_id: None,
+            default_permission: None,
+            description: "check the latency".to_owned(),
+            guild_id: None,
+            id: None,
+            name: "ping".to_owned(),
+            options: Vec::new(),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Command",
+                    len: 2,
+                },
+                Token::Str("description"),
+                Token::Str("check the latency"),
+                Token::Str("name"),
+                Token::Str("ping"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
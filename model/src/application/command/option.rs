@@ -0,0 +1,133 @@
+use super::{CommandOptionChoice, CommandOptionType};
+use serde::{Deserialize, Serialize};
+
+/// Option of a [`Command`], which is either an argument or a sub-command.
+///
+/// Sub-command groups contain sub-commands, and sub-commands contain
+/// arguments.
+///
+/// [`Command`]: super::Command
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct CommandOption {
+    /// Predefined values that users pick from, only allowed on string and
+    /// integer options.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<CommandOptionChoice>,
+    /// Description of the option, between 1 and 100 characters long.
+    pub description: String,
+    /// Type of the option.
+    #[serde(rename = "type")]
+    pub kind: CommandOptionType,
+    /// Name of the option, between 1 and 32 lowercase characters long.
+    pub name: String,
+    /// Options of a sub-command or sub-command group.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+    /// Whether the option must be provided.
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandOption, CommandOptionChoice, CommandOptionType};
+    use serde_test::Token;
+
+    #[test]
+    fn test_sub_command() {
+        let value = CommandOption {
+            choices: Vec::new(),
+            description: "mute a user".to_owned(),
+            kind: CommandOptionType::SubCommand,
+            name: "mute".to_owned(),
+            options: vec![CommandOption {
+                choices: vec![CommandOptionChoice::Int {
+                    name: "One hour".to_owned(),
+                    value: 3600,
+                }],
+                description: "duration in seconds".to_owned(),
+                kind: CommandOptionType::Integer,
+                name: "duration".to_owned(),
+                options: Vec::new(),
+                required: false,
+            }],
+            required: false,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CommandOption",
+                    len: 5,
+                },
+                Token::Str("description"),
+                Token::Str("mute a user"),
+                Token::Str("type"),
+                Token::U8(1),
+                Token::Str("name"),
+                Token::Str("mute"),
+                Token::Str("options"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "CommandOption",
+                    len: 5,
+                },
+                Token::Str("choices"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "CommandOptionChoice",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::Str("One hour"),
+                Token::Str("value"),
+                Token::I64(3600),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("description"),
+                Token::Str("duration in seconds"),
+                Token::Str("type"),
+                Token::U8(4),
+                Token::Str("name"),
+                Token::Str("duration"),
+                Token::Str("required"),
+                Token::Bool(false),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("required"),
+                Token::Bool(false),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_required_default() {
+        let value = CommandOption {
+            choices: Vec::new(),
+            description: "reason".to_owned(),
+            kind: CommandOptionType::String,
+            name: "reason".to_owned(),
+            options: Vec::new(),
+            required: false,
+        };
+
+        serde_test::assert_de_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CommandOption",
+                    len: 3,
+                },
+                Token::Str("description"),
+                Token::Str("reason"),
+                Token::Str("type"),
+                Token::U8(3),
+                Token::Str("name"),
+                Token::Str("reason"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Type of a [`CommandOption`], which determines the values users may provide.
+///
+/// [`CommandOption`]: super::CommandOption
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum CommandOptionType {
+    /// Sub-command, whose options are the command's arguments.
+    SubCommand = 1,
+    /// Group of sub-commands.
+    SubCommandGroup = 2,
+    /// String argument.
+    String = 3,
+    /// Integer argument.
+    Integer = 4,
+    /// Boolean argument.
+    Boolean = 5,
+    /// User argument.
+    User = 6,
+    /// Channel argument.
+    Channel = 7,
+    /// Role argument.
+    Role = 8,
+    /// User or role argument.
+    Mentionable = 9,
+}
+
+impl CommandOptionType {
+    /// Whether the option is a sub-command or a group of sub-commands, rather
+    /// than an argument.
+    pub fn is_sub_command(self) -> bool {
+        matches!(self, Self::SubCommand | Self::SubCommandGroup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandOptionType;
+    use serde_test::Token;
+
+    #[test]
+    fn test_is_sub_command() {
+        assert!(CommandOptionType::SubCommand.is_sub_command());
+        assert!(CommandOptionType::SubCommandGroup.is_sub_command());
+        assert!(!CommandOptionType::String.is_sub_command());
+        assert!(!CommandOptionType::Mentionable.is_sub_command());
+    }
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&CommandOptionType::SubCommand, &[Token::U8(1)]);
+        serde_test::assert_tokens(&CommandOptionType::SubCommandGroup, &[Token::U8(2)]);
+        serde_test::assert_tokens(&CommandOptionType::String, &[Token::U8(3)]);
+        serde_test::assert_tokens(&CommandOptionType::Integer, &[Token::U8(4)]);
+        serde_test::assert_tokens(&CommandOptionType::Boolean, &[Token::U8(5)]);
+        serde_test::assert_tokens(&CommandOptionType::User, &[Token::U8(6)]);
+        serde_test::assert_tokens(&CommandOptionType::Channel, &[Token::U8(7)]);
+        serde_test::assert_tokens(&CommandOptionType::Role, &[Token::U8(8)]);
+        serde_test::assert_tokens(&CommandOptionType::Mentionable, &[Token::U8(9)]);
+    }
+}
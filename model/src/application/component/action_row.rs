@@ -0,0 +1,10 @@
+use super::Component;
+
+/// Row of components on a message.
+///
+/// A row contains either up to five buttons or a single select menu.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ActionRow {
+    /// Components in the row.
+    pub components: Vec<Component>,
+}
@@ -0,0 +1,55 @@
+use crate::channel::ReactionType;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Button on a message.
+///
+/// Link buttons have a URL and no custom ID, while all other buttons have a
+/// custom ID and no URL.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Button {
+    /// Developer-defined identifier of the button, sent with interactions.
+    pub custom_id: Option<String>,
+    /// Whether the button is disabled.
+    pub disabled: bool,
+    /// Emoji shown on the button.
+    pub emoji: Option<ReactionType>,
+    /// Text shown on the button.
+    pub label: Option<String>,
+    /// Style of the button.
+    pub style: ButtonStyle,
+    /// URL that link buttons open.
+    pub url: Option<String>,
+}
+
+/// Style of a [`Button`].
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum ButtonStyle {
+    /// Blurple button.
+    Primary = 1,
+    /// Grey button.
+    Secondary = 2,
+    /// Green button.
+    Success = 3,
+    /// Red button.
+    Danger = 4,
+    /// Grey button that opens its URL.
+    Link = 5,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ButtonStyle;
+    use serde_test::Token;
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&ButtonStyle::Primary, &[Token::U8(1)]);
+        serde_test::assert_tokens(&ButtonStyle::Secondary, &[Token::U8(2)]);
+        serde_test::assert_tokens(&ButtonStyle::Success, &[Token::U8(3)]);
+        serde_test::assert_tokens(&ButtonStyle::Danger, &[Token::U8(4)]);
+        serde_test::assert_tokens(&ButtonStyle::Link, &[Token::U8(5)]);
+    }
+}
@@ -0,0 +1,375 @@
+//! Components of messages, such as buttons, which users interact with.
+
+mod action_row;
+mod button;
+mod select_menu;
+
+pub use self::{
+    action_row::ActionRow,
+    button::{Button, ButtonStyle},
+    select_menu::{SelectMenu, SelectMenuOption},
+};
+
+use crate::channel::ReactionType;
+use serde::{
+    de::{Deserializer, Error as DeError},
+    ser::SerializeStruct,
+    Deserialize, Serialize, Serializer,
+};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Component of a message.
+///
+/// Messages contain action rows, which contain the other components.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Component {
+    /// Row of components.
+    ActionRow(ActionRow),
+    /// Button.
+    Button(Button),
+    /// Dropdown menu.
+    SelectMenu(SelectMenu),
+}
+
+impl Component {
+    /// Type of the component.
+    pub fn kind(&self) -> ComponentType {
+        match self {
+            Self::ActionRow(_) => ComponentType::ActionRow,
+            Self::Button(_) => ComponentType::Button,
+            Self::SelectMenu(_) => ComponentType::SelectMenu,
+        }
+    }
+}
+
+impl From<ActionRow> for Component {
+    fn from(action_row: ActionRow) -> Self {
+        Self::ActionRow(action_row)
+    }
+}
+
+impl From<Button> for Component {
+    fn from(button: Button) -> Self {
+        Self::Button(button)
+    }
+}
+
+impl From<SelectMenu> for Component {
+    fn from(select_menu: SelectMenu) -> Self {
+        Self::SelectMenu(select_menu)
+    }
+}
+
+/// Type of a [`Component`].
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum ComponentType {
+    /// Row of components.
+    ActionRow = 1,
+    /// Button.
+    Button = 2,
+    /// Dropdown menu.
+    SelectMenu = 3,
+}
+
+#[derive(Deserialize)]
+struct ComponentData {
+    components: Option<Vec<Component>>,
+    custom_id: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+    emoji: Option<ReactionType>,
+    #[serde(rename = "type")]
+    kind: ComponentType,
+    label: Option<String>,
+    max_values: Option<u8>,
+    min_values: Option<u8>,
+    options: Option<Vec<SelectMenuOption>>,
+    placeholder: Option<String>,
+    style: Option<ButtonStyle>,
+    url: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for Component {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ComponentData::deserialize(deserializer)?;
+
+        let span = tracing::trace_span!("deserializing component");
+        let _span_enter = span.enter();
+        tracing::trace!(kind = ?data.kind);
+
+        Ok(match data.kind {
+            ComponentType::ActionRow => Self::ActionRow(ActionRow {
+                components: data
+                    .components
+                    .ok_or_else(|| DeError::missing_field("components"))?,
+            }),
+            ComponentType::Button => Self::Button(Button {
+                custom_id: data.custom_id,
+                disabled: data.disabled,
+                emoji: data.emoji,
+                label: data.label,
+                style: data.style.ok_or_else(|| DeError::missing_field("style"))?,
+                url: data.url,
+            }),
+            ComponentType::SelectMenu => Self::SelectMenu(SelectMenu {
+                custom_id: data
+                    .custom_id
+                    .ok_or_else(|| DeError::missing_field("custom_id"))?,
+                disabled: data.disabled,
+                max_values: data.max_values,
+                min_values: data.min_values,
+                options: data
+                    .options
+                    .ok_or_else(|| DeError::missing_field("options"))?,
+                placeholder: data.placeholder,
+            }),
+        })
+    }
+}
+
+impl Serialize for Component {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::ActionRow(action_row) => {
+                let mut state = serializer.serialize_struct("Component", 2)?;
+                state.serialize_field("type", &ComponentType::ActionRow)?;
+                state.serialize_field("components", &action_row.components)?;
+
+                state.end()
+            }
+            Self::Button(button) => {
+                let len = 3
+                    + usize::from(button.custom_id.is_some())
+                    + usize::from(button.emoji.is_some())
+                    + usize::from(button.label.is_some())
+                    + usize::from(button.url.is_some());
+                let mut state = serializer.serialize_struct("Component", len)?;
+                state.serialize_field("type", &ComponentType::Button)?;
+
+                if let Some(custom_id) = button.custom_id.as_ref() {
+                    state.serialize_field("custom_id", custom_id)?;
+                }
+
+                state.serialize_field("disabled", &button.disabled)?;
+
+                if let Some(emoji) = button.emoji.as_ref() {
+                    state.serialize_field("emoji", emoji)?;
+                }
+
+                if let Some(label) = button.label.as_ref() {
+                    state.serialize_field("label", label)?;
+                }
+
+                state.serialize_field("style", &button.style)?;
+
+                if let Some(url) = button.url.as_ref() {
+                    state.serialize_field("url", url)?;
+                }
+
+                state.end()
+            }
+            Self::SelectMenu(select_menu) => {
+                let len = 4
+                    + usize::from(select_menu.max_values.is_some())
+                    + usize::from(select_menu.min_values.is_some())
+                    + usize::from(select_menu.placeholder.is_some());
+                let mut state = serializer.serialize_struct("Component", len)?;
+                state.serialize_field("type", &ComponentType::SelectMenu)?;
+                state.serialize_field("custom_id", &select_menu.custom_id)?;
+                state.serialize_field("disabled", &select_menu.disabled)?;
+
+                if let Some(max_values) = select_menu.max_values {
+                    state.serialize_field("max_values", &max_values)?;
+                }
+
+                if let Some(min_values) = select_menu.min_values {
+                    state.serialize_field("min_values", &min_values)?;
+                }
+
+                state.serialize_field("options", &select_menu.options)?;
+
+                if let Some(placeholder) = select_menu.placeholder.as_ref() {
+                    state.serialize_field("placeholder", placeholder)?;
+                }
+
+                state.end()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ActionRow, Button, ButtonStyle, Component, ComponentType, SelectMenu, SelectMenuOption,
+    };
+    use crate::{channel::ReactionType, id::EmojiId};
+    use serde_test::Token;
+
+    #[test]
+    fn test_component_type_variants() {
+        serde_test::assert_tokens(&ComponentType::ActionRow, &[Token::U8(1)]);
+        serde_test::assert_tokens(&ComponentType::Button, &[Token::U8(2)]);
+        serde_test::assert_tokens(&ComponentType::SelectMenu, &[Token::U8(3)]);
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn test_action_row_buttons() {
+        let value = Component::ActionRow(ActionRow {
+            components: vec![
+                Component::Button(Button {
+                    custom_id: Some("approve".to_owned()),
+                    disabled: false,
+                    emoji: Some(ReactionType::Custom {
+                        animated: false,
+                        id: EmojiId(1),
+                        name: Some("check".to_owned()),
+                    }),
+                    label: Some("Approve".to_owned()),
+                    style: ButtonStyle::Success,
+                    url: None,
+                }),
+                Component::Button(Button {
+                    custom_id: None,
+                    disabled: true,
+                    emoji: None,
+                    label: None,
+                    style: ButtonStyle::Link,
+                    url: Some("https://twilight.rs".to_owned()),
+                }),
+            ],
+        });
+        assert_eq!(ComponentType::ActionRow, value.kind());
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Component",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(1),
+                Token::Str("components"),
+                Token::Seq { len: Some(2) },
+                Token::Struct {
+                    name: "Component",
+                    len: 6,
+                },
+                Token::Str("type"),
+                Token::U8(2),
+                Token::Str("custom_id"),
+                Token::Str("approve"),
+                Token::Str("disabled"),
+                Token::Bool(false),
+                Token::Str("emoji"),
+                Token::Struct {
+                    name: "ReactionType",
+                    len: 3,
+                },
+                Token::Str("animated"),
+                Token::Bool(false),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "EmojiId" },
+                Token::Str("1"),
+                Token::Str("name"),
+                Token::Some,
+                Token::Str("check"),
+                Token::StructEnd,
+                Token::Str("label"),
+                Token::Str("Approve"),
+                Token::Str("style"),
+                Token::U8(3),
+                Token::StructEnd,
+                Token::Struct {
+                    name: "Component",
+                    len: 4,
+                },
+                Token::Str("type"),
+                Token::U8(2),
+                Token::Str("disabled"),
+                Token::Bool(true),
+                Token::Str("style"),
+                Token::U8(5),
+                Token::Str("url"),
+                Token::Str("https://twilight.rs"),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_select_menu() {
+        let value = Component::SelectMenu(SelectMenu {
+            custom_id: "action".to_owned(),
+            disabled: false,
+            max_values: Some(1),
+            min_values: None,
+            options: vec![SelectMenuOption {
+                default: false,
+                description: None,
+                emoji: None,
+                label: "Ban".to_owned(),
+                value: "ban".to_owned(),
+            }],
+            placeholder: Some("Pick an action".to_owned()),
+        });
+        assert_eq!(ComponentType::SelectMenu, value.kind());
+        assert_eq!(
+            value,
+            serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap()
+        );
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Component",
+                    len: 6,
+                },
+                Token::Str("type"),
+                Token::U8(3),
+                Token::Str("custom_id"),
+                Token::Str("action"),
+                Token::Str("disabled"),
+                Token::Bool(false),
+                Token::Str("max_values"),
+                Token::U8(1),
+                Token::Str("options"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "SelectMenuOption",
+                    len: 3,
+                },
+                Token::Str("default"),
+                Token::Bool(false),
+                Token::Str("label"),
+                Token::Str("Ban"),
+                Token::Str("value"),
+                Token::Str("ban"),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("placeholder"),
+                Token::Str("Pick an action"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        assert!(serde_json::from_str::<Component>(r#"{"type":1}"#).is_err());
+        assert!(serde_json::from_str::<Component>(r#"{"type":2,"custom_id":"a"}"#).is_err());
+        assert!(serde_json::from_str::<Component>(r#"{"type":3,"options":[]}"#).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::channel::ReactionType;
+use serde::{Deserialize, Serialize};
+
+/// Dropdown menu on a message, which takes up a whole row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SelectMenu {
+    /// Developer-defined identifier of the menu, sent with interactions.
+    pub custom_id: String,
+    /// Whether the menu is disabled.
+    pub disabled: bool,
+    /// Maximum number of options that may be selected.
+    pub max_values: Option<u8>,
+    /// Minimum number of options that must be selected.
+    pub min_values: Option<u8>,
+    /// Options to select from.
+    pub options: Vec<SelectMenuOption>,
+    /// Text shown when no option is selected.
+    pub placeholder: Option<String>,
+}
+
+/// Option of a [`SelectMenu`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SelectMenuOption {
+    /// Whether the option is selected by default.
+    #[serde(default)]
+    pub default: bool,
+    /// Additional description of the option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Emoji shown with the option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<ReactionType>,
+    /// Text shown for the option.
+    pub label: String,
+    /// Developer-defined value of the option, sent with interactions.
+    pub value: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SelectMenuOption;
+    use crate::channel::ReactionType;
+    use serde_test::Token;
+
+    #[test]
+    fn test_select_menu_option() {
+        let value = SelectMenuOption {
+            default: true,
+            description: Some("remove the message".to_owned()),
+            emoji: Some(ReactionType::Unicode {
+                name: "🗑".to_owned(),
+            }),
+            label: "Delete".to_owned(),
+            value: "delete".to_owned(),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "SelectMenuOption",
+                    len: 5,
+                },
+                Token::Str("default"),
+                Token::Bool(true),
+                Token::Str("description"),
+                Token::Some,
+                Token::Str("remove the message"),
+                Token::Str("emoji"),
+                Token::Some,
+                Token::Struct {
+                    name: "ReactionType",
+                    len: 1,
+                },
+                Token::Str("name"),
+                Token::Str("🗑"),
+                Token::StructEnd,
+                Token::Str("label"),
+                Token::Str("Delete"),
+                Token::Str("value"),
+                Token::Str("delete"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{application::component::ComponentType, id::CommandId};
+use serde::{Deserialize, Serialize};
+
+/// Data of an [`Interaction`], depending on its type.
+///
+/// [`Interaction`]: super::Interaction
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum InteractionData {
+    /// Data of an invoked application command.
+    ApplicationCommand(CommandData),
+    /// Data of a used message component.
+    MessageComponent(MessageComponentData),
+}
+
+/// Invoked application command and the options users provided.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct CommandData {
+    /// ID of the invoked command.
+    pub id: CommandId,
+    /// Name of the invoked command.
+    pub name: String,
+    /// Options users provided, such as a sub-command or its arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandDataOption>,
+}
+
+/// Option provided by a user when invoking an application command.
+///
+/// Sub-commands and groups of sub-commands have nested options, while
+/// arguments have a value.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct CommandDataOption {
+    /// Name of the option.
+    pub name: String,
+    /// Options of a sub-command or group of sub-commands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandDataOption>,
+    /// Value of an argument.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<CommandOptionValue>,
+}
+
+/// Value of an argument provided by a user.
+///
+/// User, channel, role, and mentionable arguments are provided as the string
+/// form of their ID.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum CommandOptionValue {
+    /// Value of a boolean argument.
+    Boolean(bool),
+    /// Value of an integer argument.
+    Integer(i64),
+    /// Value of a string or ID argument.
+    String(String),
+}
+
+/// Used message component and the values users selected.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MessageComponentData {
+    /// Type of the used component.
+    pub component_type: ComponentType,
+    /// Developer-defined ID of the used component.
+    pub custom_id: String,
+    /// Values of the options selected in a select menu.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandData, CommandDataOption, CommandOptionValue, InteractionData, MessageComponentData,
+    };
+    use crate::{application::component::ComponentType, id::CommandId};
+    use serde_test::Token;
+
+    #[test]
+    fn test_command_data() {
+        let value = InteractionData::ApplicationCommand(CommandData {
+            id: CommandId(1),
+            name: "ban".to_owned(),
+            options: vec![CommandDataOption {
+                name: "days".to_owned(),
+                options: Vec::new(),
+                value: Some(CommandOptionValue::Integer(7)),
+            }],
+        });
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "CommandData",
+                    len: 3,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "CommandId" },
+                Token::Str("1"),
+                Token::Str("name"),
+                Token::Str("ban"),
+                Token::Str("options"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "CommandDataOption",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::Str("days"),
+                Token::Str("value"),
+                Token::Some,
+                Token::I64(7),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_message_component_data() {
+        let value = InteractionData::MessageComponent(MessageComponentData {
+            component_type: ComponentType::SelectMenu,
+            custom_id: "class".to_owned(),
+            values: vec!["mage".to_owned()],
+        });
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "MessageComponentData",
+                    len: 3,
+                },
+                Token::Str("component_type"),
+                Token::U8(3),
+                Token::Str("custom_id"),
+                Token::Str("class"),
+                Token::Str("values"),
+                Token::Seq { len: Some(1) },
+                Token::Str("mage"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+}
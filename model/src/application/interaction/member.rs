@@ -0,0 +1,101 @@
+use crate::{guild::Permissions, id::RoleId, user::User};
+use serde::{Deserialize, Serialize};
+
+/// Member that invoked an [`Interaction`] in a guild.
+///
+/// [`Interaction`]: super::Interaction
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InteractionMember {
+    pub joined_at: Option<String>,
+    pub nick: Option<String>,
+    #[serde(default)]
+    pub pending: bool,
+    /// Permissions of the member in the channel the interaction was invoked
+    /// in, including overwrites.
+    pub permissions: Permissions,
+    pub premium_since: Option<String>,
+    pub roles: Vec<RoleId>,
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionMember;
+    use crate::{
+        guild::Permissions,
+        id::{RoleId, UserId},
+        user::User,
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_interaction_member() {
+        let value = InteractionMember {
+            joined_at: Some("2021-06-01T00:00:00.000000+00:00".to_owned()),
+            nick: None,
+            pending: false,
+            permissions: Permissions::SEND_MESSAGES,
+            premium_since: None,
+            roles: vec![RoleId(1)],
+            user: User {
+                avatar: None,
+                bot: false,
+                discriminator: "0001".to_owned(),
+                email: None,
+                flags: None,
+                id: UserId(2),
+                locale: None,
+                mfa_enabled: None,
+                name: "twilight".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            },
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionMember",
+                    len: 7,
+                },
+                Token::Str("joined_at"),
+                Token::Some,
+                Token::Str("2021-06-01T00:00:00.000000+00:00"),
+                Token::Str("nick"),
+                Token::None,
+                Token::Str("pending"),
+                Token::Bool(false),
+                Token::Str("permissions"),
+                Token::Str("2048"),
+                Token::Str("premium_since"),
+                Token::None,
+                Token::Str("roles"),
+                Token::Seq { len: Some(1) },
+                Token::NewtypeStruct { name: "RoleId" },
+                Token::Str("1"),
+                Token::SeqEnd,
+                Token::Str("user"),
+                Token::Struct {
+                    name: "User",
+                    len: 5,
+                },
+                Token::Str("avatar"),
+                Token::None,
+                Token::Str("bot"),
+                Token::Bool(false),
+                Token::Str("discriminator"),
+                Token::Str("0001"),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "UserId" },
+                Token::Str("2"),
+                Token::Str("username"),
+                Token::Str("twilight"),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Interactions, which Discord sends when users invoke a command or use a
+//! message component.
+
+mod data;
+mod member;
+
+pub use self::{
+    data::{
+        CommandData, CommandDataOption, CommandOptionValue, InteractionData, MessageComponentData,
+    },
+    member::InteractionMember,
+};
+
+use crate::{
+    channel::Message,
+    id::{ApplicationId, ChannelId, GuildId, InteractionId},
+    user::User,
+};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Interaction of a user with an application.
+///
+/// Interactions in guilds have a [`member`], while interactions in direct
+/// messages have a [`user`].
+///
+/// [`member`]: Self::member
+/// [`user`]: Self::user
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Interaction {
+    /// ID of the application the interaction is for.
+    pub application_id: ApplicationId,
+    /// ID of the channel the interaction was invoked in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// Data of the invoked command or used component.
+    ///
+    /// Only [`Ping`] interactions have no data.
+    ///
+    /// [`Ping`]: InteractionType::Ping
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<InteractionData>,
+    /// ID of the guild the interaction was invoked in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// Unique ID of the interaction.
+    pub id: InteractionId,
+    /// Type of the interaction.
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+    /// Member that invoked the interaction, if it was invoked in a guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<InteractionMember>,
+    /// Message the used component is attached to.
+    ///
+    /// Only present on [`MessageComponent`] interactions.
+    ///
+    /// [`MessageComponent`]: InteractionType::MessageComponent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    /// Token used to respond to the interaction.
+    pub token: String,
+    /// User that invoked the interaction, if it was invoked in a direct
+    /// message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+}
+
+impl Interaction {
+    /// User that invoked the interaction, regardless of where it was invoked.
+    pub fn author(&self) -> Option<&User> {
+        self.member
+            .as_ref()
+            .map(|member| &member.user)
+            .or(self.user.as_ref())
+    }
+}
+
+/// Type of an [`Interaction`].
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum InteractionType {
+    /// Ping sent by Discord to check that an interactions endpoint is
+    /// available.
+    Ping = 1,
+    /// Application command was invoked.
+    ApplicationCommand = 2,
+    /// Message component was used.
+    MessageComponent = 3,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interaction, InteractionData, InteractionType, MessageComponentData};
+    use crate::{
+        application::component::ComponentType,
+        id::{ApplicationId, ChannelId, InteractionId, UserId},
+        user::User,
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_interaction_type() {
+        serde_test::assert_tokens(&InteractionType::Ping, &[Token::U8(1)]);
+        serde_test::assert_tokens(&InteractionType::ApplicationCommand, &[Token::U8(2)]);
+        serde_test::assert_tokens(&InteractionType::MessageComponent, &[Token::U8(3)]);
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn test_interaction() {
+        let value = Interaction {
+            application_id: ApplicationId(1),
+            channel_id: Some(ChannelId(2)),
+            data: Some(InteractionData::MessageComponent(MessageComponentData {
+                component_type: ComponentType::Button,
+                custom_id: "ban".to_owned(),
+                values: Vec::new(),
+            })),
+            guild_id: None,
+            id: InteractionId(3),
+            kind: InteractionType::MessageComponent,
+            member: None,
+            message: None,
+            token: "token".to_owned(),
+            user: Some(User {
+                avatar: None,
+                bot: false,
+                discriminator: "0001".to_owned(),
+                email: None,
+                flags: None,
+                id: UserId(4),
+                locale: None,
+                mfa_enabled: None,
+                name: "twilight".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            }),
+        };
+
+        serde_test::assert_ser_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Interaction",
+                    len: 7,
+                },
+                Token::Str("application_id"),
+                Token::NewtypeStruct {
+                    name: "ApplicationId",
+                },
+                Token::Str("1"),
+                Token::Str("channel_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("2"),
+                Token::Str("data"),
+                Token::Some,
+                Token::Struct {
+                    name: "MessageComponentData",
+                    len: 2,
+                },
+                Token::Str("component_type"),
+                Token::U8(2),
+                Token::Str("custom_id"),
+                Token::Str("ban"),
+                Token::StructEnd,
+                Token::Str("id"),
+                Token::NewtypeStruct {
+                    name: "InteractionId",
+                },
+                Token::Str("3"),
+                Token::Str("type"),
+                Token::U8(3),
+                Token::Str("token"),
+                Token::Str("token"),
+                Token::Str("user"),
+                Token::Some,
+                Token::Struct {
+                    name: "User",
+                    len: 5,
+                },
+                Token::Str("avatar"),
+                Token::None,
+                Token::Str("bot"),
+                Token::Bool(false),
+                Token::Str("discriminator"),
+                Token::Str("0001"),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "UserId" },
+                Token::Str("4"),
+                Token::Str("username"),
+                Token::Str("twilight"),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_author() {
+        let json = r#"{
+            "application_id": "1",
+            "channel_id": "2",
+            "data": {"custom_id": "ban", "component_type": 2},
+            "guild_id": "5",
+            "id": "3",
+            "member": {
+                "joined_at": "2021-06-01T00:00:00.000000+00:00",
+                "nick": null,
+                "permissions": "8",
+                "premium_since": null,
+                "roles": [],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0001",
+                    "id": "4",
+                    "username": "twilight"
+                }
+            },
+            "token": "token",
+            "type": 3
+        }"#;
+        let interaction = serde_json::from_str::<Interaction>(json).unwrap();
+
+        assert_eq!(Some(UserId(4)), interaction.author().map(|user| user.id));
+        assert!(interaction.user.is_none());
+    }
+}
@@ -0,0 +1,12 @@
+//! Application commands, which users invoke through Discord's interface,
+//! message components, which users interact with on messages, the
+//! interactions created when users use either, and the responses to them.
+//!
+//! See the [Discord documentation] for more information.
+//!
+//! [Discord documentation]: https://discord.com/developers/docs/interactions/slash-commands
+
+pub mod callback;
+pub mod command;
+pub mod component;
+pub mod interaction;
@@ -0,0 +1,104 @@
+use crate::id::{RoleId, UserId};
+use serde::{Deserialize, Serialize};
+
+/// Mentions in the content of a message that notify the mentioned users.
+///
+/// By default no mentions are parsed, so nobody is notified.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AllowedMentions {
+    /// Types of mentions that notify everyone they mention.
+    #[serde(default)]
+    pub parse: Vec<ParseTypes>,
+    /// Whether the author of the message being replied to is notified.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub replied_user: bool,
+    /// Roles whose mentions notify their members.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<RoleId>,
+    /// Users whose mentions notify them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<UserId>,
+}
+
+/// Type of mentions in [`AllowedMentions::parse`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(rename_all = "lowercase")]
+pub enum ParseTypes {
+    /// `@everyone` and `@here` mentions.
+    Everyone,
+    /// Role mentions.
+    Roles,
+    /// User mentions.
+    Users,
+}
+
+// Serde passes fields to `skip_serializing_if` functions by reference.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AllowedMentions, ParseTypes};
+    use crate::id::{RoleId, UserId};
+    use serde_test::Token;
+
+    #[test]
+    fn test_default() {
+        serde_test::assert_tokens(
+            &AllowedMentions::default(),
+            &[
+                Token::Struct {
+                    name: "AllowedMentions",
+                    len: 1,
+                },
+                Token::Str("parse"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_allowed_mentions() {
+        let value = AllowedMentions {
+            parse: vec![ParseTypes::Everyone],
+            replied_user: true,
+            roles: vec![RoleId(1)],
+            users: vec![UserId(2)],
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AllowedMentions",
+                    len: 4,
+                },
+                Token::Str("parse"),
+                Token::Seq { len: Some(1) },
+                Token::UnitVariant {
+                    name: "ParseTypes",
+                    variant: "everyone",
+                },
+                Token::SeqEnd,
+                Token::Str("replied_user"),
+                Token::Bool(true),
+                Token::Str("roles"),
+                Token::Seq { len: Some(1) },
+                Token::NewtypeStruct { name: "RoleId" },
+                Token::Str("1"),
+                Token::SeqEnd,
+                Token::Str("users"),
+                Token::Seq { len: Some(1) },
+                Token::NewtypeStruct { name: "UserId" },
+                Token::Str("2"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
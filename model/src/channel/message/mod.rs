@@ -2,6 +2,7 @@ pub mod sticker;
 
 mod activity;
 mod activity_type;
+mod allowed_mentions;
 mod application;
 mod flags;
 mod kind;
@@ -10,9 +11,16 @@ mod reaction;
 mod reference;
 
 pub use self::{
-    activity::MessageActivity, activity_type::MessageActivityType, application::MessageApplication,
-    flags::MessageFlags, kind::MessageType, mention::Mention, reaction::MessageReaction,
-    reference::MessageReference, sticker::Sticker,
+    activity::MessageActivity,
+    activity_type::MessageActivityType,
+    allowed_mentions::{AllowedMentions, ParseTypes},
+    application::MessageApplication,
+    flags::MessageFlags,
+    kind::MessageType,
+    mention::Mention,
+    reaction::MessageReaction,
+    reference::MessageReference,
+    sticker::Sticker,
 };
 
 use crate::{
@@ -131,13 +139,16 @@ mod tests {
             reference: None,
             stickers: vec![Sticker {
                 asset: "foo1".to_owned(),
+                available: None,
                 description: "foo2".to_owned(),
                 format_type: StickerFormatType::Png,
                 id: StickerId(1),
                 name: "sticker name".to_owned(),
-                pack_id: StickerPackId(2),
+                guild_id: None,
+                pack_id: Some(StickerPackId(2)),
                 preview_asset: None,
                 tags: Some("foo,bar,baz".to_owned()),
+                user: None,
             }],
             referenced_message: None,
             timestamp: "2020-02-02T02:02:02.020000+00:00".to_owned(),
@@ -243,6 +254,7 @@ mod tests {
                 Token::Str("name"),
                 Token::Str("sticker name"),
                 Token::Str("pack_id"),
+                Token::Some,
                 Token::NewtypeStruct {
                     name: "StickerPackId",
                 },
@@ -334,13 +346,16 @@ mod tests {
             }),
             stickers: vec![Sticker {
                 asset: "foo1".to_owned(),
+                available: None,
                 description: "foo2".to_owned(),
                 format_type: StickerFormatType::Png,
                 id: StickerId(1),
                 name: "sticker name".to_owned(),
-                pack_id: StickerPackId(2),
+                guild_id: None,
+                pack_id: Some(StickerPackId(2)),
                 preview_asset: None,
                 tags: Some("foo,bar,baz".to_owned()),
+                user: None,
             }],
             referenced_message: None,
             timestamp: "2020-02-02T02:02:02.020000+00:00".to_owned(),
@@ -527,6 +542,7 @@ mod tests {
                 Token::Str("name"),
                 Token::Str("sticker name"),
                 Token::Str("pack_id"),
+                Token::Some,
                 Token::NewtypeStruct {
                     name: "StickerPackId",
                 },
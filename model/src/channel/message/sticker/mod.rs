@@ -12,46 +12,63 @@ pub use self::{
     kind::{StickerFormatType, StickerFormatTypeConversionError},
 };
 
+use crate::{id::GuildId, user::User};
 use serde::{Deserialize, Serialize};
 
-/// Message sticker.
+/// Message sticker, or a sticker of a guild.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct Sticker {
     /// Hash of the asset.
     pub asset: String,
+    /// Whether the sticker can be used, which may be false for stickers of a
+    /// guild that lost its premium tier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available: Option<bool>,
     /// Description of the sticker.
     pub description: String,
     /// Format type.
     pub format_type: StickerFormatType,
+    /// ID of the guild the sticker is in, if it's a sticker of a guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
     /// Unique ID of the sticker.
     pub id: StickerId,
     /// Name of the sticker.
     pub name: String,
-    /// Unique ID of the pack the sticker is in.
-    pub pack_id: StickerPackId,
+    /// Unique ID of the pack the sticker is in, if it's a sticker of a pack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<StickerPackId>,
     /// Hash of the preview asset, if it has one.
     pub preview_asset: Option<String>,
     /// CSV list of tags the sticker is assigned to, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<String>,
+    /// User who uploaded the sticker, if it's a sticker of a guild and the
+    /// current user has the permission to manage emojis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Sticker, StickerFormatType, StickerId, StickerPackId};
+    use crate::id::GuildId;
     use serde_test::Token;
 
     #[test]
     fn test_minimal() {
         let value = Sticker {
             asset: "foo1".to_owned(),
+            available: None,
             description: "foo2".to_owned(),
             format_type: StickerFormatType::Png,
             id: StickerId(1),
             name: "sticker name".to_owned(),
-            pack_id: StickerPackId(2),
+            guild_id: None,
+            pack_id: Some(StickerPackId(2)),
             preview_asset: None,
             tags: Some("foo,bar,baz".to_owned()),
+            user: None,
         };
 
         serde_test::assert_tokens(
@@ -73,6 +90,7 @@ mod tests {
                 Token::Str("name"),
                 Token::Str("sticker name"),
                 Token::Str("pack_id"),
+                Token::Some,
                 Token::NewtypeStruct {
                     name: "StickerPackId",
                 },
@@ -86,4 +104,55 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_guild_sticker() {
+        let value = Sticker {
+            asset: String::new(),
+            available: Some(true),
+            description: "foo2".to_owned(),
+            format_type: StickerFormatType::Lottie,
+            guild_id: Some(GuildId(3)),
+            id: StickerId(1),
+            name: "sticker name".to_owned(),
+            pack_id: None,
+            preview_asset: None,
+            tags: Some("foo".to_owned()),
+            user: None,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Sticker",
+                    len: 9,
+                },
+                Token::Str("asset"),
+                Token::Str(""),
+                Token::Str("available"),
+                Token::Some,
+                Token::Bool(true),
+                Token::Str("description"),
+                Token::Str("foo2"),
+                Token::Str("format_type"),
+                Token::U8(3),
+                Token::Str("guild_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "GuildId" },
+                Token::Str("3"),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "StickerId" },
+                Token::Str("1"),
+                Token::Str("name"),
+                Token::Str("sticker name"),
+                Token::Str("preview_asset"),
+                Token::None,
+                Token::Str("tags"),
+                Token::Some,
+                Token::Str("foo"),
+                Token::StructEnd,
+            ],
+        );
+    }
 }
@@ -1,6 +1,7 @@
 pub mod embed;
 pub mod message;
 pub mod permission_overwrite;
+pub mod thread;
 
 mod attachment;
 mod category_channel;
@@ -20,11 +21,12 @@ pub use self::{
     attachment::Attachment, category_channel::CategoryChannel, channel_mention::ChannelMention,
     channel_type::ChannelType, followed_channel::FollowedChannel, group::Group, message::Message,
     private_channel::PrivateChannel, reaction::Reaction, reaction_type::ReactionType,
-    text_channel::TextChannel, voice_channel::VoiceChannel, webhook::Webhook,
-    webhook_type::WebhookType,
+    text_channel::TextChannel, thread::ThreadChannel, voice_channel::VoiceChannel,
+    webhook::Webhook, webhook_type::WebhookType,
 };
 
-use crate::id::{ChannelId, GuildId, MessageId};
+use self::thread::ThreadMember;
+use crate::id::{ChannelId, GuildId, MessageId, UserId};
 use serde::{
     de::{Deserializer, Error as DeError, IgnoredAny, MapAccess, Visitor},
     Deserialize, Serialize,
@@ -83,6 +85,7 @@ impl Channel {
 pub enum GuildChannel {
     Category(CategoryChannel),
     Text(TextChannel),
+    Thread(ThreadChannel),
     Voice(VoiceChannel),
 }
 
@@ -92,6 +95,7 @@ impl GuildChannel {
         match self {
             Self::Category(category) => category.guild_id,
             Self::Text(text) => text.guild_id,
+            Self::Thread(thread) => thread.guild_id,
             Self::Voice(voice) => voice.guild_id,
         }
     }
@@ -101,6 +105,7 @@ impl GuildChannel {
         match self {
             Self::Category(category) => category.id,
             Self::Text(text) => text.id,
+            Self::Thread(thread) => thread.id,
             Self::Voice(voice) => voice.id,
         }
     }
@@ -110,6 +115,7 @@ impl GuildChannel {
         match self {
             Self::Category(category) => category.name.as_ref(),
             Self::Text(text) => text.name.as_ref(),
+            Self::Thread(thread) => thread.name.as_ref(),
             Self::Voice(voice) => voice.name.as_ref(),
         }
     }
@@ -123,12 +129,17 @@ enum GuildChannelField {
     Id,
     LastMessageId,
     LastPinTimestamp,
+    Member,
+    MemberCount,
+    MessageCount,
     Name,
     Nsfw,
+    OwnerId,
     ParentId,
     PermissionOverwrites,
     Position,
     RateLimitPerUser,
+    ThreadMetadata,
     Topic,
     Type,
     UserLimit,
@@ -148,6 +159,9 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
         const VARIANTS: &[&str] = &[
             "GuildCategory",
             "GuildNews",
+            "GuildNewsThread",
+            "GuildPrivateThread",
+            "GuildPublicThread",
             "GuildStore",
             "GuildText",
             "GuildVoice",
@@ -156,15 +170,20 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
         let mut bitrate = None;
         let mut guild_id = None;
         let mut id = None;
-        let mut kind = None;
+        let mut kind: Option<ChannelType> = None;
         let mut last_message_id: Option<Option<MessageId>> = None;
         let mut last_pin_timestamp: Option<Option<String>> = None;
+        let mut member: Option<Option<ThreadMember>> = None;
+        let mut member_count = None;
+        let mut message_count = None;
         let mut name = None;
         let mut nsfw = None;
+        let mut owner_id: Option<Option<UserId>> = None;
         let mut parent_id: Option<Option<ChannelId>> = None;
         let mut permission_overwrites = None;
         let mut position = None;
         let mut rate_limit_per_user = None;
+        let mut thread_metadata = None;
         let mut topic: Option<Option<String>> = None;
         let mut user_limit = None;
 
@@ -235,6 +254,27 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
 
                     last_pin_timestamp = Some(map.next_value()?);
                 }
+                GuildChannelField::Member => {
+                    if member.is_some() {
+                        return Err(DeError::duplicate_field("member"));
+                    }
+
+                    member = Some(map.next_value()?);
+                }
+                GuildChannelField::MemberCount => {
+                    if member_count.is_some() {
+                        return Err(DeError::duplicate_field("member_count"));
+                    }
+
+                    member_count = Some(map.next_value()?);
+                }
+                GuildChannelField::MessageCount => {
+                    if message_count.is_some() {
+                        return Err(DeError::duplicate_field("message_count"));
+                    }
+
+                    message_count = Some(map.next_value()?);
+                }
                 GuildChannelField::Name => {
                     if name.is_some() {
                         return Err(DeError::duplicate_field("name"));
@@ -249,6 +289,13 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
 
                     nsfw = Some(map.next_value()?);
                 }
+                GuildChannelField::OwnerId => {
+                    if owner_id.is_some() {
+                        return Err(DeError::duplicate_field("owner_id"));
+                    }
+
+                    owner_id = Some(map.next_value()?);
+                }
                 GuildChannelField::ParentId => {
                     if parent_id.is_some() {
                         return Err(DeError::duplicate_field("parent_id"));
@@ -277,6 +324,13 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
 
                     rate_limit_per_user = Some(map.next_value()?);
                 }
+                GuildChannelField::ThreadMetadata => {
+                    if thread_metadata.is_some() {
+                        return Err(DeError::duplicate_field("thread_metadata"));
+                    }
+
+                    thread_metadata = Some(map.next_value()?);
+                }
                 GuildChannelField::Topic => {
                     if topic.is_some() {
                         return Err(DeError::duplicate_field("topic"));
@@ -299,9 +353,6 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
         let id = id.ok_or_else(|| DeError::missing_field("id"))?;
         let kind = kind.ok_or_else(|| DeError::missing_field("type"))?;
         let name = name.ok_or_else(|| DeError::missing_field("name"))?;
-        let permission_overwrites =
-            permission_overwrites.ok_or_else(|| DeError::missing_field("permission_overwrites"))?;
-        let position = position.ok_or_else(|| DeError::missing_field("position"))?;
 
         let nsfw = nsfw.unwrap_or_default();
         let parent_id = parent_id.unwrap_or_default();
@@ -312,9 +363,51 @@ impl<'de> Visitor<'de> for GuildChannelVisitor {
             %name,
             %nsfw,
             ?parent_id,
+            "common fields of all variants exist"
+        );
+
+        // Threads don't have permission overwrites or a position, unlike
+        // every other guild channel type.
+        if kind.is_thread() {
+            let last_message_id = last_message_id.unwrap_or_default();
+            let member = member.unwrap_or_default();
+            let member_count = member_count.unwrap_or_default();
+            let message_count = message_count.unwrap_or_default();
+            let owner_id = owner_id.unwrap_or_default();
+            let thread_metadata =
+                thread_metadata.ok_or_else(|| DeError::missing_field("thread_metadata"))?;
+
+            tracing::trace!(
+                ?last_message_id,
+                ?owner_id,
+                ?thread_metadata,
+                "handling thread"
+            );
+
+            return Ok(GuildChannel::Thread(ThreadChannel {
+                guild_id,
+                id,
+                kind,
+                last_message_id,
+                member,
+                member_count,
+                message_count,
+                name,
+                owner_id,
+                parent_id,
+                rate_limit_per_user,
+                thread_metadata,
+            }));
+        }
+
+        let permission_overwrites =
+            permission_overwrites.ok_or_else(|| DeError::missing_field("permission_overwrites"))?;
+        let position = position.ok_or_else(|| DeError::missing_field("position"))?;
+
+        tracing::trace!(
             ?permission_overwrites,
             %position,
-            "common fields of all variants exist"
+            "common fields of non-thread variants exist"
         );
 
         Ok(match kind {
@@ -389,8 +482,9 @@ impl<'de> Deserialize<'de> for GuildChannel {
 #[cfg(test)]
 mod tests {
     use super::{
+        thread::{ThreadMember, ThreadMetadata},
         CategoryChannel, Channel, ChannelType, Group, GuildChannel, PrivateChannel, TextChannel,
-        VoiceChannel,
+        ThreadChannel, VoiceChannel,
     };
     use crate::{
         channel::permission_overwrite::PermissionOverwrite,
@@ -681,4 +775,61 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn test_guild_thread_channel_deserialization() {
+        let value = GuildChannel::Thread(ThreadChannel {
+            guild_id: Some(GuildId(1)),
+            id: ChannelId(2),
+            kind: ChannelType::GuildPrivateThread,
+            last_message_id: Some(MessageId(3)),
+            member: Some(ThreadMember {
+                flags: 0,
+                id: Some(ChannelId(2)),
+                join_timestamp: "2021-07-01T00:00:00.000000+00:00".to_owned(),
+                user_id: Some(UserId(4)),
+            }),
+            member_count: 5,
+            message_count: 6,
+            name: "thread".to_owned(),
+            owner_id: Some(UserId(4)),
+            parent_id: Some(ChannelId(7)),
+            rate_limit_per_user: Some(0),
+            thread_metadata: ThreadMetadata {
+                archive_timestamp: "2021-07-01T00:00:00.000000+00:00".to_owned(),
+                archived: true,
+                auto_archive_duration: 1440,
+                locked: true,
+            },
+        });
+
+        assert_eq!(
+            value,
+            serde_json::from_value(serde_json::json!({
+                "guild_id": "1",
+                "id": "2",
+                "last_message_id": "3",
+                "member": {
+                    "flags": 0,
+                    "id": "2",
+                    "join_timestamp": "2021-07-01T00:00:00.000000+00:00",
+                    "user_id": "4",
+                },
+                "member_count": 5,
+                "message_count": 6,
+                "name": "thread",
+                "owner_id": "4",
+                "parent_id": "7",
+                "rate_limit_per_user": 0,
+                "thread_metadata": {
+                    "archive_timestamp": "2021-07-01T00:00:00.000000+00:00",
+                    "archived": true,
+                    "auto_archive_duration": 1440,
+                    "locked": true,
+                },
+                "type": 12,
+            }))
+            .unwrap()
+        );
+    }
 }
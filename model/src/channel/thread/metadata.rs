@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Archival state of a thread.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ThreadMetadata {
+    /// Timestamp of when the archival state of the thread last changed.
+    pub archive_timestamp: String,
+    /// Whether the thread is archived.
+    pub archived: bool,
+    /// Number of minutes of inactivity after which the thread is archived.
+    pub auto_archive_duration: u64,
+    /// Whether the thread is locked, in which case only members with the
+    /// permission to manage threads can unarchive it.
+    #[serde(default)]
+    pub locked: bool,
+}
@@ -0,0 +1,140 @@
+//! Threads of guild text and news channels.
+//!
+//! See the [Discord documentation] for more information.
+//!
+//! [Discord documentation]: https://discord.com/developers/docs/topics/threads
+
+mod member;
+mod metadata;
+
+pub use self::{member::ThreadMember, metadata::ThreadMetadata};
+
+use crate::{
+    channel::ChannelType,
+    id::{ChannelId, GuildId, MessageId, UserId},
+};
+use serde::{Deserialize, Serialize};
+
+/// Thread of a guild text or news channel.
+///
+/// Its kind is one of [`ChannelType::GuildNewsThread`],
+/// [`ChannelType::GuildPublicThread`], or [`ChannelType::GuildPrivateThread`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ThreadChannel {
+    /// ID of the guild the thread is in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// Unique ID of the thread.
+    pub id: ChannelId,
+    /// Type of the thread.
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    /// ID of the last message sent in the thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_message_id: Option<MessageId>,
+    /// Thread member of the current user, if they joined the thread.
+    ///
+    /// Only present in some events, such as thread list syncs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<ThreadMember>,
+    /// Approximate number of members in the thread, stopping at 50.
+    #[serde(default)]
+    pub member_count: u8,
+    /// Approximate number of messages in the thread, stopping at 50.
+    #[serde(default)]
+    pub message_count: u8,
+    /// Name of the thread.
+    pub name: String,
+    /// ID of the user who created the thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<UserId>,
+    /// ID of the channel the thread was created in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ChannelId>,
+    /// Number of seconds members have to wait between sending messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+    /// Whether the thread is archived or locked, and when it's archived.
+    pub thread_metadata: ThreadMetadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ThreadChannel, ThreadMetadata};
+    use crate::{
+        channel::ChannelType,
+        id::{ChannelId, GuildId, UserId},
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_thread_channel() {
+        let value = ThreadChannel {
+            guild_id: Some(GuildId(1)),
+            id: ChannelId(2),
+            kind: ChannelType::GuildPublicThread,
+            last_message_id: None,
+            member: None,
+            member_count: 3,
+            message_count: 4,
+            name: "a thread".to_owned(),
+            owner_id: Some(UserId(5)),
+            parent_id: Some(ChannelId(6)),
+            rate_limit_per_user: None,
+            thread_metadata: ThreadMetadata {
+                archive_timestamp: "2021-07-01T00:00:00.000000+00:00".to_owned(),
+                archived: false,
+                auto_archive_duration: 60,
+                locked: false,
+            },
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "ThreadChannel",
+                    len: 9,
+                },
+                Token::Str("guild_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "GuildId" },
+                Token::Str("1"),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("2"),
+                Token::Str("type"),
+                Token::U8(11),
+                Token::Str("member_count"),
+                Token::U8(3),
+                Token::Str("message_count"),
+                Token::U8(4),
+                Token::Str("name"),
+                Token::Str("a thread"),
+                Token::Str("owner_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "UserId" },
+                Token::Str("5"),
+                Token::Str("parent_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("6"),
+                Token::Str("thread_metadata"),
+                Token::Struct {
+                    name: "ThreadMetadata",
+                    len: 4,
+                },
+                Token::Str("archive_timestamp"),
+                Token::Str("2021-07-01T00:00:00.000000+00:00"),
+                Token::Str("archived"),
+                Token::Bool(false),
+                Token::Str("auto_archive_duration"),
+                Token::U64(60),
+                Token::Str("locked"),
+                Token::Bool(false),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -60,4 +60,49 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_connection_info_json() {
+        let input = r#"{
+            "url": "wss://gateway.discord.gg",
+            "shards": 48,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 999,
+                "reset_after": 14400000,
+                "max_concurrency": 16
+            }
+        }"#;
+
+        let expected = BotConnectionInfo {
+            session_start_limit: SessionStartLimit {
+                max_concurrency: 16,
+                remaining: 999,
+                reset_after: 14_400_000,
+                total: 1000,
+            },
+            shards: 48,
+            url: "wss://gateway.discord.gg".to_owned(),
+        };
+
+        assert_eq!(
+            expected,
+            serde_json::from_str::<BotConnectionInfo>(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_connection_info_json_missing_max_concurrency() {
+        let input = r#"{
+            "url": "wss://gateway.discord.gg",
+            "shards": 1,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 999,
+                "reset_after": 14400000
+            }
+        }"#;
+
+        assert!(serde_json::from_str::<BotConnectionInfo>(input).is_err());
+    }
 }
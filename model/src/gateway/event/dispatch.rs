@@ -26,7 +26,9 @@ pub enum DispatchEvent {
     GuildDelete(Box<GuildDelete>),
     GuildEmojisUpdate(GuildEmojisUpdate),
     GuildIntegrationsUpdate(GuildIntegrationsUpdate),
+    GuildStickersUpdate(GuildStickersUpdate),
     GuildUpdate(Box<GuildUpdate>),
+    InteractionCreate(Box<InteractionCreate>),
     InviteCreate(Box<InviteCreate>),
     InviteDelete(InviteDelete),
     MemberAdd(Box<MemberAdd>),
@@ -48,6 +50,12 @@ pub enum DispatchEvent {
     RoleCreate(RoleCreate),
     RoleDelete(RoleDelete),
     RoleUpdate(RoleUpdate),
+    ThreadCreate(ThreadCreate),
+    ThreadDelete(ThreadDelete),
+    ThreadListSync(ThreadListSync),
+    ThreadMemberUpdate(ThreadMemberUpdate),
+    ThreadMembersUpdate(ThreadMembersUpdate),
+    ThreadUpdate(ThreadUpdate),
     TypingStart(Box<TypingStart>),
     UnavailableGuild(UnavailableGuild),
     UserUpdate(UserUpdate),
@@ -71,7 +79,9 @@ impl DispatchEvent {
             Self::GuildDelete(_) => EventType::GuildDelete,
             Self::GuildEmojisUpdate(_) => EventType::GuildEmojisUpdate,
             Self::GuildIntegrationsUpdate(_) => EventType::GuildIntegrationsUpdate,
+            Self::GuildStickersUpdate(_) => EventType::GuildStickersUpdate,
             Self::GuildUpdate(_) => EventType::GuildUpdate,
+            Self::InteractionCreate(_) => EventType::InteractionCreate,
             Self::InviteCreate(_) => EventType::InviteCreate,
             Self::InviteDelete(_) => EventType::InviteDelete,
             Self::MemberAdd(_) => EventType::MemberAdd,
@@ -93,6 +103,12 @@ impl DispatchEvent {
             Self::RoleCreate(_) => EventType::RoleCreate,
             Self::RoleDelete(_) => EventType::RoleDelete,
             Self::RoleUpdate(_) => EventType::RoleUpdate,
+            Self::ThreadCreate(_) => EventType::ThreadCreate,
+            Self::ThreadDelete(_) => EventType::ThreadDelete,
+            Self::ThreadListSync(_) => EventType::ThreadListSync,
+            Self::ThreadMemberUpdate(_) => EventType::ThreadMemberUpdate,
+            Self::ThreadMembersUpdate(_) => EventType::ThreadMembersUpdate,
+            Self::ThreadUpdate(_) => EventType::ThreadUpdate,
             Self::TypingStart(_) => EventType::TypingStart,
             Self::UnavailableGuild(_) => EventType::UnavailableGuild,
             Self::UserUpdate(_) => EventType::UserUpdate,
@@ -119,7 +135,9 @@ impl TryFrom<Event> for DispatchEvent {
             Event::GuildDelete(v) => Self::GuildDelete(v),
             Event::GuildEmojisUpdate(v) => Self::GuildEmojisUpdate(v),
             Event::GuildIntegrationsUpdate(v) => Self::GuildIntegrationsUpdate(v),
+            Event::GuildStickersUpdate(v) => Self::GuildStickersUpdate(v),
             Event::GuildUpdate(v) => Self::GuildUpdate(v),
+            Event::InteractionCreate(v) => Self::InteractionCreate(v),
             Event::InviteCreate(v) => Self::InviteCreate(v),
             Event::InviteDelete(v) => Self::InviteDelete(v),
             Event::MemberAdd(v) => Self::MemberAdd(v),
@@ -141,6 +159,12 @@ impl TryFrom<Event> for DispatchEvent {
             Event::RoleCreate(v) => Self::RoleCreate(v),
             Event::RoleDelete(v) => Self::RoleDelete(v),
             Event::RoleUpdate(v) => Self::RoleUpdate(v),
+            Event::ThreadCreate(v) => Self::ThreadCreate(v),
+            Event::ThreadDelete(v) => Self::ThreadDelete(v),
+            Event::ThreadListSync(v) => Self::ThreadListSync(v),
+            Event::ThreadMemberUpdate(v) => Self::ThreadMemberUpdate(v),
+            Event::ThreadMembersUpdate(v) => Self::ThreadMembersUpdate(v),
+            Event::ThreadUpdate(v) => Self::ThreadUpdate(v),
             Event::TypingStart(v) => Self::TypingStart(v),
             Event::UnavailableGuild(v) => Self::UnavailableGuild(v),
             Event::UserUpdate(v) => Self::UserUpdate(v),
@@ -203,6 +227,9 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "GUILD_INTEGRATIONS_UPDATE" => DispatchEvent::GuildIntegrationsUpdate(
                 GuildIntegrationsUpdate::deserialize(deserializer)?,
             ),
+            "GUILD_STICKERS_UPDATE" => {
+                DispatchEvent::GuildStickersUpdate(GuildStickersUpdate::deserialize(deserializer)?)
+            }
             "GUILD_MEMBERS_CHUNK" => {
                 DispatchEvent::MemberChunk(MemberChunk::deserialize(deserializer)?)
             }
@@ -227,6 +254,9 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "GUILD_UPDATE" => {
                 DispatchEvent::GuildUpdate(Box::new(GuildUpdate::deserialize(deserializer)?))
             }
+            "INTERACTION_CREATE" => DispatchEvent::InteractionCreate(Box::new(
+                InteractionCreate::deserialize(deserializer)?,
+            )),
             "INVITE_CREATE" => {
                 DispatchEvent::InviteCreate(Box::new(InviteCreate::deserialize(deserializer)?))
             }
@@ -271,6 +301,24 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
 
                 DispatchEvent::Resumed
             }
+            "THREAD_CREATE" => {
+                DispatchEvent::ThreadCreate(ThreadCreate::deserialize(deserializer)?)
+            }
+            "THREAD_DELETE" => {
+                DispatchEvent::ThreadDelete(ThreadDelete::deserialize(deserializer)?)
+            }
+            "THREAD_LIST_SYNC" => {
+                DispatchEvent::ThreadListSync(ThreadListSync::deserialize(deserializer)?)
+            }
+            "THREAD_MEMBER_UPDATE" => {
+                DispatchEvent::ThreadMemberUpdate(ThreadMemberUpdate::deserialize(deserializer)?)
+            }
+            "THREAD_MEMBERS_UPDATE" => {
+                DispatchEvent::ThreadMembersUpdate(ThreadMembersUpdate::deserialize(deserializer)?)
+            }
+            "THREAD_UPDATE" => {
+                DispatchEvent::ThreadUpdate(ThreadUpdate::deserialize(deserializer)?)
+            }
             "TYPING_START" => {
                 DispatchEvent::TypingStart(Box::new(TypingStart::deserialize(deserializer)?))
             }
@@ -292,6 +340,7 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
 #[cfg(test)]
 mod tests {
     use super::{DispatchEvent, DispatchEventWithTypeDeserializer};
+    use crate::application::interaction::InteractionType;
     use serde::de::DeserializeSeed;
     use serde_json::Deserializer;
 
@@ -308,4 +357,35 @@ mod tests {
 
         assert_eq!(event, DispatchEvent::GiftCodeUpdate);
     }
+
+    #[test]
+    fn test_interaction_create() {
+        let input = r#"{
+            "application_id": "1",
+            "channel_id": "2",
+            "data": {
+                "component_type": 2,
+                "custom_id": "confirm"
+            },
+            "id": "3",
+            "token": "token",
+            "type": 3,
+            "user": {
+                "avatar": null,
+                "discriminator": "0001",
+                "id": "4",
+                "username": "twilight"
+            }
+        }"#;
+
+        let deserializer = DispatchEventWithTypeDeserializer::new("INTERACTION_CREATE");
+        let mut json_deserializer = Deserializer::from_str(input);
+        let event = deserializer.deserialize(&mut json_deserializer).unwrap();
+
+        assert!(matches!(
+            event,
+            DispatchEvent::InteractionCreate(interaction)
+                if interaction.kind == InteractionType::MessageComponent
+        ));
+    }
 }
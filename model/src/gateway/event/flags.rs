@@ -1,6 +1,7 @@
+use super::EventType;
+use crate::gateway::Intents;
 use bitflags::bitflags;
 use std::convert::TryFrom;
-use twilight_model::gateway::event::EventType;
 
 bitflags! {
     /// Bitflags representing all of the possible types of events.
@@ -41,8 +42,12 @@ bitflags! {
         const GUILD_EMOJIS_UPDATE = 1 << 12;
         /// A guild's integrations have been updated.
         const GUILD_INTEGRATIONS_UPDATE = 1 << 13;
+        /// A guild's stickers have been updated.
+        const GUILD_STICKERS_UPDATE = 1 << 51;
         /// A guild has been updated.
         const GUILD_UPDATE = 1 << 14;
+        /// User has invoked a command or used a message component.
+        const INTERACTION_CREATE = 1 << 58;
         /// Invite for a channel has been created.
         const INVITE_CREATE = 1 << 46;
         /// Invite for a channel has been deleted.
@@ -100,13 +105,32 @@ bitflags! {
         /// Shard is identifying to create a session with the gateway.
         const SHARD_IDENTIFYING = 1 << 36;
         /// Incoming message has been received from the gateway.
+        ///
+        /// Unlike the other event types this one isn't emitted by default.
+        /// It carries the bytes of every message as they're received, after
+        /// transport decompression but before being deserialized, so it's
+        /// emitted even for messages of event types that aren't.
         const SHARD_PAYLOAD = 1 << 45;
         /// Shard is reconnecting to the gateway.
         const SHARD_RECONNECTING = 1 << 37;
         /// Shard is resuming a session with the gateway.
         const SHARD_RESUMING = 1 << 38;
+        /// Shard has stopped after an error it can't recover from.
+        const SHARD_STOPPED = 1 << 50;
         /// User has begun typing in a channel.
         const TYPING_START = 1 << 39;
+        /// Thread has been created.
+        const THREAD_CREATE = 1 << 52;
+        /// Thread has been deleted.
+        const THREAD_DELETE = 1 << 53;
+        /// Active threads of channels have been synced.
+        const THREAD_LIST_SYNC = 1 << 54;
+        /// Thread member of the current user has been updated.
+        const THREAD_MEMBER_UPDATE = 1 << 55;
+        /// Members have been added to or removed from a thread.
+        const THREAD_MEMBERS_UPDATE = 1 << 56;
+        /// Thread has been updated.
+        const THREAD_UPDATE = 1 << 57;
         /// Guild is unavailable, potentially due to an outage.
         const UNAVAILABLE_GUILD = 1 << 40;
         /// Current user's profile has been updated.
@@ -120,6 +144,161 @@ bitflags! {
     }
 }
 
+impl EventTypeFlags {
+    /// Event types delivered by each intent.
+    ///
+    /// Event types not delivered by any intent, such as [`READY`] or the
+    /// shard's own events, are always delivered.
+    ///
+    /// [`READY`]: Self::READY
+    const INTENT_EVENT_TYPES: [(Intents, EventTypeFlags); 15] = [
+        (
+            Intents::GUILDS,
+            EventTypeFlags::from_bits_truncate(
+                EventTypeFlags::CHANNEL_CREATE.bits()
+                    | EventTypeFlags::CHANNEL_DELETE.bits()
+                    | EventTypeFlags::CHANNEL_PINS_UPDATE.bits()
+                    | EventTypeFlags::CHANNEL_UPDATE.bits()
+                    | EventTypeFlags::GUILD_CREATE.bits()
+                    | EventTypeFlags::GUILD_DELETE.bits()
+                    | EventTypeFlags::GUILD_UPDATE.bits()
+                    | EventTypeFlags::ROLE_CREATE.bits()
+                    | EventTypeFlags::ROLE_DELETE.bits()
+                    | EventTypeFlags::ROLE_UPDATE.bits()
+                    | EventTypeFlags::THREAD_CREATE.bits()
+                    | EventTypeFlags::THREAD_DELETE.bits()
+                    | EventTypeFlags::THREAD_LIST_SYNC.bits()
+                    | EventTypeFlags::THREAD_MEMBER_UPDATE.bits()
+                    | EventTypeFlags::THREAD_MEMBERS_UPDATE.bits()
+                    | EventTypeFlags::THREAD_UPDATE.bits()
+                    | EventTypeFlags::UNAVAILABLE_GUILD.bits(),
+            ),
+        ),
+        (
+            Intents::GUILD_MEMBERS,
+            EventTypeFlags::from_bits_truncate(
+                EventTypeFlags::MEMBER_ADD.bits()
+                    | EventTypeFlags::MEMBER_CHUNK.bits()
+                    | EventTypeFlags::MEMBER_REMOVE.bits()
+                    | EventTypeFlags::MEMBER_UPDATE.bits()
+                    | EventTypeFlags::THREAD_MEMBERS_UPDATE.bits(),
+            ),
+        ),
+        (
+            Intents::GUILD_BANS,
+            EventTypeFlags::from_bits_truncate(
+                EventTypeFlags::BAN_ADD.bits() | EventTypeFlags::BAN_REMOVE.bits(),
+            ),
+        ),
+        (
+            Intents::GUILD_EMOJIS,
+            EventTypeFlags::from_bits_truncate(
+                EventTypeFlags::GUILD_EMOJIS_UPDATE.bits()
+                    | EventTypeFlags::GUILD_STICKERS_UPDATE.bits(),
+            ),
+        ),
+        (
+            Intents::GUILD_INTEGRATIONS,
+            EventTypeFlags::GUILD_INTEGRATIONS_UPDATE,
+        ),
+        (Intents::GUILD_WEBHOOKS, EventTypeFlags::WEBHOOKS_UPDATE),
+        (
+            Intents::GUILD_INVITES,
+            EventTypeFlags::from_bits_truncate(
+                EventTypeFlags::INVITE_CREATE.bits() | EventTypeFlags::INVITE_DELETE.bits(),
+            ),
+        ),
+        (
+            Intents::GUILD_VOICE_STATES,
+            EventTypeFlags::VOICE_STATE_UPDATE,
+        ),
+        (Intents::GUILD_PRESENCES, EventTypeFlags::PRESENCE_UPDATE),
+        (Intents::GUILD_MESSAGES, Self::MESSAGES),
+        (Intents::GUILD_MESSAGE_REACTIONS, Self::REACTIONS),
+        (Intents::GUILD_MESSAGE_TYPING, EventTypeFlags::TYPING_START),
+        (
+            Intents::DIRECT_MESSAGES,
+            EventTypeFlags::from_bits_truncate(
+                Self::MESSAGES.bits() | EventTypeFlags::CHANNEL_PINS_UPDATE.bits(),
+            ),
+        ),
+        (Intents::DIRECT_MESSAGE_REACTIONS, Self::REACTIONS),
+        (Intents::DIRECT_MESSAGE_TYPING, EventTypeFlags::TYPING_START),
+    ];
+
+    /// Message event types, delivered in guilds and direct messages.
+    const MESSAGES: EventTypeFlags = EventTypeFlags::from_bits_truncate(
+        EventTypeFlags::MESSAGE_CREATE.bits()
+            | EventTypeFlags::MESSAGE_DELETE.bits()
+            | EventTypeFlags::MESSAGE_DELETE_BULK.bits()
+            | EventTypeFlags::MESSAGE_UPDATE.bits(),
+    );
+
+    /// Reaction event types, delivered in guilds and direct messages.
+    const REACTIONS: EventTypeFlags = EventTypeFlags::from_bits_truncate(
+        EventTypeFlags::REACTION_ADD.bits()
+            | EventTypeFlags::REACTION_REMOVE.bits()
+            | EventTypeFlags::REACTION_REMOVE_ALL.bits()
+            | EventTypeFlags::REACTION_REMOVE_EMOJI.bits(),
+    );
+
+    /// Return the intents that deliver at least one of the event types.
+    ///
+    /// Any one of these intents suffices for some event types, such as
+    /// [`MESSAGE_CREATE`] which is delivered by both
+    /// [`Intents::GUILD_MESSAGES`] and [`Intents::DIRECT_MESSAGES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::gateway::{event::EventTypeFlags, Intents};
+    ///
+    /// assert_eq!(
+    ///     Intents::GUILD_MEMBERS,
+    ///     EventTypeFlags::MEMBER_CHUNK.intents(),
+    /// );
+    /// ```
+    ///
+    /// [`MESSAGE_CREATE`]: Self::MESSAGE_CREATE
+    pub fn intents(self) -> Intents {
+        Self::INTENT_EVENT_TYPES
+            .iter()
+            .filter(|(_, event_types)| self.intersects(*event_types))
+            .fold(Intents::empty(), |intents, (intent, _)| intents | *intent)
+    }
+
+    /// Return the event types that aren't delivered under the intents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::gateway::{event::EventTypeFlags, Intents};
+    ///
+    /// let event_types = EventTypeFlags::MEMBER_CHUNK | EventTypeFlags::MESSAGE_CREATE;
+    ///
+    /// assert_eq!(
+    ///     EventTypeFlags::MEMBER_CHUNK,
+    ///     event_types.undeliverable(Intents::GUILD_MESSAGES),
+    /// );
+    /// ```
+    pub fn undeliverable(self, intents: Intents) -> Self {
+        let (gated, delivered) = Self::INTENT_EVENT_TYPES.iter().fold(
+            (Self::empty(), Self::empty()),
+            |(gated, delivered), (intent, event_types)| {
+                let delivered = if intents.contains(*intent) {
+                    delivered | *event_types
+                } else {
+                    delivered
+                };
+
+                (gated | *event_types, delivered)
+            },
+        );
+
+        self & (gated - delivered)
+    }
+}
+
 impl From<EventType> for EventTypeFlags {
     fn from(event_type: EventType) -> Self {
         match event_type {
@@ -139,7 +318,9 @@ impl From<EventType> for EventTypeFlags {
             EventType::GuildDelete => EventTypeFlags::GUILD_DELETE,
             EventType::GuildEmojisUpdate => EventTypeFlags::GUILD_EMOJIS_UPDATE,
             EventType::GuildIntegrationsUpdate => EventTypeFlags::GUILD_INTEGRATIONS_UPDATE,
+            EventType::GuildStickersUpdate => EventTypeFlags::GUILD_STICKERS_UPDATE,
             EventType::GuildUpdate => EventTypeFlags::GUILD_UPDATE,
+            EventType::InteractionCreate => EventTypeFlags::INTERACTION_CREATE,
             EventType::InviteCreate => EventTypeFlags::INVITE_CREATE,
             EventType::InviteDelete => EventTypeFlags::INVITE_DELETE,
             EventType::MemberAdd => EventTypeFlags::MEMBER_ADD,
@@ -168,6 +349,13 @@ impl From<EventType> for EventTypeFlags {
             EventType::ShardReconnecting => EventTypeFlags::SHARD_RECONNECTING,
             EventType::ShardPayload => EventTypeFlags::SHARD_PAYLOAD,
             EventType::ShardResuming => EventTypeFlags::SHARD_RESUMING,
+            EventType::ShardStopped => EventTypeFlags::SHARD_STOPPED,
+            EventType::ThreadCreate => EventTypeFlags::THREAD_CREATE,
+            EventType::ThreadDelete => EventTypeFlags::THREAD_DELETE,
+            EventType::ThreadListSync => EventTypeFlags::THREAD_LIST_SYNC,
+            EventType::ThreadMemberUpdate => EventTypeFlags::THREAD_MEMBER_UPDATE,
+            EventType::ThreadMembersUpdate => EventTypeFlags::THREAD_MEMBERS_UPDATE,
+            EventType::ThreadUpdate => EventTypeFlags::THREAD_UPDATE,
             EventType::TypingStart => EventTypeFlags::TYPING_START,
             EventType::UnavailableGuild => EventTypeFlags::UNAVAILABLE_GUILD,
             EventType::UserUpdate => EventTypeFlags::USER_UPDATE,
@@ -209,7 +397,7 @@ impl Default for EventTypeFlags {
 
 #[cfg(test)]
 mod tests {
-    use super::{EventType, EventTypeFlags};
+    use super::{EventType, EventTypeFlags, Intents};
     use static_assertions::assert_impl_all;
     use std::{convert::TryFrom, fmt::Debug, hash::Hash};
 
@@ -225,4 +413,40 @@ mod tests {
         Sync,
         TryFrom<(u8, Option<&'static str>)>
     );
+
+    #[test]
+    fn test_intents() {
+        assert_eq!(
+            Intents::GUILD_MESSAGES | Intents::DIRECT_MESSAGES,
+            EventTypeFlags::MESSAGE_CREATE.intents()
+        );
+        assert_eq!(
+            Intents::GUILDS | Intents::GUILD_MEMBERS,
+            (EventTypeFlags::GUILD_CREATE | EventTypeFlags::MEMBER_CHUNK).intents()
+        );
+        assert!((EventTypeFlags::READY | EventTypeFlags::SHARD_CONNECTED)
+            .intents()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_undeliverable() {
+        let event_types =
+            EventTypeFlags::MEMBER_CHUNK | EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::READY;
+
+        assert_eq!(
+            EventTypeFlags::MEMBER_CHUNK,
+            event_types.undeliverable(Intents::GUILD_MESSAGES)
+        );
+        assert_eq!(
+            EventTypeFlags::MEMBER_CHUNK,
+            event_types.undeliverable(Intents::DIRECT_MESSAGES)
+        );
+        assert!(event_types
+            .undeliverable(Intents::GUILD_MEMBERS | Intents::GUILD_MESSAGES)
+            .is_empty());
+        assert!(EventTypeFlags::all()
+            .undeliverable(Intents::all())
+            .is_empty());
+    }
 }
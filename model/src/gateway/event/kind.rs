@@ -23,7 +23,9 @@ pub enum EventType {
     GuildDelete,
     GuildEmojisUpdate,
     GuildIntegrationsUpdate,
+    GuildStickersUpdate,
     GuildUpdate,
+    InteractionCreate,
     InviteCreate,
     InviteDelete,
     #[serde(rename = "GUILD_MEMBER_ADD")]
@@ -63,6 +65,13 @@ pub enum EventType {
     ShardReconnecting,
     ShardPayload,
     ShardResuming,
+    ShardStopped,
+    ThreadCreate,
+    ThreadDelete,
+    ThreadListSync,
+    ThreadMemberUpdate,
+    ThreadMembersUpdate,
+    ThreadUpdate,
     TypingStart,
     UnavailableGuild,
     UserUpdate,
@@ -85,7 +94,9 @@ impl EventType {
             Self::GuildDelete => Some("GUILD_DELETE"),
             Self::GuildEmojisUpdate => Some("GUILD_EMOJIS_UPDATE"),
             Self::GuildIntegrationsUpdate => Some("GUILD_INTEGRATIONS_UPDATE"),
+            Self::GuildStickersUpdate => Some("GUILD_STICKERS_UPDATE"),
             Self::GuildUpdate => Some("GUILD_UPDATE"),
+            Self::InteractionCreate => Some("INTERACTION_CREATE"),
             Self::InviteCreate => Some("INVITE_CREATE"),
             Self::InviteDelete => Some("INVITE_DELETE"),
             Self::MemberAdd => Some("GUILD_MEMBER_ADD"),
@@ -107,6 +118,12 @@ impl EventType {
             Self::RoleCreate => Some("GUILD_ROLE_CREATE"),
             Self::RoleDelete => Some("GUILD_ROLE_DELETE"),
             Self::RoleUpdate => Some("GUILD_ROLE_UPDATE"),
+            Self::ThreadCreate => Some("THREAD_CREATE"),
+            Self::ThreadDelete => Some("THREAD_DELETE"),
+            Self::ThreadListSync => Some("THREAD_LIST_SYNC"),
+            Self::ThreadMemberUpdate => Some("THREAD_MEMBER_UPDATE"),
+            Self::ThreadMembersUpdate => Some("THREAD_MEMBERS_UPDATE"),
+            Self::ThreadUpdate => Some("THREAD_UPDATE"),
             Self::TypingStart => Some("TYPING_START"),
             Self::UnavailableGuild => Some("UNAVAILABLE_GUILD"),
             Self::UserUpdate => Some("USER_UPDATE"),
@@ -124,7 +141,8 @@ impl EventType {
             | Self::ShardIdentifying
             | Self::ShardReconnecting
             | Self::ShardPayload
-            | Self::ShardResuming => None,
+            | Self::ShardResuming
+            | Self::ShardStopped => None,
         }
     }
 }
@@ -145,7 +163,9 @@ impl<'a> TryFrom<&'a str> for EventType {
             "GUILD_DELETE" => Ok(Self::GuildDelete),
             "GUILD_EMOJIS_UPDATE" => Ok(Self::GuildEmojisUpdate),
             "GUILD_INTEGRATIONS_UPDATE" => Ok(Self::GuildIntegrationsUpdate),
+            "GUILD_STICKERS_UPDATE" => Ok(Self::GuildStickersUpdate),
             "GUILD_UPDATE" => Ok(Self::GuildUpdate),
+            "INTERACTION_CREATE" => Ok(Self::InteractionCreate),
             "INVITE_CREATE" => Ok(Self::InviteCreate),
             "INVITE_DELETE" => Ok(Self::InviteDelete),
             "GUILD_MEMBER_ADD" => Ok(Self::MemberAdd),
@@ -167,6 +187,12 @@ impl<'a> TryFrom<&'a str> for EventType {
             "GUILD_ROLE_CREATE" => Ok(Self::RoleCreate),
             "GUILD_ROLE_DELETE" => Ok(Self::RoleDelete),
             "GUILD_ROLE_UPDATE" => Ok(Self::RoleUpdate),
+            "THREAD_CREATE" => Ok(Self::ThreadCreate),
+            "THREAD_DELETE" => Ok(Self::ThreadDelete),
+            "THREAD_LIST_SYNC" => Ok(Self::ThreadListSync),
+            "THREAD_MEMBER_UPDATE" => Ok(Self::ThreadMemberUpdate),
+            "THREAD_MEMBERS_UPDATE" => Ok(Self::ThreadMembersUpdate),
+            "THREAD_UPDATE" => Ok(Self::ThreadUpdate),
             "TYPING_START" => Ok(Self::TypingStart),
             "UNAVAILABLE_GUILD" => Ok(Self::UnavailableGuild),
             "USER_UPDATE" => Ok(Self::UserUpdate),
@@ -217,7 +243,9 @@ mod tests {
             EventType::GuildIntegrationsUpdate,
             "GUILD_INTEGRATIONS_UPDATE",
         );
+        assert_variant(EventType::GuildStickersUpdate, "GUILD_STICKERS_UPDATE");
         assert_variant(EventType::GuildUpdate, "GUILD_UPDATE");
+        assert_variant(EventType::InteractionCreate, "INTERACTION_CREATE");
         assert_variant(EventType::InviteCreate, "INVITE_CREATE");
         assert_variant(EventType::InviteDelete, "INVITE_DELETE");
         assert_variant(EventType::MemberAdd, "GUILD_MEMBER_ADD");
@@ -249,6 +277,13 @@ mod tests {
         assert_variant(EventType::ShardPayload, "SHARD_PAYLOAD");
         assert_variant(EventType::ShardReconnecting, "SHARD_RECONNECTING");
         assert_variant(EventType::ShardResuming, "SHARD_RESUMING");
+        assert_variant(EventType::ShardStopped, "SHARD_STOPPED");
+        assert_variant(EventType::ThreadCreate, "THREAD_CREATE");
+        assert_variant(EventType::ThreadDelete, "THREAD_DELETE");
+        assert_variant(EventType::ThreadListSync, "THREAD_LIST_SYNC");
+        assert_variant(EventType::ThreadMemberUpdate, "THREAD_MEMBER_UPDATE");
+        assert_variant(EventType::ThreadMembersUpdate, "THREAD_MEMBERS_UPDATE");
+        assert_variant(EventType::ThreadUpdate, "THREAD_UPDATE");
         assert_variant(EventType::TypingStart, "TYPING_START");
         assert_variant(EventType::UnavailableGuild, "UNAVAILABLE_GUILD");
         assert_variant(EventType::UserUpdate, "USER_UPDATE");
@@ -4,10 +4,12 @@ pub mod gateway;
 pub mod shard;
 
 mod dispatch;
+mod flags;
 mod kind;
 
 pub use self::{
     dispatch::{DispatchEvent, DispatchEventWithTypeDeserializer},
+    flags::EventTypeFlags,
     gateway::{GatewayEvent, GatewayEventDeserializer, GatewayEventDeserializerOwned},
     kind::EventType,
 };
@@ -57,8 +59,12 @@ pub enum Event {
     GuildEmojisUpdate(GuildEmojisUpdate),
     /// A guild's integrations were updated.
     GuildIntegrationsUpdate(GuildIntegrationsUpdate),
+    /// A guild's stickers were updated.
+    GuildStickersUpdate(GuildStickersUpdate),
     /// A guild was updated.
     GuildUpdate(Box<GuildUpdate>),
+    /// A user invoked a command or used a message component.
+    InteractionCreate(Box<InteractionCreate>),
     /// A invite was made.
     InviteCreate(Box<InviteCreate>),
     /// A invite was deleted.
@@ -121,6 +127,20 @@ pub enum Event {
     ShardPayload(Payload),
     /// A shard is now in a Resuming stage after a disconnect.
     ShardResuming(Resuming),
+    /// A shard has stopped after an error it can't recover from.
+    ShardStopped(Stopped),
+    /// A thread was created.
+    ThreadCreate(ThreadCreate),
+    /// A thread was deleted.
+    ThreadDelete(ThreadDelete),
+    /// The active threads of channels were synced.
+    ThreadListSync(ThreadListSync),
+    /// The thread member of the current user was updated.
+    ThreadMemberUpdate(ThreadMemberUpdate),
+    /// Members were added to or removed from a thread.
+    ThreadMembersUpdate(ThreadMembersUpdate),
+    /// A thread was updated.
+    ThreadUpdate(ThreadUpdate),
     /// A user started typing in a channel.
     TypingStart(Box<TypingStart>),
     /// A guild is now unavailable.
@@ -154,7 +174,9 @@ impl Event {
             Self::GuildDelete(_) => EventType::GuildDelete,
             Self::GuildEmojisUpdate(_) => EventType::GuildEmojisUpdate,
             Self::GuildIntegrationsUpdate(_) => EventType::GuildIntegrationsUpdate,
+            Self::GuildStickersUpdate(_) => EventType::GuildStickersUpdate,
             Self::GuildUpdate(_) => EventType::GuildUpdate,
+            Self::InteractionCreate(_) => EventType::InteractionCreate,
             Self::InviteCreate(_) => EventType::InviteCreate,
             Self::InviteDelete(_) => EventType::InviteDelete,
             Self::MemberAdd(_) => EventType::MemberAdd,
@@ -183,6 +205,13 @@ impl Event {
             Self::ShardReconnecting(_) => EventType::ShardReconnecting,
             Self::ShardPayload(_) => EventType::ShardPayload,
             Self::ShardResuming(_) => EventType::ShardResuming,
+            Self::ShardStopped(_) => EventType::ShardStopped,
+            Self::ThreadCreate(_) => EventType::ThreadCreate,
+            Self::ThreadDelete(_) => EventType::ThreadDelete,
+            Self::ThreadListSync(_) => EventType::ThreadListSync,
+            Self::ThreadMemberUpdate(_) => EventType::ThreadMemberUpdate,
+            Self::ThreadMembersUpdate(_) => EventType::ThreadMembersUpdate,
+            Self::ThreadUpdate(_) => EventType::ThreadUpdate,
             Self::TypingStart(_) => EventType::TypingStart,
             Self::UnavailableGuild(_) => EventType::UnavailableGuild,
             Self::UserUpdate(_) => EventType::UserUpdate,
@@ -207,6 +236,8 @@ impl From<Box<DispatchEvent>> for Event {
             DispatchEvent::GuildDelete(v) => Self::GuildDelete(v),
             DispatchEvent::GuildEmojisUpdate(v) => Self::GuildEmojisUpdate(v),
             DispatchEvent::GuildIntegrationsUpdate(v) => Self::GuildIntegrationsUpdate(v),
+            DispatchEvent::GuildStickersUpdate(v) => Self::GuildStickersUpdate(v),
+            DispatchEvent::InteractionCreate(v) => Self::InteractionCreate(v),
             DispatchEvent::InviteCreate(v) => Self::InviteCreate(v),
             DispatchEvent::InviteDelete(v) => Self::InviteDelete(v),
             DispatchEvent::MemberAdd(v) => Self::MemberAdd(v),
@@ -229,6 +260,12 @@ impl From<Box<DispatchEvent>> for Event {
             DispatchEvent::ReactionRemoveEmoji(v) => Self::ReactionRemoveEmoji(v),
             DispatchEvent::Ready(v) => Self::Ready(v),
             DispatchEvent::Resumed => Self::Resumed,
+            DispatchEvent::ThreadCreate(v) => Self::ThreadCreate(v),
+            DispatchEvent::ThreadDelete(v) => Self::ThreadDelete(v),
+            DispatchEvent::ThreadListSync(v) => Self::ThreadListSync(v),
+            DispatchEvent::ThreadMemberUpdate(v) => Self::ThreadMemberUpdate(v),
+            DispatchEvent::ThreadMembersUpdate(v) => Self::ThreadMembersUpdate(v),
+            DispatchEvent::ThreadUpdate(v) => Self::ThreadUpdate(v),
             DispatchEvent::TypingStart(v) => Self::TypingStart(v),
             DispatchEvent::UnavailableGuild(v) => Self::UnavailableGuild(v),
             DispatchEvent::UserUpdate(v) => Self::UserUpdate(v),
@@ -262,6 +299,7 @@ impl From<ShardEvent> for Event {
             ShardEvent::Payload(v) => Self::ShardPayload(v),
             ShardEvent::Reconnecting(v) => Self::ShardReconnecting(v),
             ShardEvent::Resuming(v) => Self::ShardResuming(v),
+            ShardEvent::Stopped(v) => Self::ShardStopped(v),
         }
     }
 }
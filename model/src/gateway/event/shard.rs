@@ -43,6 +43,9 @@ pub struct Identifying {
 }
 
 /// A payload of bytes came in through the gateway.
+///
+/// The bytes are those of the message as received, after transport
+/// decompression but before being deserialized.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Payload {
     /// The bytes that came in.
@@ -65,6 +68,39 @@ pub struct Resuming {
     pub shard_id: u64,
 }
 
+/// Reason a shard stopped after an error it can't recover from.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+pub enum StopReason {
+    /// The gateway closed the connection with code 4004 because the token is
+    /// invalid.
+    AuthorizationInvalid,
+    /// The gateway closed the connection with code 4014 because the current
+    /// user isn't allowed to use at least one of the configured intents.
+    IntentsDisallowed,
+    /// The gateway closed the connection with code 4013 because at least one
+    /// of the configured intents isn't valid.
+    IntentsInvalid,
+    /// The shard used up the attempts to reconnect that its reconnect policy
+    /// allows.
+    ReconnectsExhausted,
+    /// No more sessions can be started until the session start limit resets.
+    SessionStartLimitExhausted {
+        /// The number of milliseconds until the limit resets.
+        reset_after: u64,
+    },
+}
+
+/// Indicator that a shard has stopped and won't reconnect, after which its
+/// event streams end.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Stopped {
+    /// The reason the shard stopped.
+    pub reason: StopReason,
+    /// The ID of the shard that stopped.
+    pub shard_id: u64,
+}
+
 /// "Meta" events about a shard's status, not from the gateway.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
@@ -86,6 +122,8 @@ pub enum ShardEvent {
     Reconnecting(Reconnecting),
     /// A shard is now in a Resuming stage after a disconnect.
     Resuming(Resuming),
+    /// A shard has stopped after an error it can't recover from.
+    Stopped(Stopped),
 }
 
 impl TryFrom<Event> for ShardEvent {
@@ -100,6 +138,7 @@ impl TryFrom<Event> for ShardEvent {
             Event::ShardPayload(v) => Self::Payload(v),
             Event::ShardReconnecting(v) => Self::Reconnecting(v),
             Event::ShardResuming(v) => Self::Resuming(v),
+            Event::ShardStopped(v) => Self::Stopped(v),
 
             _ => return Err(EventConversionError::new(event)),
         })
@@ -110,7 +149,7 @@ impl TryFrom<Event> for ShardEvent {
 mod tests {
     use super::{
         Connected, Connecting, Disconnected, Event, Identifying, Payload, Reconnecting, Resuming,
-        ShardEvent,
+        ShardEvent, StopReason, Stopped,
     };
     use serde_test::Token;
     use std::convert::TryInto;
@@ -274,6 +313,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stopped() {
+        let value = Stopped {
+            reason: StopReason::SessionStartLimitExhausted { reset_after: 5_000 },
+            shard_id: 4,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Stopped",
+                    len: 2,
+                },
+                Token::Str("reason"),
+                Token::StructVariant {
+                    name: "StopReason",
+                    variant: "SessionStartLimitExhausted",
+                    len: 1,
+                },
+                Token::Str("reset_after"),
+                Token::U64(5_000),
+                Token::StructVariantEnd,
+                Token::Str("shard_id"),
+                Token::U64(4),
+                Token::StructEnd,
+            ],
+        );
+
+        serde_test::assert_tokens(
+            &StopReason::AuthorizationInvalid,
+            &[Token::UnitVariant {
+                name: "StopReason",
+                variant: "AuthorizationInvalid",
+            }],
+        );
+    }
+
     #[test]
     fn test_shard_event_try_from_event() {
         let connected = Event::ShardConnected(Connected {
@@ -333,5 +410,14 @@ mod tests {
             resuming.try_into().unwrap(),
             ShardEvent::Resuming(_)
         ));
+
+        let stopped = Event::ShardStopped(Stopped {
+            reason: StopReason::IntentsInvalid,
+            shard_id: 4,
+        });
+        assert!(matches!(
+            stopped.try_into().unwrap(),
+            ShardEvent::Stopped(_)
+        ));
     }
 }
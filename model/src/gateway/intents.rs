@@ -27,6 +27,12 @@ bitflags! {
         ///  - [`CHANNEL_UPDATE`]
         ///  - [`CHANNEL_DELETE`]
         ///  - [`CHANNEL_PINS_UPDATE`]
+        ///  - [`THREAD_CREATE`]
+        ///  - [`THREAD_UPDATE`]
+        ///  - [`THREAD_DELETE`]
+        ///  - [`THREAD_LIST_SYNC`]
+        ///  - [`THREAD_MEMBER_UPDATE`]
+        ///  - [`THREAD_MEMBERS_UPDATE`], only for the current user
         ///
         /// [`GUILD_CREATE`]: super::event::Event::GuildCreate
         /// [`GUILD_UPDATE`]: super::event::Event::GuildUpdate
@@ -38,6 +44,12 @@ bitflags! {
         /// [`CHANNEL_UPDATE`]: super::event::Event::ChannelUpdate
         /// [`CHANNEL_DELETE`]: super::event::Event::ChannelDelete
         /// [`CHANNEL_PINS_UPDATE`]: super::event::Event::ChannelPinsUpdate
+        /// [`THREAD_CREATE`]: super::event::Event::ThreadCreate
+        /// [`THREAD_UPDATE`]: super::event::Event::ThreadUpdate
+        /// [`THREAD_DELETE`]: super::event::Event::ThreadDelete
+        /// [`THREAD_LIST_SYNC`]: super::event::Event::ThreadListSync
+        /// [`THREAD_MEMBER_UPDATE`]: super::event::Event::ThreadMemberUpdate
+        /// [`THREAD_MEMBERS_UPDATE`]: super::event::Event::ThreadMembersUpdate
         const GUILDS = 1;
         /// Guild members intent.
         ///
@@ -47,11 +59,13 @@ bitflags! {
         ///  - [`GUILD_MEMBER_ADD`]
         ///  - [`GUILD_MEMBER_UPDATE`]
         ///  - [`GUILD_MEMBER_REMOVE`]
+        ///  - [`THREAD_MEMBERS_UPDATE`]
         ///
         /// [the discord docs]: https://discord.com/developers/docs/topics/gateway#privileged-intents
         /// [`GUILD_MEMBER_ADD`]: super::event::Event::MemberAdd
         /// [`GUILD_MEMBER_UPDATE`]: super::event::Event::MemberUpdate
         /// [`GUILD_MEMBER_REMOVE`]: super::event::Event::MemberRemove
+        /// [`THREAD_MEMBERS_UPDATE`]: super::event::Event::ThreadMembersUpdate
         const GUILD_MEMBERS = 1 << 1;
         /// Guild bans intent.
         ///
@@ -62,12 +76,14 @@ bitflags! {
         /// [`GUILD_BAN_ADD`]: super::event::Event::BanAdd
         /// [`GUILD_BAN_REMOVE`]: super::event::Event::BanRemove
         const GUILD_BANS = 1 << 2;
-        /// Guild emojis intent.
+        /// Guild emojis and stickers intent.
         ///
         /// Event(s) received:
         ///  - [`GUILD_EMOJIS_UPDATE`]
+        ///  - [`GUILD_STICKERS_UPDATE`]
         ///
         /// [`GUILD_EMOJIS_UPDATE`]: super::event::Event::GuildEmojisUpdate
+        /// [`GUILD_STICKERS_UPDATE`]: super::event::Event::GuildStickersUpdate
         const GUILD_EMOJIS = 1 << 3;
         /// Guild integrations intent.
         ///
@@ -192,6 +208,52 @@ bitflags! {
     }
 }
 
+impl Intents {
+    /// All intents that aren't privileged.
+    ///
+    /// Unlike hand-listing intents, this includes intents added to the model
+    /// later. Refer to [`privileged`] for the intents that aren't included.
+    ///
+    /// # Examples
+    ///
+    /// Receive everything except for members and presences:
+    ///
+    /// ```
+    /// use twilight_model::gateway::Intents;
+    ///
+    /// let intents = Intents::all_unprivileged();
+    ///
+    /// assert!(intents.contains(Intents::GUILD_MESSAGES));
+    /// assert!(!intents.is_privileged());
+    /// ```
+    ///
+    /// [`privileged`]: Self::privileged
+    pub const fn all_unprivileged() -> Self {
+        Self::from_bits_truncate(Self::all().bits() & !Self::privileged().bits())
+    }
+
+    /// Intents that are privileged.
+    ///
+    /// Privileged intents must be enabled for the bot in the developer portal,
+    /// otherwise the gateway closes the connection with close code 4014. Bots
+    /// in 100 or more guilds must additionally be approved for them. See [the
+    /// discord docs] for more information.
+    ///
+    /// [the discord docs]: https://discord.com/developers/docs/topics/gateway#privileged-intents
+    pub const fn privileged() -> Self {
+        Self::from_bits_truncate(Self::GUILD_MEMBERS.bits() | Self::GUILD_PRESENCES.bits())
+    }
+
+    /// Whether any of the intents are privileged.
+    ///
+    /// Refer to [`privileged`] for more information.
+    ///
+    /// [`privileged`]: Self::privileged
+    pub const fn is_privileged(&self) -> bool {
+        self.bits() & Self::privileged().bits() != 0
+    }
+}
+
 impl<'de> Deserialize<'de> for Intents {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
@@ -230,4 +292,37 @@ mod tests {
         serde_test::assert_tokens(&Intents::DIRECT_MESSAGE_REACTIONS, &[Token::U64(1 << 13)]);
         serde_test::assert_tokens(&Intents::DIRECT_MESSAGE_TYPING, &[Token::U64(1 << 14)]);
     }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(0b111_1111_1111_1111, Intents::all().bits());
+    }
+
+    #[test]
+    fn test_all_unprivileged() {
+        assert_eq!(0b111_1110_1111_1101, Intents::all_unprivileged().bits());
+        assert!(!Intents::all_unprivileged().is_privileged());
+    }
+
+    #[test]
+    fn test_privileged() {
+        assert_eq!(
+            Intents::GUILD_MEMBERS | Intents::GUILD_PRESENCES,
+            Intents::privileged()
+        );
+        assert_eq!(0b1_0000_0010, Intents::privileged().bits());
+        assert_eq!(
+            Intents::all(),
+            Intents::all_unprivileged() | Intents::privileged()
+        );
+    }
+
+    #[test]
+    fn test_is_privileged() {
+        assert!(Intents::GUILD_MEMBERS.is_privileged());
+        assert!(Intents::GUILD_PRESENCES.is_privileged());
+        assert!((Intents::GUILDS | Intents::GUILD_PRESENCES).is_privileged());
+        assert!(!Intents::GUILDS.is_privileged());
+        assert!(!Intents::empty().is_privileged());
+    }
 }
@@ -14,8 +14,10 @@ mod guild_create;
 mod guild_delete;
 mod guild_emojis_update;
 mod guild_integrations_update;
+mod guild_stickers_update;
 mod guild_update;
 mod heartbeat;
+mod interaction_create;
 mod invite_create;
 mod invite_delete;
 mod member_add;
@@ -34,6 +36,12 @@ mod ready;
 mod role_create;
 mod role_delete;
 mod role_update;
+mod thread_create;
+mod thread_delete;
+mod thread_list_sync;
+mod thread_member_update;
+mod thread_members_update;
+mod thread_update;
 mod typing_start;
 mod unavailable_guild;
 mod update_voice_state;
@@ -47,7 +55,8 @@ pub use self::{
     channel_delete::ChannelDelete, channel_pins_update::ChannelPinsUpdate,
     channel_update::ChannelUpdate, guild_create::GuildCreate, guild_delete::GuildDelete,
     guild_emojis_update::GuildEmojisUpdate, guild_integrations_update::GuildIntegrationsUpdate,
-    guild_update::GuildUpdate, heartbeat::Heartbeat, invite_create::InviteCreate,
+    guild_stickers_update::GuildStickersUpdate, guild_update::GuildUpdate, heartbeat::Heartbeat,
+    interaction_create::InteractionCreate, invite_create::InviteCreate,
     invite_delete::InviteDelete, member_add::MemberAdd, member_chunk::MemberChunk,
     member_remove::MemberRemove, member_update::MemberUpdate, message_create::MessageCreate,
     message_delete::MessageDelete, message_delete_bulk::MessageDeleteBulk,
@@ -55,8 +64,11 @@ pub use self::{
     reaction_remove::ReactionRemove, reaction_remove_all::ReactionRemoveAll,
     reaction_remove_emoji::ReactionRemoveEmoji, ready::Ready,
     request_guild_members::RequestGuildMembers, role_create::RoleCreate, role_delete::RoleDelete,
-    role_update::RoleUpdate, typing_start::TypingStart, unavailable_guild::UnavailableGuild,
-    update_status::UpdateStatus, update_voice_state::UpdateVoiceState, user_update::UserUpdate,
+    role_update::RoleUpdate, thread_create::ThreadCreate, thread_delete::ThreadDelete,
+    thread_list_sync::ThreadListSync, thread_member_update::ThreadMemberUpdate,
+    thread_members_update::ThreadMembersUpdate, thread_update::ThreadUpdate,
+    typing_start::TypingStart, unavailable_guild::UnavailableGuild, update_status::UpdateStatus,
+    update_voice_state::UpdateVoiceState, user_update::UserUpdate,
     voice_server_update::VoiceServerUpdate, voice_state_update::VoiceStateUpdate,
     webhooks_update::WebhooksUpdate,
 };
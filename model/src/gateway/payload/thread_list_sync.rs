@@ -0,0 +1,22 @@
+use crate::{
+    channel::{thread::ThreadMember, ThreadChannel},
+    id::{ChannelId, GuildId},
+};
+use serde::{Deserialize, Serialize};
+
+/// Active threads of a guild, sent when the current user gains access to
+/// channels.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ThreadListSync {
+    /// IDs of the parent channels whose threads are being synced.
+    ///
+    /// If not present, the threads of every channel of the guild are being
+    /// synced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ids: Option<Vec<ChannelId>>,
+    pub guild_id: GuildId,
+    /// Thread members of the current user in the synced threads.
+    pub members: Vec<ThreadMember>,
+    /// Active threads of the synced channels.
+    pub threads: Vec<ThreadChannel>,
+}
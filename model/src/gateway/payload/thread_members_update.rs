@@ -0,0 +1,19 @@
+use crate::{
+    channel::thread::ThreadMember,
+    id::{ChannelId, GuildId, UserId},
+};
+use serde::{Deserialize, Serialize};
+
+/// Members were added to or removed from a thread.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ThreadMembersUpdate {
+    #[serde(default)]
+    pub added_members: Vec<ThreadMember>,
+    pub guild_id: GuildId,
+    /// ID of the thread.
+    pub id: ChannelId,
+    /// Approximate number of members in the thread, stopping at 50.
+    pub member_count: u8,
+    #[serde(default)]
+    pub removed_member_ids: Vec<UserId>,
+}
@@ -3,11 +3,15 @@ use serde::{Deserialize, Serialize};
 /// Current gateway session utilisation status.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct SessionStartLimit {
-    /// Maximum number of session that may be started concurrently.
+    /// Maximum number of sessions that may be started concurrently.
+    ///
+    /// Shards are grouped into buckets by their ID modulo this value, and one
+    /// shard of each bucket may identify every 5 seconds.
     pub max_concurrency: u64,
     /// Number of remaining sessions for a given time period.
     pub remaining: u64,
-    /// When the remaining sessions resets back to the total.
+    /// Number of milliseconds until the remaining sessions resets back to the
+    /// total.
     pub reset_after: u64,
     /// Total number of sessions that can be started within the given time
     /// period.
@@ -40,7 +40,7 @@ pub use self::{
 use self::member::MemberListDeserializer;
 use super::gateway::presence::PresenceListDeserializer;
 use crate::{
-    channel::GuildChannel,
+    channel::{message::sticker::Sticker, GuildChannel},
     gateway::presence::Presence,
     id::{ApplicationId, ChannelId, GuildId, UserId},
     voice::voice_state::VoiceState,
@@ -105,6 +105,8 @@ pub struct Guild {
     pub roles: Vec<Role>,
     pub rules_channel_id: Option<ChannelId>,
     pub splash: Option<String>,
+    #[serde(default)]
+    pub stickers: Vec<Sticker>,
     pub system_channel_flags: SystemChannelFlags,
     pub system_channel_id: Option<ChannelId>,
     #[serde(default)]
@@ -160,6 +162,7 @@ impl<'de> Deserialize<'de> for Guild {
             Region,
             Roles,
             Splash,
+            Stickers,
             SystemChannelFlags,
             SystemChannelId,
             RulesChannelId,
@@ -217,6 +220,7 @@ impl<'de> Deserialize<'de> for Guild {
                 let mut region = None;
                 let mut roles = None;
                 let mut splash = None::<Option<_>>;
+                let mut stickers = None;
                 let mut system_channel_id = None::<Option<_>>;
                 let mut system_channel_flags = None;
                 let mut rules_channel_id = None::<Option<_>>;
@@ -503,6 +507,13 @@ impl<'de> Deserialize<'de> for Guild {
 
                             splash = Some(map.next_value()?);
                         }
+                        Field::Stickers => {
+                            if stickers.is_some() {
+                                return Err(DeError::duplicate_field("stickers"));
+                            }
+
+                            stickers = Some(map.next_value()?);
+                        }
                         Field::SystemChannelId => {
                             if system_channel_id.is_some() {
                                 return Err(DeError::duplicate_field("system_channel_id"));
@@ -614,6 +625,7 @@ impl<'de> Deserialize<'de> for Guild {
                 let mut presences = presences.unwrap_or_default();
                 let rules_channel_id = rules_channel_id.unwrap_or_default();
                 let splash = splash.unwrap_or_default();
+                let stickers = stickers.unwrap_or_default();
                 let system_channel_id = system_channel_id.unwrap_or_default();
                 let unavailable = unavailable.unwrap_or_default();
                 let vanity_url_code = vanity_url_code.unwrap_or_default();
@@ -662,6 +674,7 @@ impl<'de> Deserialize<'de> for Guild {
                     ?rules_channel_id,
                     ?roles,
                     ?splash,
+                    ?stickers,
                     ?system_channel_flags,
                     ?system_channel_id,
                     ?unavailable,
@@ -680,6 +693,9 @@ impl<'de> Deserialize<'de> for Guild {
                         GuildChannel::Text(c) => {
                             c.guild_id.replace(id);
                         }
+                        GuildChannel::Thread(c) => {
+                            c.guild_id.replace(id);
+                        }
                         GuildChannel::Voice(c) => {
                             c.guild_id.replace(id);
                         }
@@ -734,6 +750,7 @@ impl<'de> Deserialize<'de> for Guild {
                     region,
                     roles,
                     splash,
+                    stickers,
                     system_channel_id,
                     system_channel_flags,
                     rules_channel_id,
@@ -783,6 +800,7 @@ impl<'de> Deserialize<'de> for Guild {
             "region",
             "roles",
             "splash",
+            "stickers",
             "system_channel_id",
             "system_channel_flags",
             "rules_channel_id",
@@ -846,6 +864,7 @@ mod tests {
             roles: Vec::new(),
             rules_channel_id: Some(ChannelId(6)),
             splash: Some("splash hash".to_owned()),
+            stickers: Vec::new(),
             system_channel_flags: SystemChannelFlags::SUPPRESS_PREMIUM_SUBSCRIPTIONS,
             system_channel_id: Some(ChannelId(7)),
             unavailable: false,
@@ -861,7 +880,7 @@ mod tests {
             &[
                 Token::Struct {
                     name: "Guild",
-                    len: 44,
+                    len: 45,
                 },
                 Token::Str("afk_channel_id"),
                 Token::Some,
@@ -968,6 +987,9 @@ mod tests {
                 Token::Str("splash"),
                 Token::Some,
                 Token::Str("splash hash"),
+                Token::Str("stickers"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
                 Token::Str("system_channel_flags"),
                 Token::U64(2),
                 Token::Str("system_channel_id"),
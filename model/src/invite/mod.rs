@@ -1,6 +1,7 @@
 mod channel;
 mod guild;
 mod metadata;
+mod target_type;
 mod target_user_type;
 mod welcome_screen;
 
@@ -8,6 +9,7 @@ pub use self::{
     channel::InviteChannel,
     guild::InviteGuild,
     metadata::InviteMetadata,
+    target_type::TargetType,
     target_user_type::TargetUserType,
     welcome_screen::{WelcomeScreen, WelcomeScreenChannel},
 };
@@ -0,0 +1,25 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Type of target of an invite to a voice channel.
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum TargetType {
+    /// Invite to watch the stream of a user.
+    Stream = 1,
+    /// Invite to an embedded application.
+    EmbeddedApplication = 2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TargetType;
+    use serde_test::Token;
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&TargetType::Stream, &[Token::U8(1)]);
+        serde_test::assert_tokens(&TargetType::EmbeddedApplication, &[Token::U8(2)]);
+    }
+}
@@ -47,6 +47,7 @@
 )]
 #![allow(clippy::module_name_repetitions, clippy::must_use_candidate)]
 
+pub mod application;
 pub mod channel;
 pub mod gateway;
 pub mod guild;
@@ -1,5 +1,6 @@
+use super::Registration;
 use futures_channel::{
-    mpsc::UnboundedReceiver as MpscReceiver,
+    mpsc::{Receiver as MpscReceiver, UnboundedReceiver},
     oneshot::{Canceled, Receiver},
 };
 use futures_util::{
@@ -7,21 +8,108 @@ use futures_util::{
     stream::{Stream, StreamExt},
 };
 use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
-use twilight_model::gateway::{
-    event::Event,
-    payload::{MessageCreate, ReactionAdd},
+use tokio::time::{self, Sleep};
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{InteractionCreate, MemberChunk, MessageCreate, ReactionAdd},
+        presence::Presence,
+    },
+    guild::Member,
+    id::UserId,
 };
 
+/// The future returned from [`Standby::wait_for_component`].
+///
+/// [`Standby::wait_for_component`]: crate::Standby::wait_for_component
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForComponentFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: Receiver<InteractionCreate>,
+}
+
+impl Future for WaitForComponentFuture {
+    type Output = Result<InteractionCreate, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.rx.poll_unpin(cx)
+    }
+}
+
+/// The stream returned from [`Standby::wait_for_component_stream`].
+///
+/// [`Standby::wait_for_component_stream`]: crate::Standby::wait_for_component_stream
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct WaitForComponentStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: MpscReceiver<InteractionCreate>,
+}
+
+impl Stream for WaitForComponentStream {
+    type Item = InteractionCreate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// The future returned from [`Standby::wait_for_dm_message`].
+///
+/// [`Standby::wait_for_dm_message`]: crate::Standby::wait_for_dm_message
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForDmMessageFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: Receiver<MessageCreate>,
+}
+
+impl Future for WaitForDmMessageFuture {
+    type Output = Result<MessageCreate, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.rx.poll_unpin(cx)
+    }
+}
+
+/// The stream returned from [`Standby::wait_for_dm_message_stream`].
+///
+/// [`Standby::wait_for_dm_message_stream`]: crate::Standby::wait_for_dm_message_stream
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct WaitForDmMessageStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: MpscReceiver<MessageCreate>,
+}
+
+impl Stream for WaitForDmMessageStream {
+    type Item = MessageCreate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
 /// The future returned from [`Standby::wait_for_event`].
 ///
 /// [`Standby::wait_for_event`]: crate::Standby::wait_for_event
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct WaitForEventFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: Receiver<Event>,
 }
 
@@ -39,6 +127,8 @@ impl Future for WaitForEventFuture {
 #[derive(Debug)]
 #[must_use = "streams do nothing unless you poll them"]
 pub struct WaitForEventStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: MpscReceiver<Event>,
 }
 
@@ -56,6 +146,8 @@ impl Stream for WaitForEventStream {
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct WaitForGuildEventFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: Receiver<Event>,
 }
 
@@ -73,6 +165,8 @@ impl Future for WaitForGuildEventFuture {
 #[derive(Debug)]
 #[must_use = "streams do nothing unless you poll them"]
 pub struct WaitForGuildEventStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: MpscReceiver<Event>,
 }
 
@@ -84,12 +178,131 @@ impl Stream for WaitForGuildEventStream {
     }
 }
 
+/// The future returned from [`Standby::wait_for_interaction`].
+///
+/// [`Standby::wait_for_interaction`]: crate::Standby::wait_for_interaction
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForInteractionFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: Receiver<InteractionCreate>,
+}
+
+impl Future for WaitForInteractionFuture {
+    type Output = Result<InteractionCreate, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.rx.poll_unpin(cx)
+    }
+}
+
+/// The stream returned from [`Standby::wait_for_interaction_stream`].
+///
+/// [`Standby::wait_for_interaction_stream`]: crate::Standby::wait_for_interaction_stream
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct WaitForInteractionStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: MpscReceiver<InteractionCreate>,
+}
+
+impl Stream for WaitForInteractionStream {
+    type Item = InteractionCreate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// Members sent across all of the chunks of a response to a request for guild
+/// members.
+///
+/// Returned by [`WaitForMemberChunksFuture`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MemberChunks {
+    /// Members of the guild matching the request.
+    pub members: Vec<Member>,
+    /// IDs of the requested users that aren't members of the guild.
+    pub not_found: Vec<UserId>,
+    /// Presences of the members, if requested.
+    pub presences: Vec<Presence>,
+}
+
+/// The future returned from [`Standby::wait_for_member_chunks`].
+///
+/// [`Standby::wait_for_member_chunks`]: crate::Standby::wait_for_member_chunks
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForMemberChunksFuture {
+    /// Members of the chunks received so far.
+    chunks: MemberChunks,
+    /// Whether the last chunk was received.
+    complete: bool,
+    stream: WaitForMemberChunkStream,
+}
+
+impl WaitForMemberChunksFuture {
+    pub(crate) fn new(stream: WaitForMemberChunkStream) -> Self {
+        Self {
+            chunks: MemberChunks::default(),
+            complete: false,
+            stream,
+        }
+    }
+}
+
+impl Future for WaitForMemberChunksFuture {
+    type Output = Result<MemberChunks, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        while let Some(chunk) = futures_util::ready!(this.stream.poll_next_unpin(cx)) {
+            this.complete = chunk.chunk_index + 1 >= chunk.chunk_count;
+            this.chunks.members.extend(chunk.members);
+            this.chunks.not_found.extend(chunk.not_found);
+            this.chunks.presences.extend(chunk.presences);
+        }
+
+        // The stream also ends if the standby is dropped before the last chunk
+        // is received.
+        if this.complete {
+            Poll::Ready(Ok(std::mem::take(&mut this.chunks)))
+        } else {
+            Poll::Ready(Err(Canceled))
+        }
+    }
+}
+
+/// The stream returned from [`Standby::wait_for_member_chunk_stream`].
+///
+/// [`Standby::wait_for_member_chunk_stream`]: crate::Standby::wait_for_member_chunk_stream
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct WaitForMemberChunkStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
+    pub(crate) rx: UnboundedReceiver<MemberChunk>,
+}
+
+impl Stream for WaitForMemberChunkStream {
+    type Item = MemberChunk;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
 /// The future returned from [`Standby::wait_for_message`].
 ///
 /// [`Standby::wait_for_message`]: crate::Standby::wait_for_message
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct WaitForMessageFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: Receiver<MessageCreate>,
 }
 
@@ -107,6 +320,8 @@ impl Future for WaitForMessageFuture {
 #[derive(Debug)]
 #[must_use = "streams do nothing unless you poll them"]
 pub struct WaitForMessageStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: MpscReceiver<MessageCreate>,
 }
 
@@ -124,6 +339,8 @@ impl Stream for WaitForMessageStream {
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct WaitForReactionFuture {
+    /// Removes the bystander of the future once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: Receiver<ReactionAdd>,
 }
 
@@ -141,6 +358,8 @@ impl Future for WaitForReactionFuture {
 #[derive(Debug)]
 #[must_use = "streams do nothing unless you poll them"]
 pub struct WaitForReactionStream {
+    /// Removes the bystander of the stream once it's dropped.
+    pub(crate) _registration: Registration,
     pub(crate) rx: MpscReceiver<ReactionAdd>,
 }
 
@@ -152,22 +371,125 @@ impl Stream for WaitForReactionStream {
     }
 }
 
+/// Waiting for an event with a timeout failed.
+///
+/// Returned by [`WaitForTimeoutFuture`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WaitForTimeoutError {
+    /// The [`Standby`] was dropped.
+    ///
+    /// [`Standby`]: crate::Standby
+    Canceled,
+    /// No matching event was processed before the timeout elapsed.
+    TimedOut,
+}
+
+impl Display for WaitForTimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Canceled => f.write_str("the standby was dropped"),
+            Self::TimedOut => f.write_str("no matching event was processed in time"),
+        }
+    }
+}
+
+impl Error for WaitForTimeoutError {}
+
+/// The future returned from the methods of [`Standby`] waiting for up to a
+/// duration, such as [`Standby::wait_for_with_timeout`].
+///
+/// The bystander of the future is removed from the standby once the timeout
+/// elapses.
+///
+/// [`Standby`]: crate::Standby
+/// [`Standby::wait_for_with_timeout`]: crate::Standby::wait_for_with_timeout
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForTimeoutFuture<F> {
+    /// Future waiting for the event, taken once it completes or the timeout
+    /// elapses.
+    future: Option<F>,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl<F> WaitForTimeoutFuture<F> {
+    pub(crate) fn new(future: F, timeout: Duration) -> Self {
+        Self {
+            future: Some(future),
+            sleep: Box::pin(time::sleep(timeout)),
+        }
+    }
+}
+
+impl<F: Future<Output = Result<T, Canceled>> + Unpin, T> Future for WaitForTimeoutFuture<F> {
+    type Output = Result<T, WaitForTimeoutError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let future = this
+            .future
+            .as_mut()
+            .expect("future polled after completion");
+
+        if let Poll::Ready(output) = future.poll_unpin(cx) {
+            this.future.take();
+
+            return Poll::Ready(output.map_err(|_| WaitForTimeoutError::Canceled));
+        }
+
+        if this.sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        // Dropping the future removes its bystander from the standby.
+        this.future.take();
+
+        Poll::Ready(Err(WaitForTimeoutError::TimedOut))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        WaitForEventFuture, WaitForEventStream, WaitForGuildEventFuture, WaitForGuildEventStream,
-        WaitForMessageFuture, WaitForMessageStream, WaitForReactionFuture, WaitForReactionStream,
+        MemberChunks, WaitForComponentFuture, WaitForComponentStream, WaitForDmMessageFuture,
+        WaitForDmMessageStream, WaitForEventFuture, WaitForEventStream, WaitForGuildEventFuture,
+        WaitForGuildEventStream, WaitForInteractionFuture, WaitForInteractionStream,
+        WaitForMemberChunkStream, WaitForMemberChunksFuture, WaitForMessageFuture,
+        WaitForMessageStream, WaitForReactionFuture, WaitForReactionStream, WaitForTimeoutError,
+        WaitForTimeoutFuture,
     };
     use futures_util::stream::Stream;
     use static_assertions::assert_impl_all;
-    use std::{fmt::Debug, future::Future};
+    use std::{error::Error, fmt::Debug, future::Future, hash::Hash};
 
+    assert_impl_all!(WaitForComponentFuture: Debug, Future, Send, Sync);
+    assert_impl_all!(WaitForComponentStream: Debug, Stream, Send, Sync);
+    assert_impl_all!(WaitForDmMessageFuture: Debug, Future, Send, Sync);
+    assert_impl_all!(WaitForDmMessageStream: Debug, Stream, Send, Sync);
     assert_impl_all!(WaitForEventFuture: Debug, Future, Send, Sync);
     assert_impl_all!(WaitForGuildEventFuture: Debug, Future, Send, Sync);
+    assert_impl_all!(WaitForInteractionFuture: Debug, Future, Send, Sync);
+    assert_impl_all!(WaitForInteractionStream: Debug, Stream, Send, Sync);
+    assert_impl_all!(MemberChunks: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(WaitForMemberChunksFuture: Debug, Future, Send, Sync);
+    assert_impl_all!(WaitForMemberChunkStream: Debug, Stream, Send, Sync);
     assert_impl_all!(WaitForMessageFuture: Debug, Future, Send, Sync);
     assert_impl_all!(WaitForReactionFuture: Debug, Future, Send, Sync);
     assert_impl_all!(WaitForEventStream: Debug, Stream, Send, Sync);
     assert_impl_all!(WaitForGuildEventStream: Debug, Stream, Send, Sync);
     assert_impl_all!(WaitForMessageStream: Debug, Stream, Send, Sync);
     assert_impl_all!(WaitForReactionStream: Debug, Stream, Send, Sync);
+    assert_impl_all!(WaitForTimeoutFuture<WaitForEventFuture>: Debug, Future, Send, Sync);
+    assert_impl_all!(
+        WaitForTimeoutError: Clone,
+        Copy,
+        Debug,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
 }
@@ -16,6 +16,15 @@
 //! a guild, such as a new `Ready` event ([`Standby::wait_for_event`]). Each
 //! method also has a stream variant.
 //!
+//! Standby can also wait for a direct message by a user without knowing the ID
+//! of their private channel ([`Standby::wait_for_dm_message`]), and collect the
+//! member chunks sent in response to a request for guild members with a nonce
+//! ([`Standby::wait_for_member_chunks`]).
+//!
+//! Interactions can be waited for too, such as a button on a message being
+//! pressed ([`Standby::wait_for_component`]) or any interaction in a guild
+//! ([`Standby::wait_for_interaction`]).
+//!
 //! To use Standby, you must process events with it in your main event loop.
 //! Check out the [`Standby::process`] method.
 //!
@@ -34,6 +43,26 @@
 //! miss some events while processing a received event. By using a stream, you
 //! won't miss any events.
 //!
+//! ## Timeouts
+//!
+//! Futures only complete once an event matching their predicate is processed.
+//! Methods like [`Standby::wait_for_message_with_timeout`] wait for up to a
+//! duration instead. Dropping a future removes it from the standby, so futures
+//! wrapped in timeouts of their own don't linger either.
+//!
+//! ## Features
+//!
+//! ### `metrics`
+//!
+//! The `metrics` feature records the number of bystanders waiting for events
+//! via the `metrics` crate each time an event is processed, as the
+//! `StandbyBystanders` gauge labeled by the kind of event they're waiting for
+//! as `BystanderType`, such as `Message` or `Reaction`. Bystanders that keep accumulating are a
+//! symptom of leaked futures or of events not being processed.
+//!
+//! Metrics are shared by all standbys in the process. When the feature is
+//! disabled no metrics code is compiled. It is not enabled by default.
+//!
 //! ## Examples
 //!
 //! ### At a glance
@@ -122,37 +151,59 @@
 
 #![deny(rust_2018_idioms, broken_intra_doc_links, unused, warnings)]
 
+pub mod stats;
+
 mod futures;
+#[cfg(feature = "metrics")]
+mod metrics;
 
 pub use futures::{
-    WaitForEventFuture, WaitForEventStream, WaitForGuildEventFuture, WaitForGuildEventStream,
-    WaitForMessageFuture, WaitForMessageStream, WaitForReactionFuture, WaitForReactionStream,
+    MemberChunks, WaitForComponentFuture, WaitForComponentStream, WaitForDmMessageFuture,
+    WaitForDmMessageStream, WaitForEventFuture, WaitForEventStream, WaitForGuildEventFuture,
+    WaitForGuildEventStream, WaitForInteractionFuture, WaitForInteractionStream,
+    WaitForMemberChunkStream, WaitForMemberChunksFuture, WaitForMessageFuture,
+    WaitForMessageStream, WaitForReactionFuture, WaitForReactionStream, WaitForTimeoutError,
+    WaitForTimeoutFuture,
 };
 
+use self::stats::StandbyStats;
+
 use dashmap::DashMap;
 use futures_channel::{
-    mpsc::{self, UnboundedSender as MpscSender},
+    mpsc::{self, Sender as MpscSender, UnboundedSender},
     oneshot::{self, Sender as OneshotSender},
 };
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
+    hash::Hash,
     sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
     },
+    time::Duration,
 };
 use twilight_model::{
+    application::interaction::InteractionType,
     channel::Channel,
     gateway::{
-        event::Event,
-        payload::{MessageCreate, ReactionAdd},
+        event::{Event, EventType, EventTypeFlags},
+        payload::{InteractionCreate, MemberChunk, MessageCreate, ReactionAdd},
     },
-    id::{ChannelId, GuildId, MessageId},
+    id::{ChannelId, GuildId, MessageId, UserId},
 };
 
+/// Number of events buffered by the streams of bystanders.
+///
+/// Events matching a stream whose buffer is full are dropped instead of
+/// blocking the processing of events for every other bystander.
+const STREAM_BUFFER: usize = 100;
+
 enum Sender<E> {
     Mpsc(MpscSender<E>),
     Oneshot(OneshotSender<E>),
+    /// Sender of a stream whose number of events is bounded, such as the
+    /// chunks of a guild members request, so none of them are dropped.
+    Unbounded(UnboundedSender<E>),
 }
 
 impl<E> Sender<E> {
@@ -160,12 +211,15 @@ impl<E> Sender<E> {
         match self {
             Self::Mpsc(sender) => sender.is_closed(),
             Self::Oneshot(sender) => sender.is_canceled(),
+            Self::Unbounded(sender) => sender.is_closed(),
         }
     }
 }
 
 struct Bystander<E> {
     func: Box<dyn Fn(&E) -> bool + Send + Sync>,
+    /// ID of the bystander, unique across all of the bystanders of a standby.
+    id: u64,
     sender: Option<Sender<E>>,
 }
 
@@ -178,15 +232,234 @@ impl<E> Debug for Bystander<E> {
     }
 }
 
+/// Bystander of events not in a certain guild, along with the types of events
+/// it's waiting for.
+#[derive(Debug)]
+struct EventBystander {
+    bystander: Bystander<Event>,
+    event_types: EventTypeFlags,
+}
+
+/// Map that a bystander is registered in, along with its key in it.
+#[derive(Clone, Debug)]
+enum BystanderKey {
+    Component(MessageId),
+    DmMessage(UserId),
+    Event,
+    Guild(GuildId),
+    Interaction(GuildId),
+    MemberChunk(GuildId, String),
+    Message(ChannelId),
+    Reaction(MessageId),
+}
+
+/// Number of bystanders registered in each map of a standby.
+///
+/// Counters are incremented when bystanders are registered and decremented
+/// when they're removed from their map, so they can be read without iterating
+/// over the maps.
+#[derive(Debug, Default)]
+struct BystanderCounts {
+    components: AtomicUsize,
+    dm_messages: AtomicUsize,
+    events: AtomicUsize,
+    guilds: AtomicUsize,
+    interactions: AtomicUsize,
+    member_chunks: AtomicUsize,
+    messages: AtomicUsize,
+    reactions: AtomicUsize,
+}
+
+impl BystanderCounts {
+    /// Counter of the map of a bystander.
+    fn get(&self, key: &BystanderKey) -> &AtomicUsize {
+        match key {
+            BystanderKey::Component(_) => &self.components,
+            BystanderKey::DmMessage(_) => &self.dm_messages,
+            BystanderKey::Event => &self.events,
+            BystanderKey::Guild(_) => &self.guilds,
+            BystanderKey::Interaction(_) => &self.interactions,
+            BystanderKey::MemberChunk(..) => &self.member_chunks,
+            BystanderKey::Message(_) => &self.messages,
+            BystanderKey::Reaction(_) => &self.reactions,
+        }
+    }
+}
+
+/// Registration of the bystander of a future or stream, removing it from its
+/// standby once the future or stream is dropped, such as when it times out, so
+/// that bystanders that never match don't leak.
+#[derive(Debug)]
+struct Registration {
+    id: u64,
+    key: BystanderKey,
+    standby: Weak<StandbyRef>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        // The standby was dropped along with its bystanders.
+        let standby = match self.standby.upgrade() {
+            Some(standby) => standby,
+            None => return,
+        };
+
+        let removed = match &self.key {
+            BystanderKey::Component(message_id) => {
+                remove_bystander(&standby.components, *message_id, self.id)
+            }
+            BystanderKey::DmMessage(user_id) => {
+                remove_bystander(&standby.dm_messages, *user_id, self.id)
+            }
+            BystanderKey::Event => standby.events.remove(&self.id).is_some(),
+            BystanderKey::Guild(guild_id) => remove_bystander(&standby.guilds, *guild_id, self.id),
+            BystanderKey::Interaction(guild_id) => {
+                remove_bystander(&standby.interactions, *guild_id, self.id)
+            }
+            BystanderKey::MemberChunk(guild_id, nonce) => {
+                remove_bystander(&standby.member_chunks, (*guild_id, nonce.clone()), self.id)
+            }
+            BystanderKey::Message(channel_id) => {
+                remove_bystander(&standby.messages, *channel_id, self.id)
+            }
+            BystanderKey::Reaction(message_id) => {
+                remove_bystander(&standby.reactions, *message_id, self.id)
+            }
+        };
+
+        // The bystander may have already been removed by processing an event.
+        if removed {
+            standby
+                .counts
+                .get(&self.key)
+                .fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Remove a bystander by its ID, removing the list of bystanders it was in
+/// if it's now empty.
+///
+/// Returns whether the bystander was in the map.
+fn remove_bystander<K: Eq + Hash, E>(map: &DashMap<K, Vec<Bystander<E>>>, key: K, id: u64) -> bool {
+    let (removed, remove) = match map.get_mut(&key) {
+        Some(mut bystanders) => {
+            let len = bystanders.len();
+            bystanders.retain(|bystander| bystander.id != id);
+
+            (bystanders.len() < len, bystanders.is_empty())
+        }
+        None => return false,
+    };
+
+    if remove {
+        map.remove_if(&key, |_, bystanders| bystanders.is_empty());
+    }
+
+    removed
+}
+
 #[derive(Debug, Default)]
 struct StandbyRef {
-    events: DashMap<u64, Bystander<Event>>,
-    event_counter: AtomicU64,
+    /// Bystanders of component interactions, keyed by the ID of the message
+    /// the component is attached to.
+    components: DashMap<MessageId, Vec<Bystander<InteractionCreate>>>,
+    /// Bystanders of direct messages, keyed by the ID of their author.
+    dm_messages: DashMap<UserId, Vec<Bystander<MessageCreate>>>,
+    events: DashMap<u64, EventBystander>,
+    /// Counter of the IDs of bystanders.
+    bystander_counter: AtomicU64,
+    counts: BystanderCounts,
     guilds: DashMap<GuildId, Vec<Bystander<Event>>>,
+    /// Bystanders of interactions, keyed by the guild they're invoked in.
+    interactions: DashMap<GuildId, Vec<Bystander<InteractionCreate>>>,
+    /// Bystanders of member chunks, keyed by the guild and the nonce of the
+    /// request they're waiting for the response of.
+    member_chunks: DashMap<(GuildId, String), Vec<Bystander<MemberChunk>>>,
     messages: DashMap<ChannelId, Vec<Bystander<MessageCreate>>>,
     reactions: DashMap<MessageId, Vec<Bystander<ReactionAdd>>>,
 }
 
+/// Number of bystanders that an event processed by [`Standby::process`] was
+/// sent to, by the kind of event they're waiting for.
+///
+/// An event sent to a bystander may be considered consumed by it, such as a
+/// reaction confirming a prompt that shouldn't also be handled as a reaction
+/// elsewhere.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ProcessResults {
+    components: usize,
+    dm_messages: usize,
+    events: usize,
+    guild_events: usize,
+    interactions: usize,
+    member_chunks: usize,
+    messages: usize,
+    reactions: usize,
+}
+
+impl ProcessResults {
+    /// Number of bystanders waiting for component interactions on a message
+    /// that the event was sent to.
+    pub const fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Number of bystanders waiting for direct messages that the event was
+    /// sent to.
+    pub const fn dm_messages(&self) -> usize {
+        self.dm_messages
+    }
+
+    /// Number of bystanders waiting for events of any guild that the event
+    /// was sent to.
+    pub const fn events(&self) -> usize {
+        self.events
+    }
+
+    /// Number of bystanders waiting for events in a guild that the event was
+    /// sent to.
+    pub const fn guild_events(&self) -> usize {
+        self.guild_events
+    }
+
+    /// Number of bystanders waiting for interactions in a guild that the
+    /// event was sent to.
+    pub const fn interactions(&self) -> usize {
+        self.interactions
+    }
+
+    /// Number of bystanders waiting for member chunks that the event was sent
+    /// to.
+    pub const fn member_chunks(&self) -> usize {
+        self.member_chunks
+    }
+
+    /// Number of bystanders waiting for messages in a channel that the event
+    /// was sent to.
+    pub const fn messages(&self) -> usize {
+        self.messages
+    }
+
+    /// Number of bystanders waiting for reactions on a message that the event
+    /// was sent to.
+    pub const fn reactions(&self) -> usize {
+        self.reactions
+    }
+
+    /// Total number of bystanders that the event was sent to.
+    pub const fn matched(&self) -> usize {
+        self.components
+            + self.dm_messages
+            + self.events
+            + self.guild_events
+            + self.interactions
+            + self.member_chunks
+            + self.messages
+            + self.reactions
+    }
+}
+
 /// The `Standby` struct, used by the main event loop to process events and by
 /// tasks to wait for an event.
 ///
@@ -205,6 +478,31 @@ impl Standby {
         Self::default()
     }
 
+    /// Number of bystanders currently waiting for events.
+    ///
+    /// Bystanders are removed once they're fulfilled or their futures or
+    /// streams are dropped, so a number that keeps growing indicates that
+    /// futures are leaked or that events aren't being processed.
+    ///
+    /// Refer to [`stats`] for the number of bystanders waiting for each kind
+    /// of event.
+    ///
+    /// [`stats`]: Self::stats
+    pub fn len(&self) -> usize {
+        self.stats().total()
+    }
+
+    /// Whether no bystanders are waiting for events.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Create an interface for retrieving statistics about the bystanders
+    /// waiting for events, such as the number waiting for messages.
+    pub fn stats(&self) -> StandbyStats<'_> {
+        StandbyStats::new(self)
+    }
+
     /// Process an event, calling any bystanders that might be waiting on it.
     ///
     /// When a bystander checks to see if an event is what it's waiting for, it
@@ -212,26 +510,57 @@ impl Standby {
     ///
     /// This function must be called when events are received in order for
     /// futures returned by methods to fulfill.
-    pub fn process(&self, event: &Event) {
+    ///
+    /// Returns the number of bystanders that the event was sent to, which may
+    /// be used to stop handling events consumed by a bystander.
+    pub fn process(&self, event: &Event) -> ProcessResults {
         tracing::trace!(event_type = ?event.kind(), ?event, "processing event");
 
+        let mut results = ProcessResults::default();
+
         match event {
-            Event::MessageCreate(e) => self.process_message(e.0.channel_id, &e),
-            Event::ReactionAdd(e) => self.process_reaction(e.0.message_id, &e),
+            Event::InteractionCreate(e) => {
+                if e.kind == InteractionType::MessageComponent {
+                    if let Some(message) = e.message.as_ref() {
+                        results.components = self.process_component(message.id, e);
+                    }
+                }
+
+                if let Some(guild_id) = e.guild_id {
+                    results.interactions = self.process_interaction(guild_id, e);
+                }
+            }
+            Event::MemberChunk(e) => results.member_chunks = self.process_member_chunk(e),
+            Event::MessageCreate(e) => {
+                if e.0.guild_id.is_none() {
+                    results.dm_messages = self.process_dm_message(e.0.author.id, e);
+                }
+
+                results.messages = self.process_message(e.0.channel_id, e);
+            }
+            Event::ReactionAdd(e) => results.reactions = self.process_reaction(e.0.message_id, e),
             _ => {}
         }
 
         if let Some(guild_id) = event_guild_id(event) {
-            self.process_guild(guild_id, event);
+            results.guild_events = self.process_guild(guild_id, event);
         }
 
-        self.process_event(event);
+        results.events = self.process_event(event);
+
+        #[cfg(feature = "metrics")]
+        self::metrics::record_bystanders(self);
+
+        results
     }
 
     /// Wait for an event in a certain guild.
     ///
     /// Returns a Canceled error if the Standby struct was dropped.
     ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
     /// If you need to wait for multiple guild events matching the given
     /// predicate, use [`wait_for_stream`].
     ///
@@ -264,22 +593,75 @@ impl Standby {
     ) -> WaitForGuildEventFuture {
         tracing::trace!(%guild_id, "waiting for event in guild");
         let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Guild(guild_id));
 
         {
             let mut guild = self.0.guilds.entry(guild_id).or_default();
             guild.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Oneshot(tx)),
             });
         }
 
-        WaitForGuildEventFuture { rx }
+        WaitForGuildEventFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for an event in a certain guild, for up to a duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching event
+    /// was processed in time, in which case the bystander is removed from the
+    /// standby. Refer to [`wait_for`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Wait up to a minute for a `BanAdd` event in guild 123:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use twilight_model::{
+    ///     gateway::event::{EventType, Event},
+    ///     id::GuildId,
+    /// };
+    /// use twilight_standby::{Standby, WaitForTimeoutError};
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let timeout = Duration::from_secs(60);
+    /// let ban = standby.wait_for_with_timeout(GuildId(123), |event: &Event| {
+    ///     event.kind() == EventType::BanAdd
+    /// }, timeout).await;
+    ///
+    /// if let Err(WaitForTimeoutError::TimedOut) = ban {
+    ///     println!("no one was banned within a minute");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for`]: Self::wait_for
+    pub fn wait_for_with_timeout<F: Fn(&Event) -> bool + Send + Sync + 'static>(
+        &self,
+        guild_id: GuildId,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForGuildEventFuture> {
+        WaitForTimeoutFuture::new(self.wait_for(guild_id, check), timeout)
     }
 
     /// Wait for a stream of events in a certain guild.
     ///
     /// Returns a Canceled error if the Standby struct was dropped.
     ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
     /// If you need to wait for only one guild event matching the given
     /// predicate, use [`wait_for`].
     ///
@@ -317,17 +699,23 @@ impl Standby {
         check: impl Into<Box<F>>,
     ) -> WaitForGuildEventStream {
         tracing::trace!(%guild_id, "waiting for event in guild");
-        let (tx, rx) = mpsc::unbounded();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Guild(guild_id));
 
         {
             let mut guild = self.0.guilds.entry(guild_id).or_default();
             guild.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Mpsc(tx)),
             });
         }
 
-        WaitForGuildEventStream { rx }
+        WaitForGuildEventStream {
+            _registration: registration,
+            rx,
+        }
     }
 
     /// Wait for an event not in a certain guild. This must be filtered by an
@@ -335,6 +723,9 @@ impl Standby {
     ///
     /// Returns a `Canceled` error if the `Standby` struct was dropped.
     ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
     /// If you need to wait for multiple events matching the given predicate,
     /// use [`wait_for_event_stream`].
     ///
@@ -365,20 +756,144 @@ impl Standby {
         &self,
         check: impl Into<Box<F>>,
     ) -> WaitForEventFuture {
-        tracing::trace!("waiting for event");
+        self.wait_for_event_types(EventTypeFlags::all(), check)
+    }
+
+    /// Wait for an event of a certain type not in a certain guild.
+    ///
+    /// The predicate is only called with events of the type. Refer to
+    /// [`wait_for_event_types`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Wait for user 456 to be banned from any guild:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{gateway::event::{Event, EventType}, id::UserId};
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let ban = standby.wait_for_event_of(EventType::BanAdd, |event: &Event| {
+    ///     matches!(event, Event::BanAdd(ban) if ban.user.id == UserId(456))
+    /// }).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_event_types`]: Self::wait_for_event_types
+    pub fn wait_for_event_of<F: Fn(&Event) -> bool + Send + Sync + 'static>(
+        &self,
+        kind: EventType,
+        check: impl Into<Box<F>>,
+    ) -> WaitForEventFuture {
+        self.wait_for_event_types(kind.into(), check)
+    }
+
+    /// Wait for an event of one of a set of types not in a certain guild.
+    ///
+    /// Events of other types are skipped without calling the predicate, which
+    /// avoids calling the predicates of every bystander of
+    /// [`wait_for_event`] with every event that's processed.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
+    /// If you need to wait for multiple events matching the given predicate,
+    /// use [`wait_for_event_types_stream`].
+    ///
+    /// # Examples
+    ///
+    /// Wait for a member to be added to or removed from guild 123:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     gateway::event::{Event, EventTypeFlags},
+    ///     id::GuildId,
+    /// };
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let event_types = EventTypeFlags::MEMBER_ADD | EventTypeFlags::MEMBER_REMOVE;
+    /// let event = standby.wait_for_event_types(event_types, |event: &Event| match event {
+    ///     Event::MemberAdd(member) => member.guild_id == GuildId(123),
+    ///     Event::MemberRemove(member) => member.guild_id == GuildId(123),
+    ///     _ => false,
+    /// }).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_event`]: Self::wait_for_event
+    /// [`wait_for_event_types_stream`]: Self::wait_for_event_types_stream
+    pub fn wait_for_event_types<F: Fn(&Event) -> bool + Send + Sync + 'static>(
+        &self,
+        event_types: EventTypeFlags,
+        check: impl Into<Box<F>>,
+    ) -> WaitForEventFuture {
+        tracing::trace!(?event_types, "waiting for event");
         let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Event);
 
         {
             self.0.events.insert(
-                self.next_event_id(),
-                Bystander {
-                    func: check.into(),
-                    sender: Some(Sender::Oneshot(tx)),
+                id,
+                EventBystander {
+                    bystander: Bystander {
+                        func: check.into(),
+                        id,
+                        sender: Some(Sender::Oneshot(tx)),
+                    },
+                    event_types,
                 },
             );
         }
 
-        WaitForEventFuture { rx }
+        WaitForEventFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for an event not in a certain guild, for up to a duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching event
+    /// was processed in time, in which case the bystander is removed from the
+    /// standby. Refer to [`wait_for_event`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Wait up to a minute for a `Ready` event for shard 5:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use twilight_model::gateway::event::Event;
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let ready = standby.wait_for_event_with_timeout(|event: &Event| {
+    ///     if let Event::Ready(ready) = event {
+    ///         ready.shard.map(|[id, _]| id == 5).unwrap_or(false)
+    ///     } else {
+    ///         false
+    ///     }
+    /// }, Duration::from_secs(60)).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_event`]: Self::wait_for_event
+    pub fn wait_for_event_with_timeout<F: Fn(&Event) -> bool + Send + Sync + 'static>(
+        &self,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForEventFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_event(check), timeout)
     }
 
     /// Wait for a stream of events not in a certain guild. This must be
@@ -386,6 +901,11 @@ impl Standby {
     ///
     /// Returns a `Canceled` error if the `Standby` struct was dropped.
     ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
     /// If you need to wait for only one event matching the given predicate, use
     /// [`wait_for_event`].
     ///
@@ -420,121 +940,334 @@ impl Standby {
         &self,
         check: impl Into<Box<F>>,
     ) -> WaitForEventStream {
-        tracing::trace!("waiting for event");
-        let (tx, rx) = mpsc::unbounded();
+        self.wait_for_event_types_stream(EventTypeFlags::all(), check)
+    }
+
+    /// Wait for a stream of events of one of a set of types not in a certain
+    /// guild.
+    ///
+    /// Events of other types are skipped without calling the predicate. Refer
+    /// to [`wait_for_event_stream`] for more information.
+    ///
+    /// [`wait_for_event_stream`]: Self::wait_for_event_stream
+    pub fn wait_for_event_types_stream<F: Fn(&Event) -> bool + Send + Sync + 'static>(
+        &self,
+        event_types: EventTypeFlags,
+        check: impl Into<Box<F>>,
+    ) -> WaitForEventStream {
+        tracing::trace!(?event_types, "waiting for event");
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Event);
 
         {
             self.0.events.insert(
-                self.next_event_id(),
-                Bystander {
-                    func: check.into(),
-                    sender: Some(Sender::Mpsc(tx)),
+                id,
+                EventBystander {
+                    bystander: Bystander {
+                        func: check.into(),
+                        id,
+                        sender: Some(Sender::Mpsc(tx)),
+                    },
+                    event_types,
                 },
             );
         }
 
-        WaitForEventStream { rx }
+        WaitForEventStream {
+            _registration: registration,
+            rx,
+        }
     }
 
-    /// Wait for a message in a certain channel.
+    /// Wait for a direct message by a certain user.
+    ///
+    /// Matches messages created outside of guilds with the user as their
+    /// author, regardless of the channel they're in, so the ID of the user's
+    /// private channel doesn't need to be known.
     ///
     /// Returns a `Canceled` error if the `Standby` struct was dropped.
     ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
     /// If you need to wait for multiple messages matching the given predicate,
-    /// use [`wait_for_message_stream`].
+    /// use [`wait_for_dm_message_stream`].
     ///
     /// # Examples
     ///
-    /// Wait for a message in channel 123 by user 456 with the content "test":
+    /// Wait for a direct message by user 456 with the content "yes":
     ///
     /// ```no_run
     /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use futures_util::future;
-    /// use twilight_model::{gateway::payload::MessageCreate, id::{ChannelId, UserId}};
+    /// use twilight_model::{gateway::payload::MessageCreate, id::UserId};
     /// use twilight_standby::Standby;
     ///
     /// let standby = Standby::new();
     ///
-    /// let message = standby.wait_for_message(ChannelId(123), |event: &MessageCreate| {
-    ///     event.author.id == UserId(456) && event.content == "test"
+    /// let message = standby.wait_for_dm_message(UserId(456), |event: &MessageCreate| {
+    ///     event.content == "yes"
     /// }).await?;
     /// # Ok(()) }
     /// ```
     ///
-    /// [`wait_for_message_stream`]: Self::wait_for_message_stream
-    pub fn wait_for_message<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+    /// [`wait_for_dm_message_stream`]: Self::wait_for_dm_message_stream
+    pub fn wait_for_dm_message<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
         &self,
-        channel_id: ChannelId,
+        user_id: UserId,
         check: impl Into<Box<F>>,
-    ) -> WaitForMessageFuture {
-        tracing::trace!(%channel_id, "waiting for message in channel");
+    ) -> WaitForDmMessageFuture {
+        tracing::trace!(%user_id, "waiting for direct message by user");
         let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::DmMessage(user_id));
 
         {
-            let mut guild = self.0.messages.entry(channel_id).or_default();
-            guild.push(Bystander {
+            let mut user = self.0.dm_messages.entry(user_id).or_default();
+            user.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Oneshot(tx)),
             });
         }
 
-        WaitForMessageFuture { rx }
+        WaitForDmMessageFuture {
+            _registration: registration,
+            rx,
+        }
     }
 
-    /// Wait for a stream of message in a certain channel.
-    ///
-    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    /// Wait for a direct message by a certain user, for up to a duration.
     ///
-    /// If you need to wait for only one message matching the given predicate,
-    /// use [`wait_for_message`].
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching
+    /// message was processed in time, in which case the bystander is removed
+    /// from the standby. Refer to [`wait_for_dm_message`] for more
+    /// information.
     ///
     /// # Examples
     ///
-    /// Wait for multiple messages in channel 123 by user 456 with the content
-    /// "test":
+    /// Wait up to 5 minutes for a direct message by user 456:
     ///
     /// ```no_run
     /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use futures_util::stream::StreamExt;
-    /// use twilight_model::{gateway::payload::MessageCreate, id::{ChannelId, UserId}};
+    /// use std::time::Duration;
+    /// use twilight_model::{gateway::payload::MessageCreate, id::UserId};
     /// use twilight_standby::Standby;
     ///
     /// let standby = Standby::new();
     ///
-    /// let mut messages = standby.wait_for_message_stream(ChannelId(123), |event: &MessageCreate| {
-    ///     event.author.id == UserId(456) && event.content == "test"
-    /// });
-    ///
-    /// while let Some(message) = messages.next().await {
-    ///     println!("got message by {}", message.author.id);
-    /// }
+    /// let message = standby.wait_for_dm_message_with_timeout(
+    ///     UserId(456),
+    ///     |_: &MessageCreate| true,
+    ///     Duration::from_secs(5 * 60),
+    /// ).await?;
     /// # Ok(()) }
     /// ```
     ///
-    /// [`wait_for_message`]: Self::wait_for_message
-    pub fn wait_for_message_stream<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+    /// [`wait_for_dm_message`]: Self::wait_for_dm_message
+    pub fn wait_for_dm_message_with_timeout<
+        F: Fn(&MessageCreate) -> bool + Send + Sync + 'static,
+    >(
         &self,
-        channel_id: ChannelId,
+        user_id: UserId,
         check: impl Into<Box<F>>,
-    ) -> WaitForMessageStream {
-        tracing::trace!(%channel_id, "waiting for message in channel");
-        let (tx, rx) = mpsc::unbounded();
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForDmMessageFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_dm_message(user_id, check), timeout)
+    }
+
+    /// Wait for a stream of direct messages by a certain user.
+    ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
+    /// If you need to wait for only one message matching the given predicate,
+    /// use [`wait_for_dm_message`].
+    ///
+    /// [`wait_for_dm_message`]: Self::wait_for_dm_message
+    pub fn wait_for_dm_message_stream<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        user_id: UserId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForDmMessageStream {
+        tracing::trace!(%user_id, "waiting for direct messages by user");
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::DmMessage(user_id));
 
         {
-            let mut guild = self.0.messages.entry(channel_id).or_default();
-            guild.push(Bystander {
+            let mut user = self.0.dm_messages.entry(user_id).or_default();
+            user.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Mpsc(tx)),
             });
         }
 
-        WaitForMessageStream { rx }
+        WaitForDmMessageStream {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for a message in a certain channel.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
+    /// If you need to wait for multiple messages matching the given predicate,
+    /// use [`wait_for_message_stream`].
+    ///
+    /// # Examples
+    ///
+    /// Wait for a message in channel 123 by user 456 with the content "test":
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures_util::future;
+    /// use twilight_model::{gateway::payload::MessageCreate, id::{ChannelId, UserId}};
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let message = standby.wait_for_message(ChannelId(123), |event: &MessageCreate| {
+    ///     event.author.id == UserId(456) && event.content == "test"
+    /// }).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_message_stream`]: Self::wait_for_message_stream
+    pub fn wait_for_message<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        channel_id: ChannelId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForMessageFuture {
+        tracing::trace!(%channel_id, "waiting for message in channel");
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Message(channel_id));
+
+        {
+            let mut guild = self.0.messages.entry(channel_id).or_default();
+            guild.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Oneshot(tx)),
+            });
+        }
+
+        WaitForMessageFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for a message in a certain channel, for up to a duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching
+    /// message was processed in time, in which case the bystander is removed
+    /// from the standby. Refer to [`wait_for_message`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Wait up to 30 seconds for a message in channel 123 by user 456:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use twilight_model::{gateway::payload::MessageCreate, id::{ChannelId, UserId}};
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let message = standby.wait_for_message_with_timeout(ChannelId(123), |event: &MessageCreate| {
+    ///     event.author.id == UserId(456)
+    /// }, Duration::from_secs(30)).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_message`]: Self::wait_for_message
+    pub fn wait_for_message_with_timeout<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        channel_id: ChannelId,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForMessageFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_message(channel_id, check), timeout)
+    }
+
+    /// Wait for a stream of message in a certain channel.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
+    /// If you need to wait for only one message matching the given predicate,
+    /// use [`wait_for_message`].
+    ///
+    /// # Examples
+    ///
+    /// Wait for multiple messages in channel 123 by user 456 with the content
+    /// "test":
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures_util::stream::StreamExt;
+    /// use twilight_model::{gateway::payload::MessageCreate, id::{ChannelId, UserId}};
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let mut messages = standby.wait_for_message_stream(ChannelId(123), |event: &MessageCreate| {
+    ///     event.author.id == UserId(456) && event.content == "test"
+    /// });
+    ///
+    /// while let Some(message) = messages.next().await {
+    ///     println!("got message by {}", message.author.id);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_message`]: Self::wait_for_message
+    pub fn wait_for_message_stream<F: Fn(&MessageCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        channel_id: ChannelId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForMessageStream {
+        tracing::trace!(%channel_id, "waiting for message in channel");
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Message(channel_id));
+
+        {
+            let mut guild = self.0.messages.entry(channel_id).or_default();
+            guild.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Mpsc(tx)),
+            });
+        }
+
+        WaitForMessageStream {
+            _registration: registration,
+            rx,
+        }
     }
 
     /// Wait for a reaction on a certain message.
     ///
     /// Returns a `Canceled` error if the `Standby` struct was dropped.
     ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
     /// If you need to wait for multiple reactions matching the given predicate,
     /// use [`wait_for_reaction_stream`].
     ///
@@ -564,22 +1297,67 @@ impl Standby {
     ) -> WaitForReactionFuture {
         tracing::trace!(%message_id, "waiting for reaction on message");
         let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Reaction(message_id));
 
         {
             let mut guild = self.0.reactions.entry(message_id).or_default();
             guild.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Oneshot(tx)),
             });
         }
 
-        WaitForReactionFuture { rx }
+        WaitForReactionFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for a reaction on a certain message, for up to a duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching
+    /// reaction was processed in time, in which case the bystander is removed
+    /// from the standby. Refer to [`wait_for_reaction`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Wait up to 30 seconds for a reaction on message 123 by user 456:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use twilight_model::{gateway::payload::ReactionAdd, id::{MessageId, UserId}};
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let reaction = standby.wait_for_reaction_with_timeout(MessageId(123), |event: &ReactionAdd| {
+    ///     event.user_id == UserId(456)
+    /// }, Duration::from_secs(30)).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_reaction`]: Self::wait_for_reaction
+    pub fn wait_for_reaction_with_timeout<F: Fn(&ReactionAdd) -> bool + Send + Sync + 'static>(
+        &self,
+        message_id: MessageId,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForReactionFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_reaction(message_id, check), timeout)
     }
 
     /// Wait for a stream of reactions on a certain message.
     ///
     /// Returns a `Canceled` error if the `Standby` struct was dropped.
     ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
     /// If you need to wait for only one reaction matching the given predicate,
     /// use [`wait_for_reaction`].
     ///
@@ -616,74 +1394,559 @@ impl Standby {
         check: impl Into<Box<F>>,
     ) -> WaitForReactionStream {
         tracing::trace!(%message_id, "waiting for reaction on message");
-        let (tx, rx) = mpsc::unbounded();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Reaction(message_id));
 
         {
             let mut guild = self.0.reactions.entry(message_id).or_default();
             guild.push(Bystander {
                 func: check.into(),
+                id,
                 sender: Some(Sender::Mpsc(tx)),
             });
         }
 
-        WaitForReactionStream { rx }
-    }
+        WaitForReactionStream {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for a component interaction on a certain message, such as a
+    /// button on it being pressed.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
+    /// If you need to wait for multiple component interactions matching the
+    /// given predicate, use [`wait_for_component_stream`].
+    ///
+    /// # Examples
+    ///
+    /// Wait for the button with the custom ID "confirm" on message 123 to be
+    /// pressed:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     application::interaction::InteractionData,
+    ///     gateway::payload::InteractionCreate,
+    ///     id::MessageId,
+    /// };
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let interaction = standby.wait_for_component(MessageId(123), |event: &InteractionCreate| {
+    ///     matches!(&event.data, Some(InteractionData::MessageComponent(data)) if data.custom_id == "confirm")
+    /// }).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_component_stream`]: Self::wait_for_component_stream
+    pub fn wait_for_component<F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        message_id: MessageId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForComponentFuture {
+        tracing::trace!(%message_id, "waiting for component interaction on message");
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Component(message_id));
+
+        {
+            let mut bystanders = self.0.components.entry(message_id).or_default();
+            bystanders.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Oneshot(tx)),
+            });
+        }
+
+        WaitForComponentFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for a component interaction on a certain message, for up to a
+    /// duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching
+    /// interaction was processed in time, in which case the bystander is
+    /// removed from the standby. Refer to [`wait_for_component`] for more
+    /// information.
+    ///
+    /// [`wait_for_component`]: Self::wait_for_component
+    pub fn wait_for_component_with_timeout<
+        F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static,
+    >(
+        &self,
+        message_id: MessageId,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForComponentFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_component(message_id, check), timeout)
+    }
+
+    /// Wait for a stream of component interactions on a certain message.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
+    /// If you need to wait for only one component interaction matching the
+    /// given predicate, use [`wait_for_component`].
+    ///
+    /// [`wait_for_component`]: Self::wait_for_component
+    pub fn wait_for_component_stream<F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        message_id: MessageId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForComponentStream {
+        tracing::trace!(%message_id, "waiting for component interactions on message");
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Component(message_id));
+
+        {
+            let mut bystanders = self.0.components.entry(message_id).or_default();
+            bystanders.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Mpsc(tx)),
+            });
+        }
+
+        WaitForComponentStream {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for an interaction in a certain guild, of any type.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// Dropping the returned future removes its bystander from the standby,
+    /// such as when it's wrapped in a timeout that elapses.
+    ///
+    /// If you need to wait for multiple interactions matching the given
+    /// predicate, use [`wait_for_interaction_stream`]. To wait for the
+    /// components of a certain message, use [`wait_for_component`].
+    ///
+    /// # Examples
+    ///
+    /// Wait for user 456 to invoke a command in guild 123:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     application::interaction::InteractionType,
+    ///     gateway::payload::InteractionCreate,
+    ///     id::{GuildId, UserId},
+    /// };
+    /// use twilight_standby::Standby;
+    ///
+    /// let standby = Standby::new();
+    ///
+    /// let interaction = standby.wait_for_interaction(GuildId(123), |event: &InteractionCreate| {
+    ///     event.kind == InteractionType::ApplicationCommand
+    ///         && event.author().map(|user| user.id) == Some(UserId(456))
+    /// }).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`wait_for_component`]: Self::wait_for_component
+    /// [`wait_for_interaction_stream`]: Self::wait_for_interaction_stream
+    pub fn wait_for_interaction<F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static>(
+        &self,
+        guild_id: GuildId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForInteractionFuture {
+        tracing::trace!(%guild_id, "waiting for interaction in guild");
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Interaction(guild_id));
+
+        {
+            let mut bystanders = self.0.interactions.entry(guild_id).or_default();
+            bystanders.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Oneshot(tx)),
+            });
+        }
+
+        WaitForInteractionFuture {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for an interaction in a certain guild, for up to a duration.
+    ///
+    /// Returns a [`WaitForTimeoutError::TimedOut`] error if no matching
+    /// interaction was processed in time, in which case the bystander is
+    /// removed from the standby. Refer to [`wait_for_interaction`] for more
+    /// information.
+    ///
+    /// [`wait_for_interaction`]: Self::wait_for_interaction
+    pub fn wait_for_interaction_with_timeout<
+        F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static,
+    >(
+        &self,
+        guild_id: GuildId,
+        check: impl Into<Box<F>>,
+        timeout: Duration,
+    ) -> WaitForTimeoutFuture<WaitForInteractionFuture> {
+        WaitForTimeoutFuture::new(self.wait_for_interaction(guild_id, check), timeout)
+    }
+
+    /// Wait for a stream of interactions in a certain guild.
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// The stream yields matching events until it's dropped, which removes
+    /// its bystander from the standby. Up to 100 events are buffered while
+    /// the stream isn't polled, and matching events are dropped while the
+    /// buffer is full.
+    ///
+    /// If you need to wait for only one interaction matching the given
+    /// predicate, use [`wait_for_interaction`].
+    ///
+    /// [`wait_for_interaction`]: Self::wait_for_interaction
+    pub fn wait_for_interaction_stream<
+        F: Fn(&InteractionCreate) -> bool + Send + Sync + 'static,
+    >(
+        &self,
+        guild_id: GuildId,
+        check: impl Into<Box<F>>,
+    ) -> WaitForInteractionStream {
+        tracing::trace!(%guild_id, "waiting for interactions in guild");
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::Interaction(guild_id));
+
+        {
+            let mut bystanders = self.0.interactions.entry(guild_id).or_default();
+            bystanders.push(Bystander {
+                func: check.into(),
+                id,
+                sender: Some(Sender::Mpsc(tx)),
+            });
+        }
+
+        WaitForInteractionStream {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    /// Wait for all of the member chunks sent in response to a request for
+    /// guild members with a nonce.
+    ///
+    /// The future resolves once the last chunk is received, with the members,
+    /// presences, and IDs of users not found across every chunk. Chunks of
+    /// other requests, even in the same guild, are ignored: use a nonce unique
+    /// to the request, such as with [`RequestGuildMembersBuilder::nonce`].
+    ///
+    /// Returns a `Canceled` error if the `Standby` struct was dropped.
+    ///
+    /// To process the chunks as they're received, use
+    /// [`wait_for_member_chunk_stream`].
+    ///
+    /// # Examples
+    ///
+    /// Request the members of guild 123 and wait for the response:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+    /// use twilight_standby::Standby;
+    ///
+    /// let shard = Shard::new("token", Intents::GUILD_MEMBERS);
+    /// let standby = Standby::new();
+    ///
+    /// let chunks = standby.wait_for_member_chunks(GuildId(123), "members-123");
+    /// let request = RequestGuildMembers::builder(GuildId(123))
+    ///     .nonce("members-123")
+    ///     .query("", None);
+    /// shard.command(&request).await?;
+    ///
+    /// let chunks = chunks.await?;
+    /// println!("got {} members", chunks.members.len());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`RequestGuildMembersBuilder::nonce`]: twilight_model::gateway::payload::request_guild_members::RequestGuildMembersBuilder::nonce
+    /// [`wait_for_member_chunk_stream`]: Self::wait_for_member_chunk_stream
+    pub fn wait_for_member_chunks(
+        &self,
+        guild_id: GuildId,
+        nonce: impl Into<String>,
+    ) -> WaitForMemberChunksFuture {
+        WaitForMemberChunksFuture::new(self.wait_for_member_chunk_stream(guild_id, nonce))
+    }
+
+    /// Wait for a stream of the member chunks sent in response to a request
+    /// for guild members with a nonce.
+    ///
+    /// The stream yields each chunk of the response, including chunks
+    /// processed while it isn't polled, and ends after the last one. Chunks of
+    /// other requests, even in the same guild, are ignored.
+    ///
+    /// To wait for all of the chunks at once, use [`wait_for_member_chunks`].
+    ///
+    /// [`wait_for_member_chunks`]: Self::wait_for_member_chunks
+    pub fn wait_for_member_chunk_stream(
+        &self,
+        guild_id: GuildId,
+        nonce: impl Into<String>,
+    ) -> WaitForMemberChunkStream {
+        let nonce = nonce.into();
+        tracing::trace!(%guild_id, %nonce, "waiting for member chunks in guild");
+        let (tx, rx) = mpsc::unbounded();
+        let id = self.next_id();
+        let registration = self.register(id, BystanderKey::MemberChunk(guild_id, nonce.clone()));
+
+        {
+            let mut bystanders = self.0.member_chunks.entry((guild_id, nonce)).or_default();
+            bystanders.push(Bystander {
+                func: Box::new(|_: &MemberChunk| true),
+                id,
+                sender: Some(Sender::Unbounded(tx)),
+            });
+        }
+
+        WaitForMemberChunkStream {
+            _registration: registration,
+            rx,
+        }
+    }
+
+    fn next_id(&self) -> u64 {
+        self.0.bystander_counter.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Register the bystander of a future or stream, to remove it once the
+    /// future or stream is dropped.
+    ///
+    /// Must be called before the bystander is added to its map, so that it's
+    /// counted before it can be removed by processing an event.
+    fn register(&self, id: u64, key: BystanderKey) -> Registration {
+        self.0.counts.get(&key).fetch_add(1, Ordering::Relaxed);
+
+        Registration {
+            id,
+            key,
+            standby: Arc::downgrade(&self.0),
+        }
+    }
+
+    fn process_component(&self, message_id: MessageId, event: &InteractionCreate) -> usize {
+        let (matched, remove) = match self.0.components.get_mut(&message_id) {
+            Some(mut bystanders) => {
+                let matched =
+                    self.bystander_iter(&mut bystanders, event, &self.0.counts.components);
+
+                (matched, bystanders.is_empty())
+            }
+            None => {
+                tracing::trace!(%message_id, "message has no component bystanders");
+
+                return 0;
+            }
+        };
+
+        if remove {
+            tracing::trace!(%message_id, "removing message from component map");
+
+            self.0
+                .components
+                .remove_if(&message_id, |_, bystanders| bystanders.is_empty());
+        }
+
+        matched
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn process_dm_message(&self, user_id: UserId, event: &MessageCreate) -> usize {
+        let (matched, remove) = match self.0.dm_messages.get_mut(&user_id) {
+            Some(mut bystanders) => {
+                let matched =
+                    self.bystander_iter(&mut bystanders, event, &self.0.counts.dm_messages);
+
+                (matched, bystanders.is_empty())
+            }
+            None => {
+                tracing::trace!(%user_id, "user has no direct message bystanders");
+
+                return 0;
+            }
+        };
+
+        if remove {
+            tracing::trace!(%user_id, "removing user from map");
+
+            self.0
+                .dm_messages
+                .remove_if(&user_id, |_, bystanders| bystanders.is_empty());
+        }
 
-    fn next_event_id(&self) -> u64 {
-        self.0.event_counter.fetch_add(1, Ordering::SeqCst)
+        matched
     }
 
     #[tracing::instrument(level = "trace")]
-    fn process_event(&self, event: &Event) {
+    fn process_event(&self, event: &Event) -> usize {
         tracing::trace!(?event, event_type = ?event.kind(), "processing event");
 
+        let event_type = EventTypeFlags::from(event.kind());
+        let mut matched = 0;
+
         self.0.events.retain(|id, bystander| {
+            // Bystanders not waiting for events of the type are retained
+            // without calling their predicates.
+            if !bystander.event_types.contains(event_type) {
+                return true;
+            }
+
             // `bystander_process` returns whether it is fulfilled, so invert it
             // here. If it's fulfilled, then we don't want to retain it.
-            let retaining = !self.bystander_process(bystander, event);
+            let retaining = !self.bystander_process(&mut bystander.bystander, event, &mut matched);
+
+            if !retaining {
+                self.0.counts.events.fetch_sub(1, Ordering::Relaxed);
+            }
 
             tracing::trace!(bystander_id = id, %retaining, "event bystander processed");
 
             retaining
         });
+
+        matched
     }
 
     #[tracing::instrument(level = "trace")]
-    fn process_guild(&self, guild_id: GuildId, event: &Event) {
-        let remove = match self.0.guilds.get_mut(&guild_id) {
+    fn process_guild(&self, guild_id: GuildId, event: &Event) -> usize {
+        let (matched, remove) = match self.0.guilds.get_mut(&guild_id) {
             Some(mut bystanders) => {
-                self.bystander_iter(&mut bystanders, event);
+                let matched = self.bystander_iter(&mut bystanders, event, &self.0.counts.guilds);
 
-                bystanders.is_empty()
+                (matched, bystanders.is_empty())
             }
             None => {
                 tracing::trace!(%guild_id, "guild has no event bystanders");
 
-                return;
+                return 0;
             }
         };
 
         if remove {
             tracing::trace!(%guild_id, "removing guild from map");
 
-            self.0.guilds.remove(&guild_id);
+            self.0
+                .guilds
+                .remove_if(&guild_id, |_, bystanders| bystanders.is_empty());
+        }
+
+        matched
+    }
+
+    fn process_interaction(&self, guild_id: GuildId, event: &InteractionCreate) -> usize {
+        let (matched, remove) = match self.0.interactions.get_mut(&guild_id) {
+            Some(mut bystanders) => {
+                let matched =
+                    self.bystander_iter(&mut bystanders, event, &self.0.counts.interactions);
+
+                (matched, bystanders.is_empty())
+            }
+            None => {
+                tracing::trace!(%guild_id, "guild has no interaction bystanders");
+
+                return 0;
+            }
+        };
+
+        if remove {
+            tracing::trace!(%guild_id, "removing guild from interaction map");
+
+            self.0
+                .interactions
+                .remove_if(&guild_id, |_, bystanders| bystanders.is_empty());
+        }
+
+        matched
+    }
+
+    fn process_member_chunk(&self, chunk: &MemberChunk) -> usize {
+        let nonce = match chunk.nonce.as_ref() {
+            Some(nonce) => nonce,
+            None => return 0,
+        };
+        let key = (chunk.guild_id, nonce.clone());
+
+        // Once the last chunk is sent, the bystanders (and their senders) are
+        // dropped, ending their streams.
+        if chunk.chunk_index + 1 >= chunk.chunk_count {
+            return match self.0.member_chunks.remove(&key) {
+                Some((_, mut bystanders)) => {
+                    tracing::trace!(guild_id = %chunk.guild_id, %nonce, "processing last member chunk");
+
+                    let count = &self.0.counts.member_chunks;
+                    let matched = self.bystander_iter(&mut bystanders, chunk, count);
+                    count.fetch_sub(bystanders.len(), Ordering::Relaxed);
+
+                    matched
+                }
+                None => 0,
+            };
+        }
+
+        let (matched, remove) = match self.0.member_chunks.get_mut(&key) {
+            Some(mut bystanders) => {
+                let matched =
+                    self.bystander_iter(&mut bystanders, chunk, &self.0.counts.member_chunks);
+
+                (matched, bystanders.is_empty())
+            }
+            None => {
+                tracing::trace!(guild_id = %chunk.guild_id, %nonce, "nonce has no member chunk bystanders");
+
+                return 0;
+            }
+        };
+
+        if remove {
+            self.0
+                .member_chunks
+                .remove_if(&key, |_, bystanders| bystanders.is_empty());
         }
+
+        matched
     }
 
     #[tracing::instrument(level = "trace")]
-    fn process_message(&self, channel_id: ChannelId, event: &MessageCreate) {
+    fn process_message(&self, channel_id: ChannelId, event: &MessageCreate) -> usize {
         tracing::trace!(%channel_id, "processing message bystanders in channel");
 
-        let remove = match self.0.messages.get_mut(&channel_id) {
+        let (matched, remove) = match self.0.messages.get_mut(&channel_id) {
             Some(mut bystanders) => {
-                self.bystander_iter(&mut bystanders, event);
+                let matched = self.bystander_iter(&mut bystanders, event, &self.0.counts.messages);
 
-                bystanders.is_empty()
+                (matched, bystanders.is_empty())
             }
             None => {
                 tracing::trace!(%channel_id, "channel has no message bystanders");
 
-                return;
+                return 0;
             }
         };
 
@@ -692,60 +1955,86 @@ impl Standby {
         if remove {
             tracing::trace!(%channel_id, "removing channel");
 
-            self.0.messages.remove(&channel_id);
+            self.0
+                .messages
+                .remove_if(&channel_id, |_, bystanders| bystanders.is_empty());
         }
+
+        matched
     }
 
-    fn process_reaction(&self, message_id: MessageId, event: &ReactionAdd) {
-        let remove = match self.0.reactions.get_mut(&message_id) {
+    fn process_reaction(&self, message_id: MessageId, event: &ReactionAdd) -> usize {
+        let (matched, remove) = match self.0.reactions.get_mut(&message_id) {
             Some(mut bystanders) => {
-                self.bystander_iter(&mut bystanders, event);
+                let matched = self.bystander_iter(&mut bystanders, event, &self.0.counts.reactions);
 
-                bystanders.is_empty()
+                (matched, bystanders.is_empty())
             }
             None => {
                 tracing::trace!("message {} has no reaction bystanders", message_id);
 
-                return;
+                return 0;
             }
         };
 
         if remove {
             tracing::trace!("removing message {}", message_id);
-            self.0.reactions.remove(&message_id);
+            self.0
+                .reactions
+                .remove_if(&message_id, |_, bystanders| bystanders.is_empty());
         }
+
+        matched
     }
 
-    /// Iterate over bystanders and remove the ones that match the predicate.
+    /// Iterate over bystanders and remove the ones that match the predicate,
+    /// decrementing the count of bystanders in their map.
+    ///
+    /// Returns the number of bystanders that the event was sent to.
     #[tracing::instrument(level = "trace")]
-    fn bystander_iter<E: Clone + Debug>(&self, bystanders: &mut Vec<Bystander<E>>, event: &E) {
+    fn bystander_iter<E: Clone + Debug>(
+        &self,
+        bystanders: &mut Vec<Bystander<E>>,
+        event: &E,
+        count: &AtomicUsize,
+    ) -> usize {
         tracing::trace!(?bystanders, "iterating over bystanders");
 
         let mut idx = 0;
+        let mut matched = 0;
 
         while idx < bystanders.len() {
             tracing::trace!(%idx, "checking bystander");
             let bystander = &mut bystanders[idx];
 
-            if self.bystander_process(bystander, event) {
+            if self.bystander_process(bystander, event, &mut matched) {
                 tracing::trace!(%idx, "removing bystander in list");
 
                 bystanders.remove(idx);
+                count.fetch_sub(1, Ordering::Relaxed);
             } else {
                 tracing::trace!("retaining bystander");
 
                 idx += 1;
             }
         }
+
+        matched
     }
 
     /// Process a bystander, sending the event if the sender is active and the
     /// predicate matches. Returns whether the bystander has fulfilled.
     ///
     /// Returns `true` if the bystander is fulfilled, meaning that the channel
-    /// is now closed or the predicate matched and the event closed.
+    /// is now closed or the predicate matched and the event closed. Increments
+    /// `matched` if the event was sent to the bystander.
     #[tracing::instrument(level = "trace")]
-    fn bystander_process<E: Clone + Debug>(&self, bystander: &mut Bystander<E>, event: &E) -> bool {
+    fn bystander_process<E: Clone + Debug>(
+        &self,
+        bystander: &mut Bystander<E>,
+        event: &E,
+        matched: &mut usize,
+    ) -> bool {
         let sender = match bystander.sender.take() {
             Some(sender) => sender,
             None => {
@@ -770,22 +2059,41 @@ impl Standby {
 
         match sender {
             Sender::Oneshot(tx) => {
-                let _ = tx.send(event.clone());
+                if tx.send(event.clone()).is_ok() {
+                    *matched += 1;
+                }
+
                 tracing::trace!("bystander matched event, indicating for removal");
 
                 true
             }
-            Sender::Mpsc(tx) => {
-                if tx.unbounded_send(event.clone()).is_ok() {
+            Sender::Mpsc(mut tx) => match tx.try_send(event.clone()) {
+                Ok(()) => {
+                    *matched += 1;
                     tracing::trace!("bystander is a stream, retaining in map");
 
                     bystander.sender.replace(Sender::Mpsc(tx));
 
                     false
-                } else {
-                    true
                 }
-            }
+                Err(source) if source.is_full() => {
+                    tracing::debug!("bystander stream's buffer is full, dropping event");
+
+                    bystander.sender.replace(Sender::Mpsc(tx));
+
+                    false
+                }
+                Err(_) => true,
+            },
+            Sender::Unbounded(tx) => match tx.unbounded_send(event.clone()) {
+                Ok(()) => {
+                    *matched += 1;
+                    bystander.sender.replace(Sender::Unbounded(tx));
+
+                    false
+                }
+                Err(_) => true,
+            },
         }
     }
 }
@@ -808,7 +2116,9 @@ fn event_guild_id(event: &Event) -> Option<GuildId> {
         Event::GuildDelete(e) => Some(e.id),
         Event::GuildEmojisUpdate(e) => Some(e.guild_id),
         Event::GuildIntegrationsUpdate(e) => Some(e.guild_id),
+        Event::GuildStickersUpdate(e) => Some(e.guild_id),
         Event::GuildUpdate(e) => Some(e.id),
+        Event::InteractionCreate(e) => e.guild_id,
         Event::InviteCreate(e) => Some(e.guild_id),
         Event::InviteDelete(e) => Some(e.guild_id),
         Event::MemberAdd(e) => Some(e.guild_id),
@@ -837,6 +2147,13 @@ fn event_guild_id(event: &Event) -> Option<GuildId> {
         Event::ShardPayload(_) => None,
         Event::ShardReconnecting(_) => None,
         Event::ShardResuming(_) => None,
+        Event::ShardStopped(_) => None,
+        Event::ThreadCreate(e) => e.guild_id,
+        Event::ThreadDelete(e) => Some(e.guild_id),
+        Event::ThreadListSync(e) => Some(e.guild_id),
+        Event::ThreadMemberUpdate(_) => None,
+        Event::ThreadMembersUpdate(e) => Some(e.guild_id),
+        Event::ThreadUpdate(e) => e.guild_id,
         Event::TypingStart(e) => e.guild_id,
         Event::UnavailableGuild(e) => Some(e.id),
         Event::UserUpdate(_) => None,
@@ -855,23 +2172,50 @@ fn channel_guild_id(channel: &Channel) -> Option<GuildId> {
 
 #[cfg(test)]
 mod tests {
-    use super::Standby;
-    use futures_util::StreamExt;
+    use super::{MemberChunks, ProcessResults, Standby, WaitForTimeoutError};
+    use futures_util::{FutureExt, StreamExt};
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{
+        fmt::Debug,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
     use twilight_model::{
+        application::{
+            component::ComponentType,
+            interaction::{
+                CommandData, Interaction, InteractionData, InteractionType, MessageComponentData,
+            },
+        },
         channel::{
             message::{Message, MessageType},
             Reaction, ReactionType,
         },
         gateway::{
-            event::{Event, EventType},
-            payload::{MessageCreate, ReactionAdd, Ready, RoleDelete},
+            event::{Event, EventType, EventTypeFlags},
+            payload::{
+                InteractionCreate, MemberChunk, MessageCreate, ReactionAdd, Ready, RoleDelete,
+            },
+        },
+        id::{
+            ApplicationId, ChannelId, CommandId, GuildId, InteractionId, MessageId, RoleId, UserId,
         },
-        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
         user::{CurrentUser, User},
     };
 
+    assert_impl_all!(
+        ProcessResults: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        PartialEq,
+        Send,
+        Sync
+    );
     assert_impl_all!(Standby: Clone, Debug, Default, Send, Sync);
 
     fn message() -> Message {
@@ -931,6 +2275,37 @@ mod tests {
         }
     }
 
+    /// Interaction created by pressing the button with a custom ID on a
+    /// message in guild 4.
+    fn button_press(message_id: MessageId, custom_id: &str) -> Event {
+        let mut message = message();
+        message.id = message_id;
+
+        Event::InteractionCreate(Box::new(InteractionCreate(Interaction {
+            application_id: ApplicationId(5),
+            channel_id: Some(message.channel_id),
+            data: Some(InteractionData::MessageComponent(MessageComponentData {
+                component_type: ComponentType::Button,
+                custom_id: custom_id.to_owned(),
+                values: Vec::new(),
+            })),
+            guild_id: message.guild_id,
+            id: InteractionId(6),
+            kind: InteractionType::MessageComponent,
+            member: None,
+            message: Some(message),
+            token: "token".to_owned(),
+            user: None,
+        })))
+    }
+
+    fn custom_id(interaction: &InteractionCreate) -> Option<&str> {
+        match &interaction.data {
+            Some(InteractionData::MessageComponent(data)) => Some(&data.custom_id),
+            _ => None,
+        }
+    }
+
     #[tokio::test]
     async fn test_wait_for() {
         let standby = Standby::new();
@@ -1066,11 +2441,64 @@ mod tests {
         assert!(stream.next().await.is_some());
         assert!(stream.next().await.is_some());
         drop(stream);
-        assert_eq!(1, standby.0.messages.len());
+        assert!(standby.0.messages.is_empty());
         standby.process(&Event::MessageCreate(Box::new(MessageCreate(message()))));
         assert!(standby.0.messages.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_wait_for_message_stream_collect() {
+        let create = |author_id| {
+            let mut message = message();
+            message.author.id = UserId(author_id);
+
+            Event::MessageCreate(Box::new(MessageCreate(message)))
+        };
+
+        let standby = Standby::new();
+        let stream = standby.wait_for_message_stream(ChannelId(1), |message: &MessageCreate| {
+            message.author.id == UserId(2)
+        });
+
+        for author_id in &[2, 5, 2, 2, 5] {
+            standby.process(&create(*author_id));
+        }
+
+        let messages = stream.take(3).collect::<Vec<_>>().await;
+        assert!(messages
+            .iter()
+            .all(|message| message.author.id == UserId(2)));
+        // The stream was dropped by `take`, deregistering its bystander.
+        assert!(standby.0.messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_event_stream_full() {
+        let standby = Standby::new();
+        let mut stream =
+            standby.wait_for_event_stream(|event: &Event| event.kind() == EventType::Resumed);
+
+        // Events matching the stream while its buffer is full are dropped.
+        for _ in 0..super::STREAM_BUFFER * 2 {
+            standby.process(&Event::Resumed);
+        }
+        assert!(!standby.0.events.is_empty());
+
+        let mut buffered = 0;
+
+        while let Some(Some(_)) = stream.next().now_or_never() {
+            buffered += 1;
+        }
+
+        // Each sender of a channel is guaranteed a slot on top of its buffer.
+        assert_eq!(super::STREAM_BUFFER + 1, buffered);
+
+        standby.process(&Event::Resumed);
+        assert_eq!(Some(Event::Resumed), stream.next().await);
+        drop(stream);
+        assert!(standby.0.events.is_empty());
+    }
+
     #[tokio::test]
     async fn test_wait_for_reaction() {
         let event = Event::ReactionAdd(Box::new(ReactionAdd(reaction())));
@@ -1086,6 +2514,55 @@ mod tests {
         assert!(standby.0.reactions.is_empty());
     }
 
+    /// Test that reaction bystanders only receive reactions on their message
+    /// that match their predicate.
+    #[tokio::test]
+    async fn test_wait_for_reaction_concurrent() {
+        let reaction_on = |message_id, user_id| {
+            let mut reaction = reaction();
+            reaction.message_id = MessageId(message_id);
+            reaction.user_id = UserId(user_id);
+
+            Event::ReactionAdd(Box::new(ReactionAdd(reaction)))
+        };
+
+        let standby = Standby::new();
+        let first = standby.wait_for_reaction(MessageId(4), |reaction: &ReactionAdd| {
+            reaction.user_id == UserId(3)
+        });
+        let second = standby.wait_for_reaction(MessageId(5), |reaction: &ReactionAdd| {
+            reaction.user_id == UserId(3)
+        });
+        let mut stream = standby.wait_for_reaction_stream(MessageId(5), |_: &ReactionAdd| true);
+        assert_eq!(2, standby.0.reactions.len());
+
+        // Neither the reaction by another user nor the reaction on another
+        // message completes the first bystander.
+        standby.process(&reaction_on(4, 6));
+        standby.process(&reaction_on(5, 3));
+        assert_eq!(1, standby.0.reactions.get(&MessageId(4)).unwrap().len());
+        assert_eq!(
+            Ok((MessageId(5), UserId(3))),
+            second.await.map(|r| (r.message_id, r.user_id))
+        );
+        assert_eq!(
+            Some((MessageId(5), UserId(3))),
+            stream.next().await.map(|r| (r.message_id, r.user_id))
+        );
+
+        standby.process(&reaction_on(4, 3));
+        assert_eq!(
+            Ok((MessageId(4), UserId(3))),
+            first.await.map(|r| (r.message_id, r.user_id))
+        );
+        assert!(!standby.0.reactions.contains_key(&MessageId(4)));
+
+        // The stream keeps receiving reactions on its message until it's
+        // dropped.
+        standby.process(&reaction_on(5, 6));
+        assert_eq!(Some(UserId(6)), stream.next().await.map(|r| r.user_id));
+    }
+
     #[tokio::test]
     async fn test_wait_for_reaction_stream() {
         let standby = Standby::new();
@@ -1096,11 +2573,113 @@ mod tests {
         assert!(stream.next().await.is_some());
         assert!(stream.next().await.is_some());
         drop(stream);
-        assert_eq!(1, standby.0.reactions.len());
+        assert!(standby.0.reactions.is_empty());
         standby.process(&Event::ReactionAdd(Box::new(ReactionAdd(reaction()))));
         assert!(standby.0.reactions.is_empty());
     }
 
+    /// Test that a button press is only sent to the bystanders of the message
+    /// the button is on whose predicate matches its custom ID.
+    #[tokio::test]
+    async fn test_wait_for_component() {
+        let standby = Standby::new();
+        let confirm = standby.wait_for_component(MessageId(3), |event: &InteractionCreate| {
+            custom_id(event) == Some("confirm")
+        });
+        let other = standby.wait_for_component(MessageId(7), |_: &InteractionCreate| true);
+        assert_eq!(2, standby.stats().components());
+
+        let results = standby.process(&button_press(MessageId(3), "cancel"));
+        assert_eq!(0, results.components());
+
+        let results = standby.process(&button_press(MessageId(3), "confirm"));
+        assert_eq!(1, results.components());
+        let interaction = confirm.await.unwrap();
+        assert_eq!(Some("confirm"), custom_id(&interaction));
+        assert!(!standby.0.components.contains_key(&MessageId(3)));
+
+        assert!(other.now_or_never().is_none());
+        assert!(standby.0.components.is_empty());
+        assert!(standby.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_component_stream() {
+        let standby = Standby::new();
+        let mut stream =
+            standby.wait_for_component_stream(MessageId(3), |_: &InteractionCreate| true);
+        standby.process(&button_press(MessageId(3), "previous"));
+        standby.process(&button_press(MessageId(3), "next"));
+
+        assert_eq!(
+            Some("previous".to_owned()),
+            stream
+                .next()
+                .await
+                .and_then(|e| custom_id(&e).map(ToOwned::to_owned))
+        );
+        assert_eq!(
+            Some("next".to_owned()),
+            stream
+                .next()
+                .await
+                .and_then(|e| custom_id(&e).map(ToOwned::to_owned))
+        );
+        drop(stream);
+        assert!(standby.0.components.is_empty());
+        assert_eq!(0, standby.stats().components());
+    }
+
+    /// Test that interaction bystanders receive interactions of any type in
+    /// their guild, while command interactions aren't sent to component
+    /// bystanders.
+    #[tokio::test]
+    async fn test_wait_for_interaction() {
+        let standby = Standby::new();
+        let mut stream =
+            standby.wait_for_interaction_stream(GuildId(4), |_: &InteractionCreate| true);
+        let component = standby.wait_for_component(MessageId(3), |_: &InteractionCreate| true);
+        let other_guild = standby.wait_for_interaction(GuildId(1), |_: &InteractionCreate| true);
+
+        let results = standby.process(&button_press(MessageId(3), "confirm"));
+        assert_eq!(1, results.components());
+        assert_eq!(1, results.interactions());
+        assert!(component.await.is_ok());
+        assert_eq!(
+            Some(InteractionType::MessageComponent),
+            stream.next().await.map(|e| e.kind)
+        );
+
+        let command = Event::InteractionCreate(Box::new(InteractionCreate(Interaction {
+            application_id: ApplicationId(5),
+            channel_id: Some(ChannelId(1)),
+            data: Some(InteractionData::ApplicationCommand(CommandData {
+                id: CommandId(8),
+                name: "ban".to_owned(),
+                options: Vec::new(),
+            })),
+            guild_id: Some(GuildId(4)),
+            id: InteractionId(9),
+            kind: InteractionType::ApplicationCommand,
+            member: None,
+            message: None,
+            token: "token".to_owned(),
+            user: None,
+        })));
+        let results = standby.process(&command);
+        assert_eq!(0, results.components());
+        assert_eq!(1, results.interactions());
+        assert_eq!(
+            Some(InteractionType::ApplicationCommand),
+            stream.next().await.map(|e| e.kind)
+        );
+
+        assert!(other_guild.now_or_never().is_none());
+        drop(stream);
+        assert!(standby.0.interactions.is_empty());
+        assert!(standby.is_empty());
+    }
+
     #[tokio::test]
     async fn test_handles_wrong_events() {
         let standby = Standby::new();
@@ -1146,4 +2725,280 @@ mod tests {
         standby.process(&Event::ReactionAdd(Box::new(ReactionAdd(reaction()))));
         assert!(matches!(wait.await, Ok(Event::ReactionAdd(_))));
     }
+
+    /// Test that bystanders of futures are removed once the futures are
+    /// dropped, without needing events to be processed.
+    #[tokio::test]
+    async fn test_drop_removes_bystanders() {
+        let standby = Standby::new();
+
+        let event = standby.wait_for_event(|_: &Event| false);
+        let guild = standby.wait_for(GuildId(1), |_: &Event| false);
+        let other_guild = standby.wait_for(GuildId(1), |_: &Event| false);
+        let message = standby.wait_for_message(ChannelId(1), |_: &MessageCreate| false);
+        let reaction = standby.wait_for_reaction(MessageId(4), |_: &ReactionAdd| false);
+        assert_eq!(1, standby.0.events.len());
+        assert_eq!(2, standby.0.guilds.get(&GuildId(1)).unwrap().len());
+        assert_eq!(1, standby.0.messages.len());
+        assert_eq!(1, standby.0.reactions.len());
+
+        drop(event);
+        drop(guild);
+        drop(message);
+        drop(reaction);
+        assert!(standby.0.events.is_empty());
+        assert_eq!(1, standby.0.guilds.get(&GuildId(1)).unwrap().len());
+        assert!(standby.0.messages.is_empty());
+        assert!(standby.0.reactions.is_empty());
+
+        drop(other_guild);
+        assert!(standby.0.guilds.is_empty());
+    }
+
+    fn member_chunk(nonce: Option<&str>, chunk_index: u32, not_found: UserId) -> Event {
+        Event::MemberChunk(MemberChunk {
+            chunk_count: 2,
+            chunk_index,
+            guild_id: GuildId(1),
+            members: Vec::new(),
+            nonce: nonce.map(ToOwned::to_owned),
+            not_found: vec![not_found],
+            presences: Vec::new(),
+        })
+    }
+
+    /// Test that the chunks of two requests for members in the same guild are
+    /// collected separately when they're interleaved.
+    #[tokio::test]
+    async fn test_wait_for_member_chunks_interleaved() {
+        let standby = Standby::new();
+        let a = standby.wait_for_member_chunks(GuildId(1), "a");
+        let mut b = standby.wait_for_member_chunk_stream(GuildId(1), "b");
+        let mut other_guild = standby.wait_for_member_chunks(GuildId(2), "a");
+
+        standby.process(&member_chunk(Some("a"), 0, UserId(1)));
+        standby.process(&member_chunk(Some("b"), 0, UserId(2)));
+        standby.process(&member_chunk(None, 0, UserId(5)));
+        standby.process(&member_chunk(Some("a"), 1, UserId(3)));
+        standby.process(&member_chunk(Some("b"), 1, UserId(4)));
+
+        assert_eq!(
+            MemberChunks {
+                members: Vec::new(),
+                not_found: vec![UserId(1), UserId(3)],
+                presences: Vec::new(),
+            },
+            a.await.unwrap()
+        );
+
+        let chunks = b.by_ref().collect::<Vec<_>>().await;
+        assert_eq!(2, chunks.len());
+        assert_eq!(vec![UserId(2)], chunks[0].not_found);
+        assert_eq!(vec![UserId(4)], chunks[1].not_found);
+        assert!(b.next().await.is_none());
+
+        assert!((&mut other_guild).now_or_never().is_none());
+        assert_eq!(1, standby.len());
+    }
+
+    /// Test that member chunk waiters resolve with an error if the standby is
+    /// dropped before the last chunk is received.
+    #[tokio::test]
+    async fn test_wait_for_member_chunks_canceled() {
+        let standby = Standby::new();
+        let wait = standby.wait_for_member_chunks(GuildId(1), "a");
+
+        standby.process(&member_chunk(Some("a"), 0, UserId(1)));
+        drop(standby);
+
+        assert!(wait.await.is_err());
+    }
+
+    /// Test that direct message waiters match messages by their user outside of
+    /// guilds, in any channel, and not guild messages by the same user.
+    #[tokio::test]
+    async fn test_wait_for_dm_message() {
+        fn dm(channel_id: ChannelId, author_id: UserId) -> Event {
+            let mut message = message();
+            message.author.id = author_id;
+            message.channel_id = channel_id;
+            message.guild_id = None;
+            message.member = None;
+
+            Event::MessageCreate(Box::new(MessageCreate(message)))
+        }
+
+        let standby = Standby::new();
+        let mut wait = standby.wait_for_dm_message(UserId(2), |_: &MessageCreate| true);
+        let mut stream = standby.wait_for_dm_message_stream(UserId(2), |_: &MessageCreate| true);
+
+        standby.process(&Event::MessageCreate(Box::new(MessageCreate(message()))));
+        standby.process(&dm(ChannelId(5), UserId(3)));
+        assert!((&mut wait).now_or_never().is_none());
+        assert!(stream.next().now_or_never().is_none());
+
+        standby.process(&dm(ChannelId(5), UserId(2)));
+        standby.process(&dm(ChannelId(6), UserId(2)));
+        assert_eq!(ChannelId(5), wait.await.unwrap().channel_id);
+        assert_eq!(ChannelId(5), stream.next().await.unwrap().channel_id);
+        assert_eq!(ChannelId(6), stream.next().await.unwrap().channel_id);
+
+        assert_eq!(1, standby.len());
+        drop(stream);
+        assert!(standby.0.dm_messages.is_empty());
+    }
+
+    /// Test that the predicates of bystanders waiting for certain types of
+    /// events aren't called with events of other types.
+    #[tokio::test]
+    async fn test_wait_for_event_types() {
+        let standby = Standby::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&calls);
+        let wait = standby.wait_for_event_of(EventType::RoleDelete, move |event: &Event| {
+            counter.fetch_add(1, Ordering::Relaxed);
+
+            matches!(event, Event::RoleDelete(_))
+        });
+        let counter = Arc::clone(&calls);
+        let mut stream = standby.wait_for_event_types_stream(
+            EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::REACTION_ADD,
+            move |_: &Event| {
+                counter.fetch_add(1, Ordering::Relaxed);
+
+                true
+            },
+        );
+
+        standby.process(&Event::MessageCreate(Box::new(MessageCreate(message()))));
+        standby.process(&Event::ReactionAdd(Box::new(ReactionAdd(reaction()))));
+        assert_eq!(2, calls.load(Ordering::Relaxed));
+        assert_eq!(
+            EventType::MessageCreate,
+            stream.next().await.unwrap().kind()
+        );
+        assert_eq!(EventType::ReactionAdd, stream.next().await.unwrap().kind());
+
+        standby.process(&Event::RoleDelete(RoleDelete {
+            guild_id: GuildId(1),
+            role_id: RoleId(2),
+        }));
+        assert_eq!(3, calls.load(Ordering::Relaxed));
+        assert!(wait.await.is_ok());
+        assert!(stream.next().now_or_never().is_none());
+    }
+
+    /// Test that processing events returns the number of bystanders they were
+    /// sent to.
+    #[tokio::test]
+    async fn test_process_results() {
+        let standby = Standby::new();
+        let event = Event::ReactionAdd(Box::new(ReactionAdd(reaction())));
+
+        let reaction = standby.wait_for_reaction(MessageId(4), |_: &ReactionAdd| true);
+        let mut guild = standby.wait_for_stream(GuildId(1), |_: &Event| true);
+        let _message = standby.wait_for_message(ChannelId(2), |_: &MessageCreate| true);
+        let _other = standby.wait_for_reaction(MessageId(4), |_: &ReactionAdd| false);
+
+        let results = standby.process(&event);
+        assert_eq!(1, results.reactions());
+        assert_eq!(1, results.guild_events());
+        assert_eq!(0, results.messages());
+        assert_eq!(2, results.matched());
+        assert!(reaction.await.is_ok());
+        assert!(guild.next().await.is_some());
+
+        let results = standby.process(&Event::RoleDelete(RoleDelete {
+            guild_id: GuildId(2),
+            role_id: RoleId(1),
+        }));
+        assert_eq!(ProcessResults::default(), results);
+        assert_eq!(0, results.matched());
+    }
+
+    /// Test that dropping futures and streams leaves no bystanders registered.
+    #[tokio::test]
+    async fn test_len_after_drop() {
+        const WAITERS: u64 = 10;
+
+        let standby = Standby::new();
+        assert!(standby.is_empty());
+
+        let guilds = (1..=WAITERS)
+            .map(|id| standby.wait_for(GuildId(id), |_: &Event| false))
+            .collect::<Vec<_>>();
+        let events = (1..=WAITERS)
+            .map(|_| standby.wait_for_event(|_: &Event| false))
+            .collect::<Vec<_>>();
+        let messages = (1..=WAITERS)
+            .map(|id| standby.wait_for_message_stream(ChannelId(id), |_: &MessageCreate| false))
+            .collect::<Vec<_>>();
+        let reactions = (1..=WAITERS)
+            .map(|id| standby.wait_for_reaction_stream(MessageId(id), |_: &ReactionAdd| false))
+            .collect::<Vec<_>>();
+        assert_eq!(4 * WAITERS as usize, standby.len());
+
+        drop(guilds);
+        drop(events);
+        drop(messages);
+        drop(reactions);
+        standby.process(&Event::MessageCreate(Box::new(MessageCreate(message()))));
+        assert_eq!(0, standby.len());
+        assert!(standby.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_with_timeout() {
+        let standby = Standby::new();
+        let timeout = Duration::from_millis(10);
+
+        let wait = standby.wait_for_with_timeout(GuildId(1), |_: &Event| false, timeout);
+        assert_eq!(1, standby.0.guilds.len());
+        assert_eq!(Err(WaitForTimeoutError::TimedOut), wait.await.map(|_| ()));
+        assert!(standby.0.guilds.is_empty());
+
+        let wait = standby.wait_for_event_with_timeout(|_: &Event| false, timeout);
+        assert_eq!(Err(WaitForTimeoutError::TimedOut), wait.await);
+        assert!(standby.0.events.is_empty());
+
+        let wait =
+            standby.wait_for_message_with_timeout(ChannelId(1), |_: &MessageCreate| false, timeout);
+        assert_eq!(
+            Err(WaitForTimeoutError::TimedOut),
+            wait.await.map(|message| message.id)
+        );
+        assert!(standby.0.messages.is_empty());
+
+        let wait =
+            standby.wait_for_reaction_with_timeout(MessageId(4), |_: &ReactionAdd| false, timeout);
+        assert_eq!(
+            Err(WaitForTimeoutError::TimedOut),
+            wait.await.map(|reaction| reaction.user_id)
+        );
+        assert!(standby.0.reactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_with_timeout_matched() {
+        let standby = Standby::new();
+        let wait = standby.wait_for_message_with_timeout(
+            ChannelId(1),
+            |message: &MessageCreate| message.author.id == UserId(2),
+            Duration::from_secs(60),
+        );
+        standby.process(&Event::MessageCreate(Box::new(MessageCreate(message()))));
+
+        assert_eq!(Ok(MessageId(3)), wait.await.map(|message| message.id));
+        assert!(standby.0.messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_with_timeout_canceled() {
+        let standby = Standby::new();
+        let wait = standby.wait_for_event_with_timeout(|_: &Event| false, Duration::from_secs(60));
+        drop(standby);
+
+        assert_eq!(Err(WaitForTimeoutError::Canceled), wait.await);
+    }
 }
@@ -0,0 +1,103 @@
+//! Metrics about a [`Standby`], recorded via the `metrics` crate.
+//!
+//! [`Standby`]: crate::Standby
+
+use super::Standby;
+use std::convert::TryInto;
+
+/// Record the number of bystanders of each kind waiting for events.
+pub(super) fn record_bystanders(standby: &Standby) {
+    if ::metrics::try_recorder().is_none() {
+        return;
+    }
+
+    let stats = standby.stats();
+
+    let bystanders = [
+        ("Component", stats.components()),
+        ("DmMessage", stats.dm_messages()),
+        ("Event", stats.events()),
+        ("GuildEvent", stats.guild_events()),
+        ("Interaction", stats.interactions()),
+        ("MemberChunk", stats.member_chunks()),
+        ("Message", stats.messages()),
+        ("Reaction", stats.reactions()),
+    ];
+
+    for (bystander_type, count) in bystanders.iter() {
+        ::metrics::gauge!(
+            "StandbyBystanders",
+            (*count).try_into().unwrap_or(-1),
+            "BystanderType" => *bystander_type,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Standby;
+    use metrics::{Key, Recorder};
+    use std::{cell::RefCell, sync::Once};
+    use twilight_model::{
+        gateway::{event::Event, payload::RoleDelete},
+        id::{GuildId, RoleId},
+    };
+
+    thread_local! {
+        /// Gauges recorded on the current thread, so that tests running in
+        /// parallel don't observe each other's metrics.
+        static GAUGES: RefCell<Vec<(String, i64)>> = RefCell::new(Vec::new());
+    }
+
+    /// Recorder keeping the gauges recorded on each thread.
+    struct DebuggingRecorder;
+
+    impl DebuggingRecorder {
+        /// Install the recorder, and take the gauges recorded on the current
+        /// thread since the last call.
+        fn take() -> Vec<(String, i64)> {
+            static INSTALL: Once = Once::new();
+            static RECORDER: DebuggingRecorder = DebuggingRecorder;
+
+            INSTALL.call_once(|| {
+                metrics::set_recorder(&RECORDER).expect("recorder already set");
+            });
+
+            GAUGES.with(|gauges| gauges.borrow_mut().drain(..).collect())
+        }
+    }
+
+    impl Recorder for DebuggingRecorder {
+        fn increment_counter(&self, _: Key, _: u64) {}
+
+        fn update_gauge(&self, key: Key, value: i64) {
+            let name = key.labels().fold(key.name().into_owned(), |name, label| {
+                format!("{}:{}", name, label.value())
+            });
+
+            GAUGES.with(|gauges| gauges.borrow_mut().push((name, value)));
+        }
+
+        fn record_histogram(&self, _: Key, _: u64) {}
+    }
+
+    #[test]
+    fn test_bystanders() {
+        let standby = Standby::new();
+        DebuggingRecorder::take();
+
+        let _guild = standby.wait_for(GuildId(1), |_: &Event| false);
+        let _event = standby.wait_for_event(|_: &Event| false);
+        let _other_event = standby.wait_for_event(|_: &Event| false);
+        standby.process(&Event::RoleDelete(RoleDelete {
+            guild_id: GuildId(1),
+            role_id: RoleId(2),
+        }));
+
+        let gauges = DebuggingRecorder::take();
+        assert_eq!(8, gauges.len());
+        assert!(gauges.contains(&("StandbyBystanders:Event".to_owned(), 2)));
+        assert!(gauges.contains(&("StandbyBystanders:GuildEvent".to_owned(), 1)));
+        assert!(gauges.contains(&("StandbyBystanders:Message".to_owned(), 0)));
+    }
+}
@@ -0,0 +1,186 @@
+//! Statistics about the bystanders waiting for events in a [`Standby`].
+//!
+//! Statistics are read from counters updated as bystanders are registered and
+//! removed, so retrieving them doesn't walk the standby's maps and is cheap
+//! enough to be done frequently, such as for exporting metrics.
+//!
+//! [`Standby`]: crate::Standby
+
+use super::{Standby, StandbyRef};
+use std::sync::atomic::Ordering;
+
+/// Interface to retrieve statistics about the bystanders of a [`Standby`].
+///
+/// Bystanders are removed once they're fulfilled or their futures or streams
+/// are dropped, so counts that keep growing indicate that futures are leaked or
+/// that events aren't being processed.
+///
+/// Created via [`Standby::stats`].
+///
+/// # Examples
+///
+/// Retrieve the number of bystanders waiting for messages and reactions:
+///
+/// ```
+/// use twilight_standby::Standby;
+///
+/// let standby = Standby::new();
+/// let stats = standby.stats();
+///
+/// println!("messages: {}", stats.messages());
+/// println!("reactions: {}", stats.reactions());
+/// ```
+///
+/// [`Standby`]: crate::Standby
+/// [`Standby::stats`]: crate::Standby::stats
+#[derive(Clone, Copy, Debug)]
+pub struct StandbyStats<'a>(&'a StandbyRef);
+
+impl<'a> StandbyStats<'a> {
+    pub(super) fn new(standby: &'a Standby) -> Self {
+        Self(&standby.0)
+    }
+
+    /// Number of bystanders waiting for component interactions on a message.
+    pub fn components(&self) -> usize {
+        self.0.counts.components.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for direct messages.
+    pub fn dm_messages(&self) -> usize {
+        self.0.counts.dm_messages.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for events of any guild.
+    pub fn events(&self) -> usize {
+        self.0.counts.events.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for events in a guild.
+    pub fn guild_events(&self) -> usize {
+        self.0.counts.guilds.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for interactions in a guild.
+    pub fn interactions(&self) -> usize {
+        self.0.counts.interactions.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for member chunks.
+    pub fn member_chunks(&self) -> usize {
+        self.0.counts.member_chunks.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for messages in a channel.
+    pub fn messages(&self) -> usize {
+        self.0.counts.messages.load(Ordering::Relaxed)
+    }
+
+    /// Number of bystanders waiting for reactions on a message.
+    pub fn reactions(&self) -> usize {
+        self.0.counts.reactions.load(Ordering::Relaxed)
+    }
+
+    /// Total number of bystanders.
+    pub fn total(&self) -> usize {
+        self.components()
+            + self.dm_messages()
+            + self.events()
+            + self.guild_events()
+            + self.interactions()
+            + self.member_chunks()
+            + self.messages()
+            + self.reactions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StandbyStats;
+    use crate::Standby;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, time::Duration};
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::{InteractionCreate, MemberChunk, MessageCreate, ReactionAdd, RoleDelete},
+        },
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    };
+
+    assert_impl_all!(StandbyStats<'_>: Clone, Copy, Debug, Send, Sync);
+
+    fn role_delete(guild_id: GuildId) -> Event {
+        Event::RoleDelete(RoleDelete {
+            guild_id,
+            role_id: RoleId(1),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_counts() {
+        let standby = Standby::new();
+        let guild = standby.wait_for(GuildId(1), |_: &Event| true);
+        let guild_stream = standby.wait_for_stream(GuildId(1), |_: &Event| true);
+        let event = standby.wait_for_event(|event: &Event| matches!(event, Event::RoleDelete(_)));
+        let dm = standby.wait_for_dm_message(UserId(1), |_: &MessageCreate| true);
+        let chunks = standby.wait_for_member_chunks(GuildId(1), "a");
+        let message = standby.wait_for_message(ChannelId(1), |_: &MessageCreate| true);
+        let reaction = standby.wait_for_reaction(MessageId(1), |_: &ReactionAdd| true);
+        let component = standby.wait_for_component(MessageId(1), |_: &InteractionCreate| true);
+        let interaction = standby.wait_for_interaction(GuildId(1), |_: &InteractionCreate| true);
+
+        let stats = standby.stats();
+        assert_eq!(1, stats.components());
+        assert_eq!(1, stats.dm_messages());
+        assert_eq!(1, stats.events());
+        assert_eq!(2, stats.guild_events());
+        assert_eq!(1, stats.interactions());
+        assert_eq!(1, stats.member_chunks());
+        assert_eq!(1, stats.messages());
+        assert_eq!(1, stats.reactions());
+        assert_eq!(9, stats.total());
+        assert_eq!(9, standby.len());
+
+        // The guild future and event future complete, while the guild stream
+        // keeps waiting.
+        standby.process(&role_delete(GuildId(1)));
+        assert!(guild.await.is_ok());
+        assert!(event.await.is_ok());
+        assert_eq!(0, stats.events());
+        assert_eq!(1, stats.guild_events());
+
+        standby.process(&Event::MemberChunk(MemberChunk {
+            chunk_count: 1,
+            chunk_index: 0,
+            guild_id: GuildId(1),
+            members: Vec::new(),
+            nonce: Some("a".to_owned()),
+            not_found: Vec::new(),
+            presences: Vec::new(),
+        }));
+        assert!(chunks.await.is_ok());
+        assert_eq!(0, stats.member_chunks());
+
+        drop(guild_stream);
+        drop(dm);
+        drop(message);
+        drop(reaction);
+        drop(component);
+        drop(interaction);
+        assert_eq!(0, stats.total());
+    }
+
+    #[tokio::test]
+    async fn test_counts_timeout() {
+        let standby = Standby::new();
+        let timeout = Duration::from_millis(10);
+        let wait =
+            standby.wait_for_message_with_timeout(ChannelId(1), |_: &MessageCreate| true, timeout);
+        assert_eq!(1, standby.stats().messages());
+
+        assert!(wait.await.is_err());
+        assert_eq!(0, standby.stats().messages());
+        assert!(standby.is_empty());
+    }
+}
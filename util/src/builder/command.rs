@@ -0,0 +1,973 @@
+//! Create application commands.
+//!
+//! A [`CommandBuilder`] is given options created by the builders of each
+//! option type, such as a [`UserBuilder`] for a user argument or a
+//! [`SubCommandBuilder`] for a sub-command, and validates the whole command
+//! once it's built.
+//!
+//! # Examples
+//!
+//! Build a `/mod` command with `ban` and `mute` sub-commands:
+//!
+//! ```
+//! use twilight_util::builder::command::{
+//!     CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder, UserBuilder,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let command = CommandBuilder::new("mod", "moderate members")
+//!     .option(
+//!         SubCommandBuilder::new("ban", "ban a member")
+//!             .option(UserBuilder::new("member", "member to ban").required(true))
+//!             .option(StringBuilder::new("reason", "reason for the ban")),
+//!     )
+//!     .option(
+//!         SubCommandBuilder::new("mute", "mute a member")
+//!             .option(UserBuilder::new("member", "member to mute").required(true))
+//!             .option(
+//!                 IntegerBuilder::new("duration", "duration of the mute")
+//!                     .choice("one hour", 3600)
+//!                     .choice("one day", 86_400),
+//!             ),
+//!     )
+//!     .build()?;
+//!
+//! assert_eq!(2, command.options.len());
+//! # Ok(()) }
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    application::command::{Command, CommandOption, CommandOptionChoice, CommandOptionType},
+    id::GuildId,
+};
+
+/// Error building a command.
+///
+/// This is returned from [`CommandBuilder::build`].
+#[derive(Debug)]
+pub struct CommandBuildError {
+    kind: CommandBuildErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl CommandBuildError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use]
+    pub const fn kind(&self) -> &CommandBuildErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use]
+    pub fn into_parts(self) -> (CommandBuildErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for CommandBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            CommandBuildErrorType::ChoiceNameInvalid { name } => f.write_fmt(format_args!(
+                "the choice name `{}` isn't between 1 and {} characters long",
+                name,
+                CommandBuilder::CHOICE_NAME_LENGTH_LIMIT,
+            )),
+            CommandBuildErrorType::DescriptionInvalid { description } => f.write_fmt(format_args!(
+                "the description `{}` isn't between 1 and {} characters long",
+                description,
+                CommandBuilder::DESCRIPTION_LENGTH_LIMIT,
+            )),
+            CommandBuildErrorType::NameInvalid { name } => f.write_fmt(format_args!(
+                "the name `{}` isn't between 1 and {} lowercase letters, digits, dashes or \
+                 underscores",
+                name,
+                CommandBuilder::NAME_LENGTH_LIMIT,
+            )),
+            CommandBuildErrorType::OptionNestingInvalid { name } => f.write_fmt(format_args!(
+                "the option `{}` isn't allowed where it was added",
+                name,
+            )),
+            CommandBuildErrorType::OptionRequiredAfterOptional { name } => f.write_fmt(
+                format_args!("the required option `{}` follows an optional option", name),
+            ),
+            CommandBuildErrorType::TooManyChoices { name, count } => f.write_fmt(format_args!(
+                "the option `{}` has {} choices, but the limit is {}",
+                name,
+                count,
+                CommandBuilder::CHOICE_LIMIT,
+            )),
+            CommandBuildErrorType::TooManyOptions { count } => f.write_fmt(format_args!(
+                "{} options were provided, but the limit is {}",
+                count,
+                CommandBuilder::OPTION_LIMIT,
+            )),
+        }
+    }
+}
+
+impl Error for CommandBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`CommandBuildError`] that occurred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CommandBuildErrorType {
+    /// Name of a choice is empty or longer than
+    /// [`CommandBuilder::CHOICE_NAME_LENGTH_LIMIT`] characters.
+    ChoiceNameInvalid {
+        /// Provided name.
+        name: String,
+    },
+    /// Description of the command or an option is empty or longer than
+    /// [`CommandBuilder::DESCRIPTION_LENGTH_LIMIT`] characters.
+    DescriptionInvalid {
+        /// Provided description.
+        description: String,
+    },
+    /// Name of the command or an option is empty, longer than
+    /// [`CommandBuilder::NAME_LENGTH_LIMIT`] characters, or contains
+    /// characters other than lowercase letters, digits, dashes and
+    /// underscores.
+    NameInvalid {
+        /// Provided name.
+        name: String,
+    },
+    /// Option isn't allowed where it was added.
+    ///
+    /// Sub-command groups may only contain sub-commands, sub-commands may
+    /// only contain arguments, and a command may contain either sub-commands
+    /// and groups or arguments but not both.
+    OptionNestingInvalid {
+        /// Name of the option.
+        name: String,
+    },
+    /// Required argument follows an optional one.
+    OptionRequiredAfterOptional {
+        /// Name of the required argument.
+        name: String,
+    },
+    /// More than [`CommandBuilder::CHOICE_LIMIT`] choices were added to an
+    /// option.
+    TooManyChoices {
+        /// Name of the option.
+        name: String,
+        /// Number of provided choices.
+        count: usize,
+    },
+    /// More than [`CommandBuilder::OPTION_LIMIT`] options were added to the
+    /// command, a sub-command or a sub-command group.
+    TooManyOptions {
+        /// Number of provided options.
+        count: usize,
+    },
+}
+
+/// Create a command with a builder.
+///
+/// # Examples
+///
+/// Refer to the [module-level documentation] for examples.
+///
+/// [module-level documentation]: self
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be built into a command"]
+pub struct CommandBuilder(Command);
+
+impl CommandBuilder {
+    /// The maximum number of choices of an option.
+    pub const CHOICE_LIMIT: usize = 25;
+
+    /// The maximum number of characters of the name of a choice.
+    pub const CHOICE_NAME_LENGTH_LIMIT: usize = 100;
+
+    /// The maximum number of characters of the description of the command or
+    /// an option.
+    pub const DESCRIPTION_LENGTH_LIMIT: usize = 100;
+
+    /// The maximum number of characters of the name of the command or an
+    /// option.
+    pub const NAME_LENGTH_LIMIT: usize = 32;
+
+    /// The maximum number of options of the command, a sub-command or a
+    /// sub-command group.
+    pub const OPTION_LIMIT: usize = 25;
+
+    /// Create a new builder of a command with a name and description.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self(Command {
+            application_id: None,
+            default_permission: None,
+            description: description.into(),
+            guild_id: None,
+            id: None,
+            name: name.into(),
+            options: Vec::new(),
+        })
+    }
+
+    /// Build the command, validating it and its options.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CommandBuildErrorType::NameInvalid`] or
+    /// [`CommandBuildErrorType::DescriptionInvalid`] error type if the name or
+    /// description of the command or an option is invalid.
+    ///
+    /// Returns a [`CommandBuildErrorType::TooManyOptions`] error type if the
+    /// command, a sub-command or a sub-command group has more than
+    /// [`OPTION_LIMIT`] options.
+    ///
+    /// Returns a [`CommandBuildErrorType::OptionNestingInvalid`] error type if
+    /// an option isn't allowed where it was added, such as a sub-command
+    /// within a sub-command.
+    ///
+    /// Returns a [`CommandBuildErrorType::OptionRequiredAfterOptional`] error
+    /// type if a required argument follows an optional one.
+    ///
+    /// Returns a [`CommandBuildErrorType::TooManyChoices`] or
+    /// [`CommandBuildErrorType::ChoiceNameInvalid`] error type if an option
+    /// has more than [`CHOICE_LIMIT`] choices or a choice's name is invalid.
+    ///
+    /// [`CHOICE_LIMIT`]: Self::CHOICE_LIMIT
+    /// [`OPTION_LIMIT`]: Self::OPTION_LIMIT
+    pub fn build(self) -> Result<Command, CommandBuildError> {
+        validate_name(&self.0.name)?;
+        validate_description(&self.0.description)?;
+
+        let parent = if self
+            .0
+            .options
+            .first()
+            .map_or(false, |option| option.kind.is_sub_command())
+        {
+            Parent::SubCommandsAndGroups
+        } else {
+            Parent::Arguments
+        };
+        validate_options(&self.0.options, parent)?;
+
+        Ok(self.0)
+    }
+
+    /// Set whether the command is enabled for everyone when it's added to a
+    /// guild.
+    ///
+    /// Defaults to Discord's default of `true`.
+    pub fn default_permission(mut self, default_permission: bool) -> Self {
+        self.0.default_permission.replace(default_permission);
+
+        self
+    }
+
+    /// Set the guild the command is registered in, instead of it being
+    /// global.
+    pub fn guild_id(mut self, guild_id: GuildId) -> Self {
+        self.0.guild_id.replace(guild_id);
+
+        self
+    }
+
+    /// Add an option, such as an argument or a sub-command.
+    pub fn option(mut self, option: impl Into<CommandOption>) -> Self {
+        self.0.options.push(option.into());
+
+        self
+    }
+}
+
+/// Options that may be added to a command, sub-command or group.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Parent {
+    /// Arguments, such as of a sub-command.
+    Arguments,
+    /// Sub-commands, such as of a sub-command group.
+    SubCommands,
+    /// Sub-commands and groups, such as of a command.
+    SubCommandsAndGroups,
+}
+
+impl Parent {
+    fn allows(self, kind: CommandOptionType) -> bool {
+        match self {
+            Self::Arguments => !kind.is_sub_command(),
+            Self::SubCommands => kind == CommandOptionType::SubCommand,
+            Self::SubCommandsAndGroups => kind.is_sub_command(),
+        }
+    }
+}
+
+fn validate_options(options: &[CommandOption], parent: Parent) -> Result<(), CommandBuildError> {
+    if options.len() > CommandBuilder::OPTION_LIMIT {
+        return Err(CommandBuildError {
+            kind: CommandBuildErrorType::TooManyOptions {
+                count: options.len(),
+            },
+            source: None,
+        });
+    }
+
+    let mut optional = false;
+
+    for option in options {
+        validate_name(&option.name)?;
+        validate_description(&option.description)?;
+
+        if !parent.allows(option.kind) {
+            return Err(CommandBuildError {
+                kind: CommandBuildErrorType::OptionNestingInvalid {
+                    name: option.name.clone(),
+                },
+                source: None,
+            });
+        }
+
+        if option.required && optional {
+            return Err(CommandBuildError {
+                kind: CommandBuildErrorType::OptionRequiredAfterOptional {
+                    name: option.name.clone(),
+                },
+                source: None,
+            });
+        }
+
+        optional |= !option.required && !option.kind.is_sub_command();
+
+        if option.choices.len() > CommandBuilder::CHOICE_LIMIT {
+            return Err(CommandBuildError {
+                kind: CommandBuildErrorType::TooManyChoices {
+                    name: option.name.clone(),
+                    count: option.choices.len(),
+                },
+                source: None,
+            });
+        }
+
+        for choice in &option.choices {
+            let name = match choice {
+                CommandOptionChoice::Int { name, .. }
+                | CommandOptionChoice::String { name, .. } => name,
+            };
+            let len = name.chars().count();
+
+            if len == 0 || len > CommandBuilder::CHOICE_NAME_LENGTH_LIMIT {
+                return Err(CommandBuildError {
+                    kind: CommandBuildErrorType::ChoiceNameInvalid { name: name.clone() },
+                    source: None,
+                });
+            }
+        }
+
+        match option.kind {
+            CommandOptionType::SubCommand => {
+                validate_options(&option.options, Parent::Arguments)?;
+            }
+            CommandOptionType::SubCommandGroup => {
+                validate_options(&option.options, Parent::SubCommands)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), CommandBuildError> {
+    let len = name.chars().count();
+    let valid = name
+        .chars()
+        .all(|c| c == '-' || c == '_' || (c.is_alphanumeric() && !c.is_uppercase()));
+
+    if len == 0 || len > CommandBuilder::NAME_LENGTH_LIMIT || !valid {
+        return Err(CommandBuildError {
+            kind: CommandBuildErrorType::NameInvalid {
+                name: name.to_owned(),
+            },
+            source: None,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_description(description: &str) -> Result<(), CommandBuildError> {
+    let len = description.chars().count();
+
+    if len == 0 || len > CommandBuilder::DESCRIPTION_LENGTH_LIMIT {
+        return Err(CommandBuildError {
+            kind: CommandBuildErrorType::DescriptionInvalid {
+                description: description.to_owned(),
+            },
+            source: None,
+        });
+    }
+
+    Ok(())
+}
+
+/// Create an option of a type with a name and description.
+fn option(kind: CommandOptionType, name: String, description: String) -> CommandOption {
+    CommandOption {
+        choices: Vec::new(),
+        description,
+        kind,
+        name,
+        options: Vec::new(),
+        required: false,
+    }
+}
+
+/// Define a builder of an argument of an option type.
+macro_rules! argument_builder {
+    ($(#[$meta:meta])* $builder:ident, $kind:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        #[must_use = "must be added to a command or sub-command"]
+        pub struct $builder(CommandOption);
+
+        impl $builder {
+            /// Create a new builder of an optional argument with a name and
+            /// description.
+            pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+                Self(option(CommandOptionType::$kind, name.into(), description.into()))
+            }
+
+            /// Build the argument into an option.
+            ///
+            /// The option is validated once the command it's added to is
+            /// built.
+            #[must_use = "must be added to a command or sub-command"]
+            pub fn build(self) -> CommandOption {
+                self.0
+            }
+
+            /// Set whether the argument must be provided.
+            ///
+            /// Required arguments must precede optional ones.
+            pub fn required(mut self, required: bool) -> Self {
+                self.0.required = required;
+
+                self
+            }
+        }
+
+        impl From<$builder> for CommandOption {
+            fn from(builder: $builder) -> Self {
+                builder.build()
+            }
+        }
+    };
+}
+
+argument_builder!(
+    /// Create a boolean argument with a builder.
+    BooleanBuilder,
+    Boolean
+);
+argument_builder!(
+    /// Create a channel argument with a builder.
+    ChannelBuilder,
+    Channel
+);
+argument_builder!(
+    /// Create an integer argument with a builder.
+    IntegerBuilder,
+    Integer
+);
+argument_builder!(
+    /// Create an argument of a user or role with a builder.
+    MentionableBuilder,
+    Mentionable
+);
+argument_builder!(
+    /// Create a role argument with a builder.
+    RoleBuilder,
+    Role
+);
+argument_builder!(
+    /// Create a string argument with a builder.
+    StringBuilder,
+    String
+);
+argument_builder!(
+    /// Create a user argument with a builder.
+    UserBuilder,
+    User
+);
+
+impl IntegerBuilder {
+    /// Add a choice that users pick from.
+    ///
+    /// Users may only provide one of the choices once any are added.
+    pub fn choice(mut self, name: impl Into<String>, value: i64) -> Self {
+        self.0.choices.push(CommandOptionChoice::Int {
+            name: name.into(),
+            value,
+        });
+
+        self
+    }
+
+    /// Add choices that users pick from, as pairs of names and values.
+    pub fn choices<N: Into<String>>(self, choices: impl IntoIterator<Item = (N, i64)>) -> Self {
+        choices
+            .into_iter()
+            .fold(self, |builder, (name, value)| builder.choice(name, value))
+    }
+}
+
+impl StringBuilder {
+    /// Add a choice that users pick from.
+    ///
+    /// Users may only provide one of the choices once any are added.
+    pub fn choice(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.choices.push(CommandOptionChoice::String {
+            name: name.into(),
+            value: value.into(),
+        });
+
+        self
+    }
+
+    /// Add choices that users pick from, as pairs of names and values.
+    pub fn choices<N: Into<String>, V: Into<String>>(
+        self,
+        choices: impl IntoIterator<Item = (N, V)>,
+    ) -> Self {
+        choices
+            .into_iter()
+            .fold(self, |builder, (name, value)| builder.choice(name, value))
+    }
+}
+
+/// Create a sub-command with a builder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be added to a command or sub-command group"]
+pub struct SubCommandBuilder(CommandOption);
+
+impl SubCommandBuilder {
+    /// Create a new builder of a sub-command with a name and description.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self(option(
+            CommandOptionType::SubCommand,
+            name.into(),
+            description.into(),
+        ))
+    }
+
+    /// Build the sub-command into an option.
+    ///
+    /// The option is validated once the command it's added to is built.
+    #[must_use = "must be added to a command or sub-command group"]
+    pub fn build(self) -> CommandOption {
+        self.0
+    }
+
+    /// Add an argument.
+    pub fn option(mut self, option: impl Into<CommandOption>) -> Self {
+        self.0.options.push(option.into());
+
+        self
+    }
+}
+
+impl From<SubCommandBuilder> for CommandOption {
+    fn from(builder: SubCommandBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Create a group of sub-commands with a builder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be added to a command"]
+pub struct SubCommandGroupBuilder(CommandOption);
+
+impl SubCommandGroupBuilder {
+    /// Create a new builder of a sub-command group with a name and
+    /// description.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self(option(
+            CommandOptionType::SubCommandGroup,
+            name.into(),
+            description.into(),
+        ))
+    }
+
+    /// Build the group into an option.
+    ///
+    /// The option is validated once the command it's added to is built.
+    #[must_use = "must be added to a command"]
+    pub fn build(self) -> CommandOption {
+        self.0
+    }
+
+    /// Add a sub-command.
+    pub fn sub_command(mut self, sub_command: SubCommandBuilder) -> Self {
+        self.0.options.push(sub_command.build());
+
+        self
+    }
+}
+
+impl From<SubCommandGroupBuilder> for CommandOption {
+    fn from(builder: SubCommandGroupBuilder) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BooleanBuilder, ChannelBuilder, CommandBuildError, CommandBuildErrorType, CommandBuilder,
+        IntegerBuilder, MentionableBuilder, RoleBuilder, StringBuilder, SubCommandBuilder,
+        SubCommandGroupBuilder, UserBuilder,
+    };
+    use serde_json::json;
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::{
+        application::command::{CommandOption, CommandOptionType},
+        id::GuildId,
+    };
+
+    assert_impl_all!(CommandBuildError: Debug, Error, Send, Sync);
+    assert_impl_all!(CommandBuildErrorType: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(CommandBuilder: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(StringBuilder: Clone, Debug, Into<CommandOption>, Send, Sync);
+    assert_impl_all!(SubCommandBuilder: Clone, Debug, Into<CommandOption>, Send, Sync);
+    assert_impl_all!(SubCommandGroupBuilder: Clone, Debug, Into<CommandOption>, Send, Sync);
+
+    #[test]
+    fn test_name_invalid() {
+        for name in &["", "Ban", "ban user", "ban!", &"a".repeat(33)] {
+            assert_eq!(
+                &CommandBuildErrorType::NameInvalid {
+                    name: (*name).to_owned()
+                },
+                CommandBuilder::new(*name, "ban a user")
+                    .build()
+                    .unwrap_err()
+                    .kind()
+            );
+        }
+
+        assert_eq!(
+            &CommandBuildErrorType::NameInvalid {
+                name: "User".to_owned()
+            },
+            CommandBuilder::new("ban", "ban a user")
+                .option(UserBuilder::new("User", "user to ban"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+
+        assert!(CommandBuilder::new("ban-user_2", "ban a user")
+            .build()
+            .is_ok());
+        assert!(CommandBuilder::new("a".repeat(32), "ban a user")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_description_invalid() {
+        assert_eq!(
+            &CommandBuildErrorType::DescriptionInvalid {
+                description: String::new()
+            },
+            CommandBuilder::new("ban", "").build().unwrap_err().kind()
+        );
+        assert_eq!(
+            &CommandBuildErrorType::DescriptionInvalid {
+                description: "a".repeat(101)
+            },
+            CommandBuilder::new("ban", "ban a user")
+                .option(UserBuilder::new("user", "a".repeat(101)))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert!(CommandBuilder::new("ban", "a".repeat(100)).build().is_ok());
+    }
+
+    #[test]
+    fn test_too_many_options() {
+        let command = (0..26).fold(CommandBuilder::new("ban", "ban users"), |builder, idx| {
+            builder.option(UserBuilder::new(format!("user{}", idx), "user to ban"))
+        });
+
+        assert_eq!(
+            &CommandBuildErrorType::TooManyOptions { count: 26 },
+            command.build().unwrap_err().kind()
+        );
+
+        let sub_command = (0..26).fold(
+            SubCommandBuilder::new("users", "ban users"),
+            |builder, idx| builder.option(UserBuilder::new(format!("user{}", idx), "user to ban")),
+        );
+
+        assert_eq!(
+            &CommandBuildErrorType::TooManyOptions { count: 26 },
+            CommandBuilder::new("ban", "ban users")
+                .option(sub_command)
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_option_required_after_optional() {
+        assert_eq!(
+            &CommandBuildErrorType::OptionRequiredAfterOptional {
+                name: "user".to_owned()
+            },
+            CommandBuilder::new("ban", "ban a user")
+                .option(StringBuilder::new("reason", "reason for the ban"))
+                .option(UserBuilder::new("user", "user to ban").required(true))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+
+        // Sub-commands aren't arguments, so they're never optional.
+        assert!(CommandBuilder::new("mod", "moderate")
+            .option(SubCommandBuilder::new("ban", "ban a user"))
+            .option(
+                SubCommandBuilder::new("kick", "kick a user")
+                    .option(UserBuilder::new("user", "user to kick").required(true))
+            )
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_option_nesting_invalid() {
+        // Sub-commands can't be mixed with arguments.
+        assert_eq!(
+            &CommandBuildErrorType::OptionNestingInvalid {
+                name: "user".to_owned()
+            },
+            CommandBuilder::new("mod", "moderate")
+                .option(SubCommandBuilder::new("ban", "ban a user"))
+                .option(UserBuilder::new("user", "user to moderate"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert_eq!(
+            &CommandBuildErrorType::OptionNestingInvalid {
+                name: "ban".to_owned()
+            },
+            CommandBuilder::new("mod", "moderate")
+                .option(UserBuilder::new("user", "user to moderate"))
+                .option(SubCommandBuilder::new("ban", "ban a user"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+
+        // Sub-commands can't contain sub-commands or groups.
+        assert_eq!(
+            &CommandBuildErrorType::OptionNestingInvalid {
+                name: "user".to_owned()
+            },
+            CommandBuilder::new("mod", "moderate")
+                .option(
+                    SubCommandBuilder::new("ban", "ban a user")
+                        .option(SubCommandBuilder::new("user", "ban a user"))
+                )
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert_eq!(
+            &CommandBuildErrorType::OptionNestingInvalid {
+                name: "users".to_owned()
+            },
+            CommandBuilder::new("mod", "moderate")
+                .option(
+                    SubCommandBuilder::new("ban", "ban a user")
+                        .option(SubCommandGroupBuilder::new("users", "ban users"))
+                )
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_choices() {
+        let choices = (0..26).map(|idx| (format!("{} days", idx), idx * 86_400));
+
+        assert_eq!(
+            &CommandBuildErrorType::TooManyChoices {
+                name: "duration".to_owned(),
+                count: 26
+            },
+            CommandBuilder::new("mute", "mute a user")
+                .option(IntegerBuilder::new("duration", "duration of the mute").choices(choices))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+
+        assert_eq!(
+            &CommandBuildErrorType::ChoiceNameInvalid {
+                name: String::new()
+            },
+            CommandBuilder::new("ban", "ban a user")
+                .option(StringBuilder::new("reason", "reason for the ban").choice("", "spam"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_argument_kinds() {
+        let options = [
+            BooleanBuilder::new("a", "a").build(),
+            ChannelBuilder::new("a", "a").build(),
+            IntegerBuilder::new("a", "a").build(),
+            MentionableBuilder::new("a", "a").build(),
+            RoleBuilder::new("a", "a").build(),
+            StringBuilder::new("a", "a").build(),
+            UserBuilder::new("a", "a").build(),
+        ];
+
+        assert_eq!(
+            vec![
+                CommandOptionType::Boolean,
+                CommandOptionType::Channel,
+                CommandOptionType::Integer,
+                CommandOptionType::Mentionable,
+                CommandOptionType::Role,
+                CommandOptionType::String,
+                CommandOptionType::User,
+            ],
+            options.iter().map(|option| option.kind).collect::<Vec<_>>()
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn test_complex_command() -> Result<(), Box<dyn Error>> {
+        let command = CommandBuilder::new("mod", "moderate members")
+            .default_permission(false)
+            .guild_id(GuildId(1))
+            .option(
+                SubCommandBuilder::new("ban", "ban a member")
+                    .option(UserBuilder::new("member", "member to ban").required(true))
+                    .option(
+                        StringBuilder::new("reason", "reason for the ban")
+                            .choices(vec![("Spam", "spam"), ("Raid", "raid")]),
+                    ),
+            )
+            .option(
+                SubCommandGroupBuilder::new("role", "manage the roles of members").sub_command(
+                    SubCommandBuilder::new("add", "add a role to a member")
+                        .option(UserBuilder::new("member", "member to add to").required(true))
+                        .option(RoleBuilder::new("role", "role to add").required(true))
+                        .option(IntegerBuilder::new("days", "days to add it for").choice("one", 1))
+                        .option(BooleanBuilder::new("silent", "whether to skip the log")),
+                ),
+            )
+            .build()?;
+
+        assert_eq!(
+            json!({
+                "default_permission": false,
+                "description": "moderate members",
+                "guild_id": "1",
+                "name": "mod",
+                "options": [
+                    {
+                        "description": "ban a member",
+                        "name": "ban",
+                        "options": [
+                            {
+                                "description": "member to ban",
+                                "name": "member",
+                                "required": true,
+                                "type": 6,
+                            },
+                            {
+                                "choices": [
+                                    { "name": "Spam", "value": "spam" },
+                                    { "name": "Raid", "value": "raid" },
+                                ],
+                                "description": "reason for the ban",
+                                "name": "reason",
+                                "required": false,
+                                "type": 3,
+                            },
+                        ],
+                        "required": false,
+                        "type": 1,
+                    },
+                    {
+                        "description": "manage the roles of members",
+                        "name": "role",
+                        "options": [
+                            {
+                                "description": "add a role to a member",
+                                "name": "add",
+                                "options": [
+                                    {
+                                        "description": "member to add to",
+                                        "name": "member",
+                                        "required": true,
+                                        "type": 6,
+                                    },
+                                    {
+                                        "description": "role to add",
+                                        "name": "role",
+                                        "required": true,
+                                        "type": 8,
+                                    },
+                                    {
+                                        "choices": [{ "name": "one", "value": 1 }],
+                                        "description": "days to add it for",
+                                        "name": "days",
+                                        "required": false,
+                                        "type": 4,
+                                    },
+                                    {
+                                        "description": "whether to skip the log",
+                                        "name": "silent",
+                                        "required": false,
+                                        "type": 5,
+                                    },
+                                ],
+                                "required": false,
+                                "type": 1,
+                            },
+                        ],
+                        "required": false,
+                        "type": 2,
+                    },
+                ],
+            }),
+            serde_json::to_value(&command)?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,793 @@
+//! Create message components.
+//!
+//! A [`ComponentsBuilder`] is given rows created by an [`ActionRowBuilder`],
+//! which are in turn given buttons created by a [`ButtonBuilder`] or a select
+//! menu created by a [`SelectMenuBuilder`], and validates the whole layout
+//! once it's built.
+//!
+//! # Examples
+//!
+//! Build a row of buttons to approve or deny an appeal, and a row with a link
+//! to the appeal:
+//!
+//! ```
+//! use twilight_model::application::component::ButtonStyle;
+//! use twilight_util::builder::component::{ActionRowBuilder, ButtonBuilder, ComponentsBuilder};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let components = ComponentsBuilder::new()
+//!     .row(
+//!         ActionRowBuilder::new()
+//!             .button(
+//!                 ButtonBuilder::new(ButtonStyle::Success)
+//!                     .custom_id("appeal-approve")
+//!                     .label("Approve"),
+//!             )
+//!             .button(
+//!                 ButtonBuilder::new(ButtonStyle::Danger)
+//!                     .custom_id("appeal-deny")
+//!                     .label("Deny"),
+//!             ),
+//!     )
+//!     .row(
+//!         ActionRowBuilder::new().button(
+//!             ButtonBuilder::new(ButtonStyle::Link)
+//!                 .label("View appeal")
+//!                 .url("https://example.com/appeals/1"),
+//!         ),
+//!     )
+//!     .build()?;
+//!
+//! assert_eq!(2, components.len());
+//! # Ok(()) }
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    application::component::{
+        ActionRow, Button, ButtonStyle, Component, SelectMenu, SelectMenuOption,
+    },
+    channel::ReactionType,
+};
+
+/// Error building message components.
+///
+/// This is returned from [`ComponentsBuilder::build`] and
+/// [`ActionRowBuilder::build`].
+#[derive(Debug)]
+pub struct ComponentBuildError {
+    kind: ComponentBuildErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ComponentBuildError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use]
+    pub const fn kind(&self) -> &ComponentBuildErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use]
+    pub fn into_parts(
+        self,
+    ) -> (
+        ComponentBuildErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for ComponentBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ComponentBuildErrorType::ButtonInvalid => {
+                f.write_str("buttons other than link buttons must have a custom ID and no URL")
+            }
+            ComponentBuildErrorType::CustomIdInvalid { custom_id } => f.write_fmt(format_args!(
+                "the custom ID `{}` isn't between 1 and {} characters long",
+                custom_id,
+                ComponentsBuilder::CUSTOM_ID_LENGTH_LIMIT,
+            )),
+            ComponentBuildErrorType::LinkButtonInvalid => {
+                f.write_str("link buttons must have a URL and no custom ID")
+            }
+            ComponentBuildErrorType::RowEmpty => f.write_str("the action row has no components"),
+            ComponentBuildErrorType::SelectMenuNotAlone => {
+                f.write_str("a select menu must be the only component in its action row")
+            }
+            ComponentBuildErrorType::SelectMenuOptionCountInvalid { count } => {
+                f.write_fmt(format_args!(
+                    "the select menu has {} options, but must have between 1 and {}",
+                    count,
+                    ComponentsBuilder::SELECT_MENU_OPTION_LIMIT,
+                ))
+            }
+            ComponentBuildErrorType::TooManyButtons { count } => f.write_fmt(format_args!(
+                "the action row has {} buttons, but the limit is {}",
+                count,
+                ComponentsBuilder::BUTTON_LIMIT,
+            )),
+            ComponentBuildErrorType::TooManyRows { count } => f.write_fmt(format_args!(
+                "{} action rows were provided, but the limit is {}",
+                count,
+                ComponentsBuilder::ROW_LIMIT,
+            )),
+        }
+    }
+}
+
+impl Error for ComponentBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ComponentBuildError`] that occurred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ComponentBuildErrorType {
+    /// Non-link button doesn't have a custom ID or has a URL.
+    ButtonInvalid,
+    /// Custom ID of a button or select menu is empty or longer than
+    /// [`ComponentsBuilder::CUSTOM_ID_LENGTH_LIMIT`] characters.
+    CustomIdInvalid {
+        /// Provided custom ID.
+        custom_id: String,
+    },
+    /// Link button doesn't have a URL or has a custom ID.
+    LinkButtonInvalid,
+    /// Action row doesn't contain any components.
+    RowEmpty,
+    /// Select menu shares its action row with other components.
+    SelectMenuNotAlone,
+    /// Select menu has no options or more than
+    /// [`ComponentsBuilder::SELECT_MENU_OPTION_LIMIT`] options.
+    SelectMenuOptionCountInvalid {
+        /// Number of provided options.
+        count: usize,
+    },
+    /// More than [`ComponentsBuilder::BUTTON_LIMIT`] buttons were added to an
+    /// action row.
+    TooManyButtons {
+        /// Number of provided buttons.
+        count: usize,
+    },
+    /// More than [`ComponentsBuilder::ROW_LIMIT`] action rows were added.
+    TooManyRows {
+        /// Number of provided rows.
+        count: usize,
+    },
+}
+
+/// Create the components of a message with a builder.
+///
+/// # Examples
+///
+/// Refer to the [module-level documentation] for examples.
+///
+/// [module-level documentation]: self
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[must_use = "must be built into components"]
+pub struct ComponentsBuilder(Vec<Component>);
+
+impl ComponentsBuilder {
+    /// The maximum number of buttons in an action row.
+    pub const BUTTON_LIMIT: usize = 5;
+
+    /// The maximum number of characters of the custom ID of a button or
+    /// select menu.
+    pub const CUSTOM_ID_LENGTH_LIMIT: usize = 100;
+
+    /// The maximum number of action rows of a message.
+    pub const ROW_LIMIT: usize = 5;
+
+    /// The maximum number of options of a select menu.
+    pub const SELECT_MENU_OPTION_LIMIT: usize = 25;
+
+    /// Create a new builder of a message's components.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the components, validating the action rows and their
+    /// components.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ComponentBuildErrorType::TooManyRows`] error type if more
+    /// than [`ROW_LIMIT`] action rows were added.
+    ///
+    /// Returns any error of [`ActionRowBuilder::build`] if an action row is
+    /// invalid.
+    ///
+    /// [`ROW_LIMIT`]: Self::ROW_LIMIT
+    pub fn build(self) -> Result<Vec<Component>, ComponentBuildError> {
+        if self.0.len() > Self::ROW_LIMIT {
+            return Err(ComponentBuildError {
+                kind: ComponentBuildErrorType::TooManyRows {
+                    count: self.0.len(),
+                },
+                source: None,
+            });
+        }
+
+        for component in &self.0 {
+            if let Component::ActionRow(row) = component {
+                validate_row(row)?;
+            }
+        }
+
+        Ok(self.0)
+    }
+
+    /// Add an action row.
+    pub fn row(mut self, row: ActionRowBuilder) -> Self {
+        self.0.push(Component::ActionRow(row.0));
+
+        self
+    }
+}
+
+/// Create an action row with a builder.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[must_use = "must be added to components or built into a component"]
+pub struct ActionRowBuilder(ActionRow);
+
+impl ActionRowBuilder {
+    /// Create a new builder of an empty action row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the action row into a component, validating it and its
+    /// components.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ComponentBuildErrorType::RowEmpty`] error type if the row
+    /// has no components.
+    ///
+    /// Returns a [`ComponentBuildErrorType::TooManyButtons`] error type if the
+    /// row has more than [`ComponentsBuilder::BUTTON_LIMIT`] buttons.
+    ///
+    /// Returns a [`ComponentBuildErrorType::SelectMenuNotAlone`] error type if
+    /// the row has a select menu and other components.
+    ///
+    /// Returns a [`ComponentBuildErrorType::LinkButtonInvalid`] or
+    /// [`ComponentBuildErrorType::ButtonInvalid`] error type if a button has a
+    /// custom ID or URL not matching its style.
+    ///
+    /// Returns a [`ComponentBuildErrorType::CustomIdInvalid`] error type if
+    /// the custom ID of a component is invalid.
+    ///
+    /// Returns a [`ComponentBuildErrorType::SelectMenuOptionCountInvalid`]
+    /// error type if a select menu has no options or more than
+    /// [`ComponentsBuilder::SELECT_MENU_OPTION_LIMIT`] options.
+    pub fn build(self) -> Result<Component, ComponentBuildError> {
+        validate_row(&self.0)?;
+
+        Ok(Component::ActionRow(self.0))
+    }
+
+    /// Add a button.
+    pub fn button(mut self, button: ButtonBuilder) -> Self {
+        self.0.components.push(Component::Button(button.0));
+
+        self
+    }
+
+    /// Add a select menu.
+    ///
+    /// A select menu must be the only component in its row.
+    pub fn select_menu(mut self, select_menu: SelectMenuBuilder) -> Self {
+        self.0.components.push(Component::SelectMenu(select_menu.0));
+
+        self
+    }
+}
+
+/// Create a button with a builder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be added to an action row"]
+pub struct ButtonBuilder(Button);
+
+impl ButtonBuilder {
+    /// Create a new builder of a button with a style.
+    ///
+    /// Link buttons must be given a [`url`], while buttons of other styles
+    /// must be given a [`custom_id`].
+    ///
+    /// [`custom_id`]: Self::custom_id
+    /// [`url`]: Self::url
+    pub fn new(style: ButtonStyle) -> Self {
+        Self(Button {
+            custom_id: None,
+            disabled: false,
+            emoji: None,
+            label: None,
+            style,
+            url: None,
+        })
+    }
+
+    /// Build the button.
+    ///
+    /// The button is validated once the action row it's added to is built.
+    #[must_use = "must be added to an action row"]
+    pub fn build(self) -> Button {
+        self.0
+    }
+
+    /// Set the custom ID that's sent to the bot when the button is clicked.
+    ///
+    /// Link buttons mustn't have a custom ID.
+    pub fn custom_id(mut self, custom_id: impl Into<String>) -> Self {
+        self.0.custom_id.replace(custom_id.into());
+
+        self
+    }
+
+    /// Set whether the button is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.0.disabled = disabled;
+
+        self
+    }
+
+    /// Set the emoji shown on the button.
+    pub fn emoji(mut self, emoji: ReactionType) -> Self {
+        self.0.emoji.replace(emoji);
+
+        self
+    }
+
+    /// Set the text shown on the button.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.0.label.replace(label.into());
+
+        self
+    }
+
+    /// Set the URL that a link button opens.
+    ///
+    /// Only link buttons may have a URL.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.0.url.replace(url.into());
+
+        self
+    }
+}
+
+impl From<ButtonBuilder> for Button {
+    fn from(builder: ButtonBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Create a select menu with a builder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be added to an action row"]
+pub struct SelectMenuBuilder(SelectMenu);
+
+impl SelectMenuBuilder {
+    /// Create a new builder of a select menu with the custom ID that's sent to
+    /// the bot when options are selected.
+    pub fn new(custom_id: impl Into<String>) -> Self {
+        Self(SelectMenu {
+            custom_id: custom_id.into(),
+            disabled: false,
+            max_values: None,
+            min_values: None,
+            options: Vec::new(),
+            placeholder: None,
+        })
+    }
+
+    /// Build the select menu.
+    ///
+    /// The select menu is validated once the action row it's added to is
+    /// built.
+    #[must_use = "must be added to an action row"]
+    pub fn build(self) -> SelectMenu {
+        self.0
+    }
+
+    /// Set whether the select menu is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.0.disabled = disabled;
+
+        self
+    }
+
+    /// Set the maximum number of options that may be selected.
+    pub fn max_values(mut self, max_values: u8) -> Self {
+        self.0.max_values.replace(max_values);
+
+        self
+    }
+
+    /// Set the minimum number of options that must be selected.
+    pub fn min_values(mut self, min_values: u8) -> Self {
+        self.0.min_values.replace(min_values);
+
+        self
+    }
+
+    /// Add an option that users select.
+    pub fn option(mut self, option: SelectMenuOption) -> Self {
+        self.0.options.push(option);
+
+        self
+    }
+
+    /// Set the text shown when no option is selected.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.0.placeholder.replace(placeholder.into());
+
+        self
+    }
+}
+
+impl From<SelectMenuBuilder> for SelectMenu {
+    fn from(builder: SelectMenuBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn validate_row(row: &ActionRow) -> Result<(), ComponentBuildError> {
+    if row.components.is_empty() {
+        return Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::RowEmpty,
+            source: None,
+        });
+    }
+
+    let has_select_menu = row
+        .components
+        .iter()
+        .any(|component| matches!(component, Component::SelectMenu(_)));
+
+    if has_select_menu && row.components.len() > 1 {
+        return Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::SelectMenuNotAlone,
+            source: None,
+        });
+    }
+
+    if row.components.len() > ComponentsBuilder::BUTTON_LIMIT {
+        return Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::TooManyButtons {
+                count: row.components.len(),
+            },
+            source: None,
+        });
+    }
+
+    for component in &row.components {
+        match component {
+            Component::Button(button) => validate_button(button)?,
+            Component::SelectMenu(select_menu) => validate_select_menu(select_menu)?,
+            Component::ActionRow(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_button(button: &Button) -> Result<(), ComponentBuildError> {
+    if button.style == ButtonStyle::Link {
+        if button.url.is_none() || button.custom_id.is_some() {
+            return Err(ComponentBuildError {
+                kind: ComponentBuildErrorType::LinkButtonInvalid,
+                source: None,
+            });
+        }
+
+        return Ok(());
+    }
+
+    match button.custom_id.as_ref() {
+        Some(custom_id) if button.url.is_none() => validate_custom_id(custom_id),
+        _ => Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::ButtonInvalid,
+            source: None,
+        }),
+    }
+}
+
+fn validate_select_menu(select_menu: &SelectMenu) -> Result<(), ComponentBuildError> {
+    validate_custom_id(&select_menu.custom_id)?;
+
+    let count = select_menu.options.len();
+
+    if count == 0 || count > ComponentsBuilder::SELECT_MENU_OPTION_LIMIT {
+        return Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::SelectMenuOptionCountInvalid { count },
+            source: None,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_custom_id(custom_id: &str) -> Result<(), ComponentBuildError> {
+    let len = custom_id.chars().count();
+
+    if len == 0 || len > ComponentsBuilder::CUSTOM_ID_LENGTH_LIMIT {
+        return Err(ComponentBuildError {
+            kind: ComponentBuildErrorType::CustomIdInvalid {
+                custom_id: custom_id.to_owned(),
+            },
+            source: None,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ActionRowBuilder, ButtonBuilder, ComponentBuildError, ComponentBuildErrorType,
+        ComponentsBuilder, SelectMenuBuilder,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::application::component::{
+        ActionRow, Button, ButtonStyle, Component, SelectMenu, SelectMenuOption,
+    };
+
+    assert_impl_all!(ActionRowBuilder: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(ButtonBuilder: Clone, Debug, Into<Button>, Send, Sync);
+    assert_impl_all!(ComponentBuildError: Debug, Error, Send, Sync);
+    assert_impl_all!(ComponentBuildErrorType: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(ComponentsBuilder: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(SelectMenuBuilder: Clone, Debug, Into<SelectMenu>, Send, Sync);
+
+    fn button(custom_id: &str) -> ButtonBuilder {
+        ButtonBuilder::new(ButtonStyle::Primary).custom_id(custom_id)
+    }
+
+    fn option(value: &str) -> SelectMenuOption {
+        SelectMenuOption {
+            default: false,
+            description: None,
+            emoji: None,
+            label: value.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_two_rows() {
+        let components = ComponentsBuilder::new()
+            .row(
+                ActionRowBuilder::new()
+                    .button(button("approve").label("Approve"))
+                    .button(
+                        ButtonBuilder::new(ButtonStyle::Link)
+                            .label("View")
+                            .url("https://example.com"),
+                    ),
+            )
+            .row(
+                ActionRowBuilder::new().select_menu(
+                    SelectMenuBuilder::new("reason")
+                        .option(option("spam"))
+                        .option(option("abuse"))
+                        .placeholder("Reason"),
+                ),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                Component::ActionRow(ActionRow {
+                    components: vec![
+                        Component::Button(Button {
+                            custom_id: Some("approve".to_owned()),
+                            disabled: false,
+                            emoji: None,
+                            label: Some("Approve".to_owned()),
+                            style: ButtonStyle::Primary,
+                            url: None,
+                        }),
+                        Component::Button(Button {
+                            custom_id: None,
+                            disabled: false,
+                            emoji: None,
+                            label: Some("View".to_owned()),
+                            style: ButtonStyle::Link,
+                            url: Some("https://example.com".to_owned()),
+                        }),
+                    ],
+                }),
+                Component::ActionRow(ActionRow {
+                    components: vec![Component::SelectMenu(SelectMenu {
+                        custom_id: "reason".to_owned(),
+                        disabled: false,
+                        max_values: None,
+                        min_values: None,
+                        options: vec![option("spam"), option("abuse")],
+                        placeholder: Some("Reason".to_owned()),
+                    })],
+                }),
+            ],
+            components
+        );
+    }
+
+    #[test]
+    fn test_too_many_rows() {
+        let components = (0..6).fold(ComponentsBuilder::new(), |builder, idx| {
+            builder.row(ActionRowBuilder::new().button(button(&idx.to_string())))
+        });
+
+        assert_eq!(
+            &ComponentBuildErrorType::TooManyRows { count: 6 },
+            components.build().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn test_too_many_buttons() {
+        let row = (0..6).fold(ActionRowBuilder::new(), |builder, idx| {
+            builder.button(button(&idx.to_string()))
+        });
+
+        assert_eq!(
+            &ComponentBuildErrorType::TooManyButtons { count: 6 },
+            row.build().unwrap_err().kind()
+        );
+
+        let row = (0..5).fold(ActionRowBuilder::new(), |builder, idx| {
+            builder.button(button(&idx.to_string()))
+        });
+
+        assert!(row.build().is_ok());
+    }
+
+    #[test]
+    fn test_row_empty() {
+        assert_eq!(
+            &ComponentBuildErrorType::RowEmpty,
+            ActionRowBuilder::new().build().unwrap_err().kind()
+        );
+        assert_eq!(
+            &ComponentBuildErrorType::RowEmpty,
+            ComponentsBuilder::new()
+                .row(ActionRowBuilder::new())
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_select_menu_not_alone() {
+        assert_eq!(
+            &ComponentBuildErrorType::SelectMenuNotAlone,
+            ActionRowBuilder::new()
+                .select_menu(SelectMenuBuilder::new("reason").option(option("spam")))
+                .button(button("approve"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_select_menu_option_count_invalid() {
+        assert_eq!(
+            &ComponentBuildErrorType::SelectMenuOptionCountInvalid { count: 0 },
+            ActionRowBuilder::new()
+                .select_menu(SelectMenuBuilder::new("reason"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+
+        let select_menu = (0..26).fold(SelectMenuBuilder::new("reason"), |builder, idx| {
+            builder.option(option(&idx.to_string()))
+        });
+
+        assert_eq!(
+            &ComponentBuildErrorType::SelectMenuOptionCountInvalid { count: 26 },
+            ActionRowBuilder::new()
+                .select_menu(select_menu)
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_link_button_invalid() {
+        assert_eq!(
+            &ComponentBuildErrorType::LinkButtonInvalid,
+            ActionRowBuilder::new()
+                .button(ButtonBuilder::new(ButtonStyle::Link).label("View"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert_eq!(
+            &ComponentBuildErrorType::LinkButtonInvalid,
+            ActionRowBuilder::new()
+                .button(
+                    ButtonBuilder::new(ButtonStyle::Link)
+                        .custom_id("view")
+                        .url("https://example.com")
+                )
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_button_invalid() {
+        assert_eq!(
+            &ComponentBuildErrorType::ButtonInvalid,
+            ActionRowBuilder::new()
+                .button(ButtonBuilder::new(ButtonStyle::Danger).label("Deny"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert_eq!(
+            &ComponentBuildErrorType::ButtonInvalid,
+            ActionRowBuilder::new()
+                .button(button("deny").url("https://example.com"))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_custom_id_invalid() {
+        for custom_id in &["", &"a".repeat(101)] {
+            assert_eq!(
+                &ComponentBuildErrorType::CustomIdInvalid {
+                    custom_id: (*custom_id).to_owned()
+                },
+                ActionRowBuilder::new()
+                    .button(button(custom_id))
+                    .build()
+                    .unwrap_err()
+                    .kind()
+            );
+        }
+
+        assert_eq!(
+            &ComponentBuildErrorType::CustomIdInvalid {
+                custom_id: String::new()
+            },
+            ActionRowBuilder::new()
+                .select_menu(SelectMenuBuilder::new("").option(option("spam")))
+                .build()
+                .unwrap_err()
+                .kind()
+        );
+        assert!(ActionRowBuilder::new()
+            .button(button(&"a".repeat(100)))
+            .build()
+            .is_ok());
+    }
+}
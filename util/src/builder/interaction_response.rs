@@ -0,0 +1,692 @@
+//! Create responses to interactions.
+//!
+//! An [`InteractionResponseBuilder`] is created for the type of the
+//! interaction being responded to and the type of the response, and validates
+//! that the response is allowed for the interaction once it's built.
+//!
+//! Ephemeral messages, which are only shown to the user who invoked the
+//! interaction, are a [`MessageFlags::EPHEMERAL`] flag of the response's
+//! data rather than a field of their own: set it with
+//! [`InteractionResponseBuilder::ephemeral`].
+//!
+//! # Examples
+//!
+//! Respond to a command with an ephemeral message asking for confirmation:
+//!
+//! ```
+//! use twilight_model::application::{
+//!     callback::{InteractionResponse, InteractionResponseType},
+//!     component::ButtonStyle,
+//!     interaction::InteractionType,
+//! };
+//! use twilight_util::builder::{
+//!     component::{ActionRowBuilder, ButtonBuilder, ComponentsBuilder},
+//!     interaction_response::InteractionResponseBuilder,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let components = ComponentsBuilder::new()
+//!     .row(
+//!         ActionRowBuilder::new().button(
+//!             ButtonBuilder::new(ButtonStyle::Danger)
+//!                 .custom_id("ban-confirm")
+//!                 .label("Ban"),
+//!         ),
+//!     )
+//!     .build()?;
+//!
+//! let response = InteractionResponseBuilder::new(
+//!     InteractionType::ApplicationCommand,
+//!     InteractionResponseType::ChannelMessageWithSource,
+//! )
+//! .content("Ban this user?")
+//! .components(components)
+//! .ephemeral(true)
+//! .build()?;
+//!
+//! assert!(matches!(response, InteractionResponse::ChannelMessageWithSource(_)));
+//! # Ok(()) }
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse, InteractionResponseType},
+        component::Component,
+        interaction::InteractionType,
+    },
+    channel::{
+        embed::Embed,
+        message::{AllowedMentions, MessageFlags},
+    },
+};
+
+/// Error building an interaction response.
+///
+/// This is returned from [`InteractionResponseBuilder::build`].
+#[derive(Debug)]
+pub struct InteractionResponseBuildError {
+    kind: InteractionResponseBuildErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl InteractionResponseBuildError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use]
+    pub const fn kind(&self) -> &InteractionResponseBuildErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use]
+    pub fn into_parts(
+        self,
+    ) -> (
+        InteractionResponseBuildErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for InteractionResponseBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            InteractionResponseBuildErrorType::ContentInvalid { content } => {
+                f.write_fmt(format_args!(
+                    "the content is {} characters long, but the limit is {}",
+                    content.chars().count(),
+                    InteractionResponseBuilder::CONTENT_LENGTH_LIMIT,
+                ))
+            }
+            InteractionResponseBuildErrorType::MessageEmpty => {
+                f.write_str("the message has no content or embeds")
+            }
+            InteractionResponseBuildErrorType::MessageNotAllowed { response_type } => {
+                f.write_fmt(format_args!(
+                    "{:?} responses can't set the content, embeds, components, allowed \
+                     mentions or text-to-speech of a message",
+                    response_type,
+                ))
+            }
+            InteractionResponseBuildErrorType::ResponseTypeInvalid {
+                interaction_type,
+                response_type,
+            } => f.write_fmt(format_args!(
+                "{:?} interactions can't be responded to with {:?} responses",
+                interaction_type, response_type,
+            )),
+            InteractionResponseBuildErrorType::TooManyEmbeds { count } => {
+                f.write_fmt(format_args!(
+                    "{} embeds were provided, but the limit is {}",
+                    count,
+                    InteractionResponseBuilder::EMBED_LIMIT,
+                ))
+            }
+        }
+    }
+}
+
+impl Error for InteractionResponseBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`InteractionResponseBuildError`] that occurred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InteractionResponseBuildErrorType {
+    /// Content is longer than
+    /// [`InteractionResponseBuilder::CONTENT_LENGTH_LIMIT`] characters.
+    ContentInvalid {
+        /// Provided content.
+        content: String,
+    },
+    /// Message sent in response has neither content nor embeds.
+    MessageEmpty,
+    /// Response of a deferred type, or of a type without a message, sets
+    /// parts of a message.
+    ///
+    /// Deferred messages may only set flags, such as to make the message
+    /// sent later ephemeral.
+    MessageNotAllowed {
+        /// Type of the response.
+        response_type: InteractionResponseType,
+    },
+    /// Response type isn't allowed for the type of the interaction.
+    ///
+    /// Pings may only be responded to with pongs, and messages may only be
+    /// updated in response to message component interactions.
+    ResponseTypeInvalid {
+        /// Type of the interaction.
+        interaction_type: InteractionType,
+        /// Type of the response.
+        response_type: InteractionResponseType,
+    },
+    /// More than [`InteractionResponseBuilder::EMBED_LIMIT`] embeds were
+    /// added.
+    TooManyEmbeds {
+        /// Number of provided embeds.
+        count: usize,
+    },
+}
+
+/// Create a response to an interaction with a builder.
+///
+/// # Examples
+///
+/// Refer to the [module-level documentation] for examples.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "must be built into an interaction response"]
+pub struct InteractionResponseBuilder {
+    data: CallbackData,
+    interaction_type: InteractionType,
+    response_type: InteractionResponseType,
+}
+
+impl InteractionResponseBuilder {
+    /// The maximum number of characters of the content.
+    pub const CONTENT_LENGTH_LIMIT: usize = 2000;
+
+    /// The maximum number of embeds.
+    pub const EMBED_LIMIT: usize = 10;
+
+    /// Create a new builder of a response of a type to an interaction of a
+    /// type.
+    pub fn new(interaction_type: InteractionType, response_type: InteractionResponseType) -> Self {
+        Self {
+            data: CallbackData::default(),
+            interaction_type,
+            response_type,
+        }
+    }
+
+    /// Build the response, validating that it's allowed for the interaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InteractionResponseBuildErrorType::ResponseTypeInvalid`]
+    /// error type if the response type isn't allowed for the interaction
+    /// type, such as updating a message in response to a command.
+    ///
+    /// Returns a [`InteractionResponseBuildErrorType::MessageNotAllowed`]
+    /// error type if a deferred response or a pong sets parts of a message,
+    /// such as its content.
+    ///
+    /// Returns a [`InteractionResponseBuildErrorType::MessageEmpty`] error
+    /// type if a message sent in response has neither content nor embeds.
+    ///
+    /// Returns a [`InteractionResponseBuildErrorType::ContentInvalid`] error
+    /// type if the content is longer than [`CONTENT_LENGTH_LIMIT`].
+    ///
+    /// Returns a [`InteractionResponseBuildErrorType::TooManyEmbeds`] error
+    /// type if more than [`EMBED_LIMIT`] embeds were added.
+    ///
+    /// [`CONTENT_LENGTH_LIMIT`]: Self::CONTENT_LENGTH_LIMIT
+    /// [`EMBED_LIMIT`]: Self::EMBED_LIMIT
+    pub fn build(self) -> Result<InteractionResponse, InteractionResponseBuildError> {
+        let allowed = match self.response_type {
+            InteractionResponseType::Pong => self.interaction_type == InteractionType::Ping,
+            InteractionResponseType::ChannelMessageWithSource
+            | InteractionResponseType::DeferredChannelMessageWithSource => {
+                self.interaction_type != InteractionType::Ping
+            }
+            InteractionResponseType::DeferredUpdateMessage
+            | InteractionResponseType::UpdateMessage => {
+                self.interaction_type == InteractionType::MessageComponent
+            }
+        };
+
+        if !allowed {
+            return Err(InteractionResponseBuildError {
+                kind: InteractionResponseBuildErrorType::ResponseTypeInvalid {
+                    interaction_type: self.interaction_type,
+                    response_type: self.response_type,
+                },
+                source: None,
+            });
+        }
+
+        let data = self.data;
+        let has_message = data.allowed_mentions.is_some()
+            || data.components.is_some()
+            || data.content.is_some()
+            || data.embeds.is_some()
+            || data.tts.is_some();
+
+        let message_allowed = match self.response_type {
+            InteractionResponseType::ChannelMessageWithSource
+            | InteractionResponseType::UpdateMessage => true,
+            InteractionResponseType::DeferredChannelMessageWithSource => !has_message,
+            InteractionResponseType::Pong | InteractionResponseType::DeferredUpdateMessage => {
+                !has_message && data.flags.is_none()
+            }
+        };
+
+        if !message_allowed {
+            return Err(InteractionResponseBuildError {
+                kind: InteractionResponseBuildErrorType::MessageNotAllowed {
+                    response_type: self.response_type,
+                },
+                source: None,
+            });
+        }
+
+        if self.response_type == InteractionResponseType::ChannelMessageWithSource
+            && data.content.as_ref().map_or(true, String::is_empty)
+            && data.embeds.as_ref().map_or(true, Vec::is_empty)
+        {
+            return Err(InteractionResponseBuildError {
+                kind: InteractionResponseBuildErrorType::MessageEmpty,
+                source: None,
+            });
+        }
+
+        if let Some(content) = data.content.as_ref() {
+            if content.chars().count() > Self::CONTENT_LENGTH_LIMIT {
+                return Err(InteractionResponseBuildError {
+                    kind: InteractionResponseBuildErrorType::ContentInvalid {
+                        content: content.clone(),
+                    },
+                    source: None,
+                });
+            }
+        }
+
+        if let Some(embeds) = data.embeds.as_ref() {
+            if embeds.len() > Self::EMBED_LIMIT {
+                return Err(InteractionResponseBuildError {
+                    kind: InteractionResponseBuildErrorType::TooManyEmbeds {
+                        count: embeds.len(),
+                    },
+                    source: None,
+                });
+            }
+        }
+
+        Ok(match self.response_type {
+            InteractionResponseType::Pong => InteractionResponse::Pong,
+            InteractionResponseType::ChannelMessageWithSource => {
+                InteractionResponse::ChannelMessageWithSource(data)
+            }
+            InteractionResponseType::DeferredChannelMessageWithSource => {
+                InteractionResponse::DeferredChannelMessageWithSource(data)
+            }
+            InteractionResponseType::DeferredUpdateMessage => {
+                InteractionResponse::DeferredUpdateMessage
+            }
+            InteractionResponseType::UpdateMessage => InteractionResponse::UpdateMessage(data),
+        })
+    }
+
+    /// Set the mentions in the content that notify the mentioned users.
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.data.allowed_mentions.replace(allowed_mentions);
+
+        self
+    }
+
+    /// Set the components of the message, such as those built by a
+    /// [`ComponentsBuilder`].
+    ///
+    /// Updating a message with no components removes its components.
+    ///
+    /// [`ComponentsBuilder`]: super::component::ComponentsBuilder
+    pub fn components(mut self, components: Vec<Component>) -> Self {
+        self.data.components.replace(components);
+
+        self
+    }
+
+    /// Set the content of the message.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.data.content.replace(content.into());
+
+        self
+    }
+
+    /// Add an embed to the message.
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.data.embeds.get_or_insert_with(Vec::new).push(embed);
+
+        self
+    }
+
+    /// Set whether the message is only shown to the user who invoked the
+    /// interaction.
+    ///
+    /// This sets the [`MessageFlags::EPHEMERAL`] flag of the message, and may
+    /// be set on deferred messages to make the message sent later ephemeral.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        let mut flags = self.data.flags.unwrap_or_else(MessageFlags::empty);
+        flags.set(MessageFlags::EPHEMERAL, ephemeral);
+        self.data.flags = if flags.is_empty() { None } else { Some(flags) };
+
+        self
+    }
+
+    /// Set whether the message is read aloud with text-to-speech.
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.data.tts.replace(tts);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InteractionResponseBuildError, InteractionResponseBuildErrorType,
+        InteractionResponseBuilder,
+    };
+    use serde_json::json;
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug};
+    use twilight_embed_builder::EmbedBuilder;
+    use twilight_model::{
+        application::{
+            callback::{InteractionResponse, InteractionResponseType},
+            component::{ActionRow, Button, ButtonStyle, Component},
+            interaction::InteractionType,
+        },
+        channel::message::{AllowedMentions, MessageFlags},
+    };
+
+    assert_impl_all!(InteractionResponseBuildError: Debug, Error, Send, Sync);
+    assert_impl_all!(
+        InteractionResponseBuildErrorType: Clone,
+        Debug,
+        Eq,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(InteractionResponseBuilder: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    fn button() -> Component {
+        Component::ActionRow(ActionRow {
+            components: vec![Component::Button(Button {
+                custom_id: Some("confirm".to_owned()),
+                disabled: false,
+                emoji: None,
+                label: Some("Confirm".to_owned()),
+                style: ButtonStyle::Danger,
+                url: None,
+            })],
+        })
+    }
+
+    fn build(
+        interaction_type: InteractionType,
+        response_type: InteractionResponseType,
+    ) -> Result<InteractionResponse, InteractionResponseBuildError> {
+        InteractionResponseBuilder::new(interaction_type, response_type).build()
+    }
+
+    #[test]
+    fn test_pong() {
+        let response = build(InteractionType::Ping, InteractionResponseType::Pong).unwrap();
+
+        assert_eq!(json!({"type": 1}), serde_json::to_value(&response).unwrap());
+    }
+
+    #[test]
+    fn test_channel_message_with_source() {
+        let response = InteractionResponseBuilder::new(
+            InteractionType::ApplicationCommand,
+            InteractionResponseType::ChannelMessageWithSource,
+        )
+        .allowed_mentions(AllowedMentions::default())
+        .components(vec![button()])
+        .content("ban user 1?")
+        .ephemeral(true)
+        .tts(false)
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            json!({
+                "type": 4,
+                "data": {
+                    "allowed_mentions": {"parse": []},
+                    "components": [{
+                        "type": 1,
+                        "components": [{
+                            "type": 2,
+                            "custom_id": "confirm",
+                            "disabled": false,
+                            "label": "Confirm",
+                            "style": 4,
+                        }],
+                    }],
+                    "content": "ban user 1?",
+                    "flags": 64,
+                    "tts": false,
+                },
+            }),
+            serde_json::to_value(&response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_channel_message_with_embed() {
+        let embed = EmbedBuilder::new()
+            .description("banned")
+            .unwrap()
+            .build()
+            .unwrap();
+        let response = InteractionResponseBuilder::new(
+            InteractionType::MessageComponent,
+            InteractionResponseType::ChannelMessageWithSource,
+        )
+        .embed(embed)
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            json!({
+                "type": 4,
+                "data": {
+                    "embeds": [{"description": "banned", "type": "rich"}],
+                },
+            }),
+            serde_json::to_value(&response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deferred_channel_message_with_source() {
+        let response = InteractionResponseBuilder::new(
+            InteractionType::ApplicationCommand,
+            InteractionResponseType::DeferredChannelMessageWithSource,
+        )
+        .ephemeral(true)
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            json!({"type": 5, "data": {"flags": 64}}),
+            serde_json::to_value(&response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deferred_update_message() {
+        let response = build(
+            InteractionType::MessageComponent,
+            InteractionResponseType::DeferredUpdateMessage,
+        )
+        .unwrap();
+
+        assert_eq!(json!({"type": 6}), serde_json::to_value(&response).unwrap());
+    }
+
+    #[test]
+    fn test_update_message() {
+        let response = InteractionResponseBuilder::new(
+            InteractionType::MessageComponent,
+            InteractionResponseType::UpdateMessage,
+        )
+        .content("user 1 was banned")
+        .components(Vec::new())
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            json!({
+                "type": 7,
+                "data": {"components": [], "content": "user 1 was banned"},
+            }),
+            serde_json::to_value(&response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ephemeral_toggle() {
+        let builder = InteractionResponseBuilder::new(
+            InteractionType::ApplicationCommand,
+            InteractionResponseType::ChannelMessageWithSource,
+        )
+        .content("hi");
+
+        let response = builder.clone().ephemeral(true).build().unwrap();
+        assert_eq!(
+            Some(MessageFlags::EPHEMERAL),
+            response.data().and_then(|data| data.flags)
+        );
+
+        let response = builder.ephemeral(true).ephemeral(false).build().unwrap();
+        assert_eq!(None, response.data().and_then(|data| data.flags));
+    }
+
+    #[test]
+    fn test_response_type_invalid() {
+        let invalid = [
+            (
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::UpdateMessage,
+            ),
+            (
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::DeferredUpdateMessage,
+            ),
+            (
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::Pong,
+            ),
+            (
+                InteractionType::Ping,
+                InteractionResponseType::ChannelMessageWithSource,
+            ),
+        ];
+
+        for (interaction_type, response_type) in invalid.iter().copied() {
+            assert_eq!(
+                &InteractionResponseBuildErrorType::ResponseTypeInvalid {
+                    interaction_type,
+                    response_type,
+                },
+                InteractionResponseBuilder::new(interaction_type, response_type)
+                    .content("hi")
+                    .build()
+                    .unwrap_err()
+                    .kind()
+            );
+        }
+    }
+
+    #[test]
+    fn test_message_not_allowed() {
+        let error = InteractionResponseBuilder::new(
+            InteractionType::ApplicationCommand,
+            InteractionResponseType::DeferredChannelMessageWithSource,
+        )
+        .content("loading")
+        .build()
+        .unwrap_err();
+        assert_eq!(
+            &InteractionResponseBuildErrorType::MessageNotAllowed {
+                response_type: InteractionResponseType::DeferredChannelMessageWithSource,
+            },
+            error.kind()
+        );
+
+        let error = InteractionResponseBuilder::new(
+            InteractionType::MessageComponent,
+            InteractionResponseType::DeferredUpdateMessage,
+        )
+        .ephemeral(true)
+        .build()
+        .unwrap_err();
+        assert_eq!(
+            &InteractionResponseBuildErrorType::MessageNotAllowed {
+                response_type: InteractionResponseType::DeferredUpdateMessage,
+            },
+            error.kind()
+        );
+    }
+
+    #[test]
+    fn test_message_empty() {
+        assert_eq!(
+            &InteractionResponseBuildErrorType::MessageEmpty,
+            InteractionResponseBuilder::new(
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::ChannelMessageWithSource,
+            )
+            .components(vec![button()])
+            .build()
+            .unwrap_err()
+            .kind()
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let content = "a".repeat(2001);
+        assert_eq!(
+            &InteractionResponseBuildErrorType::ContentInvalid {
+                content: content.clone()
+            },
+            InteractionResponseBuilder::new(
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::ChannelMessageWithSource,
+            )
+            .content(content)
+            .build()
+            .unwrap_err()
+            .kind()
+        );
+
+        let embed = EmbedBuilder::new()
+            .description("a")
+            .unwrap()
+            .build()
+            .unwrap();
+        let builder = (0..11).fold(
+            InteractionResponseBuilder::new(
+                InteractionType::ApplicationCommand,
+                InteractionResponseType::ChannelMessageWithSource,
+            ),
+            |builder, _| builder.embed(embed.clone()),
+        );
+        assert_eq!(
+            &InteractionResponseBuildErrorType::TooManyEmbeds { count: 11 },
+            builder.build().unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,6 @@
+//! Builders of application commands, message components and interaction
+//! responses, validating them against Discord's rules when they're built.
+
+pub mod command;
+pub mod component;
+pub mod interaction_response;
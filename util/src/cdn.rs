@@ -0,0 +1,445 @@
+//! Utilities for building URLs to images on Discord's CDN.
+//!
+//! Each function creates a [`CdnUrl`] of a resource, whose format and size
+//! may be configured before it's formatted via its [`Display`]
+//! implementation.
+//!
+//! # Examples
+//!
+//! Build the URL of a user's animated avatar:
+//!
+//! ```
+//! use twilight_model::id::UserId;
+//! use twilight_util::cdn;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let url = cdn::user_avatar(UserId(123), "a_1269e74af4df7417b13759eae50c83dc").size(256)?;
+//!
+//! assert_eq!(
+//!     "https://cdn.discordapp.com/avatars/123/a_1269e74af4df7417b13759eae50c83dc.gif?size=256",
+//!     url.to_string(),
+//! );
+//! # Ok(()) }
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    channel::message::sticker::StickerId,
+    id::{EmojiId, GuildId, UserId},
+};
+
+/// Base URL of Discord's CDN.
+const BASE: &str = "https://cdn.discordapp.com";
+
+/// Maximum size of an image.
+const SIZE_MAX: u16 = 4096;
+
+/// Minimum size of an image.
+const SIZE_MIN: u16 = 16;
+
+/// Error when configuring a [`CdnUrl`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CdnUrlError {
+    /// Format isn't supported by the resource.
+    ///
+    /// Only animated resources may be formatted as GIFs, and default avatars
+    /// and stickers may only be formatted as PNGs.
+    FormatUnsupported {
+        /// Provided format.
+        format: ImageFormat,
+    },
+    /// Size isn't a power of two between 16 and 4096.
+    SizeInvalid {
+        /// Provided size.
+        size: u16,
+    },
+}
+
+impl Display for CdnUrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::FormatUnsupported { format } => f.write_fmt(format_args!(
+                "the resource can't be formatted as {}",
+                format.extension(),
+            )),
+            Self::SizeInvalid { size } => f.write_fmt(format_args!(
+                "the size {} isn't a power of two between {} and {}",
+                size, SIZE_MIN, SIZE_MAX,
+            )),
+        }
+    }
+}
+
+impl Error for CdnUrlError {}
+
+/// Format of an image on the CDN.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ImageFormat {
+    /// GIF, which is only supported by animated resources.
+    Gif,
+    /// JPEG.
+    Jpeg,
+    /// PNG.
+    Png,
+    /// WebP.
+    WebP,
+}
+
+impl ImageFormat {
+    /// Return the file extension of the format.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::WebP => "webp",
+        }
+    }
+}
+
+/// Resource on the CDN.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Resource<'a> {
+    DefaultAvatar { discriminator: u16 },
+    Emoji { emoji_id: EmojiId },
+    GuildBanner { guild_id: GuildId, hash: &'a str },
+    GuildIcon { guild_id: GuildId, hash: &'a str },
+    Sticker { sticker_id: u64 },
+    UserAvatar { user_id: UserId, hash: &'a str },
+}
+
+impl Resource<'_> {
+    /// Whether the resource may be formatted as a format.
+    fn supports(self, format: ImageFormat) -> bool {
+        match self {
+            Self::DefaultAvatar { .. } | Self::Sticker { .. } => format == ImageFormat::Png,
+            // Whether an emoji is animated isn't known from its ID.
+            Self::Emoji { .. } => true,
+            Self::GuildBanner { hash, .. }
+            | Self::GuildIcon { hash, .. }
+            | Self::UserAvatar { hash, .. } => format != ImageFormat::Gif || animated(hash),
+        }
+    }
+}
+
+/// URL of an image on Discord's CDN.
+///
+/// Unless a format is configured, animated resources are formatted as GIFs
+/// and all others as PNGs.
+///
+/// The URL is built by formatting it, such as via [`ToString::to_string`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "the url must be formatted to be built"]
+pub struct CdnUrl<'a> {
+    format: Option<ImageFormat>,
+    resource: Resource<'a>,
+    size: Option<u16>,
+}
+
+impl<'a> CdnUrl<'a> {
+    const fn new(resource: Resource<'a>) -> Self {
+        Self {
+            format: None,
+            resource,
+            size: None,
+        }
+    }
+
+    /// Set the format of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CdnUrlError::FormatUnsupported`] if the resource can't be
+    /// formatted as the format. Only resources with animated hashes and emojis
+    /// may be formatted as GIFs, and default avatars and stickers may only be
+    /// formatted as PNGs.
+    pub fn format(mut self, format: ImageFormat) -> Result<Self, CdnUrlError> {
+        if !self.resource.supports(format) {
+            return Err(CdnUrlError::FormatUnsupported { format });
+        }
+
+        self.format.replace(format);
+
+        Ok(self)
+    }
+
+    /// Set the size of the image in pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CdnUrlError::SizeInvalid`] if the size isn't a power of two
+    /// between 16 and 4096.
+    pub fn size(mut self, size: u16) -> Result<Self, CdnUrlError> {
+        if !size.is_power_of_two() || !(SIZE_MIN..=SIZE_MAX).contains(&size) {
+            return Err(CdnUrlError::SizeInvalid { size });
+        }
+
+        self.size.replace(size);
+
+        Ok(self)
+    }
+
+    /// Format of the image, whether configured or automatically selected.
+    #[must_use]
+    pub fn image_format(&self) -> ImageFormat {
+        self.format.unwrap_or_else(|| match self.resource {
+            Resource::GuildBanner { hash, .. }
+            | Resource::GuildIcon { hash, .. }
+            | Resource::UserAvatar { hash, .. }
+                if animated(hash) =>
+            {
+                ImageFormat::Gif
+            }
+            _ => ImageFormat::Png,
+        })
+    }
+}
+
+impl Display for CdnUrl<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(BASE)?;
+
+        match self.resource {
+            Resource::DefaultAvatar { discriminator } => {
+                f.write_fmt(format_args!("/embed/avatars/{}", discriminator % 5))
+            }
+            Resource::Emoji { emoji_id } => f.write_fmt(format_args!("/emojis/{}", emoji_id)),
+            Resource::GuildBanner { guild_id, hash } => {
+                f.write_fmt(format_args!("/banners/{}/{}", guild_id, hash))
+            }
+            Resource::GuildIcon { guild_id, hash } => {
+                f.write_fmt(format_args!("/icons/{}/{}", guild_id, hash))
+            }
+            Resource::Sticker { sticker_id } => {
+                f.write_fmt(format_args!("/stickers/{}", sticker_id))
+            }
+            Resource::UserAvatar { user_id, hash } => {
+                f.write_fmt(format_args!("/avatars/{}/{}", user_id, hash))
+            }
+        }?;
+
+        f.write_str(".")?;
+        f.write_str(self.image_format().extension())?;
+
+        if let Some(size) = self.size {
+            f.write_fmt(format_args!("?size={}", size))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Create the URL of the default avatar of users with a discriminator.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_util::cdn;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let discriminator = "1234".parse()?;
+///
+/// assert_eq!(
+///     "https://cdn.discordapp.com/embed/avatars/4.png",
+///     cdn::default_avatar(discriminator).to_string(),
+/// );
+/// # Ok(()) }
+/// ```
+pub const fn default_avatar(discriminator: u16) -> CdnUrl<'static> {
+    CdnUrl::new(Resource::DefaultAvatar { discriminator })
+}
+
+/// Create the URL of a custom emoji.
+///
+/// Animated emojis must be [formatted] as GIFs to be animated.
+///
+/// [formatted]: CdnUrl::format
+pub const fn emoji(emoji_id: EmojiId) -> CdnUrl<'static> {
+    CdnUrl::new(Resource::Emoji { emoji_id })
+}
+
+/// Create the URL of the banner of a guild.
+pub const fn guild_banner(guild_id: GuildId, hash: &str) -> CdnUrl<'_> {
+    CdnUrl::new(Resource::GuildBanner { guild_id, hash })
+}
+
+/// Create the URL of the icon of a guild.
+pub const fn guild_icon(guild_id: GuildId, hash: &str) -> CdnUrl<'_> {
+    CdnUrl::new(Resource::GuildIcon { guild_id, hash })
+}
+
+/// Create the URL of a sticker.
+///
+/// Stickers with the [`Lottie`] format are not images, so their URLs point to
+/// an image that isn't usable.
+///
+/// [`Lottie`]: twilight_model::channel::message::sticker::StickerFormatType::Lottie
+pub const fn sticker(sticker_id: &StickerId) -> CdnUrl<'static> {
+    CdnUrl::new(Resource::Sticker {
+        sticker_id: sticker_id.0,
+    })
+}
+
+/// Create the URL of the avatar of a user.
+///
+/// Users without an avatar have a [default avatar].
+///
+/// [default avatar]: default_avatar
+pub const fn user_avatar(user_id: UserId, hash: &str) -> CdnUrl<'_> {
+    CdnUrl::new(Resource::UserAvatar { user_id, hash })
+}
+
+/// Whether an image hash is of an animated image.
+fn animated(hash: &str) -> bool {
+    hash.starts_with("a_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CdnUrl, CdnUrlError, ImageFormat};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::message::sticker::StickerId,
+        id::{EmojiId, GuildId, UserId},
+    };
+
+    assert_fields!(CdnUrlError::FormatUnsupported: format);
+    assert_fields!(CdnUrlError::SizeInvalid: size);
+    assert_impl_all!(CdnUrl<'_>: Clone, Debug, Display, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(CdnUrlError: Clone, Debug, Display, Eq, Error, PartialEq, Send, Sync);
+    assert_impl_all!(ImageFormat: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    const ANIMATED: &str = "a_1269e74af4df7417b13759eae50c83dc";
+    const STILL: &str = "1269e74af4df7417b13759eae50c83dc";
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(
+            "https://cdn.discordapp.com/avatars/1/1269e74af4df7417b13759eae50c83dc.png",
+            super::user_avatar(UserId(1), STILL).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/avatars/1/a_1269e74af4df7417b13759eae50c83dc.gif",
+            super::user_avatar(UserId(1), ANIMATED).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/embed/avatars/0.png",
+            super::default_avatar(5).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/embed/avatars/4.png",
+            super::default_avatar(9999).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/icons/2/1269e74af4df7417b13759eae50c83dc.png",
+            super::guild_icon(GuildId(2), STILL).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/banners/2/a_1269e74af4df7417b13759eae50c83dc.gif",
+            super::guild_banner(GuildId(2), ANIMATED).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/emojis/3.png",
+            super::emoji(EmojiId(3)).to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/stickers/4.png",
+            super::sticker(&StickerId(4)).to_string(),
+        );
+    }
+
+    #[test]
+    fn test_format() -> Result<(), CdnUrlError> {
+        assert_eq!(
+            "https://cdn.discordapp.com/avatars/1/a_1269e74af4df7417b13759eae50c83dc.webp",
+            super::user_avatar(UserId(1), ANIMATED)
+                .format(ImageFormat::WebP)?
+                .to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/icons/2/1269e74af4df7417b13759eae50c83dc.jpg",
+            super::guild_icon(GuildId(2), STILL)
+                .format(ImageFormat::Jpeg)?
+                .to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/emojis/3.gif",
+            super::emoji(EmojiId(3))
+                .format(ImageFormat::Gif)?
+                .to_string(),
+        );
+
+        assert_eq!(
+            CdnUrlError::FormatUnsupported {
+                format: ImageFormat::Gif
+            },
+            super::user_avatar(UserId(1), STILL)
+                .format(ImageFormat::Gif)
+                .unwrap_err(),
+        );
+        assert!(super::guild_banner(GuildId(2), STILL)
+            .format(ImageFormat::Gif)
+            .is_err());
+
+        for format in &[ImageFormat::Gif, ImageFormat::Jpeg, ImageFormat::WebP] {
+            assert!(super::default_avatar(1).format(*format).is_err());
+            assert!(super::sticker(&StickerId(4)).format(*format).is_err());
+        }
+
+        assert!(super::sticker(&StickerId(4))
+            .format(ImageFormat::Png)
+            .is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_size() -> Result<(), CdnUrlError> {
+        assert_eq!(
+            "https://cdn.discordapp.com/avatars/1/1269e74af4df7417b13759eae50c83dc.webp?size=16",
+            super::user_avatar(UserId(1), STILL)
+                .format(ImageFormat::WebP)?
+                .size(16)?
+                .to_string(),
+        );
+        assert_eq!(
+            "https://cdn.discordapp.com/emojis/3.png?size=4096",
+            super::emoji(EmojiId(3)).size(4096)?.to_string(),
+        );
+
+        for size in &[0, 8, 100, 8192, u16::MAX] {
+            assert_eq!(
+                CdnUrlError::SizeInvalid { size: *size },
+                super::emoji(EmojiId(3)).size(*size).unwrap_err(),
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_error() {
+        assert_eq!(
+            "the resource can't be formatted as gif",
+            CdnUrlError::FormatUnsupported {
+                format: ImageFormat::Gif
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "the size 100 isn't a power of two between 16 and 4096",
+            CdnUrlError::SizeInvalid { size: 100 }.to_string(),
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Utilities for working with colors, such as those of roles and embeds.
+//!
+//! Colors are RGB values stored in the lower 24 bits of a `u32`, which is how
+//! Discord represents them. Larger values are rejected by Discord.
+//!
+//! # Examples
+//!
+//! Set the color of an embed from a hex string:
+//!
+//! ```
+//! use twilight_embed_builder::EmbedBuilder;
+//! use twilight_util::color;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let embed = EmbedBuilder::new()
+//!     .color(color::parse_hex("#5865F2")?)?
+//!     .description("Member banned")?
+//!     .build()?;
+//!
+//! assert_eq!(Some(color::BLURPLE), embed.color);
+//! # Ok(()) }
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Blue of Discord's branding.
+pub const BLURPLE: u32 = 0x58_65_f2;
+
+/// Red used for dangerous or destructive actions, such as bans.
+pub const DANGER: u32 = 0xed_42_45;
+
+/// Green used for successful actions.
+pub const SUCCESS: u32 = 0x57_f2_87;
+
+/// Yellow used for warnings.
+pub const WARNING: u32 = 0xfe_e7_5c;
+
+/// Teal role color.
+pub const TEAL: u32 = 0x1a_bc_9c;
+
+/// Dark teal role color.
+pub const DARK_TEAL: u32 = 0x11_80_6a;
+
+/// Green role color.
+pub const GREEN: u32 = 0x2e_cc_71;
+
+/// Dark green role color.
+pub const DARK_GREEN: u32 = 0x1f_8b_4c;
+
+/// Blue role color.
+pub const BLUE: u32 = 0x34_98_db;
+
+/// Dark blue role color.
+pub const DARK_BLUE: u32 = 0x20_66_94;
+
+/// Purple role color.
+pub const PURPLE: u32 = 0x9b_59_b6;
+
+/// Dark purple role color.
+pub const DARK_PURPLE: u32 = 0x71_36_8a;
+
+/// Magenta role color.
+pub const MAGENTA: u32 = 0xe9_1e_63;
+
+/// Dark magenta role color.
+pub const DARK_MAGENTA: u32 = 0xad_14_57;
+
+/// Gold role color.
+pub const GOLD: u32 = 0xf1_c4_0f;
+
+/// Dark gold role color.
+pub const DARK_GOLD: u32 = 0xc2_7c_0e;
+
+/// Orange role color.
+pub const ORANGE: u32 = 0xe6_7e_22;
+
+/// Dark orange role color.
+pub const DARK_ORANGE: u32 = 0xa8_43_00;
+
+/// Red role color.
+pub const RED: u32 = 0xe7_4c_3c;
+
+/// Dark red role color.
+pub const DARK_RED: u32 = 0x99_2d_22;
+
+/// Lighter grey role color.
+pub const LIGHTER_GREY: u32 = 0x95_a5_a6;
+
+/// Dark grey role color.
+pub const DARK_GREY: u32 = 0x60_7d_8b;
+
+/// Light grey role color.
+pub const LIGHT_GREY: u32 = 0x97_9c_9f;
+
+/// Darker grey role color.
+pub const DARKER_GREY: u32 = 0x54_6e_7a;
+
+/// Maximum value of a color.
+pub const MAX: u32 = 0xff_ff_ff;
+
+/// Error when [parsing] a hex color.
+///
+/// [parsing]: parse_hex
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ColorParseError {
+    /// Character isn't a hexadecimal digit.
+    DigitInvalid {
+        /// Invalid character.
+        found: char,
+    },
+    /// Color doesn't have exactly 6 digits.
+    ///
+    /// Shorthand colors such as `#fff` aren't supported.
+    LengthInvalid {
+        /// Number of characters after the optional leading `#`.
+        length: usize,
+    },
+}
+
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::DigitInvalid { found } => f.write_fmt(format_args!(
+                "'{}' isn't a hexadecimal digit",
+                found.escape_debug(),
+            )),
+            Self::LengthInvalid { length } => f.write_fmt(format_args!(
+                "a color has 6 digits, but {} were provided",
+                length,
+            )),
+        }
+    }
+}
+
+impl Error for ColorParseError {}
+
+/// Create a color from its red, green and blue components.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_util::color;
+///
+/// assert_eq!(color::BLURPLE, color::rgb(88, 101, 242));
+/// ```
+#[must_use]
+pub const fn rgb(red: u8, green: u8, blue: u8) -> u32 {
+    (red as u32) << 16 | (green as u32) << 8 | blue as u32
+}
+
+/// Split a color into its red, green and blue components.
+///
+/// Bits above the lower 24 are ignored.
+#[allow(clippy::cast_possible_truncation)]
+#[must_use]
+pub const fn components(color: u32) -> (u8, u8, u8) {
+    ((color >> 16) as u8, (color >> 8) as u8, color as u8)
+}
+
+/// Whether a value is a valid color, fitting in 24 bits.
+#[must_use]
+pub const fn is_valid(color: u32) -> bool {
+    color <= MAX
+}
+
+/// Parse a color from a hex string, such as `#5865F2` or `5865f2`.
+///
+/// The leading `#` is optional and digits may be of either case. Shorthand
+/// colors such as `#fff` are rejected rather than expanded, as they're
+/// ambiguous with mistyped colors.
+///
+/// # Errors
+///
+/// Returns [`ColorParseError::LengthInvalid`] if there aren't exactly 6
+/// characters after the optional `#`.
+///
+/// Returns [`ColorParseError::DigitInvalid`] if a character isn't a
+/// hexadecimal digit.
+pub fn parse_hex(hex: &str) -> Result<u32, ColorParseError> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    let length = digits.chars().count();
+
+    if length != 6 {
+        return Err(ColorParseError::LengthInvalid { length });
+    }
+
+    if let Some(found) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(ColorParseError::DigitInvalid { found });
+    }
+
+    u32::from_str_radix(digits, 16).map_err(|_| ColorParseError::LengthInvalid { length })
+}
+
+/// Format a color as a hex string with a leading `#`, such as `#5865f2`.
+///
+/// Bits above the lower 24 are ignored.
+#[must_use]
+pub fn to_hex(color: u32) -> String {
+    format!("#{:06x}", color & MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorParseError;
+    use static_assertions::{assert_fields, assert_impl_all, const_assert};
+    use std::{error::Error, fmt::Debug};
+
+    assert_fields!(ColorParseError::DigitInvalid: found);
+    assert_fields!(ColorParseError::LengthInvalid: length);
+    assert_impl_all!(ColorParseError: Clone, Debug, Error, Eq, PartialEq, Send, Sync);
+    const_assert!(super::is_valid(super::MAX));
+    const_assert!(!super::is_valid(super::MAX + 1));
+
+    const COLORS: &[u32] = &[
+        super::BLURPLE,
+        super::DANGER,
+        super::SUCCESS,
+        super::WARNING,
+        super::TEAL,
+        super::DARK_TEAL,
+        super::GREEN,
+        super::DARK_GREEN,
+        super::BLUE,
+        super::DARK_BLUE,
+        super::PURPLE,
+        super::DARK_PURPLE,
+        super::MAGENTA,
+        super::DARK_MAGENTA,
+        super::GOLD,
+        super::DARK_GOLD,
+        super::ORANGE,
+        super::DARK_ORANGE,
+        super::RED,
+        super::DARK_RED,
+        super::LIGHTER_GREY,
+        super::DARK_GREY,
+        super::LIGHT_GREY,
+        super::DARKER_GREY,
+    ];
+
+    #[test]
+    fn test_constants() {
+        for color in COLORS {
+            assert!(super::is_valid(*color));
+            assert_ne!(0, *color);
+            assert_eq!(Ok(*color), super::parse_hex(&super::to_hex(*color)));
+        }
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(0, super::rgb(0, 0, 0));
+        assert_eq!(super::MAX, super::rgb(255, 255, 255));
+        assert_eq!(super::DANGER, super::rgb(237, 66, 69));
+        assert_eq!((237, 66, 69), super::components(super::DANGER));
+        assert_eq!((255, 255, 255), super::components(u32::MAX));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Ok(super::BLURPLE), super::parse_hex("#5865F2"));
+        assert_eq!(Ok(super::BLURPLE), super::parse_hex("5865f2"));
+        assert_eq!(Ok(0), super::parse_hex("#000000"));
+        assert_eq!(Ok(super::MAX), super::parse_hex("FFFFFF"));
+    }
+
+    #[test]
+    fn test_parse_hex_invalid() {
+        assert_eq!(
+            Err(ColorParseError::LengthInvalid { length: 3 }),
+            super::parse_hex("#fff"),
+        );
+        assert_eq!(
+            Err(ColorParseError::LengthInvalid { length: 0 }),
+            super::parse_hex("#"),
+        );
+        assert_eq!(
+            Err(ColorParseError::LengthInvalid { length: 7 }),
+            super::parse_hex("##5865f2"),
+        );
+        assert_eq!(
+            Err(ColorParseError::LengthInvalid { length: 8 }),
+            super::parse_hex("ff5865f2"),
+        );
+        assert_eq!(
+            Err(ColorParseError::DigitInvalid { found: '+' }),
+            super::parse_hex("+58652"),
+        );
+        assert_eq!(
+            Err(ColorParseError::DigitInvalid { found: 'é' }),
+            super::parse_hex("5865fé"),
+        );
+    }
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!("#5865f2", super::to_hex(super::BLURPLE));
+        assert_eq!("#000001", super::to_hex(1));
+        assert_eq!("#ffffff", super::to_hex(u32::MAX));
+    }
+
+    #[test]
+    fn test_display_error() {
+        assert_eq!(
+            "a color has 6 digits, but 3 were provided",
+            ColorParseError::LengthInvalid { length: 3 }.to_string(),
+        );
+        assert_eq!(
+            "'g' isn't a hexadecimal digit",
+            ColorParseError::DigitInvalid { found: 'g' }.to_string(),
+        );
+    }
+}
@@ -7,6 +7,30 @@
 //!
 //! ## Features
 //!
+//! ### `builder`
+//!
+//! Allows the use of builders of application commands, message components and interaction
+//! responses, which validate them against Discord's rules when they're built.
+//!
+//! ### `cdn`
+//!
+//! Allows the use of builders of the URLs of images on Discord's CDN, such as avatars and emojis.
+//!
+//! ### `color`
+//!
+//! Allows the use of named colors, such as those of roles, and utilities for creating and parsing
+//! colors.
+//!
+//! ### `link`
+//!
+//! Allows the use of utilities for parsing and formatting links, such as message jump links, invites
+//! and webhook URLs.
+//!
+//! ### `permission-calculator`
+//!
+//! Allows the use of a calculator of the permissions of guild members that only uses the data
+//! it's given, such as data fetched over REST or from a cache.
+//!
 //! ### `snowflake`
 //!
 //! Allows the use of the `Snowflake` trait, which provides methods for the extraction of
@@ -35,10 +59,26 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "builder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
+pub mod builder;
+
+#[cfg(feature = "cdn")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cdn")))]
+pub mod cdn;
+
+#[cfg(feature = "color")]
+#[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+pub mod color;
+
 #[cfg(feature = "link")]
 #[cfg_attr(docsrs, doc(cfg(feature = "link")))]
 pub mod link;
 
+#[cfg(feature = "permission-calculator")]
+#[cfg_attr(docsrs, doc(cfg(feature = "permission-calculator")))]
+pub mod permission_calculator;
+
 #[cfg(feature = "snowflake")]
 #[cfg_attr(docsrs, doc(cfg(feature = "snowflake")))]
 pub mod snowflake;
@@ -0,0 +1,98 @@
+//! Utilities for formatting links to channels and messages.
+//!
+//! Opening a link in the client jumps to the channel or message, which is
+//! useful for linking back to the context of an action, such as in a log.
+
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+/// Format a link to a channel.
+///
+/// Channels that aren't in a guild, such as private channels, don't have a
+/// guild ID and are linked to through `@me`.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_model::id::{ChannelId, GuildId};
+/// use twilight_util::link::channel;
+///
+/// assert_eq!(
+///     "https://discord.com/channels/1/2",
+///     channel::channel(Some(GuildId(1)), ChannelId(2)),
+/// );
+/// assert_eq!(
+///     "https://discord.com/channels/@me/2",
+///     channel::channel(None, ChannelId(2)),
+/// );
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[must_use]
+pub fn channel(guild_id: Option<GuildId>, channel_id: ChannelId) -> String {
+    if let Some(guild_id) = guild_id {
+        format!("https://discord.com/channels/{}/{}", guild_id, channel_id)
+    } else {
+        format!("https://discord.com/channels/@me/{}", channel_id)
+    }
+}
+
+/// Format a link that jumps to a message.
+///
+/// Messages that aren't in a guild, such as those in private channels, don't
+/// have a guild ID and are linked to through `@me`.
+///
+/// # Examples
+///
+/// Link to a message that was deleted by a moderator in a log:
+///
+/// ```
+/// use twilight_model::id::{ChannelId, GuildId, MessageId};
+/// use twilight_util::link::channel;
+///
+/// let link = channel::message(Some(GuildId(1)), ChannelId(2), MessageId(3));
+///
+/// assert_eq!("https://discord.com/channels/1/2/3", link);
+/// ```
+#[must_use]
+pub fn message(guild_id: Option<GuildId>, channel_id: ChannelId, message_id: MessageId) -> String {
+    format!("{}/{}", channel(guild_id, channel_id), message_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+    #[test]
+    fn test_channel() {
+        assert_eq!(
+            "https://discord.com/channels/745809834183753828/745811002771374151",
+            super::channel(
+                Some(GuildId(745_809_834_183_753_828)),
+                ChannelId(745_811_002_771_374_151)
+            ),
+        );
+        assert_eq!(
+            "https://discord.com/channels/@me/745811002771374151",
+            super::channel(None, ChannelId(745_811_002_771_374_151)),
+        );
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            "https://discord.com/channels/745809834183753828/745811002771374151/794590023369752587",
+            super::message(
+                Some(GuildId(745_809_834_183_753_828)),
+                ChannelId(745_811_002_771_374_151),
+                MessageId(794_590_023_369_752_587),
+            ),
+        );
+        assert_eq!(
+            "https://discord.com/channels/@me/745811002771374151/794590023369752587",
+            super::message(
+                None,
+                ChannelId(745_811_002_771_374_151),
+                MessageId(794_590_023_369_752_587),
+            ),
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Utilities for formatting links to invites.
+
+/// Format a link to an invite from its code.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_util::link::invite;
+///
+/// assert_eq!("https://discord.gg/7jj8n7D", invite::invite("7jj8n7D"));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[must_use]
+pub fn invite(code: &str) -> String {
+    format!("https://discord.gg/{}", code)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_invite() {
+        assert_eq!("https://discord.gg/7jj8n7D", super::invite("7jj8n7D"));
+        assert_eq!("https://discord.gg/twilight", super::invite("twilight"));
+    }
+}
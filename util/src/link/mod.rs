@@ -1,3 +1,7 @@
 //! Utilities for parsing and formatting links to various resources.
 
+pub mod channel;
+pub mod invite;
+pub mod oauth;
+pub mod user;
 pub mod webhook;
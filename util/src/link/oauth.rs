@@ -0,0 +1,75 @@
+//! Utilities for formatting links that authorize applications, such as those
+//! adding a bot to a guild.
+
+use twilight_model::{guild::Permissions, id::ApplicationId};
+
+/// Format a link to authorize an application, such as to add its bot to a
+/// guild.
+///
+/// Scopes are separated by spaces, such as `bot` and
+/// `applications.commands`. The permissions are those that the bot is
+/// requested to be granted through a role upon joining a guild, and are
+/// encoded as the decimal string of their bits.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_model::{guild::Permissions, id::ApplicationId};
+/// use twilight_util::link::oauth;
+///
+/// let link = oauth::authorize(
+///     ApplicationId(1),
+///     &["bot"],
+///     Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS,
+/// );
+///
+/// assert_eq!(
+///     "https://discord.com/oauth2/authorize?client_id=1&scope=bot&permissions=6",
+///     link,
+/// );
+/// ```
+#[must_use]
+pub fn authorize(
+    application_id: ApplicationId,
+    scopes: &[&str],
+    permissions: Permissions,
+) -> String {
+    format!(
+        "https://discord.com/oauth2/authorize?client_id={}&scope={}&permissions={}",
+        application_id,
+        scopes.join("%20"),
+        permissions.bits(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{guild::Permissions, id::ApplicationId};
+
+    #[test]
+    fn test_authorize() {
+        assert_eq!(
+            "https://discord.com/oauth2/authorize?client_id=1&scope=bot&permissions=0",
+            super::authorize(ApplicationId(1), &["bot"], Permissions::empty()),
+        );
+        assert_eq!(
+            "https://discord.com/oauth2/authorize?client_id=1&scope=bot%20applications.commands&permissions=8",
+            super::authorize(
+                ApplicationId(1),
+                &["bot", "applications.commands"],
+                Permissions::ADMINISTRATOR,
+            ),
+        );
+        assert_eq!(
+            "https://discord.com/oauth2/authorize?client_id=1&scope=bot&permissions=268443654",
+            super::authorize(
+                ApplicationId(1),
+                &["bot"],
+                Permissions::BAN_MEMBERS
+                    | Permissions::KICK_MEMBERS
+                    | Permissions::MANAGE_MESSAGES
+                    | Permissions::MANAGE_ROLES,
+            ),
+        );
+    }
+}
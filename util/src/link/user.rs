@@ -0,0 +1,31 @@
+//! Utilities for formatting links to users.
+
+use twilight_model::id::UserId;
+
+/// Format a link to the profile of a user.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_model::id::UserId;
+/// use twilight_util::link::user;
+///
+/// assert_eq!("https://discord.com/users/1", user::profile(UserId(1)));
+/// ```
+#[must_use]
+pub fn profile(user_id: UserId) -> String {
+    format!("https://discord.com/users/{}", user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::UserId;
+
+    #[test]
+    fn test_profile() {
+        assert_eq!(
+            "https://discord.com/users/114941315417899012",
+            super::profile(UserId(114_941_315_417_899_012)),
+        );
+    }
+}
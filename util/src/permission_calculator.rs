@@ -0,0 +1,703 @@
+//! Calculate the permissions of members from plain data, such as data
+//! fetched over REST or from a cache.
+//!
+//! Permissions are calculated following [Discord's documented algorithm]:
+//!
+//! 1. The owner of a guild has all permissions.
+//! 2. The permissions of the `@everyone` role are the base permissions.
+//! 3. The permissions of the member's roles are added.
+//! 4. Members with the [`ADMINISTRATOR`] permission have all permissions.
+//! 5. In a channel, the channel's permission overwrites are applied: first
+//!    the `@everyone` role's, then those of the member's roles together, and
+//!    then the member's own.
+//! 6. In a channel, permissions are removed if they can't be used:
+//!     - all permissions if [`VIEW_CHANNEL`] isn't granted;
+//!     - permissions depending on [`SEND_MESSAGES`], such as
+//!       [`ATTACH_FILES`], if it isn't granted in a text channel;
+//!     - permissions depending on [`CONNECT`], such as [`SPEAK`], if it
+//!       isn't granted in a voice channel;
+//!     - voice permissions in text channels, and text permissions in voice
+//!       channels.
+//! 7. Members who are timed out only keep the [`VIEW_CHANNEL`] and
+//!    [`READ_MESSAGE_HISTORY`] permissions, unless they're the owner or an
+//!    administrator.
+//!
+//! [Discord's documented algorithm]: https://discord.com/developers/docs/topics/permissions#permission-overwrites
+//! [`ADMINISTRATOR`]: Permissions::ADMINISTRATOR
+//! [`ATTACH_FILES`]: Permissions::ATTACH_FILES
+//! [`CONNECT`]: Permissions::CONNECT
+//! [`READ_MESSAGE_HISTORY`]: Permissions::READ_MESSAGE_HISTORY
+//! [`SEND_MESSAGES`]: Permissions::SEND_MESSAGES
+//! [`SPEAK`]: Permissions::SPEAK
+//! [`VIEW_CHANNEL`]: Permissions::VIEW_CHANNEL
+
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Permissions that are removed in a text channel when [`SEND_MESSAGES`]
+/// isn't granted.
+///
+/// [`SEND_MESSAGES`]: Permissions::SEND_MESSAGES
+const MESSAGE_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::ATTACH_FILES.bits()
+        | Permissions::EMBED_LINKS.bits()
+        | Permissions::MENTION_EVERYONE.bits()
+        | Permissions::SEND_TTS_MESSAGES.bits(),
+);
+
+/// Permissions that only apply to text channels.
+const TEXT: Permissions = Permissions::from_bits_truncate(
+    MESSAGE_DEPENDENT.bits()
+        | Permissions::ADD_REACTIONS.bits()
+        | Permissions::MANAGE_MESSAGES.bits()
+        | Permissions::READ_MESSAGE_HISTORY.bits()
+        | Permissions::SEND_MESSAGES.bits()
+        | Permissions::USE_EXTERNAL_EMOJIS.bits(),
+);
+
+/// Permissions that are kept by members who are timed out.
+const TIMED_OUT: Permissions = Permissions::from_bits_truncate(
+    Permissions::READ_MESSAGE_HISTORY.bits() | Permissions::VIEW_CHANNEL.bits(),
+);
+
+/// Permissions that are removed in a voice channel when [`CONNECT`] isn't
+/// granted.
+///
+/// [`CONNECT`]: Permissions::CONNECT
+const VOICE_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::DEAFEN_MEMBERS.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::MUTE_MEMBERS.bits()
+        | Permissions::PRIORITY_SPEAKER.bits()
+        | Permissions::SPEAK.bits()
+        | Permissions::STREAM.bits()
+        | Permissions::USE_VAD.bits(),
+);
+
+/// Permissions that only apply to voice channels.
+const VOICE: Permissions =
+    Permissions::from_bits_truncate(VOICE_DEPENDENT.bits() | Permissions::CONNECT.bits());
+
+/// Calculator of the permissions of a member in a guild and in its channels.
+///
+/// The calculator only uses the data it's given, so it can be used with data
+/// fetched over REST as well as with cached data. Refer to the [module]
+/// documentation for the algorithm used.
+///
+/// # Examples
+///
+/// Check whether a moderator can kick members, and can send messages in a
+/// channel:
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_util::permission_calculator::PermissionCalculator;
+///
+/// let guild_id = GuildId(1);
+/// let everyone = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+/// let roles = [(RoleId(2), Permissions::KICK_MEMBERS)];
+///
+/// let calculator = PermissionCalculator::new(guild_id, UserId(3), everyone, &roles)
+///     .owner_id(UserId(4));
+/// assert!(calculator.root().contains(Permissions::KICK_MEMBERS));
+///
+/// // The @everyone role has the same ID as the guild.
+/// let overwrites = [PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+///
+/// let permissions = calculator.in_channel(ChannelType::GuildText, &overwrites);
+/// assert!(!permissions.contains(Permissions::SEND_MESSAGES));
+/// ```
+///
+/// [module]: self
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "calculators do nothing unless permissions are calculated"]
+pub struct PermissionCalculator<'a> {
+    everyone: Permissions,
+    guild_id: GuildId,
+    member_roles: &'a [(RoleId, Permissions)],
+    owner_id: Option<UserId>,
+    timed_out: bool,
+    user_id: UserId,
+}
+
+impl<'a> PermissionCalculator<'a> {
+    /// Create a calculator of the permissions of a member of a guild.
+    ///
+    /// The permissions of the `@everyone` role are given separately from the
+    /// member's roles, which are pairs of their IDs and permissions.
+    pub const fn new(
+        guild_id: GuildId,
+        user_id: UserId,
+        everyone: Permissions,
+        member_roles: &'a [(RoleId, Permissions)],
+    ) -> Self {
+        Self {
+            everyone,
+            guild_id,
+            member_roles,
+            owner_id: None,
+            timed_out: false,
+            user_id,
+        }
+    }
+
+    /// Set the ID of the owner of the guild, who has all permissions.
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+
+        self
+    }
+
+    /// Set whether the member is timed out, in which case they only keep the
+    /// permissions to view channels and read their message history.
+    ///
+    /// Members aren't timed out by default.
+    pub fn timed_out(mut self, timed_out: bool) -> Self {
+        self.timed_out = timed_out;
+
+        self
+    }
+
+    /// Calculate the permissions of the member in a channel of a given type
+    /// with the channel's permission overwrites.
+    ///
+    /// Threads don't have overwrites of their own: the overwrites of their
+    /// parent channel apply to them.
+    #[must_use]
+    pub fn in_channel(
+        &self,
+        channel_type: ChannelType,
+        overwrites: &[PermissionOverwrite],
+    ) -> Permissions {
+        let root = self.root_unrestricted();
+
+        if root.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        let mut permissions = self.apply_overwrites(root, overwrites);
+
+        // Overwrites can't grant administrator.
+        permissions.remove(Permissions::ADMINISTRATOR);
+
+        // Members who can't view a channel can't do anything else in it.
+        if !permissions.contains(Permissions::VIEW_CHANNEL) {
+            return Permissions::empty();
+        }
+
+        match channel_type {
+            ChannelType::GuildVoice => {
+                permissions.remove(TEXT);
+
+                if !permissions.contains(Permissions::CONNECT) {
+                    permissions.remove(VOICE_DEPENDENT);
+                }
+            }
+            ChannelType::GuildCategory | ChannelType::GuildStore => {}
+            ChannelType::Group
+            | ChannelType::GuildNews
+            | ChannelType::GuildNewsThread
+            | ChannelType::GuildPrivateThread
+            | ChannelType::GuildPublicThread
+            | ChannelType::GuildText
+            | ChannelType::Private => {
+                permissions.remove(VOICE);
+
+                if !permissions.contains(Permissions::SEND_MESSAGES) {
+                    permissions.remove(MESSAGE_DEPENDENT);
+                }
+            }
+        }
+
+        self.restrict(permissions)
+    }
+
+    /// Calculate the permissions of the member in the guild, without the
+    /// overwrites of any channel.
+    #[must_use]
+    pub fn root(&self) -> Permissions {
+        let permissions = self.root_unrestricted();
+
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return permissions;
+        }
+
+        self.restrict(permissions)
+    }
+
+    /// Calculate the permissions of the member in the guild without the
+    /// restrictions of timeouts.
+    ///
+    /// The owner and administrators are given all permissions.
+    fn root_unrestricted(&self) -> Permissions {
+        if self.owner_id == Some(self.user_id) {
+            return Permissions::all();
+        }
+
+        let permissions = self
+            .member_roles
+            .iter()
+            .fold(self.everyone, |permissions, (_, role)| permissions | *role);
+
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        permissions
+    }
+
+    /// Apply the permission overwrites of a channel to the root permissions
+    /// of the member, in the order of the `@everyone` role, the member's
+    /// roles, and the member.
+    fn apply_overwrites(
+        &self,
+        mut permissions: Permissions,
+        overwrites: &[PermissionOverwrite],
+    ) -> Permissions {
+        // The @everyone role has the same ID as the guild.
+        let everyone_id = RoleId(self.guild_id.0);
+
+        let mut everyone = None;
+        let mut member = None;
+        let mut role_allow = Permissions::empty();
+        let mut role_deny = Permissions::empty();
+
+        for overwrite in overwrites {
+            match overwrite.kind {
+                PermissionOverwriteType::Role(role_id) if role_id == everyone_id => {
+                    everyone = Some(overwrite);
+                }
+                PermissionOverwriteType::Role(role_id)
+                    if self.member_roles.iter().any(|(id, _)| *id == role_id) =>
+                {
+                    role_allow |= overwrite.allow;
+                    role_deny |= overwrite.deny;
+                }
+                PermissionOverwriteType::Member(user_id) if user_id == self.user_id => {
+                    member = Some(overwrite);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(overwrite) = everyone {
+            permissions.remove(overwrite.deny);
+            permissions.insert(overwrite.allow);
+        }
+
+        // A role allowing a permission wins over another role denying it.
+        permissions.remove(role_deny);
+        permissions.insert(role_allow);
+
+        if let Some(overwrite) = member {
+            permissions.remove(overwrite.deny);
+            permissions.insert(overwrite.allow);
+        }
+
+        permissions
+    }
+
+    /// Remove the permissions that members who are timed out don't have.
+    fn restrict(&self, permissions: Permissions) -> Permissions {
+        if self.timed_out {
+            permissions & TIMED_OUT
+        } else {
+            permissions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PermissionCalculator, MESSAGE_DEPENDENT, TEXT, VOICE, VOICE_DEPENDENT};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(PermissionCalculator<'_>: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    const GUILD_ID: GuildId = GuildId(1);
+    const EVERYONE_ID: RoleId = RoleId(1);
+    const OWNER_ID: UserId = UserId(2);
+    const MEMBER_ID: UserId = UserId(3);
+    const MODERATOR_ID: RoleId = RoleId(4);
+    const MUTED_ID: RoleId = RoleId(5);
+    const OTHER_ID: RoleId = RoleId(6);
+
+    const ROLES: [(RoleId, Permissions); 2] = [
+        (
+            MODERATOR_ID,
+            Permissions::from_bits_truncate(
+                Permissions::KICK_MEMBERS.bits() | Permissions::MANAGE_MESSAGES.bits(),
+            ),
+        ),
+        (MUTED_ID, Permissions::empty()),
+    ];
+
+    fn everyone() -> Permissions {
+        Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::READ_MESSAGE_HISTORY
+            | Permissions::ATTACH_FILES
+            | Permissions::EMBED_LINKS
+            | Permissions::CONNECT
+            | Permissions::SPEAK
+    }
+
+    fn calculator(roles: &[(RoleId, Permissions)]) -> PermissionCalculator<'_> {
+        PermissionCalculator::new(GUILD_ID, MEMBER_ID, everyone(), roles).owner_id(OWNER_ID)
+    }
+
+    fn role(role_id: RoleId, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite {
+            allow,
+            deny,
+            kind: PermissionOverwriteType::Role(role_id),
+        }
+    }
+
+    fn member(user_id: UserId, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite {
+            allow,
+            deny,
+            kind: PermissionOverwriteType::Member(user_id),
+        }
+    }
+
+    #[test]
+    fn test_root() {
+        let cases: &[(&[(RoleId, Permissions)], Permissions)] = &[
+            (&[], everyone()),
+            (
+                &ROLES,
+                everyone() | Permissions::KICK_MEMBERS | Permissions::MANAGE_MESSAGES,
+            ),
+            (&[(MUTED_ID, Permissions::empty())], everyone()),
+            (
+                &[(OTHER_ID, Permissions::ADMINISTRATOR)],
+                Permissions::all(),
+            ),
+        ];
+
+        for (roles, expected) in cases {
+            assert_eq!(*expected, calculator(roles).root(), "{:?}", roles);
+        }
+    }
+
+    #[test]
+    fn test_owner() {
+        let calculator = PermissionCalculator::new(GUILD_ID, OWNER_ID, Permissions::empty(), &[])
+            .owner_id(OWNER_ID)
+            .timed_out(true);
+        let overwrites = [
+            role(EVERYONE_ID, Permissions::empty(), Permissions::all()),
+            member(OWNER_ID, Permissions::empty(), Permissions::all()),
+        ];
+
+        assert_eq!(Permissions::all(), calculator.root());
+        assert_eq!(
+            Permissions::all(),
+            calculator.in_channel(ChannelType::GuildText, &overwrites)
+        );
+
+        // Members aren't owners without an owner ID.
+        let calculator = PermissionCalculator::new(GUILD_ID, OWNER_ID, Permissions::empty(), &[]);
+        assert_eq!(Permissions::empty(), calculator.root());
+    }
+
+    #[test]
+    fn test_administrator() {
+        let roles = [(OTHER_ID, Permissions::ADMINISTRATOR)];
+        let calculator = calculator(&roles).timed_out(true);
+        let overwrites = [member(MEMBER_ID, Permissions::empty(), Permissions::all())];
+
+        assert_eq!(Permissions::all(), calculator.root());
+
+        for kind in &[ChannelType::GuildText, ChannelType::GuildVoice] {
+            assert_eq!(
+                Permissions::all(),
+                calculator.in_channel(*kind, &overwrites)
+            );
+        }
+    }
+
+    #[test]
+    fn test_overwrites() {
+        let cases: &[(&[PermissionOverwrite], Permissions)] = &[
+            (&[], everyone() | Permissions::MANAGE_MESSAGES),
+            // The member's overwrite is applied last, so it wins over the
+            // role, which in turn wins over @everyone's. The order of the
+            // overwrites doesn't matter.
+            (
+                &[
+                    member(
+                        MEMBER_ID,
+                        Permissions::EMBED_LINKS,
+                        Permissions::ATTACH_FILES,
+                    ),
+                    role(
+                        MODERATOR_ID,
+                        Permissions::ADD_REACTIONS,
+                        Permissions::EMBED_LINKS,
+                    ),
+                    role(
+                        EVERYONE_ID,
+                        Permissions::MENTION_EVERYONE,
+                        Permissions::ADD_REACTIONS | Permissions::READ_MESSAGE_HISTORY,
+                    ),
+                ],
+                Permissions::VIEW_CHANNEL
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::EMBED_LINKS
+                    | Permissions::MANAGE_MESSAGES
+                    | Permissions::ADD_REACTIONS
+                    | Permissions::MENTION_EVERYONE,
+            ),
+            (
+                &[
+                    role(
+                        EVERYONE_ID,
+                        Permissions::MENTION_EVERYONE,
+                        Permissions::ADD_REACTIONS | Permissions::READ_MESSAGE_HISTORY,
+                    ),
+                    role(
+                        MODERATOR_ID,
+                        Permissions::ADD_REACTIONS,
+                        Permissions::EMBED_LINKS,
+                    ),
+                    member(
+                        MEMBER_ID,
+                        Permissions::EMBED_LINKS,
+                        Permissions::ATTACH_FILES,
+                    ),
+                ],
+                Permissions::VIEW_CHANNEL
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::EMBED_LINKS
+                    | Permissions::MANAGE_MESSAGES
+                    | Permissions::ADD_REACTIONS
+                    | Permissions::MENTION_EVERYONE,
+            ),
+            // A role allowing a permission wins over another denying it.
+            (
+                &[
+                    role(MUTED_ID, Permissions::empty(), Permissions::ATTACH_FILES),
+                    role(
+                        MODERATOR_ID,
+                        Permissions::ATTACH_FILES,
+                        Permissions::empty(),
+                    ),
+                ],
+                everyone() | Permissions::MANAGE_MESSAGES,
+            ),
+            // Overwrites of other roles and members are ignored.
+            (
+                &[
+                    role(OTHER_ID, Permissions::empty(), Permissions::all()),
+                    member(OWNER_ID, Permissions::empty(), Permissions::all()),
+                ],
+                everyone() | Permissions::MANAGE_MESSAGES,
+            ),
+            // Overwrites can't grant administrator.
+            (
+                &[member(
+                    MEMBER_ID,
+                    Permissions::ADMINISTRATOR,
+                    Permissions::empty(),
+                )],
+                everyone() | Permissions::MANAGE_MESSAGES,
+            ),
+        ];
+
+        let calculator = calculator(&ROLES);
+
+        for (overwrites, expected) in cases {
+            // The moderator role's permission to kick members is kept, and
+            // voice permissions are removed in text channels.
+            let expected = (*expected | Permissions::KICK_MEMBERS) - VOICE;
+            let actual = calculator.in_channel(ChannelType::GuildText, overwrites);
+
+            assert_eq!(expected, actual, "{:?}", overwrites);
+        }
+    }
+
+    #[test]
+    fn test_view_channel() {
+        let calculator = calculator(&ROLES);
+
+        for overwrites in &[
+            vec![role(
+                EVERYONE_ID,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            )],
+            vec![member(
+                MEMBER_ID,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            )],
+        ] {
+            for kind in &[
+                ChannelType::GuildCategory,
+                ChannelType::GuildText,
+                ChannelType::GuildVoice,
+            ] {
+                assert_eq!(
+                    Permissions::empty(),
+                    calculator.in_channel(*kind, overwrites)
+                );
+            }
+        }
+
+        // A role allowing the permission wins over the @everyone role.
+        let overwrites = [
+            role(EVERYONE_ID, Permissions::empty(), Permissions::VIEW_CHANNEL),
+            role(
+                MODERATOR_ID,
+                Permissions::VIEW_CHANNEL,
+                Permissions::empty(),
+            ),
+        ];
+        assert!(calculator
+            .in_channel(ChannelType::GuildText, &overwrites)
+            .contains(Permissions::VIEW_CHANNEL));
+    }
+
+    #[test]
+    fn test_send_messages_implies() {
+        let roles = [(
+            OTHER_ID,
+            Permissions::MENTION_EVERYONE | Permissions::SEND_TTS_MESSAGES,
+        )];
+        let calculator = calculator(&roles);
+        let overwrites = [role(
+            OTHER_ID,
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        )];
+
+        for kind in &[
+            ChannelType::GuildNews,
+            ChannelType::GuildNewsThread,
+            ChannelType::GuildPrivateThread,
+            ChannelType::GuildPublicThread,
+            ChannelType::GuildText,
+        ] {
+            let permissions = calculator.in_channel(*kind, &overwrites);
+
+            assert!(!permissions.intersects(MESSAGE_DEPENDENT), "{:?}", kind);
+            assert!(!permissions.contains(Permissions::SEND_MESSAGES));
+            assert!(permissions.contains(Permissions::READ_MESSAGE_HISTORY));
+        }
+
+        // Categories don't have messages, so nothing is removed.
+        let permissions = calculator.in_channel(ChannelType::GuildCategory, &overwrites);
+        assert!(permissions.contains(Permissions::EMBED_LINKS | Permissions::MENTION_EVERYONE));
+    }
+
+    #[test]
+    fn test_connect_implies() {
+        let roles = [(OTHER_ID, Permissions::STREAM | Permissions::MUTE_MEMBERS)];
+        let calculator = calculator(&roles);
+        let overwrites = [member(
+            MEMBER_ID,
+            Permissions::empty(),
+            Permissions::CONNECT,
+        )];
+
+        let permissions = calculator.in_channel(ChannelType::GuildVoice, &overwrites);
+        assert!(!permissions.intersects(VOICE));
+        assert!(permissions.contains(Permissions::VIEW_CHANNEL));
+
+        let permissions = calculator.in_channel(ChannelType::GuildVoice, &[]);
+        assert!(permissions.contains(VOICE_DEPENDENT & (everyone() | roles[0].1)));
+    }
+
+    #[test]
+    fn test_channel_kind() {
+        let calculator = calculator(&ROLES);
+        let root = calculator.root();
+
+        let cases = &[
+            (ChannelType::GuildText, root - VOICE),
+            (ChannelType::GuildNews, root - VOICE),
+            (ChannelType::GuildPublicThread, root - VOICE),
+            (ChannelType::GuildVoice, root - TEXT),
+            (ChannelType::GuildCategory, root),
+            (ChannelType::GuildStore, root),
+        ];
+
+        for (kind, expected) in cases {
+            assert_eq!(*expected, calculator.in_channel(*kind, &[]), "{:?}", kind);
+        }
+
+        // Guild-level permissions are kept in channels.
+        assert!(calculator
+            .in_channel(ChannelType::GuildVoice, &[])
+            .contains(Permissions::KICK_MEMBERS));
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let calculator = calculator(&ROLES).timed_out(true);
+
+        assert_eq!(
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+            calculator.root()
+        );
+        assert_eq!(
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+            calculator.in_channel(ChannelType::GuildText, &[])
+        );
+
+        // Overwrites can't grant permissions to members that are timed out.
+        let overwrites = [member(
+            MEMBER_ID,
+            Permissions::SEND_MESSAGES,
+            Permissions::empty(),
+        )];
+        assert_eq!(
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+            calculator.in_channel(ChannelType::GuildText, &overwrites)
+        );
+
+        // The restrictions of timeouts don't add permissions that were denied.
+        let overwrites = [role(
+            EVERYONE_ID,
+            Permissions::empty(),
+            Permissions::READ_MESSAGE_HISTORY,
+        )];
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            calculator.in_channel(ChannelType::GuildText, &overwrites)
+        );
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            calculator.in_channel(ChannelType::GuildVoice, &[])
+        );
+    }
+}
@@ -1,8 +1,9 @@
 //! Provides the Snowflake trait for defining extractable information from a Discord Snowflake.
 
+use std::ops::Range;
 use twilight_model::id::{
-    AttachmentId, AuditLogEntryId, ChannelId, EmojiId, GenericId, GuildId, IntegrationId,
-    MessageId, RoleId, UserId, WebhookId,
+    ApplicationId, AttachmentId, AuditLogEntryId, ChannelId, EmojiId, GenericId, GuildId,
+    IntegrationId, MessageId, RoleId, UserId, WebhookId,
 };
 
 /// Snowflake is a trait for defining extractable information from a Snowflake. A Snowflake is a
@@ -48,9 +49,6 @@ pub trait Snowflake {
     /// ```
     #[allow(clippy::cast_possible_wrap)]
     fn timestamp(&self) -> i64 {
-        // Discord's custom epoch, the unix time in milliseconds for the first second of 2015.
-        const DISCORD_EPOCH: u64 = 1_420_070_400_000;
-
         ((self.id() >> 22) + DISCORD_EPOCH) as i64
     }
 
@@ -80,81 +78,196 @@ pub trait Snowflake {
     }
 }
 
-impl Snowflake for AttachmentId {
-    fn id(&self) -> u64 {
-        self.0
-    }
-}
+/// Discord's custom epoch, the unix time in milliseconds for the first second of 2015.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
 
-impl Snowflake for AuditLogEntryId {
-    fn id(&self) -> u64 {
-        self.0
+/// Create a synthetic Snowflake from a Unix timestamp in milliseconds.
+///
+/// The worker, process, and increment of the Snowflake are 0, so it's the
+/// smallest Snowflake generated at the timestamp. This makes it useful as a
+/// boundary, such as to get the messages sent after a time, as well as for
+/// test fixtures.
+///
+/// Returns `None` if the timestamp is before Discord's epoch, the start of
+/// 2015, or too far in the future to be represented.
+///
+/// # Examples
+///
+/// Create the ID of a message sent at the start of 2021, to get the messages
+/// sent after it:
+///
+/// ```rust
+/// use twilight_model::id::MessageId;
+/// use twilight_util::snowflake::{self, Snowflake};
+///
+/// let timestamp = 1_609_459_200_000;
+/// let id = MessageId(snowflake::from_timestamp(timestamp).expect("after 2015"));
+///
+/// assert_eq!(timestamp, id.timestamp());
+/// assert_eq!(0, id.increment());
+///
+/// assert!(snowflake::from_timestamp(0).is_none());
+/// ```
+#[allow(clippy::cast_sign_loss)]
+#[must_use]
+pub fn from_timestamp(timestamp: i64) -> Option<u64> {
+    if timestamp < 0 {
+        return None;
     }
-}
 
-impl Snowflake for ChannelId {
-    fn id(&self) -> u64 {
-        self.0
-    }
-}
+    let since_epoch = (timestamp as u64).checked_sub(DISCORD_EPOCH)?;
 
-impl Snowflake for EmojiId {
-    fn id(&self) -> u64 {
-        self.0
+    // The timestamp is stored in the top 42 bits.
+    if since_epoch >> 42 != 0 {
+        return None;
     }
-}
 
-impl Snowflake for GenericId {
-    fn id(&self) -> u64 {
-        self.0
-    }
+    Some(since_epoch << 22)
 }
 
-impl Snowflake for GuildId {
-    fn id(&self) -> u64 {
-        self.0
-    }
+/// Create the range of Snowflakes generated from one Unix timestamp in
+/// milliseconds up to, but not including, another.
+///
+/// Returns `None` if either timestamp can't be [converted] to a Snowflake.
+///
+/// # Examples
+///
+/// Check whether a message was sent on the first day of 2021:
+///
+/// ```rust
+/// use twilight_model::id::MessageId;
+/// use twilight_util::snowflake;
+///
+/// let start = 1_609_459_200_000;
+/// let day = snowflake::range(start, start + 24 * 60 * 60 * 1000).expect("after 2015");
+///
+/// let id = MessageId(794_398_835_374_489_600);
+/// assert!(day.contains(&id.0));
+/// ```
+///
+/// [converted]: from_timestamp
+#[must_use]
+pub fn range(start: i64, end: i64) -> Option<Range<u64>> {
+    Some(from_timestamp(start)?..from_timestamp(end)?)
 }
 
-impl Snowflake for IntegrationId {
-    fn id(&self) -> u64 {
-        self.0
-    }
+/// Deterministic generator of synthetic Snowflakes, such as for fixtures.
+///
+/// Snowflakes are generated in increasing order, starting at a timestamp with
+/// an increment of 0 or a configured increment. Once the increment runs out,
+/// the timestamp moves forward by one millisecond. The worker and process of
+/// each Snowflake are 0.
+///
+/// # Examples
+///
+/// Create the IDs of three messages sent after the start of 2021:
+///
+/// ```rust
+/// use twilight_model::id::MessageId;
+/// use twilight_util::snowflake::{Generator, Snowflake};
+///
+/// let generator = Generator::new(1_609_459_200_000).expect("after 2015");
+/// let ids: Vec<MessageId> = generator.take(3).map(MessageId).collect();
+///
+/// assert!(ids.windows(2).all(|ids| ids[0].0 < ids[1].0));
+/// assert_eq!(2, ids[2].increment());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "generators are lazy and do nothing unless iterated"]
+pub struct Generator {
+    next: Option<u64>,
 }
 
-impl Snowflake for MessageId {
-    fn id(&self) -> u64 {
-        self.0
+impl Generator {
+    /// Maximum increment of a Snowflake.
+    const INCREMENT_MAX: u64 = 0xFFF;
+
+    /// Create a generator starting at a Unix timestamp in milliseconds.
+    ///
+    /// Returns `None` if the timestamp can't be [converted] to a Snowflake.
+    ///
+    /// [converted]: from_timestamp
+    pub fn new(timestamp: i64) -> Option<Self> {
+        Some(Self {
+            next: Some(from_timestamp(timestamp)?),
+        })
     }
-}
 
-impl Snowflake for RoleId {
-    fn id(&self) -> u64 {
-        self.0
+    /// Set the increment of the next Snowflake.
+    ///
+    /// Only the lower 12 bits of the increment are used.
+    pub fn increment(mut self, increment: u16) -> Self {
+        if let Some(next) = self.next.as_mut() {
+            *next = (*next & !Self::INCREMENT_MAX) | (u64::from(increment) & Self::INCREMENT_MAX);
+        }
+
+        self
     }
 }
 
-impl Snowflake for UserId {
-    fn id(&self) -> u64 {
-        self.0
+impl Iterator for Generator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+
+        self.next = if id & Self::INCREMENT_MAX == Self::INCREMENT_MAX {
+            // Move to the start of the next millisecond, unless the
+            // timestamp can't go any further.
+            let timestamp = (id >> 22) + 1;
+
+            if timestamp >> 42 == 0 {
+                Some(timestamp << 22)
+            } else {
+                None
+            }
+        } else {
+            Some(id + 1)
+        };
+
+        Some(id)
     }
 }
 
-impl Snowflake for WebhookId {
-    fn id(&self) -> u64 {
-        self.0
-    }
+/// Implement [`Snowflake`] for ID newtypes wrapping a `u64`.
+macro_rules! impl_snowflake {
+    ($($id:ident),* $(,)?) => {
+        $(
+            impl Snowflake for $id {
+                fn id(&self) -> u64 {
+                    self.0
+                }
+            }
+        )*
+    };
 }
 
+impl_snowflake!(
+    ApplicationId,
+    AttachmentId,
+    AuditLogEntryId,
+    ChannelId,
+    EmojiId,
+    GenericId,
+    GuildId,
+    IntegrationId,
+    MessageId,
+    RoleId,
+    UserId,
+    WebhookId,
+);
+
 #[cfg(test)]
 mod tests {
-    use super::Snowflake;
+    use super::{Generator, Snowflake};
     use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
     use twilight_model::id::{
-        AttachmentId, AuditLogEntryId, ChannelId, EmojiId, GenericId, GuildId, IntegrationId,
-        MessageId, RoleId, UserId, WebhookId,
+        ApplicationId, AttachmentId, AuditLogEntryId, ChannelId, EmojiId, GenericId, GuildId,
+        IntegrationId, MessageId, RoleId, UserId, WebhookId,
     };
 
+    assert_impl_all!(ApplicationId: Snowflake);
     assert_impl_all!(AttachmentId: Snowflake);
     assert_impl_all!(AuditLogEntryId: Snowflake);
     assert_impl_all!(ChannelId: Snowflake);
@@ -166,6 +279,7 @@ mod tests {
     assert_impl_all!(RoleId: Snowflake);
     assert_impl_all!(UserId: Snowflake);
     assert_impl_all!(WebhookId: Snowflake);
+    assert_impl_all!(Generator: Clone, Debug, Eq, Iterator, PartialEq, Send, Sync);
     assert_obj_safe!(Snowflake);
 
     #[test]
@@ -199,4 +313,125 @@ mod tests {
 
         assert_eq!(expected, id.increment())
     }
+
+    #[test]
+    fn test_documented_example() {
+        // The example of Discord's documentation on snowflakes.
+        let id = GenericId(175_928_847_299_117_063);
+
+        assert_eq!(1_462_015_105_796, id.timestamp());
+        assert_eq!(1, id.worker_id());
+        assert_eq!(0, id.process_id());
+        assert_eq!(7, id.increment());
+    }
+
+    #[test]
+    fn test_from_timestamp() {
+        let id = GenericId(175_928_847_299_117_063);
+        let boundary = GenericId(super::from_timestamp(id.timestamp()).unwrap());
+
+        assert_eq!(id.timestamp(), boundary.timestamp());
+        assert_eq!(0, boundary.worker_id());
+        assert_eq!(0, boundary.process_id());
+        assert_eq!(0, boundary.increment());
+
+        // The synthetic snowflake is the smallest at its timestamp.
+        assert!(boundary.0 <= id.0);
+        assert!(super::from_timestamp(id.timestamp() + 1).unwrap() > id.0);
+    }
+
+    #[test]
+    fn test_from_timestamp_round_trip() {
+        for timestamp in &[
+            1_420_070_400_000,
+            1_445_219_918_546,
+            1_609_459_200_000,
+            1_420_070_400_000 + (1 << 42) - 1,
+        ] {
+            let id = MessageId(super::from_timestamp(*timestamp).unwrap());
+
+            assert_eq!(*timestamp, id.timestamp());
+        }
+
+        assert_eq!(Some(0), super::from_timestamp(1_420_070_400_000));
+        assert_eq!(None, super::from_timestamp(1_420_070_399_999));
+        assert_eq!(None, super::from_timestamp(-1));
+        assert_eq!(None, super::from_timestamp(1_420_070_400_000 + (1 << 42)));
+        assert_eq!(None, super::from_timestamp(i64::MAX));
+    }
+
+    #[test]
+    fn test_range() {
+        let id = GenericId(175_928_847_299_117_063);
+        let range = super::range(id.timestamp(), id.timestamp() + 1).unwrap();
+
+        assert!(range.contains(&id.0));
+        assert!(range.contains(&super::from_timestamp(id.timestamp()).unwrap()));
+        assert!(!range.contains(&super::from_timestamp(id.timestamp() + 1).unwrap()));
+        assert!(!range.contains(&(range.start - 1)));
+        assert_eq!(1 << 22, range.end - range.start);
+
+        assert!(super::range(0, id.timestamp()).is_none());
+        assert!(super::range(id.timestamp(), i64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_generator() {
+        let timestamp = 1_609_459_200_000;
+        let ids: Vec<_> = Generator::new(timestamp)
+            .unwrap()
+            .take(5000)
+            .map(GenericId)
+            .collect();
+
+        assert_eq!(super::from_timestamp(timestamp), Some(ids[0].0));
+        assert!(ids.windows(2).all(|ids| ids[0].0 < ids[1].0));
+        assert!(ids
+            .iter()
+            .all(|id| id.worker_id() == 0 && id.process_id() == 0));
+
+        // The timestamp moves forward once the increment runs out.
+        assert_eq!(timestamp, ids[4095].timestamp());
+        assert_eq!(4095, ids[4095].increment());
+        assert_eq!(timestamp + 1, ids[4096].timestamp());
+        assert_eq!(0, ids[4096].increment());
+
+        // Generators are deterministic.
+        assert!(Generator::new(timestamp)
+            .unwrap()
+            .take(5000)
+            .eq(ids.iter().map(|id| id.0)));
+    }
+
+    #[test]
+    fn test_generator_increment() {
+        let mut generator = Generator::new(1_609_459_200_000).unwrap().increment(4095);
+        let first = GenericId(generator.next().unwrap());
+        let second = GenericId(generator.next().unwrap());
+
+        assert_eq!(4095, first.increment());
+        assert_eq!(0, second.increment());
+        assert_eq!(first.timestamp() + 1, second.timestamp());
+
+        let id = Generator::new(1_609_459_200_000)
+            .unwrap()
+            .increment(u16::MAX)
+            .next()
+            .unwrap();
+        assert_eq!(4095, GenericId(id).increment());
+    }
+
+    #[test]
+    fn test_generator_end() {
+        let last = 1_420_070_400_000 + (1 << 42) - 1;
+        let ids: Vec<_> = Generator::new(last).unwrap().increment(4094).collect();
+
+        assert_eq!(
+            vec![
+                u64::MAX - (1 << 22) + 1 + 4094,
+                u64::MAX - (1 << 22) + 1 + 4095
+            ],
+            ids
+        );
+    }
 }